@@ -1206,6 +1206,19 @@ public class CallManager {
     groupCall.handlePeekChanged(info);
   }
 
+  @CalledByNative
+  private void handleJoinRejected(long clientId, int httpStatus, @Nullable String message) {
+    Log.i(TAG, "handleJoinRejected():");
+
+    GroupCall groupCall = this.groupCallByClientId.get(clientId);
+    if (groupCall == null) {
+      Log.w(TAG, "groupCall not found by clientId: " + clientId);
+      return;
+    }
+
+    groupCall.handleJoinRejected(httpStatus, message);
+  }
+
   @CalledByNative
   private void handleEnded(long clientId, GroupCall.GroupCallEndReason reason) {
     Log.i(TAG, "handleEnded():");
@@ -1389,7 +1402,25 @@ public class CallManager {
     RECEIVED_OFFER_WITH_GLARE,
 
     /** Received an offer on a linked device from one that doesn't support multi-ring. */
-    IGNORE_CALLS_FROM_NON_MULTIRING_CALLERS;
+    IGNORE_CALLS_FROM_NON_MULTIRING_CALLERS,
+
+    /** The local side has put the call on hold. */
+    LOCAL_HOLD,
+
+    /** The local side has resumed a call that was on hold. */
+    LOCAL_RESUME,
+
+    /** Received an offer with no protocol version this build supports, so the app needs an update. */
+    RECEIVED_OFFER_WITH_UNSUPPORTED_VERSION,
+
+    /** The call was transferred to another user, so this leg has ended. */
+    ENDED_TRANSFERRED,
+
+    /** The call ended because the remote stepped away, such as by backgrounding the app, rather than hanging up. */
+    ENDED_REMOTE_AWAY,
+
+    /** The call ended because the connection was lost after the call was accepted, such as when the network changed, and couldn't be reconnected. */
+    ENDED_NETWORK_CHANGED;
 
     @CalledByNative
     static CallEvent fromNativeIndex(int nativeIndex) {
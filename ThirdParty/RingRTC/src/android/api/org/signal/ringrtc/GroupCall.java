@@ -56,6 +56,10 @@ public final class GroupCall {
 
     @Nullable private   PeekInfo                           peekInfo;
 
+    // Set if the SFU turned down the request to join, before handleEnded().
+              private   int                                joinRejectedHttpStatus;
+    @Nullable private   String                             joinRejectedMessage;
+
     @Nullable private   AudioSource                        outgoingAudioSource;
     @Nullable private   AudioTrack                         outgoingAudioTrack;
     @Nullable private   VideoSource                        outgoingVideoSource;
@@ -275,6 +279,30 @@ public final class GroupCall {
         return this.peekInfo;
     }
 
+    /**
+     * Returns the HTTP status with which the SFU turned down the request
+     * to join (e.g. 403 when the user isn't a member of the call), or 0
+     * if it didn't. Set before onEnded() is called.
+     */
+    public int getJoinRejectedHttpStatus()
+    {
+        Log.i(TAG, "getJoinRejectedHttpStatus():");
+
+        return this.joinRejectedHttpStatus;
+    }
+
+    /**
+     * Returns the message, if any, that came with the SFU turning down
+     * the request to join.
+     */
+    @Nullable
+    public String getJoinRejectedMessage()
+    {
+        Log.i(TAG, "getJoinRejectedMessage():");
+
+        return this.joinRejectedMessage;
+    }
+
     /**
      *
      * Mute (or unmute) outgoing audio. This adjusts the outgoing audio
@@ -556,6 +584,19 @@ public final class GroupCall {
         this.observer.onPeekChanged(this);
     }
 
+    /**
+     *
+     * Callback from RingRTC that the SFU turned down the request to join,
+     * right before the group call ends. Called via the CallManager.
+     *
+     */
+    void handleJoinRejected(int httpStatus, @Nullable String message) {
+        Log.i(TAG, "handleJoinRejected(): httpStatus: " + httpStatus);
+
+        this.joinRejectedHttpStatus = httpStatus;
+        this.joinRejectedMessage = message;
+    }
+
     /**
      *
      * Callback from RingRTC when the group call ends. Called via the
@@ -23,11 +23,12 @@ public class CallContext {
     let videoSource: RTCVideoSource
     let videoTrack: RTCVideoTrack
     let certificate: RTCCertificate
+    let enableRtpDataChannel: Bool
 
     // Cache the latest settings so we don't repeat them.
     var currentVideoEnableSetting: Bool
 
-    init (iceServers: [RTCIceServer], hideIp: Bool, audioSource: RTCAudioSource, audioTrack: RTCAudioTrack, videoSource: RTCVideoSource, videoTrack: RTCVideoTrack, videoCaptureController: VideoCaptureController, certificate: RTCCertificate) {
+    init (iceServers: [RTCIceServer], hideIp: Bool, audioSource: RTCAudioSource, audioTrack: RTCAudioTrack, videoSource: RTCVideoSource, videoTrack: RTCVideoTrack, videoCaptureController: VideoCaptureController, certificate: RTCCertificate, enableRtpDataChannel: Bool = true) {
         self.iceServers = iceServers
         self.hideIp = hideIp
         self.audioSource = audioSource
@@ -36,6 +37,7 @@ public class CallContext {
         self.videoTrack = videoTrack
         self.videoCaptureController = videoCaptureController
         self.certificate = certificate
+        self.enableRtpDataChannel = enableRtpDataChannel
 
         // For now, assume video starts out as disabled.
         currentVideoEnableSetting = false
@@ -50,7 +52,8 @@ public class CallContext {
     func getWrapper() -> AppCallContext {
         return AppCallContext(
             object: UnsafeMutableRawPointer(Unmanaged.passRetained(self).toOpaque()),
-            destroy: callContextDestroy)
+            destroy: callContextDestroy,
+            enableRtpDataChannel: enableRtpDataChannel)
     }
 
     func getCaptureSession() -> AVCaptureSession {
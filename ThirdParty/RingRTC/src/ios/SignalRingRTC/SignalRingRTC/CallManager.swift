@@ -11,6 +11,8 @@ import PromiseKit
 // Errors that the Call Manager APIs can throw.
 public enum CallManagerError: Error {
     case apiFailed(description: String)
+    /// The SFU didn't respond to a peek within its timeout.
+    case peekTimedOut
 }
 
 /// Primary events a Call UI can act upon.
@@ -65,6 +67,18 @@ public enum CallManagerEvent: Int32 {
     case receivedOfferWithGlare = 23
     /// Received an offer on a linked device from one that doesn't support multi-ring.
     case ignoreCallsFromNonMultiringCallers = 24
+    /// The local side has put the call on hold.
+    case localHold = 25
+    /// The local side has resumed a call that was on hold.
+    case localResume = 26
+    /// Received an offer with no protocol version this build supports; the user should update the app.
+    case receivedOfferWithUnsupportedVersion = 27
+    /// The call was transferred to another user, so this leg has ended.
+    case endedTransferred = 28
+    /// The remote side stepped away, such as by backgrounding the app, rather than hanging up.
+    case endedRemoteAway = 29
+    /// The connection was lost after the call was accepted, usually because the network changed, and couldn't be reconnected.
+    case endedNetworkChanged = 30
 }
 
 /// Type of media for call at time of origination.
@@ -88,6 +102,32 @@ public enum BandwidthMode: Int32 {
     case normal = 2
 }
 
+/// Which processing is applied to the outgoing audio. Turning it off suits
+/// music, or accessories that do their own processing.
+public struct AudioProcessingConfig {
+    /// Acoustic echo cancellation.
+    public let aec: Bool
+    /// Noise suppression.
+    public let ns: Bool
+    /// Automatic gain control.
+    public let agc: Bool
+
+    public init(aec: Bool = true, ns: Bool = true, agc: Bool = true) {
+        self.aec = aec
+        self.ns = ns
+        self.agc = agc
+    }
+
+    var audioConstraints: RTCMediaConstraints {
+        let mandatoryConstraints = [
+            "googEchoCancellation": aec ? kRTCMediaConstraintsValueTrue : kRTCMediaConstraintsValueFalse,
+            "googNoiseSuppression": ns ? kRTCMediaConstraintsValueTrue : kRTCMediaConstraintsValueFalse,
+            "googAutoGainControl": agc ? kRTCMediaConstraintsValueTrue : kRTCMediaConstraintsValueFalse
+        ]
+        return RTCMediaConstraints(mandatoryConstraints: mandatoryConstraints, optionalConstraints: nil)
+    }
+}
+
 /// Type of hangup message.
 public enum HangupType: Int32 {
     /// Normal hangup, typically remote user initiated.
@@ -100,18 +140,34 @@ public enum HangupType: Int32 {
     case busy = 3
     /// Call needed permission on a different device.
     case needPermission = 4
+    /// Like normal, but the user stepped away rather than hanging up.
+    case away = 5
 }
 
 /// Contains the list of currently joined participants and related info about the call in progress.
+/// A device joined to a group call, and the user it belongs to.
+public struct JoinedDevice {
+    public let userId: UUID
+    public let demuxId: UInt32
+
+    public init(userId: UUID, demuxId: UInt32) {
+        self.userId = userId
+        self.demuxId = demuxId
+    }
+}
+
 public struct PeekInfo {
     public let joinedMembers: [UUID]
+    /// Every joined device by user, so a user on several devices can be told apart.
+    public let joinedDevices: [JoinedDevice]
     public let creator: UUID?
     public let eraId: String?
     public let maxDevices: UInt32?
     public let deviceCount: UInt32
 
-    public init(joinedMembers: [UUID], creator: UUID?, eraId: String?, maxDevices: UInt32?, deviceCount: UInt32) {
+    public init(joinedMembers: [UUID], joinedDevices: [JoinedDevice] = [], creator: UUID?, eraId: String?, maxDevices: UInt32?, deviceCount: UInt32) {
         self.joinedMembers = joinedMembers
+        self.joinedDevices = joinedDevices
         self.creator = creator
         self.eraId = eraId
         self.maxDevices = maxDevices
@@ -159,6 +215,15 @@ class Requests<T> {
         self.sealById[id] = nil
         return true
     }
+
+    func reject(id: UInt32, error: Error) -> Bool {
+        guard let seal = self.sealById[id] else {
+            return false
+        }
+        seal.reject(error)
+        self.sealById[id] = nil
+        return true
+    }
 }
 
 public protocol CallManagerDelegate: class {
@@ -212,6 +277,28 @@ public protocol CallManagerDelegate: class {
      */
     func callManager(_ callManager: CallManager<CallManagerDelegateCallType, Self>, shouldSendBusy callId: UInt64, call: CallManagerDelegateCallType, destinationDeviceId: UInt32?)
 
+    /**
+     * A TransferRequest message should be sent to the given remote, asking
+     * it to end the call and call the target instead.
+     * Invoked on the main thread, asychronously.
+     * If there is any error, the UI can reset UI state and invoke the reset() API.
+     */
+    func callManager(_ callManager: CallManager<CallManagerDelegateCallType, Self>, shouldSendTransferRequest callId: UInt64, call: CallManagerDelegateCallType, destinationDeviceId: UInt32?, target: CallManagerDelegateCallType)
+
+    /**
+     * A TransferAccepted message should be sent to the given remote.
+     * Invoked on the main thread, asychronously.
+     * If there is any error, the UI can reset UI state and invoke the reset() API.
+     */
+    func callManager(_ callManager: CallManager<CallManagerDelegateCallType, Self>, shouldSendTransferAccepted callId: UInt64, call: CallManagerDelegateCallType, destinationDeviceId: UInt32?)
+
+    /**
+     * The remote asked to transfer the call to the target. To go along with
+     * it, invoke acceptTransfer() and then start a new call with the target.
+     * Invoked on the main thread, asychronously.
+     */
+    func callManager(_ callManager: CallManager<CallManagerDelegateCallType, Self>, onTransferRequest call: CallManagerDelegateCallType, target: CallManagerDelegateCallType)
+
     /**
      * A call message should be sent to the given remote recipient.
      * Invoked on the main thread, asychronously.
@@ -246,6 +333,23 @@ public protocol CallManagerDelegate: class {
      * Invoked on the main thread, asychronously.
      */
     func callManager(_ callManager: CallManager<CallManagerDelegateCallType, Self>, onAddRemoteVideoTrack call: CallManagerDelegateCallType, track: RTCVideoTrack)
+
+    /**
+     * The remote peer sent an application-defined payload with
+     * sendDataMessage().  Like the data channel it came over, payloads
+     * may be lost or arrive out of order.
+     * Invoked on the main thread, asychronously.
+     */
+    func callManager(_ callManager: CallManager<CallManagerDelegateCallType, Self>, onDataMessage call: CallManagerDelegateCallType, payload: Data)
+}
+
+/// Defaults for the call transfer and data messages, so delegates that
+/// don't use them need not implement them.
+public extension CallManagerDelegate {
+    func callManager(_ callManager: CallManager<CallManagerDelegateCallType, Self>, shouldSendTransferRequest callId: UInt64, call: CallManagerDelegateCallType, destinationDeviceId: UInt32?, target: CallManagerDelegateCallType) {}
+    func callManager(_ callManager: CallManager<CallManagerDelegateCallType, Self>, shouldSendTransferAccepted callId: UInt64, call: CallManagerDelegateCallType, destinationDeviceId: UInt32?) {}
+    func callManager(_ callManager: CallManager<CallManagerDelegateCallType, Self>, onTransferRequest call: CallManagerDelegateCallType, target: CallManagerDelegateCallType) {}
+    func callManager(_ callManager: CallManager<CallManagerDelegateCallType, Self>, onDataMessage call: CallManagerDelegateCallType, payload: Data) {}
 }
 
 public protocol CallManagerCallReference: AnyObject { }
@@ -267,7 +371,13 @@ public class CallManager<CallType, CallManagerDelegateType>: CallManagerInterfac
 
     private var videoCaptureController: VideoCaptureController?
 
-    public init() {
+    // Called with the captured audio level while an audio loopback runs.
+    private var audioLoopbackLevelHandler: ((UInt16) -> Void)?
+
+    /// - Parameter maxPendingIceCandidates: The most ICE candidates kept from
+    ///   each remote device for an incoming call that hasn't proceeded yet.
+    ///   Candidates beyond it are dropped.
+    public init(maxPendingIceCandidates: UInt32 = 100) {
         // Initialize the global object (mainly for logging).
         _ = CallManagerGlobal.shared
 
@@ -283,7 +393,7 @@ public class CallManager<CallType, CallManagerDelegateType>: CallManagerInterfac
         let interface = CallManagerInterface(delegate: self)
 
         // Create the RingRTC Call Manager itself.
-        guard let ringRtcCallManager = ringrtcCreate(Unmanaged.passUnretained(self).toOpaque(), interface.getWrapper()) else {
+        guard let ringRtcCallManager = ringrtcCreate(Unmanaged.passUnretained(self).toOpaque(), interface.getWrapper(), maxPendingIceCandidates) else {
             owsFail("unable to create ringRtcCallManager")
         }
 
@@ -335,6 +445,75 @@ public class CallManager<CallType, CallManagerDelegateType>: CallManagerInterfac
         }
     }
 
+    /// Tells RingRTC that the ringtone for the incoming call has started
+    /// playing. An accept() made before the call was ringing is held
+    /// back until this has been called and the call is connected.
+    public func notifyRinging(callId: UInt64) throws {
+        AssertIsOnMainThread()
+        Logger.debug("notifyRinging")
+
+        let retPtr = ringrtcNotifyRinging(ringRtcCallManager, callId)
+        if retPtr == nil {
+            throw CallManagerError.apiFailed(description: "notifyRinging() function failure")
+        }
+    }
+
+    /// Puts the call on hold, stopping its media. Call this when
+    /// performing a CallKit `CXSetHeldCallAction` that holds the call;
+    /// the app still owns deactivating the audio session. A call from
+    /// someone else can be answered while this one is held.
+    public func hold(callId: UInt64) throws {
+        AssertIsOnMainThread()
+        Logger.debug("hold")
+
+        let retPtr = ringrtcHoldCall(ringRtcCallManager, callId)
+        if retPtr == nil {
+            throw CallManagerError.apiFailed(description: "hold() function failure")
+        }
+    }
+
+    /// Resumes a call previously put on hold. The audio session should
+    /// be active again before calling this. Resuming the held call
+    /// while a second call is active puts the second call on hold.
+    public func resume(callId: UInt64) throws {
+        AssertIsOnMainThread()
+        Logger.debug("resume")
+
+        let retPtr = ringrtcResumeCall(ringRtcCallManager, callId)
+        if retPtr == nil {
+            throw CallManagerError.apiFailed(description: "resume() function failure")
+        }
+    }
+
+    /// Asks the remote to end the call and call the target instead. The
+    /// call carries on until the remote accepts, then ends with
+    /// endedTransferred. The target must stay alive until
+    /// shouldSendTransferRequest is invoked.
+    public func transferCall(callId: UInt64, target: CallType) throws {
+        AssertIsOnMainThread()
+        Logger.debug("transferCall")
+
+        let unmanagedTarget: Unmanaged<CallType> = Unmanaged.passUnretained(target)
+
+        let retPtr = ringrtcTransferCall(ringRtcCallManager, callId, unmanagedTarget.toOpaque())
+        if retPtr == nil {
+            throw CallManagerError.apiFailed(description: "transferCall() function failure")
+        }
+    }
+
+    /// Accepts a transfer the remote asked for in onTransferRequest. The
+    /// call ends with endedTransferred, after which the application should
+    /// start a new call with the target.
+    public func acceptTransfer(callId: UInt64) throws {
+        AssertIsOnMainThread()
+        Logger.debug("acceptTransfer")
+
+        let retPtr = ringrtcAcceptTransfer(ringRtcCallManager, callId)
+        if retPtr == nil {
+            throw CallManagerError.apiFailed(description: "acceptTransfer() function failure")
+        }
+    }
+
     public func hangup() throws {
         AssertIsOnMainThread()
         Logger.debug("hangup")
@@ -345,6 +524,18 @@ public class CallManager<CallType, CallManagerDelegateType>: CallManagerInterfac
         }
     }
 
+    /// Like hangup(), but tells the remote that the user stepped away,
+    /// such as by backgrounding the app, rather than hanging up.
+    public func hangupAsAway() throws {
+        AssertIsOnMainThread()
+        Logger.debug("hangupAsAway")
+
+        let retPtr = ringrtcHangupAsAway(ringRtcCallManager)
+        if retPtr == nil {
+            throw CallManagerError.apiFailed(description: "hangupAsAway() function failure")
+        }
+    }
+
     // MARK: - Flow API
 
     /// Proceed with a call after the shouldStartCall delegate was invoked.
@@ -355,13 +546,13 @@ public class CallManager<CallType, CallManagerDelegateType>: CallManagerInterfac
     ///   - hideIp: A flag used to hide the IP of the user by using relay (TURN) servers only
     ///   - videoCaptureController: UI provided capturer interface
     ///   - bandwidthMode: The desired bandwidth mode to start the session with
-    public func proceed(callId: UInt64, iceServers: [RTCIceServer], hideIp: Bool, videoCaptureController: VideoCaptureController, bandwidthMode: BandwidthMode) throws {
+    ///   - enableRtpDataChannel: Set false to leave out the RTP data channel, which carries accepts, hangups and video status once connected
+    public func proceed(callId: UInt64, iceServers: [RTCIceServer], hideIp: Bool, videoCaptureController: VideoCaptureController, bandwidthMode: BandwidthMode, audioProcessingConfig: AudioProcessingConfig = AudioProcessingConfig(), enableRtpDataChannel: Bool = true) throws {
         AssertIsOnMainThread()
         Logger.debug("proceed")
 
         // Create a shared media sources.
-        let audioConstraints = RTCMediaConstraints(mandatoryConstraints: nil, optionalConstraints: nil)
-        let audioSource = self.factory!.audioSource(with: audioConstraints)
+        let audioSource = self.factory!.audioSource(with: audioProcessingConfig.audioConstraints)
         // Note: This must stay "audio1" to stay in sync with V4 signaling.
         let audioTrack = self.factory!.audioTrack(with: audioSource, trackId: "audio1")
         audioTrack.isEnabled = false
@@ -386,7 +577,7 @@ public class CallManager<CallType, CallManagerDelegateType>: CallManagerInterfac
         // Create a call context object to hold on to some of
         // the settings needed by the application when actually
         // creating the connection.
-        let appCallContext = CallContext(iceServers: iceServers, hideIp: hideIp, audioSource: audioSource, audioTrack: audioTrack, videoSource: videoSource, videoTrack: videoTrack, videoCaptureController: videoCaptureController, certificate: certificate)
+        let appCallContext = CallContext(iceServers: iceServers, hideIp: hideIp, audioSource: audioSource, audioTrack: audioTrack, videoSource: videoSource, videoTrack: videoTrack, videoCaptureController: videoCaptureController, certificate: certificate, enableRtpDataChannel: enableRtpDataChannel)
 
         let retPtr = ringrtcProceed(ringRtcCallManager, callId, appCallContext.getWrapper(), bandwidthMode.rawValue)
         if retPtr == nil {
@@ -414,6 +605,29 @@ public class CallManager<CallType, CallManagerDelegateType>: CallManagerInterfac
         }
     }
 
+    // Sends an application-defined payload of up to 1 KiB to the remote
+    // peer of the active call.  It may be lost or arrive out of order.
+    public func sendDataMessage(callId: UInt64, payload: Data) throws {
+        AssertIsOnMainThread()
+        Logger.debug("sendDataMessage")
+
+        let payloadSlice = allocatedAppByteSliceFromData(maybe_data: payload)
+
+        // Make sure to release the allocated memory when the function exists,
+        // to ensure that the pointers are still valid when used in the RingRTC
+        // API function.
+        defer {
+            if payloadSlice.bytes != nil {
+                payloadSlice.bytes.deallocate()
+            }
+        }
+
+        let retPtr = ringrtcSendDataMessage(ringRtcCallManager, callId, payloadSlice)
+        if retPtr == nil {
+            throw CallManagerError.apiFailed(description: "ringrtcSendDataMessage() function failure")
+        }
+    }
+
     public func signalingMessageDidFail(callId: UInt64) {
         AssertIsOnMainThread()
         Logger.debug("signalingMessageDidFail")
@@ -424,6 +638,34 @@ public class CallManager<CallType, CallManagerDelegateType>: CallManagerInterfac
         }
     }
 
+    /// Play captured audio straight back out, without a call, so the user
+    /// can check their microphone and speaker.
+    ///
+    /// - Parameters:
+    ///   - onLevel: Called on the main thread with the captured audio level (0 to 32767), roughly every 100ms
+    public func startAudioLoopback(onLevel: @escaping (UInt16) -> Void) throws {
+        AssertIsOnMainThread()
+        Logger.debug("startAudioLoopback")
+
+        audioLoopbackLevelHandler = onLevel
+        let retPtr = ringrtcStartAudioLoopback(ringRtcCallManager)
+        if retPtr == nil {
+            audioLoopbackLevelHandler = nil
+            throw CallManagerError.apiFailed(description: "ringrtcStartAudioLoopback() function failure")
+        }
+    }
+
+    public func stopAudioLoopback() {
+        AssertIsOnMainThread()
+        Logger.debug("stopAudioLoopback")
+
+        let retPtr = ringrtcStopAudioLoopback(ringRtcCallManager)
+        if retPtr == nil {
+            owsFailDebug("ringrtcStopAudioLoopback() function failure")
+        }
+        audioLoopbackLevelHandler = nil
+    }
+
     public func reset() {
         AssertIsOnMainThread()
         Logger.debug("reset")
@@ -611,6 +853,28 @@ public class CallManager<CallType, CallManagerDelegateType>: CallManagerInterfac
         }
     }
 
+    public func receivedTransferRequest(sourceDevice: UInt32, callId: UInt64, target: CallType) throws {
+        AssertIsOnMainThread()
+        Logger.debug("receivedTransferRequest")
+
+        let unmanagedTarget: Unmanaged<CallType> = Unmanaged.passUnretained(target)
+
+        let retPtr = ringrtcReceivedTransferRequest(ringRtcCallManager, callId, sourceDevice, unmanagedTarget.toOpaque())
+        if retPtr == nil {
+            throw CallManagerError.apiFailed(description: "receivedTransferRequest() function failure")
+        }
+    }
+
+    public func receivedTransferAccepted(sourceDevice: UInt32, callId: UInt64) throws {
+        AssertIsOnMainThread()
+        Logger.debug("receivedTransferAccepted")
+
+        let retPtr = ringrtcReceivedTransferAccepted(ringRtcCallManager, callId, sourceDevice)
+        if retPtr == nil {
+            throw CallManagerError.apiFailed(description: "receivedTransferAccepted() function failure")
+        }
+    }
+
     public func receivedCallMessage(senderUuid: UUID, senderDeviceId: UInt32, localDeviceId: UInt32, message: Data, messageAgeSec: UInt64) {
         AssertIsOnMainThread()
         Logger.debug("receivedCallMessage")
@@ -673,7 +937,10 @@ public class CallManager<CallType, CallManagerDelegateType>: CallManagerInterfac
         return groupCall
     }
 
-    public func peekGroupCall(sfuUrl: String, membershipProof: Data, groupMembers: [GroupMemberInfo]) -> Promise<PeekInfo> {
+    /// Peeks into the group call.  If a timeout is given and the SFU
+    /// doesn't respond within it, the promise is rejected with
+    /// CallManagerError.peekTimedOut.
+    public func peekGroupCall(sfuUrl: String, membershipProof: Data, groupMembers: [GroupMemberInfo], timeout: TimeInterval? = nil) -> Promise<PeekInfo> {
         AssertIsOnMainThread()
         Logger.debug("peekGroupCall")
 
@@ -716,7 +983,9 @@ public class CallManager<CallType, CallManagerDelegateType>: CallManagerInterfac
         }
 
         let (requestId, promise) = self.peekInfoRequests.add()
-        ringrtcPeekGroupCall(self.ringRtcCallManager, requestId, sfuUrlSlice, membershipProofSlice, &appGroupMemberInfoArray)
+        // A timeout of zero means no timeout.
+        let timeoutMillis = UInt32(max(0, min((timeout ?? 0) * 1000, Double(UInt32.max))))
+        ringrtcPeekGroupCall(self.ringRtcCallManager, requestId, sfuUrlSlice, membershipProofSlice, &appGroupMemberInfoArray, timeoutMillis)
         return promise
     }
 
@@ -815,6 +1084,47 @@ public class CallManager<CallType, CallManagerDelegateType>: CallManagerInterfac
         }
     }
 
+    func onSendTransferRequest(callId: UInt64, remote: UnsafeRawPointer, destinationDeviceId: UInt32?, target: UnsafeRawPointer) {
+        Logger.debug("onSendTransferRequest")
+
+        DispatchQueue.main.async {
+            Logger.debug("onSendTransferRequest - main.async")
+
+            guard let delegate = self.delegate else { return }
+
+            let callReference: CallType = Unmanaged.fromOpaque(remote).takeUnretainedValue()
+            let targetReference: CallType = Unmanaged.fromOpaque(target).takeUnretainedValue()
+            delegate.callManager(self, shouldSendTransferRequest: callId, call: callReference, destinationDeviceId: destinationDeviceId, target: targetReference)
+        }
+    }
+
+    func onSendTransferAccepted(callId: UInt64, remote: UnsafeRawPointer, destinationDeviceId: UInt32?) {
+        Logger.debug("onSendTransferAccepted")
+
+        DispatchQueue.main.async {
+            Logger.debug("onSendTransferAccepted - main.async")
+
+            guard let delegate = self.delegate else { return }
+
+            let callReference: CallType = Unmanaged.fromOpaque(remote).takeUnretainedValue()
+            delegate.callManager(self, shouldSendTransferAccepted: callId, call: callReference, destinationDeviceId: destinationDeviceId)
+        }
+    }
+
+    func handleTransferRequest(remote: UnsafeRawPointer, target: UnsafeRawPointer) {
+        Logger.debug("handleTransferRequest")
+
+        DispatchQueue.main.async {
+            Logger.debug("handleTransferRequest - main.async")
+
+            guard let delegate = self.delegate else { return }
+
+            let callReference: CallType = Unmanaged.fromOpaque(remote).takeUnretainedValue()
+            let targetReference: CallType = Unmanaged.fromOpaque(target).takeUnretainedValue()
+            delegate.callManager(self, onTransferRequest: callReference, target: targetReference)
+        }
+    }
+
     func sendCallMessage(recipientUuid: UUID, message: Data) {
         Logger.debug("sendCallMessage")
 
@@ -943,6 +1253,25 @@ public class CallManager<CallType, CallManagerDelegateType>: CallManagerInterfac
         }
     }
 
+    func onDataMessage(remote: UnsafeRawPointer, payload: Data) {
+        Logger.debug("onDataMessage")
+
+        DispatchQueue.main.async {
+            Logger.debug("onDataMessage - main.async")
+
+            guard let delegate = self.delegate else { return }
+
+            let callReference: CallType = Unmanaged.fromOpaque(remote).takeUnretainedValue()
+            delegate.callManager(self, onDataMessage: callReference, payload: payload)
+        }
+    }
+
+    func onAudioLoopbackLevel(level: UInt16) {
+        DispatchQueue.main.async {
+            self.audioLoopbackLevelHandler?(level)
+        }
+    }
+
     func handlePeekResponse(requestId: UInt32, peekInfo: PeekInfo) {
         Logger.debug("handlePeekResponse")
 
@@ -955,6 +1284,18 @@ public class CallManager<CallType, CallManagerDelegateType>: CallManagerInterfac
         }
     }
 
+    func handlePeekTimedOut(requestId: UInt32) {
+        Logger.debug("handlePeekTimedOut")
+
+        DispatchQueue.main.async {
+            Logger.debug("handlePeekTimedOut - main.async")
+
+            if !self.peekInfoRequests.reject(id: requestId, error: CallManagerError.peekTimedOut) {
+                Logger.warn("Invalid requestId for handlePeekTimedOut: \(requestId)")
+            }
+        }
+    }
+
     // MARK: - Group Call Observers
 
     func requestMembershipProof(clientId: UInt32) {
@@ -999,7 +1340,7 @@ public class CallManager<CallType, CallManagerDelegateType>: CallManagerInterfac
         }
     }
 
-    func handleJoinStateChanged(clientId: UInt32, joinState: JoinState) {
+    func handleJoinStateChanged(clientId: UInt32, joinState: JoinState, outgoingAudioMuted: Bool?) {
         Logger.debug("handleJoinStateChanged")
 
         DispatchQueue.main.async {
@@ -1009,7 +1350,7 @@ public class CallManager<CallType, CallManagerDelegateType>: CallManagerInterfac
                 return
             }
 
-            groupCall.handleJoinStateChanged(joinState: joinState)
+            groupCall.handleJoinStateChanged(joinState: joinState, outgoingAudioMuted: outgoingAudioMuted)
         }
     }
 
@@ -1055,6 +1396,102 @@ public class CallManager<CallType, CallManagerDelegateType>: CallManagerInterfac
         }
     }
 
+    func handleEraChanged(clientId: UInt32, oldEraId: String?, newEraId: String?) {
+        Logger.debug("handleEraChanged")
+
+        DispatchQueue.main.async {
+            Logger.debug("handleEraChanged - main.async")
+
+            guard let groupCall = self.groupCallByClientId[clientId] else {
+                return
+            }
+
+            groupCall.handleEraChanged(oldEraId: oldEraId, newEraId: newEraId)
+        }
+    }
+
+    func handleParticipantQualityChanged(clientId: UInt32, remoteDemuxId: UInt32, quality: UInt8) {
+        Logger.debug("handleParticipantQualityChanged")
+
+        DispatchQueue.main.async {
+            Logger.debug("handleParticipantQualityChanged - main.async")
+
+            guard let groupCall = self.groupCallByClientId[clientId] else {
+                return
+            }
+
+            groupCall.handleParticipantQualityChanged(remoteDemuxId: remoteDemuxId, quality: quality)
+        }
+    }
+
+    func handleAudioLevels(clientId: UInt32, audioLevels: [UInt32: UInt8]) {
+        Logger.verbose("handleAudioLevels")
+
+        DispatchQueue.main.async {
+            guard let groupCall = self.groupCallByClientId[clientId] else {
+                return
+            }
+
+            groupCall.handleAudioLevels(audioLevels: audioLevels)
+        }
+    }
+
+    func handleMediaKeysReceived(clientId: UInt32, remoteDemuxId: UInt32) {
+        Logger.debug("handleMediaKeysReceived")
+
+        DispatchQueue.main.async {
+            Logger.debug("handleMediaKeysReceived - main.async")
+
+            guard let groupCall = self.groupCallByClientId[clientId] else {
+                return
+            }
+
+            groupCall.handleMediaKeysReceived(remoteDemuxId: remoteDemuxId)
+        }
+    }
+
+    func handleIncomingInterpreterAudioTrack(clientId: UInt32, remoteDemuxId: UInt32) {
+        Logger.debug("handleIncomingInterpreterAudioTrack")
+
+        DispatchQueue.main.async {
+            Logger.debug("handleIncomingInterpreterAudioTrack - main.async")
+
+            guard let groupCall = self.groupCallByClientId[clientId] else {
+                return
+            }
+
+            groupCall.handleIncomingInterpreterAudioTrack(remoteDemuxId: remoteDemuxId)
+        }
+    }
+
+    func handleCandidatePairChanged(clientId: UInt32, localDemuxId: UInt32, candidatePairType: CandidatePairType) {
+        Logger.debug("handleCandidatePairChanged")
+
+        DispatchQueue.main.async {
+            Logger.debug("handleCandidatePairChanged - main.async")
+
+            guard let groupCall = self.groupCallByClientId[clientId] else {
+                return
+            }
+
+            groupCall.handleCandidatePairChanged(localDemuxId: localDemuxId, candidatePairType: candidatePairType)
+        }
+    }
+
+    func handleJoinRejected(clientId: UInt32, httpStatus: UInt16, message: String?) {
+        Logger.debug("handleJoinRejected")
+
+        DispatchQueue.main.async {
+            Logger.debug("handleJoinRejected - main.async")
+
+            guard let groupCall = self.groupCallByClientId[clientId] else {
+                return
+            }
+
+            groupCall.handleJoinRejected(httpStatus: httpStatus, message: message)
+        }
+    }
+
     func handleEnded(clientId: UInt32, reason: GroupCallEndReason) {
         Logger.debug("handleEnded")
 
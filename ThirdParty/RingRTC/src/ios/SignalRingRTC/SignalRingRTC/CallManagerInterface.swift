@@ -15,24 +15,37 @@ protocol CallManagerInterfaceDelegate: class {
     func onSendIceCandidates(callId: UInt64, remote: UnsafeRawPointer, destinationDeviceId: UInt32?, candidates: [Data])
     func onSendHangup(callId: UInt64, remote: UnsafeRawPointer, destinationDeviceId: UInt32?, hangupType: HangupType, deviceId: UInt32, useLegacyHangupMessage: Bool)
     func onSendBusy(callId: UInt64, remote: UnsafeRawPointer, destinationDeviceId: UInt32?)
+    func onSendTransferRequest(callId: UInt64, remote: UnsafeRawPointer, destinationDeviceId: UInt32?, target: UnsafeRawPointer)
+    func onSendTransferAccepted(callId: UInt64, remote: UnsafeRawPointer, destinationDeviceId: UInt32?)
+    func handleTransferRequest(remote: UnsafeRawPointer, target: UnsafeRawPointer)
     func sendCallMessage(recipientUuid: UUID, message: Data)
     func sendHttpRequest(requestId: UInt32, url: String, method: CallManagerHttpMethod, headers: [String: String], body: Data?)
     func onCreateConnection(pcObserver: UnsafeMutableRawPointer?, deviceId: UInt32, appCallContext: CallContext, enableDtls: Bool, enableRtpDataChannel: Bool) -> (connection: Connection, pc: UnsafeMutableRawPointer?)
     func onConnectMedia(remote: UnsafeRawPointer, appCallContext: CallContext, stream: RTCMediaStream)
     func onCompareRemotes(remote1: UnsafeRawPointer, remote2: UnsafeRawPointer) -> Bool
     func onCallConcluded(remote: UnsafeRawPointer)
+    func onDataMessage(remote: UnsafeRawPointer, payload: Data)
+    func onAudioLoopbackLevel(level: UInt16)
 
     // Group Calls
 
     func handlePeekResponse(requestId: UInt32, peekInfo: PeekInfo)
+    func handlePeekTimedOut(requestId: UInt32)
 
     func requestMembershipProof(clientId: UInt32)
     func requestGroupMembers(clientId: UInt32)
     func handleConnectionStateChanged(clientId: UInt32, connectionState: ConnectionState)
-    func handleJoinStateChanged(clientId: UInt32, joinState: JoinState)
+    func handleJoinStateChanged(clientId: UInt32, joinState: JoinState, outgoingAudioMuted: Bool?)
     func handleRemoteDevicesChanged(clientId: UInt32, remoteDeviceStates: [RemoteDeviceState])
     func handleIncomingVideoTrack(clientId: UInt32, remoteDemuxId: UInt32, nativeVideoTrack: UnsafeMutableRawPointer?)
     func handlePeekChanged(clientId: UInt32, peekInfo: PeekInfo)
+    func handleEraChanged(clientId: UInt32, oldEraId: String?, newEraId: String?)
+    func handleParticipantQualityChanged(clientId: UInt32, remoteDemuxId: UInt32, quality: UInt8)
+    func handleAudioLevels(clientId: UInt32, audioLevels: [UInt32: UInt8])
+    func handleMediaKeysReceived(clientId: UInt32, remoteDemuxId: UInt32)
+    func handleIncomingInterpreterAudioTrack(clientId: UInt32, remoteDemuxId: UInt32)
+    func handleCandidatePairChanged(clientId: UInt32, localDemuxId: UInt32, candidatePairType: CandidatePairType)
+    func handleJoinRejected(clientId: UInt32, httpStatus: UInt16, message: String?)
     func handleEnded(clientId: UInt32, reason: GroupCallEndReason)
 }
 
@@ -63,6 +76,9 @@ class CallManagerInterface {
             onSendIceCandidates: callManagerInterfaceOnSendIceCandidates,
             onSendHangup: callManagerInterfaceOnSendHangup,
             onSendBusy: callManagerInterfaceOnSendBusy,
+            onSendTransferRequest: callManagerInterfaceOnSendTransferRequest,
+            onSendTransferAccepted: callManagerInterfaceOnSendTransferAccepted,
+            handleTransferRequest: callManagerInterfaceHandleTransferRequest,
             sendCallMessage: callManagerInterfaceSendCallMessage,
             sendHttpRequest: callManagerInterfaceSendHttpRequest,
             onCreateConnectionInterface: callManagerInterfaceOnCreateConnectionInterface,
@@ -70,10 +86,13 @@ class CallManagerInterface {
             onConnectMedia: callManagerInterfaceOnConnectMedia,
             onCompareRemotes: callManagerInterfaceOnCompareRemotes,
             onCallConcluded: callManagerInterfaceOnCallConcluded,
+            onDataMessage: callManagerInterfaceOnDataMessage,
+            onAudioLoopbackLevel: callManagerInterfaceOnAudioLoopbackLevel,
 
             // Group Calls
 
             handlePeekResponse: callManagerInterfaceHandlePeekResponse,
+            handlePeekTimedOut: callManagerInterfaceHandlePeekTimedOut,
 
             requestMembershipProof: callManagerInterfaceRequestMembershipProof,
             requestGroupMembers: callManagerInterfaceRequestGroupMembers,
@@ -82,6 +101,13 @@ class CallManagerInterface {
             handleRemoteDevicesChanged: callManagerInterfaceHandleRemoteDevicesChanged,
             handleIncomingVideoTrack: callManagerInterfaceHandleIncomingVideoTrack,
             handlePeekChanged: callManagerInterfaceHandlePeekChanged,
+            handleEraChanged: callManagerInterfaceHandleEraChanged,
+            handleParticipantQualityChanged: callManagerInterfaceHandleParticipantQualityChanged,
+            handleAudioLevels: callManagerInterfaceHandleAudioLevels,
+            handleMediaKeysReceived: callManagerInterfaceHandleMediaKeysReceived,
+            handleIncomingInterpreterAudioTrack: callManagerInterfaceHandleIncomingInterpreterAudioTrack,
+            handleCandidatePairChanged: callManagerInterfaceHandleCandidatePairChanged,
+            handleJoinRejected: callManagerInterfaceHandleJoinRejected,
             handleEnded: callManagerInterfaceHandleEnded
         )
     }
@@ -148,6 +174,30 @@ class CallManagerInterface {
         delegate.onSendBusy(callId: callId, remote: remote, destinationDeviceId: destinationDeviceId)
     }
 
+    func onSendTransferRequest(callId: UInt64, remote: UnsafeRawPointer, destinationDeviceId: UInt32?, target: UnsafeRawPointer) {
+        guard let delegate = self.callManagerObserverDelegate else {
+            return
+        }
+
+        delegate.onSendTransferRequest(callId: callId, remote: remote, destinationDeviceId: destinationDeviceId, target: target)
+    }
+
+    func onSendTransferAccepted(callId: UInt64, remote: UnsafeRawPointer, destinationDeviceId: UInt32?) {
+        guard let delegate = self.callManagerObserverDelegate else {
+            return
+        }
+
+        delegate.onSendTransferAccepted(callId: callId, remote: remote, destinationDeviceId: destinationDeviceId)
+    }
+
+    func handleTransferRequest(remote: UnsafeRawPointer, target: UnsafeRawPointer) {
+        guard let delegate = self.callManagerObserverDelegate else {
+            return
+        }
+
+        delegate.handleTransferRequest(remote: remote, target: target)
+    }
+
     func sendCallMessage(recipientUuid: UUID, message: Data) {
         guard let delegate = self.callManagerObserverDelegate else {
             return
@@ -196,6 +246,22 @@ class CallManagerInterface {
         delegate.onCallConcluded(remote: remote)
     }
 
+    func onDataMessage(remote: UnsafeRawPointer, payload: Data) {
+        guard let delegate = self.callManagerObserverDelegate else {
+            return
+        }
+
+        delegate.onDataMessage(remote: remote, payload: payload)
+    }
+
+    func onAudioLoopbackLevel(level: UInt16) {
+        guard let delegate = self.callManagerObserverDelegate else {
+            return
+        }
+
+        delegate.onAudioLoopbackLevel(level: level)
+    }
+
     // Group Calls
 
     func handlePeekResponse(requestId: UInt32, peekInfo: PeekInfo) {
@@ -206,6 +272,14 @@ class CallManagerInterface {
         delegate.handlePeekResponse(requestId: requestId, peekInfo: peekInfo)
     }
 
+    func handlePeekTimedOut(requestId: UInt32) {
+        guard let delegate = self.callManagerObserverDelegate else {
+            return
+        }
+
+        delegate.handlePeekTimedOut(requestId: requestId)
+    }
+
     func requestMembershipProof(clientId: UInt32) {
         guard let delegate = self.callManagerObserverDelegate else {
             return
@@ -230,12 +304,12 @@ class CallManagerInterface {
         delegate.handleConnectionStateChanged(clientId: clientId, connectionState: connectionState)
     }
 
-    func handleJoinStateChanged(clientId: UInt32, joinState: JoinState) {
+    func handleJoinStateChanged(clientId: UInt32, joinState: JoinState, outgoingAudioMuted: Bool?) {
         guard let delegate = self.callManagerObserverDelegate else {
             return
         }
 
-        delegate.handleJoinStateChanged(clientId: clientId, joinState: joinState)
+        delegate.handleJoinStateChanged(clientId: clientId, joinState: joinState, outgoingAudioMuted: outgoingAudioMuted)
     }
 
     func handleRemoteDevicesChanged(clientId: UInt32, remoteDeviceStates: [RemoteDeviceState]) {
@@ -262,6 +336,62 @@ class CallManagerInterface {
         delegate.handlePeekChanged(clientId: clientId, peekInfo: peekInfo)
     }
 
+    func handleEraChanged(clientId: UInt32, oldEraId: String?, newEraId: String?) {
+        guard let delegate = self.callManagerObserverDelegate else {
+            return
+        }
+
+        delegate.handleEraChanged(clientId: clientId, oldEraId: oldEraId, newEraId: newEraId)
+    }
+
+    func handleParticipantQualityChanged(clientId: UInt32, remoteDemuxId: UInt32, quality: UInt8) {
+        guard let delegate = self.callManagerObserverDelegate else {
+            return
+        }
+
+        delegate.handleParticipantQualityChanged(clientId: clientId, remoteDemuxId: remoteDemuxId, quality: quality)
+    }
+
+    func handleAudioLevels(clientId: UInt32, audioLevels: [UInt32: UInt8]) {
+        guard let delegate = self.callManagerObserverDelegate else {
+            return
+        }
+
+        delegate.handleAudioLevels(clientId: clientId, audioLevels: audioLevels)
+    }
+
+    func handleMediaKeysReceived(clientId: UInt32, remoteDemuxId: UInt32) {
+        guard let delegate = self.callManagerObserverDelegate else {
+            return
+        }
+
+        delegate.handleMediaKeysReceived(clientId: clientId, remoteDemuxId: remoteDemuxId)
+    }
+
+    func handleIncomingInterpreterAudioTrack(clientId: UInt32, remoteDemuxId: UInt32) {
+        guard let delegate = self.callManagerObserverDelegate else {
+            return
+        }
+
+        delegate.handleIncomingInterpreterAudioTrack(clientId: clientId, remoteDemuxId: remoteDemuxId)
+    }
+
+    func handleCandidatePairChanged(clientId: UInt32, localDemuxId: UInt32, candidatePairType: CandidatePairType) {
+        guard let delegate = self.callManagerObserverDelegate else {
+            return
+        }
+
+        delegate.handleCandidatePairChanged(clientId: clientId, localDemuxId: localDemuxId, candidatePairType: candidatePairType)
+    }
+
+    func handleJoinRejected(clientId: UInt32, httpStatus: UInt16, message: String?) {
+        guard let delegate = self.callManagerObserverDelegate else {
+            return
+        }
+
+        delegate.handleJoinRejected(clientId: clientId, httpStatus: httpStatus, message: message)
+    }
+
     func handleEnded(clientId: UInt32, reason: GroupCallEndReason) {
         guard let delegate = self.callManagerObserverDelegate else {
             return
@@ -469,6 +599,73 @@ func callManagerInterfaceOnSendBusy(object: UnsafeMutableRawPointer?, callId: UI
     obj.onSendBusy(callId: callId, remote: remote, destinationDeviceId: destinationDeviceId)
 }
 
+func callManagerInterfaceOnSendTransferRequest(object: UnsafeMutableRawPointer?, callId: UInt64, remote: UnsafeRawPointer?, destinationDeviceId: UInt32, broadcast: Bool, target: UnsafeRawPointer?) {
+    guard let object = object else {
+        owsFailDebug("object was unexpectedly nil")
+        return
+    }
+    let obj: CallManagerInterface = Unmanaged.fromOpaque(object).takeUnretainedValue()
+
+    guard let remote = remote else {
+        owsFailDebug("remote was unexpectedly nil")
+        return
+    }
+
+    guard let target = target else {
+        owsFailDebug("target was unexpectedly nil")
+        return
+    }
+
+    // If we will broadcast this message, ignore the deviceId.
+    var destinationDeviceId: UInt32? = destinationDeviceId
+    if broadcast {
+        destinationDeviceId = nil
+    }
+
+    obj.onSendTransferRequest(callId: callId, remote: remote, destinationDeviceId: destinationDeviceId, target: target)
+}
+
+func callManagerInterfaceOnSendTransferAccepted(object: UnsafeMutableRawPointer?, callId: UInt64, remote: UnsafeRawPointer?, destinationDeviceId: UInt32, broadcast: Bool) {
+    guard let object = object else {
+        owsFailDebug("object was unexpectedly nil")
+        return
+    }
+    let obj: CallManagerInterface = Unmanaged.fromOpaque(object).takeUnretainedValue()
+
+    guard let remote = remote else {
+        owsFailDebug("remote was unexpectedly nil")
+        return
+    }
+
+    // If we will broadcast this message, ignore the deviceId.
+    var destinationDeviceId: UInt32? = destinationDeviceId
+    if broadcast {
+        destinationDeviceId = nil
+    }
+
+    obj.onSendTransferAccepted(callId: callId, remote: remote, destinationDeviceId: destinationDeviceId)
+}
+
+func callManagerInterfaceHandleTransferRequest(object: UnsafeMutableRawPointer?, remote: UnsafeRawPointer?, target: UnsafeRawPointer?) {
+    guard let object = object else {
+        owsFailDebug("object was unexpectedly nil")
+        return
+    }
+    let obj: CallManagerInterface = Unmanaged.fromOpaque(object).takeUnretainedValue()
+
+    guard let remote = remote else {
+        owsFailDebug("remote was unexpectedly nil")
+        return
+    }
+
+    guard let target = target else {
+        owsFailDebug("target was unexpectedly nil")
+        return
+    }
+
+    obj.handleTransferRequest(remote: remote, target: target)
+}
+
 func callManagerInterfaceSendCallMessage(object: UnsafeMutableRawPointer?, recipientUuid: AppByteSlice, message: AppByteSlice) {
     guard let object = object else {
         owsFailDebug("object was unexpectedly nil")
@@ -660,9 +857,34 @@ func callManagerInterfaceOnCallConcluded(object: UnsafeMutableRawPointer?, remot
     obj.onCallConcluded(remote: remote)
 }
 
+func callManagerInterfaceOnDataMessage(object: UnsafeMutableRawPointer?, remote: UnsafeRawPointer?, payload: AppByteSlice) {
+    guard let object = object else {
+        owsFailDebug("object was unexpectedly nil")
+        return
+    }
+    let obj: CallManagerInterface = Unmanaged.fromOpaque(object).takeUnretainedValue()
+
+    guard let remote = remote else {
+        owsFailDebug("remote was unexpectedly nil")
+        return
+    }
+
+    obj.onDataMessage(remote: remote, payload: payload.asData() ?? Data())
+}
+
+func callManagerInterfaceOnAudioLoopbackLevel(object: UnsafeMutableRawPointer?, level: UInt16) {
+    guard let object = object else {
+        owsFailDebug("object was unexpectedly nil")
+        return
+    }
+    let obj: CallManagerInterface = Unmanaged.fromOpaque(object).takeUnretainedValue()
+
+    obj.onAudioLoopbackLevel(level: level)
+}
+
 // Group Calls
 
-func callManagerInterfaceHandlePeekResponse(object: UnsafeMutableRawPointer?, requestId: UInt32, joinedMembers: AppUuidArray, creator: AppByteSlice, eraId: AppByteSlice, maxDevices: AppOptionalUInt32, deviceCount: UInt32) {
+func callManagerInterfaceHandlePeekResponse(object: UnsafeMutableRawPointer?, requestId: UInt32, joinedMembers: AppUuidArray, joinedDevices: AppJoinedDeviceArray, creator: AppByteSlice, eraId: AppByteSlice, maxDevices: AppOptionalUInt32, deviceCount: UInt32) {
     guard let object = object else {
         owsFailDebug("object was unexpectedly nil")
         return
@@ -680,16 +902,38 @@ func callManagerInterfaceHandlePeekResponse(object: UnsafeMutableRawPointer?, re
         finalJoinedMembers.append(userId.uuid)
     }
 
+    var finalJoinedDevices: [JoinedDevice] = []
+
+    for index in 0..<joinedDevices.count {
+        let joinedDevice = joinedDevices.devices[index]
+        guard let userId = joinedDevice.userId.asData() else {
+            Logger.debug("missing userId")
+            continue
+        }
+
+        finalJoinedDevices.append(JoinedDevice(userId: userId.uuid, demuxId: joinedDevice.demuxId))
+    }
+
     var finalMaxDevices: UInt32?
     if maxDevices.valid {
         finalMaxDevices = maxDevices.value
     }
 
-    let peekInfo = PeekInfo(joinedMembers: finalJoinedMembers, creator: creator.asData()?.uuid, eraId: eraId.asString(), maxDevices: finalMaxDevices, deviceCount: deviceCount)
+    let peekInfo = PeekInfo(joinedMembers: finalJoinedMembers, joinedDevices: finalJoinedDevices, creator: creator.asData()?.uuid, eraId: eraId.asString(), maxDevices: finalMaxDevices, deviceCount: deviceCount)
 
     obj.handlePeekResponse(requestId: requestId, peekInfo: peekInfo)
 }
 
+func callManagerInterfaceHandlePeekTimedOut(object: UnsafeMutableRawPointer?, requestId: UInt32) {
+    guard let object = object else {
+        owsFailDebug("object was unexpectedly nil")
+        return
+    }
+    let obj: CallManagerInterface = Unmanaged.fromOpaque(object).takeUnretainedValue()
+
+    obj.handlePeekTimedOut(requestId: requestId)
+}
+
 func callManagerInterfaceRequestMembershipProof(object: UnsafeMutableRawPointer?, clientId: UInt32) {
     guard let object = object else {
         owsFailDebug("object was unexpectedly nil")
@@ -728,7 +972,7 @@ func callManagerInterfaceHandleConnectionStateChanged(object: UnsafeMutableRawPo
     obj.handleConnectionStateChanged(clientId: clientId, connectionState: _connectionState)
 }
 
-func callManagerInterfaceHandleJoinStateChanged(object: UnsafeMutableRawPointer?, clientId: UInt32, joinState: Int32) {
+func callManagerInterfaceHandleJoinStateChanged(object: UnsafeMutableRawPointer?, clientId: UInt32, joinState: Int32, outgoingAudioMuted: AppOptionalBool) {
     guard let object = object else {
         owsFailDebug("object was unexpectedly nil")
         return
@@ -743,7 +987,9 @@ func callManagerInterfaceHandleJoinStateChanged(object: UnsafeMutableRawPointer?
         return
     }
 
-    obj.handleJoinStateChanged(clientId: clientId, joinState: _joinState)
+    let _outgoingAudioMuted: Bool? = outgoingAudioMuted.valid ? outgoingAudioMuted.value : nil
+
+    obj.handleJoinStateChanged(clientId: clientId, joinState: _joinState, outgoingAudioMuted: _outgoingAudioMuted)
 }
 
 func callManagerInterfaceHandleRemoteDevicesChanged(object: UnsafeMutableRawPointer?, clientId: UInt32, remoteDeviceStates: AppRemoteDeviceStateArray) {
@@ -789,7 +1035,7 @@ func callManagerInterfaceHandleIncomingVideoTrack(object: UnsafeMutableRawPointe
     obj.handleIncomingVideoTrack(clientId: clientId, remoteDemuxId: remoteDemuxId, nativeVideoTrack: nativeVideoTrack)
 }
 
-func callManagerInterfaceHandlePeekChanged(object: UnsafeMutableRawPointer?, clientId: UInt32, joinedMembers: AppUuidArray, creator: AppByteSlice, eraId: AppByteSlice, maxDevices: AppOptionalUInt32, deviceCount: UInt32) {
+func callManagerInterfaceHandlePeekChanged(object: UnsafeMutableRawPointer?, clientId: UInt32, joinedMembers: AppUuidArray, joinedDevices: AppJoinedDeviceArray, creator: AppByteSlice, eraId: AppByteSlice, maxDevices: AppOptionalUInt32, deviceCount: UInt32) {
     guard let object = object else {
         owsFailDebug("object was unexpectedly nil")
         return
@@ -807,16 +1053,110 @@ func callManagerInterfaceHandlePeekChanged(object: UnsafeMutableRawPointer?, cli
         finalJoinedMembers.append(userId.uuid)
     }
 
+    var finalJoinedDevices: [JoinedDevice] = []
+
+    for index in 0..<joinedDevices.count {
+        let joinedDevice = joinedDevices.devices[index]
+        guard let userId = joinedDevice.userId.asData() else {
+            Logger.debug("missing userId")
+            continue
+        }
+
+        finalJoinedDevices.append(JoinedDevice(userId: userId.uuid, demuxId: joinedDevice.demuxId))
+    }
+
     var finalMaxDevices: UInt32?
     if maxDevices.valid {
         finalMaxDevices = maxDevices.value
     }
 
-    let peekInfo = PeekInfo(joinedMembers: finalJoinedMembers, creator: creator.asData()?.uuid, eraId: eraId.asString(), maxDevices: finalMaxDevices, deviceCount: deviceCount)
+    let peekInfo = PeekInfo(joinedMembers: finalJoinedMembers, joinedDevices: finalJoinedDevices, creator: creator.asData()?.uuid, eraId: eraId.asString(), maxDevices: finalMaxDevices, deviceCount: deviceCount)
 
     obj.handlePeekChanged(clientId: clientId, peekInfo: peekInfo)
 }
 
+func callManagerInterfaceHandleEraChanged(object: UnsafeMutableRawPointer?, clientId: UInt32, oldEraId: AppByteSlice, newEraId: AppByteSlice) {
+    guard let object = object else {
+        owsFailDebug("object was unexpectedly nil")
+        return
+    }
+    let obj: CallManagerInterface = Unmanaged.fromOpaque(object).takeUnretainedValue()
+
+    obj.handleEraChanged(clientId: clientId, oldEraId: oldEraId.asString(), newEraId: newEraId.asString())
+}
+
+func callManagerInterfaceHandleParticipantQualityChanged(object: UnsafeMutableRawPointer?, clientId: UInt32, remoteDemuxId: UInt32, quality: UInt8) {
+    guard let object = object else {
+        owsFailDebug("object was unexpectedly nil")
+        return
+    }
+    let obj: CallManagerInterface = Unmanaged.fromOpaque(object).takeUnretainedValue()
+
+    obj.handleParticipantQualityChanged(clientId: clientId, remoteDemuxId: remoteDemuxId, quality: quality)
+}
+
+func callManagerInterfaceHandleAudioLevels(object: UnsafeMutableRawPointer?, clientId: UInt32, audioLevels: AppAudioLevelArray) {
+    guard let object = object else {
+        owsFailDebug("object was unexpectedly nil")
+        return
+    }
+    let obj: CallManagerInterface = Unmanaged.fromOpaque(object).takeUnretainedValue()
+
+    var finalAudioLevels: [UInt32: UInt8] = [:]
+
+    for index in 0..<audioLevels.count {
+        let audioLevel = audioLevels.levels[index]
+        finalAudioLevels[audioLevel.demuxId] = audioLevel.level
+    }
+
+    obj.handleAudioLevels(clientId: clientId, audioLevels: finalAudioLevels)
+}
+
+func callManagerInterfaceHandleMediaKeysReceived(object: UnsafeMutableRawPointer?, clientId: UInt32, remoteDemuxId: UInt32) {
+    guard let object = object else {
+        owsFailDebug("object was unexpectedly nil")
+        return
+    }
+    let obj: CallManagerInterface = Unmanaged.fromOpaque(object).takeUnretainedValue()
+
+    obj.handleMediaKeysReceived(clientId: clientId, remoteDemuxId: remoteDemuxId)
+}
+
+func callManagerInterfaceHandleIncomingInterpreterAudioTrack(object: UnsafeMutableRawPointer?, clientId: UInt32, remoteDemuxId: UInt32) {
+    guard let object = object else {
+        owsFailDebug("object was unexpectedly nil")
+        return
+    }
+    let obj: CallManagerInterface = Unmanaged.fromOpaque(object).takeUnretainedValue()
+
+    obj.handleIncomingInterpreterAudioTrack(clientId: clientId, remoteDemuxId: remoteDemuxId)
+}
+
+func callManagerInterfaceHandleCandidatePairChanged(object: UnsafeMutableRawPointer?, clientId: UInt32, localDemuxId: UInt32, candidatePairType: Int32) {
+    guard let object = object else {
+        owsFailDebug("object was unexpectedly nil")
+        return
+    }
+    let obj: CallManagerInterface = Unmanaged.fromOpaque(object).takeUnretainedValue()
+
+    guard let _candidatePairType = CandidatePairType(rawValue: candidatePairType) else {
+        owsFailDebug("unexpected candidate pair type")
+        return
+    }
+
+    obj.handleCandidatePairChanged(clientId: clientId, localDemuxId: localDemuxId, candidatePairType: _candidatePairType)
+}
+
+func callManagerInterfaceHandleJoinRejected(object: UnsafeMutableRawPointer?, clientId: UInt32, httpStatus: UInt16, message: AppByteSlice) {
+    guard let object = object else {
+        owsFailDebug("object was unexpectedly nil")
+        return
+    }
+    let obj: CallManagerInterface = Unmanaged.fromOpaque(object).takeUnretainedValue()
+
+    obj.handleJoinRejected(clientId: clientId, httpStatus: httpStatus, message: message.asString())
+}
+
 func callManagerInterfaceHandleEnded(object: UnsafeMutableRawPointer?, clientId: UInt32, reason: Int32) {
     guard let object = object else {
         owsFailDebug("object was unexpectedly nil")
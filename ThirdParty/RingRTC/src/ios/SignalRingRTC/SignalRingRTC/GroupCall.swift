@@ -44,10 +44,22 @@ public enum GroupCallEndReason: Int32 {
     case hasMaxDevices = 14
 }
 
+/// The kind of ICE candidate pair carrying media to and from the media server.
+public enum CandidatePairType: Int32 {
+    case host = 0
+    case srflx = 1
+    case prflx = 2
+    // Media goes through a TURN server.
+    case relay = 3
+}
+
 /// The local device state for a group call.
 public class LocalDeviceState {
     public internal(set) var connectionState: ConnectionState
     public internal(set) var joinState: JoinState
+    // The outgoing audio mute state as of the last join state change, if
+    // known. Set from the first one when joining muted.
+    public internal(set) var audioMuted: Bool?
 
     init() {
         self.connectionState = .notConnected
@@ -104,15 +116,26 @@ public struct VideoRequest {
     let width: UInt16
     let height: UInt16
     let framerate: UInt16?
+    /// Pinned requests get first claim on the incoming videos allowed.
+    let pinned: Bool
 
-    public init(demuxId: UInt32, width: UInt16, height: UInt16, framerate: UInt16?) {
+    public init(demuxId: UInt32, width: UInt16, height: UInt16, framerate: UInt16?, pinned: Bool = false) {
         self.demuxId = demuxId
         self.width = width
         self.height = height
         self.framerate = framerate
+        self.pinned = pinned
     }
 }
 
+/// Why the server turned down the request to join a group call.
+public struct JoinRejection {
+    /// The HTTP status, e.g. 403 when the user isn't a member of the call.
+    public let httpStatus: UInt16
+    /// The server's message, if any.
+    public let message: String?
+}
+
 /// The group call observer.
 public protocol GroupCallDelegate: class {
     /**
@@ -145,6 +168,56 @@ public protocol GroupCallDelegate: class {
      */
     func groupCall(onPeekChanged groupCall: GroupCall)
 
+    /**
+     * Indication that the era of the group call changed, such as when the
+     * server restarted it. Fired before the onPeekChanged with the new era.
+     */
+    func groupCall(onEraChanged groupCall: GroupCall, oldEraId: String?, newEraId: String?)
+
+    /**
+     * Indication that the quality, from 0 (unusable) to 100 (perfect), of a
+     * remote device's connection changed. This is the remote device's uplink
+     * to the server as the server sees it, not how well we receive from it.
+     */
+    func groupCall(onParticipantQualityChanged groupCall: GroupCall, remoteDemuxId: UInt32, quality: UInt8)
+
+    /**
+     * Indication of the audio level, from 0 (silent) to 100 (loudest), of
+     * the local device (what is sent) and of each remote device (what is
+     * received), keyed by demux ID. Fired about once a second while
+     * connected, to drive a speaking indicator.
+     */
+    func groupCall(onAudioLevels groupCall: GroupCall, audioLevels: [UInt32: UInt8])
+
+    /**
+     * Indication that the media keys of a remote device arrived for the
+     * first time, so its media can now be decrypted. Rendering its video
+     * can wait until then to avoid showing undecryptable frames.
+     */
+    func groupCall(onMediaKeysReceived groupCall: GroupCall, remoteDemuxId: UInt32)
+
+    /**
+     * Indication that ICE selected a candidate pair for the connection to the
+     * server, with the local demux ID. Fired again whenever ICE switches to
+     * another pair, so a .relay type tells that media now goes through TURN.
+     */
+    func groupCall(onCandidatePairChanged groupCall: GroupCall, localDemuxId: UInt32, candidatePairType: CandidatePairType)
+
+    /**
+     * Indication that the server turned down the request to join, with the
+     * HTTP status (e.g. 403 when the user isn't a member of the call) and the
+     * server's message, if any. Fired before onEnded.
+     */
+    func groupCall(onJoinRejected groupCall: GroupCall, httpStatus: UInt16, message: String?)
+
+    /**
+     * Indication that a remote device started sending interpreter audio,
+     * which is kept apart from its main audio and stays silent until
+     * setRemoteInterpreterAudioEnabled() turns it on. Only fired after
+     * setInterpreterAudioEnabled(true) or setInterpreterAudioSource().
+     */
+    func groupCall(onIncomingInterpreterAudio groupCall: GroupCall, remoteDemuxId: UInt32)
+
     /**
      * Indication that group call ended due to a reason other than the user choosing
      * to disconnect from it.
@@ -152,6 +225,18 @@ public protocol GroupCallDelegate: class {
     func groupCall(onEnded groupCall: GroupCall, reason: GroupCallEndReason)
 }
 
+/// Defaults for the optional indications, so delegates that don't use
+/// them need not implement them.
+public extension GroupCallDelegate {
+    func groupCall(onParticipantQualityChanged groupCall: GroupCall, remoteDemuxId: UInt32, quality: UInt8) {}
+    func groupCall(onEraChanged groupCall: GroupCall, oldEraId: String?, newEraId: String?) {}
+    func groupCall(onAudioLevels groupCall: GroupCall, audioLevels: [UInt32: UInt8]) {}
+    func groupCall(onMediaKeysReceived groupCall: GroupCall, remoteDemuxId: UInt32) {}
+    func groupCall(onCandidatePairChanged groupCall: GroupCall, localDemuxId: UInt32, candidatePairType: CandidatePairType) {}
+    func groupCall(onJoinRejected groupCall: GroupCall, httpStatus: UInt16, message: String?) {}
+    func groupCall(onIncomingInterpreterAudio groupCall: GroupCall, remoteDemuxId: UInt32) {}
+}
+
 public class GroupCall {
     let ringRtcCallManager: UnsafeMutableRawPointer
     let factory: RTCPeerConnectionFactory
@@ -169,10 +254,13 @@ public class GroupCall {
     public private(set) var localDeviceState: LocalDeviceState
     public private(set) var remoteDeviceStates: [UInt32: RemoteDeviceState]
     public private(set) var peekInfo: PeekInfo?
+    /// Set if the server turned down the last request to join, before onEnded.
+    public private(set) var joinRejection: JoinRejection?
 
     let videoCaptureController: VideoCaptureController
     var audioTrack: RTCAudioTrack?
     var videoTrack: RTCVideoTrack?
+    var interpreterAudioTrack: RTCAudioTrack?
 
     internal init(ringRtcCallManager: UnsafeMutableRawPointer, factory: RTCPeerConnectionFactory, groupCallByClientId: GroupCallByClientId, groupId: Data, sfuUrl: String, videoCaptureController: VideoCaptureController) {
         AssertIsOnMainThread()
@@ -205,6 +293,7 @@ public class GroupCall {
 
         if self.clientId == nil {
             // There is no RingRTC instance yet or anymore, so create it.
+            self.joinRejection = nil
 
             let groupIdSlice = allocatedAppByteSliceFromData(maybe_data: self.groupId)
             let sfuUrlSlice = allocatedAppByteSliceFromString(maybe_string: self.sfuUrl)
@@ -292,6 +381,7 @@ public class GroupCall {
         // When leaving, make sure outgoing media is stopped as soon as possible.
         self.audioTrack?.isEnabled = false
         self.videoTrack?.isEnabled = false
+        self.interpreterAudioTrack?.isEnabled = false
 
         ringrtcLeave(self.ringRtcCallManager, clientId)
     }
@@ -308,6 +398,7 @@ public class GroupCall {
         // When disconnecting, make sure outgoing media is stopped as soon as possible.
         self.audioTrack?.isEnabled = false
         self.videoTrack?.isEnabled = false
+        self.interpreterAudioTrack?.isEnabled = false
 
         ringrtcDisconnect(self.ringRtcCallManager, clientId)
     }
@@ -368,6 +459,58 @@ public class GroupCall {
         ringrtcResendMediaKeys(self.ringRtcCallManager, clientId)
     }
 
+    /// Turns interpreter audio on or off. While off (the default), the
+    /// interpreter audio of remote devices isn't received at all.
+    public func setInterpreterAudioEnabled(_ enabled: Bool) {
+        AssertIsOnMainThread()
+        Logger.debug("setInterpreterAudioEnabled")
+
+        guard let clientId = self.clientId else {
+            Logger.warn("no clientId defined for groupCall")
+            return
+        }
+
+        ringrtcSetInterpreterAudioEnabled(self.ringRtcCallManager, clientId, enabled)
+    }
+
+    /// Sends the given source, such as an interpreter's microphone, as a
+    /// second audio track alongside the main one. Turns on interpreter audio.
+    /// Can only be done once per call.
+    public func setInterpreterAudioSource(_ audioSource: RTCAudioSource) {
+        AssertIsOnMainThread()
+        Logger.debug("setInterpreterAudioSource")
+
+        guard let clientId = self.clientId else {
+            Logger.warn("no clientId defined for groupCall")
+            return
+        }
+
+        guard self.interpreterAudioTrack == nil else {
+            Logger.warn("interpreter audio source was already set")
+            return
+        }
+
+        // Note: This must stay "interpreter1" to stay in sync with CreateSessionDescriptionForGroupCall.
+        let interpreterAudioTrack = self.factory.audioTrack(with: audioSource, trackId: "interpreter1")
+        self.interpreterAudioTrack = interpreterAudioTrack
+
+        ringrtcSetInterpreterTrack(self.ringRtcCallManager, clientId, interpreterAudioTrack.getNativeAudioTrack())
+    }
+
+    /// Plays or silences the interpreter audio of a remote device. See
+    /// GroupCallDelegate.groupCall(onIncomingInterpreterAudio:remoteDemuxId:).
+    public func setRemoteInterpreterAudioEnabled(remoteDemuxId: UInt32, enabled: Bool) {
+        AssertIsOnMainThread()
+        Logger.debug("setRemoteInterpreterAudioEnabled")
+
+        guard let clientId = self.clientId else {
+            Logger.warn("no clientId defined for groupCall")
+            return
+        }
+
+        ringrtcSetRemoteInterpreterAudioEnabled(self.ringRtcCallManager, clientId, remoteDemuxId, enabled)
+    }
+
     public func updateBandwidthMode(bandwidthMode: BandwidthMode) {
         AssertIsOnMainThread()
         Logger.debug("updateBandwidthMode")
@@ -397,7 +540,7 @@ public class GroupCall {
                 appFramerate = AppOptionalUInt16(value: 0, valid: false)
             }
 
-            return AppVideoRequest(demux_id: resolution.demuxId, width: resolution.width, height: resolution.height, framerate: appFramerate)
+            return AppVideoRequest(demux_id: resolution.demuxId, width: resolution.width, height: resolution.height, framerate: appFramerate, pinned: resolution.pinned)
         }
 
         var appResolutionArray = appResolutions.withUnsafeBufferPointer { appResolutionBytes in
@@ -495,10 +638,16 @@ public class GroupCall {
         self.delegate?.groupCall(onLocalDeviceStateChanged: self)
     }
 
-    func handleJoinStateChanged(joinState: JoinState) {
+    func handleJoinStateChanged(joinState: JoinState, outgoingAudioMuted: Bool?) {
        AssertIsOnMainThread()
 
        self.localDeviceState.joinState = joinState
+       self.localDeviceState.audioMuted = outgoingAudioMuted
+       if let outgoingAudioMuted = outgoingAudioMuted {
+           // Keep the track in step when RingRTC muted it while joining.
+           _isOutgoingAudioMuted = outgoingAudioMuted
+           self.audioTrack?.isEnabled = !outgoingAudioMuted
+       }
 
        self.delegate?.groupCall(onLocalDeviceStateChanged: self)
     }
@@ -551,6 +700,50 @@ public class GroupCall {
         self.delegate?.groupCall(onPeekChanged: self)
     }
 
+    func handleEraChanged(oldEraId: String?, newEraId: String?) {
+        AssertIsOnMainThread()
+
+        self.delegate?.groupCall(onEraChanged: self, oldEraId: oldEraId, newEraId: newEraId)
+    }
+
+    func handleParticipantQualityChanged(remoteDemuxId: UInt32, quality: UInt8) {
+        AssertIsOnMainThread()
+
+        self.delegate?.groupCall(onParticipantQualityChanged: self, remoteDemuxId: remoteDemuxId, quality: quality)
+    }
+
+    func handleAudioLevels(audioLevels: [UInt32: UInt8]) {
+        AssertIsOnMainThread()
+
+        self.delegate?.groupCall(onAudioLevels: self, audioLevels: audioLevels)
+    }
+
+    func handleMediaKeysReceived(remoteDemuxId: UInt32) {
+        AssertIsOnMainThread()
+
+        self.delegate?.groupCall(onMediaKeysReceived: self, remoteDemuxId: remoteDemuxId)
+    }
+
+    func handleIncomingInterpreterAudioTrack(remoteDemuxId: UInt32) {
+        AssertIsOnMainThread()
+        Logger.debug("handleIncomingInterpreterAudioTrack() for remoteDemuxId: \(remoteDemuxId)")
+
+        self.delegate?.groupCall(onIncomingInterpreterAudio: self, remoteDemuxId: remoteDemuxId)
+    }
+
+    func handleCandidatePairChanged(localDemuxId: UInt32, candidatePairType: CandidatePairType) {
+        AssertIsOnMainThread()
+
+        self.delegate?.groupCall(onCandidatePairChanged: self, localDemuxId: localDemuxId, candidatePairType: candidatePairType)
+    }
+
+    func handleJoinRejected(httpStatus: UInt16, message: String?) {
+        AssertIsOnMainThread()
+
+        self.joinRejection = JoinRejection(httpStatus: httpStatus, message: message)
+        self.delegate?.groupCall(onJoinRejected: self, httpStatus: httpStatus, message: message)
+    }
+
     func handleEnded(reason: GroupCallEndReason) {
         AssertIsOnMainThread()
 
@@ -570,6 +763,7 @@ public class GroupCall {
         self.peekInfo = nil
         self.audioTrack = nil
         self.videoTrack = nil
+        self.interpreterAudioTrack = nil
 
         ringrtcDeleteGroupCallClient(self.ringRtcCallManager, clientId)
 
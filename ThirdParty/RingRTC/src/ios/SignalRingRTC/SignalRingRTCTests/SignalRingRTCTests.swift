@@ -64,6 +64,9 @@ final class TestDelegate: CallManagerDelegate {
     var shouldSendHangupBusyInvoked = false
     var shouldSendHangupNeedPermissionInvoked = false
     var shouldSendBusyInvoked = false
+    var shouldSendTransferRequestInvoked = false
+    var shouldSendTransferAcceptedInvoked = false
+    var onTransferRequestInvoked = false
     var shouldSendCallMessageInvoked = false
     var shouldSendHttpRequestInvoked = false
     var shouldCompareCallsInvoked = false
@@ -281,6 +284,22 @@ final class TestDelegate: CallManagerDelegate {
         case .ignoreCallsFromNonMultiringCallers:
             Logger.debug("TestDelegate:ignoreCallsFromNonMultiringCallers")
             eventIgnoreCallsFromNonMultiringCallers = true
+
+        case .localHold:
+            Logger.debug("TestDelegate:localHold")
+        case .localResume:
+            Logger.debug("TestDelegate:localResume")
+        case .receivedOfferWithUnsupportedVersion:
+            Logger.debug("TestDelegate:receivedOfferWithUnsupportedVersion")
+        case .endedTransferred:
+            Logger.debug("TestDelegate:endedTransferred")
+            eventGeneralEnded = true
+        case .endedRemoteAway:
+            Logger.debug("TestDelegate:endedRemoteAway")
+            eventGeneralEnded = true
+        case .endedNetworkChanged:
+            Logger.debug("TestDelegate:endedNetworkChanged")
+            eventGeneralEnded = true
         }
     }
 
@@ -493,6 +512,47 @@ final class TestDelegate: CallManagerDelegate {
         }
     }
 
+    func callManager(_ callManager: CallManager<OpaqueCallData, TestDelegate>, shouldSendTransferRequest callId: UInt64, call: OpaqueCallData, destinationDeviceId: UInt32?, target: OpaqueCallData) {
+        Logger.debug("TestDelegate:shouldSendTransferRequest")
+        generalInvocationDetected = true
+
+        recentCallId = callId
+
+        DispatchQueue.main.async {
+            self.shouldSendTransferRequestInvoked = true
+
+            do {
+                try callManager.signalingMessageDidSend(callId: callId)
+            } catch {
+                // @todo
+            }
+        }
+    }
+
+    func callManager(_ callManager: CallManager<OpaqueCallData, TestDelegate>, shouldSendTransferAccepted callId: UInt64, call: OpaqueCallData, destinationDeviceId: UInt32?) {
+        Logger.debug("TestDelegate:shouldSendTransferAccepted")
+        generalInvocationDetected = true
+
+        recentCallId = callId
+
+        DispatchQueue.main.async {
+            self.shouldSendTransferAcceptedInvoked = true
+
+            do {
+                try callManager.signalingMessageDidSend(callId: callId)
+            } catch {
+                // @todo
+            }
+        }
+    }
+
+    func callManager(_ callManager: CallManager<OpaqueCallData, TestDelegate>, onTransferRequest call: OpaqueCallData, target: OpaqueCallData) {
+        Logger.debug("TestDelegate:onTransferRequest")
+        generalInvocationDetected = true
+
+        onTransferRequestInvoked = true
+    }
+
     func callManager(_ callManager: CallManager<OpaqueCallData, TestDelegate>, shouldSendCallMessage recipientUuid: UUID, message: Data) {
         Logger.debug("TestDelegate:shouldSendCallMessage")
         generalInvocationDetected = true
@@ -0,0 +1,41 @@
+/*
+ * Copyright 2021 Signal Messenger, LLC
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+#ifndef RFFI_API_AUDIO_LOOPBACK_H__
+#define RFFI_API_AUDIO_LOOPBACK_H__
+
+#include "rffi/api/rffi_defs.h"
+
+/**
+ * Rust friendly wrapper for playing captured audio straight back out,
+ * without any PeerConnection, so users can check their microphone and
+ * speaker before a call.
+ *
+ */
+
+namespace webrtc {
+namespace rffi {
+  class AudioLoopback;
+} // namespace rffi
+} // namespace webrtc
+
+/* Audio Loopback callback function pointers */
+typedef struct {
+  // The peak level of the captured audio, from 0 to 32767, roughly every 100ms.
+  void (*onAudioLevel)(rust_object, uint16_t level);
+} AudioLoopbackCallbacks;
+
+// Opens the default audio devices and starts playing out what is recorded.
+// Returns nullptr if the devices couldn't be started.
+RUSTEXPORT webrtc::rffi::AudioLoopback*
+Rust_startAudioLoopback(rust_object                   observer,
+                        const AudioLoopbackCallbacks* callbacks);
+
+// Stops the audio devices and deletes the loopback.  No callbacks are made
+// once this returns.
+RUSTEXPORT void
+Rust_stopAudioLoopback(webrtc::rffi::AudioLoopback* loopback);
+
+#endif /* RFFI_API_AUDIO_LOOPBACK_H__ */
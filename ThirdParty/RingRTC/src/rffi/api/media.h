@@ -57,10 +57,17 @@ class VideoSource : public VideoTrackSource {
   rtc::VideoBroadcaster broadcaster_;
 };
 
+// Remote interpreter audio tracks in group calls have IDs of the form
+// "<demux ID>-interpreter", while the main audio track of the same
+// remote device is just "<demux ID>".
+const char INTERPRETER_AUDIO_TRACK_ID_SUFFIX[] = "-interpreter";
+
+bool IsInterpreterAudioTrackId(const std::string& track_id);
+
 } // namespace rffi
 } // namespace webrtc
 
-// Parses track->id()
+// Parses track->id(), ignoring the suffix of interpreter audio tracks.
 // Returns 0 upon failure
 RUSTEXPORT uint32_t Rust_getTrackIdAsUint32(webrtc::MediaStreamTrackInterface* track);
 
@@ -37,6 +37,7 @@ namespace webrtc {
     virtual int16_t AudioRecordingDevices() = 0;
     virtual int32_t AudioRecordingDeviceName(uint16_t index, char *out_name, char *out_uuid) = 0;
     virtual bool SetAudioRecordingDevice(uint16_t index) = 0;
+    virtual void ConfigureAudioProcessing(bool aec, bool ns, bool agc) = 0;
   };
 
   namespace rffi {
@@ -51,6 +52,13 @@ typedef struct {
   size_t urls_size;
 } RffiIceServer;
 
+// Which processing is applied to the outgoing audio.
+typedef struct {
+  bool aec;
+  bool ns;
+  bool agc;
+} RffiAudioProcessingConfig;
+
 // Technically creates a PeerConnectionFactoryOwner, but if you only use the
 // functions below, that won't matter to you.
 // You can create more than one, but you should probably only have one unless
@@ -66,12 +74,14 @@ RUSTEXPORT webrtc::PeerConnectionInterface* Rust_createPeerConnection(
   webrtc::rffi::PeerConnectionObserverRffi*,
   rtc::RTCCertificate* certificate,
   bool hide_ip,
-  RffiIceServer ice_server,
+  const RffiIceServer* ice_servers,
+  size_t ice_servers_size,
   webrtc::AudioTrackInterface*,
   webrtc::VideoTrackInterface*,
   bool enable_dtls,
   bool enable_rtp_data_channel);
 RUSTEXPORT webrtc::AudioTrackInterface* Rust_createAudioTrack(webrtc::PeerConnectionFactoryOwner*);
+RUSTEXPORT void Rust_configureAudioProcessing(webrtc::PeerConnectionFactoryOwner*, const RffiAudioProcessingConfig* config);
 RUSTEXPORT webrtc::VideoTrackSourceInterface* Rust_createVideoSource(webrtc::PeerConnectionFactoryOwner*);
 RUSTEXPORT webrtc::VideoTrackInterface* Rust_createVideoTrack(webrtc::PeerConnectionFactoryOwner*, webrtc::VideoTrackSourceInterface* source);
 RUSTEXPORT int16_t Rust_getAudioPlayoutDevices(webrtc::PeerConnectionFactoryOwner*);
@@ -11,6 +11,12 @@
 #include "rffi/api/sdp_observer_intf.h"
 #include "rffi/api/stats_observer_intf.h"
 
+namespace webrtc {
+  namespace rffi {
+    class PeerConnectionObserverRffi;
+  }
+}
+
 /**
  * Rust friendly wrapper around some webrtc::PeerConnectionInterface
  * methods
@@ -53,6 +59,26 @@ typedef struct {
   uint32_t level;
 } RffiVideoCodec;
 
+// Upper bounds on outgoing video.  0 means no bound.
+typedef struct {
+  uint32_t max_width;
+  uint32_t max_height;
+  uint32_t max_fps;
+} RffiVideoEncoderCaps;
+
+// One simulcast layer of the outgoing video, lowest first.
+// The optional values are 0 when unset.
+typedef struct {
+  uint32_t ssrc;
+  bool active;
+  uint32_t width;
+  uint32_t height;
+  double scale_resolution_down_by;
+  uint32_t max_framerate;
+  uint32_t max_bitrate_bps;
+  uint32_t num_temporal_layers;
+} RffiLayerConfig;
+
 class ConnectionParametersV4 {
  public:
   std::string ice_ufrag;
@@ -94,10 +120,25 @@ RUSTEXPORT void
 Rust_setOutgoingMediaEnabled(webrtc::PeerConnectionInterface* peer_connection,
                              bool                             enabled);
 
+RUSTEXPORT void
+Rust_setOutgoingAudioEnabled(webrtc::PeerConnectionInterface* peer_connection,
+                             bool                             enabled);
+
 RUSTEXPORT bool
 Rust_setIncomingMediaEnabled(webrtc::PeerConnectionInterface* peer_connection,
                              bool                             enabled);
 
+RUSTEXPORT void
+Rust_setAudioJitterBufferMinDelay(webrtc::PeerConnectionInterface* peer_connection,
+                                  uint32_t                         min_delay_ms);
+
+// Adds a second outgoing audio track carrying an interpreter channel.
+// The track ID must be "interpreter1".
+RUSTEXPORT bool
+Rust_addInterpreterAudioTrack(webrtc::PeerConnectionInterface*           peer_connection,
+                              webrtc::rffi::PeerConnectionObserverRffi* pc_observer,
+                              webrtc::AudioTrackInterface*               track);
+
 /*
  * NOTE: The object created with Rust_createSignalingDataChannel() must be
  * freed using Rust_releaseRef().
@@ -152,4 +193,26 @@ Rust_receiveRtp(webrtc::PeerConnectionInterface* peer_connection, uint8_t pt);
 RUSTEXPORT void
 Rust_configureAudioEncoders(webrtc::PeerConnectionInterface* peer_connection, const webrtc::AudioEncoder::Config* config);
 
+RUSTEXPORT void
+Rust_setOutgoingVideoCaps(webrtc::PeerConnectionInterface* peer_connection, const RffiVideoEncoderCaps* caps);
+
+// Returns the number of layers, of which at most layers_capacity are written.
+RUSTEXPORT size_t
+Rust_getOutgoingVideoLayers(webrtc::PeerConnectionInterface* peer_connection,
+                            RffiLayerConfig*                  layers_out,
+                            size_t                            layers_capacity);
+
+// Returns the SRTP crypto suite negotiated by DTLS (such as
+// rtc::SRTP_AEAD_AES_256_GCM), or 0 if there isn't one yet.
+RUSTEXPORT int
+Rust_getSrtpCipherSuite(webrtc::PeerConnectionInterface* peer_connection);
+
+// Queues the tones to be sent as RFC 4733 telephone-events on the audio
+// sender. Returns false if there is no audio sender able to send them.
+RUSTEXPORT bool
+Rust_insertDtmf(webrtc::PeerConnectionInterface* peer_connection,
+                const char*                       tones,
+                int                               duration_ms,
+                int                               gap_ms);
+
 #endif /* RFFI_API_PEER_CONNECTION_INTF_H__ */
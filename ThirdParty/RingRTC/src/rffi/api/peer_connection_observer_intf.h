@@ -26,11 +26,19 @@ typedef struct {
   // ICE events
   void (*onIceCandidate)(rust_object, const RustIceCandidate*);
   void (*onIceConnectionChange)(rust_object, webrtc::PeerConnectionInterface::IceConnectionState);
+  void (*onIceCandidateError)(rust_object, const char* url, uint16_t error_code, const char* error_text);
+  void (*onIceGatheringComplete)(rust_object);
+  // The value is an rtc::AdapterType.
+  void (*onIceNetworkRouteChanged)(rust_object, int32_t local_adapter_type);
+  // 0 for host, 1 for server reflexive, 2 for peer reflexive, 3 for relayed.
+  void (*onIceCandidatePairChanged)(rust_object, int32_t candidate_pair_type);
 
   // Media events
   void (*onAddStream)(rust_object, webrtc::MediaStreamInterface*);
   void (*onAddAudioRtpReceiver)(rust_object, webrtc::MediaStreamTrackInterface*);
   void (*onAddVideoRtpReceiver)(rust_object, webrtc::MediaStreamTrackInterface*);
+  void (*onAddInterpreterAudioRtpReceiver)(rust_object, webrtc::MediaStreamTrackInterface*);
+  void (*onOutgoingKeyFrameEncoded)(rust_object);
 
   // Data Channel events
   void (*onSignalingDataChannel)(rust_object, webrtc::DataChannelInterface*);
@@ -20,6 +20,9 @@ typedef void* rust_object;
 /* Ice Update Message structure passed between Rust and c++ */
 typedef struct {
   const char* sdp;
+  // Name of the local network interface the candidate was gathered on.
+  const char* network_name;
+  bool is_loopback;
 } RustIceCandidate;
 
 #endif /* RFFI_API_DEFS_H__ */
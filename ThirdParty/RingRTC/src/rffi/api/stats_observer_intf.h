@@ -7,6 +7,7 @@
 #define RFFI_API_STATS_OBSERVER_INTF_H__
 
 #include "api/peer_connection_interface.h"
+#include "rffi/api/network.h"
 #include "rffi/api/rffi_defs.h"
 
 /**
@@ -30,6 +31,8 @@ typedef struct {
   double remote_round_trip_time;
   double audio_level;
   double total_audio_energy;
+  uint64_t retransmitted_packets_sent;
+  uint32_t nack_count;
 } AudioSenderStatistics;
 
 typedef struct {
@@ -64,6 +67,15 @@ typedef struct {
     double total_decode_time;
     double audio_level;
     double total_audio_energy;
+    uint32_t nack_count;
+    // Playout (NetEq) counters, cumulative like the rest.
+    double jitter_buffer_delay;
+    uint64_t jitter_buffer_emitted_count;
+    uint64_t total_samples_received;
+    uint64_t concealed_samples;
+    uint64_t concealment_events;
+    uint64_t inserted_samples_for_deceleration;
+    uint64_t removed_samples_for_acceleration;
 } AudioReceiverStatistics;
 
 typedef struct {
@@ -77,8 +89,19 @@ typedef struct {
     double total_decode_time;
     uint32_t frame_width;
     uint32_t frame_height;
+    uint32_t nack_count;
+    uint32_t fir_count;
+    uint32_t pli_count;
 } VideoReceiverStatistics;
 
+typedef struct {
+    double current_round_trip_time;
+    // 0 unknown, 1 host, 2 srflx, 3 prflx, 4 relay
+    int32_t local_candidate_type;
+    int32_t remote_candidate_type;
+    webrtc::rffi::IpPort remote_address;
+} ConnectionStatistics;
+
 typedef struct {
     int64_t timestamp_us;
     uint32_t audio_sender_statistics_size;
@@ -89,6 +112,9 @@ typedef struct {
     const AudioReceiverStatistics *audio_receiver_statistics;
     uint32_t video_receiver_statistics_count;
     const VideoReceiverStatistics *video_receiver_statistics;
+    // Stats of the selected ICE candidate pair, if there is one.
+    uint32_t connection_statistics_size;
+    const ConnectionStatistics *connection_statistics;
 } MediaStatistics;
 
 /* Stats Observer Callback callback function pointers */
@@ -0,0 +1,193 @@
+/*
+ * Copyright 2021 Signal Messenger, LLC
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+#include "rffi/api/audio_loopback.h"
+
+#include <algorithm>
+#include <cstdlib>
+#include <deque>
+
+#include "api/task_queue/default_task_queue_factory.h"
+#include "modules/audio_device/include/audio_device.h"
+#include "rtc_base/logging.h"
+#include "rtc_base/synchronization/mutex.h"
+#include "rtc_base/thread.h"
+
+namespace webrtc {
+namespace rffi {
+
+// Plays out recorded audio after a short delay.  Recording and playout
+// run on the audio device's own threads; everything else on worker_thread_.
+class AudioLoopback : public AudioTransport {
+ public:
+  AudioLoopback(rust_object observer, const AudioLoopbackCallbacks* callbacks)
+    : observer_(observer), callbacks_(*callbacks) {}
+
+  ~AudioLoopback() override {
+    RTC_LOG(LS_INFO) << "AudioLoopback:dtor()";
+  }
+
+  bool Start() {
+    worker_thread_ = rtc::Thread::Create();
+    worker_thread_->SetName("Audio-Loopback-Thread", nullptr);
+    worker_thread_->Start();
+
+    // Like the audio device module of the PeerConnectionFactory, this one
+    // must be created and destroyed on its worker thread.
+    return worker_thread_->Invoke<bool>(RTC_FROM_HERE, [&]() {
+      task_queue_factory_ = CreateDefaultTaskQueueFactory();
+      adm_ = AudioDeviceModule::Create(
+        AudioDeviceModule::kPlatformDefaultAudio, task_queue_factory_.get());
+      if (!adm_ || adm_->Init() != 0) {
+        RTC_LOG(LS_ERROR) << "AudioLoopback: failed to initialize the audio device module";
+        return false;
+      }
+      if (adm_->RegisterAudioCallback(this) != 0 ||
+          adm_->InitRecording() != 0 ||
+          adm_->InitPlayout() != 0 ||
+          adm_->StartRecording() != 0 ||
+          adm_->StartPlayout() != 0) {
+        RTC_LOG(LS_ERROR) << "AudioLoopback: failed to start the audio devices";
+        StopOnWorkerThread();
+        return false;
+      }
+      return true;
+    });
+  }
+
+  void Stop() {
+    worker_thread_->Invoke<void>(RTC_FROM_HERE, [&]() {
+      StopOnWorkerThread();
+    });
+    worker_thread_->Stop();
+  }
+
+  int32_t RecordedDataIsAvailable(const void* audio_samples,
+                                  const size_t samples_per_channel,
+                                  const size_t bytes_per_sample,
+                                  const size_t channels,
+                                  const uint32_t samples_per_sec,
+                                  const uint32_t total_delay_ms,
+                                  const int32_t clock_drift,
+                                  const uint32_t current_mic_level,
+                                  const bool key_pressed,
+                                  uint32_t& new_mic_level) override {
+    const int16_t* samples = static_cast<const int16_t*>(audio_samples);
+    int16_t peak = 0;
+
+    {
+      MutexLock lock(&mutex_);
+      recorded_samples_per_sec_ = samples_per_sec;
+      for (size_t i = 0; i < samples_per_channel; i++) {
+        // Only the first channel is played back.
+        int16_t sample = samples[i * channels];
+        buffer_.push_back(sample);
+        peak = std::max<int16_t>(peak, sample == INT16_MIN ? INT16_MAX : std::abs(sample));
+      }
+      // Don't let the delay grow if playout is slower than recording.
+      size_t max_buffered = samples_per_sec * kMaxBufferedMs / 1000;
+      while (buffer_.size() > max_buffered) {
+        buffer_.pop_front();
+      }
+    }
+
+    peak_level_ = std::max(peak_level_, peak);
+    if (++frames_since_level_ >= kFramesPerLevel) {
+      callbacks_.onAudioLevel(observer_, peak_level_);
+      peak_level_ = 0;
+      frames_since_level_ = 0;
+    }
+    new_mic_level = current_mic_level;
+    return 0;
+  }
+
+  int32_t NeedMorePlayData(const size_t samples_per_channel,
+                           const size_t bytes_per_sample,
+                           const size_t channels,
+                           const uint32_t samples_per_sec,
+                           void* audio_samples,
+                           size_t& samples_per_channel_out,
+                           int64_t* elapsed_time_ms,
+                           int64_t* ntp_time_ms) override {
+    int16_t* samples = static_cast<int16_t*>(audio_samples);
+
+    MutexLock lock(&mutex_);
+    for (size_t i = 0; i < samples_per_channel; i++) {
+      // Silence until there is enough to play, or if the devices disagree on
+      // the sample rate (which would need resampling).
+      int16_t sample = 0;
+      if (samples_per_sec == recorded_samples_per_sec_ && !buffer_.empty()) {
+        sample = buffer_.front();
+        buffer_.pop_front();
+      }
+      for (size_t channel = 0; channel < channels; channel++) {
+        samples[i * channels + channel] = sample;
+      }
+    }
+    samples_per_channel_out = samples_per_channel;
+    *elapsed_time_ms = -1;
+    *ntp_time_ms = -1;
+    return 0;
+  }
+
+  void PullRenderData(int bits_per_sample,
+                      int sample_rate,
+                      size_t number_of_channels,
+                      size_t number_of_frames,
+                      void* audio_data,
+                      int64_t* elapsed_time_ms,
+                      int64_t* ntp_time_ms) override {
+    // Only used by Chromium's WebRTC audio renderer.
+  }
+
+ private:
+  // 10ms frames, so the level is reported every 100ms.
+  static const int kFramesPerLevel = 10;
+  static const size_t kMaxBufferedMs = 500;
+
+  void StopOnWorkerThread() {
+    if (adm_) {
+      adm_->StopRecording();
+      adm_->StopPlayout();
+      adm_->RegisterAudioCallback(nullptr);
+      adm_->Terminate();
+      adm_ = nullptr;
+    }
+    task_queue_factory_ = nullptr;
+  }
+
+  const rust_object observer_;
+  const AudioLoopbackCallbacks callbacks_;
+  std::unique_ptr<rtc::Thread> worker_thread_;
+  std::unique_ptr<TaskQueueFactory> task_queue_factory_;
+  rtc::scoped_refptr<AudioDeviceModule> adm_;
+
+  Mutex mutex_;
+  std::deque<int16_t> buffer_ RTC_GUARDED_BY(mutex_);
+  uint32_t recorded_samples_per_sec_ RTC_GUARDED_BY(mutex_) = 0;
+  // Only touched on the recording thread.
+  int16_t peak_level_ = 0;
+  int frames_since_level_ = 0;
+};
+
+RUSTEXPORT AudioLoopback*
+Rust_startAudioLoopback(rust_object                   observer,
+                        const AudioLoopbackCallbacks* callbacks) {
+  auto loopback = std::make_unique<AudioLoopback>(observer, callbacks);
+  if (!loopback->Start()) {
+    loopback->Stop();
+    return nullptr;
+  }
+  return loopback.release();
+}
+
+RUSTEXPORT void
+Rust_stopAudioLoopback(AudioLoopback* loopback) {
+  loopback->Stop();
+  delete loopback;
+}
+
+} // namespace rffi
+} // namespace webrtc
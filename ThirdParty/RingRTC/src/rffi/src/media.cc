@@ -52,10 +52,20 @@ void VideoSource::PushVideoFrame(const webrtc::VideoFrame& frame) {
   broadcaster_.OnFrame(frame);
 }
 
+bool IsInterpreterAudioTrackId(const std::string& track_id) {
+  std::string suffix = INTERPRETER_AUDIO_TRACK_ID_SUFFIX;
+  return track_id.size() > suffix.size() &&
+         track_id.compare(track_id.size() - suffix.size(), suffix.size(), suffix) == 0;
+}
+
 // Returns 0 upon failure
 RUSTEXPORT uint32_t Rust_getTrackIdAsUint32(webrtc::MediaStreamTrackInterface* track) {
   uint32_t id = 0;
-  rtc::FromString(track->id(), &id);
+  std::string track_id = track->id();
+  if (IsInterpreterAudioTrackId(track_id)) {
+    track_id.resize(track_id.size() - strlen(INTERPRETER_AUDIO_TRACK_ID_SUFFIX));
+  }
+  rtc::FromString(track_id, &id);
   return id;
 }
 
@@ -15,13 +15,16 @@
 #include "pc/sdp_utils.h"
 #include "pc/session_description.h"
 #include "sdk/media_constraints.h"
+#include "rffi/api/media.h"
 #include "rffi/api/peer_connection_intf.h"
+#include "rffi/src/peer_connection_observer.h"
 #include "rffi/src/sdp_observer.h"
 #include "rffi/src/stats_observer.h"
 #include "rtc_base/message_digest.h"
 #include "rtc_base/string_encode.h"
 #include "rtc_base/third_party/base64/base64.h"
 
+#include <algorithm>
 #include <string>
 
 namespace webrtc {
@@ -233,7 +236,7 @@ Rust_releaseV4(RffiConnectionParametersV4* v4) {
 RUSTEXPORT webrtc::SessionDescriptionInterface*
 Rust_sessionDescriptionFromV4(bool offer, const RffiConnectionParametersV4* v4) {
   // Major changes from the default WebRTC behavior:
-  // 1. We remove all codecs except Opus, VP8, and H264
+  // 1. We remove all codecs except Opus, telephone-event, VP8, and H264
   // 2. We remove all header extensions except for transport-cc, video orientation,
   //    abs send time, and timestamp offset.
   // 3. Opus CBR is enabled.
@@ -248,6 +251,9 @@ Rust_sessionDescriptionFromV4(bool offer, const RffiConnectionParametersV4* v4)
   // Payload types must be over 96 and less than 128.
   int DATA_PT = 101;
   int OPUS_PT = 102;
+  // This must stay in sync with kTelephoneEventPayloadType and
+  // TELEPHONE_EVENT_PAYLOAD_TYPE in connection.rs.
+  int TELEPHONE_EVENT_PT = 110;
   int VP8_PT = 108;
   int VP8_RTX_PT = 118;
   int H264_CHP_PT = 104;
@@ -318,6 +324,11 @@ Rust_sessionDescriptionFromV4(bool offer, const RffiConnectionParametersV4* v4)
   opus.AddFeedbackParam(cricket::FeedbackParam(cricket::kRtcpFbParamTransportCc, cricket::kParamValueEmpty));
   audio->AddCodec(opus);
 
+  // For DTMF (RFC 4733).  It has the same clock rate as Opus so the tones
+  // can be sent in the middle of the audio.
+  auto telephone_event = cricket::AudioCodec(TELEPHONE_EVENT_PT, cricket::kDtmfCodecName, 48000, 0, 1);
+  audio->AddCodec(telephone_event);
+
   auto add_video_feedback_params = [] (cricket::VideoCodec* video_codec) {
     video_codec->AddFeedbackParam(cricket::FeedbackParam(cricket::kRtcpFbParamTransportCc, cricket::kParamValueEmpty));
     video_codec->AddFeedbackParam(cricket::FeedbackParam(cricket::kRtcpFbParamCcm, cricket::kRtcpFbCcmParamFir));
@@ -471,7 +482,8 @@ CreateSessionDescriptionForGroupCall(bool local,
                                      const std::string& ice_ufrag,
                                      const std::string& ice_pwd,
                                      std::unique_ptr<rtc::SSLFingerprint> dtls_fingerprint,
-                                     std::vector<uint32_t> rtp_demux_ids) {
+                                     std::vector<uint32_t> rtp_demux_ids,
+                                     bool interpreter_audio) {
   // Major changes from the default WebRTC behavior:
   // 1. We remove all codecs except Opus and VP8.
   // 2. We remove all header extensions except for transport-cc, video orientation,
@@ -498,6 +510,8 @@ CreateSessionDescriptionForGroupCall(bool local,
   std::string LOCAL_AUDIO_TRACK_ID = "audio1";
   // This must stay in sync with PeerConnectionFactory.createVideoTrack
   std::string LOCAL_VIDEO_TRACK_ID = "video1";
+  // This must stay in sync with GroupCall.setInterpreterAudioSource (for iOS).
+  std::string LOCAL_INTERPRETER_AUDIO_TRACK_ID = "interpreter1";
 
   auto transport = cricket::TransportDescription();
   transport.ice_mode = cricket::ICEMODE_FULL;
@@ -596,6 +610,7 @@ CreateSessionDescriptionForGroupCall(bool local,
     uint32_t video3_rtx_ssrc = rtp_demux_id + 7;
     // Leave room for some more video layers or FEC
     // uint32_t data_ssrc = rtp_demux_id + 0xD;  Used by group_call.rs
+    uint32_t interpreter_audio_ssrc = rtp_demux_id + 0xE;
 
     auto audio_stream = cricket::StreamParams();
 
@@ -608,6 +623,13 @@ CreateSessionDescriptionForGroupCall(bool local,
     audio_stream.id = local ? LOCAL_AUDIO_TRACK_ID : rtp_demux_id_str;
     audio_stream.add_ssrc(audio_ssrc);
 
+    // The interpreter audio is only described once the client has turned it on,
+    // so calls without an interpreter don't carry an extra SSRC per device.
+    // For remote, the track ID tells it apart from the main audio track.
+    auto interpreter_audio_stream = cricket::StreamParams();
+    interpreter_audio_stream.id = local ? LOCAL_INTERPRETER_AUDIO_TRACK_ID : rtp_demux_id_str + INTERPRETER_AUDIO_TRACK_ID_SUFFIX;
+    interpreter_audio_stream.add_ssrc(interpreter_audio_ssrc);
+
     auto video_stream = cricket::StreamParams();
     // For local, this should stay in sync with PeerConnectionFactory.createVideoSource
     // For remote, this will result in the remote video track/receiver's ID,
@@ -627,7 +649,7 @@ CreateSessionDescriptionForGroupCall(bool local,
     }
 
     // Things that are the same for all of them
-    for (auto* stream : {&audio_stream, &video_stream}) {
+    for (auto* stream : {&audio_stream, &interpreter_audio_stream, &video_stream}) {
       // WebRTC just generates a random 16-byte string for the entire PeerConnection.
       // It's used to send an SDES RTCP message.
       // The value doesn't seem to be used for anything else.
@@ -637,6 +659,9 @@ CreateSessionDescriptionForGroupCall(bool local,
     }
 
     audio->AddStream(audio_stream);
+    if (interpreter_audio) {
+      audio->AddStream(interpreter_audio_stream);
+    }
     video->AddStream(video_stream);
   }
 
@@ -675,7 +700,8 @@ RUSTEXPORT webrtc::SessionDescriptionInterface*
 Rust_localDescriptionForGroupCall(const char* ice_ufrag,
                                   const char* ice_pwd,
                                   const uint8_t dtls_fingerprint_sha256[32],
-                                  uint32_t rtp_demux_id) {
+                                  uint32_t rtp_demux_id,
+                                  bool interpreter_audio) {
   std::unique_ptr<rtc::SSLFingerprint> dtls_fingerprint = std::make_unique<rtc::SSLFingerprint>(
     rtc::DIGEST_SHA_256, rtc::ArrayView<const uint8_t>(dtls_fingerprint_sha256, 32));
   std::vector<uint32_t> rtp_demux_ids;
@@ -684,7 +710,7 @@ Rust_localDescriptionForGroupCall(const char* ice_ufrag,
     rtp_demux_ids.push_back(rtp_demux_id);
   }
   return CreateSessionDescriptionForGroupCall(
-    true /* local */, std::string(ice_ufrag), std::string(ice_pwd), std::move(dtls_fingerprint), rtp_demux_ids);
+    true /* local */, std::string(ice_ufrag), std::string(ice_pwd), std::move(dtls_fingerprint), rtp_demux_ids, interpreter_audio);
 }
 
 RUSTEXPORT webrtc::SessionDescriptionInterface*
@@ -692,13 +718,14 @@ Rust_remoteDescriptionForGroupCall(const char* ice_ufrag,
                                    const char* ice_pwd,
                                    const uint8_t dtls_fingerprint_sha256[32],
                                    uint32_t* rtp_demux_ids_data,
-                                   size_t rtp_demux_ids_len) {
+                                   size_t rtp_demux_ids_len,
+                                   bool interpreter_audio) {
   std::unique_ptr<rtc::SSLFingerprint> dtls_fingerprint = std::make_unique<rtc::SSLFingerprint>(
     rtc::DIGEST_SHA_256, rtc::ArrayView<const uint8_t>(dtls_fingerprint_sha256, 32));
   std::vector<uint32_t> rtp_demux_ids;
   rtp_demux_ids.assign(rtp_demux_ids_data, rtp_demux_ids_data + rtp_demux_ids_len);
   return CreateSessionDescriptionForGroupCall(
-    false /* local */, std::string(ice_ufrag), std::string(ice_pwd), std::move(dtls_fingerprint), rtp_demux_ids);
+    false /* local */, std::string(ice_ufrag), std::string(ice_pwd), std::move(dtls_fingerprint), rtp_demux_ids, interpreter_audio);
 }
 
 RUSTEXPORT void
@@ -743,6 +770,21 @@ Rust_setOutgoingMediaEnabled(PeerConnectionInterface* peer_connection,
   RTC_LOG(LS_INFO) << "Rust_setOutgoingMediaEnabled(" << enabled << ") for " << encodings_changed << " encodings.";
 }
 
+RUSTEXPORT void
+Rust_setOutgoingAudioEnabled(PeerConnectionInterface* peer_connection,
+                             bool                     enabled) {
+  // Unlike Rust_setOutgoingMediaEnabled, this only touches the audio track,
+  // so video keeps flowing while the microphone is muted.
+  int tracks_changed = 0;
+  for (auto& sender : peer_connection->GetSenders()) {
+    if (sender->media_type() == cricket::MEDIA_TYPE_AUDIO && sender->track()) {
+      sender->track()->set_enabled(enabled);
+      tracks_changed++;
+    }
+  }
+  RTC_LOG(LS_INFO) << "Rust_setOutgoingAudioEnabled(" << enabled << ") for " << tracks_changed << " tracks.";
+}
+
 RUSTEXPORT bool
 Rust_setIncomingMediaEnabled(PeerConnectionInterface* peer_connection,
                            bool                     enabled) {
@@ -750,6 +792,39 @@ Rust_setIncomingMediaEnabled(PeerConnectionInterface* peer_connection,
   return peer_connection->SetIncomingRtpEnabled(enabled);
 }
 
+RUSTEXPORT void
+Rust_setAudioJitterBufferMinDelay(PeerConnectionInterface* peer_connection,
+                                  uint32_t                 min_delay_ms) {
+  int receivers_changed = 0;
+  for (auto& receiver : peer_connection->GetReceivers()) {
+    if (receiver->media_type() == cricket::MEDIA_TYPE_AUDIO) {
+      receiver->SetJitterBufferMinimumDelay(min_delay_ms / 1000.0);
+      receivers_changed++;
+    }
+  }
+  RTC_LOG(LS_INFO) << "Rust_setAudioJitterBufferMinDelay(" << min_delay_ms << ") for " << receivers_changed << " receivers.";
+}
+
+RUSTEXPORT bool
+Rust_addInterpreterAudioTrack(PeerConnectionInterface*    peer_connection,
+                              PeerConnectionObserverRffi* pc_observer,
+                              AudioTrackInterface*        track) {
+  // Use the same stream as the main audio track (see Rust_createPeerConnection).
+  std::vector<std::string> stream_ids;
+  stream_ids.push_back("s");
+
+  auto result = peer_connection->AddTrack(track, stream_ids);
+  if (!result.ok()) {
+    RTC_LOG(LS_ERROR) << "Failed to PeerConnection::AddTrack(interpreter audio)";
+    return false;
+  }
+  if (pc_observer->enable_frame_encryption()) {
+    auto rtp_sender = result.MoveValue();
+    rtp_sender->SetFrameEncryptor(pc_observer->CreateEncryptor());
+  }
+  return true;
+}
+
 RUSTEXPORT DataChannelInterface*
 Rust_createSignalingDataChannel(PeerConnectionInterface* peer_connection,
                                 PeerConnectionObserver* pc_observer) {
@@ -864,6 +939,137 @@ Rust_configureAudioEncoders(webrtc::PeerConnectionInterface* peer_connection, co
   peer_connection->ConfigureAudioEncoders(*config);
 }
 
+// The resolution cap is turned into a scale factor for the size of the frames
+// currently being captured, so it has to be applied again when that changes.
+// The long side is capped by the larger of max_width and max_height so that
+// rotating the device doesn't change the outcome.
+RUSTEXPORT void
+Rust_setOutgoingVideoCaps(webrtc::PeerConnectionInterface* peer_connection, const RffiVideoEncoderCaps* caps) {
+  uint32_t max_long_side = std::max(caps->max_width, caps->max_height);
+  uint32_t max_short_side = std::min(caps->max_width, caps->max_height);
+  for (auto& sender : peer_connection->GetSenders()) {
+    if (sender->media_type() != cricket::MEDIA_TYPE_VIDEO) {
+      continue;
+    }
+
+    double scale_down_by = 1.0;
+    auto track = sender->track();
+    if (track) {
+      auto source = static_cast<VideoTrackInterface*>(track.get())->GetSource();
+      VideoTrackSourceInterface::Stats stats;
+      if (source && source->GetStats(&stats)) {
+        int long_side = std::max(stats.input_width, stats.input_height);
+        int short_side = std::min(stats.input_width, stats.input_height);
+        if (max_long_side > 0 && long_side > static_cast<int>(max_long_side)) {
+          scale_down_by = std::max(scale_down_by, static_cast<double>(long_side) / max_long_side);
+        }
+        if (max_short_side > 0 && short_side > static_cast<int>(max_short_side)) {
+          scale_down_by = std::max(scale_down_by, static_cast<double>(short_side) / max_short_side);
+        }
+      }
+    }
+
+    RtpParameters parameters = sender->GetParameters();
+    for (auto& encoding: parameters.encodings) {
+      if (scale_down_by > 1.0) {
+        encoding.scale_resolution_down_by = scale_down_by;
+      } else {
+        encoding.scale_resolution_down_by.reset();
+      }
+      if (caps->max_fps > 0) {
+        encoding.max_framerate = caps->max_fps;
+      } else {
+        encoding.max_framerate.reset();
+      }
+    }
+    RTCError error = sender->SetParameters(parameters);
+    if (!error.ok()) {
+      RTC_LOG(LS_WARNING) << "Rust_setOutgoingVideoCaps(): failed to set parameters: " << error.message();
+    }
+  }
+  RTC_LOG(LS_INFO) << "Rust_setOutgoingVideoCaps(" << caps->max_width << "x" << caps->max_height << "@" << caps->max_fps << ")";
+}
+
+// Without an explicit scale, WebRTC's simulcast defaults halve the
+// resolution for each layer below the top one.
+RUSTEXPORT size_t
+Rust_getOutgoingVideoLayers(webrtc::PeerConnectionInterface* peer_connection,
+                            RffiLayerConfig*                  layers_out,
+                            size_t                            layers_capacity) {
+  for (auto& sender : peer_connection->GetSenders()) {
+    if (sender->media_type() != cricket::MEDIA_TYPE_VIDEO) {
+      continue;
+    }
+
+    int input_width = 0;
+    int input_height = 0;
+    auto track = sender->track();
+    if (track) {
+      auto source = static_cast<VideoTrackInterface*>(track.get())->GetSource();
+      VideoTrackSourceInterface::Stats stats;
+      if (source && source->GetStats(&stats)) {
+        input_width = stats.input_width;
+        input_height = stats.input_height;
+      }
+    }
+
+    RtpParameters parameters = sender->GetParameters();
+    size_t layer_count = parameters.encodings.size();
+    for (size_t i = 0; i < layer_count && i < layers_capacity; i++) {
+      const auto& encoding = parameters.encodings[i];
+      double scale = encoding.scale_resolution_down_by.value_or(
+          static_cast<double>(1 << (layer_count - 1 - i)));
+      RffiLayerConfig* layer = &layers_out[i];
+      layer->ssrc = encoding.ssrc.value_or(0);
+      layer->active = encoding.active;
+      layer->width = static_cast<uint32_t>(input_width / scale);
+      layer->height = static_cast<uint32_t>(input_height / scale);
+      layer->scale_resolution_down_by = scale;
+      layer->max_framerate = static_cast<uint32_t>(encoding.max_framerate.value_or(0));
+      layer->max_bitrate_bps = static_cast<uint32_t>(encoding.max_bitrate_bps.value_or(0));
+      layer->num_temporal_layers = static_cast<uint32_t>(encoding.num_temporal_layers.value_or(0));
+    }
+    return layer_count;
+  }
+  return 0;
+}
+
+// Every sender shares the one bundled transport, so the first with a
+// negotiated suite answers for the whole call.
+RUSTEXPORT int
+Rust_getSrtpCipherSuite(PeerConnectionInterface* peer_connection) {
+  for (auto& sender : peer_connection->GetSenders()) {
+    auto dtls_transport = sender->dtls_transport();
+    if (!dtls_transport) {
+      continue;
+    }
+    auto srtp_cipher_suite = dtls_transport->Information().srtp_cipher_suite();
+    if (srtp_cipher_suite) {
+      return *srtp_cipher_suite;
+    }
+  }
+  return rtc::SRTP_INVALID_CRYPTO_SUITE;
+}
+
+RUSTEXPORT bool
+Rust_insertDtmf(PeerConnectionInterface* peer_connection,
+                const char*              tones,
+                int                      duration_ms,
+                int                      gap_ms) {
+  for (auto& sender : peer_connection->GetSenders()) {
+    if (sender->media_type() != cricket::MEDIA_TYPE_AUDIO) {
+      continue;
+    }
+    auto dtmf_sender = sender->GetDtmfSender();
+    if (!dtmf_sender || !dtmf_sender->CanInsertDtmf()) {
+      continue;
+    }
+    return dtmf_sender->InsertDtmf(std::string(tones), duration_ms, gap_ms);
+  }
+  RTC_LOG(LS_WARNING) << "Rust_insertDtmf(): no audio sender can send DTMF";
+  return false;
+}
+
 RUSTEXPORT void
 Rust_closePeerConnection(PeerConnectionInterface* peer_connection) {
     peer_connection->Close();
@@ -76,7 +76,8 @@ class PeerConnectionFactoryWithOwnedThreads
     media_dependencies.adm = adm;
     media_dependencies.audio_encoder_factory = CreateBuiltinAudioEncoderFactory();
     media_dependencies.audio_decoder_factory = CreateBuiltinAudioDecoderFactory();
-    media_dependencies.audio_processing = AudioProcessingBuilder().Create();
+    rtc::scoped_refptr<AudioProcessing> apm = AudioProcessingBuilder().Create();
+    media_dependencies.audio_processing = apm;
     media_dependencies.audio_mixer = AudioMixerImpl::Create();
     media_dependencies.video_encoder_factory = CreateBuiltinVideoEncoderFactory();
     media_dependencies.video_decoder_factory = CreateBuiltinVideoDecoderFactory();
@@ -88,7 +89,8 @@ class PeerConnectionFactoryWithOwnedThreads
         std::move(worker_thread),
         std::move(signaling_thread),
         std::move(injectable_network),
-        adm);
+        adm,
+        apm);
     owner->AddRef();
     return owner;
   }
@@ -183,6 +185,20 @@ class PeerConnectionFactoryWithOwnedThreads
     });
   }
 
+  // The voice engine only overrides these when audio options set them,
+  // which our audio sources never do, so they stick until changed again.
+  // The APM is shared by every call made with this factory, so callers
+  // put back the defaults once the call that wanted them has ended.
+  void ConfigureAudioProcessing(bool aec, bool ns, bool agc) override {
+    owned_worker_thread_->Invoke<void>(RTC_FROM_HERE, [&]() {
+      AudioProcessing::Config config = audio_processing_->GetConfig();
+      config.echo_canceller.enabled = aec;
+      config.noise_suppression.enabled = ns;
+      config.gain_controller1.enabled = agc;
+      audio_processing_->ApplyConfig(config);
+    });
+  }
+
  protected:
   PeerConnectionFactoryWithOwnedThreads(
       rtc::scoped_refptr<PeerConnectionFactoryInterface> factory,
@@ -190,12 +206,14 @@ class PeerConnectionFactoryWithOwnedThreads
       std::unique_ptr<rtc::Thread> owned_worker_thread,
       std::unique_ptr<rtc::Thread> owned_signaling_thread,
       std::unique_ptr<rffi::InjectableNetwork> injectable_network,
-      AudioDeviceModule* audio_device_module) :
+      AudioDeviceModule* audio_device_module,
+      rtc::scoped_refptr<AudioProcessing> audio_processing) :
     owned_network_thread_(std::move(owned_network_thread)),
     owned_worker_thread_(std::move(owned_worker_thread)),
     owned_signaling_thread_(std::move(owned_signaling_thread)),
     injectable_network_(std::move(injectable_network)),
     audio_device_module_(audio_device_module),
+    audio_processing_(std::move(audio_processing)),
     factory_(std::move(factory)) {
   }
 
@@ -219,6 +237,7 @@ class PeerConnectionFactoryWithOwnedThreads
   const std::unique_ptr<rtc::Thread> owned_signaling_thread_;
   std::unique_ptr<rffi::InjectableNetwork> injectable_network_;
   webrtc::AudioDeviceModule* audio_device_module_;
+  const rtc::scoped_refptr<AudioProcessing> audio_processing_;
   const rtc::scoped_refptr<PeerConnectionFactoryInterface> factory_;
 };
 
@@ -232,7 +251,8 @@ RUSTEXPORT PeerConnectionInterface* Rust_createPeerConnection(
     PeerConnectionObserverRffi* observer,
     rtc::RTCCertificate* certificate,
     bool hide_ip,
-    RffiIceServer ice_server,
+    const RffiIceServer* ice_servers,
+    size_t ice_servers_size,
     webrtc::AudioTrackInterface* outgoing_audio_track,
     webrtc::VideoTrackInterface* outgoing_video_track,
     bool enable_dtls,
@@ -247,12 +267,16 @@ RUSTEXPORT PeerConnectionInterface* Rust_createPeerConnection(
     config.type = PeerConnectionInterface::kRelay;
   }
   config.certificates.push_back(certificate);
-  if (ice_server.urls_size > 0) {
+  for (size_t i = 0; i < ice_servers_size; i++) {
+    const RffiIceServer& ice_server = ice_servers[i];
+    if (ice_server.urls_size == 0) {
+      continue;
+    }
     webrtc::PeerConnectionInterface::IceServer rtc_ice_server;
     rtc_ice_server.username = std::string(ice_server.username);
     rtc_ice_server.password = std::string(ice_server.password);
-    for (size_t i = 0; i < ice_server.urls_size; i++) {
-      rtc_ice_server.urls.push_back(std::string(ice_server.urls[i]));
+    for (size_t j = 0; j < ice_server.urls_size; j++) {
+      rtc_ice_server.urls.push_back(std::string(ice_server.urls[j]));
     }
     config.servers.push_back(rtc_ice_server);
   }
@@ -292,9 +316,12 @@ RUSTEXPORT PeerConnectionInterface* Rust_createPeerConnection(
   if (outgoing_video_track) {
     auto result = pc->AddTrack(outgoing_video_track, stream_ids);
     if (result.ok()) {
+      auto rtp_sender = result.MoveValue();
       if (observer->enable_frame_encryption()) {
-        auto rtp_sender = result.MoveValue();
         rtp_sender->SetFrameEncryptor(observer->CreateEncryptor());
+      } else {
+        // The Encryptor sees key frames itself, so this is only needed without it.
+        rtp_sender->SetEncoderToPacketizerFrameTransformer(observer->CreateKeyFrameTransformer());
       }
     } else {
       RTC_LOG(LS_ERROR) << "Failed to PeerConnection::AddTrack(video)";
@@ -320,6 +347,14 @@ RUSTEXPORT AudioTrackInterface* Rust_createAudioTrack(
   return track.release();
 }
 
+RUSTEXPORT void Rust_configureAudioProcessing(
+    PeerConnectionFactoryOwner* factory_owner,
+    const RffiAudioProcessingConfig* config) {
+  RTC_LOG(LS_INFO) << "Rust_configureAudioProcessing(aec: " << config->aec
+                   << ", ns: " << config->ns << ", agc: " << config->agc << ")";
+  factory_owner->ConfigureAudioProcessing(config->aec, config->ns, config->agc);
+}
+
 RUSTEXPORT VideoTrackSourceInterface* Rust_createVideoSource(
     PeerConnectionFactoryOwner* factory_owner) {
   auto source = new rtc::RefCountedObject<webrtc::rffi::VideoSource>();
@@ -8,6 +8,12 @@
 #include "rffi/api/media.h"
 #include "rffi/src/peer_connection_observer.h"
 
+#include "p2p/base/ice_transport_internal.h"
+#include "p2p/base/port.h"
+#include "rtc_base/synchronization/mutex.h"
+
+#include <map>
+
 namespace webrtc {
 namespace rffi {
 
@@ -29,6 +35,9 @@ void PeerConnectionObserverRffi::OnIceCandidate(const IceCandidateInterface* can
   std::string sdp;
   candidate->ToString(&sdp);
   rust_candidate.sdp = sdp.c_str();
+  rust_candidate.network_name = candidate->candidate().network_name().c_str();
+  rust_candidate.is_loopback =
+      candidate->candidate().network_type() == rtc::ADAPTER_TYPE_LOOPBACK;
 
   callbacks_.onIceCandidate(observer_, &rust_candidate);
 
@@ -39,6 +48,15 @@ void PeerConnectionObserverRffi::OnIceCandidatesRemoved(
   RTC_LOG(LS_INFO) << "OnIceCandidatesRemoved()";
 }
 
+void PeerConnectionObserverRffi::OnIceCandidateError(const std::string& address,
+                                                     int port,
+                                                     const std::string& url,
+                                                     int error_code,
+                                                     const std::string& error_text) {
+  // The address and port are of the local candidate, which we don't report.
+  callbacks_.onIceCandidateError(observer_, url.c_str(), error_code, error_text.c_str());
+}
+
 void PeerConnectionObserverRffi::OnSignalingChange(
     PeerConnectionInterface::SignalingState new_state) {
 }
@@ -59,6 +77,32 @@ void PeerConnectionObserverRffi::OnIceConnectionReceivingChange(bool receiving)
 void PeerConnectionObserverRffi::OnIceGatheringChange(
     PeerConnectionInterface::IceGatheringState new_state) {
   RTC_LOG(LS_INFO) << "OnIceGatheringChange()";
+  if (new_state == PeerConnectionInterface::kIceGatheringComplete) {
+    callbacks_.onIceGatheringComplete(observer_);
+  }
+}
+
+// The pair is relayed if either side is, and otherwise as direct as the local side.
+static int32_t CandidatePairType(const cricket::CandidatePair& pair) {
+  if (pair.local_candidate().type() == cricket::RELAY_PORT_TYPE ||
+      pair.remote_candidate().type() == cricket::RELAY_PORT_TYPE) {
+    return 3;
+  }
+  if (pair.local_candidate().type() == cricket::PRFLX_PORT_TYPE) {
+    return 2;
+  }
+  if (pair.local_candidate().type() == cricket::STUN_PORT_TYPE) {
+    return 1;
+  }
+  return 0;
+}
+
+void PeerConnectionObserverRffi::OnIceSelectedCandidatePairChanged(
+    const cricket::CandidatePairChangeEvent& event) {
+  auto local_adapter_type = event.selected_candidate_pair.local_candidate().network_type();
+  RTC_LOG(LS_INFO) << "OnIceSelectedCandidatePairChanged(): local_adapter_type: " << local_adapter_type;
+  callbacks_.onIceNetworkRouteChanged(observer_, local_adapter_type);
+  callbacks_.onIceCandidatePairChanged(observer_, CandidatePairType(event.selected_candidate_pair));
 }
 
 void PeerConnectionObserverRffi::OnAddStream(
@@ -110,16 +154,24 @@ void PeerConnectionObserverRffi::OnAddTrack(
   // handler.  Someone must call RefCountInterface::Release()
   // eventually.
   if (receiver->media_type() == cricket::MEDIA_TYPE_AUDIO) {
+    // Interpreter audio is encrypted with the same key as the main audio
+    // of the remote device, so it gets the same decryptor.
+    auto onAddRtpReceiver = IsInterpreterAudioTrackId(receiver->track()->id())
+        ? callbacks_.onAddInterpreterAudioRtpReceiver
+        : callbacks_.onAddAudioRtpReceiver;
     if (enable_frame_encryption_) {
       uint32_t id = Rust_getTrackIdAsUint32(receiver->track());
       if (id != 0) {
         receiver->SetFrameDecryptor(CreateDecryptor(id));
-        callbacks_.onAddAudioRtpReceiver(observer_, receiver->track().release());
+        onAddRtpReceiver(observer_, receiver->track().release());
       } else {
         RTC_LOG(LS_WARNING) << "Not sending decryptor for RtpReceiver with strange ID: " << receiver->track()->id();
       }
     } else {
-      callbacks_.onAddAudioRtpReceiver(observer_, receiver->track().release());
+      if (!IsInterpreterAudioTrackId(receiver->track()->id())) {
+        receiver->SetDepacketizerToDecoderFrameTransformer(CreateTelephoneEventTransformer());
+      }
+      onAddRtpReceiver(observer_, receiver->track().release());
     }
   } else if (receiver->media_type() == cricket::MEDIA_TYPE_VIDEO) {
     if (enable_frame_encryption_) {
@@ -195,6 +247,100 @@ rtc::scoped_refptr<FrameEncryptorInterface> PeerConnectionObserverRffi::CreateEn
   return new rtc::RefCountedObject<Encryptor>(observer_, &callbacks_);
 }
 
+class KeyFrameTransformer : public webrtc::FrameTransformerInterface {
+ public:
+  KeyFrameTransformer(const rust_object observer, PeerConnectionObserverCallbacks* callbacks) : observer_(observer), callbacks_(callbacks) {}
+
+  // Called on the encoder thread for every encoded frame, before packetization.
+  void Transform(std::unique_ptr<TransformableFrameInterface> frame) override {
+    auto video_frame = static_cast<TransformableVideoFrameInterface*>(frame.get());
+    if (video_frame->IsKeyFrame()) {
+      callbacks_->onOutgoingKeyFrameEncoded(observer_);
+    }
+
+    rtc::scoped_refptr<TransformedFrameCallback> sink;
+    {
+      MutexLock lock(&mutex_);
+      auto it = sinks_.find(frame->GetSsrc());
+      if (it != sinks_.end()) {
+        sink = it->second;
+      }
+    }
+    if (sink) {
+      sink->OnTransformedFrame(std::move(frame));
+    }
+  }
+
+  // Each simulcast layer registers its own sink.
+  void RegisterTransformedFrameSinkCallback(rtc::scoped_refptr<TransformedFrameCallback> sink,
+                                            uint32_t ssrc) override {
+    MutexLock lock(&mutex_);
+    sinks_[ssrc] = sink;
+  }
+
+  void UnregisterTransformedFrameSinkCallback(uint32_t ssrc) override {
+    MutexLock lock(&mutex_);
+    sinks_.erase(ssrc);
+  }
+
+ private:
+  const rust_object observer_;
+  PeerConnectionObserverCallbacks* callbacks_;
+  Mutex mutex_;
+  std::map<uint32_t, rtc::scoped_refptr<TransformedFrameCallback>> sinks_ RTC_GUARDED_BY(mutex_);
+};
+
+rtc::scoped_refptr<FrameTransformerInterface> PeerConnectionObserverRffi::CreateKeyFrameTransformer() {
+  // Outlives the KeyFrameTransformer for the same reasons as the Encryptor.
+  return new rtc::RefCountedObject<KeyFrameTransformer>(observer_, &callbacks_);
+}
+
+class TelephoneEventTransformer : public webrtc::FrameTransformerInterface {
+ public:
+  TelephoneEventTransformer(const rust_object observer, PeerConnectionObserverCallbacks* callbacks) : observer_(observer), callbacks_(callbacks) {}
+
+  // Called on the audio receive thread for every received frame, before decoding.
+  void Transform(std::unique_ptr<TransformableFrameInterface> frame) override {
+    auto audio_frame = static_cast<TransformableAudioFrameInterface*>(frame.get());
+    const RTPHeader& header = audio_frame->GetHeader();
+    if (header.payloadType == kTelephoneEventPayloadType) {
+      auto data = frame->GetData();
+      callbacks_->onRtpReceived(observer_, header.payloadType, header.sequenceNumber, header.timestamp, header.ssrc, data.data(), data.size());
+    }
+
+    rtc::scoped_refptr<TransformedFrameCallback> sink;
+    {
+      MutexLock lock(&mutex_);
+      sink = sink_;
+    }
+    if (sink) {
+      sink->OnTransformedFrame(std::move(frame));
+    }
+  }
+
+  // Receivers register a single sink, without an SSRC.
+  void RegisterTransformedFrameCallback(rtc::scoped_refptr<TransformedFrameCallback> sink) override {
+    MutexLock lock(&mutex_);
+    sink_ = sink;
+  }
+
+  void UnregisterTransformedFrameCallback() override {
+    MutexLock lock(&mutex_);
+    sink_ = nullptr;
+  }
+
+ private:
+  const rust_object observer_;
+  PeerConnectionObserverCallbacks* callbacks_;
+  Mutex mutex_;
+  rtc::scoped_refptr<TransformedFrameCallback> sink_ RTC_GUARDED_BY(mutex_);
+};
+
+rtc::scoped_refptr<FrameTransformerInterface> PeerConnectionObserverRffi::CreateTelephoneEventTransformer() {
+  // Outlives the TelephoneEventTransformer for the same reasons as the Decryptor.
+  return new rtc::RefCountedObject<TelephoneEventTransformer>(observer_, &callbacks_);
+}
+
 class Decryptor : public webrtc::FrameDecryptorInterface {
  public:
   Decryptor(uint32_t track_id, const rust_object observer, PeerConnectionObserverCallbacks* callbacks) : track_id_(track_id), observer_(observer), callbacks_(callbacks) {}
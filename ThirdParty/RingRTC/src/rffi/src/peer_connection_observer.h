@@ -8,6 +8,7 @@
 
 #include "api/data_channel_interface.h"
 #include "api/crypto/frame_encryptor_interface.h"
+#include "api/frame_transformer_interface.h"
 #include "api/peer_connection_interface.h"
 
 /**
@@ -19,6 +20,10 @@
 namespace webrtc {
 namespace rffi {
 
+// The payload type of telephone-events (DTMF) in 1:1 calls.  This must
+// stay in sync with Rust_sessionDescriptionFromV4.
+const uint8_t kTelephoneEventPayloadType = 110;
+
 class PeerConnectionObserverRffi : public PeerConnectionObserver, public DataChannelObserver {
  public:
   PeerConnectionObserverRffi(const rust_object observer,
@@ -35,12 +40,25 @@ class PeerConnectionObserverRffi : public PeerConnectionObserver, public DataCha
   // These will be a passed into RtpReceivers and will be implemented
   // with callbacks to PeerConnectionObserverCallbacks.
   rtc::scoped_refptr<FrameDecryptorInterface> CreateDecryptor(uint32_t track_id);
+  // Passed into the video RtpSender when frames aren't encrypted, to let
+  // PeerConnectionObserverCallbacks know about each key frame encoded.
+  // Frames go through unchanged.
+  rtc::scoped_refptr<FrameTransformerInterface> CreateKeyFrameTransformer();
+  // Passed into audio RtpReceivers when frames aren't encrypted, to pass
+  // received telephone-events to PeerConnectionObserverCallbacks.
+  // Frames go through unchanged.
+  rtc::scoped_refptr<FrameTransformerInterface> CreateTelephoneEventTransformer();
 
   // Implementation of PeerConnectionObserver interface, which propagates
   // the callbacks to the Rust observer.
   void OnIceCandidate(const IceCandidateInterface* candidate) override;
   void OnIceCandidatesRemoved(
       const std::vector<cricket::Candidate>& candidates) override;
+  void OnIceCandidateError(const std::string& address,
+                           int port,
+                           const std::string& url,
+                           int error_code,
+                           const std::string& error_text) override;
   void OnSignalingChange(
       PeerConnectionInterface::SignalingState new_state) override;
   void OnIceConnectionChange(
@@ -50,6 +68,8 @@ class PeerConnectionObserverRffi : public PeerConnectionObserver, public DataCha
   void OnIceConnectionReceivingChange(bool receiving) override;
   void OnIceGatheringChange(
       PeerConnectionInterface::IceGatheringState new_state) override;
+  void OnIceSelectedCandidatePairChanged(
+      const cricket::CandidatePairChangeEvent& event) override;
   void OnAddStream(rtc::scoped_refptr<MediaStreamInterface> stream) override;
   void OnRemoveStream(rtc::scoped_refptr<MediaStreamInterface> stream) override;
   void OnDataChannel(rtc::scoped_refptr<DataChannelInterface> channel) override;
@@ -11,6 +11,24 @@
 namespace webrtc {
 namespace rffi {
 
+// Matches the values in ConnectionStatistics.
+static int32_t CandidateTypeFromStats(const RTCIceCandidateStats* candidate_stat) {
+  if (!candidate_stat || !candidate_stat->candidate_type.is_defined()) {
+    return 0;
+  }
+  const std::string& candidate_type = *candidate_stat->candidate_type;
+  if (candidate_type == RTCIceCandidateType::kHost) {
+    return 1;
+  } else if (candidate_type == RTCIceCandidateType::kSrflx) {
+    return 2;
+  } else if (candidate_type == RTCIceCandidateType::kPrflx) {
+    return 3;
+  } else if (candidate_type == RTCIceCandidateType::kRelay) {
+    return 4;
+  }
+  return 0;
+}
+
 StatsObserverRffi::StatsObserverRffi(const rust_object             stats_observer,
                                      const StatsObserverCallbacks* stats_observer_cbs)
         : stats_observer_(stats_observer), stats_observer_cbs_(*stats_observer_cbs)
@@ -29,6 +47,7 @@ void StatsObserverRffi::OnStatsDelivered(const rtc::scoped_refptr<const RTCStats
   this->video_sender_statistics_.clear();
   this->audio_receiver_statistics_.clear();
   this->video_receiver_statistics_.clear();
+  this->connection_statistics_.clear();
 
   auto outbound_stream_stats = report->GetStatsOfType<RTCOutboundRTPStreamStats>();
   auto inbound_stream_stats = report->GetStatsOfType<RTCInboundRTPStreamStats>();
@@ -40,6 +59,8 @@ void StatsObserverRffi::OnStatsDelivered(const rtc::scoped_refptr<const RTCStats
       audio_sender.ssrc = stat->ssrc.ValueOrDefault(0);
       audio_sender.packets_sent = stat->packets_sent.ValueOrDefault(0);
       audio_sender.bytes_sent = stat->bytes_sent.ValueOrDefault(0);
+      audio_sender.retransmitted_packets_sent = stat->retransmitted_packets_sent.ValueOrDefault(0);
+      audio_sender.nack_count = stat->nack_count.ValueOrDefault(0);
 
       if (stat->remote_id.is_defined()) {
         auto remote_stat = report->GetAs<RTCRemoteInboundRtpStreamStats>(*stat->remote_id);
@@ -112,12 +133,20 @@ void StatsObserverRffi::OnStatsDelivered(const rtc::scoped_refptr<const RTCStats
       audio_receiver.jitter = stat->jitter.ValueOrDefault(0.0);
       audio_receiver.frames_decoded = stat->frames_decoded.ValueOrDefault(0);
       audio_receiver.total_decode_time = stat->total_decode_time.ValueOrDefault(0.0);
+      audio_receiver.nack_count = stat->nack_count.ValueOrDefault(0);
 
       if (stat->track_id.is_defined()) {
         auto track_stat = report->GetAs<RTCMediaStreamTrackStats>(*stat->track_id);
         if (track_stat) {
           audio_receiver.audio_level = track_stat->audio_level.ValueOrDefault(0.0);
           audio_receiver.total_audio_energy = track_stat->total_audio_energy.ValueOrDefault(0.0);
+          audio_receiver.jitter_buffer_delay = track_stat->jitter_buffer_delay.ValueOrDefault(0.0);
+          audio_receiver.jitter_buffer_emitted_count = track_stat->jitter_buffer_emitted_count.ValueOrDefault(0);
+          audio_receiver.total_samples_received = track_stat->total_samples_received.ValueOrDefault(0);
+          audio_receiver.concealed_samples = track_stat->concealed_samples.ValueOrDefault(0);
+          audio_receiver.concealment_events = track_stat->concealment_events.ValueOrDefault(0);
+          audio_receiver.inserted_samples_for_deceleration = track_stat->inserted_samples_for_deceleration.ValueOrDefault(0);
+          audio_receiver.removed_samples_for_acceleration = track_stat->removed_samples_for_acceleration.ValueOrDefault(0);
         }
       }
 
@@ -133,6 +162,9 @@ void StatsObserverRffi::OnStatsDelivered(const rtc::scoped_refptr<const RTCStats
       video_receiver.frames_decoded = stat->frames_decoded.ValueOrDefault(0);
       video_receiver.key_frames_decoded = stat->key_frames_decoded.ValueOrDefault(0);
       video_receiver.total_decode_time = stat->total_decode_time.ValueOrDefault(0.0);
+      video_receiver.nack_count = stat->nack_count.ValueOrDefault(0);
+      video_receiver.fir_count = stat->fir_count.ValueOrDefault(0);
+      video_receiver.pli_count = stat->pli_count.ValueOrDefault(0);
 
       if (stat->track_id.is_defined()) {
         auto track_stat = report->GetAs<RTCMediaStreamTrackStats>(*stat->track_id);
@@ -146,6 +178,39 @@ void StatsObserverRffi::OnStatsDelivered(const rtc::scoped_refptr<const RTCStats
     }
   }
 
+  auto transport_stats = report->GetStatsOfType<RTCTransportStats>();
+
+  for (const auto& stat : transport_stats) {
+    if (!stat->selected_candidate_pair_id.is_defined()) {
+      continue;
+    }
+    auto candidate_pair_stat = report->GetAs<RTCIceCandidatePairStats>(*stat->selected_candidate_pair_id);
+    if (candidate_pair_stat && candidate_pair_stat->current_round_trip_time.is_defined()) {
+      ConnectionStatistics connection = {0};
+
+      connection.current_round_trip_time = *candidate_pair_stat->current_round_trip_time;
+
+      if (candidate_pair_stat->local_candidate_id.is_defined()) {
+        connection.local_candidate_type = CandidateTypeFromStats(
+            report->GetAs<RTCLocalIceCandidateStats>(*candidate_pair_stat->local_candidate_id));
+      }
+      if (candidate_pair_stat->remote_candidate_id.is_defined()) {
+        auto remote_candidate_stat =
+            report->GetAs<RTCRemoteIceCandidateStats>(*candidate_pair_stat->remote_candidate_id);
+        connection.remote_candidate_type = CandidateTypeFromStats(remote_candidate_stat);
+        rtc::IPAddress remote_ip;
+        if (remote_candidate_stat && remote_candidate_stat->ip.is_defined() &&
+            rtc::IPFromString(*remote_candidate_stat->ip, &remote_ip)) {
+          connection.remote_address = RtcSocketAddressToIpPort(
+              rtc::SocketAddress(remote_ip, remote_candidate_stat->port.ValueOrDefault(0)));
+        }
+      }
+
+      this->connection_statistics_.push_back(connection);
+      break;
+    }
+  }
+
   MediaStatistics media_statistics;
   media_statistics.timestamp_us = report->timestamp_us();
   media_statistics.audio_sender_statistics_size = this->audio_sender_statistics_.size();
@@ -156,6 +221,8 @@ void StatsObserverRffi::OnStatsDelivered(const rtc::scoped_refptr<const RTCStats
   media_statistics.audio_receiver_statistics = this->audio_receiver_statistics_.data();
   media_statistics.video_receiver_statistics_count = this->video_receiver_statistics_.size();
   media_statistics.video_receiver_statistics = this->video_receiver_statistics_.data();
+  media_statistics.connection_statistics_size = this->connection_statistics_.size();
+  media_statistics.connection_statistics = this->connection_statistics_.data();
 
   // Pass media_statistics up to Rust, which will consume the data before returning.
   this->stats_observer_cbs_.OnStatsComplete(this->stats_observer_, &media_statistics);
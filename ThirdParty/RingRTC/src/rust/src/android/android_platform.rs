@@ -926,6 +926,7 @@ impl Platform for AndroidPlatform {
         &self,
         request_id: u32,
         joined_members: &[group_call::UserId],
+        _joined_devices: &[(group_call::UserId, group_call::DemuxId)],
         creator: Option<group_call::UserId>,
         era_id: Option<&str>,
         max_devices: Option<u32>,
@@ -1053,6 +1054,8 @@ impl Platform for AndroidPlatform {
         &self,
         client_id: group_call::ClientId,
         join_state: group_call::JoinState,
+        // Android doesn't join with start_muted, so the app already knows.
+        _outgoing_audio_muted: Option<bool>,
     ) {
         info!("handle_join_state_changed():");
 
@@ -1229,6 +1232,7 @@ impl Platform for AndroidPlatform {
         &self,
         client_id: group_call::ClientId,
         joined_members: &[group_call::UserId],
+        _joined_devices: &[(group_call::UserId, group_call::DemuxId)],
         creator: Option<group_call::UserId>,
         era_id: Option<&str>,
         max_devices: Option<u32>,
@@ -1325,6 +1329,56 @@ impl Platform for AndroidPlatform {
         });
     }
 
+    fn handle_join_rejected(
+        &self,
+        client_id: group_call::ClientId,
+        http_status: u16,
+        message: Option<&str>,
+    ) {
+        info!("handle_join_rejected(): {}", http_status);
+
+        let env = match self.java_env() {
+            Ok(v) => v,
+            Err(error) => {
+                error!("{:?}", error);
+                return;
+            }
+        };
+        let jni_call_manager = self.jni_call_manager.as_obj();
+
+        // Set a frame capacity of min (5) + objects (1).
+        let capacity = 6;
+        let _ = env.with_local_frame(capacity, || {
+            let jni_client_id = client_id as jlong;
+            let jni_http_status = http_status as jint;
+
+            let jni_message = match message {
+                None => JObject::null(),
+                Some(message) => match env.new_string(message) {
+                    Ok(v) => JObject::from(v),
+                    Err(error) => {
+                        error!("{:?}", error);
+                        return Ok(JObject::null());
+                    }
+                },
+            };
+
+            const METHOD: &str = "handleJoinRejected";
+            const SIG: &str = "(JILjava/lang/String;)V";
+            let args = [
+                jni_client_id.into(),
+                jni_http_status.into(),
+                jni_message.into(),
+            ];
+            let result = jni_call_method(&env, jni_call_manager, METHOD, SIG, &args);
+            if result.is_err() {
+                error!("jni_call_method: {:?}", result.err());
+            }
+
+            Ok(JObject::null())
+        });
+    }
+
     fn handle_ended(&self, client_id: group_call::ClientId, reason: group_call::EndReason) {
         info!("handle_ended():");
 
@@ -90,6 +90,7 @@ pub fn create_peer_connection(
     let pc_observer =
         PeerConnectionObserver::new(native_connection, false /* enable_frame_encryption */)?;
     let connection = unsafe { ptr_as_mut(native_connection)? };
+    connection.set_frame_encryption_enabled(pc_observer.frame_encryption_enabled())?;
 
     // construct JNI OwnedPeerConnection object
     let jni_owned_pc = unsafe {
@@ -598,6 +599,7 @@ pub fn create_group_call_client(
         None,
         outgoing_audio_track,
         outgoing_video_track,
+        None,
     )
 }
 
@@ -761,6 +763,7 @@ pub fn request_video(
             width,
             height,
             framerate,
+            pinned: false,
         };
 
         rendered_resolutions.push(rendered_resolution);
@@ -232,7 +232,7 @@ impl CallEndpoint {
         router: &Router,
         stopper: &Stopper,
     ) -> Result<Self> {
-        let peer_id = PeerId::from(peer_id);
+        let peer_id = PeerId::parse(peer_id)?;
 
         // To send across threads
         let ice_server = ice_server.clone();
@@ -483,6 +483,21 @@ impl CallEndpoint {
                     cm.received_busy(call_id, signaling::ReceivedBusy { sender_device_id })
                         .expect("received busy");
                 }
+                signaling::Message::TransferRequest(request) => {
+                    cm.received_transfer_request(
+                        call_id,
+                        signaling::ReceivedTransferRequest { sender_device_id },
+                        String::from_utf8_lossy(&request.target).into_owned(),
+                    )
+                    .expect("received transfer request");
+                }
+                signaling::Message::TransferAccepted => {
+                    cm.received_transfer_accepted(
+                        call_id,
+                        signaling::ReceivedTransferAccepted { sender_device_id },
+                    )
+                    .expect("received transfer accepted");
+                }
             }
         });
     }
@@ -517,7 +532,7 @@ impl SignalingSender for CallEndpoint {
         msg: signaling::Message,
     ) -> Result<()> {
         // To send across threads
-        let recipient_id = recipient_id.to_string();
+        let recipient_id = PeerId::parse(recipient_id)?;
 
         self.actor.send(move |state| {
             let sender_id = &state.peer_id;
@@ -0,0 +1,168 @@
+//
+// Copyright 2019-2021 Signal Messenger, LLC
+// SPDX-License-Identifier: AGPL-3.0-only
+//
+
+//! A buffer of the most recent log lines, kept so they can be attached
+//! to a bug report.
+//!
+//! The buffer is bounded by both line count and total bytes, so a long
+//! verbose session with huge lines can't make it grow without limit.
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+use lazy_static::lazy_static;
+
+pub const DEFAULT_MAX_LINES: usize = 1000;
+pub const DEFAULT_MAX_BYTES: usize = 1024 * 1024;
+
+lazy_static! {
+    static ref RECENT_LOGS: Mutex<LogBuffer> = Mutex::new(LogBuffer::default());
+}
+
+pub struct LogBuffer {
+    lines:     VecDeque<String>,
+    /// Total length of everything in lines.
+    bytes:     usize,
+    max_lines: usize,
+    max_bytes: usize,
+}
+
+impl Default for LogBuffer {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_LINES, DEFAULT_MAX_BYTES)
+    }
+}
+
+impl LogBuffer {
+    pub fn new(max_lines: usize, max_bytes: usize) -> Self {
+        Self {
+            lines: VecDeque::new(),
+            bytes: 0,
+            max_lines,
+            max_bytes,
+        }
+    }
+
+    /// Adds a line, evicting the oldest lines until both limits hold.
+    /// A line longer than the byte limit is cut down to fit.
+    pub fn push(&mut self, mut line: String) {
+        if line.len() > self.max_bytes {
+            let mut end = self.max_bytes;
+            while !line.is_char_boundary(end) {
+                end -= 1;
+            }
+            line.truncate(end);
+        }
+        self.bytes += line.len();
+        self.lines.push_back(line);
+        self.evict();
+    }
+
+    /// Changes the limits, evicting the oldest lines if the buffer is
+    /// now over either one.
+    pub fn set_limits(&mut self, max_lines: usize, max_bytes: usize) {
+        self.max_lines = max_lines;
+        self.max_bytes = max_bytes;
+        self.evict();
+    }
+
+    pub fn lines(&self) -> impl Iterator<Item = &String> {
+        self.lines.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    pub fn bytes(&self) -> usize {
+        self.bytes
+    }
+
+    // Each line is popped at most once after being pushed, so eviction
+    // is O(1) amortized per push.
+    fn evict(&mut self) {
+        while self.lines.len() > self.max_lines || self.bytes > self.max_bytes {
+            match self.lines.pop_front() {
+                Some(line) => self.bytes -= line.len(),
+                None => break,
+            }
+        }
+    }
+}
+
+/// Adds a line to the recent logs.
+pub fn record(line: String) {
+    if let Ok(mut recent_logs) = RECENT_LOGS.lock() {
+        recent_logs.push(line);
+    }
+}
+
+/// Sets how many lines and how many bytes the recent logs may hold.
+pub fn set_log_buffer_limits(max_lines: usize, max_bytes: usize) {
+    if let Ok(mut recent_logs) = RECENT_LOGS.lock() {
+        recent_logs.set_limits(max_lines, max_bytes);
+    }
+}
+
+/// Returns the recent logs, oldest first, one line each.
+pub fn recent_logs() -> String {
+    match RECENT_LOGS.lock() {
+        Ok(recent_logs) => recent_logs
+            .lines()
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join("\n"),
+        Err(_) => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn large_lines_never_exceed_byte_cap() {
+        let max_bytes = 10 * 1024;
+        let mut buffer = LogBuffer::new(DEFAULT_MAX_LINES, max_bytes);
+        for i in 0..1000 {
+            buffer.push("x".repeat(100 + (i * 37) % 3000));
+            assert!(buffer.bytes() <= max_bytes);
+            assert_eq!(buffer.bytes(), buffer.lines().map(String::len).sum::<usize>());
+        }
+        assert!(!buffer.is_empty());
+
+        // A single line bigger than the whole cap is cut down to fit.
+        buffer.push("y".repeat(max_bytes * 2));
+        assert_eq!(buffer.len(), 1);
+        assert_eq!(buffer.bytes(), max_bytes);
+    }
+
+    #[test]
+    fn oldest_lines_evicted_at_line_cap() {
+        let mut buffer = LogBuffer::new(3, DEFAULT_MAX_BYTES);
+        for i in 0..5 {
+            buffer.push(format!("line {}", i));
+        }
+        assert_eq!(
+            buffer.lines().cloned().collect::<Vec<_>>(),
+            vec!["line 2", "line 3", "line 4"]
+        );
+
+        buffer.set_limits(DEFAULT_MAX_LINES, "line 4".len());
+        assert_eq!(buffer.lines().cloned().collect::<Vec<_>>(), vec!["line 4"]);
+    }
+
+    #[test]
+    fn oversized_line_truncated_on_char_boundary() {
+        let mut buffer = LogBuffer::new(DEFAULT_MAX_LINES, 5);
+        buffer.push("ab\u{e9}\u{e9}".to_string());
+        assert_eq!(buffer.lines().cloned().collect::<Vec<_>>(), vec!["ab\u{e9}"]);
+        assert_eq!(buffer.bytes(), 4);
+    }
+}
@@ -6,6 +6,7 @@
 //! Common types used throughout the library.
 
 pub mod actor;
+pub mod log_buffer;
 pub mod units;
 
 use std::fmt;
@@ -92,6 +93,11 @@ pub enum CallState {
     /// After ConnectedAndAccepted, has gone disconnected temporarily and is trying to reconnect.
     ReconnectingAfterAccepted,
 
+    /// The call was accepted and has since been put on hold locally, so
+    /// no media is sent or received. Goes back to ConnectedAndAccepted when
+    /// the call is resumed.
+    OnHold,
+
     /// The call is in the process of terminating (hanging up).
     Terminating,
 
@@ -186,6 +192,29 @@ pub enum ApplicationEvent {
 
     /// Received an offer on a linked device from one that doesn't support multi-ring.
     IgnoreCallsFromNonMultiringCallers,
+
+    /// The local side has put the call on hold.
+    LocalHold,
+
+    /// The local side has resumed a call that was on hold.
+    LocalResume,
+
+    /// Received an offer with none of the protocol versions this build
+    /// supports, so the caller is on a newer version and the user needs to
+    /// update the app to take the call.
+    ReceivedOfferWithUnsupportedVersion,
+
+    /// The call was transferred to another user, so this leg has ended.
+    EndedTransferred,
+
+    /// The call ended because the remote stepped away, such as by
+    /// backgrounding the app, rather than hanging up.
+    EndedRemoteAway,
+
+    /// The call ended because the connection was lost after the call was
+    /// accepted, such as when the network changed, and couldn't be
+    /// reconnected.
+    EndedNetworkChanged,
 }
 
 impl Clone for ApplicationEvent {
@@ -305,7 +334,7 @@ impl FeatureLevel {
 
 /// Type of media for a call at time of origination.
 #[repr(i32)]
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum CallMediaType {
     /// Call should start as audio only.
     Audio = 0,
@@ -7,7 +7,7 @@
 
 use std::fmt;
 
-use crate::common::units;
+use crate::common::{units, CallMediaType};
 
 pub const MINIMUM_BITRATE_BPS: u64 = 30_000;
 pub const MAXIMUM_BITRATE_BPS: u64 = 2_000_001;
@@ -77,18 +77,62 @@ impl BandwidthMode {
         }
     }
 
-    pub fn audio_encoder_config(&self) -> crate::webrtc::media::AudioEncoderConfig {
+    /// The audio encoder config for the mode, with the bitrate capped by
+    /// the preset of the call's media type.
+    pub fn audio_encoder_config(
+        &self,
+        preset: &AudioEncoderPreset,
+    ) -> crate::webrtc::media::AudioEncoderConfig {
         let (packet_size_ms, start_bitrate_bps, min_bitrate_bps, max_bitrate_bps) = match self {
             BandwidthMode::VeryLow => (60, 16_000, 16_000, 16_000),
             BandwidthMode::Low => (40, 28_000, 16_000, 28_000),
-            BandwidthMode::Normal => (20, 40_000, 20_000, 40_000),
+            BandwidthMode::Normal => (20, 40_000, 20_000, u16::MAX),
         };
+        let max_bitrate_bps = max_bitrate_bps.min(preset.max_bitrate_bps);
         crate::webrtc::media::AudioEncoderConfig {
             packet_size_ms,
-            start_bitrate_bps,
-            min_bitrate_bps,
+            start_bitrate_bps: start_bitrate_bps.min(max_bitrate_bps),
+            min_bitrate_bps: min_bitrate_bps.min(max_bitrate_bps),
             max_bitrate_bps,
+            complexity: preset.complexity,
             ..Default::default()
         }
     }
 }
+
+/// The bandwidth mode a call is held to right now.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct EffectiveBandwidthMode {
+    pub mode:     BandwidthMode,
+    /// True if RingRTC picked the mode rather than the app, such as when
+    /// the remote side asked for less than the app set, or when a group call
+    /// adjusts its send bitrate to the number of participants.
+    pub adaptive: bool,
+}
+
+/// Audio encoder settings that depend on the media type of a 1:1 call.
+/// The audio of a video call shares its bandwidth with the video, while
+/// an audio-only call can give all of it to the audio.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AudioEncoderPreset {
+    /// The most the bandwidth mode allows is used, up to this bitrate.
+    pub max_bitrate_bps: u16,
+    /// Valid range: 0-9 (9 most complex)
+    pub complexity:      u16,
+}
+
+impl AudioEncoderPreset {
+    pub fn for_call_media_type(call_media_type: CallMediaType) -> Self {
+        match call_media_type {
+            CallMediaType::Audio => Self {
+                max_bitrate_bps: 56_000,
+                complexity:      9,
+            },
+            CallMediaType::Video => Self {
+                max_bitrate_bps: 40_000,
+                complexity:      9,
+            },
+        }
+    }
+}
@@ -28,33 +28,50 @@ use crate::core::bandwidth_mode::BandwidthMode;
 use crate::core::call_fsm::{CallEvent, CallStateMachine};
 use crate::core::call_manager::CallManager;
 use crate::core::call_mutex::CallMutex;
-use crate::core::connection::{Connection, ConnectionObserverEvent, ConnectionType};
+use crate::core::connection::{
+    Connection,
+    ConnectionObserverEvent,
+    ConnectionType,
+    HeldDescription,
+    ReconnectPolicy,
+};
 use crate::core::platform::Platform;
 use crate::core::signaling;
 use crate::core::util::TaskQueueRuntime;
 use crate::error::RingRtcError;
 use crate::webrtc::ice_gatherer::IceGatherer;
 use crate::webrtc::media::MediaStream;
+use crate::webrtc::peer_connection_observer::NetworkRoute;
 
 /// Encapsulates the FSM and runtime upon which a Call runs.
 struct Context {
     /// Runtime upon which the CallStateMachine runs.
-    pub worker_runtime:  TaskQueueRuntime,
+    pub worker_runtime:   TaskQueueRuntime,
     /// Runtime that manages timing out a call.
-    pub timeout_runtime: Option<TaskQueueRuntime>,
+    pub timeout_runtime:  Option<TaskQueueRuntime>,
+    /// How long an incoming call may ring before timing out, if limited.
+    pub ring_timeout:     Option<Duration>,
+    /// Runtime that manages timing out a ringing call.
+    pub ring_runtime:     Option<TaskQueueRuntime>,
+    /// How long connections wait for ICE to recover, if limited.
+    pub reconnect_policy: Option<ReconnectPolicy>,
 }
 
 impl Context {
     fn new() -> Result<Self> {
         Ok(Self {
-            worker_runtime:  TaskQueueRuntime::new("fsm-worker")?,
-            timeout_runtime: None,
+            worker_runtime:   TaskQueueRuntime::new("fsm-worker")?,
+            timeout_runtime:  None,
+            ring_timeout:     None,
+            ring_runtime:     None,
+            reconnect_policy: None,
         })
     }
 
     fn close(&mut self) {
         info!("stopping timeout runtime");
         self.timeout_runtime.take();
+        self.ring_runtime.take();
         info!("stopping timeout runtime: complete");
     }
 }
@@ -62,10 +79,12 @@ impl Context {
 /// Container for incoming call data, retained briefly while an
 /// underlying Connection object is created and initialized.
 struct PendingCall {
-    pub received:       signaling::ReceivedOffer,
+    pub received:             signaling::ReceivedOffer,
     /// Buffer to hold received ICE candidates before the Connection
     /// object is ready.
-    pub ice_candidates: Vec<signaling::IceCandidate>,
+    pub ice_candidates:       Vec<signaling::IceCandidate>,
+    /// Number of buffered ICE candidates from each remote device.
+    pub ice_candidate_counts: HashMap<DeviceId, usize>,
 }
 
 /// A mpsc::Receiver for receiving CallEvents in the
@@ -122,6 +141,9 @@ where
     terminate_condvar: Arc<(Mutex<bool>, Condvar)>,
     /// Whether or not an offer has been sent via messaging for this call.
     did_send_offer:    Arc<AtomicBool>,
+    /// Whether a transfer request has been sent for this call, so that a
+    /// TransferAccepted from the remote is expected.
+    transfer_pending:  Arc<AtomicBool>,
     /// When doing call forking, the parent that must be kept alive to keep
     /// ICE candidates and signaling alive.
     /// And we also need to keep around that parent's offer that it created.
@@ -203,6 +225,7 @@ where
             connection_map:    Arc::clone(&self.connection_map),
             terminate_condvar: Arc::clone(&self.terminate_condvar),
             did_send_offer:    Arc::clone(&self.did_send_offer),
+            transfer_pending:  Arc::clone(&self.transfer_pending),
             forking:           Arc::clone(&self.forking),
         }
     }
@@ -227,7 +250,8 @@ where
         // create a FSM runtime for this connection
         let fsm_context = Context::new()?;
         let (fsm_sender, fsm_receiver) = futures::channel::mpsc::channel(256);
-        let call_fsm = CallStateMachine::new(fsm_receiver)?
+        let awaits_ringing_ack = call_manager.platform()?.acknowledges_ringing();
+        let call_fsm = CallStateMachine::new(fsm_receiver, awaits_ringing_ack)?
             .map_err(|e| info!("call state machine returned error: {}", e));
         fsm_context.worker_runtime.spawn(call_fsm);
 
@@ -247,6 +271,7 @@ where
             connection_map: Arc::new(CallMutex::new(HashMap::new(), "connection_map")),
             terminate_condvar: Arc::new((Mutex::new(false), Condvar::new())),
             did_send_offer: Arc::new(AtomicBool::new(false)),
+            transfer_pending: Arc::new(AtomicBool::new(false)),
             forking: Arc::new(CallMutex::new(None, "forking")),
         };
 
@@ -277,6 +302,53 @@ where
         Ok(())
     }
 
+    /// Set how long the call may ring before timing out, or None to
+    /// let it ring until the setup timeout.
+    pub fn set_ring_timeout(&self, ring_timeout: Option<Duration>) -> Result<()> {
+        self.fsm_context.lock()?.ring_timeout = ring_timeout;
+        Ok(())
+    }
+
+    /// Set how long the call's connections wait for ICE to recover after
+    /// dropping, or None to wait until ICE itself gives up.
+    pub fn set_reconnect_policy(&self, reconnect_policy: Option<ReconnectPolicy>) -> Result<()> {
+        self.fsm_context.lock()?.reconnect_policy = reconnect_policy;
+        Ok(())
+    }
+
+    pub fn reconnect_policy(&self) -> Result<Option<ReconnectPolicy>> {
+        Ok(self.fsm_context.lock()?.reconnect_policy)
+    }
+
+    /// Start a timer to terminate the call if it rings for longer than
+    /// the ring timeout. Does nothing if there is no ring timeout.
+    pub fn start_ring_timer(&self) -> Result<()> {
+        let mut fsm_context = self.fsm_context.lock()?;
+        if let Some(ring_timeout) = fsm_context.ring_timeout {
+            let ring_runtime = TaskQueueRuntime::new("fsm-ring-timeout")?;
+
+            let mut call_clone = self.clone();
+            let when = Instant::now() + ring_timeout;
+            let ring_timeout_future = async move {
+                let sleep = tokio::time::sleep_until(tokio::time::Instant::from_std(when));
+                sleep.await;
+                call_clone
+                    .inject_call_timeout()
+                    .map_err(|e| error!("Inject ring timeout failed: {:?}", e))
+            };
+
+            ring_runtime.spawn(ring_timeout_future);
+            fsm_context.ring_runtime = Some(ring_runtime);
+        }
+        Ok(())
+    }
+
+    /// Stop the ring timer, if running.
+    pub fn cancel_ring_timer(&self) -> Result<()> {
+        self.fsm_context.lock()?.ring_runtime.take();
+        Ok(())
+    }
+
     /// Return the Call identifier.
     pub fn call_id(&self) -> CallId {
         self.call_id
@@ -287,6 +359,11 @@ where
         self.direction
     }
 
+    /// Return the Call media type at time of origination.
+    pub fn media_type(&self) -> CallMediaType {
+        self.media_type
+    }
+
     /// Return the current Call state.
     pub fn state(&self) -> Result<CallState> {
         let state = self.state.lock()?;
@@ -321,6 +398,16 @@ where
         )
     }
 
+    /// Note that a transfer request was sent for the call.
+    pub fn set_transfer_requested(&self) {
+        self.transfer_pending.store(true, Ordering::Release);
+    }
+
+    /// Return true if a transfer request was sent for the call.
+    pub fn transfer_requested(&self) -> bool {
+        self.transfer_pending.load(Ordering::Acquire)
+    }
+
     /// Return the active Connection this call is associated with.
     pub fn active_connection(&self) -> Result<Connection<T>> {
         let connection_map = self.connection_map.lock()?;
@@ -346,6 +433,7 @@ where
                 let pending_data = PendingCall {
                     received,
                     ice_candidates: Vec::new(),
+                    ice_candidate_counts: HashMap::new(),
                 };
                 *pending_call = Some(pending_data);
             }
@@ -425,6 +513,90 @@ where
         call_manager.notify_application(&*remote_peer, event)
     }
 
+    /// Notify application of an error from a STUN or TURN server.
+    ///
+    /// This is a pass through to the CallManager.
+    pub fn notify_ice_candidate_error(
+        &self,
+        url: String,
+        error_code: u16,
+        error_text: String,
+    ) -> Result<()> {
+        let call_manager = self.call_manager()?;
+        let remote_peer = self.remote_peer()?;
+
+        call_manager.notify_ice_candidate_error(&*remote_peer, url, error_code, error_text)
+    }
+
+    /// Notify application that the media moved to another kind of network.
+    ///
+    /// This is a pass through to the CallManager.
+    pub fn notify_network_route_changed(&self, network_route: NetworkRoute) -> Result<()> {
+        let call_manager = self.call_manager()?;
+        let remote_peer = self.remote_peer()?;
+
+        call_manager.notify_network_route_changed(&*remote_peer, network_route)
+    }
+
+    /// Notify application of a data message received from a remote
+    /// device.
+    ///
+    /// Messages from any device other than the active one are dropped.
+    pub fn notify_data_message(&self, remote_device_id: DeviceId, payload: Vec<u8>) -> Result<()> {
+        match self.active_device_id() {
+            Ok(active_device_id) if active_device_id == remote_device_id => {}
+            _ => {
+                debug!(
+                    "notify_data_message(): dropping message from inactive device: {}",
+                    remote_device_id
+                );
+                return Ok(());
+            }
+        }
+
+        let call_manager = self.call_manager()?;
+        let remote_peer = self.remote_peer()?;
+
+        call_manager.notify_data_message(&*remote_peer, payload)
+    }
+
+    /// Notify application of a DTMF tone received from a remote device.
+    ///
+    /// Tones from any device other than the active one are dropped.
+    pub fn notify_dtmf(&self, remote_device_id: DeviceId, tone: char) -> Result<()> {
+        match self.active_device_id() {
+            Ok(active_device_id) if active_device_id == remote_device_id => {}
+            _ => {
+                debug!(
+                    "notify_dtmf(): dropping tones from inactive device: {}",
+                    remote_device_id
+                );
+                return Ok(());
+            }
+        }
+
+        let call_manager = self.call_manager()?;
+        let remote_peer = self.remote_peer()?;
+
+        call_manager.notify_dtmf(&*remote_peer, tone)
+    }
+
+    /// Notify application that a key frame was encoded for the local
+    /// video sent to a remote device.
+    ///
+    /// Only the active device counts; others are still negotiating.
+    pub fn notify_outgoing_keyframe(&self, remote_device_id: DeviceId) -> Result<()> {
+        match self.active_device_id() {
+            Ok(active_device_id) if active_device_id == remote_device_id => {}
+            _ => return Ok(()),
+        }
+
+        let call_manager = self.call_manager()?;
+        let remote_peer = self.remote_peer()?;
+
+        call_manager.notify_outgoing_keyframe(&*remote_peer)
+    }
+
     /// Notify call manager of an internal error.
     ///
     pub fn internal_error(&self, error: failure::Error) -> Result<()> {
@@ -458,6 +630,32 @@ where
         }
     }
 
+    /// Send an offer or answer that was held back until ICE gathering
+    /// completed.
+    ///
+    /// This is a pass through to the CallManager.
+    pub fn send_held_description(
+        &self,
+        connection: Connection<T>,
+        description: HeldDescription,
+    ) -> Result<()> {
+        let state = self.state()?;
+
+        info!("send_held_description(): {}", state);
+
+        match state {
+            CallState::Terminating | CallState::Terminated => {
+                info!("send_held_description(): ignoring, terminating state");
+                Ok(())
+            }
+            _ => {
+                let mut call_manager = self.call_manager()?;
+
+                send_description(&mut call_manager, self.clone(), connection, description)
+            }
+        }
+    }
+
     /// Associate a MediaStream with a Connection.
     ///
     /// This is a pass through to the CallManager.
@@ -518,14 +716,22 @@ where
                     )?;
                     let answer = connection
                         .start_incoming(pending_call.received, pending_call.ice_candidates)?;
-                    call_manager.send_answer(
-                        self.clone(),
-                        connection.clone(),
-                        signaling::SendAnswer {
-                            receiver_device_id: remote_device_id,
-                            answer,
-                        },
-                    )?;
+                    let send = signaling::SendAnswer {
+                        receiver_device_id: remote_device_id,
+                        answer,
+                    };
+                    if connection.trickle_ice()? {
+                        call_manager.send_answer(self.clone(), connection.clone(), send)?;
+                    } else if let Some(description) =
+                        connection.hold_until_ice_gathered(HeldDescription::Answer(send))?
+                    {
+                        send_description(
+                            &mut call_manager,
+                            self.clone(),
+                            connection.clone(),
+                            description,
+                        )?;
+                    }
 
                     let mut connection_map = self.connection_map.lock()?;
                     connection_map.insert(remote_device_id, connection);
@@ -562,7 +768,18 @@ where
                     offer: offer.clone(),
                 });
 
-                call_manager.send_offer(self.clone(), parent_connection, offer)?;
+                if parent_connection.trickle_ice()? {
+                    call_manager.send_offer(self.clone(), parent_connection, offer)?;
+                } else if let Some(description) =
+                    parent_connection.hold_until_ice_gathered(HeldDescription::Offer(offer))?
+                {
+                    send_description(
+                        &mut call_manager,
+                        self.clone(),
+                        parent_connection,
+                        description,
+                    )?;
+                }
                 // If we don't do this, then hangups won't be sent.
                 self.did_send_offer.store(true, Ordering::Release);
             }
@@ -577,6 +794,7 @@ where
         if !connection_map.contains_key(&sender_device_id) {
             if self.state()? == CallState::ConnectedAndAccepted
                 || self.state()? == CallState::ReconnectingAfterAccepted
+                || self.state()? == CallState::OnHold
             {
                 info!(
                     "received_answer from device {} when already accepted, so ignore",
@@ -621,13 +839,33 @@ where
             self.call_id().format(received.sender_device_id)
         );
         let sender_device_id = received.sender_device_id;
+        let max_pending = self.call_manager()?.max_pending_ice_candidates();
 
         let mut pending_call = self.pending_call.lock()?;
-        if let Some(pending_call) = pending_call.as_mut() {
+        if let Some(pending) = pending_call.as_mut() {
             info!("received_ice(): storing in pending_call");
-            pending_call
+            let buffered = pending
+                .ice_candidate_counts
+                .entry(sender_device_id)
+                .or_insert(0);
+            let received_count = received.ice.candidates_added.len();
+            let kept = received_count.min(max_pending.saturating_sub(*buffered));
+            received.ice.candidates_added.truncate(kept);
+            *buffered += kept;
+            pending
                 .ice_candidates
                 .append(&mut received.ice.candidates_added);
+            drop(pending_call);
+
+            let dropped = received_count - kept;
+            if dropped > 0 {
+                warn!(
+                    "received_ice(): dropped {} candidates from device {}, pending limit of {} reached",
+                    dropped, sender_device_id, max_pending
+                );
+                self.call_manager()?
+                    .record_dropped_pending_ice_candidates(dropped)?;
+            }
             Ok(())
         } else {
             let mut connection_map = self.connection_map.lock()?;
@@ -636,6 +874,7 @@ where
                 None => {
                     if self.state()? == CallState::ConnectedAndAccepted
                         || self.state()? == CallState::ReconnectingAfterAccepted
+                        || self.state()? == CallState::OnHold
                     {
                         // This can happen when call forking is enabled.
                         info!(
@@ -644,6 +883,14 @@ where
                         );
                         return Ok(());
                     }
+                    if let CallState::Terminating | CallState::Terminated = self.state()? {
+                        // The connections are gone once the call has ended.
+                        debug!(
+                            "received_ice from device {} after the call ended, so ignore",
+                            sender_device_id
+                        );
+                        return Ok(());
+                    }
                     Err(RingRtcError::ConnectionNotFound(sender_device_id).into())
                 }
             }
@@ -732,9 +979,18 @@ where
         )
     }
 
-    /// ICE failed for a specific connection
-    pub fn handle_ice_failed(&mut self, remote_device: DeviceId) -> Result<()> {
-        info!("ice_failed(): id: {}", self.call_id().format(remote_device));
+    /// ICE failed for a specific connection, while reconnecting after
+    /// a network change if network_changed is set.
+    pub fn handle_ice_failed(
+        &mut self,
+        remote_device: DeviceId,
+        network_changed: bool,
+    ) -> Result<()> {
+        info!(
+            "ice_failed(): id: {}, network_changed: {}",
+            self.call_id().format(remote_device),
+            network_changed
+        );
 
         if let Ok(active_device_id) = self.active_device_id() {
             // There is an active connection.
@@ -742,7 +998,11 @@ where
                 // The active connection failed, close the call.
                 info!("ice_failed(): active connection");
                 let mut call_manager = self.call_manager()?;
-                call_manager.connection_failure(self.call_id)?;
+                if network_changed {
+                    call_manager.network_changed(self.call_id)?;
+                } else {
+                    call_manager.connection_failure(self.call_id)?;
+                }
             }
         } else if self.connection_map.lock()?.len() == 1 {
             // Only one connection left for this call and it just
@@ -930,6 +1190,21 @@ where
         self.inject_event(CallEvent::AcceptCall)
     }
 
+    /// Inject a local `NotifyRinging` event into the FSM.
+    pub fn inject_notify_ringing(&mut self) -> Result<()> {
+        self.inject_event(CallEvent::NotifyRinging)
+    }
+
+    /// Inject a local `Hold` event into the FSM.
+    pub fn inject_hold(&mut self) -> Result<()> {
+        self.inject_event(CallEvent::Hold)
+    }
+
+    /// Inject a local `Resume` event into the FSM.
+    pub fn inject_resume(&mut self) -> Result<()> {
+        self.inject_event(CallEvent::Resume)
+    }
+
     /// Inject a local `SendHangupViaDataChannelToAll` event into the FSM.
     pub fn inject_send_hangup_via_data_channel_to_all(
         &mut self,
@@ -1090,3 +1365,20 @@ where
         Ok(parent_connection)
     }
 }
+
+/// Send an offer or answer once it's no longer held back, with whichever
+/// CallManager call it would have gone through when trickling.
+fn send_description<T>(
+    call_manager: &mut CallManager<T>,
+    call: Call<T>,
+    connection: Connection<T>,
+    description: HeldDescription,
+) -> Result<()>
+where
+    T: Platform,
+{
+    match description {
+        HeldDescription::Offer(offer) => call_manager.send_offer(call, connection, offer),
+        HeldDescription::Answer(send) => call_manager.send_answer(call, connection, send),
+    }
+}
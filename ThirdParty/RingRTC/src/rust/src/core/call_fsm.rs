@@ -78,6 +78,12 @@ pub enum CallEvent {
     StartCall,
     /// Accept incoming call (callee only).
     AcceptCall,
+    /// The application has started ringing (callee only).
+    NotifyRinging,
+    /// Put an accepted call on hold.
+    Hold,
+    /// Resume a call that is on hold.
+    Resume,
     /// Send Hangup
     SendHangupViaDataChannelToAll(signaling::Hangup),
 
@@ -114,6 +120,9 @@ impl fmt::Display for CallEvent {
         let display = match self {
             CallEvent::StartCall => "StartCall".to_string(),
             CallEvent::AcceptCall => "AcceptCall".to_string(),
+            CallEvent::NotifyRinging => "NotifyRinging".to_string(),
+            CallEvent::Hold => "Hold".to_string(),
+            CallEvent::Resume => "Resume".to_string(),
             CallEvent::SendHangupViaDataChannelToAll(hangup) => {
                 format!("SendHangupViaDataChannelToAll, hangup: {}", hangup)
             }
@@ -174,11 +183,17 @@ where
     T: Platform,
 {
     /// Receiving end of EventPump.
-    event_stream:   EventStream<T>,
+    event_stream:         EventStream<T>,
     /// Runtime for processing long running requests.
-    worker_runtime: Option<TaskQueueRuntime>,
+    worker_runtime:       Option<TaskQueueRuntime>,
     /// Runtime for processing client application notification events.
-    notify_runtime: Option<TaskQueueRuntime>,
+    notify_runtime:       Option<TaskQueueRuntime>,
+    /// Whether the application has acknowledged that it is ringing, or
+    /// never does.
+    ringing_acknowledged: bool,
+    /// Whether the application accepted the call before it could be
+    /// accepted, so the accept has to be applied later.
+    accept_queued:        bool,
 }
 
 impl<T> fmt::Display for CallStateMachine<T>
@@ -232,12 +247,19 @@ impl<T> CallStateMachine<T>
 where
     T: Platform,
 {
-    /// Creates a new CallStateMachine object.
-    pub fn new(event_stream: EventStream<T>) -> Result<CallStateMachine<T>> {
+    /// Creates a new CallStateMachine object.  If awaits_ringing_ack is
+    /// set, an early accept waits for the application to call
+    /// notify_ringing().
+    pub fn new(
+        event_stream: EventStream<T>,
+        awaits_ringing_ack: bool,
+    ) -> Result<CallStateMachine<T>> {
         let mut fsm = CallStateMachine {
             event_stream,
             worker_runtime: Some(TaskQueueRuntime::new("call-worker")?),
             notify_runtime: Some(TaskQueueRuntime::new("call-notify")?),
+            ringing_acknowledged: !awaits_ringing_ack,
+            accept_queued: false,
         };
 
         if let Some(worker_runtime) = &mut fsm.worker_runtime {
@@ -323,6 +345,9 @@ where
             CallEvent::StartCall => self.handle_start_call(call, state),
             CallEvent::Proceed(bandwidth_mode) => self.handle_proceed(call, state, bandwidth_mode),
             CallEvent::AcceptCall => self.handle_accept_call(call, state),
+            CallEvent::NotifyRinging => self.handle_notify_ringing(call, state),
+            CallEvent::Hold => self.handle_hold(call, state),
+            CallEvent::Resume => self.handle_resume(call, state),
             CallEvent::ReceivedAnswer(received) => {
                 self.handle_received_answer(call, state, received)
             }
@@ -442,7 +467,8 @@ where
             | CallState::ConnectingBeforeAccepted
             | CallState::ConnectedWithDataChannelBeforeAccepted
             | CallState::ConnectedAndAccepted
-            | CallState::ReconnectingAfterAccepted => {
+            | CallState::ReconnectingAfterAccepted
+            | CallState::OnHold => {
                 let mut err_call = call.clone();
                 let handle_received_ice_future = lazy(move |_| {
                     if call.terminating()? {
@@ -456,6 +482,9 @@ where
 
                 self.worker_spawn(handle_received_ice_future);
             }
+            CallState::Terminating | CallState::Terminated => {
+                debug!("handle_received_ice(): call already ended, ignoring");
+            }
             _ => self.unexpected_state(state, "HandleReceivedIceCandidates"),
         }
         Ok(())
@@ -536,6 +565,18 @@ where
                 app_event_without_propagation(ApplicationEvent::EndedRemoteHangupBusy)
             }
 
+            // Callee gets Away: like Normal, but with specific app event.
+            (signaling::HangupType::Away, CallDirection::InComing) => {
+                app_event_without_propagation(ApplicationEvent::EndedRemoteAway)
+            }
+
+            // Caller gets Away: like Normal, propagate it as Declined, but
+            // with specific app event.
+            (signaling::HangupType::Away, CallDirection::OutGoing) => propagate_with_app_event(
+                signaling::Hangup::DeclinedOnAnotherDevice(sender_device_id),
+                ApplicationEvent::EndedRemoteAway,
+            ),
+
             // Everything else is unexpected: warn, and mostly treat like normal, no propagation.
             // TODO: Isn't NeedPermission for incoming normal because it's propagated above?
             // Should we make this no_app_event_and_no_propagation?
@@ -557,7 +598,8 @@ where
         | CallState::ConnectingBeforeAccepted
         | CallState::ConnectedWithDataChannelBeforeAccepted
         | CallState::ConnectedAndAccepted
-        | CallState::ReconnectingAfterAccepted = state
+        | CallState::ReconnectingAfterAccepted
+        | CallState::OnHold = state
         {
             call.set_state(CallState::Terminating)?;
         }
@@ -600,7 +642,17 @@ where
     fn handle_accept_call(&mut self, call: Call<T>, state: CallState) -> Result<()> {
         info!("handle_accept_call():");
         match state {
+            CallState::WaitingToProceed | CallState::ConnectingBeforeAccepted
+                if call.direction() == CallDirection::InComing =>
+            {
+                // Too early to accept, so hold on to it until we are connected
+                // and the application has acknowledged ringing.
+                info!("handle_accept_call(): queuing accept until ringing");
+                self.accept_queued = true;
+            }
             CallState::ConnectedWithDataChannelBeforeAccepted => {
+                self.accept_queued = false;
+                call.cancel_ring_timer()?;
                 call.set_state(CallState::ConnectedAndAccepted)?;
                 let mut err_call = call.clone();
                 let accept_future = lazy(move |_| {
@@ -624,6 +676,76 @@ where
         Ok(())
     }
 
+    fn handle_notify_ringing(&mut self, call: Call<T>, state: CallState) -> Result<()> {
+        info!("handle_notify_ringing():");
+        self.ringing_acknowledged = true;
+        self.maybe_apply_queued_accept(call, state)
+    }
+
+    /// Apply an accept that arrived before the call could be accepted,
+    /// once the call is connected and ringing has been acknowledged.
+    fn maybe_apply_queued_accept(&mut self, call: Call<T>, state: CallState) -> Result<()> {
+        if self.accept_queued
+            && self.ringing_acknowledged
+            && state == CallState::ConnectedWithDataChannelBeforeAccepted
+        {
+            info!("maybe_apply_queued_accept(): applying queued accept");
+            self.handle_accept_call(call, state)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn handle_hold(&mut self, call: Call<T>, state: CallState) -> Result<()> {
+        info!("handle_hold():");
+        match state {
+            CallState::ConnectedAndAccepted => {
+                call.set_state(CallState::OnHold)?;
+                let mut err_call = call.clone();
+                let hold_future = lazy(move |_| {
+                    if call.terminating()? {
+                        return Ok(());
+                    }
+                    let connection = call.active_connection()?;
+                    connection.set_media_on_hold(true)?;
+                    call.notify_application(ApplicationEvent::LocalHold)
+                })
+                .map_err(move |err| {
+                    err_call.inject_internal_error(err, "Processing local hold request failed");
+                });
+
+                self.worker_spawn(hold_future);
+            }
+            _ => self.unexpected_state(state, "Hold"),
+        }
+        Ok(())
+    }
+
+    fn handle_resume(&mut self, call: Call<T>, state: CallState) -> Result<()> {
+        info!("handle_resume():");
+        match state {
+            CallState::OnHold => {
+                call.set_state(CallState::ConnectedAndAccepted)?;
+                let mut err_call = call.clone();
+                let resume_future = lazy(move |_| {
+                    if call.terminating()? {
+                        return Ok(());
+                    }
+                    let connection = call.active_connection()?;
+                    connection.set_media_on_hold(false)?;
+                    call.notify_application(ApplicationEvent::LocalResume)
+                })
+                .map_err(move |err| {
+                    err_call.inject_internal_error(err, "Processing local resume request failed");
+                });
+
+                self.worker_spawn(resume_future);
+            }
+            _ => self.unexpected_state(state, "Resume"),
+        }
+        Ok(())
+    }
+
     fn handle_send_hangup_via_data_channel_to_all(
         &mut self,
         call: Call<T>,
@@ -676,7 +798,12 @@ where
                         // as a signal that the application should ring.
                         call.set_state(CallState::ConnectedWithDataChannelBeforeAccepted)?;
                         if let CallDirection::InComing = call.direction() {
-                            self.notify_application(call, ApplicationEvent::LocalRinging)
+                            call.start_ring_timer()?;
+                            self.notify_application(call.clone(), ApplicationEvent::LocalRinging);
+                            self.maybe_apply_queued_accept(
+                                call,
+                                CallState::ConnectedWithDataChannelBeforeAccepted,
+                            )?;
                         } else {
                             self.notify_application(call, ApplicationEvent::RemoteRinging)
                         }
@@ -860,13 +987,16 @@ where
                 }
                 Ok(())
             }
-            ConnectionObserverEvent::IceFailed => {
+            ConnectionObserverEvent::IceFailed
+            | ConnectionObserverEvent::IceFailedWhileReconnecting => {
+                let network_changed =
+                    event == ConnectionObserverEvent::IceFailedWhileReconnecting;
                 let mut err_call = call.clone();
                 let future = lazy(move |_| {
                     if call.terminating()? {
                         return Ok(());
                     }
-                    call.handle_ice_failed(remote_device_id)
+                    call.handle_ice_failed(remote_device_id, network_changed)
                 })
                 .map_err(move |err| {
                     err_call
@@ -911,7 +1041,9 @@ where
         info!("handle_call_timeout():");
 
         match state {
-            CallState::ConnectedAndAccepted | CallState::ReconnectingAfterAccepted => {} // Ok
+            CallState::ConnectedAndAccepted
+            | CallState::ReconnectingAfterAccepted
+            | CallState::OnHold => {} // Ok
             _ => {
                 let mut err_call = call.clone();
                 let timeout_future = lazy(move |_| {
@@ -5,7 +5,6 @@
 
 //! The main Call Manager object definitions.
 
-use std::cmp::Ordering;
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::fmt;
 use std::stringify;
@@ -14,8 +13,8 @@ use std::thread;
 use std::time::{Duration, SystemTime};
 
 use bytes::{Bytes, BytesMut};
-use futures::future::lazy;
-use futures::future::TryFutureExt;
+use futures::channel::oneshot;
+use futures::future::{self, lazy, TryFutureExt};
 use futures::Future;
 use prost::Message;
 
@@ -32,10 +31,10 @@ use crate::common::{
     Result,
     RingBench,
 };
-use crate::core::bandwidth_mode::BandwidthMode;
+use crate::core::bandwidth_mode::{BandwidthMode, EffectiveBandwidthMode};
 use crate::core::call::Call;
 use crate::core::call_mutex::CallMutex;
-use crate::core::connection::{Connection, ConnectionType};
+use crate::core::connection::{Connection, ConnectionType, ReconnectPolicy};
 use crate::core::http_client::HttpClient;
 use crate::core::platform::Platform;
 use crate::core::sfu_client::SfuClient;
@@ -43,11 +42,26 @@ use crate::core::util::{uuid_to_string, TaskQueueRuntime};
 use crate::core::{group_call, signaling};
 use crate::error::RingRtcError;
 use crate::protobuf;
-use crate::webrtc::media::{AudioTrack, MediaStream, VideoTrack};
+use crate::webrtc::audio_loopback::AudioLoopback;
+use crate::webrtc::media::{AudioTrack, LayerConfig, MediaStream, VideoEncoderCaps, VideoTrack};
 use crate::webrtc::peer_connection_factory::PeerConnectionFactory;
+use crate::webrtc::peer_connection_observer::{CandidatePairType, NetworkRoute};
+use crate::webrtc::stats_observer::CallStatistics;
 
 const TIME_OUT_PERIOD_SEC: u64 = 120;
 pub const MAX_MESSAGE_AGE_SEC: u64 = 120;
+/// Offers older than this, in seconds, are rejected as bogus rather than
+/// reported as expired. Nothing holds a message for anywhere near a year,
+/// so a larger age can only come from a bad clock or a bad value, such
+/// as a negative age reinterpreted as unsigned.
+pub const MAX_VALID_MESSAGE_AGE_SEC: u64 = 365 * 24 * 60 * 60;
+/// Default limit on the number of ICE candidates buffered per remote
+/// device while an incoming call is waiting to proceed.
+pub const DEFAULT_MAX_PENDING_ICE_CANDIDATES: usize = 100;
+/// Longest client identifier accepted by set_client_identifier().
+pub const MAX_CLIENT_IDENTIFIER_LEN: usize = 256;
+/// Most signaling messages held while signaling is suspended.
+pub const MAX_SUSPENDED_SIGNALING_MESSAGES: usize = 64;
 
 /// Spawns a task on the worker runtime thread to handle an API
 /// request with error handling.
@@ -153,6 +167,9 @@ where
     /// of being sent). We will only send one at a time to the
     /// application.
     messages_in_flight:     bool,
+    /// Whether the application has asked us to hold messages, such as
+    /// while it is suspended.
+    suspended:              bool,
 }
 
 impl<T> SignalingMessageQueue<T>
@@ -165,8 +182,14 @@ where
             queue:                  VecDeque::new(),
             last_sent_message_type: None,
             messages_in_flight:     false,
+            suspended:              false,
         })
     }
+
+    /// Whether signaling is suspended and no more messages can be held.
+    fn is_full(&self) -> bool {
+        self.suspended && self.queue.len() >= MAX_SUSPENDED_SIGNALING_MESSAGES
+    }
 }
 
 /// Maintains the set of HTTP requests in progress, and their associated callbacks.
@@ -176,6 +199,22 @@ struct HttpRequestTracker {
     next_request_id:    u32,
 }
 
+/// Options fixed when a CallManager is created.
+#[derive(Clone, Debug)]
+pub struct CallManagerConfig {
+    /// Most ICE candidates buffered per remote device for an incoming
+    /// call that has not yet proceeded.  Candidates beyond it are dropped.
+    pub max_pending_ice_candidates: usize,
+}
+
+impl Default for CallManagerConfig {
+    fn default() -> Self {
+        Self {
+            max_pending_ice_candidates: DEFAULT_MAX_PENDING_ICE_CANDIDATES,
+        }
+    }
+}
+
 pub struct CallManager<T>
 where
     T: Platform,
@@ -186,6 +225,8 @@ where
     call_by_call_id:           Arc<CallMutex<HashMap<CallId, Call<T>>>>,
     /// CallId of the active call.
     active_call_id:            Arc<CallMutex<Option<CallId>>>,
+    /// CallId of the call on hold while another call is active.
+    held_call_id:              Arc<CallMutex<Option<CallId>>>,
     /// Map of all group calls.
     group_call_by_client_id:   Arc<CallMutex<HashMap<group_call::ClientId, group_call::Client>>>,
     /// Next value of the group call client id (sequential).
@@ -198,6 +239,25 @@ where
     message_queue:             Arc<CallMutex<SignalingMessageQueue<T>>>,
     /// Outstanding HTTP requests
     http_request_tracker:      Arc<CallMutex<HttpRequestTracker>>,
+    /// Maximum number of ICE candidates buffered per remote device
+    /// before an incoming call's Connection exists.
+    pending_ice_limit:         usize,
+    /// Count of received ICE candidates dropped because the pending
+    /// buffer was full.
+    pending_ice_dropped:       Arc<CallMutex<u64>>,
+    /// Sent as the User-Agent and X-Client headers of every HTTP
+    /// request, if set.
+    client_identifier:         Arc<CallMutex<Option<String>>>,
+    /// Loopback of captured audio, while the application is checking
+    /// the audio devices.
+    audio_loopback:            Arc<CallMutex<Option<AudioLoopback>>>,
+    /// How long an incoming call may ring before timing out, if limited.
+    ring_timeout:              Arc<CallMutex<Option<Duration>>>,
+    /// How long connections wait for ICE to recover, if limited.
+    reconnect_policy:          Arc<CallMutex<Option<ReconnectPolicy>>>,
+    /// Cancels the timeout of each peek still waiting on a response,
+    /// by request ID.
+    peek_timeouts:             Arc<CallMutex<HashMap<u32, oneshot::Sender<()>>>>,
 }
 
 impl<T> fmt::Display for CallManager<T>
@@ -254,13 +314,87 @@ where
             platform:                  Arc::clone(&self.platform),
             call_by_call_id:           Arc::clone(&self.call_by_call_id),
             active_call_id:            Arc::clone(&self.active_call_id),
+            held_call_id:              Arc::clone(&self.held_call_id),
             group_call_by_client_id:   Arc::clone(&self.group_call_by_client_id),
             next_group_call_client_id: Arc::clone(&self.next_group_call_client_id),
             busy:                      Arc::clone(&self.busy),
             worker_runtime:            Arc::clone(&self.worker_runtime),
             message_queue:             Arc::clone(&self.message_queue),
             http_request_tracker:      Arc::clone(&self.http_request_tracker),
+            pending_ice_limit:         self.pending_ice_limit,
+            pending_ice_dropped:       Arc::clone(&self.pending_ice_dropped),
+            client_identifier:         Arc::clone(&self.client_identifier),
+            audio_loopback:            Arc::clone(&self.audio_loopback),
+            ring_timeout:              Arc::clone(&self.ring_timeout),
+            reconnect_policy:          Arc::clone(&self.reconnect_policy),
+            peek_timeouts:             Arc::clone(&self.peek_timeouts),
+        }
+    }
+}
+
+/// Tag written into every CallManagerHandle, used to detect stale or
+/// bogus pointers passed back by the application.
+const CALL_MANAGER_HANDLE_MAGIC: u64 = 0x5249_4e47_5254_4321;
+
+/// A CallManager as handed out to the application as an opaque
+/// pointer.
+///
+/// The handle carries a tag that is checked every time the
+/// application passes the pointer back, so that a stale or wrong
+/// pointer results in an error rather than the CallManager being
+/// read from garbage.
+#[repr(C)]
+pub struct CallManagerHandle<T>
+where
+    T: Platform,
+{
+    /// Must be first, so it can be checked without knowing T.
+    magic:        u64,
+    call_manager: CallManager<T>,
+}
+
+impl<T> CallManagerHandle<T>
+where
+    T: Platform,
+{
+    /// Moves the CallManager to the heap and returns a raw pointer
+    /// to its handle, suitable for passing to the application.
+    pub fn into_raw(call_manager: CallManager<T>) -> *mut Self {
+        Box::into_raw(Box::new(Self {
+            magic: CALL_MANAGER_HANDLE_MAGIC,
+            call_manager,
+        }))
+    }
+
+    /// # Safety
+    ///
+    /// Casts a raw handle created by into_raw() into a &mut CallManager<T>.
+    pub unsafe fn as_call_manager(ptr: *mut Self) -> Result<&'static mut CallManager<T>> {
+        Self::check(ptr, "as_call_manager()")?;
+        Ok(&mut (*ptr).call_manager)
+    }
+
+    /// # Safety
+    ///
+    /// Takes back ownership of the CallManager behind a raw handle
+    /// created by into_raw(). The handle is invalidated.
+    pub unsafe fn into_call_manager(ptr: *mut Self) -> Result<CallManager<T>> {
+        Self::check(ptr, "into_call_manager()")?;
+        // Clear the tag before freeing, so a later use of the same
+        // pointer is caught for as long as the memory isn't reused.
+        std::ptr::write_volatile(&mut (*ptr).magic, 0);
+        let handle = Box::from_raw(ptr);
+        Ok(handle.call_manager)
+    }
+
+    unsafe fn check(ptr: *mut Self, caller: &str) -> Result<()> {
+        if ptr.is_null() {
+            return Err(RingRtcError::NullPointer(caller.to_string(), "ptr".to_string()).into());
+        }
+        if std::ptr::read_volatile(&(*ptr).magic) != CALL_MANAGER_HANDLE_MAGIC {
+            return Err(RingRtcError::InvalidHandle(caller.to_string()).into());
         }
+        Ok(())
     }
 }
 
@@ -272,11 +406,14 @@ where
         &self,
         url: String,
         method: HttpMethod,
-        headers: HashMap<String, String>,
+        mut headers: HashMap<String, String>,
         body: Option<Vec<u8>>,
         on_response: HttpResponseCallback,
     ) {
         info!("make_request():");
+        if let Err(e) = self.insert_client_identifier_headers(&mut headers) {
+            error!("make_request(): client identifier unavailable: {:?}", e);
+        }
         debug!("  url: {} method: {:?} headers: {:?}", url, method, headers);
         let request_id = {
             let mut tracker = self
@@ -314,8 +451,13 @@ where
     // a) fast or b) asynchronous.
     ////////////////////////////////////////////////////////////////////////
 
-    /// Create a new CallManager.
+    /// Create a new CallManager with the default options.
     pub fn new(platform: T) -> Result<Self> {
+        Self::with_config(platform, CallManagerConfig::default())
+    }
+
+    /// Create a new CallManager with the given options.
+    pub fn with_config(platform: T, config: CallManagerConfig) -> Result<Self> {
         info!(
             "RingRTC v{}",
             option_env!("CARGO_PKG_VERSION").unwrap_or("unknown")
@@ -325,6 +467,7 @@ where
             platform:                  Arc::new(CallMutex::new(platform, "platform")),
             call_by_call_id:           Arc::new(CallMutex::new(HashMap::new(), "call_by_call_id")),
             active_call_id:            Arc::new(CallMutex::new(None, "active_call_id")),
+            held_call_id:              Arc::new(CallMutex::new(None, "held_call_id")),
             group_call_by_client_id:   Arc::new(CallMutex::new(
                 HashMap::new(),
                 "group_call_by_client_id",
@@ -346,9 +489,81 @@ where
                 },
                 "http_request_tracker",
             )),
+            pending_ice_limit:         config.max_pending_ice_candidates,
+            pending_ice_dropped:       Arc::new(CallMutex::new(0, "pending_ice_dropped")),
+            client_identifier:         Arc::new(CallMutex::new(None, "client_identifier")),
+            audio_loopback:            Arc::new(CallMutex::new(None, "audio_loopback")),
+            ring_timeout:              Arc::new(CallMutex::new(None, "ring_timeout")),
+            reconnect_policy:          Arc::new(CallMutex::new(None, "reconnect_policy")),
+            peek_timeouts:             Arc::new(CallMutex::new(HashMap::new(), "peek_timeouts")),
         })
     }
 
+    /// Return the maximum number of ICE candidates buffered per remote
+    /// device for an incoming call that has not yet proceeded.
+    pub fn max_pending_ice_candidates(&self) -> usize {
+        self.pending_ice_limit
+    }
+
+    /// Set how long an incoming call may ring without being accepted
+    /// or declined before it is ended with EndedTimeout and a hangup is
+    /// sent. A zero duration removes the limit, leaving only the call
+    /// setup timeout.
+    ///
+    /// Applies to calls received after it is set.
+    pub fn set_ring_timeout(&mut self, ring_timeout: Duration) -> Result<()> {
+        info!("API:set_ring_timeout(): {:?}", ring_timeout);
+        *self.ring_timeout.lock()? = if ring_timeout.is_zero() {
+            None
+        } else {
+            Some(ring_timeout)
+        };
+        Ok(())
+    }
+
+    /// Set how long a connection that dropped after the call was accepted
+    /// waits for ICE to recover before the call is ended with
+    /// EndedConnectionFailure, as a series of attempts with growing delays.
+    /// ICE keeps checking its candidate pairs meanwhile; there is no ICE
+    /// restart. None leaves it to ICE itself to give up.
+    ///
+    /// Applies to calls started or received after it is set.
+    pub fn set_reconnect_policy(
+        &mut self,
+        reconnect_policy: Option<ReconnectPolicy>,
+    ) -> Result<()> {
+        info!("API:set_reconnect_policy(): {:?}", reconnect_policy);
+        *self.reconnect_policy.lock()? = reconnect_policy;
+        Ok(())
+    }
+
+    /// Return the number of received ICE candidates dropped so far
+    /// because the pending buffer was full.
+    pub fn dropped_pending_ice_candidates(&self) -> Result<u64> {
+        Ok(*self.pending_ice_dropped.lock()?)
+    }
+
+    /// Set the identifier sent as the User-Agent and X-Client headers of
+    /// all HTTP requests, such as those made to the SFU, so the server can
+    /// attribute them to an app build.
+    ///
+    /// The identifier must be printable ASCII without leading or trailing
+    /// spaces, and at most MAX_CLIENT_IDENTIFIER_LEN bytes long.
+    pub fn set_client_identifier(&mut self, client_identifier: String) -> Result<()> {
+        info!("API:set_client_identifier(): {}", client_identifier);
+        let is_header_safe = !client_identifier.is_empty()
+            && client_identifier.len() <= MAX_CLIENT_IDENTIFIER_LEN
+            && client_identifier
+                .bytes()
+                .all(|b| (b' '..=b'~').contains(&b))
+            && client_identifier.trim() == client_identifier;
+        if !is_header_safe {
+            return Err(RingRtcError::InvalidClientIdentifier.into());
+        }
+        *self.client_identifier.lock()? = Some(client_identifier);
+        Ok(())
+    }
+
     /// Create an outgoing call.
     pub fn call(
         &mut self,
@@ -389,6 +604,35 @@ where
         handle_active_call_api!(self, CallManager::handle_accept_call, call_id)
     }
 
+    /// The application has started ringing for the incoming call.
+    ///
+    /// If the platform acknowledges ringing, an accept_call() that came
+    /// in before the call was connected is applied once this has been
+    /// called.
+    pub fn notify_ringing(&mut self, call_id: CallId) -> Result<()> {
+        handle_active_call_api!(self, CallManager::handle_notify_ringing, call_id)
+    }
+
+    /// Put the active call on hold.
+    ///
+    /// Outgoing and incoming media stop until the call is resumed. The
+    /// held call stays the active call until an offer for another call
+    /// arrives, which is then started as the active call instead of being
+    /// answered with Busy. Only one call can be held at a time.
+    pub fn hold_call(&mut self, call_id: CallId) -> Result<()> {
+        handle_active_call_api!(self, CallManager::handle_hold_call, call_id)
+    }
+
+    /// Resume a call after it was put on hold.
+    ///
+    /// Resuming the held call while another call is active swaps the two:
+    /// the active call is put on hold and the held call becomes the active
+    /// call again. This is ignored if the active call hasn't been accepted
+    /// yet, since it can't be held.
+    pub fn resume_call(&mut self, call_id: CallId) -> Result<()> {
+        handle_active_call_api!(self, CallManager::handle_resume_call, call_id)
+    }
+
     /// Drop the active call.
     pub fn drop_call(&mut self, call_id: CallId) -> Result<()> {
         handle_active_call_api!(self, CallManager::handle_drop_call, call_id)
@@ -420,11 +664,79 @@ where
         handle_active_call_api!(self, CallManager::handle_message_send_failure, call_id)
     }
 
+    /// Hold outgoing 1:1 signaling messages until resume_signaling(), such
+    /// as while the application is suspended and can't deliver them.
+    ///
+    /// At most MAX_SUSPENDED_SIGNALING_MESSAGES are held, and a held
+    /// message is never dropped.  Past that, new ICE candidates are
+    /// dropped, since a call can still connect with the candidates
+    /// gathered later, and any other new message fails to send.  A
+    /// message already handed to the application still needs
+    /// message_sent() or message_send_failure() unless the platform
+    /// assumes messages are sent.  Group call messages are not held.
+    pub fn suspend_signaling(&mut self) -> Result<()> {
+        handle_api!(self, CallManager::handle_suspend_signaling)
+    }
+
+    /// Send the messages held since suspend_signaling(), in order, with
+    /// the usual one-at-a-time pacing.
+    pub fn resume_signaling(&mut self) -> Result<()> {
+        handle_api!(self, CallManager::handle_resume_signaling)
+    }
+
+    /// Play captured audio straight back out, with no network involved,
+    /// so the user can check their microphone and speaker before a call.
+    /// The level of the captured audio is reported with
+    /// Platform::on_audio_loopback_level() until stop_audio_loopback().
+    ///
+    /// Fails if already in a call, since the call owns the audio devices;
+    /// likewise the loopback should be stopped before a call is started
+    /// or accepted.
+    pub fn start_audio_loopback(&mut self) -> Result<()> {
+        info!("API:start_audio_loopback():");
+
+        if *self.busy.lock()? {
+            return Err(RingRtcError::CallManagerIsBusy.into());
+        }
+        let mut audio_loopback = self.audio_loopback.lock()?;
+        if audio_loopback.is_some() {
+            info!("start_audio_loopback(): already started");
+            return Ok(());
+        }
+
+        let platform = Arc::clone(&self.platform);
+        let level_handler = move |level| match platform.lock() {
+            Ok(platform) => platform.on_audio_loopback_level(level),
+            Err(e) => error!("on_audio_loopback_level(): {}", e),
+        };
+        *audio_loopback = Some(AudioLoopback::start(Box::new(level_handler))?);
+        Ok(())
+    }
+
+    /// Stop the loopback started by start_audio_loopback().  Does nothing
+    /// if it isn't running.
+    pub fn stop_audio_loopback(&mut self) -> Result<()> {
+        info!("API:stop_audio_loopback():");
+
+        // Dropping the loopback stops it.
+        let audio_loopback = self.audio_loopback.lock()?.take();
+        drop(audio_loopback);
+        Ok(())
+    }
+
     /// Local hangup of the active call.
     pub fn hangup(&mut self) -> Result<()> {
         handle_active_call_api!(self, CallManager::handle_hangup)
     }
 
+    /// Local hangup of the active call because the user stepped away,
+    /// such as by backgrounding the app. The remote sees the call end
+    /// as away rather than hung up, unless it doesn't know the
+    /// difference.
+    pub fn hangup_as_away(&mut self) -> Result<()> {
+        handle_active_call_api!(self, CallManager::handle_hangup_as_away)
+    }
+
     /// Received offer from application.
     pub fn received_offer(
         &mut self,
@@ -434,6 +746,14 @@ where
     ) -> Result<()> {
         info!("API:received_offer():");
 
+        if received.age > Duration::from_secs(MAX_VALID_MESSAGE_AGE_SEC) {
+            warn!(
+                "received_offer(): rejecting offer with bogus age: {}s",
+                received.age.as_secs()
+            );
+            return Err(RingRtcError::InvalidOfferAge(received.age.as_secs()).into());
+        }
+
         let mut call_manager = self.clone();
         let mut cm_error = self.clone();
         let remote_peer_error = remote_peer.clone();
@@ -482,6 +802,65 @@ where
         handle_active_call_api!(self, CallManager::handle_received_busy, call_id, received)
     }
 
+    /// Reply busy to a call, for when the application knows the user
+    /// is busy elsewhere, such as in a call on another device.
+    ///
+    /// If the call is the active call, it is ended as well.
+    pub fn send_busy(
+        &mut self,
+        remote_peer: <T as Platform>::AppRemotePeer,
+        call_id: CallId,
+    ) -> Result<()> {
+        handle_active_call_api!(self, CallManager::handle_send_busy, remote_peer, call_id)
+    }
+
+    /// Ask the remote peer to end the call and call the target peer
+    /// instead.  The call carries on until the remote peer accepts, at
+    /// which point it ends as transferred.
+    pub fn transfer_call(
+        &mut self,
+        call_id: CallId,
+        target_peer: <T as Platform>::AppRemotePeer,
+    ) -> Result<()> {
+        handle_active_call_api!(self, CallManager::handle_transfer_call, call_id, target_peer)
+    }
+
+    /// Received call transfer request message from application.
+    pub fn received_transfer_request(
+        &mut self,
+        call_id: CallId,
+        received: signaling::ReceivedTransferRequest,
+        target_peer: <T as Platform>::AppRemotePeer,
+    ) -> Result<()> {
+        handle_active_call_api!(
+            self,
+            CallManager::handle_received_transfer_request,
+            call_id,
+            received,
+            target_peer
+        )
+    }
+
+    /// Accept a call transfer requested by the remote peer, ending the
+    /// call so the application can start a new one with the target.
+    pub fn accept_transfer(&mut self, call_id: CallId) -> Result<()> {
+        handle_active_call_api!(self, CallManager::handle_accept_transfer, call_id)
+    }
+
+    /// Received call transfer accepted message from application.
+    pub fn received_transfer_accepted(
+        &mut self,
+        call_id: CallId,
+        received: signaling::ReceivedTransferAccepted,
+    ) -> Result<()> {
+        handle_active_call_api!(
+            self,
+            CallManager::handle_received_transfer_accepted,
+            call_id,
+            received
+        )
+    }
+
     /// Received a call message from the application.
     pub fn received_call_message(
         &mut self,
@@ -503,6 +882,10 @@ where
     }
 
     /// Received a HTTP response from the application.
+    ///
+    /// Each request ID is outstanding until its first response (or failure)
+    /// arrives.  Responses for IDs that aren't outstanding, such as a late
+    /// duplicate delivered after the app retried, are dropped.
     pub fn received_http_response(
         &mut self,
         request_id: u32,
@@ -528,12 +911,24 @@ where
     ///
     /// Close down the call manager and all the calls it is currently managing.
     ///
+    /// Teardown happens in a fixed order:
+    ///
+    /// 1. Every group call client is disconnected and removed.  Each
+    ///    client's final connection state and `handle_ended()` are
+    ///    delivered to the platform before moving on.
+    /// 2. Any 1:1 calls are terminated, as with `reset()`.
+    /// 3. The worker runtime is flushed and then shut down.
+    ///
     /// This is a blocking call.
     #[allow(clippy::mutex_atomic)]
     pub fn close(&mut self) -> Result<()> {
         info!("close():");
 
         if self.worker_runtime.lock()?.is_some() {
+            // End the group calls while the runtime is still running,
+            // as their observers call back into the call manager.
+            self.end_group_call_clients()?;
+
             // Clear out any outstanding calls
             let _ = self.reset();
 
@@ -571,6 +966,47 @@ where
         active_call.active_connection()
     }
 
+    /// Send an application-defined payload, such as a reaction, to the
+    /// remote peer of the active call, which receives it through
+    /// Platform::on_data_message().
+    ///
+    /// The payload is sent once over the RTP data channel, which is
+    /// unreliable: it may be lost or delivered out of order.  Payloads
+    /// larger than MAX_DATA_MESSAGE_SIZE fail with DataMessageTooLarge.
+    pub fn send_rtp_data(&self, call_id: CallId, payload: Vec<u8>) -> Result<()> {
+        info!("API:send_rtp_data(): {}, len: {}", call_id, payload.len());
+
+        let active_call = self.active_call()?;
+        if active_call.call_id() != call_id {
+            return Err(RingRtcError::CallIdNotFound(call_id).into());
+        }
+        let mut active_connection = active_call.active_connection()?;
+        active_connection.inject_send_data_message_via_data_channel(payload)
+    }
+
+    /// Mute or unmute the outgoing audio of the active call, leaving its
+    /// video alone.  Fails if there is no active connection.
+    pub fn set_outgoing_audio_enabled(&self, enabled: bool) -> Result<()> {
+        info!("API:set_outgoing_audio_enabled(): {}", enabled);
+
+        self.active_connection()?.set_outgoing_audio_enabled(enabled)
+    }
+
+    /// Returns the call on hold while another call is active, if any.
+    pub fn held_call(&self) -> Result<Call<T>> {
+        let held_call_id = self.held_call_id.lock()?;
+        match *held_call_id {
+            Some(call_id) => {
+                let call_map = self.call_by_call_id.lock()?;
+                match call_map.get(&call_id) {
+                    Some(call) => Ok(call.clone()),
+                    None => Err(RingRtcError::CallIdNotFound(call_id).into()),
+                }
+            }
+            None => Err(RingRtcError::NoHeldCall.into()),
+        }
+    }
+
     /// Checks if a call is active.
     pub fn call_active(&self) -> Result<bool> {
         Ok(self.active_call_id.lock()?.is_some())
@@ -585,6 +1021,71 @@ where
         }
     }
 
+    /// Check if call_id refers to the call on hold while another call is
+    /// active.
+    fn call_is_held(&self, call_id: CallId) -> Result<bool> {
+        Ok(*self.held_call_id.lock()? == Some(call_id))
+    }
+
+    /// Check whether the media of the active call is frame encrypted, as
+    /// configured on its connection, in addition to SRTP.
+    pub fn is_frame_encryption_active(&self, call_id: CallId) -> Result<bool> {
+        let active_call = self.active_call()?;
+        if active_call.call_id() != call_id {
+            return Err(RingRtcError::CallIdNotFound(call_id).into());
+        }
+        active_call.active_connection()?.frame_encryption_enabled()
+    }
+
+    /// Returns the name of the SRTP cipher suite negotiated for the active
+    /// call, such as "AEAD_AES_256_GCM".  Available once the call connects.
+    pub fn srtp_cipher_suite(&self, call_id: CallId) -> Result<String> {
+        let active_call = self.active_call()?;
+        if active_call.call_id() != call_id {
+            return Err(RingRtcError::CallIdNotFound(call_id).into());
+        }
+        active_call.active_connection()?.srtp_cipher_suite()
+    }
+
+    /// Returns a snapshot of the quality of the active call with call_id,
+    /// or None if its stats haven't started yet.
+    pub fn get_call_statistics(&self, call_id: CallId) -> Result<Option<CallStatistics>> {
+        let active_call = self.active_call()?;
+        if active_call.call_id() != call_id {
+            return Err(RingRtcError::CallIdNotFound(call_id).into());
+        }
+        active_call.active_connection()?.call_statistics()
+    }
+
+    /// Returns the bandwidth mode being enforced on either the active call
+    /// with call_id or the group call client_id, whichever is given.
+    pub fn effective_bandwidth_mode(
+        &self,
+        call_id: Option<CallId>,
+        client_id: Option<group_call::ClientId>,
+    ) -> Result<EffectiveBandwidthMode> {
+        match (call_id, client_id) {
+            (Some(call_id), None) => {
+                let active_call = self.active_call()?;
+                if active_call.call_id() != call_id {
+                    return Err(RingRtcError::CallIdNotFound(call_id).into());
+                }
+                active_call.active_connection()?.effective_bandwidth_mode()
+            }
+            (None, Some(client_id)) => {
+                // Don't hold the lock while waiting on the client's actor.
+                let group_call = self
+                    .group_call_by_client_id
+                    .lock()?
+                    .get(&client_id)
+                    .cloned()
+                    .ok_or(RingRtcError::GroupCallClientNotFound(client_id))?;
+                Ok(group_call.effective_bandwidth_mode())
+            }
+            _ => Err(RingRtcError::CallOrGroupCallClientRequired.into()),
+        }
+    }
+
     /// Return the platform, under a locked mutex.
     pub fn platform(&self) -> Result<MutexGuard<'_, T>> {
         self.platform.lock()
@@ -623,6 +1124,18 @@ where
     // Private internal functions start here
     ////////////////////////////////////////////////////////////////////////
 
+    /// Add the client identifier headers, if there is a client identifier.
+    fn insert_client_identifier_headers(
+        &self,
+        headers: &mut HashMap<String, String>,
+    ) -> Result<()> {
+        if let Some(client_identifier) = self.client_identifier.lock()?.as_ref() {
+            headers.insert("User-Agent".to_string(), client_identifier.clone());
+            headers.insert("X-Client".to_string(), client_identifier.clone());
+        }
+        Ok(())
+    }
+
     /// Return the strong reference count on the platform.
     fn ref_count(&self) -> usize {
         Arc::strong_count(&self.platform)
@@ -721,10 +1234,11 @@ where
         Ok(())
     }
 
-    /// Releases busy so another call can begin
+    /// Releases busy so another call can begin, unless a call is still
+    /// on hold.
     fn release_busy(&mut self) -> Result<()> {
         let mut busy = self.busy.lock()?;
-        *busy = false;
+        *busy = self.held_call_id.lock()?.is_some();
 
         Ok(())
     }
@@ -861,6 +1375,25 @@ where
         self.terminate_call(call, hangup, Some(event))
     }
 
+    /// Terminates the call on hold, leaving the active call alone.
+    fn terminate_held_call(&mut self, send_hangup: bool, event: ApplicationEvent) -> Result<()> {
+        info!("terminate_held_call():");
+
+        let call = self.held_call()?;
+        let _ = self.held_call_id.lock()?.take();
+        if !self.call_active()? {
+            self.release_busy()?;
+        }
+
+        let hangup = if send_hangup {
+            Some(signaling::Hangup::Normal)
+        } else {
+            None
+        };
+
+        self.terminate_call(call, hangup, Some(event))
+    }
+
     /// Handle call() API from application.
     fn handle_call(
         &mut self,
@@ -900,6 +1433,7 @@ where
                     *busy = true;
                     *active_call_id = Some(call_id);
                     call.start_timeout_timer(TIME_OUT_PERIOD_SEC)?;
+                    call.set_reconnect_policy(*self.reconnect_policy.lock()?)?;
                     call.inject_start_call()
                 }
             }
@@ -925,6 +1459,87 @@ where
         active_call.inject_accept_call()
     }
 
+    fn handle_notify_ringing(&mut self, call_id: CallId) -> Result<()> {
+        ringbench!(
+            RingBench::App,
+            RingBench::CM,
+            format!("ringing()\t{}", call_id)
+        );
+
+        let mut active_call = check_active_call!(self, "handle_notify_ringing");
+        if active_call.call_id() != call_id {
+            ringbenchx!(RingBench::CM, RingBench::App, "inactive call_id");
+            return Ok(());
+        }
+
+        active_call.inject_notify_ringing()
+    }
+
+    fn handle_hold_call(&mut self, call_id: CallId) -> Result<()> {
+        ringbench!(
+            RingBench::App,
+            RingBench::CM,
+            format!("hold()\t{}", call_id)
+        );
+
+        let mut active_call = check_active_call!(self, "handle_hold_call");
+        if active_call.call_id() != call_id {
+            ringbenchx!(RingBench::CM, RingBench::App, "inactive call_id");
+            return Ok(());
+        }
+
+        active_call.inject_hold()
+    }
+
+    fn handle_resume_call(&mut self, call_id: CallId) -> Result<()> {
+        ringbench!(
+            RingBench::App,
+            RingBench::CM,
+            format!("resume()\t{}", call_id)
+        );
+
+        if self.call_is_held(call_id)? {
+            return self.swap_held_call(call_id);
+        }
+
+        let mut active_call = check_active_call!(self, "handle_resume_call");
+        if active_call.call_id() != call_id {
+            ringbenchx!(RingBench::CM, RingBench::App, "inactive call_id");
+            return Ok(());
+        }
+
+        active_call.inject_resume()
+    }
+
+    /// Resume the held call, putting the active call, if any, on hold in
+    /// its place.
+    fn swap_held_call(&mut self, call_id: CallId) -> Result<()> {
+        let mut held_call = self.held_call()?;
+
+        let active_call = self.active_call().ok();
+        if let Some(mut active_call) = active_call {
+            match active_call.state()? {
+                CallState::ConnectedAndAccepted | CallState::OnHold => {}
+                _ => {
+                    ringbenchx!(RingBench::CM, RingBench::App, "active call can't be held");
+                    return Ok(());
+                }
+            }
+            info!(
+                "swap_held_call(): holding call_id: {}, resuming call_id: {}",
+                active_call.call_id(),
+                call_id
+            );
+            active_call.inject_hold()?;
+            *self.held_call_id.lock()? = Some(active_call.call_id());
+        } else {
+            let _ = self.held_call_id.lock()?.take();
+        }
+        *self.active_call_id.lock()? = Some(call_id);
+
+        held_call.inject_resume()
+    }
+
     fn handle_terminate_active_call(
         &mut self,
         active_call: Call<T>,
@@ -988,6 +1603,30 @@ where
         self.send_next_message(None)
     }
 
+    /// Handle suspend_signaling() API from application.
+    fn handle_suspend_signaling(&mut self) -> Result<()> {
+        let mut message_queue = self.message_queue.lock()?;
+        info!(
+            "handle_suspend_signaling(): len: {}",
+            message_queue.queue.len()
+        );
+        message_queue.suspended = true;
+        Ok(())
+    }
+
+    /// Handle resume_signaling() API from application.
+    fn handle_resume_signaling(&mut self) -> Result<()> {
+        {
+            let mut message_queue = self.message_queue.lock()?;
+            info!(
+                "handle_resume_signaling(): len: {}",
+                message_queue.queue.len()
+            );
+            message_queue.suspended = false;
+        }
+        self.send_next_message(None)
+    }
+
     /// Handle message_send_failure() API from application.
     fn handle_message_send_failure(&mut self, call_id: CallId) -> Result<()> {
         // Get the last sent message type and see if it was for Ice.
@@ -1006,7 +1645,8 @@ where
                     match state {
                         CallState::ConnectedWithDataChannelBeforeAccepted
                         | CallState::ConnectedAndAccepted
-                        | CallState::ReconnectingAfterAccepted => {
+                        | CallState::ReconnectingAfterAccepted
+                        | CallState::OnHold => {
                             // We are in some connected state, ignore if the failed message
                             // was an Ice message.
                             if last_sent_message_ice {
@@ -1026,6 +1666,13 @@ where
             );
 
             let _ = self.terminate_active_call(true, ApplicationEvent::EndedSignalingFailure);
+        } else if self.call_is_held(call_id)? {
+            info!(
+                "handle_message_send_failure(): id: {}, concluding held call",
+                call_id
+            );
+
+            let _ = self.terminate_held_call(true, ApplicationEvent::EndedSignalingFailure);
         } else {
             // See if the associated call is in the call map.
             let mut call = None;
@@ -1081,13 +1728,26 @@ where
         )
     }
 
-    /// Handle received_offer() API from application.
-    fn handle_received_offer(
-        &mut self,
-        remote_peer: <T as Platform>::AppRemotePeer,
-        incoming_call_id: CallId,
-        received: signaling::ReceivedOffer,
-    ) -> Result<()> {
+    /// Handle hangup_as_away() API from application.
+    fn handle_hangup_as_away(&mut self) -> Result<()> {
+        ringbench!(RingBench::App, RingBench::CM, "hangup_as_away()");
+
+        let active_call = check_active_call!(self, "handle_hangup_as_away");
+
+        self.handle_terminate_active_call(
+            active_call,
+            Some(signaling::Hangup::Away),
+            ApplicationEvent::EndedLocalHangup,
+        )
+    }
+
+    /// Handle received_offer() API from application.
+    fn handle_received_offer(
+        &mut self,
+        remote_peer: <T as Platform>::AppRemotePeer,
+        incoming_call_id: CallId,
+        received: signaling::ReceivedOffer,
+    ) -> Result<()> {
         ringbench!(
             RingBench::App,
             RingBench::CM,
@@ -1109,6 +1769,21 @@ where
             return Ok(());
         }
 
+        if received.offer.supported_versions().is_empty() {
+            ringbenchx!(
+                RingBench::CM,
+                RingBench::App,
+                "offer has no supported version"
+            );
+            self.notify_application(
+                &remote_peer,
+                ApplicationEvent::ReceivedOfferWithUnsupportedVersion,
+            )?;
+            // Notify application we are completely done with this remote.
+            self.notify_call_concluded(&remote_peer, incoming_call_id)?;
+            return Ok(());
+        }
+
         if (received.sender_device_feature_level == FeatureLevel::Unspecified)
             && !received.receiver_device_is_primary
         {
@@ -1159,6 +1834,9 @@ where
             None,
             /// An active call with a different user, so act busy
             Busy,
+            /// An active call on hold with a different user, so hold it aside
+            /// and proceed with the incoming call
+            Waiting,
             /// An active call with the same user, but we win so ignore the incoming call
             Winner,
             /// An active call with the same user, but we lose so drop our call
@@ -1178,26 +1856,32 @@ where
                 let glare =
                     self.check_for_glare(&active_call, &remote_peer, received.sender_device_id);
                 if !glare {
-                    info!("handle_received_offer(): normal busy");
-                    Collision::Busy
+                    if active_call.state()? == CallState::OnHold
+                        && self.held_call_id.lock()?.is_none()
+                    {
+                        info!("handle_received_offer(): active call on hold, waiting");
+                        Collision::Waiting
+                    } else {
+                        info!("handle_received_offer(): normal busy");
+                        Collision::Busy
+                    }
                 } else {
                     info!("handle_received_offer(): glare detected");
-                    // Let the highest call_id keep the active call and be the caller, and
-                    // let the other side end the active call and become a callee.
-                    match active_call
-                        .call_id()
-                        .as_u64()
-                        .cmp(&incoming_call_id.as_u64())
-                    {
-                        Ordering::Greater => {
+                    let am_i_caller = active_call.direction() == CallDirection::OutGoing;
+                    match signaling::resolve_glare(
+                        active_call.call_id(),
+                        incoming_call_id,
+                        am_i_caller,
+                    ) {
+                        signaling::GlareResolution::KeepLocal => {
                             info!("handle_received_offer(): keep the active call");
                             Collision::Winner
                         }
-                        Ordering::Less => {
+                        signaling::GlareResolution::DropLocal => {
                             info!("handle_received_offer(): end the active call");
                             Collision::Loser
                         }
-                        Ordering::Equal => {
+                        signaling::GlareResolution::BothDrop => {
                             warn!("handle_received_offer(): unexpected call_id match");
                             Collision::DoubleLoser
                         }
@@ -1212,6 +1896,7 @@ where
 
         enum ActiveCallAction {
             DontTerminate,
+            Hold,
             Terminate(ApplicationEvent),
         }
 
@@ -1227,6 +1912,7 @@ where
                 ActiveCallAction::DontTerminate,
                 IncomingCallAction::RejectAsBusy(ApplicationEvent::ReceivedOfferWhileActive),
             ),
+            Collision::Waiting => (ActiveCallAction::Hold, IncomingCallAction::Start),
             Collision::Winner => (
                 ActiveCallAction::DontTerminate,
                 IncomingCallAction::Ignore(ApplicationEvent::ReceivedOfferWithGlare),
@@ -1243,6 +1929,10 @@ where
 
         match active_call_action {
             ActiveCallAction::DontTerminate => {}
+            ActiveCallAction::Hold => {
+                *self.held_call_id.lock()? = active_call_id;
+                self.clear_active_call()?;
+            }
             ActiveCallAction::Terminate(app_event) => {
                 self.clear_active_call()?;
                 *busy = false;
@@ -1260,7 +1950,7 @@ where
             }
             IncomingCallAction::RejectAsBusy(app_event) => {
                 self.notify_application(&remote_peer, app_event)?;
-                self.send_busy(incoming_call)?;
+                self.send_busy_for_call(incoming_call)?;
             }
             IncomingCallAction::Start => {
                 let mut active_call_id = self.active_call_id.lock()?;
@@ -1277,6 +1967,8 @@ where
                 *busy = true;
                 *active_call_id = Some(incoming_call_id);
                 incoming_call.start_timeout_timer(TIME_OUT_PERIOD_SEC)?;
+                incoming_call.set_ring_timeout(*self.ring_timeout.lock()?)?;
+                incoming_call.set_reconnect_policy(*self.reconnect_policy.lock()?)?;
                 incoming_call.handle_received_offer(received)?;
                 incoming_call.inject_start_call()?
             }
@@ -1332,6 +2024,14 @@ where
             ringbenchx!(RingBench::CM, RingBench::App, "inactive call_id");
             return Ok(());
         }
+        // ICE often arrives after the call ended, so it isn't an error.
+        if let CallState::Terminating | CallState::Terminated = active_call.state()? {
+            debug!(
+                "handle_received_ice(): call {} already ended, ignoring",
+                call_id
+            );
+            return Ok(());
+        }
 
         active_call.inject_received_ice(received)
     }
@@ -1351,6 +2051,11 @@ where
             )
         );
 
+        if self.call_is_held(call_id)? {
+            let mut held_call = self.held_call()?;
+            return held_call.inject_received_hangup(received);
+        }
+
         let mut active_call = check_active_call!(self, "handle_received_hangup");
         if active_call.call_id() != call_id {
             ringbenchx!(RingBench::CM, RingBench::App, "inactive call_id");
@@ -1405,6 +2110,143 @@ where
         )
     }
 
+    /// Handle send_busy() API from application.
+    fn handle_send_busy(
+        &mut self,
+        remote_peer: <T as Platform>::AppRemotePeer,
+        call_id: CallId,
+    ) -> Result<()> {
+        ringbench!(
+            RingBench::App,
+            RingBench::CM,
+            format!("send_busy()\t{}", call_id)
+        );
+
+        if let Ok(active_call) = self.active_call() {
+            if active_call.call_id() == call_id {
+                self.send_busy_for_call(active_call.clone())?;
+                return self.handle_terminate_active_call(
+                    active_call,
+                    None,
+                    ApplicationEvent::EndedLocalHangup,
+                );
+            }
+        }
+
+        // The call isn't known here, so create one just to carry the remote
+        // peer until the busy is sent; dropping it concludes the call.
+        let call = Call::new(
+            remote_peer,
+            call_id,
+            CallDirection::InComing,
+            CallMediaType::Audio,
+            0 as DeviceId,
+            self.clone(),
+        )?;
+        self.send_busy_for_call(call)
+    }
+
+    /// Handle transfer_call() API from application.
+    fn handle_transfer_call(
+        &mut self,
+        call_id: CallId,
+        target_peer: <T as Platform>::AppRemotePeer,
+    ) -> Result<()> {
+        ringbench!(
+            RingBench::App,
+            RingBench::CM,
+            format!("transfer_call()\t{}", call_id)
+        );
+
+        let active_call = check_active_call!(self, "handle_transfer_call");
+        if active_call.call_id() != call_id {
+            ringbenchx!(RingBench::CM, RingBench::App, "inactive call_id");
+            return Ok(());
+        }
+
+        active_call.set_transfer_requested();
+        self.send_transfer_request_for_call(active_call, target_peer)
+    }
+
+    /// Handle received_transfer_request() API from application.
+    fn handle_received_transfer_request(
+        &mut self,
+        call_id: CallId,
+        received: signaling::ReceivedTransferRequest,
+        target_peer: <T as Platform>::AppRemotePeer,
+    ) -> Result<()> {
+        let sender_device_id = received.sender_device_id;
+        ringbench!(
+            RingBench::App,
+            RingBench::CM,
+            format!("received_transfer_request()\t{}\t{}", call_id, sender_device_id)
+        );
+
+        let active_call = check_active_call!(self, "handle_received_transfer_request");
+        if active_call.call_id() != call_id {
+            ringbenchx!(RingBench::CM, RingBench::App, "inactive call_id");
+            return Ok(());
+        }
+
+        let remote_peer = active_call.remote_peer()?;
+        let platform = self.platform.lock()?;
+        platform.handle_transfer_request(&*remote_peer, &target_peer);
+        Ok(())
+    }
+
+    /// Handle accept_transfer() API from application.
+    fn handle_accept_transfer(&mut self, call_id: CallId) -> Result<()> {
+        ringbench!(
+            RingBench::App,
+            RingBench::CM,
+            format!("accept_transfer()\t{}", call_id)
+        );
+
+        let active_call = check_active_call!(self, "handle_accept_transfer");
+        if active_call.call_id() != call_id {
+            ringbenchx!(RingBench::CM, RingBench::App, "inactive call_id");
+            return Ok(());
+        }
+
+        // The transferring side ends its leg when the acceptance arrives,
+        // so there is no need for a hangup as well.
+        self.send_transfer_accepted_for_call(active_call.clone())?;
+        self.handle_terminate_active_call(active_call, None, ApplicationEvent::EndedTransferred)
+    }
+
+    /// Handle received_transfer_accepted() API from application.
+    fn handle_received_transfer_accepted(
+        &mut self,
+        call_id: CallId,
+        received: signaling::ReceivedTransferAccepted,
+    ) -> Result<()> {
+        let sender_device_id = received.sender_device_id;
+        ringbench!(
+            RingBench::App,
+            RingBench::CM,
+            format!("received_transfer_accepted()\t{}\t{}", call_id, sender_device_id)
+        );
+
+        let active_call = check_active_call!(self, "handle_received_transfer_accepted");
+        if active_call.call_id() != call_id {
+            ringbenchx!(RingBench::CM, RingBench::App, "inactive call_id");
+            return Ok(());
+        }
+
+        // Only the device we're connected to can accept, and only a
+        // transfer we asked for.
+        if !active_call.transfer_requested() {
+            ringbenchx!(RingBench::CM, RingBench::App, "no transfer requested");
+            return Ok(());
+        }
+        if active_call.active_device_id().ok() != Some(sender_device_id) {
+            ringbenchx!(RingBench::CM, RingBench::App, "not the active device");
+            return Ok(());
+        }
+
+        self.handle_terminate_active_call(active_call, None, ApplicationEvent::EndedTransferred)
+    }
+
     /// Handle received_call_message() API from the application.
     fn handle_received_call_message(
         &mut self,
@@ -1474,8 +2316,8 @@ where
             debug!("received_http_response(): calling registered callback");
             callback(response);
         } else {
-            error!(
-                "received_http_response(): received response for untracked request: {}",
+            debug!(
+                "received_http_response(): ignoring response for request that is no longer outstanding: {}",
                 request_id
             );
         }
@@ -1501,6 +2343,7 @@ where
             let _ = self.terminate_call(call, Some(signaling::Hangup::Normal), None);
         }
 
+        let _ = self.held_call_id.lock()?.take();
         self.clear_active_call()?;
         self.release_busy()?;
 
@@ -1513,9 +2356,9 @@ where
         Ok(())
     }
 
-    fn send_busy(&mut self, call: Call<T>) -> Result<()> {
+    fn send_busy_for_call(&mut self, call: Call<T>) -> Result<()> {
         let call_id = call.call_id();
-        info!("send_busy(): call_id: {}", call_id);
+        info!("send_busy_for_call(): call_id: {}", call_id);
 
         let busy_closure = Box::new(move |cm: &CallManager<T>| {
             ringbench!(
@@ -1541,6 +2384,66 @@ where
         self.send_next_message(Some(message_item))
     }
 
+    fn send_transfer_request_for_call(
+        &mut self,
+        call: Call<T>,
+        target_peer: <T as Platform>::AppRemotePeer,
+    ) -> Result<()> {
+        let call_id = call.call_id();
+        info!("send_transfer_request_for_call(): call_id: {}", call_id);
+
+        let transfer_request_closure = Box::new(move |cm: &CallManager<T>| {
+            ringbench!(
+                RingBench::CM,
+                RingBench::App,
+                format!("send_transfer_request()\t{}", call_id)
+            );
+
+            let remote_peer = call.remote_peer()?;
+
+            let platform = cm.platform.lock()?;
+            platform.on_send_transfer_request(&*remote_peer, call_id, &target_peer)?;
+
+            Ok(MessageSendResult::Sent)
+        });
+
+        let message_item = SignalingMessageItem {
+            call_id,
+            message_type: signaling::MessageType::TransferRequest,
+            message_closure: transfer_request_closure,
+        };
+
+        self.send_next_message(Some(message_item))
+    }
+
+    fn send_transfer_accepted_for_call(&mut self, call: Call<T>) -> Result<()> {
+        let call_id = call.call_id();
+        info!("send_transfer_accepted_for_call(): call_id: {}", call_id);
+
+        let transfer_accepted_closure = Box::new(move |cm: &CallManager<T>| {
+            ringbench!(
+                RingBench::CM,
+                RingBench::App,
+                format!("send_transfer_accepted()\t{}", call_id)
+            );
+
+            let remote_peer = call.remote_peer()?;
+
+            let platform = cm.platform.lock()?;
+            platform.on_send_transfer_accepted(&*remote_peer, call_id)?;
+
+            Ok(MessageSendResult::Sent)
+        });
+
+        let message_item = SignalingMessageItem {
+            call_id,
+            message_type: signaling::MessageType::TransferAccepted,
+            message_closure: transfer_accepted_closure,
+        };
+
+        self.send_next_message(Some(message_item))
+    }
+
     /// If the remote peer of the active call equals the remote peer
     /// of an incoming offer, then we might have a glare situation.
     ///
@@ -1665,6 +2568,13 @@ where
         if let Some(message_item) = message_item_option {
             match self.message_queue.lock() {
                 Ok(mut message_queue) => {
+                    if message_queue.is_full() {
+                        return Err(RingRtcError::SuspendedSignalingQueueFull(
+                            format!("{:?}", message_item.message_type),
+                            message_item.call_id,
+                        )
+                        .into());
+                    }
                     message_queue.queue.push_back(message_item);
                 }
                 Err(e) => {
@@ -1685,6 +2595,14 @@ where
 
             match self.message_queue.lock() {
                 Ok(mut message_queue) => {
+                    if message_queue.suspended {
+                        info!(
+                            "send_next_message(): signaling is suspended, len: {}",
+                            message_queue.queue.len()
+                        );
+                        return Ok(());
+                    }
+
                     if message_queue.messages_in_flight {
                         info!("send_next_message(): messages are in flight already");
                         return Ok(());
@@ -1711,6 +2629,13 @@ where
                                     message_queue.last_sent_message_type =
                                         Some(message_item.message_type);
 
+                                    if message_is_in_flight == MessageSendResult::Sent
+                                        && assume_messages_sent
+                                    {
+                                        let platform = self.platform.lock()?;
+                                        platform.on_signaling_sent_assumed(message_item.call_id);
+                                    }
+
                                     if message_queue.messages_in_flight {
                                         // If there are messages in flight, exit the loop and
                                         // wait for confirmation that they actually got sent.
@@ -1749,7 +2674,8 @@ where
     /// messages as they might have been sent on behalf of the
     /// call before termination. Also ignore Hangup messages, since
     /// they should always be sent as backup for callees to end
-    /// their side of the call.
+    /// their side of the call, and TransferAccepted messages, since
+    /// they are what end the transferring side of the call.
     fn trim_messages(&self, call_id: CallId) -> Result<()> {
         let mut message_queue = self.message_queue.lock()?;
         let mq = &mut *message_queue;
@@ -1763,6 +2689,7 @@ where
             (x.call_id != call_id)
                 || (x.message_type == signaling::MessageType::Busy)
                 || (x.message_type == signaling::MessageType::Hangup)
+                || (x.message_type == signaling::MessageType::TransferAccepted)
         });
         debug!("trim_messages(): end len: {}", mq.queue.len());
 
@@ -1803,6 +2730,66 @@ where
         platform.on_event(remote_peer, event)
     }
 
+    /// Notify application of an error from a STUN or TURN server.
+    pub(super) fn notify_ice_candidate_error(
+        &self,
+        remote_peer: &<T as Platform>::AppRemotePeer,
+        url: String,
+        error_code: u16,
+        error_text: String,
+    ) -> Result<()> {
+        let platform = self.platform.lock()?;
+        platform.on_ice_candidate_error(remote_peer, url, error_code, error_text)
+    }
+
+    /// Notify application that the media moved to another kind of network.
+    pub(super) fn notify_network_route_changed(
+        &self,
+        remote_peer: &<T as Platform>::AppRemotePeer,
+        network_route: NetworkRoute,
+    ) -> Result<()> {
+        let platform = self.platform.lock()?;
+        platform.on_network_route_changed(remote_peer, network_route)
+    }
+
+    /// Notify application of a data message from the remote peer.
+    pub(super) fn notify_data_message(
+        &self,
+        remote_peer: &<T as Platform>::AppRemotePeer,
+        payload: Vec<u8>,
+    ) -> Result<()> {
+        ringbench!(
+            RingBench::CM,
+            RingBench::App,
+            format!("data_message({})", payload.len())
+        );
+
+        let platform = self.platform.lock()?;
+        platform.on_data_message(remote_peer, payload)
+    }
+
+    /// Notify application of a DTMF tone from the remote peer.
+    pub(super) fn notify_dtmf(
+        &self,
+        remote_peer: &<T as Platform>::AppRemotePeer,
+        tone: char,
+    ) -> Result<()> {
+        ringbench!(RingBench::CM, RingBench::App, "dtmf()");
+
+        let platform = self.platform.lock()?;
+        platform.on_dtmf(remote_peer, tone)
+    }
+
+    /// Notify application that a key frame was encoded for the local
+    /// video.
+    pub(super) fn notify_outgoing_keyframe(
+        &self,
+        remote_peer: &<T as Platform>::AppRemotePeer,
+    ) -> Result<()> {
+        let platform = self.platform.lock()?;
+        platform.on_outgoing_keyframe(remote_peer)
+    }
+
     /// Create a new connection to a remote device
     pub(super) fn create_connection(
         &self,
@@ -1865,6 +2852,9 @@ where
                 Some(event) => self.terminate_active_call(false, event),
                 None => self.terminate_active_call(false, ApplicationEvent::EndedRemoteHangup),
             }
+        } else if self.call_is_held(call_id)? {
+            let event = app_event_override.unwrap_or(ApplicationEvent::EndedRemoteHangup);
+            self.terminate_held_call(false, event)
         } else {
             info!("remote_hangup(): ignoring for inactive call");
             Ok(())
@@ -1893,6 +2883,8 @@ where
 
         if self.call_is_active(call_id)? {
             self.terminate_active_call(true, ApplicationEvent::EndedTimeout)
+        } else if self.call_is_held(call_id)? {
+            self.terminate_held_call(true, ApplicationEvent::EndedTimeout)
         } else {
             info!("timeout(): ignoring for inactive call");
             Ok(())
@@ -1905,12 +2897,29 @@ where
 
         if self.call_is_active(call_id)? {
             self.terminate_active_call(true, ApplicationEvent::EndedConnectionFailure)
+        } else if self.call_is_held(call_id)? {
+            self.terminate_held_call(true, ApplicationEvent::EndedConnectionFailure)
         } else {
             info!("call_failed(): ignoring for inactive call");
             Ok(())
         }
     }
 
+    /// The active call's connection was lost after it was accepted and
+    /// couldn't be reconnected.
+    pub(super) fn network_changed(&mut self, call_id: CallId) -> Result<()> {
+        info!("network_changed(): call_id: {}", call_id);
+
+        if self.call_is_active(call_id)? {
+            self.terminate_active_call(true, ApplicationEvent::EndedNetworkChanged)
+        } else if self.call_is_held(call_id)? {
+            self.terminate_held_call(true, ApplicationEvent::EndedNetworkChanged)
+        } else {
+            info!("network_changed(): ignoring for inactive call");
+            Ok(())
+        }
+    }
+
     /// Internal error occurred on the active call.
     ///
     /// This shuts down the specified call if active and notifies the
@@ -1920,6 +2929,8 @@ where
 
         if self.call_is_active(call_id)? {
             self.terminate_active_call(true, ApplicationEvent::EndedInternalFailure)
+        } else if self.call_is_held(call_id)? {
+            self.terminate_held_call(true, ApplicationEvent::EndedInternalFailure)
         } else {
             info!("internal_error(): ignoring for inactive call");
             Ok(())
@@ -2004,6 +3015,13 @@ where
         self.send_next_message(Some(message_item))
     }
 
+    /// Record received ICE candidates dropped from a pending call's
+    /// buffer.
+    pub(super) fn record_dropped_pending_ice_candidates(&self, count: usize) -> Result<()> {
+        *self.pending_ice_dropped.lock()? += count as u64;
+        Ok(())
+    }
+
     /// Send ICE candidates to remote_peer via the application.
     pub(super) fn send_buffered_local_ice_candidates(
         &mut self,
@@ -2014,6 +3032,17 @@ where
         let call_id = call.call_id();
         info!("send_ice_candidates(): call_id: {}", call_id);
 
+        // Drop the candidates rather than fail, taking them out of the
+        // buffer so that later ones are sent again once there is room.
+        if self.message_queue.lock()?.is_full() {
+            let dropped = connection.take_buffered_local_ice_candidates()?;
+            warn!(
+                "send_ice_candidates(): suspended queue is full, dropping {} candidates",
+                dropped.len()
+            );
+            return Ok(());
+        }
+
         let ice_closure = Box::new(move |cm: &CallManager<T>| {
             let local_candidates = connection.take_buffered_local_ice_candidates()?;
 
@@ -2113,9 +3142,16 @@ where
         &self,
         client_id: group_call::ClientId,
         join_state: group_call::JoinState,
+        outgoing_audio_muted: Option<bool>,
     ) {
         info!("handle_join_state_changed():");
-        platform_handler!(self, handle_join_state_changed, client_id, join_state);
+        platform_handler!(
+            self,
+            handle_join_state_changed,
+            client_id,
+            join_state,
+            outgoing_audio_muted
+        );
     }
 
     fn handle_remote_devices_changed(
@@ -2148,10 +3184,27 @@ where
         );
     }
 
+    fn handle_incoming_interpreter_audio_track(
+        &mut self,
+        client_id: group_call::ClientId,
+        remote_demux_id: group_call::DemuxId,
+        incoming_interpreter_audio_track: AudioTrack,
+    ) {
+        info!("handle_incoming_interpreter_audio_track():");
+        platform_handler!(
+            self,
+            handle_incoming_interpreter_audio_track,
+            client_id,
+            remote_demux_id,
+            incoming_interpreter_audio_track
+        );
+    }
+
     fn handle_peek_changed(
         &self,
         client_id: group_call::ClientId,
         joined_members: &[group_call::UserId],
+        joined_devices: &[(group_call::UserId, group_call::DemuxId)],
         creator: Option<group_call::UserId>,
         era_id: Option<&str>,
         max_devices: Option<u32>,
@@ -2163,6 +3216,7 @@ where
             handle_peek_changed,
             client_id,
             joined_members,
+            joined_devices,
             creator,
             era_id,
             max_devices,
@@ -2170,6 +3224,85 @@ where
         );
     }
 
+    fn handle_era_changed(
+        &self,
+        client_id: group_call::ClientId,
+        old_era_id: Option<&str>,
+        new_era_id: Option<&str>,
+    ) {
+        info!("handle_era_changed():");
+        platform_handler!(self, handle_era_changed, client_id, old_era_id, new_era_id);
+    }
+
+    fn handle_outgoing_keyframe(
+        &self,
+        client_id: group_call::ClientId,
+        local_demux_id: group_call::DemuxId,
+    ) {
+        debug!("handle_outgoing_keyframe():");
+        platform_handler!(self, handle_outgoing_keyframe, client_id, local_demux_id);
+    }
+
+    fn handle_participant_quality_changed(
+        &self,
+        client_id: group_call::ClientId,
+        remote_demux_id: group_call::DemuxId,
+        quality: u8,
+    ) {
+        debug!("handle_participant_quality_changed():");
+        platform_handler!(
+            self,
+            handle_participant_quality_changed,
+            client_id,
+            remote_demux_id,
+            quality
+        );
+    }
+
+    fn handle_audio_levels(
+        &self,
+        client_id: group_call::ClientId,
+        audio_levels: Vec<(group_call::DemuxId, u8)>,
+    ) {
+        trace!("handle_audio_levels():");
+        platform_handler!(self, handle_audio_levels, client_id, audio_levels);
+    }
+
+    fn handle_media_keys_received(
+        &self,
+        client_id: group_call::ClientId,
+        remote_demux_id: group_call::DemuxId,
+    ) {
+        debug!("handle_media_keys_received():");
+        platform_handler!(self, handle_media_keys_received, client_id, remote_demux_id);
+    }
+
+    fn handle_candidate_pair_changed(
+        &self,
+        client_id: group_call::ClientId,
+        local_demux_id: group_call::DemuxId,
+        candidate_pair_type: CandidatePairType,
+    ) {
+        debug!("handle_candidate_pair_changed():");
+        platform_handler!(
+            self,
+            handle_candidate_pair_changed,
+            client_id,
+            local_demux_id,
+            candidate_pair_type
+        );
+    }
+
+    fn handle_join_rejected(
+        &self,
+        client_id: group_call::ClientId,
+        http_status: u16,
+        message: Option<&str>,
+    ) {
+        info!("handle_join_rejected({}):", http_status);
+        platform_handler!(self, handle_join_rejected, client_id, http_status, message);
+    }
+
     fn handle_ended(&self, client_id: group_call::ClientId, reason: group_call::EndReason) {
         info!("handle_ended({:?}):", reason);
         platform_handler!(self, handle_ended, client_id, reason);
@@ -2217,6 +3350,26 @@ where
         membership_proof: group_call::MembershipProof,
         group_members: Vec<group_call::GroupMemberInfo>,
     ) {
+        self.peek_group_call_with_timeout(request_id, url, membership_proof, group_members, None)
+    }
+
+    /// Like peek_group_call(), but if the SFU hasn't responded within the
+    /// timeout, handle_peek_timed_out() is called for the request instead
+    /// of handle_peek_response(), and a response arriving later is dropped.
+    pub fn peek_group_call_with_timeout(
+        &self,
+        request_id: u32,
+        url: String,
+        membership_proof: group_call::MembershipProof,
+        group_members: Vec<group_call::GroupMemberInfo>,
+        timeout: Option<Duration>,
+    ) {
+        if let Some(timeout) = timeout {
+            if let Err(e) = self.start_peek_timer(request_id, timeout) {
+                error!("peek_group_call(): failed to start the timeout: {}", e);
+            }
+        }
+
         let http_client = Box::new(self.clone());
         let mut sfu_client = SfuClient::new(http_client, url);
         let call_manager = self.clone();
@@ -2226,14 +3379,21 @@ where
             Box::new(move |peek_info| {
                 info!("handle_peek_response");
 
+                if timeout.is_some() && !call_manager.cancel_peek_timer(request_id) {
+                    info!("handle_peek_response(): request_id {} already timed out", request_id);
+                    return;
+                }
+
                 // Treat failures the same as peeking into empty calls.
+                let peek_info = peek_info.unwrap_or_default();
+                let joined_devices = peek_info.joined_devices();
                 let group_call::PeekInfo {
                     devices,
                     creator,
                     era_id,
                     max_devices,
                     device_count,
-                } = peek_info.unwrap_or_default();
+                } = peek_info;
 
                 let members: HashSet<group_call::UserId> = devices
                     .into_iter()
@@ -2246,6 +3406,7 @@ where
                     handle_peek_response,
                     request_id,
                     &members[..],
+                    &joined_devices[..],
                     creator,
                     era_id.as_deref(),
                     max_devices,
@@ -2255,6 +3416,56 @@ where
         );
     }
 
+    fn start_peek_timer(&self, request_id: u32, timeout: Duration) -> Result<()> {
+        let (cancel_sender, cancel_receiver) = oneshot::channel::<()>();
+        // Replacing the sender of an earlier peek with the same request ID
+        // drops it, which cancels that peek's timer.
+        self.peek_timeouts.lock()?.insert(request_id, cancel_sender);
+
+        let call_manager = self.clone();
+        let peek_timeout_future = async move {
+            let sleep = tokio::time::sleep(timeout);
+            pin_mut!(sleep);
+            if let future::Either::Right(_) = future::select(sleep, cancel_receiver).await {
+                return Ok(());
+            }
+            let timed_out = match call_manager.peek_timeouts.lock() {
+                Ok(mut peek_timeouts) => peek_timeouts.remove(&request_id).is_some(),
+                Err(e) => {
+                    error!("{}", e);
+                    false
+                }
+            };
+            if timed_out {
+                info!("handle_peek_timed_out(): request_id: {}", request_id);
+                platform_handler!(call_manager, handle_peek_timed_out, request_id);
+            }
+            Ok(())
+        };
+        self.clone().worker_spawn(peek_timeout_future)
+    }
+
+    /// Stops the timeout of a peek, returning false if it already fired.
+    fn cancel_peek_timer(&self, request_id: u32) -> bool {
+        match self.peek_timeouts.lock() {
+            Ok(mut peek_timeouts) => match peek_timeouts.remove(&request_id) {
+                Some(cancel_sender) => {
+                    let _ = cancel_sender.send(());
+                    true
+                }
+                None => false,
+            },
+            Err(e) => {
+                error!("{}", e);
+                true
+            }
+        }
+    }
+
+    /// Create a group call client.  When rejoining a call after the app was
+    /// terminated, pass the era and demux ID from before as the
+    /// previous_session so that our old device is reconciled rather than
+    /// shown as another participant.  See group_call::PreviousSession.
     pub fn create_group_call_client(
         &mut self,
         group_id: group_call::GroupId,
@@ -2262,14 +3473,31 @@ where
         peer_connection_factory: Option<PeerConnectionFactory>,
         outgoing_audio_track: AudioTrack,
         outgoing_video_track: VideoTrack,
+        previous_session: Option<group_call::PreviousSession>,
     ) -> Result<group_call::ClientId> {
         info!("create_group_call_client():");
         debug!(
-            "  group_id: {} sfu_url: {}",
+            "  group_id: {} sfu_url: {} previous_session: {:?}",
             uuid_to_string(&group_id),
-            sfu_url
+            sfu_url,
+            previous_session
         );
 
+        if outgoing_audio_track.rffi().is_null() {
+            return Err(RingRtcError::NullPointer(
+                "create_group_call_client".to_string(),
+                "outgoing_audio_track".to_string(),
+            )
+            .into());
+        }
+        if outgoing_video_track.rffi().is_null() {
+            return Err(RingRtcError::NullPointer(
+                "create_group_call_client".to_string(),
+                "outgoing_video_track".to_string(),
+            )
+            .into());
+        }
+
         let mut next_group_call_client_id = self.next_group_call_client_id.lock()?;
         if *next_group_call_client_id == group_call::INVALID_CLIENT_ID {
             *next_group_call_client_id += 1;
@@ -2287,6 +3515,7 @@ where
             peer_connection_factory,
             outgoing_audio_track,
             Some(outgoing_video_track),
+            previous_session,
         )?;
 
         let mut client_by_id = self.group_call_by_client_id.lock()?;
@@ -2297,6 +3526,22 @@ where
         Ok(client_id)
     }
 
+    /// Disconnect and remove all group call clients, waiting for each
+    /// one to end.
+    fn end_group_call_clients(&mut self) -> Result<()> {
+        // Take the clients out of the map first so that the lock isn't
+        // held while their actors call back into the call manager.
+        let clients: Vec<(group_call::ClientId, group_call::Client)> =
+            self.group_call_by_client_id.lock()?.drain().collect();
+
+        for (client_id, client) in clients {
+            info!("end_group_call_clients(): ending client_id: {}", client_id);
+            client.disconnect_and_wait();
+        }
+
+        Ok(())
+    }
+
     pub fn delete_group_call_client(&mut self, client_id: group_call::ClientId) {
         info!("delete_group_call_client(): id: {}", client_id);
 
@@ -2362,6 +3607,18 @@ where
         group_call_api_handler!(self, client_id, join);
     }
 
+    pub fn join_with_options(
+        &mut self,
+        client_id: group_call::ClientId,
+        options: group_call::JoinOptions,
+    ) {
+        info!(
+            "join_with_options(): id: {} options: {:?}",
+            client_id, options
+        );
+        group_call_api_handler!(self, client_id, join_with_options, options);
+    }
+
     pub fn leave(&mut self, client_id: group_call::ClientId) {
         info!("leave(): id: {}", client_id);
         group_call_api_handler!(self, client_id, leave);
@@ -2382,6 +3639,11 @@ where
         group_call_api_handler!(self, client_id, set_outgoing_video_muted, muted);
     }
 
+    pub fn refresh_peek(&mut self, client_id: group_call::ClientId) {
+        info!("refresh_peek(): id: {}", client_id);
+        group_call_api_handler!(self, client_id, refresh_peek);
+    }
+
     pub fn resend_media_keys(&mut self, client_id: group_call::ClientId) {
         info!("resend_media_keys(): id: {}", client_id);
         group_call_api_handler!(self, client_id, resend_media_keys);
@@ -2410,6 +3672,123 @@ where
         group_call_api_handler!(self, client_id, request_video, rendered_resolutions);
     }
 
+    pub fn set_max_incoming_videos(&mut self, client_id: group_call::ClientId, max: u32) {
+        info!("set_max_incoming_videos(): id: {} max: {}", client_id, max);
+        group_call_api_handler!(self, client_id, set_max_incoming_videos, max);
+    }
+
+    pub fn set_remote_video_hidden(
+        &mut self,
+        client_id: group_call::ClientId,
+        demux_id: group_call::DemuxId,
+        hidden: bool,
+    ) {
+        info!(
+            "set_remote_video_hidden(): id: {} demux_id: {} hidden: {}",
+            client_id, demux_id, hidden
+        );
+        group_call_api_handler!(self, client_id, set_remote_video_hidden, demux_id, hidden);
+    }
+
+    pub fn set_low_resource_mode(&mut self, client_id: group_call::ClientId, enabled: bool) {
+        info!(
+            "set_low_resource_mode(): id: {} enabled: {}",
+            client_id, enabled
+        );
+        group_call_api_handler!(self, client_id, set_low_resource_mode, enabled);
+    }
+
+    pub fn set_roster_update_interval(
+        &mut self,
+        client_id: group_call::ClientId,
+        interval: Duration,
+    ) {
+        info!(
+            "set_roster_update_interval(): id: {} interval: {:?}",
+            client_id, interval
+        );
+        group_call_api_handler!(self, client_id, set_roster_update_interval, interval);
+    }
+
+    pub fn set_outgoing_video_caps(
+        &mut self,
+        client_id: group_call::ClientId,
+        caps: VideoEncoderCaps,
+    ) {
+        info!(
+            "set_outgoing_video_caps(): id: {} caps: {:?}",
+            client_id, caps
+        );
+        group_call_api_handler!(self, client_id, set_outgoing_video_caps, caps);
+    }
+
+    pub fn outgoing_simulcast_config(
+        &self,
+        client_id: group_call::ClientId,
+    ) -> Result<Vec<LayerConfig>> {
+        info!("outgoing_simulcast_config(): id: {}", client_id);
+        // Don't hold the lock while waiting on the client's actor, which
+        // may be calling back into the call manager.
+        let group_call = self
+            .group_call_by_client_id
+            .lock()?
+            .get(&client_id)
+            .cloned()
+            .ok_or(RingRtcError::GroupCallClientNotFound(client_id))?;
+        Ok(group_call.outgoing_simulcast_config())
+    }
+
+    pub fn current_speaker(
+        &self,
+        client_id: group_call::ClientId,
+    ) -> Result<Option<group_call::DemuxId>> {
+        debug!("current_speaker(): id: {}", client_id);
+        // Don't hold the lock while waiting on the client's actor.
+        let group_call = self
+            .group_call_by_client_id
+            .lock()?
+            .get(&client_id)
+            .cloned()
+            .ok_or(RingRtcError::GroupCallClientNotFound(client_id))?;
+        Ok(group_call.current_speaker())
+    }
+
+    pub fn set_interpreter_track(&mut self, client_id: group_call::ClientId, track: AudioTrack) {
+        info!("set_interpreter_track(): id: {}", client_id);
+        group_call_api_handler!(self, client_id, set_interpreter_track, track);
+    }
+
+    pub fn set_interpreter_audio_enabled(
+        &mut self,
+        client_id: group_call::ClientId,
+        enabled: bool,
+    ) {
+        info!(
+            "set_interpreter_audio_enabled(): id: {} enabled: {}",
+            client_id, enabled
+        );
+        group_call_api_handler!(self, client_id, set_interpreter_audio_enabled, enabled);
+    }
+
+    pub fn set_remote_interpreter_audio_enabled(
+        &mut self,
+        client_id: group_call::ClientId,
+        demux_id: group_call::DemuxId,
+        enabled: bool,
+    ) {
+        info!(
+            "set_remote_interpreter_audio_enabled(): id: {} demux_id: {} enabled: {}",
+            client_id, demux_id, enabled
+        );
+        group_call_api_handler!(
+            self,
+            client_id,
+            set_remote_interpreter_audio_enabled,
+            demux_id,
+            enabled
+        );
+    }
+
     pub fn set_group_members(
         &mut self,
         client_id: group_call::ClientId,
@@ -36,21 +36,28 @@ use crate::common::{
     Result,
     RingBench,
 };
-use crate::core::bandwidth_mode::BandwidthMode;
+use crate::core::bandwidth_mode::{AudioEncoderPreset, BandwidthMode, EffectiveBandwidthMode};
 use crate::core::call::Call;
 use crate::core::call_mutex::CallMutex;
 use crate::core::connection_fsm::{ConnectionEvent, ConnectionStateMachine};
 use crate::core::platform::Platform;
 use crate::core::signaling;
-use crate::core::util::{ptr_as_box, redact_string, TaskQueueRuntime};
+use crate::core::util::{ptr_as_box, redact_string, sha256_as_hexstring, TaskQueueRuntime};
 use crate::error::RingRtcError;
 use crate::protobuf;
 
 use crate::webrtc::data_channel::DataChannel;
 use crate::webrtc::ice_gatherer::IceGatherer;
-use crate::webrtc::media::MediaStream;
+use crate::webrtc::media::{MediaStream, VideoEncoderCaps};
 use crate::webrtc::peer_connection::PeerConnection;
-use crate::webrtc::peer_connection_observer::{IceConnectionState, PeerConnectionObserverTrait};
+use crate::webrtc::peer_connection_observer::{
+    IceCandidateNetwork,
+    IceConnectionState,
+    IceInterfacePolicy,
+    NetworkRoute,
+    PeerConnectionObserverTrait,
+};
+use crate::webrtc::rtp;
 use crate::webrtc::sdp_observer::{
     create_csd_observer,
     create_ssd_observer,
@@ -58,7 +65,12 @@ use crate::webrtc::sdp_observer::{
     SrtpCryptoSuite,
     SrtpKey,
 };
-use crate::webrtc::stats_observer::{create_stats_observer, StatsObserver};
+use crate::webrtc::stats_observer::{
+    create_stats_observer,
+    AudioPlayoutStatistics,
+    CallStatistics,
+    StatsObserver,
+};
 
 /// The periodic tick interval. Used to generate stats and to retransmit data channel messages.
 pub const TICK_PERIOD_SEC: u64 = 1;
@@ -66,6 +78,109 @@ pub const TICK_PERIOD_SEC: u64 = 1;
 /// The stats period, how often to get and log them. Assumes tick period is 1 second.
 pub const STATS_PERIOD_SEC: u64 = 10;
 
+/// The largest application payload that can be sent in a single data
+/// channel message.
+pub const MAX_DATA_MESSAGE_SIZE: usize = 1024;
+
+/// The DTMF tones that can be sent or received: the 16 RFC 4733 events
+/// for the digits, `*`, `#` and `A` through `D`.
+pub const VALID_DTMF_TONES: &str = "0123456789*#ABCD";
+
+/// The shortest and longest duration, in milliseconds, of a DTMF tone.
+/// These are the limits of WebRTC's DtmfSender.
+pub const MIN_DTMF_DURATION_MS: u32 = 40;
+pub const MAX_DTMF_DURATION_MS: u32 = 6000;
+
+/// The shortest gap, in milliseconds, between DTMF tones.
+pub const MIN_DTMF_GAP_MS: u32 = 30;
+
+/// The payload type of RFC 4733 telephone-events in 1:1 calls.  This
+/// must match TELEPHONE_EVENT_PT in Rust_sessionDescriptionFromV4().
+pub const TELEPHONE_EVENT_PAYLOAD_TYPE: rtp::PayloadType = 110;
+
+/// Fails unless `tones` is one or more of
+/// [VALID_DTMF_TONES](constant.VALID_DTMF_TONES.html).
+pub fn validate_dtmf_tones(tones: &str) -> Result<()> {
+    if tones.is_empty() || !tones.chars().all(|tone| VALID_DTMF_TONES.contains(tone)) {
+        return Err(RingRtcError::InvalidDtmfTones(tones.to_string()).into());
+    }
+    Ok(())
+}
+
+/// Returns the DTMF tone of an RFC 4733 telephone-event payload, or None
+/// if the payload is too short or the event isn't a DTMF tone.
+///
+/// Events 0 through 15 are the tones in the order of
+/// [VALID_DTMF_TONES](constant.VALID_DTMF_TONES.html).
+pub fn dtmf_tone_from_telephone_event(payload: &[u8]) -> Option<char> {
+    // event (8 bits), E/R/volume (8 bits), duration (16 bits)
+    if payload.len() < 4 {
+        return None;
+    }
+    VALID_DTMF_TONES.chars().nth(payload[0] as usize)
+}
+
+/// The smallest audio jitter buffer target, in milliseconds, that can be
+/// requested. This is about one audio frame.
+pub const MIN_AUDIO_JITTER_BUFFER_TARGET_MS: u32 = 20;
+
+/// The largest audio jitter buffer target, in milliseconds, that can be
+/// requested.
+pub const MAX_AUDIO_JITTER_BUFFER_TARGET_MS: u32 = 1000;
+
+/// The highest Opus in-band FEC level that can be requested. Level 0
+/// turns FEC off. The encoder config only exposes FEC as on or off, with
+/// the encoder itself scaling the redundancy to the reported packet loss,
+/// so every level above 0 behaves the same.
+pub const MAX_AUDIO_FEC_LEVEL: u8 = 1;
+
+/// The number of groups of decimal digits in a security fingerprint.
+const SECURITY_FINGERPRINT_GROUPS: usize = 6;
+
+/// The number of decimal digits in each group of a security fingerprint.
+const SECURITY_FINGERPRINT_GROUP_DIGITS: usize = 5;
+
+/// The number of hex digits of the hash that each group is taken from.
+/// 32 bits keep the modulo bias of the decimal digits below 0.01%.
+const SECURITY_FINGERPRINT_GROUP_HEX_DIGITS: usize = 8;
+
+/// The length of a security fingerprint: space separated groups of digits.
+pub const SECURITY_FINGERPRINT_LEN: usize =
+    SECURITY_FINGERPRINT_GROUPS * (SECURITY_FINGERPRINT_GROUP_DIGITS + 1) - 1;
+
+/// How long a connection that dropped after the call was accepted waits
+/// for ICE to recover before giving up on the call.
+///
+/// ICE keeps trying to reconnect on its own; each attempt gives it another
+/// delay, starting at initial_delay and growing by multiplier (treated as
+/// at least 1) up to max_delay.  If the connection is still reconnecting
+/// after max_attempts, the call ends with a connection failure.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ReconnectPolicy {
+    pub initial_delay: Duration,
+    pub multiplier:    f64,
+    pub max_delay:     Duration,
+    pub max_attempts:  u32,
+}
+
+impl ReconnectPolicy {
+    /// The delay before the given attempt, counting from 0.
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let delay = self.initial_delay.as_secs_f64()
+            * self.multiplier.max(1.0).powi(attempt.min(i32::MAX as u32) as i32);
+        if delay.is_finite() && delay < self.max_delay.as_secs_f64() {
+            Duration::from_secs_f64(delay)
+        } else {
+            self.max_delay
+        }
+    }
+
+    /// The delays before each of the attempts, in order.
+    pub fn delays(&self) -> impl Iterator<Item = Duration> + '_ {
+        (0..self.max_attempts).map(move |attempt| self.delay(attempt))
+    }
+}
+
 /// Connection observer status notification types
 /// Sent from the Connection to the parent Call object
 #[derive(Copy, Debug, PartialEq, Eq, Hash)]
@@ -88,6 +203,10 @@ pub enum ConnectionObserverEvent {
     /// The call failed to connect during ICE negotiation.
     IceFailed,
 
+    /// ICE failed while reconnecting a connection that dropped after the
+    /// call was accepted, usually because the network changed.
+    IceFailedWhileReconnecting,
+
     /// The connection temporarily disconnected and it attempting to reconnect.
     ReconnectingAfterAccepted,
 
@@ -307,6 +426,35 @@ impl BandwidthModes {
     }
 }
 
+/// An offer or answer held back until ICE gathering completes, so that
+/// its SDP can carry every local candidate.
+pub enum HeldDescription {
+    Offer(signaling::Offer),
+    Answer(signaling::SendAnswer),
+}
+
+impl HeldDescription {
+    fn with_ice_candidates(self, candidates: &[signaling::IceCandidate]) -> Result<Self> {
+        match self {
+            HeldDescription::Offer(offer) => Ok(HeldDescription::Offer(
+                offer.with_ice_candidates(candidates)?,
+            )),
+            HeldDescription::Answer(send) => Ok(HeldDescription::Answer(signaling::SendAnswer {
+                answer:             send.answer.with_ice_candidates(candidates)?,
+                receiver_device_id: send.receiver_device_id,
+            })),
+        }
+    }
+}
+
+/// Tracks ICE gathering when trickle ICE is disabled, since the offer or
+/// answer can be ready either before or after gathering completes.
+#[derive(Default)]
+struct NonTrickleIce {
+    gathering_complete: bool,
+    held_description:   Option<HeldDescription>,
+}
+
 /// Represents the connection between a local client and one remote
 /// peer.
 ///
@@ -348,6 +496,45 @@ where
     tick_context:                  Arc<CallMutex<TickContext>>,
     /// The accumulated state of sending messages over the data channel
     accumulated_dcm_state:         Arc<CallMutex<protobuf::data_channel::Data>>,
+    /// Digest of the identity keys the SRTP keys were negotiated with.
+    /// Not set until the offer/answer exchange is complete.
+    security_fingerprint:          Arc<CallMutex<Option<String>>>,
+    /// Audio encoder settings for the media type of the call, applied
+    /// along with each bandwidth mode.
+    audio_encoder_preset:          Arc<CallMutex<AudioEncoderPreset>>,
+    /// Whether the call has been put on hold locally, which keeps
+    /// outgoing media disabled even across reconnects.
+    media_on_hold:                 Arc<CallMutex<bool>>,
+    /// Opus in-band FEC level, applied along with each bandwidth mode.
+    audio_fec_level:               Arc<CallMutex<u8>>,
+    /// Local network interfaces allowed to contribute ICE candidates.
+    ice_interface_policy:          Arc<CallMutex<IceInterfacePolicy>>,
+    /// IP address family to bias ICE candidate priorities towards.
+    ip_preference:                 Arc<CallMutex<signaling::IpPreference>>,
+    /// Upper bounds on outgoing video, applied along with each bandwidth
+    /// mode.
+    outgoing_video_caps:           Arc<CallMutex<VideoEncoderCaps>>,
+    /// Hard cap on the send bitrate, applied on top of whatever the
+    /// bandwidth mode allows.
+    max_bitrate_cap:               Arc<CallMutex<Option<DataRate>>>,
+    /// Whether local ICE candidates are sent as they are gathered, rather
+    /// than embedded in the offer or answer.
+    trickle_ice:                   Arc<CallMutex<bool>>,
+    /// The offer or answer waiting on ICE gathering, if trickle ICE is
+    /// disabled.
+    non_trickle_ice:               Arc<CallMutex<NonTrickleIce>>,
+    /// Whether the PeerConnectionObserver was created with frame
+    /// encryption enabled.
+    frame_encryption:              Arc<CallMutex<bool>>,
+    /// Overrides the priorities of local ICE candidates, if set.
+    candidate_prioritizer:         Arc<CallMutex<Option<Arc<dyn signaling::CandidatePrioritizer>>>>,
+    /// How long to wait for ICE to recover after dropping, if limited.
+    reconnect_policy:              Option<ReconnectPolicy>,
+    /// Runtime that manages giving up on reconnecting.
+    reconnect_runtime:             Arc<CallMutex<Option<TaskQueueRuntime>>>,
+    /// RTP timestamp of the last telephone-event received, which is the
+    /// same in every packet of an event.
+    telephone_event_timestamp:     Arc<CallMutex<Option<rtp::Timestamp>>>,
 }
 
 impl<T> fmt::Display for Connection<T>
@@ -429,6 +616,21 @@ where
             connection_type:               self.connection_type,
             tick_context:                  Arc::clone(&self.tick_context),
             accumulated_dcm_state:         Arc::clone(&self.accumulated_dcm_state),
+            security_fingerprint:          Arc::clone(&self.security_fingerprint),
+            audio_encoder_preset:          Arc::clone(&self.audio_encoder_preset),
+            media_on_hold:                 Arc::clone(&self.media_on_hold),
+            audio_fec_level:               Arc::clone(&self.audio_fec_level),
+            ice_interface_policy:          Arc::clone(&self.ice_interface_policy),
+            ip_preference:                 Arc::clone(&self.ip_preference),
+            outgoing_video_caps:           Arc::clone(&self.outgoing_video_caps),
+            max_bitrate_cap:               Arc::clone(&self.max_bitrate_cap),
+            trickle_ice:                   Arc::clone(&self.trickle_ice),
+            non_trickle_ice:               Arc::clone(&self.non_trickle_ice),
+            frame_encryption:              Arc::clone(&self.frame_encryption),
+            candidate_prioritizer:         Arc::clone(&self.candidate_prioritizer),
+            reconnect_policy:              self.reconnect_policy,
+            reconnect_runtime:             Arc::clone(&self.reconnect_runtime),
+            telephone_event_timestamp:     Arc::clone(&self.telephone_event_timestamp),
         }
     }
 }
@@ -451,6 +653,8 @@ where
 
         let call_id = call.call_id();
         let direction = call.direction();
+        let audio_encoder_preset = AudioEncoderPreset::for_call_media_type(call.media_type());
+        let reconnect_policy = call.reconnect_policy()?;
 
         let webrtc = WebRtcData {
             peer_connection: None,
@@ -494,6 +698,33 @@ where
                 protobuf::data_channel::Data::default(),
                 "accumulated_dcm_state",
             )),
+            security_fingerprint: Arc::new(CallMutex::new(None, "security_fingerprint")),
+            audio_encoder_preset: Arc::new(CallMutex::new(
+                audio_encoder_preset,
+                "audio_encoder_preset",
+            )),
+            media_on_hold: Arc::new(CallMutex::new(false, "media_on_hold")),
+            audio_fec_level: Arc::new(CallMutex::new(MAX_AUDIO_FEC_LEVEL, "audio_fec_level")),
+            ice_interface_policy: Arc::new(CallMutex::new(
+                IceInterfacePolicy::default(),
+                "ice_interface_policy",
+            )),
+            ip_preference: Arc::new(CallMutex::new(
+                signaling::IpPreference::default(),
+                "ip_preference",
+            )),
+            outgoing_video_caps: Arc::new(CallMutex::new(
+                VideoEncoderCaps::default(),
+                "outgoing_video_caps",
+            )),
+            max_bitrate_cap: Arc::new(CallMutex::new(None, "max_bitrate_cap")),
+            trickle_ice: Arc::new(CallMutex::new(true, "trickle_ice")),
+            non_trickle_ice: Arc::new(CallMutex::new(NonTrickleIce::default(), "non_trickle_ice")),
+            frame_encryption: Arc::new(CallMutex::new(false, "frame_encryption")),
+            candidate_prioritizer: Arc::new(CallMutex::new(None, "candidate_prioritizer")),
+            reconnect_policy,
+            reconnect_runtime: Arc::new(CallMutex::new(None, "reconnect_runtime")),
+            telephone_event_timestamp: Arc::new(CallMutex::new(None, "telephone_event_timestamp")),
         };
 
         connection.init_connection_ptr()?;
@@ -551,7 +782,11 @@ where
             let (local_secret, local_public_key) = generate_local_secret_and_public_key()?;
             let v4_offer = offer.to_v4(local_public_key.as_bytes().to_vec(), bandwidth_mode)?;
 
-            if bandwidth_mode.use_v4_only() {
+            // V4 has no SDP to embed ICE candidates in, so without trickle
+            // ICE only the V3/V2 SDP is offered.
+            let trickle_ice = self.trickle_ice()?;
+
+            if bandwidth_mode.use_v4_only() && trickle_ice {
                 info!("Using V4 signaling for outgoing offer: {:?}", v4_offer);
 
                 // The only purpose of this is to start gathering ICE candidates.
@@ -583,7 +818,7 @@ where
                 let offer = signaling::Offer::from_v4_and_v3_and_v2(
                     call_media_type,
                     local_public_key.as_bytes().to_vec(),
-                    Some(v4_offer),
+                    if trickle_ice { Some(v4_offer) } else { None },
                     v2_offer_sdp,
                 )?;
 
@@ -630,15 +865,15 @@ where
 
             let mut bandwidth_modes = self.bandwidth_modes.lock()?;
 
+            let v4_answer = received.answer.to_v4();
             let (mut offer, mut answer, remote_public_key, bandwidth_mode) =
-                if let (Some(v4_offer), Some(v4_answer)) = (offer.to_v4(), received.answer.to_v4())
-                {
+                if let (Some(v4_offer), Some(v4_answer)) = (offer.v4_ref(), v4_answer) {
                     // Set the remote mode based on the bitrate in the answer.
                     bandwidth_modes.set_remote_from_bitrate(v4_answer.max_bitrate_bps);
                     // Get the lowest bandwidth mode and use it for constraints.
                     let bandwidth_mode = bandwidth_modes.min();
 
-                    let offer = SessionDescription::offer_from_v4(&v4_offer)?;
+                    let offer = SessionDescription::offer_from_v4(v4_offer)?;
                     let answer = SessionDescription::answer_from_v4(&v4_answer)?;
 
                     info!(
@@ -686,6 +921,10 @@ where
                 )?;
                 offer.disable_dtls_and_set_srtp_key(&offer_key)?;
                 answer.disable_dtls_and_set_srtp_key(&answer_key)?;
+                *self.security_fingerprint.lock()? = Some(security_fingerprint(
+                    caller_identity_key,
+                    callee_identity_key,
+                ));
             }
 
             let observer = create_ssd_observer();
@@ -745,44 +984,53 @@ where
 
             let mut bandwidth_modes = self.bandwidth_modes.lock()?;
 
-            let v4_offer = received.offer.to_v4();
-            let (mut offer, remote_public_key, bandwidth_mode) =
-                if let Some(v4_offer) = v4_offer.as_ref() {
-                    // Set the remote mode based on the bitrate in the offer.
-                    bandwidth_modes.set_remote_from_bitrate(v4_offer.max_bitrate_bps);
-                    // Get the lowest bandwidth mode and use it for constraints.
-                    let bandwidth_mode = bandwidth_modes.min();
+            // Without trickle ICE the candidates go in the answer's SDP, so
+            // prefer V3/V2 whenever the offer has it.
+            let trickle_ice = self.trickle_ice()?;
+            let v4_offer = match received.offer.v4_ref() {
+                Some(_) if !trickle_ice && received.offer.v3_or_v2_params_ref().is_ok() => None,
+                v4_offer => v4_offer,
+            };
+            if v4_offer.is_some() && !trickle_ice {
+                warn!("start_incoming(): V4-only offer has no SDP for ICE candidates, trickling");
+                *self.trickle_ice.lock()? = true;
+            }
+            let (mut offer, remote_public_key, bandwidth_mode) = if let Some(v4_offer) = v4_offer {
+                // Set the remote mode based on the bitrate in the offer.
+                bandwidth_modes.set_remote_from_bitrate(v4_offer.max_bitrate_bps);
+                // Get the lowest bandwidth mode and use it for constraints.
+                let bandwidth_mode = bandwidth_modes.min();
 
-                    info!(
-                        "Using V4 signaling for incoming offer: {:?} {}",
-                        v4_offer, bandwidth_modes
-                    );
+                info!(
+                    "Using V4 signaling for incoming offer: {:?} {}",
+                    v4_offer, bandwidth_modes
+                );
 
-                    let offer = SessionDescription::offer_from_v4(&v4_offer)?;
+                let offer = SessionDescription::offer_from_v4(v4_offer)?;
 
-                    (offer, v4_offer.public_key.clone(), bandwidth_mode)
-                } else {
-                    let (offer_sdp, remote_public_key) = received.offer.to_v3_or_v2_params()?;
+                (offer, v4_offer.public_key.as_deref(), bandwidth_mode)
+            } else {
+                let (offer_sdp, remote_public_key) = received.offer.v3_or_v2_params_ref()?;
 
-                    // For V2/3 we'll just use the desired local mode on this end and ignore the remote.
-                    let bandwidth_mode = bandwidth_modes.local_bandwidth_mode;
+                // For V2/3 we'll just use the desired local mode on this end and ignore the remote.
+                let bandwidth_mode = bandwidth_modes.local_bandwidth_mode;
 
-                    if remote_public_key.is_some() {
-                        info!(
-                            "Using V3 signaling for incoming offer: {} bandwidth_mode: {}",
-                            offer_sdp, bandwidth_mode
-                        );
-                    } else {
-                        info!(
-                            "Using V2 signaling for incoming offer: {} bandwidth_mode: {}",
-                            offer_sdp, bandwidth_mode
-                        );
-                    }
+                if remote_public_key.is_some() {
+                    info!(
+                        "Using V3 signaling for incoming offer: {} bandwidth_mode: {}",
+                        offer_sdp, bandwidth_mode
+                    );
+                } else {
+                    info!(
+                        "Using V2 signaling for incoming offer: {} bandwidth_mode: {}",
+                        offer_sdp, bandwidth_mode
+                    );
+                }
 
-                    let offer = SessionDescription::offer_from_sdp(offer_sdp)?;
+                let offer = SessionDescription::offer_from_sdp(offer_sdp.to_owned())?;
 
-                    (offer, remote_public_key, bandwidth_mode)
-                };
+                (offer, remote_public_key, bandwidth_mode)
+            };
 
             let (local_secret, local_public_key) = generate_local_secret_and_public_key()?;
             let answer_key = match remote_public_key {
@@ -795,11 +1043,15 @@ where
                         answer_key,
                     } = negotiate_srtp_keys(
                         &local_secret,
-                        &remote_public_key,
+                        remote_public_key,
                         caller_identity_key,
                         callee_identity_key,
                     )?;
                     offer.disable_dtls_and_set_srtp_key(&offer_key)?;
+                    *self.security_fingerprint.lock()? = Some(security_fingerprint(
+                        caller_identity_key,
+                        callee_identity_key,
+                    ));
                     Some(answer_key)
                 }
             };
@@ -866,8 +1118,10 @@ where
                 RingBench::WebRTC,
                 format!("ice_candidates({})", remote_ice_candidates.len())
             );
+            let ip_preference = *self.ip_preference.lock()?;
             for remote_ice_candidate in remote_ice_candidates {
-                peer_connection.add_ice_candidate(&remote_ice_candidate)?;
+                peer_connection
+                    .add_ice_candidate(&remote_ice_candidate.prioritized_for(ip_preference)?)?;
             }
 
             self.set_state(ConnectionState::ConnectingBeforeAccepted)?;
@@ -919,10 +1173,12 @@ where
         *state = new_state;
         if new_state == ConnectionState::ConnectedAndAccepted {
             // Now that we are accepted, we can enable outgoing audio and incoming RTP
+            // (unless the call is on hold).
+            let on_hold = *self.media_on_hold.lock()?;
             let webrtc = self.webrtc.lock()?;
             let pc = webrtc.peer_connection()?;
-            pc.set_outgoing_media_enabled(true);
-            pc.set_incoming_media_enabled(true);
+            pc.set_outgoing_media_enabled(!on_hold);
+            pc.set_incoming_media_enabled(!on_hold);
         }
         Ok(())
     }
@@ -1091,6 +1347,221 @@ where
         self.apply_bandwidth_mode(webrtc.peer_connection()?, &bandwidth_mode)
     }
 
+    /// Set the target delay of the receive side audio jitter buffer,
+    /// returning the target actually applied.
+    ///
+    /// The target is clamped to between
+    /// [MIN_AUDIO_JITTER_BUFFER_TARGET_MS](constant.MIN_AUDIO_JITTER_BUFFER_TARGET_MS.html) and
+    /// [MAX_AUDIO_JITTER_BUFFER_TARGET_MS](constant.MAX_AUDIO_JITTER_BUFFER_TARGET_MS.html).
+    /// Smaller targets lower the latency, but leave less room to absorb
+    /// network jitter, so playback glitches more often.
+    pub fn set_audio_jitter_buffer_target(&self, target_ms: u32) -> Result<u32> {
+        let target_ms = target_ms
+            .max(MIN_AUDIO_JITTER_BUFFER_TARGET_MS)
+            .min(MAX_AUDIO_JITTER_BUFFER_TARGET_MS);
+        info!("set_audio_jitter_buffer_target(): {}ms", target_ms);
+
+        let webrtc = self.webrtc.lock()?;
+        webrtc
+            .peer_connection()?
+            .set_audio_jitter_buffer_min_delay(target_ms);
+        Ok(target_ms)
+    }
+
+    /// Test only: holds received audio for another `extra_ms` before
+    /// playout, so that playout tuning can be checked against a known
+    /// latency.  Only the simulated PeerConnection of the `testing` feature
+    /// can do this; in any other build, including release, it does nothing.
+    pub fn inject_receive_latency(&self, extra_ms: u32) -> Result<()> {
+        info!("inject_receive_latency(): {}ms", extra_ms);
+
+        #[cfg(feature = "testing")]
+        {
+            let webrtc = self.webrtc.lock()?;
+            webrtc.peer_connection()?.set_receive_latency(extra_ms);
+        }
+        #[cfg(not(feature = "testing"))]
+        warn!("inject_receive_latency(): ignored without the testing feature");
+        Ok(())
+    }
+
+    /// Returns the security fingerprint of the connection, which is the
+    /// same on both sides of the call.
+    ///
+    /// See [security_fingerprint()](fn.security_fingerprint.html).
+    pub fn security_fingerprint(&self) -> Result<String> {
+        self.security_fingerprint.lock()?.clone().ok_or_else(|| {
+            RingRtcError::OptionValueNotSet(
+                "security_fingerprint()".to_owned(),
+                "security_fingerprint".to_owned(),
+            )
+            .into()
+        })
+    }
+
+    /// Returns the round trip time of the selected ICE candidate pair, or
+    /// None if not connected. Refreshed every STATS_PERIOD_SEC, along with
+    /// the rest of the stats, so reading it doesn't cost a stats request.
+    pub fn selected_pair_rtt_ms(&self) -> Result<Option<u32>> {
+        let webrtc = self.webrtc.lock()?;
+        Ok(webrtc
+            .stats_observer
+            .as_ref()
+            .and_then(|observer| observer.selected_pair_rtt_ms()))
+    }
+
+    /// Returns a snapshot of the call quality as of the latest stats
+    /// report, or None before the stats observer is created. Refreshed
+    /// every STATS_PERIOD_SEC, like selected_pair_rtt_ms().
+    pub fn call_statistics(&self) -> Result<Option<CallStatistics>> {
+        let webrtc = self.webrtc.lock()?;
+        Ok(webrtc
+            .stats_observer
+            .as_ref()
+            .map(|observer| observer.call_statistics()))
+    }
+
+    /// Returns the playout health of received audio over the latest stats
+    /// period, or None if no audio is being received yet.
+    pub fn audio_playout_statistics(&self) -> Result<Option<AudioPlayoutStatistics>> {
+        let webrtc = self.webrtc.lock()?;
+        Ok(webrtc
+            .stats_observer
+            .as_ref()
+            .and_then(|observer| observer.audio_playout_statistics()))
+    }
+
+    /// Overrides the audio encoder preset chosen from the call's media type.
+    /// Must be called before the offer/answer exchange to take effect from
+    /// the start of the call.
+    pub fn set_audio_encoder_preset(&self, preset: AudioEncoderPreset) -> Result<()> {
+        info!("set_audio_encoder_preset(): {:?}", preset);
+        *self.audio_encoder_preset.lock()? = preset;
+        Ok(())
+    }
+
+    /// Restricts which local network interfaces contribute ICE candidates.
+    /// Must be called before ICE gathering starts to cover every candidate.
+    pub fn set_ice_interface_policy(&self, policy: IceInterfacePolicy) -> Result<()> {
+        info!("set_ice_interface_policy(): {:?}", policy);
+        *self.ice_interface_policy.lock()? = policy;
+        Ok(())
+    }
+
+    /// Biases the priorities of local and remote ICE candidates towards an
+    /// IP address family.  Must be called before ICE gathering starts to
+    /// cover every candidate.
+    pub fn set_ip_preference(&self, ip_preference: signaling::IpPreference) -> Result<()> {
+        info!("set_ip_preference(): {:?}", ip_preference);
+        *self.ip_preference.lock()? = ip_preference;
+        Ok(())
+    }
+
+    /// Override the priorities of local ICE candidates as they are
+    /// gathered.  See signaling::CandidatePrioritizer for the risks.  Must
+    /// be called before ICE gathering starts to cover every candidate.
+    pub fn set_candidate_prioritizer(
+        &self,
+        prioritizer: Option<Arc<dyn signaling::CandidatePrioritizer>>,
+    ) -> Result<()> {
+        info!("set_candidate_prioritizer(): {}", prioritizer.is_some());
+        *self.candidate_prioritizer.lock()? = prioritizer;
+        Ok(())
+    }
+
+    /// Whether to trickle local ICE candidates in separate Ice messages
+    /// (the default) or to hold the offer or answer until ICE gathering
+    /// completes and embed them in its SDP.  Must be called before the
+    /// connection is started.
+    pub fn set_trickle_ice(&self, trickle_ice: bool) -> Result<()> {
+        info!("set_trickle_ice(): {}", trickle_ice);
+        *self.trickle_ice.lock()? = trickle_ice;
+        Ok(())
+    }
+
+    pub fn trickle_ice(&self) -> Result<bool> {
+        Ok(*self.trickle_ice.lock()?)
+    }
+
+    /// Record whether media frames are encrypted, as configured on the
+    /// PeerConnectionObserver of this connection.
+    pub fn set_frame_encryption_enabled(&self, enabled: bool) -> Result<()> {
+        info!("set_frame_encryption_enabled(): {}", enabled);
+        *self.frame_encryption.lock()? = enabled;
+        Ok(())
+    }
+
+    pub fn frame_encryption_enabled(&self) -> Result<bool> {
+        Ok(*self.frame_encryption.lock()?)
+    }
+
+    /// Returns the name of the SRTP cipher suite protecting the media, once
+    /// DTLS has completed.
+    pub fn srtp_cipher_suite(&self) -> Result<String> {
+        let webrtc = self.webrtc.lock()?;
+        webrtc
+            .peer_connection()?
+            .srtp_cipher_suite()
+            .ok_or_else(|| RingRtcError::SrtpCipherSuiteNotNegotiated.into())
+    }
+
+    /// Cap the resolution and frame rate of the outgoing video. Takes effect
+    /// immediately if the call is already connected.
+    ///
+    /// The caps are an upper bound in addition to the bandwidth mode:
+    /// whichever is lower wins, and changing the bandwidth mode never lifts
+    /// them. Pass VideoEncoderCaps::default() to remove them.
+    pub fn set_outgoing_video_caps(&self, caps: VideoEncoderCaps) -> Result<()> {
+        info!("set_outgoing_video_caps(): {:?}", caps);
+
+        *self.outgoing_video_caps.lock()? = caps;
+
+        let webrtc = self.webrtc.lock()?;
+        if let Ok(peer_connection) = webrtc.peer_connection() {
+            peer_connection.set_outgoing_video_caps(&caps);
+        }
+        Ok(())
+    }
+
+    /// Set the Opus in-band FEC level of the outgoing audio, returning the
+    /// level actually applied. Takes effect immediately if the call is
+    /// already connected.
+    ///
+    /// The level is clamped to at most
+    /// [MAX_AUDIO_FEC_LEVEL](constant.MAX_AUDIO_FEC_LEVEL.html).
+    ///
+    /// FEC doesn't raise the target bitrate; the redundant copy of the
+    /// previous frame is paid for out of the same budget, so it costs
+    /// primary audio quality instead:
+    ///
+    /// * Level 0: no cost, but every lost packet is a gap in the audio.
+    /// * Level 1: close to nothing on a clean link, rising to about a
+    ///   third of the audio bitrate as packet loss climbs past 10%.
+    pub fn set_audio_fec_level(&self, level: u8) -> Result<u8> {
+        let level = level.min(MAX_AUDIO_FEC_LEVEL);
+        info!("set_audio_fec_level(): {}", level);
+
+        *self.audio_fec_level.lock()? = level;
+
+        let bandwidth_mode = self.bandwidth_modes.lock()?.min();
+        let webrtc = self.webrtc.lock()?;
+        if let Ok(peer_connection) = webrtc.peer_connection() {
+            self.apply_bandwidth_mode(peer_connection, &bandwidth_mode)?;
+        }
+        Ok(level)
+    }
+
+    /// Returns the bandwidth mode being enforced, which is the lower of the
+    /// one set locally and the one the remote asked for.
+    pub fn effective_bandwidth_mode(&self) -> Result<EffectiveBandwidthMode> {
+        let bandwidth_modes = self.bandwidth_modes.lock()?;
+        let mode = bandwidth_modes.min();
+        Ok(EffectiveBandwidthMode {
+            mode,
+            adaptive: mode != bandwidth_modes.local_bandwidth_mode,
+        })
+    }
+
     /// The local user is updating the bandwidth mode via the API. Update locally and
     /// send an updated bitrate to the remote.
     pub fn update_bandwidth_mode(&self, bandwidth_mode: BandwidthMode) -> Result<()> {
@@ -1122,6 +1593,24 @@ where
         })
     }
 
+    /// The local user is capping the send bitrate via the API, or removing
+    /// the cap if None.  The bandwidth mode, and what is sent to the
+    /// remote, stay as they are.
+    pub fn set_max_bitrate(&self, max_bitrate: Option<DataRate>) -> Result<()> {
+        let bandwidth_modes = self.bandwidth_modes.lock()?;
+
+        info!(
+            "set_max_bitrate(): {:?}",
+            max_bitrate.map(DataRate::as_bps)
+        );
+        *self.max_bitrate_cap.lock()? = max_bitrate;
+
+        let bandwidth_mode = bandwidth_modes.min();
+
+        let webrtc = self.webrtc.lock()?;
+        self.apply_bandwidth_mode(webrtc.peer_connection()?, &bandwidth_mode)
+    }
+
     /// Creates a runtime for statistics to run a timer for the given interval
     /// duration to invoke PeerConnection::GetStats which will pass specific stats
     /// to StatsObserver::on_stats_complete.
@@ -1161,6 +1650,47 @@ where
         Ok(())
     }
 
+    /// Starts waiting out the reconnect policy, if there is one, giving up
+    /// on the call if the connection is still reconnecting once every
+    /// attempt has passed.  Replaces any earlier wait.
+    pub fn start_reconnect_timer(&self) -> Result<()> {
+        let reconnect_policy = match self.reconnect_policy {
+            Some(reconnect_policy) => reconnect_policy,
+            None => return Ok(()),
+        };
+
+        let mut connection = self.clone();
+        let reconnect_future = async move {
+            for (attempt, delay) in reconnect_policy.delays().enumerate() {
+                tokio::time::sleep(delay).await;
+                if connection.state()? != ConnectionState::ReconnectingAfterAccepted {
+                    return Ok(());
+                }
+                info!(
+                    "reconnect attempt {} of {} timed out: {}",
+                    attempt + 1,
+                    reconnect_policy.max_attempts,
+                    connection.id()
+                );
+            }
+            connection.inject_reconnect_attempts_exhausted()
+        }
+        .map_err(|e: failure::Error| {
+            error!("Inject reconnect attempts exhausted failed: {:?}", e)
+        });
+
+        let reconnect_runtime = TaskQueueRuntime::new("connection-reconnect")?;
+        reconnect_runtime.spawn(reconnect_future);
+        *self.reconnect_runtime.lock()? = Some(reconnect_runtime);
+        Ok(())
+    }
+
+    /// Stops waiting out the reconnect policy, if waiting.
+    pub fn cancel_reconnect_timer(&self) -> Result<()> {
+        self.reconnect_runtime.lock()?.take();
+        Ok(())
+    }
+
     pub fn tick(&mut self, ticks_elapsed: u64) -> Result<()> {
         let webrtc = self.webrtc.lock()?;
         let data_channel = webrtc.data_channel().ok();
@@ -1169,10 +1699,19 @@ where
 
         if ticks_elapsed % STATS_PERIOD_SEC == 0 {
             if let Some(observer) = webrtc.stats_observer.as_ref() {
-                let _ = webrtc.peer_connection()?.get_stats(observer);
+                let _ =  webrtc.peer_connection()?.get_stats(observer);
             } else {
                 warn!("tick(): No stats_observer found");
             }
+
+            // The resolution cap depends on the size of the captured frames,
+            // which changes with the camera and orientation.
+            let outgoing_video_caps = *self.outgoing_video_caps.lock()?;
+            if outgoing_video_caps != VideoEncoderCaps::default() {
+                webrtc
+                    .peer_connection()?
+                    .set_outgoing_video_caps(&outgoing_video_caps);
+            }
         }
 
         Ok(())
@@ -1195,6 +1734,31 @@ where
         Ok(())
     }
 
+    /// Mute or unmute the outgoing audio track without affecting video.
+    pub fn set_outgoing_audio_enabled(&self, enabled: bool) -> Result<()> {
+        info!("set_outgoing_audio_enabled(): {}", enabled);
+        let webrtc = self.webrtc.lock()?;
+        webrtc
+            .peer_connection()?
+            .set_outgoing_audio_enabled(enabled);
+        Ok(())
+    }
+
+    /// Stop or restart outgoing and incoming media for a call that is put
+    /// on or taken off hold.
+    ///
+    /// Incoming RTP also carries the RTP data channel, so while held a
+    /// hangup from the remote only arrives through signaling.
+    pub fn set_media_on_hold(&self, on_hold: bool) -> Result<()> {
+        info!("set_media_on_hold(): {}", on_hold);
+        *self.media_on_hold.lock()? = on_hold;
+        let webrtc = self.webrtc.lock()?;
+        let pc = webrtc.peer_connection()?;
+        pc.set_outgoing_media_enabled(!on_hold);
+        pc.set_incoming_media_enabled(!on_hold);
+        Ok(())
+    }
+
     /// Buffer local ICE candidates, and maybe send them immediately
     pub fn buffer_local_ice_candidate(&self, candidate: signaling::IceCandidate) -> Result<()> {
         let num_ice_candidates = {
@@ -1203,6 +1767,12 @@ where
             buffered_local_ice_candidates.len()
         };
 
+        // Without trickle ICE the candidates wait for the held offer or
+        // answer instead.
+        if !self.trickle_ice()? {
+            return Ok(());
+        }
+
         // Only when we transition from no candidates to one do we
         // need to signal the message queue that there is something
         // to send for this Connection.
@@ -1215,6 +1785,45 @@ where
         Ok(())
     }
 
+    /// Hold back an offer or answer until ICE gathering completes.  If it
+    /// already has, returns the description with the local candidates
+    /// embedded, ready to send.
+    pub fn hold_until_ice_gathered(
+        &self,
+        description: HeldDescription,
+    ) -> Result<Option<HeldDescription>> {
+        let mut non_trickle_ice = self.non_trickle_ice.lock()?;
+        if non_trickle_ice.gathering_complete {
+            let candidates = self.take_buffered_local_ice_candidates()?;
+            Ok(Some(description.with_ice_candidates(&candidates)?))
+        } else {
+            non_trickle_ice.held_description = Some(description);
+            Ok(None)
+        }
+    }
+
+    /// Note that ICE gathering is complete and send the held offer or
+    /// answer, if any, with the local candidates embedded.
+    pub fn release_held_description(&self) -> Result<()> {
+        let held_description = {
+            let mut non_trickle_ice = self.non_trickle_ice.lock()?;
+            non_trickle_ice.gathering_complete = true;
+            non_trickle_ice.held_description.take()
+        };
+
+        if let Some(held_description) = held_description {
+            let candidates = self.take_buffered_local_ice_candidates()?;
+            info!(
+                "release_held_description(): embedding {} ICE candidates",
+                candidates.len()
+            );
+            let description = held_description.with_ice_candidates(&candidates)?;
+            let call = self.call()?;
+            call.send_held_description(self.clone(), description)?;
+        }
+        Ok(())
+    }
+
     /// Get the current local ICE candidates to send to the remote peer.
     pub fn take_buffered_local_ice_candidates(&self) -> Result<Vec<signaling::IceCandidate>> {
         info!("take_buffered_local_ice_candidates():");
@@ -1237,11 +1846,12 @@ where
             format!("ice_candidates({})", remote_ice_candidates.len())
         );
 
+        let ip_preference = *self.ip_preference.lock()?;
         let webrtc = self.webrtc.lock()?;
         for remote_ice_candidate in remote_ice_candidates {
             webrtc
                 .peer_connection()?
-                .add_ice_candidate(remote_ice_candidate)?;
+                .add_ice_candidate(&remote_ice_candidate.prioritized_for(ip_preference)?)?;
         }
         Ok(())
     }
@@ -1297,8 +1907,19 @@ where
         bandwidth_mode: &BandwidthMode,
     ) -> Result<()> {
         info!("apply_bandwidth_mode(): mode: {}", bandwidth_mode);
-        peer_connection.set_max_send_bitrate(bandwidth_mode.max_bitrate())?;
-        peer_connection.configure_audio_encoders(&bandwidth_mode.audio_encoder_config());
+        let audio_encoder_preset = *self.audio_encoder_preset.lock()?;
+        let mut audio_encoder_config = bandwidth_mode.audio_encoder_config(&audio_encoder_preset);
+        audio_encoder_config.enable_fec = *self.audio_fec_level.lock()? > 0;
+        let max_bitrate = match *self.max_bitrate_cap.lock()? {
+            Some(cap) => cmp::min(cap, bandwidth_mode.max_bitrate()),
+            None => bandwidth_mode.max_bitrate(),
+        };
+        peer_connection.set_max_send_bitrate(max_bitrate)?;
+        peer_connection.configure_audio_encoders(&audio_encoder_config);
+        let outgoing_video_caps = *self.outgoing_video_caps.lock()?;
+        if outgoing_video_caps != VideoEncoderCaps::default() {
+            peer_connection.set_outgoing_video_caps(&outgoing_video_caps);
+        }
         Ok(())
     }
 
@@ -1322,6 +1943,33 @@ where
         })
     }
 
+    /// Send the remote peer an application-defined payload via the
+    /// PeerConnection DataChannel.
+    ///
+    /// The payload is not added to the accumulated state, so it is
+    /// sent once and never retransmitted. Since the underlying RTP
+    /// data channel is unreliable, the payload may be lost or arrive
+    /// out of order relative to other data messages.
+    pub fn send_data_message_via_data_channel(&self, payload: Vec<u8>) -> Result<()> {
+        let webrtc = self.webrtc.lock()?;
+        let data_channel = webrtc.data_channel()?;
+        let message = protobuf::data_channel::Data {
+            application_data: Some(payload),
+            ..Default::default()
+        };
+        self.send_via_data_channel(data_channel, &message)
+    }
+
+    /// Play DTMF tones into the outgoing audio as RFC 4733
+    /// telephone-events, which the remote peer reports as it receives
+    /// them.
+    pub fn send_dtmf(&self, tones: String, duration_ms: u32, gap_ms: u32) -> Result<()> {
+        let webrtc = self.webrtc.lock()?;
+        webrtc
+            .peer_connection()?
+            .insert_dtmf(&tones, duration_ms, gap_ms)
+    }
+
     /// Populates a data channel message using the supplied closure and sends it via the DataChannel.
     fn update_and_send_dcm_state_via_data_channel<F>(
         &self,
@@ -1382,6 +2030,69 @@ where
         call.on_connection_observer_event(self.remote_device_id(), event)
     }
 
+    /// Notify the parent call about an error from a STUN or TURN server.
+    pub fn notify_ice_candidate_error(
+        &self,
+        url: String,
+        error_code: u16,
+        error_text: String,
+    ) -> Result<()> {
+        let call = self.call.lock()?;
+        call.notify_ice_candidate_error(url, error_code, error_text)
+    }
+
+    /// Notify the parent call that the media moved to another kind of network.
+    pub fn notify_network_route_changed(&self, network_route: NetworkRoute) -> Result<()> {
+        let call = self.call.lock()?;
+        call.notify_network_route_changed(network_route)
+    }
+
+    /// Notify the parent call about an application-defined payload
+    /// received from the remote peer.
+    pub fn notify_data_message(&self, payload: Vec<u8>) -> Result<()> {
+        let call = self.call.lock()?;
+        call.notify_data_message(self.remote_device_id(), payload)
+    }
+
+    /// Notify the parent call about a DTMF tone received from the remote
+    /// peer.
+    pub fn notify_dtmf(&self, tone: char) -> Result<()> {
+        let call = self.call.lock()?;
+        call.notify_dtmf(self.remote_device_id(), tone)
+    }
+
+    /// Handle a telephone-event RTP packet from the remote peer.  Each
+    /// event is sent in several packets, all with the timestamp it
+    /// started at, so only the first packet of an event is reported.
+    fn handle_received_telephone_event(
+        &mut self,
+        timestamp: rtp::Timestamp,
+        payload: &[u8],
+    ) -> Result<()> {
+        let tone = match dtmf_tone_from_telephone_event(payload) {
+            Some(tone) => tone,
+            None => {
+                warn!("Dropped telephone-event that isn't a DTMF tone");
+                return Ok(());
+            }
+        };
+        {
+            let mut last_timestamp = self.telephone_event_timestamp.lock()?;
+            if *last_timestamp == Some(timestamp) {
+                return Ok(());
+            }
+            *last_timestamp = Some(timestamp);
+        }
+        self.inject_received_dtmf(tone)
+    }
+
+    /// Notify the parent call about a key frame encoded for the
+    /// local video.
+    pub fn notify_outgoing_keyframe(&self) -> Result<()> {
+        let call = self.call.lock()?;
+        call.notify_outgoing_keyframe(self.remote_device_id())
+    }
+
     /// Notify the parent call observer about an internal error.
     pub fn internal_error(&self, error: failure::Error) -> Result<()> {
         let mut call = self.call.lock()?;
@@ -1451,6 +2162,8 @@ where
 
         self.set_state(ConnectionState::Terminated)?;
 
+        self.cancel_reconnect_timer()?;
+
         // Stop the timer runtime, if any.
         let mut tick_context = self.tick_context.lock()?;
         if let Some(rt) = tick_context.runtime.take() {
@@ -1575,6 +2288,13 @@ where
         Ok(())
     }
 
+    /// Inject an `IceGatheringComplete` event into the FSM.
+    ///
+    /// `Called By:` WebRTC `PeerConnectionObserver` call back thread.
+    pub fn inject_ice_gathering_complete(&mut self) -> Result<()> {
+        self.inject_event(ConnectionEvent::IceGatheringComplete)
+    }
+
     /// Inject an `IceConnected` event into the FSM.
     ///
     /// `Called By:` WebRTC `PeerConnectionObserver` call back thread.
@@ -1596,6 +2316,47 @@ where
         self.inject_event(ConnectionEvent::IceDisconnected)
     }
 
+    /// Inject a `ReconnectAttemptsExhausted` event into the FSM.
+    ///
+    /// `Called By:` Reconnect timer runtime.
+    pub fn inject_reconnect_attempts_exhausted(&mut self) -> Result<()> {
+        self.inject_event(ConnectionEvent::ReconnectAttemptsExhausted)
+    }
+
+    /// Inject an `IceCandidateError` event into the FSM.
+    ///
+    /// `Called By:` WebRTC `PeerConnectionObserver` call back thread.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - URL of the STUN or TURN server, already redacted.
+    /// * `error_code` - STUN error code, such as 401 for bad TURN credentials.
+    /// * `error_text` - Reason phrase sent by the server.
+    pub fn inject_ice_candidate_error(
+        &mut self,
+        url: String,
+        error_code: u16,
+        error_text: String,
+    ) -> Result<()> {
+        self.inject_event(ConnectionEvent::IceCandidateError(
+            url, error_code, error_text,
+        ))
+    }
+
+    /// Inject an `IceNetworkRouteChanged` event into the FSM.
+    ///
+    /// `Called By:` WebRTC `PeerConnectionObserver` call back thread.
+    pub fn inject_ice_network_route_changed(&mut self, network_route: NetworkRoute) -> Result<()> {
+        self.inject_event(ConnectionEvent::IceNetworkRouteChanged(network_route))
+    }
+
+    /// Inject an `OutgoingKeyFrameEncoded` event into the FSM.
+    ///
+    /// `Called By:` WebRTC encoder thread, as the key frame is sent.
+    pub fn inject_outgoing_key_frame_encoded(&mut self) -> Result<()> {
+        self.inject_event(ConnectionEvent::OutgoingKeyFrameEncoded)
+    }
+
     /// Inject a `InternalError` event into the FSM.
     ///
     /// This is used to send an internal error notification to the
@@ -1611,7 +2372,9 @@ where
     }
 
     pub fn inject_received_via_signaling_data_channel(&mut self, bytes: Bytes) {
-        if bytes.len() > (std::mem::size_of::<protobuf::data_channel::Data>() * 2) {
+        if bytes.len()
+            > (std::mem::size_of::<protobuf::data_channel::Data>() * 2 + MAX_DATA_MESSAGE_SIZE)
+        {
             warn!("data channel message is excessively large: {}", bytes.len());
             return;
         }
@@ -1679,6 +2442,18 @@ where
             .unwrap_or_else(|e| warn!("unable to inject remote receiver status event: {}", e));
             message_handled = true;
         };
+        if let Some(application_data) = message.application_data {
+            if application_data.len() > MAX_DATA_MESSAGE_SIZE {
+                warn!(
+                    "Dropped data message because it is too large: {}",
+                    application_data.len()
+                );
+            } else {
+                self.inject_received_data_message_via_data_channel(application_data)
+                    .unwrap_or_else(|e| warn!("unable to inject remote data message event: {}", e));
+            }
+            message_handled = true;
+        };
         if !message_handled {
             info!("Unhandled data channel message: {:?}", original_message);
         }
@@ -1749,6 +2524,32 @@ where
         ))
     }
 
+    /// Inject a `ReceivedDataMessageViaDataChannel` event into the FSM.
+    ///
+    /// `Called By:` WebRTC `DataChannelObserver` call back thread.
+    ///
+    /// # Arguments
+    ///
+    /// * `payload` - Application-defined payload from the remote peer.
+    pub fn inject_received_data_message_via_data_channel(
+        &mut self,
+        payload: Vec<u8>,
+    ) -> Result<()> {
+        self.inject_event(ConnectionEvent::ReceivedDataMessageViaDataChannel(payload))
+    }
+
+    /// Inject a `ReceivedDtmf` event into the FSM.
+    ///
+    /// `Called By:` WebRTC audio receive thread, via the
+    /// `PeerConnectionObserver`.
+    ///
+    /// # Arguments
+    ///
+    /// * `tone` - DTMF tone played by the remote peer.
+    pub fn inject_received_dtmf(&mut self, tone: char) -> Result<()> {
+        self.inject_event(ConnectionEvent::ReceivedDtmf(tone))
+    }
+
     /// Inject a `SendHangupViaDataChannel event into the FSM.
     pub fn inject_send_hangup_via_data_channel(&mut self, hangup: signaling::Hangup) -> Result<()> {
         self.set_state(ConnectionState::Terminating)?;
@@ -1762,6 +2563,41 @@ where
         self.inject_event(ConnectionEvent::Accept)
     }
 
+    /// Inject a `SendDataMessageViaDataChannel` event into the FSM.
+    ///
+    /// `Called By:` Local application.
+    ///
+    /// * `payload` - Application-defined payload, at most
+    /// [MAX_DATA_MESSAGE_SIZE](constant.MAX_DATA_MESSAGE_SIZE.html) bytes.
+    pub fn inject_send_data_message_via_data_channel(&mut self, payload: Vec<u8>) -> Result<()> {
+        if payload.len() > MAX_DATA_MESSAGE_SIZE {
+            return Err(
+                RingRtcError::DataMessageTooLarge(payload.len(), MAX_DATA_MESSAGE_SIZE).into(),
+            );
+        }
+        self.inject_event(ConnectionEvent::SendDataMessageViaDataChannel(payload))
+    }
+
+    /// Inject a `SendDtmf` event into the FSM.
+    ///
+    /// `Called By:` Local application.
+    ///
+    /// * `tones` - One or more of
+    /// [VALID_DTMF_TONES](constant.VALID_DTMF_TONES.html).
+    /// * `duration_ms` - How long to play each tone, clamped to
+    /// [MIN_DTMF_DURATION_MS](constant.MIN_DTMF_DURATION_MS.html) and
+    /// [MAX_DTMF_DURATION_MS](constant.MAX_DTMF_DURATION_MS.html).
+    /// * `gap_ms` - How long to wait between tones, at least
+    /// [MIN_DTMF_GAP_MS](constant.MIN_DTMF_GAP_MS.html).
+    pub fn inject_send_dtmf(&mut self, tones: String, duration_ms: u32, gap_ms: u32) -> Result<()> {
+        validate_dtmf_tones(&tones)?;
+        let duration_ms = duration_ms
+            .max(MIN_DTMF_DURATION_MS)
+            .min(MAX_DTMF_DURATION_MS);
+        let gap_ms = gap_ms.max(MIN_DTMF_GAP_MS);
+        self.inject_event(ConnectionEvent::SendDtmf(tones, duration_ms, gap_ms))
+    }
+
     /// Inject a `SendSenderStatusViaDataChannel` event into the FSM.
     ///
     /// `Called By:` Local application.
@@ -1785,6 +2621,21 @@ where
         self.inject_event(ConnectionEvent::UpdateBandwidthMode(bandwidth_mode))
     }
 
+    /// Inject a `SetMaxBitrate` event into the FSM.
+    ///
+    /// `Called By:` Local application.
+    ///
+    /// * `max_bitrate_bps` - The most to send, in bits per second, or 0
+    /// for no cap beyond the bandwidth mode.
+    pub fn inject_set_max_bitrate(&mut self, max_bitrate_bps: u32) -> Result<()> {
+        let max_bitrate = if max_bitrate_bps == 0 {
+            None
+        } else {
+            Some(DataRate::from_bps(max_bitrate_bps as u64))
+        };
+        self.inject_event(ConnectionEvent::SetMaxBitrate(max_bitrate))
+    }
+
     /// Inject a `ReceivedIce` event into the FSM.
     ///
     /// `Called By:` Call object.
@@ -1894,11 +2745,31 @@ where
         &mut self,
         ice_candidate: signaling::IceCandidate,
         sdp_for_logging: &str,
+        network: &IceCandidateNetwork,
     ) -> Result<()> {
+        if !self.ice_interface_policy.lock()?.allows(network) {
+            info!(
+                "Dropping local ICE candidate from excluded interface {}: {}",
+                network.name,
+                redact_string(sdp_for_logging)
+            );
+            return Ok(());
+        }
+
+        // The remote side only sees the priorities we send, so bias them
+        // here too, not just the remote candidates we add locally.
+        let mut ice_candidate = ice_candidate.prioritized_for(*self.ip_preference.lock()?)?;
+        if let Some(prioritizer) = self.candidate_prioritizer.lock()?.as_ref() {
+            ice_candidate = ice_candidate.prioritized_by(prioritizer.as_ref())?;
+        }
         let force_send = false;
         self.inject_local_ice_candidate(ice_candidate, force_send, sdp_for_logging)
     }
 
+    fn handle_ice_gathering_complete(&mut self) -> Result<()> {
+        self.inject_ice_gathering_complete()
+    }
+
     fn handle_ice_connection_state_changed(&mut self, new_state: IceConnectionState) -> Result<()> {
         match new_state {
             IceConnectionState::Completed | IceConnectionState::Connected => {
@@ -1910,6 +2781,29 @@ where
         }
     }
 
+    fn handle_ice_candidate_error(
+        &mut self,
+        url: String,
+        error_code: u16,
+        error_text: String,
+    ) -> Result<()> {
+        // The URL of a STUN or TURN server might have an IP address in it.
+        let url = redact_string(&url);
+        warn!(
+            "ICE candidate error: url: {}, error_code: {}, error_text: {}",
+            url, error_code, error_text
+        );
+        self.inject_ice_candidate_error(url, error_code, error_text)
+    }
+
+    fn handle_ice_network_route_changed(&mut self, network_route: NetworkRoute) -> Result<()> {
+        self.inject_ice_network_route_changed(network_route)
+    }
+
+    fn handle_outgoing_key_frame_encoded(&mut self) -> Result<()> {
+        self.inject_outgoing_key_frame_encoded()
+    }
+
     fn handle_incoming_media_added(&mut self, stream: MediaStream) -> Result<()> {
         self.inject_received_incoming_media(stream)
     }
@@ -1921,6 +2815,13 @@ where
     fn handle_signaling_data_channel_message(&mut self, message: Bytes) {
         self.inject_received_via_signaling_data_channel(message)
     }
+
+    fn handle_rtp_received(&mut self, header: rtp::Header, payload: &[u8]) {
+        if header.pt == TELEPHONE_EVENT_PAYLOAD_TYPE {
+            self.handle_received_telephone_event(header.timestamp, payload)
+                .unwrap_or_else(|e| warn!("unable to handle telephone-event: {}", e));
+        }
+    }
 }
 
 fn generate_local_secret_and_public_key() -> Result<(StaticSecret, PublicKey)> {
@@ -1929,6 +2830,34 @@ fn generate_local_secret_and_public_key() -> Result<(StaticSecret, PublicKey)> {
     Ok((secret, public))
 }
 
+/// Computes a short digest of the identity keys that the SRTP keys of a
+/// call are bound to, for users to compare out loud.
+///
+/// The keys are always hashed in caller, callee order, so both sides
+/// compute the same value.  The result is
+/// [SECURITY_FINGERPRINT_LEN](constant.SECURITY_FINGERPRINT_LEN.html)
+/// characters long: groups of 5 decimal digits separated by spaces.
+pub fn security_fingerprint(caller_identity_key: &[u8], callee_identity_key: &[u8]) -> String {
+    let prefix = "Signal_Calling_20210701_SecurityFingerprint";
+    let mut input =
+        Vec::with_capacity(prefix.len() + caller_identity_key.len() + callee_identity_key.len());
+    input.extend_from_slice(prefix.as_bytes());
+    input.extend_from_slice(caller_identity_key);
+    input.extend_from_slice(callee_identity_key);
+    let hash = sha256_as_hexstring(&input);
+
+    hash.as_bytes()
+        .chunks(SECURITY_FINGERPRINT_GROUP_HEX_DIGITS)
+        .take(SECURITY_FINGERPRINT_GROUPS)
+        .map(|chunk| {
+            let chunk = std::str::from_utf8(chunk).unwrap_or_default();
+            let value = u32::from_str_radix(chunk, 16).unwrap_or_default();
+            format!("{:05}", value % 100_000)
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 struct NegotiatedSrtpKeys {
     pub offer_key:  SrtpKey,
     pub answer_key: SrtpKey,
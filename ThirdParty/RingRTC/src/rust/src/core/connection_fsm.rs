@@ -21,6 +21,8 @@
 //! - LocalHangup
 //! - SendSenderStatusViaDataChannel
 //! - SendReceiverStatusViaDataChannel
+//! - SendDataMessageViaDataChannel
+//! - SendDtmf
 //! - SendBusy
 //! - ReceivedIce
 //! - ReceivedHangup
@@ -28,14 +30,20 @@
 //! ## From WebRTC observer interfaces
 //!
 //! - LocalIceCandidate
+//! - IceGatheringComplete
 //! - ConnectedBeforeAccepted
 //! - IceFailed
 //! - IceDisconnected
+//! - IceCandidateError
+//! - IceNetworkRouteChanged
+//! - OutgoingKeyFrameEncoded
 //! - ReceivedIncomingMedia
 //! - ReceivedSignalingDataChannel
 //! - ReceivedAcceptedViaDataChannel
 //! - ReceivedSenderStatusViaDataChannel
 //! - ReceivedReceiverStatusViaDataChannel
+//! - ReceivedDataMessageViaDataChannel
+//! - ReceivedDtmf
 //! - ReceivedHangup
 //!
 //! # Asynchronous Outputs:
@@ -65,6 +73,7 @@ use crate::core::util::TaskQueueRuntime;
 use crate::error::RingRtcError;
 use crate::webrtc::data_channel::DataChannel;
 use crate::webrtc::media::MediaStream;
+use crate::webrtc::peer_connection_observer::NetworkRoute;
 
 /// The different types of Connection Events.
 pub enum ConnectionEvent {
@@ -100,14 +109,39 @@ pub enum ConnectionEvent {
     /// Source: app (user action)
     /// Action: Send a sender status message over the data channel.
     SendSenderStatusViaDataChannel(bool),
+    /// Receive an application-defined payload from remote peer.
+    /// Source: data channel (PeerConnection)
+    /// Action: Bubble up to app.
+    ReceivedDataMessageViaDataChannel(Vec<u8>),
+    /// Send an application-defined payload via the data channel
+    /// Source: app
+    /// Action: Send the payload once over the data channel.
+    SendDataMessageViaDataChannel(Vec<u8>),
+    /// Receive a DTMF tone from remote peer.
+    /// Source: RTP telephone-event (PeerConnection)
+    /// Action: Bubble up to app.
+    ReceivedDtmf(char),
+    /// Send DTMF tones, with the duration and gap in milliseconds
+    /// Source: app
+    /// Action: Play the tones into the outgoing audio as RTP
+    /// telephone-events.
+    SendDtmf(String, u32, u32),
     /// Set bandwidth mode
     /// Source: app (user setting)
     /// Action: Update and send bitrate via a receiver status message over the data channel.
     UpdateBandwidthMode(BandwidthMode),
+    /// Cap the send bitrate, or remove the cap if None
+    /// Source: app (user setting)
+    /// Action: Reconfigure the senders; the bandwidth mode is left alone.
+    SetMaxBitrate(Option<DataRate>),
     /// Local ICE candidate from PeerConnection
     /// Source: PeerConnection
     /// Action: Send ICE candidate over signaling.
     LocalIceCandidate(signaling::IceCandidate),
+    /// Every local ICE candidate has been gathered.
+    /// Source: PeerConnection
+    /// Action: Send the offer or answer held back for them, if any.
+    IceGatheringComplete,
     /// ICE state changed.
     /// Source: PeerConnection
     /// Action: Bubble up to Connection and Call objects.
@@ -120,6 +154,22 @@ pub enum ConnectionEvent {
     /// Source: PeerConnection
     /// Action: Bubble up to Connection and Call objects.
     IceDisconnected,
+    /// ICE didn't recover within the reconnect policy.
+    /// Source: Reconnect timer
+    /// Action: Treat as ICE failed, ending the call.
+    ReconnectAttemptsExhausted,
+    /// A STUN or TURN server returned an error.
+    /// Source: PeerConnection
+    /// Action: Bubble up to app for diagnostics.
+    IceCandidateError(String, u16, String),
+    /// ICE switched to a candidate pair on another kind of network.
+    /// Source: PeerConnection
+    /// Action: Bubble up to app.
+    IceNetworkRouteChanged(NetworkRoute),
+    /// The encoder produced a key frame of the local video.
+    /// Source: PeerConnection
+    /// Action: Bubble up to app.
+    OutgoingKeyFrameEncoded,
     /// Send the observer an internal error message.
     /// Source: all kinds of things that can go wrong internally
     /// Action: Terminate the call.
@@ -172,14 +222,43 @@ impl fmt::Display for ConnectionEvent {
                 "SendSenderStatusViaDataChannel, enabled: {}",
                 enabled
             ),
+            ConnectionEvent::ReceivedDataMessageViaDataChannel(payload) => format!(
+                "ReceivedDataMessageViaDataChannel, len: {}",
+                payload.len()
+            ),
+            ConnectionEvent::SendDataMessageViaDataChannel(payload) => format!(
+                "SendDataMessageViaDataChannel, len: {}",
+                payload.len()
+            ),
+            ConnectionEvent::ReceivedDtmf(_) => "ReceivedDtmf".to_string(),
+            ConnectionEvent::SendDtmf(tones, duration_ms, gap_ms) => format!(
+                "SendDtmf, tones: {}, duration_ms: {}, gap_ms: {}",
+                tones.chars().count(),
+                duration_ms,
+                gap_ms
+            ),
             ConnectionEvent::UpdateBandwidthMode(mode) => format!(
                 "UpdateBandwidthMode, mode: {:?}",
                 mode
             ),
+            ConnectionEvent::SetMaxBitrate(max_bitrate) => format!(
+                "SetMaxBitrate, max_bitrate_bps: {:?}",
+                max_bitrate.map(DataRate::as_bps)
+            ),
             ConnectionEvent::LocalIceCandidate(_) => "LocalIceCandidate".to_string(),
+            ConnectionEvent::IceGatheringComplete => "IceGatheringComplete".to_string(),
             ConnectionEvent::IceConnected => "IceConnected".to_string(),
             ConnectionEvent::IceFailed => "IceConnectionFailed".to_string(),
             ConnectionEvent::IceDisconnected => "IceDisconnected".to_string(),
+            ConnectionEvent::ReconnectAttemptsExhausted => "ReconnectAttemptsExhausted".to_string(),
+            ConnectionEvent::IceCandidateError(url, error_code, _) => format!(
+                "IceCandidateError, url: {}, error_code: {}",
+                url, error_code
+            ),
+            ConnectionEvent::IceNetworkRouteChanged(network_route) => {
+                format!("IceNetworkRouteChanged, network_route: {:?}", network_route)
+            }
+            ConnectionEvent::OutgoingKeyFrameEncoded => "OutgoingKeyFrameEncoded".to_string(),
             ConnectionEvent::InternalError(e) => format!("InternalError: {}", e),
             ConnectionEvent::ReceivedIncomingMedia(stream) => {
                 format!("ReceivedIncomingMedia, stream: {:}", stream)
@@ -420,15 +499,45 @@ where
             ConnectionEvent::SendSenderStatusViaDataChannel(enabled) => {
                 self.handle_send_sender_status_via_data_channel(connection, state, enabled)
             }
+            ConnectionEvent::ReceivedDataMessageViaDataChannel(payload) => {
+                self.handle_received_data_message_via_data_channel(connection, state, payload)
+            }
+            ConnectionEvent::SendDataMessageViaDataChannel(payload) => {
+                self.handle_send_data_message_via_data_channel(connection, state, payload)
+            }
+            ConnectionEvent::ReceivedDtmf(tone) => {
+                self.handle_received_dtmf(connection, state, tone)
+            }
+            ConnectionEvent::SendDtmf(tones, duration_ms, gap_ms) => {
+                self.handle_send_dtmf(connection, state, tones, duration_ms, gap_ms)
+            }
             ConnectionEvent::UpdateBandwidthMode(mode) => {
                 self.handle_update_bandwidth_mode(connection, state, mode)
             }
+            ConnectionEvent::SetMaxBitrate(max_bitrate) => {
+                self.handle_set_max_bitrate(connection, state, max_bitrate)
+            }
             ConnectionEvent::LocalIceCandidate(candidate) => {
                 self.handle_local_ice_candidate(connection, state, candidate)
             }
+            ConnectionEvent::IceGatheringComplete => {
+                self.handle_ice_gathering_complete(connection, state)
+            }
             ConnectionEvent::IceConnected => self.handle_ice_connected(connection, state),
             ConnectionEvent::IceFailed => self.handle_ice_failed(connection, state),
             ConnectionEvent::IceDisconnected => self.handle_ice_disconnected(connection, state),
+            ConnectionEvent::ReconnectAttemptsExhausted => {
+                self.handle_reconnect_attempts_exhausted(connection, state)
+            }
+            ConnectionEvent::IceCandidateError(url, error_code, error_text) => {
+                self.handle_ice_candidate_error(connection, url, error_code, error_text)
+            }
+            ConnectionEvent::IceNetworkRouteChanged(network_route) => {
+                self.handle_ice_network_route_changed(connection, network_route)
+            }
+            ConnectionEvent::OutgoingKeyFrameEncoded => {
+                self.handle_outgoing_key_frame_encoded(connection)
+            }
             ConnectionEvent::InternalError(error) => self.handle_internal_error(connection, error),
             ConnectionEvent::ReceivedIncomingMedia(stream) => {
                 self.handle_received_incoming_media(connection, state, stream)
@@ -734,6 +843,124 @@ where
         Ok(())
     }
 
+    fn handle_received_data_message_via_data_channel(
+        &mut self,
+        connection: Connection<T>,
+        state: ConnectionState,
+        payload: Vec<u8>,
+    ) -> Result<()> {
+        match state {
+            ConnectionState::ConnectingBeforeAccepted
+            | ConnectionState::ReconnectingAfterAccepted
+            | ConnectionState::ConnectedBeforeAccepted
+            | ConnectionState::ConnectedAndAccepted => {
+                let mut err_connection = connection.clone();
+                let notify_data_message_future = lazy(move |_| {
+                    if connection.terminating()? {
+                        return Ok(());
+                    }
+                    connection.notify_data_message(payload)
+                })
+                .map_err(move |err| {
+                    err_connection.inject_internal_error(err, "Notify data message failed");
+                });
+
+                self.notify_spawn(notify_data_message_future);
+            }
+            _ => self.unexpected_state(state, "ReceivedDataMessageViaDataChannel"),
+        };
+        Ok(())
+    }
+
+    fn handle_send_data_message_via_data_channel(
+        &mut self,
+        connection: Connection<T>,
+        state: ConnectionState,
+        payload: Vec<u8>,
+    ) -> Result<()> {
+        match state {
+            ConnectionState::ConnectingBeforeAccepted
+            | ConnectionState::ReconnectingAfterAccepted
+            | ConnectionState::ConnectedBeforeAccepted
+            | ConnectionState::ConnectedAndAccepted => {
+                let send_data_message_future = lazy(move |_| {
+                    if connection.terminating()? {
+                        return Ok(());
+                    }
+                    connection.send_data_message_via_data_channel(payload)
+                })
+                .map_err(|err| {
+                    // Data messages are best effort, so a failed send
+                    // shouldn't bring down the call.
+                    warn!("Sending data message failed: {}", err);
+                });
+
+                self.worker_spawn(send_data_message_future);
+            }
+            _ => self.unexpected_state(state, "SendDataMessageViaDataChannel"),
+        };
+        Ok(())
+    }
+
+    fn handle_received_dtmf(
+        &mut self,
+        connection: Connection<T>,
+        state: ConnectionState,
+        tone: char,
+    ) -> Result<()> {
+        match state {
+            ConnectionState::ConnectingBeforeAccepted
+            | ConnectionState::ReconnectingAfterAccepted
+            | ConnectionState::ConnectedBeforeAccepted
+            | ConnectionState::ConnectedAndAccepted => {
+                let mut err_connection = connection.clone();
+                let notify_dtmf_future = lazy(move |_| {
+                    if connection.terminating()? {
+                        return Ok(());
+                    }
+                    connection.notify_dtmf(tone)
+                })
+                .map_err(move |err| {
+                    err_connection.inject_internal_error(err, "Notify DTMF failed");
+                });
+
+                self.notify_spawn(notify_dtmf_future);
+            }
+            _ => self.unexpected_state(state, "ReceivedDtmf"),
+        };
+        Ok(())
+    }
+
+    fn handle_send_dtmf(
+        &mut self,
+        connection: Connection<T>,
+        state: ConnectionState,
+        tones: String,
+        duration_ms: u32,
+        gap_ms: u32,
+    ) -> Result<()> {
+        match state {
+            // Outgoing audio is only enabled once the call is accepted.
+            ConnectionState::ReconnectingAfterAccepted | ConnectionState::ConnectedAndAccepted => {
+                let send_dtmf_future = lazy(move |_| {
+                    if connection.terminating()? {
+                        return Ok(());
+                    }
+                    connection.send_dtmf(tones, duration_ms, gap_ms)
+                })
+                .map_err(|err| {
+                    // Like data messages, DTMF is best effort, so a failed
+                    // send shouldn't bring down the call.
+                    warn!("Sending DTMF failed: {}", err);
+                });
+
+                self.worker_spawn(send_dtmf_future);
+            }
+            _ => self.unexpected_state(state, "SendDtmf"),
+        };
+        Ok(())
+    }
+
     fn handle_update_bandwidth_mode(
         &mut self,
         connection: Connection<T>,
@@ -764,6 +991,36 @@ where
         Ok(())
     }
 
+    fn handle_set_max_bitrate(
+        &mut self,
+        connection: Connection<T>,
+        state: ConnectionState,
+        max_bitrate: Option<DataRate>,
+    ) -> Result<()> {
+        match state {
+            ConnectionState::ConnectingBeforeAccepted
+            | ConnectionState::ReconnectingAfterAccepted
+            | ConnectionState::ConnectedBeforeAccepted
+            | ConnectionState::ConnectedAndAccepted => {
+                let mut err_connection = connection.clone();
+                let set_max_bitrate_future = lazy(move |_| {
+                    if connection.terminating()? {
+                        return Ok(());
+                    }
+
+                    connection.set_max_bitrate(max_bitrate)
+                })
+                .map_err(move |err| {
+                    err_connection.inject_internal_error(err, "Setting max bitrate failed");
+                });
+
+                self.worker_spawn(set_max_bitrate_future);
+            }
+            _ => self.unexpected_state(state, "SetMaxBitrate"),
+        };
+        Ok(())
+    }
+
     fn handle_local_ice_candidate(
         &mut self,
         connection: Connection<T>,
@@ -802,6 +1059,37 @@ where
         Ok(())
     }
 
+    fn handle_ice_gathering_complete(
+        &mut self,
+        connection: Connection<T>,
+        state: ConnectionState,
+    ) -> Result<()> {
+        match state {
+            ConnectionState::NotYetStarted
+            | ConnectionState::Terminating
+            | ConnectionState::Terminated => {
+                warn!("State is now idle or terminating, ignoring ICE gathering complete...");
+            }
+            _ => {
+                // Queued behind the LocalIceCandidate futures, so every
+                // candidate is buffered by the time this runs.
+                let mut err_connection = connection.clone();
+                let gathered_future = lazy(move |_| {
+                    if connection.terminating()? {
+                        return Ok(());
+                    }
+                    connection.release_held_description()
+                })
+                .map_err(move |err| {
+                    err_connection.inject_internal_error(err, "IceGatheredFuture failed");
+                });
+
+                self.worker_spawn(gathered_future);
+            }
+        }
+        Ok(())
+    }
+
     fn handle_ice_connected(
         &mut self,
         connection: Connection<T>,
@@ -832,6 +1120,7 @@ where
                 // ICE has reconnected after the call was
                 // previously accepted (and connected).  Return to that state
                 // now.
+                connection.cancel_reconnect_timer()?;
                 connection.set_state(ConnectionState::ConnectedAndAccepted)?;
                 self.notify_observer(
                     connection,
@@ -854,9 +1143,15 @@ where
             | ConnectionState::ConnectedBeforeAccepted
             | ConnectionState::ConnectedAndAccepted => {
                 connection.set_state(ConnectionState::IceFailed)?;
-                // For callee -- the call was disconnected while answering/local_ringing
-                // For caller -- the recipient was unreachable
-                self.notify_observer(connection, ConnectionObserverEvent::IceFailed);
+                let event = if state == ConnectionState::ReconnectingAfterAccepted {
+                    // The network the call was using went away.
+                    ConnectionObserverEvent::IceFailedWhileReconnecting
+                } else {
+                    // For callee -- the call was disconnected while answering/local_ringing
+                    // For caller -- the recipient was unreachable
+                    ConnectionObserverEvent::IceFailed
+                };
+                self.notify_observer(connection, event);
             }
             _ => self.unexpected_state(state, "IceFailed"),
         };
@@ -874,6 +1169,7 @@ where
             }
             ConnectionState::ConnectedAndAccepted => {
                 connection.set_state(ConnectionState::ReconnectingAfterAccepted)?;
+                connection.start_reconnect_timer()?;
                 self.notify_observer(
                     connection,
                     ConnectionObserverEvent::ReconnectingAfterAccepted,
@@ -884,6 +1180,83 @@ where
         Ok(())
     }
 
+    fn handle_reconnect_attempts_exhausted(
+        &mut self,
+        connection: Connection<T>,
+        state: ConnectionState,
+    ) -> Result<()> {
+        match state {
+            ConnectionState::ReconnectingAfterAccepted => {
+                // Unlike ICE failing while reconnecting, this isn't taken as
+                // the network going away, so the call ends as a connection
+                // failure.
+                connection.set_state(ConnectionState::IceFailed)?;
+                self.notify_observer(connection, ConnectionObserverEvent::IceFailed);
+            }
+            // Reconnected just as the last attempt ran out.
+            _ => debug!("reconnect attempts exhausted while in state {:?}", state),
+        };
+        Ok(())
+    }
+
+    fn handle_ice_candidate_error(
+        &mut self,
+        connection: Connection<T>,
+        url: String,
+        error_code: u16,
+        error_text: String,
+    ) -> Result<()> {
+        // Purely informational, so report it in any state and don't fail the
+        // call if the app can't be told.
+        let notify_error_future = lazy(move |_| {
+            if connection.terminating()? {
+                return Ok(());
+            }
+            connection.notify_ice_candidate_error(url, error_code, error_text)
+        })
+        .map_err(|err| {
+            warn!("Notify ICE candidate error failed: {}", err);
+        });
+
+        self.notify_spawn(notify_error_future);
+        Ok(())
+    }
+
+    fn handle_ice_network_route_changed(
+        &mut self,
+        connection: Connection<T>,
+        network_route: NetworkRoute,
+    ) -> Result<()> {
+        // Purely informational, like an ICE candidate error.
+        let notify_route_future = lazy(move |_| {
+            if connection.terminating()? {
+                return Ok(());
+            }
+            connection.notify_network_route_changed(network_route)
+        })
+        .map_err(|err| {
+            warn!("Notify network route changed failed: {}", err);
+        });
+
+        self.notify_spawn(notify_route_future);
+        Ok(())
+    }
+
+    fn handle_outgoing_key_frame_encoded(&mut self, connection: Connection<T>) -> Result<()> {
+        let notify_keyframe_future = lazy(move |_| {
+            if connection.terminating()? {
+                return Ok(());
+            }
+            connection.notify_outgoing_keyframe()
+        })
+        .map_err(|err| {
+            warn!("Notify outgoing key frame failed: {}", err);
+        });
+
+        self.notify_spawn(notify_keyframe_future);
+        Ok(())
+    }
+
     fn handle_internal_error(
         &mut self,
         connection: Connection<T>,
@@ -8,7 +8,7 @@ use std::{
     convert::TryInto,
     mem::size_of,
     net::SocketAddr,
-    sync::Arc,
+    sync::{mpsc, Arc},
     time::{Duration, Instant, SystemTime},
 };
 
@@ -16,29 +16,36 @@ use bytes::{Bytes, BytesMut};
 use prost::Message;
 use rand::Rng;
 
-use crate::core::util::uuid_to_string;
+use crate::core::util::{redact_string, uuid_to_string, Clock, SystemClock};
 use crate::{
     common::{
         actor::{Actor, Stopper},
         units::DataRate,
         Result,
     },
-    core::{call_mutex::CallMutex, crypto as frame_crypto, signaling},
+    core::{
+        bandwidth_mode::{BandwidthMode, EffectiveBandwidthMode},
+        call_mutex::CallMutex,
+        crypto as frame_crypto,
+        signaling,
+    },
     error::RingRtcError,
     protobuf,
     webrtc::{
         data_channel::DataChannel,
-        media::{AudioTrack, VideoTrack},
+        media::{AudioTrack, LayerConfig, VideoEncoderCaps, VideoTrack},
         peer_connection::PeerConnection,
         peer_connection_factory::{Certificate, IceServer, PeerConnectionFactory},
         peer_connection_observer::{
+            CandidatePairType,
+            IceCandidateNetwork,
             IceConnectionState,
             PeerConnectionObserver,
             PeerConnectionObserverTrait,
         },
         rtp,
         sdp_observer::{create_ssd_observer, SessionDescription},
-        stats_observer::{create_stats_observer, StatsObserver},
+        stats_observer::{create_audio_energy_observer, create_stats_observer, StatsObserver},
     },
 };
 
@@ -137,7 +144,14 @@ pub trait Observer {
         client_id: ClientId,
         connection_state: ConnectionState,
     );
-    fn handle_join_state_changed(&self, client_id: ClientId, join_state: JoinState);
+    // Carries the outgoing audio mute state as RingRTC has it (None if never set), so that
+    // when joining with start_muted the app learns of it along with Joining.
+    fn handle_join_state_changed(
+        &self,
+        client_id: ClientId,
+        join_state: JoinState,
+        outgoing_audio_muted: Option<bool>,
+    );
     fn handle_max_send_bitrate_changed(&self, _client_id: ClientId, _rate: DataRate) {}
 
     // The following notify the observer of state changes to the remote devices.
@@ -152,12 +166,81 @@ pub trait Observer {
         &self,
         client_id: ClientId,
         joined_members: &[UserId],
+        joined_devices: &[(UserId, DemuxId)],
         creator: Option<UserId>,
         era_id: Option<&str>,
         max_devices: Option<u32>,
         device_count: u32,
     );
 
+    // Notifies the observer that the era of the call changed, which happens
+    // when the call starts, ends, or is restarted by the SFU.  This comes
+    // right before the handle_peek_changed carrying the new era_id.
+    fn handle_era_changed(
+        &self,
+        _client_id: ClientId,
+        _old_era_id: Option<&str>,
+        _new_era_id: Option<&str>,
+    ) {
+    }
+
+    // Notifies the observer that a key frame was just encoded for the local
+    // video.  How often this happens depends on the encoder and the scene:
+    // besides the periodic ones, key frames are sent whenever a receiver asks
+    // for one (for example, when someone joins) or the content changes a lot.
+    fn handle_outgoing_keyframe(&self, _client_id: ClientId, _local_demux_id: DemuxId) {}
+
+    // Notifies the observer of the quality, from 0 (unusable) to 100
+    // (perfect), of a remote device's connection.  This is the remote
+    // device's uplink to the SFU as the SFU sees it, not how well we receive
+    // from the SFU, so it tells which participant is having trouble.  The SFU
+    // reports it periodically; this is only called when it changes.
+    fn handle_participant_quality_changed(
+        &self,
+        _client_id: ClientId,
+        _remote_demux_id: DemuxId,
+        _quality: u8,
+    ) {
+    }
+
+    // Notifies the observer of the audio level, from 0 (silent) to 100
+    // (loudest), of the local device (what is sent) and of each remote
+    // device (what is received), by demux ID.  This comes from the same
+    // frequent poll that chooses the current speaker, so it's enough to
+    // drive a speaking indicator.
+    fn handle_audio_levels(&self, _client_id: ClientId, _audio_levels: Vec<(DemuxId, u8)>) {}
+
+    // Notifies the observer that media keys from a remote device arrived
+    // for the first time, so its media can now be decrypted.  Keys that are
+    // sent again later (after a rotation or a resend) don't trigger it.
+    fn handle_media_keys_received(&self, _client_id: ClientId, _remote_demux_id: DemuxId) {}
+
+    // Notifies the observer of the type of the ICE candidate pair selected
+    // for the connection to the SFU, once it's first selected and again
+    // whenever ICE switches to another pair.  Relay means media goes
+    // through a TURN server, which usually costs latency.  Only called
+    // once joined, since it's reported with the local demux ID.
+    fn handle_candidate_pair_changed(
+        &self,
+        _client_id: ClientId,
+        _local_demux_id: DemuxId,
+        _candidate_pair_type: CandidatePairType,
+    ) {
+    }
+
+    // Notifies the observer that the SFU turned down the join request, with
+    // the HTTP status and whatever message came with it (redacted).  This
+    // comes right before handle_ended with EndReason::SfuClientFailedToJoin,
+    // and lets the app tell apart, say, a 403 (not a member of the call)
+    // from a generic failure.
+    fn handle_join_rejected(
+        &self,
+        _client_id: ClientId,
+        _http_status: u16,
+        _message: Option<&str>,
+    ) {
+    }
+
     // This is separate from handle_remote_devices_changed because everything else
     // is a pure state that can be copied, deleted, etc.
     // But the VideoTrack is a special handle which must be attached to.
@@ -170,6 +253,20 @@ pub trait Observer {
         incoming_video_track: VideoTrack,
     );
 
+    // A remote device may send a second audio track carrying an interpreter
+    // channel.  It arrives separately from the main audio (which is mixed and
+    // played automatically) and is identified by the same DemuxId as the
+    // device's main audio and video; the role of the track is what tells the
+    // two audio tracks apart.  The track starts disabled so that nothing is
+    // played until the app enables it.
+    fn handle_incoming_interpreter_audio_track(
+        &mut self,
+        _client_id: ClientId,
+        _remote_demux_id: DemuxId,
+        _incoming_interpreter_audio_track: AudioTrack,
+    ) {
+    }
+
     // This will be the last callback.
     // The observer can assume the Call is completely shut down and can be deleted.
     fn handle_ended(&self, client_id: ClientId, reason: EndReason);
@@ -246,6 +343,29 @@ pub enum JoinState {
     Joined(DemuxId, String),
 }
 
+/// Options for Client::join_with_options().
+#[derive(Clone, Copy, Debug, Default)]
+pub struct JoinOptions {
+    /// Join with outgoing audio already muted, so that nothing is sent
+    /// before the app gets a chance to mute.
+    pub start_muted: bool,
+}
+
+/// The session a client was part of before the app was terminated, used
+/// to rejoin the same call without leaving a ghost of ourselves behind.
+///
+/// The SFU only learns that a device is gone once its heartbeats stop, so
+/// after a relaunch the previous demux ID is usually still listed when we
+/// rejoin.  While the era is unchanged, a device with the previous demux ID
+/// is treated as our own: it is never reported as a remote device, and once
+/// we are joined the SFU is asked to remove it.  If the era has changed, the
+/// previous session ended with the old call and is ignored.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PreviousSession {
+    pub era_id:   String,
+    pub demux_id: DemuxId,
+}
+
 // The info about SFU needed in order to connect to it.
 #[derive(Clone, Debug)]
 pub struct SfuInfo {
@@ -270,6 +390,23 @@ pub struct PeekInfo {
     pub device_count: u32,
 }
 
+impl PeekInfo {
+    /// The joined devices, each paired with the user it belongs to.
+    /// Like joined members, this ignores devices of users that aren't in the group.
+    /// A user joined on several devices appears once per device.
+    pub fn joined_devices(&self) -> Vec<(UserId, DemuxId)> {
+        self.devices
+            .iter()
+            .filter_map(|device| {
+                device
+                    .user_id
+                    .clone()
+                    .map(|user_id| (user_id, device.demux_id))
+            })
+            .collect()
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct PeekDeviceInfo {
     pub demux_id:        DemuxId,
@@ -304,6 +441,51 @@ pub enum EndReason {
     HasMaxDevices,
 }
 
+impl EndReason {
+    // These values are shared with the apps (see GroupCallEndReason in Swift),
+    // so they are spelled out rather than derived from the declaration order.
+    pub fn as_i32(self) -> i32 {
+        match self {
+            EndReason::DeviceExplicitlyDisconnected => 0,
+            EndReason::ServerExplicitlyDisconnected => 1,
+            EndReason::CallManagerIsBusy => 2,
+            EndReason::SfuClientFailedToJoin => 3,
+            EndReason::FailedToCreatePeerConnectionFactory => 4,
+            EndReason::FailedToGenerateCertificate => 5,
+            EndReason::FailedToCreatePeerConnection => 6,
+            EndReason::FailedToCreateDataChannel => 7,
+            EndReason::FailedToStartPeerConnection => 8,
+            EndReason::FailedToUpdatePeerConnection => 9,
+            EndReason::FailedToSetMaxSendBitrate => 10,
+            EndReason::IceFailedWhileConnecting => 11,
+            EndReason::IceFailedAfterConnected => 12,
+            EndReason::ServerChangedDemuxId => 13,
+            EndReason::HasMaxDevices => 14,
+        }
+    }
+
+    pub fn from_i32(value: i32) -> Option<Self> {
+        match value {
+            0 => Some(EndReason::DeviceExplicitlyDisconnected),
+            1 => Some(EndReason::ServerExplicitlyDisconnected),
+            2 => Some(EndReason::CallManagerIsBusy),
+            3 => Some(EndReason::SfuClientFailedToJoin),
+            4 => Some(EndReason::FailedToCreatePeerConnectionFactory),
+            5 => Some(EndReason::FailedToGenerateCertificate),
+            6 => Some(EndReason::FailedToCreatePeerConnection),
+            7 => Some(EndReason::FailedToCreateDataChannel),
+            8 => Some(EndReason::FailedToStartPeerConnection),
+            9 => Some(EndReason::FailedToUpdatePeerConnection),
+            10 => Some(EndReason::FailedToSetMaxSendBitrate),
+            11 => Some(EndReason::IceFailedWhileConnecting),
+            12 => Some(EndReason::IceFailedAfterConnected),
+            13 => Some(EndReason::ServerChangedDemuxId),
+            14 => Some(EndReason::HasMaxDevices),
+            _ => None,
+        }
+    }
+}
+
 pub type BoxedPeekInfoHandler = Box<dyn FnOnce(Result<PeekInfo>) + Send + 'static>;
 
 // The callbacks from the Client to the "SFU client" for the group call.
@@ -352,6 +534,8 @@ pub struct RemoteDeviceState {
     // Sorting using this value will give a history of who spoke.
     pub speaker_time:        Option<SystemTime>,
     pub leaving_received:    bool,
+    // The last quality of this device's uplink reported by the SFU.
+    connection_quality:      Option<u8>,
 }
 
 fn as_unix_millis(t: Option<SystemTime>) -> u64 {
@@ -387,6 +571,7 @@ impl RemoteDeviceState {
             added_time,
             speaker_time: None,
             leaving_received: false,
+            connection_quality: None,
         }
     }
 
@@ -408,6 +593,86 @@ pub struct VideoRequest {
     pub height:    u16,
     // If not specified, it means unrestrained framerate.
     pub framerate: Option<u16>,
+    // Pinned requests get first claim on the incoming videos allowed.
+    // See limit_incoming_video_requests.
+    pub pinned:    bool,
+}
+
+// Keeps at most max_incoming_videos requests for video and sets the resolution
+// of the rest to 0 so that the SFU stops sending (and we stop decoding) them.
+// Requests are kept in this order:
+// 1. Pinned requests.
+// 2. The most recent speakers.
+// 3. The devices that were added earliest, then by demux ID.
+// Requests for unknown devices are dropped.  Requests for no video (such as
+// for hidden videos) don't count against the limit.
+fn limit_incoming_video_requests(
+    requests: &[VideoRequest],
+    remote_devices: &[RemoteDeviceState],
+    max_incoming_videos: u32,
+) -> Vec<VideoRequest> {
+    let mut ranked: Vec<(&VideoRequest, &RemoteDeviceState)> = requests
+        .iter()
+        .filter_map(|request| {
+            remote_devices
+                .iter()
+                .find(|device| device.demux_id == request.demux_id)
+                .map(|device| (request, device))
+        })
+        .collect();
+    ranked.sort_by_key(|(request, device)| {
+        (
+            !request.pinned,
+            std::cmp::Reverse(device.speaker_time_as_unix_millis()),
+            device.added_time_as_unix_millis(),
+            device.demux_id,
+        )
+    });
+    let mut kept = 0;
+    ranked
+        .into_iter()
+        .map(|(request, _)| {
+            let mut limited = request.clone();
+            if limited.width > 0 && limited.height > 0 {
+                if kept < max_incoming_videos {
+                    kept += 1;
+                } else {
+                    limited.width = 0;
+                    limited.height = 0;
+                }
+            }
+            limited
+        })
+        .collect()
+}
+
+// Keeps the current speaker unless it went silent or another device is
+// SPEAKER_SWITCH_ENERGY_RATIO times louder, in which case the loudest
+// device that isn't silent takes over.
+fn choose_current_speaker(
+    current_speaker_demux_id: Option<DemuxId>,
+    energies: &[(DemuxId, f64)],
+) -> Option<DemuxId> {
+    let (loudest_demux_id, loudest_energy) = energies
+        .iter()
+        .filter(|(_, energy)| *energy >= SILENT_AUDIO_ENERGY)
+        .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))?;
+    let current_energy = energies
+        .iter()
+        .find(|(demux_id, _)| Some(*demux_id) == current_speaker_demux_id)
+        .map(|(_, energy)| *energy)
+        .filter(|energy| *energy >= SILENT_AUDIO_ENERGY);
+    match current_energy {
+        Some(current_energy) if *loudest_energy < current_energy * SPEAKER_SWITCH_ENERGY_RATIO => {
+            current_speaker_demux_id
+        }
+        _ => Some(*loudest_demux_id),
+    }
+}
+
+// Maps an audio level from WebRTC (0 to 1) to 0 to 100.
+fn normalize_audio_level(level: f64) -> u8 {
+    (level * 100.0).round().max(0.0).min(100.0) as u8
 }
 
 // This must stay in sync with the data PT in SfuClient.
@@ -497,10 +762,22 @@ struct State {
     join_state:       JoinState,
     remote_devices:   Vec<RemoteDeviceState>,
 
+    // Things to coalesce Observer::handle_remote_devices_changed
+    // See set_roster_update_interval.
+    roster_update_interval:  Duration,
+    last_roster_update_time: Option<Instant>,
+    roster_update_pending:   bool,
+    roster_clock:            Box<dyn Clock>,
+
     // Things to control peeking
     remote_devices_request_state: RemoteDevicesRequestState,
     last_peek_info:               Option<PeekInfo>,
     known_members:                HashSet<UserId>,
+    // Set by refresh_peek() so the next peek is delivered even if nothing changed
+    peek_refresh_requested:       bool,
+    // Cleared once the SFU no longer lists the previous device (or the era changes)
+    previous_session:             Option<PreviousSession>,
+    previous_device_left:         bool,
 
     // Derived from remote_devices but stored so we can fire
     // Observer::handle_peek_changed only when it changes
@@ -512,6 +789,11 @@ struct State {
     outgoing_audio_muted: Option<bool>,
     outgoing_video_muted: Option<bool>,
 
+    // Kept so it can be disabled when joining muted.
+    outgoing_audio_track: AudioTrack,
+    // Kept so it can be paused in low resource mode.
+    outgoing_video_track: Option<VideoTrack>,
+
     // Things for controlling the PeerConnection
     local_ice_ufrag:                  String,
     local_ice_pwd:                    String,
@@ -527,6 +809,10 @@ struct State {
     next_stats_time: Option<Instant>,
     stats_observer:  Box<StatsObserver>,
 
+    // Polled every tick while joined, for the current speaker.
+    audio_energy_observer:    Box<StatsObserver>,
+    current_speaker_demux_id: Option<DemuxId>,
+
     // We have to put this inside the actor state also because
     // we change the keys from within the actor.
     frame_crypto_context: Arc<CallMutex<frame_crypto::Context>>,
@@ -557,9 +843,31 @@ struct State {
     video_requests:                               Option<Vec<VideoRequest>>,
     on_demand_video_request_sent_since_last_tick: bool,
     speaker_rtp_timestamp:                        Option<rtp::Timestamp>,
+    // If set, requests beyond this many are not delivered.
+    // See limit_incoming_video_requests.
+    max_incoming_videos:                          Option<u32>,
+    // Remote videos the local user has chosen not to see.  We request no video
+    // for these and disable their tracks, but the remote devices aren't told.
+    // Forgotten once the device leaves.
+    hidden_video_demux_ids:                       HashSet<DemuxId>,
+    // While set, no video is requested or sent.  See set_low_resource_mode.
+    low_resource_mode:                            bool,
 
     // If unset, will use automatic behavior
-    max_send_bitrate: Option<DataRate>,
+    max_send_bitrate:    Option<DataRate>,
+    // The rate last applied to the PeerConnection, automatic or not.
+    send_bitrate:        Option<DataRate>,
+    // Applied on top of max_send_bitrate.  See set_outgoing_video_caps.
+    outgoing_video_caps: VideoEncoderCaps,
+
+    // Whether the interpreter audio SSRCs are described to the PeerConnection.
+    interpreter_audio_enabled:         bool,
+    // Kept so the app's track outlives the PeerConnection's use of it.
+    outgoing_interpreter_audio_track:  Option<AudioTrack>,
+    // Kept so set_remote_interpreter_audio_enabled can reach them.
+    incoming_interpreter_audio_tracks: HashMap<DemuxId, AudioTrack>,
+    // Kept so set_remote_video_hidden can reach them.
+    incoming_video_tracks:             HashMap<DemuxId, VideoTrack>,
 
     actor: Actor<State>,
 }
@@ -571,6 +879,15 @@ const TICK_INTERVAL_SECS: u64 = 1;
 // The stats period, how often to get and log them.
 const STATS_INTERVAL_SECS: u64 = 10;
 
+// The received audio energy over a tick below which a device counts as
+// silent.  That's an average level of about -50 dBFS.
+const SILENT_AUDIO_ENERGY: f64 = 0.00001;
+
+// How much more energy than the current speaker another device needs over
+// a tick to take over as the current speaker, so that it doesn't flicker
+// between devices talking at about the same level.
+const SPEAKER_SWITCH_ENERGY_RATIO: f64 = 2.0;
+
 impl Client {
     #[allow(clippy::too_many_arguments)]
     pub fn start(
@@ -582,8 +899,12 @@ impl Client {
         peer_connection_factory: Option<PeerConnectionFactory>,
         outgoing_audio_track: AudioTrack,
         outgoing_video_track: Option<VideoTrack>,
+        previous_session: Option<PreviousSession>,
     ) -> Result<Self> {
-        debug!("group_call::Client(outer)::new(client_id: {})", client_id);
+        debug!(
+            "group_call::Client(outer)::new(client_id: {}, previous_session: {:?})",
+            client_id, previous_session
+        );
         let stopper = Stopper::new();
         // We only send with this key until the first person joins, at which point
         // we ratchet the key forward.
@@ -629,12 +950,14 @@ impl Client {
                 let ice_server = IceServer::none();
                 let enable_dtls = true;
                 let enable_rtp_data_channel = true;
+                let outgoing_audio_track_for_state = outgoing_audio_track.clone();
+                let outgoing_video_track_for_state = outgoing_video_track.clone();
                 let peer_connection = peer_connection_factory
                     .create_peer_connection(
                         peer_connection_observer,
                         certificate,
                         hide_ip,
-                        &ice_server,
+                        &[ice_server],
                         outgoing_audio_track,
                         outgoing_video_track,
                         enable_dtls,
@@ -657,17 +980,28 @@ impl Client {
                     join_state: JoinState::NotJoined,
                     remote_devices: Vec::new(),
 
+                    roster_update_interval: Duration::from_secs(0),
+                    last_roster_update_time: None,
+                    roster_update_pending: false,
+                    roster_clock: Box::new(SystemClock),
+
                     remote_devices_request_state:
                         RemoteDevicesRequestState::WaitingForMembershipProof,
                     last_peek_info: None,
 
                     known_members: HashSet::new(),
+                    peek_refresh_requested: false,
+                    previous_session,
+                    previous_device_left: false,
 
                     joined_members: HashSet::new(),
 
                     outgoing_audio_muted: None,
                     outgoing_video_muted: None,
 
+                    outgoing_audio_track: outgoing_audio_track_for_state,
+                    outgoing_video_track: outgoing_video_track_for_state,
+
                     local_dtls_fingerprint,
                     sfu_info: None,
                     peer_connection_observer_impl,
@@ -677,6 +1011,8 @@ impl Client {
 
                     next_stats_time: None,
                     stats_observer: create_stats_observer(),
+                    audio_energy_observer: create_audio_energy_observer(),
+                    current_speaker_demux_id: None,
 
                     frame_crypto_context,
                     pending_media_receive_keys: Vec::new(),
@@ -685,8 +1021,18 @@ impl Client {
                     video_requests: None,
                     on_demand_video_request_sent_since_last_tick: false,
                     speaker_rtp_timestamp: None,
+                    max_incoming_videos: None,
+                    hidden_video_demux_ids: HashSet::new(),
+                    low_resource_mode: false,
 
                     max_send_bitrate: None,
+                    send_bitrate: None,
+                    outgoing_video_caps: VideoEncoderCaps::default(),
+
+                    interpreter_audio_enabled: false,
+                    outgoing_interpreter_audio_track: None,
+                    incoming_interpreter_audio_tracks: HashMap::new(),
+                    incoming_video_tracks: HashMap::new(),
 
                     actor,
                 })
@@ -720,12 +1066,28 @@ impl Client {
             warn!("Failed to send regular heartbeat: {:?}", err);
         }
 
+        if state.next_stats_time.is_some() {
+            // The energy of the poll from the previous tick, if it's back.
+            Self::update_current_speaker(state);
+            Self::report_audio_levels(state);
+            let _ = state
+                .peer_connection
+                .get_stats(state.audio_energy_observer.as_ref());
+        }
+
         if let Some(next_stats_time) = state.next_stats_time {
             if now >= next_stats_time {
                 let _ = state
                     .peer_connection
                     .get_stats(state.stats_observer.as_ref());
                 state.next_stats_time = Some(now + Duration::from_secs(STATS_INTERVAL_SECS));
+
+                // The resolution cap depends on the size of the captured frames.
+                if state.outgoing_video_caps != VideoEncoderCaps::default() {
+                    state
+                        .peer_connection
+                        .set_outgoing_video_caps(&state.outgoing_video_caps);
+                }
             }
         }
 
@@ -894,15 +1256,21 @@ impl Client {
     }
 
     pub fn join(&self) {
+        self.join_with_options(JoinOptions::default());
+    }
+
+    /// Like join(), but with options that take effect before the first
+    /// JoinState change is reported.
+    pub fn join_with_options(&self, options: JoinOptions) {
         debug!(
-            "group_call::Client(outer)::join(client_id: {})",
-            self.client_id
+            "group_call::Client(outer)::join(client_id: {}, options: {:?})",
+            self.client_id, options
         );
         let callback = self.clone();
         self.actor.send(move |state| {
             debug!(
-                "group_call::Client(inner)::join(client_id: {})",
-                state.client_id
+                "group_call::Client(inner)::join(client_id: {}, options: {:?})",
+                state.client_id, options
             );
             match state.join_state {
                 JoinState::Joined(_, _) => {
@@ -920,6 +1288,13 @@ impl Client {
                         }
                     }
                     if Self::take_busy(state) {
+                        if options.start_muted {
+                            // Unlike set_outgoing_audio_muted(), this also disables the track,
+                            // since the app hasn't had a chance to yet.  The app re-enables it
+                            // when unmuting, as it would after muting itself.
+                            state.outgoing_audio_track.set_enabled(false);
+                            state.outgoing_audio_muted = Some(true);
+                        }
                         Self::set_join_state_and_notify_observer(state, JoinState::Joining);
 
                         // Request group membership refresh before joining.
@@ -948,9 +1323,11 @@ impl Client {
             join_state
         );
         state.join_state = join_state.clone();
-        state
-            .observer
-            .handle_join_state_changed(state.client_id, join_state);
+        state.observer.handle_join_state_changed(
+            state.client_id,
+            join_state,
+            state.outgoing_audio_muted,
+        );
     }
 
     pub fn leave(&self) {
@@ -984,6 +1361,7 @@ impl Client {
                 }
                 Self::set_join_state_and_notify_observer(state, JoinState::NotJoined);
                 state.next_stats_time = None;
+                state.current_speaker_demux_id = None;
             }
         }
     }
@@ -1002,6 +1380,28 @@ impl Client {
         });
     }
 
+    // Like disconnect(), but blocks until the actor has processed the
+    // request, so the final connection state and handle_ended() have
+    // been delivered to the observer by the time this returns.
+    // If the actor has already stopped, the task is dropped unrun,
+    // which drops the sender and unblocks the wait as well.
+    pub fn disconnect_and_wait(&self) {
+        debug!(
+            "group_call::Client(outer)::disconnect_and_wait(client_id: {})",
+            self.client_id
+        );
+        let (done_sender, done_receiver) = mpsc::channel::<()>();
+        self.actor.send(move |state| {
+            debug!(
+                "group_call::Client(inner)::disconnect_and_wait(client_id: {})",
+                state.client_id
+            );
+            Self::end(state, EndReason::DeviceExplicitlyDisconnected);
+            let _ = done_sender.send(());
+        });
+        let _ = done_receiver.recv();
+    }
+
     pub fn set_outgoing_audio_muted(&self, muted: bool) {
         debug!(
             "group_call::Client(outer)::set_audio_muted(client_id: {}, muted: {})",
@@ -1023,6 +1423,101 @@ impl Client {
         });
     }
 
+    /// Turns interpreter audio on or off.  While off (the default), the group
+    /// call SDP leaves out the interpreter SSRCs, so interpreter audio is
+    /// neither sent nor received.  While on, each remote device's interpreter
+    /// audio arrives through Observer::handle_incoming_interpreter_audio_track.
+    pub fn set_interpreter_audio_enabled(&self, enabled: bool) {
+        debug!(
+            "group_call::Client(outer)::set_interpreter_audio_enabled(client_id: {}, enabled: {})",
+            self.client_id, enabled
+        );
+        self.actor.send(move |state| {
+            debug!(
+                "group_call::Client(inner)::set_interpreter_audio_enabled(client_id: {})",
+                state.client_id
+            );
+            Self::set_interpreter_audio_enabled_inner(state, enabled);
+        });
+    }
+
+    fn set_interpreter_audio_enabled_inner(state: &mut State, enabled: bool) {
+        if state.interpreter_audio_enabled == enabled {
+            return;
+        }
+        state.interpreter_audio_enabled = enabled;
+
+        // Before joining, the descriptions pick this up when they are first set.
+        let result = if let (Some(sfu_info), JoinState::Joined(local_demux_id, _)) =
+            (&state.sfu_info, &state.join_state)
+        {
+            let remote_demux_ids: Vec<DemuxId> =
+                state.remote_devices.iter().map(|rd| rd.demux_id).collect();
+            Self::set_peer_connection_descriptions(
+                state,
+                sfu_info,
+                *local_demux_id,
+                &remote_demux_ids,
+            )
+        } else {
+            Ok(())
+        };
+        if result.is_err() {
+            Self::end(state, EndReason::FailedToUpdatePeerConnection);
+        }
+    }
+
+    /// Sends a second audio track (such as a sign language or spoken language
+    /// interpreter) alongside the main one.  The track must have the ID
+    /// "interpreter1".  It can only be set once per call, and turns on
+    /// interpreter audio (see set_interpreter_audio_enabled).
+    pub fn set_interpreter_track(&self, track: AudioTrack) {
+        debug!(
+            "group_call::Client(outer)::set_interpreter_track(client_id: {}, track: {})",
+            self.client_id, track
+        );
+        self.actor.send(move |state| {
+            debug!(
+                "group_call::Client(inner)::set_interpreter_track(client_id: {})",
+                state.client_id
+            );
+            if state.outgoing_interpreter_audio_track.is_some() {
+                warn!("Ignoring interpreter track because one was already set");
+                return;
+            }
+            Self::set_interpreter_audio_enabled_inner(state, true);
+            match state.peer_connection.add_interpreter_audio_track(&track) {
+                Ok(()) => {
+                    state.outgoing_interpreter_audio_track = Some(track);
+                }
+                Err(err) => {
+                    warn!("Failed to add interpreter track: {:?}", err);
+                }
+            }
+        });
+    }
+
+    /// Plays or silences the interpreter audio of a remote device.  Incoming
+    /// interpreter audio starts out silenced; see
+    /// Observer::handle_incoming_interpreter_audio_track.
+    pub fn set_remote_interpreter_audio_enabled(&self, demux_id: DemuxId, enabled: bool) {
+        debug!(
+            "group_call::Client(outer)::set_remote_interpreter_audio_enabled(client_id: {}, demux_id: {}, enabled: {})",
+            self.client_id, demux_id, enabled,
+        );
+        self.actor.send(move |state| {
+            debug!(
+                "group_call::Client(inner)::set_remote_interpreter_audio_enabled(client_id: {})",
+                state.client_id
+            );
+            if let Some(track) = state.incoming_interpreter_audio_tracks.get(&demux_id) {
+                track.set_enabled(enabled);
+            } else {
+                warn!("No interpreter audio track for demux_id {}", demux_id);
+            }
+        });
+    }
+
     pub fn set_outgoing_video_muted(&self, muted: bool) {
         debug!(
             "group_call::Client(outer)::set_video_muted(client_id: {}, muted: {})",
@@ -1105,6 +1600,7 @@ impl Client {
                 warn!("Could not set max send bitrate to {:?}", rate);
             } else {
                 info!("Set max send bitrate to {:?}", rate);
+                state.send_bitrate = Some(rate);
                 state
                     .observer
                     .handle_max_send_bitrate_changed(state.client_id, rate);
@@ -1112,6 +1608,34 @@ impl Client {
         }
     }
 
+    // Unless the app set a max send bitrate, the send bitrate follows the
+    // number of devices in the call, so the mode is adaptive.  Until the
+    // client connects, nothing holds it back.
+    pub fn effective_bandwidth_mode(&self) -> EffectiveBandwidthMode {
+        debug!(
+            "group_call::Client(outer)::effective_bandwidth_mode(client_id: {})",
+            self.client_id
+        );
+        let (mode_sender, mode_receiver) = mpsc::channel::<EffectiveBandwidthMode>();
+        self.actor.send(move |state| {
+            debug!(
+                "group_call::Client(inner)::effective_bandwidth_mode(client_id: {})",
+                state.client_id
+            );
+            let mode = state.send_bitrate.map_or(BandwidthMode::Normal, |rate| {
+                BandwidthMode::from_bitrate(rate.as_bps())
+            });
+            let _ = mode_sender.send(EffectiveBandwidthMode {
+                mode,
+                adaptive: state.max_send_bitrate.is_none(),
+            });
+        });
+        mode_receiver.recv().unwrap_or(EffectiveBandwidthMode {
+            mode:     BandwidthMode::Normal,
+            adaptive: true,
+        })
+    }
+
     pub fn request_video(&self, requests: Vec<VideoRequest>) {
         debug!(
             "group_call::Client(outer)::request_video(client_id: {}, requests: {:?})",
@@ -1130,108 +1654,455 @@ impl Client {
         });
     }
 
-    fn send_video_requests_to_sfu(state: &mut State) {
-        use protobuf::group_call::{
-            device_to_sfu::{
-                video_request_message::VideoRequest as VideoRequestProto,
-                VideoRequestMessage,
-            },
-            DeviceToSfu,
-        };
-        use std::cmp::min;
+    pub fn set_max_incoming_videos(&self, max_incoming_videos: u32) {
+        debug!(
+            "group_call::Client(outer)::set_max_incoming_videos(client_id: {}, max_incoming_videos: {})",
+            self.client_id, max_incoming_videos,
+        );
+        self.actor.send(move |state| {
+            debug!(
+                "group_call::Client(inner)::set_max_incoming_videos(client_id: {})",
+                state.client_id
+            );
+            state.max_incoming_videos = Some(max_incoming_videos);
+            if !state.on_demand_video_request_sent_since_last_tick {
+                Self::send_video_requests_to_sfu(state);
+                state.on_demand_video_request_sent_since_last_tick = true;
+            }
+        });
+    }
 
-        if let Some(video_requests) = &state.video_requests {
-            let requests: Vec<_> = video_requests
-                .iter()
-                .filter_map(|request| {
-                    state
-                        .remote_devices
-                        .iter()
-                        .find(|device| device.demux_id == request.demux_id)
-                        .map(|device| {
-                            VideoRequestProto {
-                                short_device_id: Some(device.short_device_id),
-                                // We use the min because the SFU does not understand the concept of video rotation
-                                // so all requests must be in terms of non-rotated video even though the apps
-                                // will request in terms of rotated video.  We assume that all video is sent over the
-                                // wire in landscape format with rotation metadata.
-                                // If it's not, we'll have a problem.
-                                height:          Some(min(request.height, request.width) as u32),
-                            }
-                        })
-                })
-                .collect();
-            match encode_proto(DeviceToSfu {
-                video_request: Some(VideoRequestMessage {
-                    // TODO: Update the server to handle this as expected or remove this altogether.
-                    // The client needs the server to sort by resolution and then cap the number after that sort.
-                    // Currently, the server is sorting by audio activity and then capping the number.
-                    // Two possible fixes on the server:
-                    // A. Sort by resolution and then cap.
-                    //    After that, the client could re-add the lines below.
-                    // B. Treat the list of resolution requests as "complete" and don't use "lastN" at all.
-                    //    After that, the client could remove the lines below.
-                    // Note: the server can't handle a None value here, so we have to pass
-                    // in a value larger than a group call would ever be.
-                    // The only problem with this mechanism is that the server will send video for
-                    // new remote devices that the local device hasn't yet learned about.
-                    // max: Some(
-                    //     requests
-                    //         .iter()
-                    //         .filter(|request| request.height.unwrap() > 0)
-                    //         .count() as u32,
-                    // ),
-                    max: Some(1000000),
-                    requests,
-                }),
-                ..DeviceToSfu::default()
-            }) {
-                Err(e) => {
-                    warn!("Failed to encode video request: {:?}", e);
-                }
-                Ok(msg) => {
-                    if let Err(e) = Self::send_data_to_sfu(state, &msg) {
-                        warn!("Failed to send video request: {:?}", e);
-                    }
+    /// Stops (or resumes) receiving video from a remote device, such as a
+    /// flickering feed the user would rather not see.  Audio is unaffected.
+    /// This is local only: the remote device isn't told and keeps sending
+    /// its video to the SFU for everyone else.
+    ///
+    /// Besides no longer requesting the video, the device's incoming video
+    /// track is disabled, so frames already on their way reach the sinks
+    /// as black frames.  The device is unhidden once it leaves the call.
+    pub fn set_remote_video_hidden(&self, demux_id: DemuxId, hidden: bool) {
+        debug!(
+            "group_call::Client(outer)::set_remote_video_hidden(client_id: {}, demux_id: {}, hidden: {})",
+            self.client_id, demux_id, hidden,
+        );
+        self.actor.send(move |state| {
+            debug!(
+                "group_call::Client(inner)::set_remote_video_hidden(client_id: {})",
+                state.client_id
+            );
+            let changed = if hidden {
+                state.hidden_video_demux_ids.insert(demux_id)
+            } else {
+                state.hidden_video_demux_ids.remove(&demux_id)
+            };
+            if changed {
+                if let Some(track) = state.incoming_video_tracks.get(&demux_id) {
+                    track.set_enabled(!hidden);
                 }
             }
-        }
+            if changed && !state.on_demand_video_request_sent_since_last_tick {
+                Self::send_video_requests_to_sfu(state);
+                state.on_demand_video_request_sent_since_last_tick = true;
+            }
+        });
     }
 
-    pub fn set_group_members(&self, group_members: Vec<GroupMemberInfo>) {
+    /// Caps the resolution and frame rate of the outgoing video, such as to
+    /// save battery.  The caps bound the video in addition to the send
+    /// bitrate, which can lower it further but never lifts the caps.
+    /// VideoEncoderCaps::default() removes them.
+    pub fn set_outgoing_video_caps(&self, caps: VideoEncoderCaps) {
         debug!(
-            "group_call::Client(outer)::set_group_members(client_id: {})",
-            self.client_id
+            "group_call::Client(outer)::set_outgoing_video_caps(client_id: {}, caps: {:?})",
+            self.client_id, caps,
         );
         self.actor.send(move |state| {
             debug!(
-                "group_call::Client(inner)::set_group_members(client_id: {})",
+                "group_call::Client(inner)::set_outgoing_video_caps(client_id: {})",
                 state.client_id
             );
-            let new_members: HashSet<UserId> =
-                group_members.iter().map(|i| i.user_id.clone()).collect();
-            if new_members != state.known_members {
-                info!("known group members changed");
-                state.known_members = new_members;
-                state.sfu_client.set_group_members(group_members);
-                Self::request_remote_devices_as_soon_as_possible(state);
-            }
-        })
+            state.outgoing_video_caps = caps;
+            state.peer_connection.set_outgoing_video_caps(&caps);
+        });
     }
 
-    pub fn set_membership_proof(&self, proof: MembershipProof) {
+    /// Returns the simulcast layers the outgoing video encoder is
+    /// configured to produce, lowest first, and logs them, to check them
+    /// against what the SFU expects.  Blocks until the actor answers, and
+    /// returns no layers if it has already stopped.
+    pub fn outgoing_simulcast_config(&self) -> Vec<LayerConfig> {
         debug!(
-            "group_call::Client(outer)::set_membership_proof(client_id: {})",
+            "group_call::Client(outer)::outgoing_simulcast_config(client_id: {})",
             self.client_id
         );
+        let (layers_sender, layers_receiver) = mpsc::channel::<Vec<LayerConfig>>();
         self.actor.send(move |state| {
             debug!(
-                "group_call::Client(inner)::set_membership_proof(client_id: {})",
+                "group_call::Client(inner)::outgoing_simulcast_config(client_id: {})",
                 state.client_id
             );
-            state.sfu_client.set_membership_proof(proof);
-            if matches!(
-                state.remote_devices_request_state,
+            let layers = state.peer_connection.outgoing_video_layers();
+            for (index, layer) in layers.iter().enumerate() {
+                info!(
+                    "group_call::Client::outgoing_simulcast_config(client_id: {}): layer {}: {:?}",
+                    state.client_id, index, layer
+                );
+            }
+            let _ = layers_sender.send(layers);
+        });
+        layers_receiver.recv().unwrap_or_default()
+    }
+
+    /// Returns the remote device heard the most over the last tick, or None
+    /// if every remote device was silent.  The current speaker only changes
+    /// when another device is clearly louder, so that it doesn't flicker.
+    /// Blocks until the actor answers, and returns None if it has already
+    /// stopped.
+    pub fn current_speaker(&self) -> Option<DemuxId> {
+        debug!(
+            "group_call::Client(outer)::current_speaker(client_id: {})",
+            self.client_id
+        );
+        let (speaker_sender, speaker_receiver) = mpsc::channel::<Option<DemuxId>>();
+        self.actor.send(move |state| {
+            debug!(
+                "group_call::Client(inner)::current_speaker(client_id: {})",
+                state.client_id
+            );
+            let _ = speaker_sender.send(state.current_speaker_demux_id);
+        });
+        speaker_receiver.recv().unwrap_or(None)
+    }
+
+    // Chooses the current speaker from the received audio energy of the
+    // latest poll, if there is a new one.
+    fn update_current_speaker(state: &mut State) {
+        let energies = match state.audio_energy_observer.take_received_audio_energy() {
+            Some(energies) => energies,
+            None => return,
+        };
+        // The audio SSRC of a remote device is its demux ID.
+        let energies: Vec<(DemuxId, f64)> = energies
+            .into_iter()
+            .filter(|(ssrc, _)| {
+                state
+                    .remote_devices
+                    .iter()
+                    .any(|device| device.demux_id == *ssrc)
+            })
+            .collect();
+        let current_speaker_demux_id =
+            choose_current_speaker(state.current_speaker_demux_id, &energies);
+        if current_speaker_demux_id != state.current_speaker_demux_id {
+            debug!(
+                "group_call::Client(inner)::update_current_speaker(client_id: {}): {:?}",
+                state.client_id, current_speaker_demux_id
+            );
+            state.current_speaker_demux_id = current_speaker_demux_id;
+        }
+    }
+
+    // Reports the audio levels of the latest poll, if there is a new one,
+    // for the local device and the remote devices that are in the call.
+    fn report_audio_levels(state: &mut State) {
+        let (sent, received) = match state.audio_energy_observer.take_audio_levels() {
+            Some(audio_levels) => audio_levels,
+            None => return,
+        };
+        let mut audio_levels = Vec::new();
+        if let (JoinState::Joined(local_demux_id, _), Some(sent)) = (&state.join_state, sent) {
+            audio_levels.push((*local_demux_id, normalize_audio_level(sent)));
+        }
+        // The audio SSRC of a remote device is its demux ID.
+        audio_levels.extend(
+            received
+                .into_iter()
+                .filter(|(ssrc, _)| {
+                    state
+                        .remote_devices
+                        .iter()
+                        .any(|device| device.demux_id == *ssrc)
+                })
+                .map(|(ssrc, level)| (ssrc, normalize_audio_level(level))),
+        );
+        if !audio_levels.is_empty() {
+            state
+                .observer
+                .handle_audio_levels(state.client_id, audio_levels);
+        }
+    }
+
+    /// Sheds load quickly, such as when the OS warns of memory pressure,
+    /// without leaving the call: all incoming video is dropped (so none is
+    /// decoded) and the outgoing video track is disabled, while audio is kept.
+    /// Other devices see the local video as muted.
+    ///
+    /// The app shouldn't enable the outgoing video track while in this mode.
+    ///
+    /// request_video() still records the latest requests while in this mode,
+    /// but nothing is requested from the SFU until the mode is exited, at
+    /// which point those requests are sent.
+    pub fn set_low_resource_mode(&self, enabled: bool) {
+        debug!(
+            "group_call::Client(outer)::set_low_resource_mode(client_id: {}, enabled: {})",
+            self.client_id, enabled,
+        );
+        self.actor.send(move |state| {
+            debug!(
+                "group_call::Client(inner)::set_low_resource_mode(client_id: {})",
+                state.client_id
+            );
+            if state.low_resource_mode == enabled {
+                return;
+            }
+            state.low_resource_mode = enabled;
+
+            if let Some(outgoing_video_track) = &state.outgoing_video_track {
+                // Don't turn on video that the user had muted.
+                let video_enabled = !enabled && state.outgoing_video_muted != Some(true);
+                outgoing_video_track.set_enabled(video_enabled);
+            }
+            if let Err(err) = Self::send_heartbeat(state) {
+                warn!(
+                    "Failed to send heartbeat after updating low resource mode: {:?}",
+                    err
+                );
+            }
+            // Unlike other changes to video requests, this one can't wait for the next tick.
+            Self::send_video_requests_to_sfu(state);
+            state.on_demand_video_request_sent_since_last_tick = true;
+        });
+    }
+
+    /// Coalesces Observer::handle_remote_devices_changed so that it fires at
+    /// most once per interval, with the latest remote devices, rather than
+    /// for every mute, speaker or key change while many devices come and go.
+    /// A device leaving is still reported right away (along with anything
+    /// else pending).  The default of zero reports every change immediately.
+    pub fn set_roster_update_interval(&self, interval: Duration) {
+        debug!(
+            "group_call::Client(outer)::set_roster_update_interval(client_id: {}, interval: {:?})",
+            self.client_id, interval,
+        );
+        self.actor.send(move |state| {
+            debug!(
+                "group_call::Client(inner)::set_roster_update_interval(client_id: {})",
+                state.client_id
+            );
+            state.roster_update_interval = interval;
+            if state.roster_update_pending && interval == Duration::from_secs(0) {
+                Self::flush_remote_devices_changed(state);
+            }
+        });
+    }
+
+    // Replaces where the roster update interval gets the current time.
+    #[cfg(test)]
+    fn set_roster_clock(&self, clock: Box<dyn Clock>) {
+        self.actor.send(move |state| {
+            state.roster_clock = clock;
+        });
+    }
+
+    // Fires Observer::handle_remote_devices_changed now if the roster update
+    // interval allows, or else once it does, if not already scheduled.
+    fn notify_remote_devices_changed(state: &mut State) {
+        let now = state.roster_clock.now();
+        let next_update_time = state
+            .last_roster_update_time
+            .map(|last| last + state.roster_update_interval);
+        match next_update_time {
+            Some(next_update_time) if now < next_update_time => {
+                if !state.roster_update_pending {
+                    state.roster_update_pending = true;
+                    state
+                        .actor
+                        .send_delayed(next_update_time - now, move |state| {
+                            // Already flushed if something significant came along.
+                            if state.roster_update_pending {
+                                Self::flush_remote_devices_changed(state);
+                            }
+                        });
+                }
+            }
+            _ => {
+                Self::flush_remote_devices_changed(state);
+            }
+        }
+    }
+
+    fn flush_remote_devices_changed(state: &mut State) {
+        state.roster_update_pending = false;
+        state.last_roster_update_time = Some(state.roster_clock.now());
+        state
+            .observer
+            .handle_remote_devices_changed(state.client_id, &state.remote_devices);
+    }
+
+    fn send_video_requests_to_sfu(state: &mut State) {
+        use protobuf::group_call::{
+            device_to_sfu::{
+                video_request_message::VideoRequest as VideoRequestProto,
+                VideoRequestMessage,
+            },
+            DeviceToSfu,
+        };
+        use std::cmp::min;
+
+        // Even if the app hasn't requested any video, the SFU must be told
+        // to stop sending it in low resource mode.
+        let no_video_requests = Vec::new();
+        let video_requests = if state.low_resource_mode {
+            state.video_requests.as_ref().or(Some(&no_video_requests))
+        } else {
+            state.video_requests.as_ref()
+        };
+        if let Some(video_requests) = video_requests {
+            let video_requests: Vec<VideoRequest> = video_requests
+                .iter()
+                .map(|request| {
+                    let mut request = request.clone();
+                    if state.low_resource_mode
+                        || state.hidden_video_demux_ids.contains(&request.demux_id)
+                    {
+                        request.width = 0;
+                        request.height = 0;
+                    }
+                    request
+                })
+                .collect();
+            // Requests for devices we don't know about are dropped.
+            let video_requests: Vec<VideoRequest> = match state.max_incoming_videos {
+                Some(max_incoming_videos) => limit_incoming_video_requests(
+                    &video_requests,
+                    &state.remote_devices,
+                    max_incoming_videos,
+                ),
+                None => video_requests
+                    .iter()
+                    .filter(|request| {
+                        state
+                            .remote_devices
+                            .iter()
+                            .any(|device| device.demux_id == request.demux_id)
+                    })
+                    .cloned()
+                    .collect(),
+            };
+            let requests: Vec<_> = video_requests
+                .iter()
+                .filter_map(|request| {
+                    state
+                        .remote_devices
+                        .iter()
+                        .find(|device| device.demux_id == request.demux_id)
+                        .map(|device| {
+                            VideoRequestProto {
+                                short_device_id: Some(device.short_device_id),
+                                // We use the min because the SFU does not understand the concept of video rotation
+                                // so all requests must be in terms of non-rotated video even though the apps
+                                // will request in terms of rotated video.  We assume that all video is sent over the
+                                // wire in landscape format with rotation metadata.
+                                // If it's not, we'll have a problem.
+                                height:          Some(min(request.height, request.width) as u32),
+                            }
+                        })
+                })
+                .collect();
+            match encode_proto(DeviceToSfu {
+                video_request: Some(VideoRequestMessage {
+                    // TODO: Update the server to handle this as expected or remove this altogether.
+                    // The client needs the server to sort by resolution and then cap the number after that sort.
+                    // Currently, the server is sorting by audio activity and then capping the number.
+                    // Two possible fixes on the server:
+                    // A. Sort by resolution and then cap.
+                    //    After that, the client could re-add the lines below.
+                    // B. Treat the list of resolution requests as "complete" and don't use "lastN" at all.
+                    //    After that, the client could remove the lines below.
+                    // Note: the server can't handle a None value here, so we have to pass
+                    // in a value larger than a group call would ever be.
+                    // The only problem with this mechanism is that the server will send video for
+                    // new remote devices that the local device hasn't yet learned about.
+                    // max: Some(
+                    //     requests
+                    //         .iter()
+                    //         .filter(|request| request.height.unwrap() > 0)
+                    //         .count() as u32,
+                    // ),
+                    // In low resource mode, this also covers remote devices we don't know about yet.
+                    max: Some(if state.low_resource_mode { 0 } else { 1000000 }),
+                    requests,
+                }),
+                ..DeviceToSfu::default()
+            }) {
+                Err(e) => {
+                    warn!("Failed to encode video request: {:?}", e);
+                }
+                Ok(msg) => {
+                    if let Err(e) = Self::send_data_to_sfu(state, &msg) {
+                        warn!("Failed to send video request: {:?}", e);
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn set_group_members(&self, group_members: Vec<GroupMemberInfo>) {
+        debug!(
+            "group_call::Client(outer)::set_group_members(client_id: {})",
+            self.client_id
+        );
+        self.actor.send(move |state| {
+            debug!(
+                "group_call::Client(inner)::set_group_members(client_id: {})",
+                state.client_id
+            );
+            let new_members: HashSet<UserId> =
+                group_members.iter().map(|i| i.user_id.clone()).collect();
+            if new_members != state.known_members {
+                info!("known group members changed");
+                state.known_members = new_members;
+                state.sfu_client.set_group_members(group_members);
+                Self::request_remote_devices_as_soon_as_possible(state);
+            }
+        })
+    }
+
+    // Peeks right away instead of waiting for the next tick, and delivers
+    // the result through handle_peek_changed even if nothing changed, so
+    // the app can tell the refresh finished.
+    pub fn refresh_peek(&self) {
+        debug!(
+            "group_call::Client(outer)::refresh_peek(client_id: {})",
+            self.client_id
+        );
+        self.actor.send(move |state| {
+            debug!(
+                "group_call::Client(inner)::refresh_peek(client_id: {})",
+                state.client_id
+            );
+            if state.connection_state == ConnectionState::NotConnected {
+                warn!("Can't refresh the peek when not connected.");
+                return;
+            }
+            state.peek_refresh_requested = true;
+            Self::request_remote_devices_as_soon_as_possible(state);
+        });
+    }
+
+    pub fn set_membership_proof(&self, proof: MembershipProof) {
+        debug!(
+            "group_call::Client(outer)::set_membership_proof(client_id: {})",
+            self.client_id
+        );
+        self.actor.send(move |state| {
+            debug!(
+                "group_call::Client(inner)::set_membership_proof(client_id: {})",
+                state.client_id
+            );
+            state.sfu_client.set_membership_proof(proof);
+            if matches!(
+                state.remote_devices_request_state,
                 RemoteDevicesRequestState::WaitingForMembershipProof
             ) {
                 state.remote_devices_request_state = RemoteDevicesRequestState::NeverRequested;
@@ -1324,9 +2195,11 @@ impl Client {
                             let peek_info = peek_info.clone();
                             Self::set_peek_info_inner(state, Ok(peek_info));
                         }
-                        state
-                            .observer
-                            .handle_join_state_changed(state.client_id, state.join_state.clone());
+                        state.observer.handle_join_state_changed(
+                            state.client_id,
+                            state.join_state.clone(),
+                            state.outgoing_audio_muted,
+                        );
                         // We just now appeared in the participants list, and possibly even updated
                         // the eraId.
                         Self::request_remote_devices_as_soon_as_possible(state);
@@ -1338,6 +2211,18 @@ impl Client {
                     }
                 };
             } else {
+                if let Err(err) = &result {
+                    if let Some(RingRtcError::SfuClientJoinRejected(http_status, message)) =
+                        err.downcast_ref::<RingRtcError>()
+                    {
+                        let message = message.as_deref().map(redact_string);
+                        state.observer.handle_join_rejected(
+                            state.client_id,
+                            *http_status,
+                            message.as_deref(),
+                        );
+                    }
+                }
                 Self::end(state, EndReason::SfuClientFailedToJoin);
             }
         });
@@ -1456,6 +2341,42 @@ impl Client {
 
     // Most of the logic moved to inner method so this can be called by both
     // set_peek_info() and as a callback to SfuClient::request_remote_devices.
+    // Returns the demux ID of our device from before a rejoin if the SFU still lists it.
+    // Once joined, asks the SFU to remove that device, and forgets the previous session
+    // when the device is gone or the era has changed.  See PreviousSession.
+    fn reconcile_previous_session(state: &mut State, peek_info: &PeekInfo) -> Option<DemuxId> {
+        let previous_session = state.previous_session.as_ref()?;
+        let previous_device = if peek_info.era_id.as_deref() == Some(&previous_session.era_id) {
+            peek_info
+                .devices
+                .iter()
+                .find(|device| device.demux_id == previous_session.demux_id)
+        } else {
+            None
+        };
+        let previous_device = match previous_device {
+            Some(previous_device) => previous_device,
+            None => {
+                info!("Previous session is no longer in the call; forgetting it");
+                state.previous_session = None;
+                return None;
+            }
+        };
+        if !state.previous_device_left {
+            if let JoinState::Joined(_, _) = state.join_state {
+                info!(
+                    "Asking the SFU to remove our previous device with demux_id: {}",
+                    previous_device.demux_id
+                );
+                state
+                    .sfu_client
+                    .leave(previous_device.long_device_id.clone());
+                state.previous_device_left = true;
+            }
+        }
+        Some(previous_device.demux_id)
+    }
+
     fn set_peek_info_inner(state: &mut State, peek_info: Result<PeekInfo>) {
         debug!(
             "group_call::Client(inner)::set_peek_info_inner(client_id: {}, info: {:?} state: {:?})",
@@ -1493,6 +2414,16 @@ impl Client {
             .filter_map(|device| device.user_id.clone())
             .collect();
 
+        // A user joining on another device doesn't change the joined members,
+        // but it does change the devices, which the observer also wants to know about.
+        let old_joined_devices: HashSet<(UserId, DemuxId)> = match &state.last_peek_info {
+            Some(last_peek_info) => last_peek_info.joined_devices().into_iter().collect(),
+            None => HashSet::new(),
+        };
+        let joined_devices = peek_info.joined_devices();
+        let new_joined_devices: HashSet<(UserId, DemuxId)> =
+            joined_devices.iter().cloned().collect();
+
         let old_era_id = match &state.last_peek_info {
             Some(PeekInfo {
                 era_id: Some(era_id),
@@ -1500,11 +2431,24 @@ impl Client {
             }) => Some(era_id.clone()),
             _ => None,
         };
-        if old_user_ids != new_user_ids || old_era_id != peek_info.era_id {
+        if old_era_id != peek_info.era_id {
+            state.observer.handle_era_changed(
+                state.client_id,
+                old_era_id.as_deref(),
+                peek_info.era_id.as_deref(),
+            );
+        }
+        let peek_refresh_requested = std::mem::take(&mut state.peek_refresh_requested);
+        if old_user_ids != new_user_ids
+            || old_joined_devices != new_joined_devices
+            || old_era_id != peek_info.era_id
+            || peek_refresh_requested
+        {
             let joined_members: Vec<UserId> = new_user_ids.iter().cloned().collect();
             state.observer.handle_peek_changed(
                 state.client_id,
                 &joined_members,
+                &joined_devices,
                 peek_info.creator.clone(),
                 peek_info.era_id.as_deref(),
                 peek_info.max_devices,
@@ -1512,6 +2456,8 @@ impl Client {
             )
         }
 
+        let previous_demux_id = Self::reconcile_previous_session(state, &peek_info);
+
         let peek_info_to_remember = peek_info.clone();
         if let JoinState::Joined(local_demux_id, _) = state.join_state {
             // We remember these before changing state.remote_devices so we can calculate changes after.
@@ -1531,8 +2477,11 @@ impl Client {
                 .devices
                 .into_iter()
                 .filter_map(|device| {
-                    if device.demux_id == local_demux_id {
-                        // Don't add a remote device to represent the local device.
+                    if device.demux_id == local_demux_id
+                        || Some(device.demux_id) == previous_demux_id
+                    {
+                        // Don't add a remote device to represent the local device,
+                        // including the one left over from before a rejoin.
                         return None;
                     }
                     if let PeekDeviceInfo {
@@ -1568,12 +2517,22 @@ impl Client {
                 state.remote_devices.iter().map(|rd| rd.demux_id).collect();
 
             let demux_ids_changed = old_demux_ids != new_demux_ids;
+            let devices_left = !old_demux_ids.is_subset(&new_demux_ids);
             // If demux IDs changed, let the PeerConnection know that related SSRCs changed as well
             if demux_ids_changed {
                 info!(
                     "New set of demux IDs to be pushed down to PeerConnection: {:?}",
                     new_demux_ids
                 );
+                state
+                    .incoming_interpreter_audio_tracks
+                    .retain(|demux_id, _| new_demux_ids.contains(demux_id));
+                state
+                    .incoming_video_tracks
+                    .retain(|demux_id, _| new_demux_ids.contains(demux_id));
+                state
+                    .hidden_video_demux_ids
+                    .retain(|demux_id| new_demux_ids.contains(demux_id));
                 if let Some(sfu_info) = state.sfu_info.as_ref() {
                     let new_demux_ids: Vec<DemuxId> = new_demux_ids.iter().copied().collect();
                     let result = Self::set_peer_connection_descriptions(
@@ -1592,10 +2551,12 @@ impl Client {
             // Note: if the first call to set_peek_info is [], we still fire the
             // handle_remote_devices_changed to ensure the observer can tell the difference
             // between "we know we have no remote devices" and "we don't know what we have yet".
-            if demux_ids_changed || is_first_update {
-                state
-                    .observer
-                    .handle_remote_devices_changed(state.client_id, &state.remote_devices);
+            // Someone leaving isn't held back by set_roster_update_interval,
+            // so their tile doesn't linger.
+            if devices_left || is_first_update {
+                Self::flush_remote_devices_changed(state);
+            } else if demux_ids_changed {
+                Self::notify_remote_devices_changed(state);
             }
 
             if new_user_ids != old_user_ids {
@@ -1603,6 +2564,7 @@ impl Client {
                 state.observer.handle_peek_changed(
                     state.client_id,
                     &joined_members,
+                    &joined_devices,
                     peek_info.creator.clone(),
                     peek_info.era_id.as_deref(),
                     peek_info.max_devices,
@@ -1688,6 +2650,7 @@ impl Client {
             &state.local_ice_pwd,
             &state.local_dtls_fingerprint,
             Some(local_demux_id),
+            state.interpreter_audio_enabled,
         )?;
         let observer = create_ssd_observer();
         state
@@ -1700,6 +2663,7 @@ impl Client {
             &sfu_info.ice_pwd,
             &sfu_info.dtls_fingerprint,
             remote_demux_ids,
+            state.interpreter_audio_enabled,
         )?;
         let observer = create_ssd_observer();
         state
@@ -1823,16 +2787,19 @@ impl Client {
                     "Adding media receive key from {}. client_id: {}",
                     device.demux_id, state.client_id
                 );
-                let mut frame_crypto_context = state
-                    .frame_crypto_context
-                    .lock()
-                    .expect("Get lock for frame encryption context to add media receive key");
-                frame_crypto_context.add_receive_secret(demux_id, ratchet_counter, secret);
+                {
+                    let mut frame_crypto_context = state
+                        .frame_crypto_context
+                        .lock()
+                        .expect("Get lock for frame encryption context to add media receive key");
+                    frame_crypto_context.add_receive_secret(demux_id, ratchet_counter, secret);
+                }
                 let had_media_keys = std::mem::replace(&mut device.media_keys_received, true);
                 if !had_media_keys {
                     state
                         .observer
-                        .handle_remote_devices_changed(state.client_id, &state.remote_devices)
+                        .handle_media_keys_received(state.client_id, demux_id);
+                    Self::notify_remote_devices_changed(state);
                 }
             } else {
                 warn!("Ignoring received media key from user because the demux ID {} doesn't make sense", demux_id);
@@ -1928,6 +2895,23 @@ impl Client {
         }
     }
 
+    // The VP8 frame tag has the lowest bit clear for key frames.
+    // See https://tools.ietf.org/html/rfc6386#section-9.1
+    fn is_vp8_key_frame(frame: &[u8]) -> bool {
+        frame.first().map_or(false, |tag| tag & 0x01 == 0)
+    }
+
+    // Called on the encoder thread, so hop over to the actor to reach the observer.
+    fn notify_outgoing_keyframe(&self) {
+        self.actor.send(move |state| {
+            if let JoinState::Joined(local_demux_id, _) = state.join_state {
+                state
+                    .observer
+                    .handle_outgoing_keyframe(state.client_id, local_demux_id);
+            }
+        });
+    }
+
     // Called by WebRTC through PeerConnectionObserver
     // See comment on FRAME_ENCRYPTION_FOOTER_LEN for more details on the format
     fn get_ciphertext_buffer_size(plaintext_size: usize) -> usize {
@@ -1949,6 +2933,10 @@ impl Client {
             .lock()
             .expect("Get e2ee context to encrypt media");
 
+        if !is_audio && Self::is_vp8_key_frame(plaintext) {
+            self.notify_outgoing_keyframe();
+        }
+
         let unencrypted_header_len = Self::unencrypted_media_header_len(is_audio);
         Self::encrypt(
             &mut frame_crypto_context,
@@ -2082,7 +3070,11 @@ impl Client {
                 heartbeat: {
                     Some(protobuf::group_call::device_to_device::Heartbeat {
                         audio_muted: state.outgoing_audio_muted,
-                        video_muted: state.outgoing_video_muted,
+                        video_muted: if state.low_resource_mode {
+                            Some(true)
+                        } else {
+                            state.outgoing_video_muted
+                        },
                     })
                 },
                 ..Default::default()
@@ -2204,6 +3196,20 @@ impl Client {
                     if let Some(DeviceJoinedOrLeft { .. }) = msg.device_joined_or_left {
                         self.handle_remote_device_joined_or_left();
                     }
+                    if !msg.connection_qualities.is_empty() {
+                        let qualities = msg
+                            .connection_qualities
+                            .iter()
+                            .filter_map(|connection_quality| {
+                                Some((
+                                    connection_quality.short_device_id?,
+                                    connection_quality.quality?,
+                                ))
+                            })
+                            .collect();
+                        self.handle_connection_quality_received(qualities);
+                        handled = true;
+                    }
                     if !handled {
                         // TODO: Handle msg.devices to trigger a remote devices request.
                         // TODO: Handle msg.video_request to trigger a change to the resolution/bitrate we send.
@@ -2280,9 +3286,7 @@ impl Client {
                     "Updated speaker time of {:?} to {:?}",
                     speaker_device.demux_id, speaker_device.speaker_time
                 );
-                state
-                    .observer
-                    .handle_remote_devices_changed(state.client_id, &state.remote_devices);
+                Self::notify_remote_devices_changed(state);
             } else {
                 debug!(
                     "Ignoring speaker change because it isn't a known remote devices: {}",
@@ -2293,6 +3297,36 @@ impl Client {
         });
     }
 
+    // Takes the quality, from 0 (unusable) to 100 (perfect), of each remote
+    // device's uplink as measured by the SFU, by short device ID.
+    fn handle_connection_quality_received(&self, qualities: Vec<(u64, u32)>) {
+        self.actor.send(move |state| {
+            for (short_device_id, quality) in qualities {
+                let quality = std::cmp::min(quality, 100) as u8;
+                if let Some(remote_device) = state
+                    .remote_devices
+                    .iter_mut()
+                    .find(|device| device.short_device_id == short_device_id)
+                {
+                    if remote_device.connection_quality != Some(quality) {
+                        remote_device.connection_quality = Some(quality);
+                        state.observer.handle_participant_quality_changed(
+                            state.client_id,
+                            remote_device.demux_id,
+                            quality,
+                        );
+                    }
+                } else {
+                    // Probably the local device, or one we haven't heard of yet.
+                    debug!(
+                        "Ignoring connection quality of unknown device: {}",
+                        short_device_id
+                    );
+                }
+            }
+        });
+    }
+
     fn handle_remote_device_joined_or_left(&self) {
         self.actor.send(move |state| {
             info!("SFU notified that a remote device has joined or left, requesting update");
@@ -2321,9 +3355,7 @@ impl Client {
                     {
                         remote_device.audio_muted = heartbeat.audio_muted;
                         remote_device.video_muted = heartbeat.video_muted;
-                        state
-                            .observer
-                            .handle_remote_devices_changed(state.client_id, &state.remote_devices);
+                        Self::notify_remote_devices_changed(state);
                     }
                 }
             } else {
@@ -2407,6 +3439,7 @@ impl PeerConnectionObserverTrait for PeerConnectionObserverImpl {
         &mut self,
         _ice_candidate: signaling::IceCandidate,
         _sdp_for_logging: &str,
+        _network: &IceCandidateNetwork,
     ) -> Result<()> {
         Ok(())
     }
@@ -2466,6 +3499,31 @@ impl PeerConnectionObserverTrait for PeerConnectionObserverImpl {
         Ok(())
     }
 
+    fn handle_ice_candidate_pair_changed(
+        &mut self,
+        candidate_pair_type: CandidatePairType,
+    ) -> Result<()> {
+        debug!(
+            "group_call::Client(outer)::handle_ice_candidate_pair_changed(client_id: {}, type: {:?})",
+            self.log_id(),
+            candidate_pair_type
+        );
+        if let Some(client) = &self.client {
+            client.actor.send(move |state| {
+                if let JoinState::Joined(local_demux_id, _) = state.join_state {
+                    state.observer.handle_candidate_pair_changed(
+                        state.client_id,
+                        local_demux_id,
+                        candidate_pair_type,
+                    );
+                }
+            });
+        } else {
+            warn!("Call isn't setup yet!");
+        }
+        Ok(())
+    }
+
     fn handle_incoming_video_added(&mut self, incoming_video_track: VideoTrack) -> Result<()> {
         debug!(
             "group_call::Client(outer)::handle_incoming_video_track(client_id: {})",
@@ -2479,6 +3537,12 @@ impl PeerConnectionObserverTrait for PeerConnectionObserverImpl {
                 );
 
                 if let Some(remote_demux_id) = incoming_video_track.id() {
+                    if state.hidden_video_demux_ids.contains(&remote_demux_id) {
+                        incoming_video_track.set_enabled(false);
+                    }
+                    state
+                        .incoming_video_tracks
+                        .insert(remote_demux_id, incoming_video_track.clone());
                     state.observer.handle_incoming_video_track(
                         state.client_id,
                         remote_demux_id,
@@ -2494,6 +3558,42 @@ impl PeerConnectionObserverTrait for PeerConnectionObserverImpl {
         Ok(())
     }
 
+    fn handle_incoming_interpreter_audio_added(
+        &mut self,
+        incoming_interpreter_audio_track: AudioTrack,
+    ) -> Result<()> {
+        debug!(
+            "group_call::Client(outer)::handle_incoming_interpreter_audio_track(client_id: {})",
+            self.log_id()
+        );
+        // Unlike the main audio, this isn't mixed in until the app asks for it.
+        incoming_interpreter_audio_track.set_enabled(false);
+        if let Some(client) = &self.client {
+            client.actor.send(move |state| {
+                debug!(
+                    "group_call::Client(inner)::handle_incoming_interpreter_audio_track(client_id: {})",
+                    state.client_id
+                );
+
+                if let Some(remote_demux_id) = incoming_interpreter_audio_track.id() {
+                    state
+                        .incoming_interpreter_audio_tracks
+                        .insert(remote_demux_id, incoming_interpreter_audio_track.clone());
+                    state.observer.handle_incoming_interpreter_audio_track(
+                        state.client_id,
+                        remote_demux_id,
+                        incoming_interpreter_audio_track,
+                    )
+                } else {
+                    warn!("Ignoring incoming interpreter audio track with unparsable ID",);
+                }
+            });
+        } else {
+            warn!("Call isn't setup yet!");
+        }
+        Ok(())
+    }
+
     fn handle_signaling_data_channel_connected(
         &mut self,
         _data_channel: DataChannel,
@@ -2666,7 +3766,9 @@ impl<'data> Reader<'data> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::webrtc::sim::media::FAKE_AUDIO_TRACK;
+    use crate::core::util::TestClock;
+    use crate::webrtc::sim::media::{video_track_enabled, FAKE_AUDIO_TRACK};
+    use crate::webrtc::stats_observer::AudioReceiverStatistics;
     use std::sync::{
         atomic::{self, AtomicU64},
         mpsc,
@@ -2680,6 +3782,8 @@ mod tests {
         sfu_info:       SfuInfo,
         local_demux_id: DemuxId,
         request_count:  Arc<AtomicU64>,
+        left_devices:   Arc<Mutex<Vec<String>>>,
+        join_rejection: Arc<Mutex<Option<(u16, Option<String>)>>>,
     }
 
     impl FakeSfuClient {
@@ -2688,6 +3792,8 @@ mod tests {
                 sfu_info,
                 local_demux_id,
                 request_count: Arc::new(AtomicU64::new(0)),
+                left_devices: Arc::default(),
+                join_rejection: Arc::default(),
             }
         }
     }
@@ -2696,6 +3802,14 @@ mod tests {
         pub fn request_count(&self) -> u64 {
             self.request_count.load(atomic::Ordering::SeqCst)
         }
+
+        pub fn left_devices(&self) -> Vec<String> {
+            self.left_devices.lock().unwrap().clone()
+        }
+
+        pub fn reject_join(&self, http_status: u16, message: Option<&str>) {
+            *self.join_rejection.lock().unwrap() = Some((http_status, message.map(String::from)));
+        }
     }
 
     impl SfuClient for FakeSfuClient {
@@ -2706,6 +3820,11 @@ mod tests {
             _dtls_fingerprint: &DtlsFingerprint,
             client: Client,
         ) {
+            if let Some((http_status, message)) = self.join_rejection.lock().unwrap().clone() {
+                let error = RingRtcError::SfuClientJoinRejected(http_status, message);
+                client.on_sfu_client_joined(Err(error.into()));
+                return;
+            }
             client.on_sfu_client_joined(Ok((
                 self.sfu_info.clone(),
                 self.local_demux_id,
@@ -2717,7 +3836,9 @@ mod tests {
         }
         fn set_group_members(&mut self, _members: Vec<GroupMemberInfo>) {}
         fn set_membership_proof(&mut self, _proof: MembershipProof) {}
-        fn leave(&mut self, _long_device_id: String) {}
+        fn leave(&mut self, long_device_id: String) {
+            self.left_devices.lock().unwrap().push(long_device_id);
+        }
     }
 
     // TODO: Put this in common util area?
@@ -2770,6 +3891,7 @@ mod tests {
     #[derive(Clone, Default)]
     struct FakeObserverPeekState {
         joined_members: Vec<UserId>,
+        joined_devices: Vec<(UserId, DemuxId)>,
         creator:        Option<UserId>,
         era_id:         Option<String>,
         max_devices:    Option<u32>,
@@ -2784,9 +3906,18 @@ mod tests {
         outgoing_signaling_blocked: Arc<CallMutex<bool>>,
 
         joined:                      Event,
+        join_states:                 Arc<CallMutex<Vec<(JoinState, Option<bool>)>>>,
         remote_devices:              Arc<CallMutex<Vec<RemoteDeviceState>>>,
+        remote_devices_update_count: Arc<AtomicU64>,
         remote_devices_at_join_time: Arc<CallMutex<Vec<RemoteDeviceState>>>,
         peek_state:                  Arc<CallMutex<FakeObserverPeekState>>,
+        era_changes:                 Arc<CallMutex<Vec<(Option<String>, Option<String>)>>>,
+        outgoing_keyframes:          Arc<CallMutex<Vec<DemuxId>>>,
+        participant_qualities:       Arc<CallMutex<Vec<(DemuxId, u8)>>>,
+        audio_levels:                Arc<CallMutex<Vec<Vec<(DemuxId, u8)>>>>,
+        media_keys_received:         Arc<CallMutex<Vec<DemuxId>>>,
+        join_rejections:             Arc<CallMutex<Vec<(u16, Option<String>)>>>,
+        interpreter_audio_tracks:    Arc<CallMutex<Vec<DemuxId>>>,
         max_send_bitrate:            Arc<CallMutex<Option<DataRate>>>,
         ended:                       Waitable<EndReason>,
         era_id:                      Option<String>,
@@ -2802,7 +3933,9 @@ mod tests {
                     "FakeObserver outgoing_signaling_blocked",
                 )),
                 joined: Event::default(),
+                join_states: Arc::new(CallMutex::new(Vec::new(), "FakeObserver join states")),
                 remote_devices: Arc::new(CallMutex::new(Vec::new(), "FakeObserver remote devices")),
+                remote_devices_update_count: Arc::new(AtomicU64::new(0)),
                 remote_devices_at_join_time: Arc::new(CallMutex::new(
                     Vec::new(),
                     "FakeObserver remote devices",
@@ -2811,6 +3944,28 @@ mod tests {
                     FakeObserverPeekState::default(),
                     "FakeObserver peek state",
                 )),
+                era_changes: Arc::new(CallMutex::new(Vec::new(), "FakeObserver era changes")),
+                outgoing_keyframes: Arc::new(CallMutex::new(
+                    Vec::new(),
+                    "FakeObserver outgoing keyframes",
+                )),
+                participant_qualities: Arc::new(CallMutex::new(
+                    Vec::new(),
+                    "FakeObserver participant qualities",
+                )),
+                audio_levels: Arc::new(CallMutex::new(Vec::new(), "FakeObserver audio levels")),
+                media_keys_received: Arc::new(CallMutex::new(
+                    Vec::new(),
+                    "FakeObserver media keys received",
+                )),
+                join_rejections: Arc::new(CallMutex::new(
+                    Vec::new(),
+                    "FakeObserver join rejections",
+                )),
+                interpreter_audio_tracks: Arc::new(CallMutex::new(
+                    Vec::new(),
+                    "FakeObserver interpreter audio tracks",
+                )),
                 max_send_bitrate: Arc::new(CallMutex::new(None, "FakeObserver max send bitrate")),
                 ended: Waitable::default(),
                 era_id: None,
@@ -2849,6 +4004,11 @@ mod tests {
             remote_devices.iter().cloned().collect()
         }
 
+        fn remote_devices_update_count(&self) -> u64 {
+            self.remote_devices_update_count
+                .load(atomic::Ordering::SeqCst)
+        }
+
         fn remote_devices_at_join_time(&self) -> Vec<RemoteDeviceState> {
             let remote_devices_at_join_time = self
                 .remote_devices_at_join_time
@@ -2867,6 +4027,67 @@ mod tests {
             peek_state.clone()
         }
 
+        fn clear_peek_state(&self) {
+            let mut peek_state = self.peek_state.lock().expect("Lock peek state to clear it");
+            *peek_state = FakeObserverPeekState::default();
+        }
+
+        fn era_changes(&self) -> Vec<(Option<String>, Option<String>)> {
+            let era_changes = self
+                .era_changes
+                .lock()
+                .expect("Lock era changes to read them");
+            era_changes.clone()
+        }
+
+        fn outgoing_keyframes(&self) -> Vec<DemuxId> {
+            let outgoing_keyframes = self
+                .outgoing_keyframes
+                .lock()
+                .expect("Lock outgoing keyframes to read them");
+            outgoing_keyframes.clone()
+        }
+
+        fn participant_qualities(&self) -> Vec<(DemuxId, u8)> {
+            let participant_qualities = self
+                .participant_qualities
+                .lock()
+                .expect("Lock participant qualities to read them");
+            participant_qualities.clone()
+        }
+
+        fn audio_levels(&self) -> Vec<Vec<(DemuxId, u8)>> {
+            let audio_levels = self
+                .audio_levels
+                .lock()
+                .expect("Lock audio levels to read them");
+            audio_levels.clone()
+        }
+
+        fn media_keys_received(&self) -> Vec<DemuxId> {
+            let media_keys_received = self
+                .media_keys_received
+                .lock()
+                .expect("Lock media keys received to read them");
+            media_keys_received.clone()
+        }
+
+        fn join_rejections(&self) -> Vec<(u16, Option<String>)> {
+            let join_rejections = self
+                .join_rejections
+                .lock()
+                .expect("Lock join rejections to read them");
+            join_rejections.clone()
+        }
+
+        fn interpreter_audio_tracks(&self) -> Vec<DemuxId> {
+            let interpreter_audio_tracks = self
+                .interpreter_audio_tracks
+                .lock()
+                .expect("Lock interpreter audio tracks to read them");
+            interpreter_audio_tracks.clone()
+        }
+
         fn max_send_bitrate(&self) -> Option<DataRate> {
             let max_send_bitrate = self
                 .max_send_bitrate
@@ -2885,7 +4106,16 @@ mod tests {
             _connection_state: ConnectionState,
         ) {
         }
-        fn handle_join_state_changed(&self, _client_id: ClientId, join_state: JoinState) {
+        fn handle_join_state_changed(
+            &self,
+            _client_id: ClientId,
+            join_state: JoinState,
+            outgoing_audio_muted: Option<bool>,
+        ) {
+            self.join_states
+                .lock()
+                .expect("Lock join states to handle update")
+                .push((join_state.clone(), outgoing_audio_muted));
             if let JoinState::Joined(_, _) = join_state {
                 let mut owned_remote_devices_at_join_time = self
                     .remote_devices_at_join_time
@@ -2905,11 +4135,14 @@ mod tests {
                 .lock()
                 .expect("Lock recipients to set remote devices");
             *owned_remote_devices = remote_devices.iter().cloned().collect();
+            self.remote_devices_update_count
+                .fetch_add(1, atomic::Ordering::SeqCst);
         }
         fn handle_peek_changed(
             &self,
             _client_id: ClientId,
             joined_members: &[UserId],
+            joined_devices: &[(UserId, DemuxId)],
             creator: Option<UserId>,
             era_id: Option<&str>,
             max_devices: Option<u32>,
@@ -2920,11 +4153,69 @@ mod tests {
                 .lock()
                 .expect("Lock peek state to handle update");
             owned_state.joined_members = joined_members.iter().cloned().collect();
+            owned_state.joined_devices = joined_devices.to_vec();
             owned_state.creator = creator.clone();
             owned_state.era_id = era_id.map(String::from);
             owned_state.max_devices = max_devices;
             owned_state.device_count = device_count;
         }
+        fn handle_era_changed(
+            &self,
+            _client_id: ClientId,
+            old_era_id: Option<&str>,
+            new_era_id: Option<&str>,
+        ) {
+            let mut era_changes = self
+                .era_changes
+                .lock()
+                .expect("Lock era changes to handle update");
+            era_changes.push((old_era_id.map(String::from), new_era_id.map(String::from)));
+        }
+        fn handle_outgoing_keyframe(&self, _client_id: ClientId, local_demux_id: DemuxId) {
+            let mut outgoing_keyframes = self
+                .outgoing_keyframes
+                .lock()
+                .expect("Lock outgoing keyframes to handle update");
+            outgoing_keyframes.push(local_demux_id);
+        }
+        fn handle_participant_quality_changed(
+            &self,
+            _client_id: ClientId,
+            remote_demux_id: DemuxId,
+            quality: u8,
+        ) {
+            let mut participant_qualities = self
+                .participant_qualities
+                .lock()
+                .expect("Lock participant qualities to handle update");
+            participant_qualities.push((remote_demux_id, quality));
+        }
+        fn handle_audio_levels(&self, _client_id: ClientId, audio_levels: Vec<(DemuxId, u8)>) {
+            let mut all_audio_levels = self
+                .audio_levels
+                .lock()
+                .expect("Lock audio levels to handle update");
+            all_audio_levels.push(audio_levels);
+        }
+        fn handle_media_keys_received(&self, _client_id: ClientId, remote_demux_id: DemuxId) {
+            let mut media_keys_received = self
+                .media_keys_received
+                .lock()
+                .expect("Lock media keys received to handle update");
+            media_keys_received.push(remote_demux_id);
+        }
+        fn handle_join_rejected(
+            &self,
+            _client_id: ClientId,
+            http_status: u16,
+            message: Option<&str>,
+        ) {
+            let mut join_rejections = self
+                .join_rejections
+                .lock()
+                .expect("Lock join rejections to handle update");
+            join_rejections.push((http_status, message.map(String::from)));
+        }
         fn handle_max_send_bitrate_changed(&self, _client_id: ClientId, rate: DataRate) {
             let mut max_send_bitrate = self
                 .max_send_bitrate
@@ -2977,6 +4268,18 @@ mod tests {
             _incoming_video_track: VideoTrack,
         ) {
         }
+        fn handle_incoming_interpreter_audio_track(
+            &mut self,
+            _client_id: ClientId,
+            remote_demux_id: DemuxId,
+            _incoming_interpreter_audio_track: AudioTrack,
+        ) {
+            let mut interpreter_audio_tracks = self
+                .interpreter_audio_tracks
+                .lock()
+                .expect("Lock interpreter audio tracks to handle update");
+            interpreter_audio_tracks.push(remote_demux_id);
+        }
         fn handle_ended(&self, _client_id: ClientId, reason: EndReason) {
             self.ended.set(reason);
         }
@@ -3005,6 +4308,15 @@ mod tests {
 
     impl TestClient {
         fn new(user_id: UserId, demux_id: DemuxId, forged_demux_id: Option<DemuxId>) -> Self {
+            Self::new_with_previous_session(user_id, demux_id, forged_demux_id, None)
+        }
+
+        fn new_with_previous_session(
+            user_id: UserId,
+            demux_id: DemuxId,
+            forged_demux_id: Option<DemuxId>,
+            previous_session: Option<PreviousSession>,
+        ) -> Self {
             let sfu_client = FakeSfuClient::new(
                 SfuInfo {
                     udp_addresses:    Vec::new(),
@@ -3026,6 +4338,7 @@ mod tests {
                 None,
                 fake_audio_track,
                 None,
+                previous_session,
             )
             .expect("Start Client");
             Self {
@@ -3091,6 +4404,18 @@ mod tests {
             self.wait_for_client_to_process();
         }
 
+        // The local and remote descriptions last set on the PeerConnection.
+        fn peer_connection_descriptions(&self) -> (Option<&'static str>, Option<&'static str>) {
+            let (sender, receiver) = mpsc::channel();
+            self.client.actor.send(move |state| {
+                let _ = sender.send((
+                    state.peer_connection.local_description(),
+                    state.peer_connection.remote_description(),
+                ));
+            });
+            receiver.recv().expect("Get PeerConnection descriptions")
+        }
+
         fn wait_for_client_to_process(&self) {
             let event = Event::default();
             let cloned = event.clone();
@@ -3148,6 +4473,53 @@ mod tests {
             self.wait_for_client_to_process();
         }
 
+        // Reports the total audio energy received from each remote device so
+        // far, as the next poll does, and waits for the current speaker to
+        // be updated.
+        fn receive_audio_energy(&self, total_energies: &[(DemuxId, f64)]) {
+            let audio_receiver_statistics = total_energies
+                .iter()
+                .map(|(demux_id, total_audio_energy)| AudioReceiverStatistics {
+                    ssrc: *demux_id,
+                    total_audio_energy: *total_audio_energy,
+                    ..Default::default()
+                })
+                .collect();
+            self.client.actor.send(move |state| {
+                state
+                    .peer_connection
+                    .set_audio_receivers_statistics(audio_receiver_statistics);
+                let _ = state
+                    .peer_connection
+                    .get_stats(state.audio_energy_observer.as_ref());
+                Client::update_current_speaker(state);
+            });
+            self.wait_for_client_to_process();
+        }
+
+        // Reports the audio level received from each remote device, as the
+        // next poll does, and waits for the levels to be reported.
+        fn receive_audio_levels(&self, levels: &[(DemuxId, f64)]) {
+            let audio_receiver_statistics = levels
+                .iter()
+                .map(|(demux_id, audio_level)| AudioReceiverStatistics {
+                    ssrc: *demux_id,
+                    audio_level: *audio_level,
+                    ..Default::default()
+                })
+                .collect();
+            self.client.actor.send(move |state| {
+                state
+                    .peer_connection
+                    .set_audio_receivers_statistics(audio_receiver_statistics);
+                let _ = state
+                    .peer_connection
+                    .get_stats(state.audio_energy_observer.as_ref());
+                Client::report_audio_levels(state);
+            });
+            self.wait_for_client_to_process();
+        }
+
         // DemuxIds sorted by speaker_time, then added_time, then demux_id.
         fn speakers(&self) -> Vec<DemuxId> {
             let mut devices = self.observer.remote_devices().clone();
@@ -3476,6 +4848,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn media_keys_received_once_per_device() {
+        let client1 = TestClient::new(vec![1], 1, None);
+        let client2 = TestClient::new(vec![2], 2, None);
+        let client3 = TestClient::new(vec![3], 3, None);
+        client1.connect_join_and_wait_until_joined();
+        client2.connect_join_and_wait_until_joined();
+        client3.connect_join_and_wait_until_joined();
+        set_group_and_wait_until_applied(&[&client1, &client2, &client3]);
+
+        let mut media_keys_received = client1.observer.media_keys_received();
+        media_keys_received.sort();
+        assert_eq!(vec![2, 3], media_keys_received);
+
+        // Keys sent again don't fire it again.
+        client2.client.resend_media_keys();
+        client3.client.resend_media_keys();
+        client2.wait_for_client_to_process();
+        client3.wait_for_client_to_process();
+        client1.wait_for_client_to_process();
+
+        let mut media_keys_received = client1.observer.media_keys_received();
+        media_keys_received.sort();
+        assert_eq!(vec![2, 3], media_keys_received);
+
+        client1.disconnect_and_wait_until_ended();
+        client2.disconnect_and_wait_until_ended();
+        client3.disconnect_and_wait_until_ended();
+    }
+
     #[test]
     fn frame_encryption_send_advanced_key_to_same_user() {
         let mut client1a = TestClient::new(vec![1], 11, None);
@@ -3543,6 +4945,44 @@ mod tests {
         client3.disconnect_and_wait_until_ended();
     }
 
+    #[test]
+    fn join_start_muted() {
+        let client1 = TestClient::new(vec![1], 1, None);
+        client1.client.connect();
+        client1
+            .client
+            .join_with_options(JoinOptions { start_muted: true });
+        client1.observer.joined.wait();
+
+        // Muted from the very first join state on.
+        let join_states = client1
+            .observer
+            .join_states
+            .lock()
+            .expect("Lock join states to check them")
+            .clone();
+        assert_eq!((JoinState::Joining, Some(true)), join_states[0]);
+        assert!(join_states
+            .iter()
+            .all(|(_, outgoing_audio_muted)| *outgoing_audio_muted == Some(true)));
+
+        let client2 = TestClient::new(vec![2], 2, None);
+        client2.connect_join_and_wait_until_joined();
+
+        set_group_and_wait_until_applied(&[&client1, &client2]);
+
+        // Any heartbeat will do; the app never calls set_outgoing_audio_muted().
+        client1.client.set_outgoing_video_muted(false);
+        client1.wait_for_client_to_process();
+        client2.wait_for_client_to_process();
+
+        let remote_devices2 = client2.observer.remote_devices();
+        assert_eq!(1, remote_devices2.len());
+        assert_eq!(client1.demux_id, remote_devices2[0].demux_id);
+        assert_eq!(Some(true), remote_devices2[0].audio_muted);
+        assert_eq!(Some(false), remote_devices2[0].video_muted);
+    }
+
     #[test]
     fn remote_mute_states() {
         let client1 = TestClient::new(vec![1], 1, None);
@@ -3580,6 +5020,61 @@ mod tests {
         assert_eq!(Some(false), remote_devices2[0].video_muted);
     }
 
+    #[test]
+    fn roster_updates_coalesced() {
+        let client1 = TestClient::new(vec![1], 1, None);
+        client1.connect_join_and_wait_until_joined();
+
+        let client2 = TestClient::new(vec![2], 2, None);
+        let clock = TestClock::new();
+        client2.client.set_roster_clock(Box::new(clock.clone()));
+        client2.connect_join_and_wait_until_joined();
+
+        set_group_and_wait_until_applied(&[&client1, &client2]);
+
+        // Only the test clock moves past the interval, so nothing is
+        // delivered late by a timer while the test runs.
+        client2
+            .client
+            .set_roster_update_interval(Duration::from_secs(3600));
+        let update_count = client2.observer.remote_devices_update_count();
+
+        client1.client.set_outgoing_audio_muted(true);
+        client1.client.set_outgoing_video_muted(false);
+        client1.client.set_outgoing_audio_muted(false);
+        client1.wait_for_client_to_process();
+        client2.wait_for_client_to_process();
+
+        // Held back until the interval is up.
+        assert_eq!(update_count, client2.observer.remote_devices_update_count());
+
+        // Once it is, the next change is delivered along with the held ones.
+        clock.advance(Duration::from_secs(3601));
+        client1.client.set_outgoing_video_muted(true);
+        client1.wait_for_client_to_process();
+        client2.wait_for_client_to_process();
+
+        assert_eq!(
+            update_count + 1,
+            client2.observer.remote_devices_update_count()
+        );
+        let remote_devices2 = client2.observer.remote_devices();
+        assert_eq!(1, remote_devices2.len());
+        assert_eq!(Some(false), remote_devices2[0].audio_muted);
+        assert_eq!(Some(true), remote_devices2[0].video_muted);
+
+        // A device leaving doesn't wait.
+        client1.client.set_outgoing_audio_muted(true);
+        client1.wait_for_client_to_process();
+        client2.set_remotes_and_wait_until_applied(&[&client2]);
+
+        assert_eq!(
+            update_count + 2,
+            client2.observer.remote_devices_update_count()
+        );
+        assert!(client2.observer.remote_devices().is_empty());
+    }
+
     fn hash_set<T: std::hash::Hash + Eq + Clone>(vals: impl IntoIterator<Item = T>) -> HashSet<T> {
         vals.into_iter().collect()
     }
@@ -3656,13 +5151,17 @@ mod tests {
         // callback when nothing changes.
         peeker
             .observer
-            .handle_peek_changed(0, &[], None, None, None, 0);
+            .handle_peek_changed(0, &[], &[], None, None, None, 0);
         assert_eq!(0, peeker.observer.joined_members().len());
         peeker.set_remotes_and_wait_until_applied(&[&joiner1, &joiner2]);
         assert_eq!(0, peeker.observer.joined_members().len());
         peeker.observer.handle_peek_changed(
             0,
             &[joiner1.user_id.clone(), joiner2.user_id.clone()],
+            &[
+                (joiner1.user_id.clone(), joiner1.demux_id),
+                (joiner2.user_id.clone(), joiner2.demux_id),
+            ],
             None,
             None,
             None,
@@ -3694,12 +5193,65 @@ mod tests {
     }
 
     #[test]
-    #[ignore] // Because it's too slow
-    fn smart_polling() {
-        let client1 = TestClient::new(vec![1], 1, None);
-        let client2 = TestClient::new(vec![2], 2, None);
-
-        assert_eq!(0, client1.sfu_client.request_count());
+    fn joined_devices_of_user_on_two_devices() {
+        let peeker = TestClient::new(vec![42], 42, None);
+        peeker.client.connect();
+        peeker.wait_for_client_to_process();
+
+        let device = |demux_id: DemuxId, user_id: &[u8]| PeekDeviceInfo {
+            demux_id,
+            user_id:         Some(user_id.to_vec()),
+            short_device_id: demux_id_to_short_device_id(demux_id),
+            long_device_id:  demux_id_to_long_device_id(demux_id),
+        };
+
+        peeker.client.set_peek_info(Ok(PeekInfo {
+            devices:      vec![device(1, b"1"), device(2, b"1"), device(3, b"2")],
+            creator:      None,
+            era_id:       None,
+            max_devices:  None,
+            device_count: 3,
+        }));
+        peeker.wait_for_client_to_process();
+
+        let peek_state = peeker.observer.peek_state();
+        assert_eq!(
+            hash_set(&[b"1".to_vec(), b"2".to_vec()]),
+            hash_set(&peek_state.joined_members)
+        );
+        assert_eq!(
+            hash_set(vec![(b"1".to_vec(), 1), (b"1".to_vec(), 2), (b"2".to_vec(), 3)]),
+            hash_set(peek_state.joined_devices)
+        );
+
+        // Leaving on one device doesn't change the members, but is still reported.
+        peeker.client.set_peek_info(Ok(PeekInfo {
+            devices:      vec![device(2, b"1"), device(3, b"2")],
+            creator:      None,
+            era_id:       None,
+            max_devices:  None,
+            device_count: 2,
+        }));
+        peeker.wait_for_client_to_process();
+
+        let peek_state = peeker.observer.peek_state();
+        assert_eq!(2, peek_state.joined_members.len());
+        assert_eq!(
+            hash_set(vec![(b"1".to_vec(), 2), (b"2".to_vec(), 3)]),
+            hash_set(peek_state.joined_devices)
+        );
+        assert_eq!(2, peek_state.device_count);
+
+        peeker.disconnect_and_wait_until_ended();
+    }
+
+    #[test]
+    #[ignore] // Because it's too slow
+    fn smart_polling() {
+        let client1 = TestClient::new(vec![1], 1, None);
+        let client2 = TestClient::new(vec![2], 2, None);
+
+        assert_eq!(0, client1.sfu_client.request_count());
 
         // We don't query until we get a membership proof
         client1.client.connect();
@@ -3790,6 +5342,335 @@ mod tests {
         client.disconnect_and_wait_until_ended();
     }
 
+    #[test]
+    fn end_reason_i32_values() {
+        // Must match GroupCallEndReason in the apps.
+        let reasons = [
+            (0, EndReason::DeviceExplicitlyDisconnected),
+            (1, EndReason::ServerExplicitlyDisconnected),
+            (2, EndReason::CallManagerIsBusy),
+            (3, EndReason::SfuClientFailedToJoin),
+            (4, EndReason::FailedToCreatePeerConnectionFactory),
+            (5, EndReason::FailedToGenerateCertificate),
+            (6, EndReason::FailedToCreatePeerConnection),
+            (7, EndReason::FailedToCreateDataChannel),
+            (8, EndReason::FailedToStartPeerConnection),
+            (9, EndReason::FailedToUpdatePeerConnection),
+            (10, EndReason::FailedToSetMaxSendBitrate),
+            (11, EndReason::IceFailedWhileConnecting),
+            (12, EndReason::IceFailedAfterConnected),
+            (13, EndReason::ServerChangedDemuxId),
+            (14, EndReason::HasMaxDevices),
+        ];
+        for (value, reason) in reasons.iter() {
+            assert_eq!(*value, reason.as_i32());
+            assert_eq!(Some(*reason), EndReason::from_i32(*value));
+        }
+        assert_eq!(None, EndReason::from_i32(-1));
+        assert_eq!(None, EndReason::from_i32(15));
+    }
+
+    #[test]
+    fn limit_incoming_video_requests_speakers_first() {
+        let now = SystemTime::now();
+        let remote_devices: Vec<RemoteDeviceState> = (1..=4)
+            .map(|demux_id| {
+                let mut device = RemoteDeviceState::new(
+                    demux_id,
+                    vec![demux_id as u8],
+                    demux_id_to_short_device_id(demux_id),
+                    demux_id_to_long_device_id(demux_id),
+                    now + Duration::from_secs(demux_id as u64),
+                );
+                // 3 spoke most recently, then 2.
+                device.speaker_time = match demux_id {
+                    2 => Some(now + Duration::from_secs(10)),
+                    3 => Some(now + Duration::from_secs(20)),
+                    _ => None,
+                };
+                device
+            })
+            .collect();
+        let request = |demux_id: DemuxId, pinned: bool| VideoRequest {
+            demux_id,
+            width: 1280,
+            height: 720,
+            framerate: None,
+            pinned,
+        };
+        let delivered = |requests: &[VideoRequest]| {
+            let mut delivered: Vec<DemuxId> = requests
+                .iter()
+                .filter(|request| request.height > 0)
+                .map(|request| request.demux_id)
+                .collect();
+            delivered.sort_unstable();
+            delivered
+        };
+
+        // 5 is unknown, so it is dropped.
+        let requests: Vec<VideoRequest> =
+            (1..=5).map(|demux_id| request(demux_id, false)).collect();
+        let limited = limit_incoming_video_requests(&requests, &remote_devices, 2);
+        assert_eq!(4, limited.len());
+        assert_eq!(vec![2, 3], delivered(&limited));
+
+        // Pinned requests come before speakers.
+        let mut requests = requests;
+        requests[3].pinned = true;
+        let limited = limit_incoming_video_requests(&requests, &remote_devices, 2);
+        assert_eq!(vec![3, 4], delivered(&limited));
+
+        let limited = limit_incoming_video_requests(&requests, &remote_devices, 0);
+        assert_eq!(Vec::<DemuxId>::new(), delivered(&limited));
+
+        let limited = limit_incoming_video_requests(&requests, &remote_devices, 10);
+        assert_eq!(vec![1, 2, 3, 4], delivered(&limited));
+    }
+
+    #[test]
+    fn limit_incoming_video_requests_skips_requests_for_no_video() {
+        let now = SystemTime::now();
+        let remote_devices: Vec<RemoteDeviceState> = (1..=4)
+            .map(|demux_id| {
+                RemoteDeviceState::new(
+                    demux_id,
+                    vec![demux_id as u8],
+                    demux_id_to_short_device_id(demux_id),
+                    demux_id_to_long_device_id(demux_id),
+                    now + Duration::from_secs(demux_id as u64),
+                )
+            })
+            .collect();
+        let request = |demux_id: DemuxId, height: u16| VideoRequest {
+            demux_id,
+            width: height * 16 / 9,
+            height,
+            framerate: None,
+            pinned: false,
+        };
+        let delivered = |requests: &[VideoRequest]| -> Vec<DemuxId> {
+            requests
+                .iter()
+                .filter(|request| request.height > 0)
+                .map(|request| request.demux_id)
+                .collect()
+        };
+
+        // 1 was added first, but asks for no video (such as when it's
+        // hidden), so the slot goes to the next device.
+        let requests = vec![request(1, 0), request(2, 720), request(3, 720), request(4, 720)];
+        let limited = limit_incoming_video_requests(&requests, &remote_devices, 2);
+        assert_eq!(4, limited.len());
+        assert_eq!(vec![2, 3], delivered(&limited));
+
+        let requests = vec![request(1, 0), request(2, 0), request(3, 0), request(4, 720)];
+        let limited = limit_incoming_video_requests(&requests, &remote_devices, 1);
+        assert_eq!(vec![4], delivered(&limited));
+    }
+
+    #[test]
+    fn hidden_remote_video_is_not_requested() {
+        use protobuf::group_call::DeviceToSfu;
+
+        let mut client1 = TestClient::new(vec![1], 1, None);
+        let client2 = TestClient::new(vec![2], 2, None);
+        let client3 = TestClient::new(vec![3], 3, None);
+
+        let (sender, receiver) = mpsc::channel();
+        client1.sfu_rtp_packet_sender = Some(sender);
+        client1.connect_join_and_wait_until_joined();
+        client1.set_remotes_and_wait_until_applied(&[&client2, &client3]);
+
+        // Waits for the next video request that satisfies the predicate.
+        // Each request is given as (short_device_id, height).
+        let wait_for_video_request = |predicate: &dyn Fn(&[(u64, u32)]) -> bool| loop {
+            let (_header, payload) = receiver
+                .recv_timeout(Duration::from_secs(3))
+                .expect("Get RTP packet to SFU");
+            let requests: Vec<(u64, u32)> = DeviceToSfu::decode(&payload[..])
+                .unwrap()
+                .video_request
+                .expect("Video request")
+                .requests
+                .iter()
+                .map(|request| (request.short_device_id.unwrap(), request.height.unwrap()))
+                .collect();
+            if predicate(&requests) {
+                return requests;
+            }
+        };
+        let height_of = |requests: &[(u64, u32)], demux_id: DemuxId| {
+            requests
+                .iter()
+                .find(|(short_device_id, _)| {
+                    *short_device_id == demux_id_to_short_device_id(demux_id)
+                })
+                .map(|(_, height)| *height)
+        };
+
+        let request = |demux_id: DemuxId| VideoRequest {
+            demux_id,
+            width: 640,
+            height: 360,
+            framerate: None,
+            pinned: false,
+        };
+        client1.client.request_video(vec![request(2), request(3)]);
+        wait_for_video_request(&|requests| height_of(requests, 2) == Some(360));
+
+        client1.client.set_remote_video_hidden(2, true);
+        let requests = wait_for_video_request(&|requests| height_of(requests, 2) == Some(0));
+        assert_eq!(Some(360), height_of(&requests, 3));
+
+        // Still hidden when the requests are resent.
+        client1.client.request_video(vec![request(2), request(3)]);
+        let requests = wait_for_video_request(&|_| true);
+        assert_eq!(Some(0), height_of(&requests, 2));
+        assert_eq!(Some(360), height_of(&requests, 3));
+
+        client1.client.set_remote_video_hidden(2, false);
+        wait_for_video_request(&|requests| height_of(requests, 2) == Some(360));
+
+        client1.disconnect_and_wait_until_ended();
+    }
+
+    #[test]
+    fn hidden_remote_video_track_is_disabled_until_device_leaves() {
+        use protobuf::group_call::DeviceToSfu;
+
+        // The sim gives every video track DemuxId 1, so 1 is the device whose
+        // video we get a track for.
+        let mut client2 = TestClient::new(vec![2], 2, None);
+        let client1 = TestClient::new(vec![1], 1, None);
+        let client3 = TestClient::new(vec![3], 3, None);
+
+        let (sender, receiver) = mpsc::channel();
+        client2.sfu_rtp_packet_sender = Some(sender);
+        client2.connect_join_and_wait_until_joined();
+        client2.set_remotes_and_wait_until_applied(&[&client1, &client3]);
+
+        // Only the address of the track matters, and it's unique to this test.
+        let track_storage = Box::new(0u32);
+        let rffi_track = &*track_storage as *const u32;
+        let mut pc_observer = PeerConnectionObserverImpl {
+            client: Some(client2.client.clone()),
+        };
+        pc_observer
+            .handle_incoming_video_added(VideoTrack::unowned(rffi_track))
+            .expect("Handle incoming video");
+        client2.wait_for_client_to_process();
+        assert!(video_track_enabled(rffi_track));
+
+        // Waits for the next video request that satisfies the predicate and
+        // returns the requested height for device 1, if any.
+        let wait_for_height_of_1 = |predicate: &dyn Fn(Option<u32>) -> bool| loop {
+            let (_header, payload) = receiver
+                .recv_timeout(Duration::from_secs(3))
+                .expect("Get RTP packet to SFU");
+            let height = DeviceToSfu::decode(&payload[..])
+                .unwrap()
+                .video_request
+                .expect("Video request")
+                .requests
+                .iter()
+                .find(|request| request.short_device_id == Some(demux_id_to_short_device_id(1)))
+                .map(|request| request.height.unwrap());
+            if predicate(height) {
+                return;
+            }
+        };
+        let request = |demux_id: DemuxId| VideoRequest {
+            demux_id,
+            width: 640,
+            height: 360,
+            framerate: None,
+            pinned: false,
+        };
+        client2.client.request_video(vec![request(1), request(3)]);
+        wait_for_height_of_1(&|height| height == Some(360));
+
+        // Both the request and the frames stop.
+        client2.client.set_remote_video_hidden(1, true);
+        wait_for_height_of_1(&|height| height == Some(0));
+        client2.wait_for_client_to_process();
+        assert!(!video_track_enabled(rffi_track));
+
+        // Once the device leaves, it's no longer hidden, so it's requested
+        // again if it comes back with the same DemuxId.
+        client2.set_remotes_and_wait_until_applied(&[&client3]);
+        client2.set_remotes_and_wait_until_applied(&[&client1, &client3]);
+        client2.client.request_video(vec![request(1), request(3)]);
+        wait_for_height_of_1(&|height| height == Some(360));
+
+        client2.disconnect_and_wait_until_ended();
+    }
+
+    #[test]
+    fn low_resource_mode_requests_no_video() {
+        use protobuf::group_call::{device_to_sfu::VideoRequestMessage, DeviceToSfu};
+
+        let mut client1 = TestClient::new(vec![1], 1, None);
+        let client2 = TestClient::new(vec![2], 2, None);
+        let client3 = TestClient::new(vec![3], 3, None);
+
+        let (sender, receiver) = mpsc::channel();
+        client1.sfu_rtp_packet_sender = Some(sender);
+        client1.connect_join_and_wait_until_joined();
+        client1.set_remotes_and_wait_until_applied(&[&client2, &client3]);
+
+        // Waits for the next video request that satisfies the predicate.
+        let wait_for_video_request = |predicate: &dyn Fn(&VideoRequestMessage) -> bool| loop {
+            let (_header, payload) = receiver
+                .recv_timeout(Duration::from_secs(3))
+                .expect("Get RTP packet to SFU");
+            if let Some(video_request) = DeviceToSfu::decode(&payload[..]).unwrap().video_request {
+                if predicate(&video_request) {
+                    return video_request;
+                }
+            }
+        };
+        let no_video = |video_request: &VideoRequestMessage| {
+            video_request.max == Some(0)
+                && video_request
+                    .requests
+                    .iter()
+                    .all(|request| request.height == Some(0))
+        };
+
+        let request = |demux_id: DemuxId| VideoRequest {
+            demux_id,
+            width: 640,
+            height: 360,
+            framerate: None,
+            pinned: false,
+        };
+        client1.client.request_video(vec![request(2), request(3)]);
+        wait_for_video_request(&|video_request| {
+            video_request
+                .requests
+                .iter()
+                .all(|request| request.height == Some(360))
+        });
+
+        client1.client.set_low_resource_mode(true);
+        let video_request = wait_for_video_request(&no_video);
+        assert_eq!(2, video_request.requests.len());
+
+        // Requests made in low resource mode are held back.
+        client1.client.request_video(vec![request(2), request(3)]);
+        assert!(no_video(&wait_for_video_request(&|_| true)));
+
+        client1.client.set_low_resource_mode(false);
+        let video_request = wait_for_video_request(&|video_request| !no_video(video_request));
+        assert!(video_request
+            .requests
+            .iter()
+            .all(|request| request.height == Some(360)));
+
+        client1.disconnect_and_wait_until_ended();
+    }
+
     #[test]
     #[ignore]
     fn request_video() {
@@ -3817,6 +5698,7 @@ mod tests {
                 width:     1920,
                 height:    1080,
                 framerate: None,
+                pinned:    false,
             },
             VideoRequest {
                 demux_id:  3,
@@ -3824,12 +5706,14 @@ mod tests {
                 width:     80,
                 height:    120,
                 framerate: Some(5),
+                pinned:    false,
             },
             VideoRequest {
                 demux_id:  4,
                 width:     0,
                 height:    0,
                 framerate: None,
+                pinned:    false,
             },
             // This should be filtered out
             VideoRequest {
@@ -3837,6 +5721,7 @@ mod tests {
                 width:     1000,
                 height:    1000,
                 framerate: None,
+                pinned:    false,
             },
         ];
         client1.client.request_video(requests.clone());
@@ -3955,6 +5840,118 @@ mod tests {
         client1.disconnect_and_wait_until_ended();
     }
 
+    #[test]
+    fn era_changed_on_sfu_restart() {
+        let mut client1 = TestClient::new(vec![1], 1, None);
+
+        client1.client.set_membership_proof(b"proof".to_vec());
+        client1.client.connect();
+        client1.wait_for_client_to_process();
+        assert!(client1.observer.era_changes().is_empty());
+
+        client1.default_peek_info = PeekInfo {
+            era_id: Some("first".to_string()),
+            ..PeekInfo::default()
+        };
+        client1.set_remotes_and_wait_until_applied(&[]);
+        // The same era again isn't a change.
+        client1.set_remotes_and_wait_until_applied(&[]);
+
+        // The SFU restarted the call.
+        client1.default_peek_info = PeekInfo {
+            era_id: Some("second".to_string()),
+            ..PeekInfo::default()
+        };
+        client1.set_remotes_and_wait_until_applied(&[]);
+
+        assert_eq!(
+            vec![
+                (None, Some("first".to_string())),
+                (Some("first".to_string()), Some("second".to_string())),
+            ],
+            client1.observer.era_changes()
+        );
+        assert_eq!(
+            Some("second"),
+            client1.observer.peek_state().era_id.as_deref()
+        );
+        client1.disconnect_and_wait_until_ended();
+    }
+
+    #[test]
+    fn participant_quality_changed() {
+        let client1 = TestClient::new(vec![1], 1, None);
+        let client2 = TestClient::new(vec![2], 2, None);
+        let client3 = TestClient::new(vec![3], 3, None);
+        client1.connect_join_and_wait_until_joined();
+        client1.set_remotes_and_wait_until_applied(&[&client2, &client3]);
+
+        // As the SFU sends them, over RTP data.
+        let receive_qualities = |qualities: &[(DemuxId, u32)]| {
+            use protobuf::group_call::{sfu_to_device::ConnectionQuality, SfuToDevice};
+
+            let msg = SfuToDevice {
+                connection_qualities: qualities
+                    .iter()
+                    .map(|(demux_id, quality)| ConnectionQuality {
+                        short_device_id: Some(demux_id_to_short_device_id(*demux_id)),
+                        quality:         Some(*quality),
+                    })
+                    .collect(),
+                ..Default::default()
+            };
+            let mut payload = Vec::new();
+            msg.encode(&mut payload).unwrap();
+            let header = rtp::Header {
+                pt:        RTP_DATA_PAYLOAD_TYPE,
+                ssrc:      RTP_DATA_TO_SFU_SSRC,
+                seqnum:    1,
+                timestamp: 1,
+            };
+            client1.client.handle_rtp_received(header, &payload);
+            client1.wait_for_client_to_process();
+        };
+
+        // Out of range is capped, and our own device is ignored.
+        receive_qualities(&[(1, 90), (2, 80), (3, 150)]);
+        assert_eq!(
+            vec![(2, 80), (3, 100)],
+            client1.observer.participant_qualities()
+        );
+
+        // Only changes are reported.
+        receive_qualities(&[(2, 80), (3, 40)]);
+        assert_eq!(
+            vec![(2, 80), (3, 100), (3, 40)],
+            client1.observer.participant_qualities()
+        );
+
+        client1.disconnect_and_wait_until_ended();
+    }
+
+    #[test]
+    fn outgoing_keyframe_notified() {
+        let mut client1 = TestClient::new(vec![1], 1, None);
+        client1.connect_join_and_wait_until_joined();
+
+        // Audio frames and VP8 delta frames aren't key frames.
+        let _ = client1.encrypt_media(true, &b"\x00Fake Audio"[..]).unwrap();
+        let _ = client1
+            .encrypt_media(false, &b"\x01Fake Video Delta"[..])
+            .unwrap();
+        client1.wait_for_client_to_process();
+        assert!(client1.observer.outgoing_keyframes().is_empty());
+
+        // Force a key frame.
+        let _ = client1
+            .encrypt_media(false, &b"\x00Fake Video Key"[..])
+            .unwrap();
+        client1.wait_for_client_to_process();
+        assert_eq!(vec![1], client1.observer.outgoing_keyframes());
+
+        client1.disconnect_and_wait_until_ended();
+    }
+
     #[test]
     fn changing_group_members_triggers_poll() {
         let client1 = TestClient::new(vec![1], 1, None);
@@ -3997,6 +5994,39 @@ mod tests {
         client1.disconnect_and_wait_until_ended();
     }
 
+    #[test]
+    fn refresh_peek_delivers_peek_update() {
+        let mut client1 = TestClient::new(vec![1], 1, None);
+        client1.client.set_membership_proof(b"proof".to_vec());
+        client1.client.connect();
+        client1.wait_for_client_to_process();
+        client1.default_peek_info = PeekInfo {
+            era_id: Some("era".to_string()),
+            device_count: 1,
+            ..PeekInfo::default()
+        };
+        client1.set_remotes_and_wait_until_applied(&[]);
+        assert_eq!(1, client1.observer.peek_state().device_count);
+        let initial_count = client1.sfu_client.request_count();
+
+        // Refreshing peeks right away rather than waiting for the next tick.
+        client1.client.refresh_peek();
+        client1.wait_for_client_to_process();
+        assert_eq!(initial_count + 1, client1.sfu_client.request_count());
+
+        // The response is delivered even though the roster didn't change.
+        client1.observer.clear_peek_state();
+        client1.set_remotes_and_wait_until_applied(&[]);
+        assert_eq!(1, client1.observer.peek_state().device_count);
+
+        // Without a refresh, an unchanged roster isn't delivered again.
+        client1.observer.clear_peek_state();
+        client1.set_remotes_and_wait_until_applied(&[]);
+        assert_eq!(0, client1.observer.peek_state().device_count);
+
+        client1.disconnect_and_wait_until_ended();
+    }
+
     #[test]
     fn full_call() {
         let client1 = TestClient::new(vec![1], 1, None);
@@ -4101,6 +6131,103 @@ mod tests {
         client1.disconnect_and_wait_until_ended();
     }
 
+    #[test]
+    fn current_speaker() {
+        let client1 = TestClient::new(vec![1], 1, None);
+        let client2 = TestClient::new(vec![2], 2, None);
+        let client3 = TestClient::new(vec![3], 3, None);
+        client1.connect_join_and_wait_until_joined();
+        client1.set_remotes_and_wait_until_applied(&[&client2, &client3]);
+        assert_eq!(None, client1.client.current_speaker());
+
+        // Nobody is talking yet.
+        client1.receive_audio_energy(&[(2, 0.0), (3, 0.0)]);
+        assert_eq!(None, client1.client.current_speaker());
+
+        client1.receive_audio_energy(&[(2, 0.01), (3, 0.0)]);
+        assert_eq!(Some(2), client1.client.current_speaker());
+
+        // A bit louder isn't enough to take over.
+        client1.receive_audio_energy(&[(2, 0.02), (3, 0.015)]);
+        assert_eq!(Some(2), client1.client.current_speaker());
+
+        // Twice as loud is.
+        client1.receive_audio_energy(&[(2, 0.021), (3, 0.035)]);
+        assert_eq!(Some(3), client1.client.current_speaker());
+
+        // When the speaker goes silent, the loudest of the rest takes over.
+        client1.receive_audio_energy(&[(2, 0.022), (3, 0.035)]);
+        assert_eq!(Some(2), client1.client.current_speaker());
+
+        client1.receive_audio_energy(&[(2, 0.022), (3, 0.035)]);
+        assert_eq!(None, client1.client.current_speaker());
+
+        // Audio from devices that aren't in the call is ignored.
+        client1.receive_audio_energy(&[(2, 0.022), (3, 0.035), (4, 1.0)]);
+        assert_eq!(None, client1.client.current_speaker());
+
+        client1.client.leave();
+        assert_eq!(None, client1.client.current_speaker());
+
+        client1.disconnect_and_wait_until_ended();
+    }
+
+    #[test]
+    fn audio_levels() {
+        let client1 = TestClient::new(vec![1], 1, None);
+        let client2 = TestClient::new(vec![2], 2, None);
+        let client3 = TestClient::new(vec![3], 3, None);
+        client1.connect_join_and_wait_until_joined();
+        client1.set_remotes_and_wait_until_applied(&[&client2, &client3]);
+
+        // Levels are scaled to 0 to 100, and devices that aren't in the
+        // call are left out.
+        client1.receive_audio_levels(&[(2, 0.5), (3, 0.004), (4, 1.0)]);
+        assert_eq!(vec![vec![(2, 50), (3, 0)]], client1.observer.audio_levels());
+
+        // Nothing is reported without a new poll.
+        client1.client.actor.send(Client::report_audio_levels);
+        client1.wait_for_client_to_process();
+        assert_eq!(1, client1.observer.audio_levels().len());
+
+        client1.disconnect_and_wait_until_ended();
+    }
+
+    #[test]
+    fn effective_bandwidth_mode() {
+        let client1 = TestClient::new(vec![1], 1, None);
+        let client2 = TestClient::new(vec![2], 2, None);
+        let effective_mode = || client1.client.effective_bandwidth_mode();
+        assert_eq!(
+            EffectiveBandwidthMode {
+                mode:     BandwidthMode::Normal,
+                adaptive: true,
+            },
+            effective_mode()
+        );
+
+        // All alone, hardly anything is sent.
+        client1.connect_join_and_wait_until_joined();
+        assert_eq!(
+            EffectiveBandwidthMode {
+                mode:     BandwidthMode::VeryLow,
+                adaptive: true,
+            },
+            effective_mode()
+        );
+
+        client1.set_remotes_and_wait_until_applied(&[&client2]);
+        assert_eq!(
+            EffectiveBandwidthMode {
+                mode:     BandwidthMode::Low,
+                adaptive: true,
+            },
+            effective_mode()
+        );
+
+        client1.disconnect_and_wait_until_ended();
+    }
+
     #[test]
     #[ignore]
     fn send_bitrate() {
@@ -4224,4 +6351,168 @@ mod tests {
 
         client1.disconnect_and_wait_until_ended();
     }
+
+    #[test]
+    fn rejoin_ignores_previous_device() {
+        let device = |demux_id: DemuxId, user_id: &[u8]| PeekDeviceInfo {
+            demux_id,
+            user_id: Some(user_id.to_vec()),
+            short_device_id: demux_id_to_short_device_id(demux_id),
+            long_device_id: demux_id_to_long_device_id(demux_id),
+        };
+        let peek_info = |era_id: &str, devices: Vec<PeekDeviceInfo>| PeekInfo {
+            device_count: devices.len() as u32,
+            devices,
+            creator: None,
+            era_id: Some(era_id.to_string()),
+            max_devices: None,
+        };
+
+        let client = TestClient::new_with_previous_session(
+            b"1".to_vec(),
+            32,
+            None,
+            Some(PreviousSession {
+                era_id:   "era".to_string(),
+                demux_id: 16,
+            }),
+        );
+        client.connect_join_and_wait_until_joined();
+
+        // The SFU still lists our device from before the app was terminated.
+        client.client.set_peek_info(Ok(peek_info(
+            "era",
+            vec![device(16, b"1"), device(32, b"1"), device(48, b"2")],
+        )));
+        client.wait_for_client_to_process();
+
+        let remote_devices = client.observer.remote_devices();
+        assert_eq!(1, remote_devices.len());
+        assert_eq!(48, remote_devices[0].demux_id);
+        assert_eq!(
+            vec![demux_id_to_long_device_id(16)],
+            client.sfu_client.left_devices()
+        );
+
+        // Until the SFU drops it, it's still not a remote device, and we only ask once.
+        client.client.set_peek_info(Ok(peek_info(
+            "era",
+            vec![device(16, b"1"), device(32, b"1"), device(48, b"2")],
+        )));
+        client.wait_for_client_to_process();
+        assert_eq!(1, client.observer.remote_devices().len());
+        assert_eq!(1, client.sfu_client.left_devices().len());
+
+        client.client.set_peek_info(Ok(peek_info(
+            "era",
+            vec![device(32, b"1"), device(48, b"2")],
+        )));
+        client.wait_for_client_to_process();
+        assert_eq!(1, client.observer.remote_devices().len());
+
+        // Once the previous session is gone, its demux ID is no longer special.
+        client.client.set_peek_info(Ok(peek_info(
+            "era",
+            vec![device(16, b"3"), device(32, b"1"), device(48, b"2")],
+        )));
+        client.wait_for_client_to_process();
+        assert_eq!(2, client.observer.remote_devices().len());
+        assert_eq!(1, client.sfu_client.left_devices().len());
+
+        client.disconnect_and_wait_until_ended();
+    }
+
+    #[test]
+    fn rejoin_in_new_era_keeps_previous_demux_id() {
+        let client = TestClient::new_with_previous_session(
+            b"1".to_vec(),
+            32,
+            None,
+            Some(PreviousSession {
+                era_id:   "old era".to_string(),
+                demux_id: 16,
+            }),
+        );
+        client.connect_join_and_wait_until_joined();
+
+        let other = TestClient::new(b"2".to_vec(), 16, None);
+        client.set_remotes_and_wait_until_applied(&[&other]);
+
+        let remote_devices = client.observer.remote_devices();
+        assert_eq!(1, remote_devices.len());
+        assert_eq!(16, remote_devices[0].demux_id);
+        assert!(client.sfu_client.left_devices().is_empty());
+
+        client.disconnect_and_wait_until_ended();
+    }
+
+    #[test]
+    fn join_rejected_by_sfu() {
+        let client = TestClient::new(vec![1], 1, None);
+        client
+            .sfu_client
+            .reject_join(403, Some("Not a member of the call"));
+        client.client.connect();
+        client.client.join();
+
+        assert_eq!(
+            EndReason::SfuClientFailedToJoin,
+            client.observer.ended.wait()
+        );
+        assert_eq!(
+            vec![(403, Some("Not a member of the call".to_string()))],
+            client.observer.join_rejections()
+        );
+    }
+
+    #[test]
+    fn interpreter_audio() {
+        let client1 = TestClient::new(vec![1], 1, None);
+        let client2 = TestClient::new(vec![2], 2, None);
+        client1.connect_join_and_wait_until_joined();
+        client1.set_remotes_and_wait_until_applied(&[&client2]);
+
+        // Off by default, so the SDP leaves out the interpreter SSRCs.
+        assert_eq!(
+            (Some("FAKE SDP OFFER"), Some("FAKE SDP ANSWER")),
+            client1.peer_connection_descriptions()
+        );
+
+        // Setting a track turns it on and renegotiates with the remote devices.
+        client1
+            .client
+            .set_interpreter_track(AudioTrack::owned(FAKE_AUDIO_TRACK as *const u32));
+        client1.wait_for_client_to_process();
+        assert_eq!(
+            (
+                Some("FAKE SDP OFFER WITH INTERPRETER"),
+                Some("FAKE SDP ANSWER WITH INTERPRETER")
+            ),
+            client1.peer_connection_descriptions()
+        );
+
+        // Incoming interpreter audio is delivered apart from the main audio,
+        // under the DemuxId parsed from the track ID (always 1 in the sim).
+        let mut pc_observer = PeerConnectionObserverImpl {
+            client: Some(client1.client.clone()),
+        };
+        pc_observer
+            .handle_incoming_interpreter_audio_added(AudioTrack::owned(
+                FAKE_AUDIO_TRACK as *const u32,
+            ))
+            .expect("Handle incoming interpreter audio");
+        client1.wait_for_client_to_process();
+        assert_eq!(vec![1], client1.observer.interpreter_audio_tracks());
+        client1.client.set_remote_interpreter_audio_enabled(1, true);
+        client1.wait_for_client_to_process();
+
+        client1.client.set_interpreter_audio_enabled(false);
+        client1.wait_for_client_to_process();
+        assert_eq!(
+            (Some("FAKE SDP OFFER"), Some("FAKE SDP ANSWER")),
+            client1.peer_connection_descriptions()
+        );
+
+        client1.disconnect_and_wait_until_ended();
+    }
 }
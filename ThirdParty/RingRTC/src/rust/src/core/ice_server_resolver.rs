@@ -0,0 +1,312 @@
+//
+// Copyright 2019-2021 Signal Messenger, LLC
+// SPDX-License-Identifier: AGPL-3.0-only
+//
+
+//! Resolves the hostnames of ICE servers ahead of time, caching the
+//! addresses for a while, so calls made close together don't each wait
+//! on DNS for the same STUN and TURN servers.
+//!
+//! Lookups happen on a background thread, never on the thread setting up
+//! the call.  A host that isn't cached yet is left for WebRTC to resolve
+//! as usual, and is cached for the calls after.
+//!
+//! Only stun: and turn: URLs are rewritten.  stuns: and turns: URLs keep
+//! their hostname, since the server's certificate is checked against it.
+
+use std::collections::{HashMap, HashSet};
+use std::net::{IpAddr, ToSocketAddrs};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use lazy_static::lazy_static;
+
+use crate::core::util::{Clock, SystemClock};
+use crate::webrtc::peer_connection_factory::IceServer;
+
+pub const DEFAULT_TTL: Duration = Duration::from_secs(5 * 60);
+pub const DEFAULT_MAX_HOSTS: usize = 32;
+
+lazy_static! {
+    static ref RESOLVED_HOSTS: Mutex<HostResolutionCache> =
+        Mutex::new(HostResolutionCache::default());
+    // Hosts being looked up in the background, so they aren't looked up twice.
+    static ref RESOLVING_HOSTS: Mutex<HashSet<String>> = Mutex::new(HashSet::new());
+}
+
+/// Addresses hostnames resolved to, each kept for the TTL.  Holds at
+/// most max_hosts hosts, evicting the one resolved longest ago.
+pub struct HostResolutionCache {
+    /// Every address and when they were resolved, by hostname.
+    entries:   HashMap<String, (Vec<IpAddr>, Instant)>,
+    ttl:       Duration,
+    max_hosts: usize,
+    clock:     Box<dyn Clock>,
+}
+
+impl Default for HostResolutionCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_TTL, DEFAULT_MAX_HOSTS, Box::new(SystemClock))
+    }
+}
+
+impl HostResolutionCache {
+    pub fn new(ttl: Duration, max_hosts: usize, clock: Box<dyn Clock>) -> Self {
+        Self {
+            entries: HashMap::new(),
+            ttl,
+            max_hosts,
+            clock,
+        }
+    }
+
+    /// Returns the addresses of the host, if it was resolved within the TTL.
+    pub fn get(&mut self, host: &str) -> Option<Vec<IpAddr>> {
+        let now = self.clock.now();
+        match self.entries.get(host) {
+            Some((addresses, resolved_at)) if now.duration_since(*resolved_at) < self.ttl => {
+                Some(addresses.clone())
+            }
+            Some(_) => {
+                self.entries.remove(host);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// Caches the addresses of the host as of now.
+    pub fn insert(&mut self, host: String, addresses: Vec<IpAddr>) {
+        if self.max_hosts == 0 || addresses.is_empty() {
+            return;
+        }
+        if !self.entries.contains_key(&host) && self.entries.len() >= self.max_hosts {
+            let oldest = self
+                .entries
+                .iter()
+                .min_by_key(|(_, (_, resolved_at))| *resolved_at)
+                .map(|(host, _)| host.clone());
+            if let Some(oldest) = oldest {
+                self.entries.remove(&oldest);
+            }
+        }
+        self.entries.insert(host, (addresses, self.clock.now()));
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// Returns the ICE servers with the hostnames of their stun: and turn:
+/// URLs replaced by their cached addresses, one URL per address.  Never
+/// blocks on DNS: hosts that aren't cached are left as they are and
+/// looked up in the background for later calls.
+pub fn resolve_ice_servers(ice_servers: &[IceServer]) -> Vec<IceServer> {
+    let (resolved_ice_servers, uncached_hosts) = rewrite_cached_hosts(ice_servers, &RESOLVED_HOSTS);
+    resolve_in_background(uncached_hosts);
+    resolved_ice_servers
+}
+
+// Returns the ICE servers rewritten with the addresses in the cache,
+// along with the hosts the cache has no addresses for.
+fn rewrite_cached_hosts(
+    ice_servers: &[IceServer],
+    resolved_hosts: &Mutex<HostResolutionCache>,
+) -> (Vec<IceServer>, Vec<String>) {
+    let mut uncached_hosts = Vec::new();
+    let resolved_ice_servers = ice_servers
+        .iter()
+        .map(|ice_server| {
+            ice_server.with_resolved_hosts(&mut |host| {
+                match resolved_hosts.lock().ok().and_then(|mut cache| cache.get(host)) {
+                    Some(addresses) => addresses,
+                    None => {
+                        uncached_hosts.push(host.to_string());
+                        Vec::new()
+                    }
+                }
+            })
+        })
+        .collect();
+    (resolved_ice_servers, uncached_hosts)
+}
+
+/// Looks up the hostnames of the ICE servers in the background, so they
+/// are cached by the time a call needs them, such as right after getting
+/// new TURN credentials.
+pub fn prefetch_ice_servers(ice_servers: &[IceServer]) {
+    let (_, uncached_hosts) = rewrite_cached_hosts(ice_servers, &RESOLVED_HOSTS);
+    resolve_in_background(uncached_hosts);
+}
+
+/// Forgets every resolved address.  Called when the network route of a
+/// call changes, as the servers may resolve differently on the new one.
+pub fn clear_resolved_hosts() {
+    if let Ok(mut resolved_hosts) = RESOLVED_HOSTS.lock() {
+        resolved_hosts.clear();
+    }
+}
+
+#[cfg(test)]
+pub(crate) fn resolved_hosts_for_tests() -> &'static Mutex<HostResolutionCache> {
+    &RESOLVED_HOSTS
+}
+
+fn resolve_in_background(hosts: Vec<String>) {
+    let hosts: Vec<String> = match RESOLVING_HOSTS.lock() {
+        Ok(mut resolving_hosts) => hosts
+            .into_iter()
+            .filter(|host| resolving_hosts.insert(host.clone()))
+            .collect(),
+        Err(_) => return,
+    };
+    if hosts.is_empty() {
+        return;
+    }
+
+    thread::spawn(move || {
+        for host in hosts {
+            let addresses = resolve_host(&host);
+            if let Ok(mut resolved_hosts) = RESOLVED_HOSTS.lock() {
+                resolved_hosts.insert(host.clone(), addresses);
+            }
+            if let Ok(mut resolving_hosts) = RESOLVING_HOSTS.lock() {
+                resolving_hosts.remove(&host);
+            }
+        }
+    });
+}
+
+// Every address, in the order the system gives them, without duplicates
+// (there is one per socket type on some systems).
+fn resolve_host(host: &str) -> Vec<IpAddr> {
+    match (host, 0).to_socket_addrs() {
+        Ok(socket_addrs) => {
+            let mut addresses: Vec<IpAddr> = Vec::new();
+            for address in socket_addrs.map(|socket_addr| socket_addr.ip()) {
+                if !addresses.contains(&address) {
+                    addresses.push(address);
+                }
+            }
+            addresses
+        }
+        Err(e) => {
+            warn!("resolve_host(): failed to resolve an ICE server: {}", e);
+            Vec::new()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::util::TestClock;
+    use std::net::Ipv4Addr;
+
+    fn cache_with_clock(max_hosts: usize) -> (HostResolutionCache, TestClock) {
+        let clock = TestClock::new();
+        let cache = HostResolutionCache::new(
+            Duration::from_secs(60),
+            max_hosts,
+            Box::new(clock.clone()),
+        );
+        (cache, clock)
+    }
+
+    fn address(last: u8) -> IpAddr {
+        IpAddr::V4(Ipv4Addr::new(192, 0, 2, last))
+    }
+
+    #[test]
+    fn resolved_host_cached_until_ttl() {
+        let (mut cache, clock) = cache_with_clock(DEFAULT_MAX_HOSTS);
+        assert_eq!(None, cache.get("turn.example.org"));
+
+        cache.insert("turn.example.org".to_string(), vec![address(1), address(2)]);
+        assert_eq!(
+            Some(vec![address(1), address(2)]),
+            cache.get("turn.example.org")
+        );
+
+        clock.advance(Duration::from_secs(59));
+        assert_eq!(
+            Some(vec![address(1), address(2)]),
+            cache.get("turn.example.org")
+        );
+
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(None, cache.get("turn.example.org"));
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn oldest_host_evicted_when_full() {
+        let (mut cache, clock) = cache_with_clock(2);
+        cache.insert("a.example.org".to_string(), vec![address(1)]);
+        clock.advance(Duration::from_secs(1));
+        cache.insert("b.example.org".to_string(), vec![address(2)]);
+        clock.advance(Duration::from_secs(1));
+        cache.insert("c.example.org".to_string(), vec![address(3)]);
+
+        assert_eq!(2, cache.len());
+        assert_eq!(None, cache.get("a.example.org"));
+        assert_eq!(Some(vec![address(2)]), cache.get("b.example.org"));
+        assert_eq!(Some(vec![address(3)]), cache.get("c.example.org"));
+    }
+
+    #[test]
+    fn unresolved_host_not_cached() {
+        let (mut cache, _clock) = cache_with_clock(DEFAULT_MAX_HOSTS);
+        cache.insert("unknown.example.org".to_string(), vec![]);
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn clear_forgets_every_host() {
+        let (mut cache, _clock) = cache_with_clock(DEFAULT_MAX_HOSTS);
+        cache.insert("a.example.org".to_string(), vec![address(1)]);
+        cache.insert("b.example.org".to_string(), vec![address(2)]);
+
+        cache.clear();
+        assert!(cache.is_empty());
+        assert_eq!(None, cache.get("a.example.org"));
+    }
+
+    #[test]
+    fn only_cached_hosts_rewritten() {
+        let (mut cache, _clock) = cache_with_clock(DEFAULT_MAX_HOSTS);
+        cache.insert(
+            "cached.resolver.example.org".to_string(),
+            vec![address(1), address(2)],
+        );
+        let ice_server = IceServer::new(
+            "user".to_string(),
+            "pass".to_string(),
+            vec![
+                "turn:cached.resolver.example.org:3478".to_string(),
+                "turn:uncached.resolver.example.org:3478".to_string(),
+            ],
+        );
+
+        let (resolved, uncached_hosts) = rewrite_cached_hosts(&[ice_server], &Mutex::new(cache));
+        assert_eq!(vec!["uncached.resolver.example.org"], uncached_hosts);
+        assert_eq!(
+            vec![
+                "192.0.2.1:3478".to_string(),
+                "192.0.2.2:3478".to_string(),
+                "uncached.resolver.example.org:3478".to_string(),
+            ],
+            resolved[0].hosts()
+        );
+    }
+}
@@ -21,7 +21,9 @@ use crate::core::bandwidth_mode::BandwidthMode;
 use crate::core::call::Call;
 use crate::core::connection::{Connection, ConnectionType};
 use crate::core::{group_call, signaling};
-use crate::webrtc::media::{MediaStream, VideoTrack};
+use crate::error::RingRtcError;
+use crate::webrtc::media::{AudioTrack, MediaStream, VideoTrack};
+use crate::webrtc::peer_connection_observer::{CandidatePairType, NetworkRoute};
 
 /// A trait encompassing the traits the platform associated types must
 /// implement.
@@ -64,6 +66,47 @@ pub trait Platform: fmt::Debug + fmt::Display + Send + Sized + 'static {
     /// Notify the client application about an event.
     fn on_event(&self, remote_peer: &Self::AppRemotePeer, event: ApplicationEvent) -> Result<()>;
 
+    /// Notify the client application that a STUN or TURN server
+    /// returned an error, such as 401 for bad TURN credentials.
+    /// The URL has already been redacted.
+    fn on_ice_candidate_error(
+        &self,
+        _remote_peer: &Self::AppRemotePeer,
+        _url: String,
+        _error_code: u16,
+        _error_text: String,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    /// Notify the client application that ICE moved the media to
+    /// another kind of network, such as from Wi-Fi to cellular.
+    fn on_network_route_changed(
+        &self,
+        _remote_peer: &Self::AppRemotePeer,
+        _network_route: NetworkRoute,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    /// Notify the client application about an application-defined
+    /// payload received from the remote peer over the data channel.
+    fn on_data_message(&self, _remote_peer: &Self::AppRemotePeer, _payload: Vec<u8>) -> Result<()> {
+        Ok(())
+    }
+
+    /// Notify the client application about a DTMF tone (one of
+    /// `0-9`, `*`, `#` or `A-D`) received from the remote peer.
+    fn on_dtmf(&self, _remote_peer: &Self::AppRemotePeer, _tone: char) -> Result<()> {
+        Ok(())
+    }
+
+    /// Notify the client application that a key frame was encoded
+    /// for the local video.
+    fn on_outgoing_keyframe(&self, _remote_peer: &Self::AppRemotePeer) -> Result<()> {
+        Ok(())
+    }
+
     /// Send an offer to a remote peer using the signaling
     /// channel.  Offers are always broadcast to all devices.
     fn on_send_offer(
@@ -104,6 +147,44 @@ pub trait Platform: fmt::Debug + fmt::Display + Send + Sized + 'static {
     /// signaling channel.  This always broadcasts to all devices.
     fn on_send_busy(&self, remote_peer: &Self::AppRemotePeer, call_id: CallId) -> Result<()>;
 
+    /// Send a call transfer request to a remote peer using the
+    /// signaling channel, asking it to end the call and call the
+    /// target peer instead.
+    ///
+    /// Only platforms that support call transfer need to handle this.
+    fn on_send_transfer_request(
+        &self,
+        _remote_peer: &Self::AppRemotePeer,
+        _call_id: CallId,
+        _target_peer: &Self::AppRemotePeer,
+    ) -> Result<()> {
+        Err(RingRtcError::CallTransferNotSupported.into())
+    }
+
+    /// Send a call transfer acceptance to a remote peer using the
+    /// signaling channel.
+    ///
+    /// Only platforms that support call transfer need to handle this.
+    fn on_send_transfer_accepted(
+        &self,
+        _remote_peer: &Self::AppRemotePeer,
+        _call_id: CallId,
+    ) -> Result<()> {
+        Err(RingRtcError::CallTransferNotSupported.into())
+    }
+
+    /// Notify the application that the remote peer asked to transfer the
+    /// call to another peer.  The application accepts with
+    /// CallManager::accept_transfer() and then starts the new call itself.
+    ///
+    /// Only platforms that support call transfer need to handle this.
+    fn handle_transfer_request(
+        &self,
+        _from_peer: &Self::AppRemotePeer,
+        _to_peer: &Self::AppRemotePeer,
+    ) {
+    }
+
     /// Send a generic call message to a recipient using the
     /// signaling channel.
     fn send_call_message(&self, recipient_uuid: Vec<u8>, message: Vec<u8>) -> Result<()>;
@@ -157,18 +238,40 @@ pub trait Platform: fmt::Debug + fmt::Display + Send + Sized + 'static {
         false
     }
 
+    /// Return true if the application calls CallManager::notify_ringing()
+    /// once it is ringing, in which case an incoming call accepted before
+    /// it could be is only accepted after that.  Otherwise such an accept
+    /// is applied as soon as the call is connected.
+    fn acknowledges_ringing(&self) -> bool {
+        false
+    }
+
+    /// Notify the application that a signaling message for call_id was
+    /// taken as sent without waiting for message_sent(), because
+    /// assume_messages_sent() returned true.
+    fn on_signaling_sent_assumed(&self, _call_id: CallId) {}
+
+    /// Notify the application of the captured audio level, from 0 to
+    /// 32767, while an audio loopback is running.
+    fn on_audio_loopback_level(&self, _level: u16) {}
+
     // Group Calls
 
     fn handle_peek_response(
         &self,
         request_id: u32,
         joined_members: &[group_call::UserId],
+        joined_devices: &[(group_call::UserId, group_call::DemuxId)],
         creator: Option<group_call::UserId>,
         era_id: Option<&str>,
         max_devices: Option<u32>,
         device_count: u32,
     );
 
+    /// Called instead of handle_peek_response when a peek started with a
+    /// timeout gets no response in time.
+    fn handle_peek_timed_out(&self, _request_id: u32) {}
+
     fn request_membership_proof(&self, client_id: group_call::ClientId);
 
     fn request_group_members(&self, client_id: group_call::ClientId);
@@ -183,6 +286,7 @@ pub trait Platform: fmt::Debug + fmt::Display + Send + Sized + 'static {
         &self,
         client_id: group_call::ClientId,
         join_state: group_call::JoinState,
+        outgoing_audio_muted: Option<bool>,
     );
 
     fn handle_remote_devices_changed(
@@ -198,15 +302,92 @@ pub trait Platform: fmt::Debug + fmt::Display + Send + Sized + 'static {
         incoming_video_track: VideoTrack,
     );
 
+    /// Only platforms that offer interpreter audio need to handle this.
+    /// See group_call::Observer::handle_incoming_interpreter_audio_track.
+    fn handle_incoming_interpreter_audio_track(
+        &self,
+        _client_id: group_call::ClientId,
+        _remote_demux_id: group_call::DemuxId,
+        _incoming_interpreter_audio_track: AudioTrack,
+    ) {
+    }
+
     fn handle_peek_changed(
         &self,
         client_id: group_call::ClientId,
         joined_members: &[group_call::UserId],
+        joined_devices: &[(group_call::UserId, group_call::DemuxId)],
         creator: Option<group_call::UserId>,
         era_id: Option<&str>,
         max_devices: Option<u32>,
         device_count: u32,
     );
 
+    /// Only platforms that segment calls by era need to handle this.
+    /// See group_call::Observer::handle_era_changed.
+    fn handle_era_changed(
+        &self,
+        _client_id: group_call::ClientId,
+        _old_era_id: Option<&str>,
+        _new_era_id: Option<&str>,
+    ) {
+    }
+
+    /// Only platforms that need to line up with local key frames need
+    /// to handle this.
+    /// See group_call::Observer::handle_outgoing_keyframe.
+    fn handle_outgoing_keyframe(
+        &self,
+        _client_id: group_call::ClientId,
+        _local_demux_id: group_call::DemuxId,
+    ) {
+    }
+
+    /// Only platforms that show the connection quality of participants
+    /// need to handle this.
+    /// See group_call::Observer::handle_participant_quality_changed.
+    fn handle_participant_quality_changed(
+        &self,
+        _client_id: group_call::ClientId,
+        _remote_demux_id: group_call::DemuxId,
+        _quality: u8,
+    ) {
+    }
+
+    /// Only platforms that show who is speaking need to handle this.
+    /// See group_call::Observer::handle_audio_levels.
+    fn handle_audio_levels(
+        &self,
+        _client_id: group_call::ClientId,
+        _audio_levels: Vec<(group_call::DemuxId, u8)>,
+    ) {
+    }
+
+    /// See group_call::Observer::handle_media_keys_received.
+    fn handle_media_keys_received(
+        &self,
+        _client_id: group_call::ClientId,
+        _remote_demux_id: group_call::DemuxId,
+    ) {
+    }
+
+    /// See group_call::Observer::handle_candidate_pair_changed.
+    fn handle_candidate_pair_changed(
+        &self,
+        _client_id: group_call::ClientId,
+        _local_demux_id: group_call::DemuxId,
+        _candidate_pair_type: CandidatePairType,
+    ) {
+    }
+
+    /// See group_call::Observer::handle_join_rejected.
+    fn handle_join_rejected(
+        &self,
+        _client_id: group_call::ClientId,
+        _http_status: u16,
+        _message: Option<&str>,
+    ) {
+    }
+
     fn handle_ended(&self, client_id: group_call::ClientId, reason: group_call::EndReason);
 }
@@ -122,13 +122,17 @@ impl SfuClient {
             }
             Some(r) => {
                 error!(
-                    "SfuClient: unexpected join response status code {}",
+                    "SfuClient: join rejected with status code {}",
                     r.status_code
                 );
-                return Err(RingRtcError::SfuClientReceivedUnexpectedResponseStatusCode(
-                    r.status_code,
-                )
-                .into());
+                // Whatever the SFU put in the body is passed along so the
+                // app can tell the user more than "failed to join".
+                let message = std::str::from_utf8(&r.body)
+                    .ok()
+                    .map(str::trim)
+                    .filter(|message| !message.is_empty())
+                    .map(String::from);
+                return Err(RingRtcError::SfuClientJoinRejected(r.status_code, message).into());
             }
             _ => {
                 error!("SfuClient: join request failed (no response)");
@@ -6,14 +6,52 @@
 use bytes::{Bytes, BytesMut};
 use prost::Message as _;
 /// The messages we send over the signaling channel to establish a call.
+use std::cmp::Ordering;
 use std::fmt;
+use std::net::IpAddr;
 use std::time::Duration;
 
-use crate::common::{CallMediaType, DeviceId, FeatureLevel, Result};
+use crate::common::{CallId, CallMediaType, DeviceId, FeatureLevel, Result};
+use crate::core::util::redact_string;
 use crate::error::RingRtcError;
 use crate::protobuf;
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// The largest opaque Offer, Answer, or ICE candidate we will try to
+/// decode.  Real ones are a few KiB at most, so anything bigger is
+/// rejected before it reaches the protobuf decoder.
+pub const MAX_OPAQUE_SIZE: usize = 10 * 1024;
+
+/// Length of an identity public key as the app stores it: the bare
+/// 32-byte Curve25519 key.
+pub const IDENTITY_KEY_LEN: usize = 32;
+/// Type byte in front of a Curve25519 key serialized along with its type.
+pub const IDENTITY_KEY_TYPE_DJB: u8 = 0x05;
+
+/// Checks that an identity key from the app is either a bare Curve25519
+/// key or one serialized with its type byte in front.
+pub fn check_identity_key(caller: &str, var: &str, identity_key: &[u8]) -> Result<()> {
+    match identity_key.len() {
+        IDENTITY_KEY_LEN => Ok(()),
+        len if len == IDENTITY_KEY_LEN + 1 => {
+            if identity_key[0] == IDENTITY_KEY_TYPE_DJB {
+                Ok(())
+            } else {
+                Err(RingRtcError::InvalidIdentityKeyType(
+                    caller.to_owned(),
+                    var.to_owned(),
+                    identity_key[0],
+                )
+                .into())
+            }
+        }
+        len => Err(
+            RingRtcError::InvalidIdentityKeyLength(caller.to_owned(), var.to_owned(), len).into(),
+        ),
+    }
+}
+
+/// Versions are ordered from oldest to newest, so V2 < V3 < V4 < V5.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Version {
     // The V1 protocol used SDP, DTLS, and SCTP. Removed.
     // The V2 protocol does not use SCTP. It uses RTP data channels.
@@ -24,15 +62,23 @@ pub enum Version {
     V3,
     // Same as V3 except without any SDP.
     V4,
+    // Same as V4 but with a new key exchange.
+    V5,
 }
 
 impl Version {
+    /// True if this is `other` or a newer version.
+    pub fn at_least(self, other: Self) -> bool {
+        self >= other
+    }
+
     pub fn enable_dtls(self) -> bool {
         match self {
             Self::V2 => true,
             // This disables DTLS
             Self::V3 => false,
             Self::V4 => false,
+            Self::V5 => false,
         }
     }
 }
@@ -43,6 +89,7 @@ impl fmt::Display for Version {
             Self::V2 => "V2".to_string(),
             Self::V3 => "V3".to_string(),
             Self::V4 => "V4".to_string(),
+            Self::V5 => "V5".to_string(),
         };
         write!(f, "{}", display)
     }
@@ -59,6 +106,8 @@ pub enum Message {
     Hangup(Hangup),
     LegacyHangup(Hangup),
     Busy,
+    TransferRequest(TransferRequest),
+    TransferAccepted,
 }
 
 impl Message {
@@ -70,10 +119,143 @@ impl Message {
             Self::Hangup(_) => MessageType::Hangup,
             Self::LegacyHangup(_) => MessageType::Hangup,
             Self::Busy => MessageType::Busy,
+            Self::TransferRequest(_) => MessageType::TransferRequest,
+            Self::TransferAccepted => MessageType::TransferAccepted,
+        }
+    }
+
+    /// Serializes the message, such as to persist it until it can be
+    /// handled, in a form that `deserialize` turns back into the same
+    /// message.
+    ///
+    /// The format is a tag byte for the variant followed by its fields.
+    /// Opaque values that aren't last are prefixed with their length.
+    pub fn serialize(&self) -> Result<Vec<u8>> {
+        use prost::encoding::encode_varint;
+
+        let mut out = Vec::new();
+        match self {
+            Self::Offer(offer) => {
+                out.push(SERIALIZED_OFFER);
+                out.push(offer.call_media_type as u8);
+                out.extend_from_slice(&offer.opaque);
+            }
+            Self::Answer(answer) => {
+                out.push(SERIALIZED_ANSWER);
+                out.extend_from_slice(&answer.opaque);
+            }
+            Self::Ice(ice) => {
+                out.push(SERIALIZED_ICE);
+                for candidate in &ice.candidates_added {
+                    encode_varint(candidate.opaque.len() as u64, &mut out);
+                    out.extend_from_slice(&candidate.opaque);
+                }
+            }
+            Self::Hangup(hangup) => {
+                out.push(SERIALIZED_HANGUP);
+                serialize_hangup(hangup, &mut out);
+            }
+            Self::LegacyHangup(hangup) => {
+                out.push(SERIALIZED_LEGACY_HANGUP);
+                serialize_hangup(hangup, &mut out);
+            }
+            Self::Busy => out.push(SERIALIZED_BUSY),
+            Self::TransferRequest(transfer_request) => {
+                out.push(SERIALIZED_TRANSFER_REQUEST);
+                out.extend_from_slice(&transfer_request.target);
+            }
+            Self::TransferAccepted => out.push(SERIALIZED_TRANSFER_ACCEPTED),
+        }
+        Ok(out)
+    }
+
+    /// Restores a message serialized with `serialize`.
+    pub fn deserialize(bytes: &[u8]) -> Result<Self> {
+        use prost::encoding::decode_varint;
+
+        let (tag, mut rest) = bytes
+            .split_first()
+            .ok_or(RingRtcError::InvalidSerializedMessage)?;
+        match *tag {
+            SERIALIZED_OFFER => {
+                let (call_media_type, opaque) = rest
+                    .split_first()
+                    .ok_or(RingRtcError::InvalidSerializedMessage)?;
+                let call_media_type = match call_media_type {
+                    0 => CallMediaType::Audio,
+                    1 => CallMediaType::Video,
+                    _ => return Err(RingRtcError::InvalidSerializedMessage.into()),
+                };
+                Ok(Self::Offer(Offer::new(call_media_type, opaque.to_vec())?))
+            }
+            SERIALIZED_ANSWER => Ok(Self::Answer(Answer::new(rest.to_vec())?)),
+            SERIALIZED_ICE => {
+                let mut candidates_added = Vec::new();
+                while !rest.is_empty() {
+                    let len = decode_varint(&mut rest)? as usize;
+                    if len > rest.len() {
+                        return Err(RingRtcError::InvalidSerializedMessage.into());
+                    }
+                    candidates_added.push(IceCandidate::new(rest[..len].to_vec()));
+                    rest = &rest[len..];
+                }
+                Ok(Self::Ice(Ice { candidates_added }))
+            }
+            SERIALIZED_HANGUP => Ok(Self::Hangup(deserialize_hangup(rest)?)),
+            SERIALIZED_LEGACY_HANGUP => Ok(Self::LegacyHangup(deserialize_hangup(rest)?)),
+            SERIALIZED_BUSY if rest.is_empty() => Ok(Self::Busy),
+            SERIALIZED_TRANSFER_REQUEST => Ok(Self::TransferRequest(TransferRequest {
+                target: rest.to_vec(),
+            })),
+            SERIALIZED_TRANSFER_ACCEPTED if rest.is_empty() => Ok(Self::TransferAccepted),
+            _ => Err(RingRtcError::InvalidSerializedMessage.into()),
         }
     }
 }
 
+// The tags of the serialized form of each Message variant.  Serialized
+// messages may be persisted, so these must never be changed or reused.
+const SERIALIZED_OFFER: u8 = 0;
+const SERIALIZED_ANSWER: u8 = 1;
+const SERIALIZED_ICE: u8 = 2;
+const SERIALIZED_HANGUP: u8 = 3;
+const SERIALIZED_LEGACY_HANGUP: u8 = 4;
+const SERIALIZED_BUSY: u8 = 5;
+const SERIALIZED_TRANSFER_REQUEST: u8 = 6;
+const SERIALIZED_TRANSFER_ACCEPTED: u8 = 7;
+
+// A hangup is its type, followed by its device ID (big endian) if it has one.
+fn serialize_hangup(hangup: &Hangup, out: &mut Vec<u8>) {
+    let (typ, device_id) = hangup.to_type_and_device_id();
+    out.push(typ as u8);
+    if let Some(device_id) = device_id {
+        out.extend_from_slice(&device_id.to_be_bytes());
+    }
+}
+
+fn deserialize_hangup(bytes: &[u8]) -> Result<Hangup> {
+    let (typ, device_id) = match *bytes {
+        [typ] => (typ, None),
+        [typ, b0, b1, b2, b3] => (typ, Some(DeviceId::from_be_bytes([b0, b1, b2, b3]))),
+        _ => return Err(RingRtcError::InvalidSerializedMessage.into()),
+    };
+    match (HangupType::from_i32(typ as i32), device_id) {
+        (Some(HangupType::Normal), None) => Ok(Hangup::Normal),
+        (Some(HangupType::AcceptedOnAnotherDevice), Some(device_id)) => {
+            Ok(Hangup::AcceptedOnAnotherDevice(device_id))
+        }
+        (Some(HangupType::DeclinedOnAnotherDevice), Some(device_id)) => {
+            Ok(Hangup::DeclinedOnAnotherDevice(device_id))
+        }
+        (Some(HangupType::BusyOnAnotherDevice), Some(device_id)) => {
+            Ok(Hangup::BusyOnAnotherDevice(device_id))
+        }
+        (Some(HangupType::NeedPermission), device_id) => Ok(Hangup::NeedPermission(device_id)),
+        (Some(HangupType::Away), None) => Ok(Hangup::Away),
+        _ => Err(RingRtcError::InvalidSerializedMessage.into()),
+    }
+}
+
 impl fmt::Display for Message {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let display = match self {
@@ -83,6 +265,8 @@ impl fmt::Display for Message {
             Self::Hangup(hangup) => format!("Hangup({:?})", hangup),
             Self::LegacyHangup(hangup) => format!("LegacyHangup({:?})", hangup),
             Self::Busy => "Busy".to_string(),
+            Self::TransferRequest(_) => "TransferRequest(...)".to_string(),
+            Self::TransferAccepted => "TransferAccepted".to_string(),
         };
         write!(f, "({})", display)
     }
@@ -97,7 +281,7 @@ impl fmt::Debug for Message {
 // It's convenient to be able to now the type of a message without having
 // an entire message, so we have the related MessageType enum
 #[repr(i32)]
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum MessageType {
     Offer,
     Answer,
@@ -105,6 +289,28 @@ pub enum MessageType {
     Hangup,
     Busy,
     MediaKey,
+    TransferRequest,
+    TransferAccepted,
+}
+
+impl MessageType {
+    pub fn from_i32(value: i32) -> Option<Self> {
+        match value {
+            0 => Some(MessageType::Offer),
+            1 => Some(MessageType::Answer),
+            2 => Some(MessageType::Ice),
+            3 => Some(MessageType::Hangup),
+            4 => Some(MessageType::Busy),
+            5 => Some(MessageType::MediaKey),
+            6 => Some(MessageType::TransferRequest),
+            7 => Some(MessageType::TransferAccepted),
+            _ => None,
+        }
+    }
+
+    pub fn as_i32(self) -> i32 {
+        self as i32
+    }
 }
 
 /// The caller sends this to several callees to initiate the call.
@@ -118,6 +324,7 @@ pub struct Offer {
 
 impl Offer {
     pub fn new(call_media_type: CallMediaType, opaque: Vec<u8>) -> Result<Self> {
+        check_opaque_size(&opaque)?;
         let proto = Self::deserialize_opaque(&opaque)?;
         Ok(Self {
             call_media_type,
@@ -127,13 +334,15 @@ impl Offer {
     }
 
     fn deserialize_opaque(opaque: &[u8]) -> Result<protobuf::signaling::Offer> {
-        Ok(protobuf::signaling::Offer::decode(Bytes::from(
-            opaque.to_owned(),
-        ))?)
+        decode_with_v5_fallback(opaque)
     }
 
     pub fn latest_version(&self) -> Version {
         match self {
+            Self {
+                proto: protobuf::signaling::Offer { v5: Some(_), .. },
+                ..
+            } => Version::V5,
             Self {
                 proto: protobuf::signaling::Offer { v4: Some(_), .. },
                 ..
@@ -154,6 +363,28 @@ impl Offer {
         }
     }
 
+    /// Returns the versions of the offer this build can use, most recent
+    /// first.  Empty if the caller only offered versions newer than any
+    /// this build knows about.
+    ///
+    /// V5 isn't listed until connections can negotiate it, so an offer
+    /// with V5 alongside V4 (or V3/V2) is answered with the older version.
+    pub fn supported_versions(&self) -> Vec<Version> {
+        let mut versions = Vec::new();
+        if self.proto.v4.is_some() {
+            versions.push(Version::V4);
+        }
+        if let Some(v3_or_v2) = &self.proto.v3_or_v2 {
+            if v3_or_v2.public_key.is_some() {
+                versions.push(Version::V3);
+            }
+            if v3_or_v2.sdp.is_some() {
+                versions.push(Version::V2);
+            }
+        }
+        versions
+    }
+
     // V4 == V3 + non-SDP; V3 == V2 + public key
     pub fn from_v4(
         call_media_type: CallMediaType,
@@ -170,6 +401,22 @@ impl Offer {
         Self::new(call_media_type, opaque.to_vec())
     }
 
+    // V5 == V4 + new key exchange
+    pub fn from_v5(
+        call_media_type: CallMediaType,
+        v5: protobuf::signaling::ConnectionParametersV5,
+    ) -> Result<Self> {
+        let proto = protobuf::signaling::Offer {
+            v5: Some(v5),
+            ..Default::default()
+        };
+
+        let mut opaque = BytesMut::with_capacity(proto.encoded_len());
+        proto.encode(&mut opaque)?;
+
+        Self::new(call_media_type, opaque.to_vec())
+    }
+
     // V4 == V3 w/o SDP; V3 == V2 + public key
     pub fn from_v4_and_v3_and_v2(
         call_media_type: CallMediaType,
@@ -184,6 +431,7 @@ impl Offer {
         let offer_proto = protobuf::signaling::Offer {
             v3_or_v2: Some(offer_proto_v3_or_v2),
             v4,
+            v5: None,
         };
 
         let mut opaque = BytesMut::with_capacity(offer_proto.encoded_len());
@@ -193,13 +441,23 @@ impl Offer {
         Self::new(call_media_type, opaque.to_vec())
     }
 
+    // V5 == V4 + new key exchange
+    pub fn to_v5(&self) -> Option<protobuf::signaling::ConnectionParametersV5> {
+        self.proto.v5.clone()
+    }
+
     // V4 == V3 + non-SDP
     pub fn to_v4(&self) -> Option<protobuf::signaling::ConnectionParametersV4> {
+        self.v4_ref().cloned()
+    }
+
+    /// Like to_v4(), but borrows from the cached proto instead of cloning.
+    pub fn v4_ref(&self) -> Option<&protobuf::signaling::ConnectionParametersV4> {
         match self {
             Self {
                 proto: protobuf::signaling::Offer { v4: Some(v4), .. },
                 ..
-            } => Some(v4.clone()),
+            } => Some(v4),
             _ => None,
         }
     }
@@ -226,6 +484,16 @@ impl Offer {
     // First return value means "is_v3_or_v2"
     // V3 == V2 + public_key
     pub fn to_v3_or_v2_params(&self) -> Result<(String, Option<Vec<u8>>)> {
+        let (sdp, public_key) = self.v3_or_v2_params_ref()?;
+        Ok((
+            sdp.to_owned(),
+            public_key.map(|public_key| public_key.to_vec()),
+        ))
+    }
+
+    /// Like to_v3_or_v2_params(), but borrows from the cached proto instead
+    /// of cloning.
+    pub fn v3_or_v2_params_ref(&self) -> Result<(&str, Option<&[u8]>)> {
         match self {
             // Prefer opaque over SDP
             Self {
@@ -239,11 +507,23 @@ impl Offer {
                         ..
                     },
                 ..
-            } => Ok((v3_or_v2_sdp.clone(), public_key.clone())),
+            } => Ok((v3_or_v2_sdp, public_key.as_deref())),
             _ => Err(RingRtcError::UnknownSignaledProtocolVersion.into()),
         }
     }
 
+    /// Returns a copy with `candidates` embedded in the V3/V2 SDP, for
+    /// peers that don't take trickled ICE candidates.
+    pub fn with_ice_candidates(&self, candidates: &[IceCandidate]) -> Result<Self> {
+        let mut proto = self.proto.clone();
+        embed_ice_candidates(&mut proto.v3_or_v2, candidates)?;
+
+        let mut opaque = BytesMut::with_capacity(proto.encoded_len());
+        proto.encode(&mut opaque)?;
+
+        Self::new(self.call_media_type, opaque.to_vec())
+    }
+
     pub fn to_info_string(&self) -> String {
         format!(
             "opaque.len={}\tproto.version={}\ttype={}",
@@ -252,6 +532,38 @@ impl Offer {
             self.call_media_type
         )
     }
+
+    /// Like to_info_string(), but also includes the SDP, if any, with
+    /// addresses and other private values redacted, so it is safe to log.
+    pub fn to_redacted_sdp_info(&self) -> String {
+        redacted_sdp_info(self.latest_version(), self.proto.v3_or_v2.as_ref())
+    }
+
+    /// Describes which sub-messages of the cached proto are present, so tests
+    /// can tell exactly which branch of latest_version() matched.
+    #[cfg(test)]
+    pub fn debug_proto_summary(&self) -> String {
+        proto_summary(
+            self.proto.v5.is_some(),
+            self.proto.v4.is_some(),
+            self.proto.v3_or_v2.as_ref(),
+        )
+    }
+
+    /// Dumps the decoded offer as JSON for logs and tests.  Not for the wire.
+    /// Keys and ICE credentials are never included, only whether they are present.
+    #[cfg(feature = "debug-signaling")]
+    pub fn to_debug_json(&self) -> String {
+        serde_json::json!({
+            "type": "offer",
+            "call_media_type": self.call_media_type.to_string(),
+            "version": self.latest_version().to_string(),
+            "opaque_len": self.opaque.len(),
+            "v4": self.proto.v4.as_ref().map(v4_debug_json),
+            "v3_or_v2": self.proto.v3_or_v2.as_ref().map(v3_or_v2_debug_json),
+        })
+        .to_string()
+    }
 }
 
 /// The callee sends this in response to an answer to setup
@@ -265,18 +577,21 @@ pub struct Answer {
 
 impl Answer {
     pub fn new(opaque: Vec<u8>) -> Result<Self> {
+        check_opaque_size(&opaque)?;
         let proto = Self::deserialize_opaque(&opaque)?;
         Ok(Self { opaque, proto })
     }
 
     fn deserialize_opaque(opaque: &[u8]) -> Result<protobuf::signaling::Answer> {
-        Ok(protobuf::signaling::Answer::decode(Bytes::from(
-            opaque.to_owned(),
-        ))?)
+        decode_with_v5_fallback(opaque)
     }
 
     pub fn latest_version(&self) -> Version {
         match self {
+            Self {
+                proto: protobuf::signaling::Answer { v5: Some(_), .. },
+                ..
+            } => Version::V5,
             Self {
                 proto: protobuf::signaling::Answer { v4: Some(_), .. },
                 ..
@@ -310,6 +625,19 @@ impl Answer {
         Self::new(opaque.to_vec())
     }
 
+    // V5 == V4 + new key exchange
+    pub fn from_v5(v5: protobuf::signaling::ConnectionParametersV5) -> Result<Self> {
+        let proto = protobuf::signaling::Answer {
+            v5: Some(v5),
+            ..Default::default()
+        };
+
+        let mut opaque = BytesMut::with_capacity(proto.encoded_len());
+        proto.encode(&mut opaque)?;
+
+        Self::new(opaque.to_vec())
+    }
+
     // V3 == V2 + public key
     pub fn from_v3_and_v2_sdp(public_key: Vec<u8>, v3_and_v2_sdp: String) -> Result<Self> {
         let answer_proto_v3_or_v2 = protobuf::signaling::ConnectionParametersV3OrV2 {
@@ -328,6 +656,11 @@ impl Answer {
         Self::new(opaque.to_vec())
     }
 
+    // V5 == V4 + new key exchange
+    pub fn to_v5(&self) -> Option<protobuf::signaling::ConnectionParametersV5> {
+        self.proto.v5.clone()
+    }
+
     // V4 == V3 + non-SDP; V3 == V2 + public key
     pub fn to_v4(&self) -> Option<protobuf::signaling::ConnectionParametersV4> {
         match self {
@@ -360,6 +693,18 @@ impl Answer {
         }
     }
 
+    /// Returns a copy with `candidates` embedded in the V3/V2 SDP, for
+    /// peers that don't take trickled ICE candidates.
+    pub fn with_ice_candidates(&self, candidates: &[IceCandidate]) -> Result<Self> {
+        let mut proto = self.proto.clone();
+        embed_ice_candidates(&mut proto.v3_or_v2, candidates)?;
+
+        let mut opaque = BytesMut::with_capacity(proto.encoded_len());
+        proto.encode(&mut opaque)?;
+
+        Self::new(opaque.to_vec())
+    }
+
     pub fn to_info_string(&self) -> String {
         format!(
             "opaque.len={}\tproto.version={}",
@@ -367,6 +712,136 @@ impl Answer {
             self.latest_version()
         )
     }
+
+    /// Like to_info_string(), but also includes the SDP, if any, with
+    /// addresses and other private values redacted, so it is safe to log.
+    pub fn to_redacted_sdp_info(&self) -> String {
+        redacted_sdp_info(self.latest_version(), self.proto.v3_or_v2.as_ref())
+    }
+
+    /// Describes which sub-messages of the cached proto are present, so tests
+    /// can tell exactly which branch of latest_version() matched.
+    #[cfg(test)]
+    pub fn debug_proto_summary(&self) -> String {
+        proto_summary(
+            self.proto.v5.is_some(),
+            self.proto.v4.is_some(),
+            self.proto.v3_or_v2.as_ref(),
+        )
+    }
+
+    /// Dumps the decoded answer as JSON for logs and tests.  Not for the wire.
+    /// Keys and ICE credentials are never included, only whether they are present.
+    #[cfg(feature = "debug-signaling")]
+    pub fn to_debug_json(&self) -> String {
+        serde_json::json!({
+            "type": "answer",
+            "version": self.latest_version().to_string(),
+            "opaque_len": self.opaque.len(),
+            "v4": self.proto.v4.as_ref().map(v4_debug_json),
+            "v3_or_v2": self.proto.v3_or_v2.as_ref().map(v3_or_v2_debug_json),
+        })
+        .to_string()
+    }
+}
+
+/// Adds `candidates` and an end-of-candidates line to the end of the first
+/// media section of the SDP.  Every other section is bundled onto that
+/// one's transport, so that's the only place they need to go.
+fn embed_ice_candidates(
+    v3_or_v2: &mut Option<protobuf::signaling::ConnectionParametersV3OrV2>,
+    candidates: &[IceCandidate],
+) -> Result<()> {
+    let sdp = match v3_or_v2.as_mut().and_then(|v3_or_v2| v3_or_v2.sdp.as_mut()) {
+        Some(sdp) => sdp,
+        None => return Err(RingRtcError::UnknownSignaledProtocolVersion.into()),
+    };
+
+    let mut lines = String::new();
+    for candidate in candidates {
+        lines.push_str(&format!("a={}\r\n", candidate.to_v3_and_v2_sdp()?));
+    }
+    lines.push_str("a=end-of-candidates\r\n");
+
+    let end_of_first_media_section = sdp.find("\r\nm=").and_then(|first| {
+        let after_first = first + 2;
+        sdp[after_first..]
+            .find("\r\nm=")
+            .map(|second| after_first + second + 2)
+    });
+    match end_of_first_media_section {
+        Some(index) => sdp.insert_str(index, &lines),
+        None => {
+            if !sdp.is_empty() && !sdp.ends_with("\r\n") {
+                sdp.push_str("\r\n");
+            }
+            sdp.push_str(&lines);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(feature = "debug-signaling")]
+fn v4_debug_json(v4: &protobuf::signaling::ConnectionParametersV4) -> serde_json::Value {
+    serde_json::json!({
+        "public_key_present": v4.public_key.is_some(),
+        "ice_ufrag_present": v4.ice_ufrag.is_some(),
+        "ice_pwd_present": v4.ice_pwd.is_some(),
+        "receive_video_codecs": v4
+            .receive_video_codecs
+            .iter()
+            .map(|codec| serde_json::json!({ "type": codec.r#type, "level": codec.level }))
+            .collect::<Vec<_>>(),
+        "max_bitrate_bps": v4.max_bitrate_bps,
+    })
+}
+
+#[cfg(feature = "debug-signaling")]
+fn v3_or_v2_debug_json(
+    v3_or_v2: &protobuf::signaling::ConnectionParametersV3OrV2,
+) -> serde_json::Value {
+    serde_json::json!({
+        "sdp_len": v3_or_v2.sdp.as_ref().map(String::len),
+        "public_key_present": v3_or_v2.public_key.is_some(),
+    })
+}
+
+fn redacted_sdp_info(
+    version: Version,
+    v3_or_v2: Option<&protobuf::signaling::ConnectionParametersV3OrV2>,
+) -> String {
+    match v3_or_v2.and_then(|v3_or_v2| v3_or_v2.sdp.as_ref()) {
+        Some(sdp) => format!(
+            "proto.version={}\tsdp.len={}\tsdp={}",
+            version,
+            sdp.len(),
+            redact_string(sdp)
+        ),
+        None => format!("proto.version={}\tsdp=none", version),
+    }
+}
+
+#[cfg(test)]
+fn proto_summary(
+    has_v5: bool,
+    has_v4: bool,
+    v3_or_v2: Option<&protobuf::signaling::ConnectionParametersV3OrV2>,
+) -> String {
+    let v3_or_v2 = match v3_or_v2 {
+        Some(protobuf::signaling::ConnectionParametersV3OrV2 {
+            public_key: Some(_),
+            ..
+        }) => "with_public_key",
+        Some(_) => "without_public_key",
+        None => "none",
+    };
+    let presence = |present| if present { "present" } else { "none" };
+    format!(
+        "v5={}\tv4={}\tv3_or_v2={}",
+        presence(has_v5),
+        presence(has_v4),
+        v3_or_v2
+    )
 }
 
 /// Each side can send these at any time after the offer and answer
@@ -376,6 +851,82 @@ pub struct Ice {
     pub candidates_added: Vec<IceCandidate>,
 }
 
+impl Ice {
+    /// Builds an Ice message from opaque candidates, such as stored ones
+    /// being replayed, taking ownership of the buffers rather than copying.
+    pub fn from_opaque_batch(opaques: Vec<Vec<u8>>) -> Self {
+        Self {
+            candidates_added: IceCandidate::from_opaque_batch(opaques),
+        }
+    }
+}
+
+/// Which IP address family ICE should favor on dual-stack networks.
+///
+/// This is a bias, not an exclusion: candidates of the other family are
+/// still gathered and used if nothing better connects, and the candidate
+/// type (host, then server reflexive, then relay) still comes first.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IpPreference {
+    /// Leave the prioritization to WebRTC.
+    Dual,
+    PreferV4,
+    PreferV6,
+}
+
+impl Default for IpPreference {
+    fn default() -> Self {
+        Self::Dual
+    }
+}
+
+impl IpPreference {
+    fn prefers(self, address: &IpAddr) -> Option<bool> {
+        match self {
+            Self::Dual => None,
+            Self::PreferV4 => Some(address.is_ipv4()),
+            Self::PreferV6 => Some(address.is_ipv6()),
+        }
+    }
+}
+
+/// The type of an ICE candidate, from its "typ" attribute.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IceCandidateType {
+    Host,
+    ServerReflexive,
+    PeerReflexive,
+    Relay,
+}
+
+/// The parts of a local ICE candidate a CandidatePrioritizer can decide on.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IceCandidateInfo {
+    pub candidate_type: IceCandidateType,
+    /// "udp" or "tcp".
+    pub protocol:       String,
+    /// An IP address, or an mDNS hostname if the address is hidden.
+    pub address:        String,
+    pub port:           u16,
+    /// The priority WebRTC gave the candidate.
+    pub priority:       u32,
+}
+
+/// Overrides the priorities of local ICE candidates as they are gathered,
+/// such as to prefer a TURN server in a particular region.
+///
+/// The priority replaces the one computed by WebRTC (including any
+/// IpPreference bias) and is what the remote peer sees, so it decides
+/// which candidate pairs are checked first and which one is chosen.
+/// Ranking a candidate too high can leave a call on a slow or
+/// expensive path, such as a far away relay, even when a direct one
+/// would have worked; WebRTC won't second-guess it.
+pub trait CandidatePrioritizer: Send + Sync {
+    /// Returns the priority to use for the candidate, or None to keep
+    /// WebRTC's.
+    fn priority(&self, candidate: &IceCandidateInfo) -> Option<u32>;
+}
+
 /// Each side sends these to setup an ICE connection
 #[derive(Clone)]
 pub struct IceCandidate {
@@ -387,6 +938,27 @@ impl IceCandidate {
         Self { opaque }
     }
 
+    /// Builds candidates from opaque buffers, in order, taking ownership
+    /// of the buffers rather than copying.
+    pub fn from_opaque_batch(opaques: Vec<Vec<u8>>) -> Vec<Self> {
+        opaques.into_iter().map(Self::new).collect()
+    }
+
+    /// Calls `f` with the candidates in order, converted by `convert`, in
+    /// batches of at most `max_per_batch`.  Each batch only lives while
+    /// `f` is running, so anything it points into stays alive for the call.
+    pub fn for_each_batch<T>(
+        candidates: &[Self],
+        max_per_batch: usize,
+        mut convert: impl FnMut(&Self) -> T,
+        mut f: impl FnMut(&[T]),
+    ) {
+        for batch in candidates.chunks(max_per_batch) {
+            let converted: Vec<T> = batch.iter().map(&mut convert).collect();
+            f(&converted);
+        }
+    }
+
     // ICE candidates are the same for V2 and V3 and V4.
     pub fn from_v3_and_v2_sdp(sdp: String) -> Result<Self> {
         let ice_candidate_proto_v3_or_v2 =
@@ -403,6 +975,7 @@ impl IceCandidate {
 
     // ICE candidates are the same for V2 and V3 and V4.
     pub fn to_v3_and_v2_sdp(&self) -> Result<String> {
+        check_opaque_size(&self.opaque)?;
         match protobuf::signaling::IceCandidate::decode(Bytes::from(self.opaque.clone()))? {
             protobuf::signaling::IceCandidate {
                 v3_or_v2:
@@ -417,6 +990,82 @@ impl IceCandidate {
     pub fn to_info_string(&self) -> String {
         format!("opaque.len={}", self.opaque.len())
     }
+
+    /// Returns the candidate with its priority biased by `ip_preference`.
+    ///
+    /// The top bit of the local preference (bits 8-23 of the priority,
+    /// see RFC 8445 section 5.1.2.1) is set for the preferred address
+    /// family and cleared for the other, which leaves the type
+    /// preference alone.  Candidates without a literal IP address, such
+    /// as mDNS hostnames, are returned unchanged.
+    pub fn prioritized_for(&self, ip_preference: IpPreference) -> Result<Self> {
+        if ip_preference == IpPreference::Dual {
+            return Ok(self.clone());
+        }
+
+        // candidate:<foundation> <component> <transport> <priority> <address> <port> typ ...
+        let sdp = self.to_v3_and_v2_sdp()?;
+        let mut fields: Vec<String> = sdp.split(' ').map(String::from).collect();
+        let priority = fields
+            .get(3)
+            .and_then(|priority| priority.parse::<u32>().ok());
+        let prefers = fields
+            .get(4)
+            .and_then(|address| address.parse::<IpAddr>().ok())
+            .and_then(|address| ip_preference.prefers(&address));
+        match (priority, prefers) {
+            (Some(priority), Some(prefers)) => {
+                const LOCAL_PREFERENCE_TOP_BIT: u32 = 0x8000 << 8;
+                let priority = if prefers {
+                    priority | LOCAL_PREFERENCE_TOP_BIT
+                } else {
+                    priority & !LOCAL_PREFERENCE_TOP_BIT
+                };
+                fields[3] = priority.to_string();
+                Self::from_v3_and_v2_sdp(fields.join(" "))
+            }
+            _ => Ok(self.clone()),
+        }
+    }
+
+    /// Parses the candidate, if it is a well-formed V3/V2 candidate line.
+    pub fn info(&self) -> Option<IceCandidateInfo> {
+        // candidate:<foundation> <component> <transport> <priority> <address> <port> typ <type> ...
+        let sdp = self.to_v3_and_v2_sdp().ok()?;
+        let fields: Vec<&str> = sdp.split(' ').collect();
+        if fields.get(6) != Some(&"typ") {
+            return None;
+        }
+        let candidate_type = match *fields.get(7)? {
+            "host" => IceCandidateType::Host,
+            "srflx" => IceCandidateType::ServerReflexive,
+            "prflx" => IceCandidateType::PeerReflexive,
+            "relay" => IceCandidateType::Relay,
+            _ => return None,
+        };
+        Some(IceCandidateInfo {
+            candidate_type,
+            protocol: fields[2].to_lowercase(),
+            address: fields[4].to_owned(),
+            port: fields[5].parse().ok()?,
+            priority: fields[3].parse().ok()?,
+        })
+    }
+
+    /// Returns a copy with the priority chosen by `prioritizer`, or an
+    /// unchanged copy if it keeps WebRTC's or the candidate can't be
+    /// parsed.
+    pub fn prioritized_by(&self, prioritizer: &dyn CandidatePrioritizer) -> Result<Self> {
+        let priority = match self.info().and_then(|info| prioritizer.priority(&info)) {
+            Some(priority) => priority,
+            None => return Ok(self.clone()),
+        };
+        let sdp = self.to_v3_and_v2_sdp()?;
+        let mut fields: Vec<&str> = sdp.split(' ').collect();
+        let priority = priority.to_string();
+        fields[3] = &priority;
+        Self::from_v3_and_v2_sdp(fields.join(" "))
+    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -428,6 +1077,9 @@ pub enum Hangup {
     // If you want to express that you NeedPermission on your device,
     // You can either fill it in or with your own device_id.
     NeedPermission(Option<DeviceId>),
+    // Like Normal, but the user stepped away (such as by backgrounding
+    // the app) rather than deliberately hanging up.
+    Away, // on this device
 }
 
 impl Hangup {
@@ -444,10 +1096,11 @@ impl Hangup {
                 (HangupType::BusyOnAnotherDevice, Some(*other_device_id))
             }
             Self::NeedPermission(other_device_id) => (HangupType::NeedPermission, *other_device_id),
+            Self::Away => (HangupType::Away, None),
         }
     }
 
-    // For Normal, device_id is ignored
+    // For Normal and Away, device_id is ignored
     // For NeedPermission, we can't express an unset DeviceId because the Android and iOS apps
     // give us DeviceIds of 0 rather than None when receiving, so we just assume it's set.
     // But since our receive logic doesn't care if it's 0 or None or anything else
@@ -459,6 +1112,7 @@ impl Hangup {
             HangupType::DeclinedOnAnotherDevice => Self::DeclinedOnAnotherDevice(device_id),
             HangupType::BusyOnAnotherDevice => Self::BusyOnAnotherDevice(device_id),
             HangupType::NeedPermission => Self::NeedPermission(Some(device_id)),
+            HangupType::Away => Self::Away,
         }
     }
 }
@@ -486,6 +1140,8 @@ pub enum HangupType {
     BusyOnAnotherDevice     = 3,
     // On either another device or this device
     NeedPermission          = 4,
+    // On this device. Peers that don't know it treat it as Normal.
+    Away                    = 5,
 }
 
 impl HangupType {
@@ -496,6 +1152,7 @@ impl HangupType {
             2 => Some(HangupType::DeclinedOnAnotherDevice),
             3 => Some(HangupType::BusyOnAnotherDevice),
             4 => Some(HangupType::NeedPermission),
+            5 => Some(HangupType::Away),
             _ => None,
         }
     }
@@ -565,3 +1222,951 @@ pub struct ReceivedHangup {
 pub struct ReceivedBusy {
     pub sender_device_id: DeviceId,
 }
+
+/// Sent during a call to ask the remote side to end it and start a new
+/// call with the target instead.  The target is whatever the application
+/// uses to identify users; it is not interpreted here.
+#[derive(Clone)]
+pub struct TransferRequest {
+    pub target: Vec<u8>,
+}
+
+/// A TransferRequest message with extra info specific to receiving
+pub struct ReceivedTransferRequest {
+    pub sender_device_id: DeviceId,
+}
+
+/// A TransferAccepted message with extra info specific to receiving
+pub struct ReceivedTransferAccepted {
+    pub sender_device_id: DeviceId,
+}
+
+/// What to do with the local call when an offer arrives for a call with
+/// the same peer (glare).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GlareResolution {
+    /// Keep the local call and ignore the incoming one.
+    KeepLocal,
+    /// End the local call and take the incoming one instead.
+    DropLocal,
+    /// End the local call and reject the incoming one too.
+    BothDrop,
+}
+
+/// Resolves glare by letting the highest call ID keep its call and be the
+/// caller, while the other side ends its call and becomes a callee.  Both
+/// sides compare the same pair of IDs, so they always agree.
+///
+/// Equal call IDs should never happen, so neither call is trusted then.
+/// The outcome doesn't depend on am_i_caller, which is only logged: each
+/// side sees itself as the caller of its own call.
+pub fn resolve_glare(
+    local_call_id: CallId,
+    remote_call_id: CallId,
+    am_i_caller: bool,
+) -> GlareResolution {
+    let resolution = match local_call_id.as_u64().cmp(&remote_call_id.as_u64()) {
+        Ordering::Greater => GlareResolution::KeepLocal,
+        Ordering::Less => GlareResolution::DropLocal,
+        Ordering::Equal => GlareResolution::BothDrop,
+    };
+    info!(
+        "resolve_glare(): local: {}, remote: {}, caller: {}, resolution: {:?}",
+        local_call_id, remote_call_id, am_i_caller, resolution
+    );
+    resolution
+}
+
+/// Guesses whether an opaque blob is an Offer, Answer, or Ice message,
+/// for when the type that came with it was lost.
+///
+/// Only the field headers are walked; nothing is decoded into a message.
+/// Offers and Answers share a wire format, so they are told apart by the
+/// DTLS setup role in their V3/V2 SDP, since "actpass" is only offered.
+/// V4-only (or V5-only) Offers and Answers carry no SDP and are
+/// ambiguous, so they return None, as does anything that doesn't parse.
+pub fn detect_message_type(opaque: &[u8]) -> Option<MessageType> {
+    let mut v3_or_v2 = None;
+    let mut has_v4 = false;
+    for_each_length_delimited_field(opaque, |tag, value| match tag {
+        2 => v3_or_v2 = Some(value),
+        4 | V5_TAG => has_v4 = true,
+        _ => {}
+    })?;
+
+    let mut sdp = None;
+    let mut has_public_key = false;
+    for_each_length_delimited_field(v3_or_v2?, |tag, value| match tag {
+        1 => sdp = Some(value),
+        2 => has_public_key = true,
+        _ => {}
+    })?;
+    let sdp = sdp?;
+
+    if sdp.starts_with(b"candidate:") {
+        if has_v4 || has_public_key {
+            None
+        } else {
+            Some(MessageType::Ice)
+        }
+    } else if contains(sdp, b"a=setup:actpass") {
+        Some(MessageType::Offer)
+    } else if contains(sdp, b"a=setup:active") || contains(sdp, b"a=setup:passive") {
+        Some(MessageType::Answer)
+    } else {
+        None
+    }
+}
+
+fn check_opaque_size(opaque: &[u8]) -> Result<()> {
+    if opaque.len() > MAX_OPAQUE_SIZE {
+        return Err(RingRtcError::SignalingMessageTooLarge(opaque.len()).into());
+    }
+    Ok(())
+}
+
+/// The field number of v5 in the Offer and Answer protos.
+const V5_TAG: u32 = 5;
+
+// Decodes an Offer or Answer.  If that fails, tries again without the v5
+// field, so that a V5 this build can't parse (such as from a newer build
+// that changed it) falls back to the v4 or v3_or_v2 alongside it.
+fn decode_with_v5_fallback<M: prost::Message + Default>(opaque: &[u8]) -> Result<M> {
+    match M::decode(Bytes::from(opaque.to_owned())) {
+        Ok(proto) => Ok(proto),
+        Err(err) => match without_field(opaque, V5_TAG) {
+            Some(fallback) if fallback.len() < opaque.len() => {
+                warn!("Ignoring V5 that failed to decode: {}", err);
+                Ok(M::decode(Bytes::from(fallback))?)
+            }
+            _ => Err(err.into()),
+        },
+    }
+}
+
+// Returns a copy of the encoded message without the fields with the given
+// tag.  Returns None if the message is malformed.
+fn without_field(mut buf: &[u8], tag_to_remove: u32) -> Option<Vec<u8>> {
+    use prost::encoding::{decode_key, decode_varint, WireType};
+
+    let mut out = Vec::with_capacity(buf.len());
+    while !buf.is_empty() {
+        let field = buf;
+        let (tag, wire_type) = decode_key(&mut buf).ok()?;
+        let len = match wire_type {
+            WireType::Varint => {
+                decode_varint(&mut buf).ok()?;
+                0
+            }
+            WireType::SixtyFourBit => 8,
+            WireType::ThirtyTwoBit => 4,
+            WireType::LengthDelimited => decode_varint(&mut buf).ok()? as usize,
+            WireType::StartGroup | WireType::EndGroup => return None,
+        };
+        if len > buf.len() {
+            return None;
+        }
+        buf = &buf[len..];
+        if tag != tag_to_remove {
+            out.extend_from_slice(&field[..field.len() - buf.len()]);
+        }
+    }
+    Some(out)
+}
+
+// Calls f with the tag and value of each length delimited field of the
+// encoded message, skipping other fields.  Returns None if the message
+// is malformed.
+fn for_each_length_delimited_field<'a>(
+    mut buf: &'a [u8],
+    mut f: impl FnMut(u32, &'a [u8]),
+) -> Option<()> {
+    use prost::encoding::{decode_key, decode_varint, WireType};
+
+    while !buf.is_empty() {
+        let (tag, wire_type) = decode_key(&mut buf).ok()?;
+        let skip = match wire_type {
+            WireType::Varint => {
+                decode_varint(&mut buf).ok()?;
+                0
+            }
+            WireType::SixtyFourBit => 8,
+            WireType::ThirtyTwoBit => 4,
+            WireType::LengthDelimited => {
+                let len = decode_varint(&mut buf).ok()? as usize;
+                if len > buf.len() {
+                    return None;
+                }
+                f(tag, &buf[..len]);
+                len
+            }
+            WireType::StartGroup | WireType::EndGroup => return None,
+        };
+        if skip > buf.len() {
+            return None;
+        }
+        buf = &buf[skip..];
+    }
+    Some(())
+}
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack
+        .windows(needle.len())
+        .any(|window| window == needle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v3_or_v2(public_key: Option<Vec<u8>>) -> protobuf::signaling::ConnectionParametersV3OrV2 {
+        protobuf::signaling::ConnectionParametersV3OrV2 {
+            sdp: Some("sdp".to_owned()),
+            public_key,
+        }
+    }
+
+    fn encode(proto: impl prost::Message) -> Vec<u8> {
+        let mut opaque = BytesMut::with_capacity(proto.encoded_len());
+        proto.encode(&mut opaque).unwrap();
+        opaque.to_vec()
+    }
+
+    fn assert_too_large(result: Result<impl Sized>, size: usize) {
+        match result.err().and_then(|err| err.downcast::<RingRtcError>().ok()) {
+            Some(RingRtcError::SignalingMessageTooLarge(len)) => assert_eq!(size, len),
+            _ => panic!("expected SignalingMessageTooLarge"),
+        }
+    }
+
+    #[test]
+    fn ice_candidates_batched_in_order() {
+        let candidates: Vec<IceCandidate> = (0..55u8).map(|i| IceCandidate::new(vec![i])).collect();
+
+        let mut batches: Vec<Vec<u8>> = Vec::new();
+        IceCandidate::for_each_batch(
+            &candidates,
+            20,
+            |candidate| candidate.opaque[0],
+            |batch| batches.push(batch.to_vec()),
+        );
+
+        assert_eq!(
+            vec![20, 20, 15],
+            batches.iter().map(Vec::len).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            (0..55).collect::<Vec<u8>>(),
+            batches.into_iter().flatten().collect::<Vec<_>>()
+        );
+
+        let mut calls = 0;
+        IceCandidate::for_each_batch(&[], 20, |_| (), |_| calls += 1);
+        assert_eq!(0, calls);
+    }
+
+    #[test]
+    fn identity_keys_checked() {
+        let check = |identity_key: &[u8]| {
+            check_identity_key("test()", "key", identity_key)
+                .err()
+                .and_then(|err| err.downcast::<RingRtcError>().ok())
+        };
+
+        assert!(check(&[7; IDENTITY_KEY_LEN]).is_none());
+        assert!(matches!(
+            check(&[5; 5]),
+            Some(RingRtcError::InvalidIdentityKeyLength(_, var, 5)) if var == "key"
+        ));
+        assert!(matches!(
+            check(&[]),
+            Some(RingRtcError::InvalidIdentityKeyLength(_, _, 0))
+        ));
+
+        // A key serialized with its type byte is accepted, but only with the
+        // Curve25519 type.
+        let mut typed_key = vec![IDENTITY_KEY_TYPE_DJB];
+        typed_key.extend_from_slice(&[7; IDENTITY_KEY_LEN]);
+        assert!(check(&typed_key).is_none());
+        typed_key[0] = 0x06;
+        assert!(matches!(
+            check(&typed_key),
+            Some(RingRtcError::InvalidIdentityKeyType(_, var, 0x06)) if var == "key"
+        ));
+        typed_key.push(7);
+        assert!(matches!(
+            check(&typed_key),
+            Some(RingRtcError::InvalidIdentityKeyLength(_, _, len)) if len == IDENTITY_KEY_LEN + 2
+        ));
+    }
+
+    #[test]
+    fn glare_resolved_by_call_id() {
+        let low = CallId::new(1000);
+        let high = CallId::new(2000);
+
+        assert_eq!(GlareResolution::KeepLocal, resolve_glare(high, low, true));
+        assert_eq!(GlareResolution::DropLocal, resolve_glare(low, high, true));
+        // Direction doesn't matter, so the two sides always agree.
+        assert_eq!(GlareResolution::KeepLocal, resolve_glare(high, low, false));
+        assert_eq!(GlareResolution::DropLocal, resolve_glare(low, high, false));
+
+        assert_eq!(GlareResolution::BothDrop, resolve_glare(low, low, true));
+        assert_eq!(GlareResolution::BothDrop, resolve_glare(high, high, false));
+    }
+
+    #[test]
+    fn oversized_opaque_rejected() {
+        // Not a valid proto, so without the size check these would be
+        // decode failures instead.
+        let oversized = vec![0xff; MAX_OPAQUE_SIZE + 1];
+        assert_too_large(
+            Offer::new(CallMediaType::Audio, oversized.clone()),
+            MAX_OPAQUE_SIZE + 1,
+        );
+        assert_too_large(Answer::new(oversized.clone()), MAX_OPAQUE_SIZE + 1);
+        assert_too_large(
+            IceCandidate::new(oversized).to_v3_and_v2_sdp(),
+            MAX_OPAQUE_SIZE + 1,
+        );
+
+        // Right at the limit is still decoded.
+        let sdp = "s".repeat(MAX_OPAQUE_SIZE - 6);
+        let ice_candidate = IceCandidate::from_v3_and_v2_sdp(sdp.clone()).unwrap();
+        assert_eq!(MAX_OPAQUE_SIZE, ice_candidate.opaque.len());
+        assert_eq!(sdp, ice_candidate.to_v3_and_v2_sdp().unwrap());
+    }
+
+    fn assert_round_trips(message: Message) {
+        let serialized = message.serialize().unwrap();
+        let deserialized = Message::deserialize(&serialized).unwrap();
+        assert_eq!(message.typ(), deserialized.typ());
+        assert_eq!(message.to_string(), deserialized.to_string());
+        assert_eq!(serialized, deserialized.serialize().unwrap());
+    }
+
+    #[test]
+    fn message_serialization_round_trip() {
+        let offer = Offer::from_v4_and_v3_and_v2(
+            CallMediaType::Video,
+            vec![1, 2, 3],
+            Some(v4()),
+            "sdp".to_owned(),
+        )
+        .unwrap();
+        assert_round_trips(Message::Offer(offer.clone()));
+        match Message::deserialize(&Message::Offer(offer.clone()).serialize().unwrap()) {
+            Ok(Message::Offer(deserialized)) => {
+                assert_eq!(CallMediaType::Video, deserialized.call_media_type);
+                assert_eq!(offer.opaque, deserialized.opaque);
+            }
+            _ => panic!("expected an Offer"),
+        }
+
+        let answer = Answer::from_v4(v4()).unwrap();
+        assert_round_trips(Message::Answer(answer));
+
+        let candidates_added = vec![
+            IceCandidate::from_v3_and_v2_sdp("candidate:1".to_owned()).unwrap(),
+            IceCandidate::new(vec![]),
+            IceCandidate::from_v3_and_v2_sdp("candidate:2".to_owned()).unwrap(),
+        ];
+        assert_round_trips(Message::Ice(Ice { candidates_added }));
+        assert_round_trips(Message::Ice(Ice {
+            candidates_added: vec![],
+        }));
+
+        for hangup in &[
+            Hangup::Normal,
+            Hangup::AcceptedOnAnotherDevice(1),
+            Hangup::DeclinedOnAnotherDevice(2),
+            Hangup::BusyOnAnotherDevice(0x01020304),
+            Hangup::NeedPermission(Some(4)),
+            Hangup::NeedPermission(None),
+            Hangup::Away,
+        ] {
+            assert_round_trips(Message::Hangup(*hangup));
+            assert_round_trips(Message::LegacyHangup(*hangup));
+
+            // The two hangup encodings stay distinct.
+            match Message::deserialize(&Message::LegacyHangup(*hangup).serialize().unwrap()) {
+                Ok(Message::LegacyHangup(deserialized)) => assert_eq!(*hangup, deserialized),
+                _ => panic!("expected a LegacyHangup"),
+            }
+            match Message::deserialize(&Message::Hangup(*hangup).serialize().unwrap()) {
+                Ok(Message::Hangup(deserialized)) => assert_eq!(*hangup, deserialized),
+                _ => panic!("expected a Hangup"),
+            }
+        }
+
+        assert_round_trips(Message::Busy);
+        assert_round_trips(Message::TransferRequest(TransferRequest {
+            target: vec![1, 2, 3],
+        }));
+        assert_round_trips(Message::TransferAccepted);
+    }
+
+    #[test]
+    fn invalid_serialized_message() {
+        for bytes in &[
+            &[][..],
+            &[8],
+            &[SERIALIZED_OFFER],
+            &[SERIALIZED_OFFER, 2],
+            &[SERIALIZED_ICE, 5, 1],
+            &[SERIALIZED_HANGUP],
+            &[SERIALIZED_HANGUP, HangupType::Normal as u8, 0, 0, 0, 1],
+            &[SERIALIZED_HANGUP, HangupType::AcceptedOnAnotherDevice as u8],
+            &[SERIALIZED_LEGACY_HANGUP, 6],
+            &[SERIALIZED_BUSY, 0],
+        ] {
+            assert!(Message::deserialize(bytes).is_err());
+        }
+    }
+
+    #[test]
+    fn version_ordering() {
+        let versions = [Version::V2, Version::V3, Version::V4, Version::V5];
+        for (i, older) in versions.iter().enumerate() {
+            assert!(older.at_least(*older));
+            for newer in &versions[i + 1..] {
+                assert!(older < newer);
+                assert!(newer.at_least(*older));
+                assert!(!older.at_least(*newer));
+            }
+        }
+        assert_eq!(Some(&Version::V5), versions.iter().max());
+    }
+
+    #[test]
+    fn offer_proto_summary() {
+        let offer = Offer::from_v4_and_v3_and_v2(
+            CallMediaType::Audio,
+            vec![1],
+            Some(Default::default()),
+            "sdp".to_owned(),
+        )
+        .unwrap();
+        assert_eq!(Version::V4, offer.latest_version());
+        assert_eq!(
+            "v5=none\tv4=present\tv3_or_v2=with_public_key",
+            offer.debug_proto_summary()
+        );
+
+        let offer = Offer::from_v4(CallMediaType::Audio, Default::default()).unwrap();
+        assert_eq!(Version::V4, offer.latest_version());
+        assert_eq!("v5=none\tv4=present\tv3_or_v2=none", offer.debug_proto_summary());
+
+        let opaque = encode(protobuf::signaling::Offer {
+            v3_or_v2: Some(v3_or_v2(Some(vec![1]))),
+            v4:       None,
+            v5:       None,
+        });
+        let offer = Offer::new(CallMediaType::Video, opaque).unwrap();
+        assert_eq!(Version::V3, offer.latest_version());
+        assert_eq!(
+            "v5=none\tv4=none\tv3_or_v2=with_public_key",
+            offer.debug_proto_summary()
+        );
+
+        let opaque = encode(protobuf::signaling::Offer {
+            v3_or_v2: Some(v3_or_v2(None)),
+            v4:       None,
+            v5:       None,
+        });
+        let offer = Offer::new(CallMediaType::Video, opaque).unwrap();
+        assert_eq!(Version::V2, offer.latest_version());
+        assert_eq!(
+            "v5=none\tv4=none\tv3_or_v2=without_public_key",
+            offer.debug_proto_summary()
+        );
+
+        let offer = Offer::new(CallMediaType::Video, vec![]).unwrap();
+        assert_eq!(Version::V2, offer.latest_version());
+        assert_eq!("v5=none\tv4=none\tv3_or_v2=none", offer.debug_proto_summary());
+    }
+
+    #[test]
+    fn offer_refs_match_clones() {
+        let offer = Offer::from_v4_and_v3_and_v2(
+            CallMediaType::Audio,
+            vec![1],
+            Some(protobuf::signaling::ConnectionParametersV4 {
+                public_key: Some(vec![2]),
+                ..Default::default()
+            }),
+            "sdp".to_owned(),
+        )
+        .unwrap();
+        assert_eq!(offer.to_v4().as_ref(), offer.v4_ref());
+        let (sdp, public_key) = offer.to_v3_or_v2_params().unwrap();
+        let (sdp_ref, public_key_ref) = offer.v3_or_v2_params_ref().unwrap();
+        assert_eq!(sdp, sdp_ref);
+        assert_eq!(public_key.as_deref(), public_key_ref);
+        assert_eq!(Some(&[1u8][..]), public_key_ref);
+
+        let offer = Offer::new(CallMediaType::Video, vec![]).unwrap();
+        assert_eq!(None, offer.v4_ref());
+        assert!(offer.v3_or_v2_params_ref().is_err());
+    }
+
+    #[test]
+    fn answer_proto_summary() {
+        let answer = Answer::from_v4(Default::default()).unwrap();
+        assert_eq!(Version::V4, answer.latest_version());
+        assert_eq!("v5=none\tv4=present\tv3_or_v2=none", answer.debug_proto_summary());
+
+        let answer = Answer::from_v3_and_v2_sdp(vec![1], "sdp".to_owned()).unwrap();
+        assert_eq!(Version::V3, answer.latest_version());
+        assert_eq!(
+            "v5=none\tv4=none\tv3_or_v2=with_public_key",
+            answer.debug_proto_summary()
+        );
+
+        let opaque = encode(protobuf::signaling::Answer {
+            v3_or_v2: Some(v3_or_v2(None)),
+            v4:       None,
+            v5:       None,
+        });
+        let answer = Answer::new(opaque).unwrap();
+        assert_eq!(Version::V2, answer.latest_version());
+        assert_eq!(
+            "v5=none\tv4=none\tv3_or_v2=without_public_key",
+            answer.debug_proto_summary()
+        );
+    }
+
+    #[test]
+    fn sdp_info_redacts_candidate_addresses() {
+        let sdp = "v=0\r\nm=audio 9 RTP 111\r\n\
+                   a=candidate:1 1 udp 2122260223 192.0.2.1 50000 typ host\r\n";
+
+        let offer =
+            Offer::from_v4_and_v3_and_v2(CallMediaType::Audio, vec![1], None, sdp.to_owned())
+                .unwrap();
+        let info = offer.to_redacted_sdp_info();
+        assert!(!info.contains("192.0.2.1"), "{}", info);
+        assert!(info.contains("a=candidate:1 1 udp 2122260223 [REDACTED] 50000 typ host"));
+
+        let answer = Answer::from_v3_and_v2_sdp(vec![1], sdp.to_owned()).unwrap();
+        let info = answer.to_redacted_sdp_info();
+        assert!(!info.contains("192.0.2.1"), "{}", info);
+        assert!(info.starts_with("proto.version=V3\tsdp.len="));
+
+        let offer = Offer::from_v4(CallMediaType::Audio, Default::default()).unwrap();
+        assert_eq!("proto.version=V4\tsdp=none", offer.to_redacted_sdp_info());
+    }
+
+    fn v5() -> protobuf::signaling::ConnectionParametersV5 {
+        protobuf::signaling::ConnectionParametersV5 {
+            public_key: Some(vec![5]),
+            ..Default::default()
+        }
+    }
+
+    fn v4() -> protobuf::signaling::ConnectionParametersV4 {
+        protobuf::signaling::ConnectionParametersV4 {
+            public_key: Some(vec![4]),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn v5_round_trip() {
+        let offer = Offer::from_v5(CallMediaType::Video, v5()).unwrap();
+        let offer = Offer::new(offer.call_media_type, offer.opaque).unwrap();
+        assert_eq!(Version::V5, offer.latest_version());
+        assert_eq!(Some(v5()), offer.to_v5());
+        assert_eq!(None, offer.to_v4());
+        assert!(!Version::V5.enable_dtls());
+
+        let answer = Answer::from_v5(v5()).unwrap();
+        let answer = Answer::new(answer.opaque).unwrap();
+        assert_eq!(Version::V5, answer.latest_version());
+        assert_eq!(Some(v5()), answer.to_v5());
+        assert_eq!(None, answer.to_v4());
+    }
+
+    #[test]
+    fn v5_only_offer_and_answer_parse() {
+        let opaque = encode(protobuf::signaling::Offer {
+            v3_or_v2: None,
+            v4:       None,
+            v5:       Some(v5()),
+        });
+        let offer = Offer::new(CallMediaType::Audio, opaque).unwrap();
+        assert_eq!(Version::V5, offer.latest_version());
+        assert_eq!(Some(v5()), offer.to_v5());
+        assert_eq!(
+            "v5=present\tv4=none\tv3_or_v2=none",
+            offer.debug_proto_summary()
+        );
+
+        let opaque = encode(protobuf::signaling::Answer {
+            v3_or_v2: None,
+            v4:       None,
+            v5:       Some(v5()),
+        });
+        let answer = Answer::new(opaque).unwrap();
+        assert_eq!(Version::V5, answer.latest_version());
+        assert_eq!(Some(v5()), answer.to_v5());
+        assert_eq!(
+            "v5=present\tv4=none\tv3_or_v2=none",
+            answer.debug_proto_summary()
+        );
+    }
+
+    #[test]
+    fn v5_offer_decoded_by_v4_only_client() {
+        // The Offer proto as it was before V5.
+        #[derive(Clone, PartialEq, ::prost::Message)]
+        struct OfferV4 {
+            #[prost(message, optional, tag = "2")]
+            v3_or_v2: Option<protobuf::signaling::ConnectionParametersV3OrV2>,
+            #[prost(message, optional, tag = "4")]
+            v4:       Option<protobuf::signaling::ConnectionParametersV4>,
+        }
+
+        let opaque = encode(protobuf::signaling::Offer {
+            v3_or_v2: Some(v3_or_v2(Some(vec![1]))),
+            v4:       Some(v4()),
+            v5:       Some(v5()),
+        });
+        let offer = Offer::new(CallMediaType::Audio, opaque.clone()).unwrap();
+        assert_eq!(Version::V5, offer.latest_version());
+        // Until connections can negotiate V5, they use V4.
+        assert_eq!(
+            vec![Version::V4, Version::V3, Version::V2],
+            offer.supported_versions()
+        );
+
+        let old: OfferV4 = prost::Message::decode(Bytes::from(opaque)).unwrap();
+        assert_eq!(Some(v4()), old.v4);
+        assert_eq!(Some(v3_or_v2(Some(vec![1]))), old.v3_or_v2);
+    }
+
+    #[test]
+    fn unparseable_v5_falls_back() {
+        // A v5 (field 5, length delimited) holding a truncated varint.
+        let bad_v5 = [0x2a, 0x02, 0xff, 0xff];
+
+        let mut opaque = encode(protobuf::signaling::Offer {
+            v3_or_v2: Some(v3_or_v2(Some(vec![1]))),
+            v4:       Some(v4()),
+            v5:       None,
+        });
+        opaque.extend_from_slice(&bad_v5);
+        let offer = Offer::new(CallMediaType::Audio, opaque.clone()).unwrap();
+        assert_eq!(Version::V4, offer.latest_version());
+        assert_eq!(None, offer.to_v5());
+        assert_eq!(Some(v4()), offer.to_v4());
+        // The opaque is passed along as received.
+        assert_eq!(opaque, offer.opaque);
+
+        let mut opaque = encode(protobuf::signaling::Answer {
+            v3_or_v2: None,
+            v4:       Some(v4()),
+            v5:       None,
+        });
+        opaque.extend_from_slice(&bad_v5);
+        let answer = Answer::new(opaque).unwrap();
+        assert_eq!(Version::V4, answer.latest_version());
+        assert_eq!(Some(v4()), answer.to_v4());
+
+        // Anything else that doesn't parse is still an error.
+        assert!(Offer::new(CallMediaType::Audio, bad_v5[2..].to_vec()).is_err());
+        assert!(Answer::new(vec![0x22, 0x02, 0xff, 0xff]).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "debug-signaling")]
+    fn debug_json() {
+        let offer = Offer::from_v4_and_v3_and_v2(
+            CallMediaType::Video,
+            vec![42; 32],
+            Some(Default::default()),
+            "sdp".to_owned(),
+        )
+        .unwrap();
+        let json: serde_json::Value = serde_json::from_str(&offer.to_debug_json()).unwrap();
+        assert_eq!("offer", json["type"]);
+        assert_eq!("V4", json["version"]);
+        assert_eq!(false, json["v4"]["public_key_present"]);
+        assert_eq!(3, json["v3_or_v2"]["sdp_len"]);
+        assert_eq!(true, json["v3_or_v2"]["public_key_present"]);
+        // The key itself is never dumped.
+        assert!(!offer.to_debug_json().contains("42,42"));
+
+        let answer = Answer::from_v3_and_v2_sdp(vec![42; 32], "sdp".to_owned()).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&answer.to_debug_json()).unwrap();
+        assert_eq!("answer", json["type"]);
+        assert_eq!("V3", json["version"]);
+        assert_eq!(serde_json::Value::Null, json["v4"]);
+    }
+
+    #[test]
+    fn message_type_i32_round_trip() {
+        let all = [
+            MessageType::Offer,
+            MessageType::Answer,
+            MessageType::Ice,
+            MessageType::Hangup,
+            MessageType::Busy,
+            MessageType::MediaKey,
+            MessageType::TransferRequest,
+            MessageType::TransferAccepted,
+        ];
+        for (i, message_type) in all.iter().enumerate() {
+            assert_eq!(i as i32, message_type.as_i32());
+            assert_eq!(Some(*message_type), MessageType::from_i32(i as i32));
+        }
+
+        assert_eq!(None, MessageType::from_i32(-1));
+        assert_eq!(None, MessageType::from_i32(all.len() as i32));
+        assert_eq!(None, MessageType::from_i32(i32::MAX));
+    }
+
+    #[test]
+    fn detect_message_types() {
+        let offer_sdp = "v=0\r\na=setup:actpass\r\n".to_owned();
+        let answer_sdp = "v=0\r\na=setup:active\r\n".to_owned();
+
+        let offer = Offer::from_v4_and_v3_and_v2(
+            CallMediaType::Audio,
+            vec![1],
+            Some(Default::default()),
+            offer_sdp.clone(),
+        )
+        .unwrap();
+        assert_eq!(Some(MessageType::Offer), detect_message_type(&offer.opaque));
+
+        let opaque = encode(protobuf::signaling::Answer {
+            v3_or_v2: Some(protobuf::signaling::ConnectionParametersV3OrV2 {
+                sdp:        Some(answer_sdp.clone()),
+                public_key: Some(vec![1]),
+            }),
+            v4:       Some(Default::default()),
+            v5:       None,
+        });
+        assert_eq!(Some(MessageType::Answer), detect_message_type(&opaque));
+        let answer = Answer::from_v3_and_v2_sdp(vec![1], answer_sdp).unwrap();
+        assert_eq!(
+            Some(MessageType::Answer),
+            detect_message_type(&answer.opaque)
+        );
+
+        let ice = IceCandidate::from_v3_and_v2_sdp(
+            "candidate:1 1 udp 2122260223 192.168.1.2 50000 typ host".to_owned(),
+        )
+        .unwrap();
+        assert_eq!(Some(MessageType::Ice), detect_message_type(&ice.opaque));
+
+        // Without SDP, an Offer and an Answer look the same.
+        let offer = Offer::from_v4(CallMediaType::Audio, Default::default()).unwrap();
+        assert_eq!(None, detect_message_type(&offer.opaque));
+        let answer = Answer::from_v4(Default::default()).unwrap();
+        assert_eq!(None, detect_message_type(&answer.opaque));
+
+        assert_eq!(None, detect_message_type(&[]));
+        assert_eq!(None, detect_message_type(&[0x12, 0x7f, 0x0a]));
+        assert_eq!(None, detect_message_type(b"not a protobuf"));
+    }
+
+    #[test]
+    fn ice_candidates_from_opaque_batch() {
+        let opaques = vec![vec![1, 2, 3], vec![], vec![4], vec![5, 6]];
+        let pointers: Vec<*const u8> = opaques.iter().map(|opaque| opaque.as_ptr()).collect();
+
+        let candidates = IceCandidate::from_opaque_batch(opaques.clone());
+        assert_eq!(
+            opaques,
+            candidates
+                .iter()
+                .map(|candidate| candidate.opaque.clone())
+                .collect::<Vec<_>>()
+        );
+
+        let ice = Ice::from_opaque_batch(opaques);
+        assert_eq!(4, ice.candidates_added.len());
+        assert_eq!(vec![5, 6], ice.candidates_added[3].opaque);
+        // The buffers were moved, not copied.
+        assert_eq!(pointers[0], ice.candidates_added[0].opaque.as_ptr());
+        assert_eq!(pointers[3], ice.candidates_added[3].opaque.as_ptr());
+    }
+
+    #[test]
+    fn embed_ice_candidates_in_first_media_section() {
+        let candidate = IceCandidate::from_v3_and_v2_sdp(
+            "candidate:1 1 udp 2122260223 192.0.2.1 50000 typ host".to_owned(),
+        )
+        .unwrap();
+        let offer_sdp =
+            "v=0\r\nm=audio 9 RTP 111\r\na=mid:audio\r\nm=video 9 RTP 96\r\na=mid:video\r\n";
+
+        let offer =
+            Offer::from_v4_and_v3_and_v2(CallMediaType::Video, vec![1], None, offer_sdp.to_owned())
+                .unwrap()
+                .with_ice_candidates(&[candidate.clone()])
+                .unwrap();
+        let (sdp, public_key) = offer.v3_or_v2_params_ref().unwrap();
+        assert_eq!(
+            "v=0\r\nm=audio 9 RTP 111\r\na=mid:audio\r\n\
+             a=candidate:1 1 udp 2122260223 192.0.2.1 50000 typ host\r\na=end-of-candidates\r\n\
+             m=video 9 RTP 96\r\na=mid:video\r\n",
+            sdp
+        );
+        assert_eq!(Some(&[1u8][..]), public_key);
+        assert_eq!(CallMediaType::Video, offer.call_media_type);
+
+        let answer = Answer::from_v3_and_v2_sdp(vec![1], "v=0\r\nm=audio 9 RTP 111".to_owned())
+            .unwrap()
+            .with_ice_candidates(&[])
+            .unwrap();
+        assert_eq!(
+            "v=0\r\nm=audio 9 RTP 111\r\na=end-of-candidates\r\n",
+            answer.to_v3_or_v2_params().unwrap().0
+        );
+
+        // V4 has no SDP to put them in.
+        let offer = Offer::from_v4(CallMediaType::Audio, Default::default()).unwrap();
+        assert!(offer.with_ice_candidates(&[candidate]).is_err());
+    }
+
+    #[test]
+    fn ip_preference_biases_candidate_priority() {
+        let host_v4 = IceCandidate::from_v3_and_v2_sdp(
+            "candidate:1 1 udp 2122260223 192.0.2.1 50000 typ host generation 0".to_owned(),
+        )
+        .unwrap();
+        let host_v6 = IceCandidate::from_v3_and_v2_sdp(
+            "candidate:2 1 udp 2122262783 2001:db8::1 50001 typ host generation 0".to_owned(),
+        )
+        .unwrap();
+        let srflx_v4 = IceCandidate::from_v3_and_v2_sdp(
+            "candidate:3 1 udp 1686052607 198.51.100.1 50002 typ srflx raddr 192.0.2.1 rport 50000 generation 0"
+                .to_owned(),
+        )
+        .unwrap();
+        let mdns = IceCandidate::from_v3_and_v2_sdp(
+            "candidate:4 1 udp 2122260223 abc.local 50003 typ host generation 0".to_owned(),
+        )
+        .unwrap();
+
+        let priority = |candidate: &IceCandidate, ip_preference| -> u32 {
+            let sdp = candidate
+                .prioritized_for(ip_preference)
+                .unwrap()
+                .to_v3_and_v2_sdp()
+                .unwrap();
+            sdp.split(' ').nth(3).unwrap().parse().unwrap()
+        };
+
+        // WebRTC ranks IPv6 first on its own.
+        assert!(priority(&host_v6, IpPreference::Dual) > priority(&host_v4, IpPreference::Dual));
+        assert_eq!(
+            host_v4.to_v3_and_v2_sdp().unwrap(),
+            host_v4
+                .prioritized_for(IpPreference::Dual)
+                .unwrap()
+                .to_v3_and_v2_sdp()
+                .unwrap()
+        );
+
+        assert!(
+            priority(&host_v4, IpPreference::PreferV4) > priority(&host_v6, IpPreference::PreferV4)
+        );
+        assert!(
+            priority(&host_v6, IpPreference::PreferV6) > priority(&host_v4, IpPreference::PreferV6)
+        );
+
+        // Only a bias: the candidate type still comes first.
+        assert!(
+            priority(&host_v6, IpPreference::PreferV4)
+                > priority(&srflx_v4, IpPreference::PreferV4)
+        );
+
+        // Hostnames have no address family to prefer.
+        assert_eq!(
+            priority(&mdns, IpPreference::Dual),
+            priority(&mdns, IpPreference::PreferV4)
+        );
+    }
+
+    #[test]
+    fn candidate_prioritizer_reorders_candidates() {
+        struct PreferRelay;
+
+        impl CandidatePrioritizer for PreferRelay {
+            fn priority(&self, candidate: &IceCandidateInfo) -> Option<u32> {
+                match candidate.candidate_type {
+                    IceCandidateType::Relay => {
+                        Some((127 << 24) | (candidate.priority & 0x00ff_ffff))
+                    }
+                    _ => None,
+                }
+            }
+        }
+
+        let candidates: Vec<IceCandidate> = vec![
+            "candidate:1 1 udp 2122260223 192.0.2.1 50000 typ host generation 0",
+            "candidate:2 1 udp 1686052607 198.51.100.1 50001 typ srflx raddr 192.0.2.1 rport 50000 generation 0",
+            "candidate:3 1 udp 41885439 203.0.113.1 50002 typ relay raddr 198.51.100.1 rport 50001 generation 0",
+        ]
+        .into_iter()
+        .map(|sdp| IceCandidate::from_v3_and_v2_sdp(sdp.to_owned()).unwrap())
+        .collect();
+
+        let relay = candidates[2].info().unwrap();
+        assert_eq!(
+            relay,
+            IceCandidateInfo {
+                candidate_type: IceCandidateType::Relay,
+                protocol:       "udp".to_owned(),
+                address:        "203.0.113.1".to_owned(),
+                port:           50002,
+                priority:       41885439,
+            }
+        );
+
+        // Highest priority first, the order ICE checks them in.
+        let order = |candidates: Vec<IceCandidate>| -> Vec<IceCandidateType> {
+            let mut infos: Vec<IceCandidateInfo> =
+                candidates.iter().map(|c| c.info().unwrap()).collect();
+            infos.sort_by(|a, b| b.priority.cmp(&a.priority));
+            infos.into_iter().map(|info| info.candidate_type).collect()
+        };
+
+        assert_eq!(
+            order(candidates.clone()),
+            vec![
+                IceCandidateType::Host,
+                IceCandidateType::ServerReflexive,
+                IceCandidateType::Relay
+            ]
+        );
+
+        let prioritized: Vec<IceCandidate> = candidates
+            .iter()
+            .map(|c| c.prioritized_by(&PreferRelay).unwrap())
+            .collect();
+        assert_eq!(
+            order(prioritized.clone()),
+            vec![
+                IceCandidateType::Relay,
+                IceCandidateType::Host,
+                IceCandidateType::ServerReflexive
+            ]
+        );
+
+        // Only the priority changes.
+        assert_eq!(
+            prioritized[2].to_v3_and_v2_sdp().unwrap(),
+            "candidate:3 1 udp 2139037439 203.0.113.1 50002 typ relay raddr 198.51.100.1 rport 50001 generation 0"
+        );
+        assert_eq!(
+            prioritized[0].to_v3_and_v2_sdp().unwrap(),
+            candidates[0].to_v3_and_v2_sdp().unwrap()
+        );
+    }
+}
@@ -9,10 +9,11 @@ use std::ffi::c_void;
 use std::mem;
 use std::sync::{Arc, Condvar, Mutex};
 use std::thread;
+use std::time::{Duration, Instant};
 
-#[cfg(any(not(debug_assertions), test))]
+#[cfg(any(not(debug_assertions), test, feature = "force-redaction"))]
 use lazy_static::lazy_static;
-#[cfg(any(not(debug_assertions), test))]
+#[cfg(any(not(debug_assertions), test, feature = "force-redaction"))]
 use regex::Regex;
 
 use futures::future::Future;
@@ -142,14 +143,59 @@ pub unsafe fn ptr_as_box<T>(ptr: *mut T) -> Result<Box<T>> {
     Ok(object)
 }
 
+/// Runs f, turning a panic into the error made by on_panic, so that a
+/// panic doesn't unwind across the FFI boundary.
+pub fn catch_panic<T>(
+    f: impl FnOnce() -> Result<T>,
+    on_panic: impl FnOnce() -> failure::Error,
+) -> Result<T> {
+    std::panic::catch_unwind(std::panic::AssertUnwindSafe(f)).unwrap_or_else(|_| Err(on_panic()))
+}
+
+/// Where the current time comes from, so tests can move it along.
+pub trait Clock: Send {
+    fn now(&self) -> Instant;
+}
+
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// A Clock that only moves when told to.
+#[cfg(test)]
+#[derive(Clone)]
+pub struct TestClock(Arc<Mutex<Instant>>);
+
+#[cfg(test)]
+impl TestClock {
+    pub fn new() -> Self {
+        Self(Arc::new(Mutex::new(Instant::now())))
+    }
+
+    pub fn advance(&self, duration: Duration) {
+        *self.0.lock().unwrap() += duration;
+    }
+}
+
+#[cfg(test)]
+impl Clock for TestClock {
+    fn now(&self) -> Instant {
+        *self.0.lock().unwrap()
+    }
+}
+
 #[allow(dead_code)]
-#[cfg(all(debug_assertions, not(test)))]
+#[cfg(all(debug_assertions, not(test), not(feature = "force-redaction")))]
 fn redact_ice_password(text: &str) -> String {
     text.to_string()
 }
 
 #[allow(dead_code)]
-#[cfg(any(not(debug_assertions), test))]
+#[cfg(any(not(debug_assertions), test, feature = "force-redaction"))]
 fn redact_ice_password(text: &str) -> String {
     let mut lines = text.lines().collect::<Vec<&str>>();
 
@@ -195,13 +241,13 @@ fn redact_ice_password(text: &str) -> String {
 //            )
 
 #[allow(dead_code)]
-#[cfg(all(debug_assertions, not(test)))]
+#[cfg(all(debug_assertions, not(test), not(feature = "force-redaction")))]
 fn redact_ipv6(text: &str) -> String {
     text.to_string()
 }
 
 #[allow(dead_code)]
-#[cfg(any(not(debug_assertions), test))]
+#[cfg(any(not(debug_assertions), test, feature = "force-redaction"))]
 fn redact_ipv6(text: &str) -> String {
     lazy_static! {
         static ref RE: Option<Regex> = {
@@ -234,13 +280,13 @@ fn redact_ipv6(text: &str) -> String {
 }
 
 #[allow(dead_code)]
-#[cfg(all(debug_assertions, not(test)))]
+#[cfg(all(debug_assertions, not(test), not(feature = "force-redaction")))]
 fn redact_ipv4(text: &str) -> String {
     text.to_string()
 }
 
 #[allow(dead_code)]
-#[cfg(any(not(debug_assertions), test))]
+#[cfg(any(not(debug_assertions), test, feature = "force-redaction"))]
 fn redact_ipv4(text: &str) -> String {
     lazy_static! {
         static ref RE: Option<Regex> = {
@@ -258,19 +304,76 @@ fn redact_ipv4(text: &str) -> String {
     }
 }
 
+#[allow(dead_code)]
+#[cfg(all(debug_assertions, not(test), not(feature = "force-redaction")))]
+fn redact_phone(text: &str) -> String {
+    text.to_string()
+}
+
+// Matches E.164 phone numbers, such as +14155550123.
+#[allow(dead_code)]
+#[cfg(any(not(debug_assertions), test, feature = "force-redaction"))]
+fn redact_phone(text: &str) -> String {
+    lazy_static! {
+        static ref RE: Option<Regex> = {
+            let re = "\\+[0-9]{7,15}\\b";
+            match Regex::new(&re) {
+                Ok(v) => Some(v),
+                Err(_) => None,
+            }
+        };
+    }
+
+    match &*RE {
+        Some(v) => v.replace_all(text, "[REDACTED]").to_string(),
+        None => "[REDACTED]".to_string(),
+    }
+}
+
+#[allow(dead_code)]
+#[cfg(all(debug_assertions, not(test), not(feature = "force-redaction")))]
+fn redact_uuid(text: &str) -> String {
+    text.to_string()
+}
+
+// Matches UUIDs in the canonical 8-4-4-4-12 hex form.
+#[allow(dead_code)]
+#[cfg(any(not(debug_assertions), test, feature = "force-redaction"))]
+fn redact_uuid(text: &str) -> String {
+    lazy_static! {
+        static ref RE: Option<Regex> = {
+            let re = "[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}";
+            match Regex::new(&re) {
+                Ok(v) => Some(v),
+                Err(_) => None,
+            }
+        };
+    }
+
+    match &*RE {
+        Some(v) => v.replace_all(text, "[REDACTED]").to_string(),
+        None => "[REDACTED]".to_string(),
+    }
+}
+
 /// Scrubs off sensitive information from the string for public
 /// logging purposes, including:
 /// - ICE passwords
+/// - UUIDs
+/// - E.164 phone numbers
 /// - IPv4 and IPv6 addresses
-#[cfg(not(debug_assertions))]
+#[cfg(any(not(debug_assertions), test, feature = "force-redaction"))]
 pub fn redact_string(text: &str) -> String {
     let mut string = redact_ice_password(text);
+    string = redact_uuid(&string);
+    string = redact_phone(&string);
     string = redact_ipv6(&string);
     redact_ipv4(&string)
 }
 
-/// For debug builds, redacting won't do anything.
-#[cfg(debug_assertions)]
+/// For debug builds, redacting won't do anything, unless the
+/// force-redaction feature is enabled.
+#[cfg(all(debug_assertions, not(test), not(feature = "force-redaction")))]
 pub fn redact_string(text: &str) -> String {
     text.to_string()
 }
@@ -284,14 +387,58 @@ pub fn redact_string(text: &str) -> String {
 /// assert_eq!(bytes_to_hexstring(&[0x01, 0xAB, 0xCD]), "01abcd");
 /// ```
 pub fn bytes_to_hexstring(bytes: &[u8]) -> String {
-    let mut result = String::new();
+    const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+    let mut result = String::with_capacity(bytes.len() * 2);
     for byte in bytes {
-        let hex_byte = format!("{:02x}", byte);
-        result.push_str(&hex_byte);
+        result.push(HEX_DIGITS[(byte >> 4) as usize] as char);
+        result.push(HEX_DIGITS[(byte & 0x0F) as usize] as char);
     }
     result
 }
 
+/// Decodes a hexadecimal string, in upper or lower case, into bytes.
+/// Fails if the string has an odd length or anything other than hex
+/// digits.
+///
+/// ```
+/// use ringrtc::core::util::{bytes_to_hexstring, hexstring_to_bytes};
+///
+/// assert_eq!(hexstring_to_bytes("").unwrap(), Vec::<u8>::new());
+/// assert_eq!(hexstring_to_bytes("01abcd").unwrap(), vec![0x01, 0xAB, 0xCD]);
+/// assert_eq!(hexstring_to_bytes("01ABcd").unwrap(), vec![0x01, 0xAB, 0xCD]);
+/// assert!(hexstring_to_bytes("01abc").is_err());
+/// assert!(hexstring_to_bytes("01abcg").is_err());
+///
+/// let bytes = [0x00, 0x7F, 0x80, 0xFF];
+/// assert_eq!(hexstring_to_bytes(&bytes_to_hexstring(&bytes)).unwrap(), bytes);
+/// ```
+pub fn hexstring_to_bytes(s: &str) -> Result<Vec<u8>> {
+    let hex = s.as_bytes();
+    if hex.len() % 2 != 0 {
+        return Err(RingRtcError::OddLengthHexString(hex.len()).into());
+    }
+
+    let nibble = |index: usize| -> Result<u8> {
+        match hex[index] {
+            c @ b'0'..=b'9' => Ok(c - b'0'),
+            c @ b'a'..=b'f' => Ok(c - b'a' + 10),
+            c @ b'A'..=b'F' => Ok(c - b'A' + 10),
+            _ => {
+                // Everything before index is ASCII, so index is a char boundary.
+                let c = s[index..].chars().next().unwrap_or_default();
+                Err(RingRtcError::InvalidHexCharacter(c, index).into())
+            }
+        }
+    };
+
+    let mut bytes = Vec::with_capacity(hex.len() / 2);
+    for index in (0..hex.len()).step_by(2) {
+        bytes.push((nibble(index)? << 4) | nibble(index + 1)?);
+    }
+    Ok(bytes)
+}
+
 /// Computes a SHA-256 hash of the input value and returns it as a hex string.
 ///
 /// ```
@@ -334,13 +481,44 @@ pub fn uuid_to_string(bytes: &[u8]) -> String {
     }
 }
 
+/// Like uuid_to_string, but returns None unless the bytes are a UUID as
+/// described in RFC 4122: 16 bytes with a version of 1 to 5 and the
+/// RFC 4122 variant.  This keeps other 16-byte values, such as keys, from
+/// being logged as if they were UUIDs.
+///
+/// ```
+/// use ringrtc::core::util::uuid_to_string_checked;
+///
+/// assert_eq!(uuid_to_string_checked(&[0xb3, 0x9b, 0x70, 0xb0, 0x1c, 0xc8, 0x4b, 0x00, 0xb9, 0x32, 0x18, 0x31, 0x03, 0x76, 0x03, 0x15]), Some("b39b70b0-1cc8-4b00-b932-183103760315".to_string()));
+/// assert_eq!(uuid_to_string_checked(&[0x01, 0xAB, 0xCD]), None);
+/// // The variant bits (the top of byte 8) are 0b11, not 0b10.
+/// assert_eq!(uuid_to_string_checked(&[0xb3, 0x9b, 0x70, 0xb0, 0x1c, 0xc8, 0x4b, 0x00, 0xf9, 0x32, 0x18, 0x31, 0x03, 0x76, 0x03, 0x15]), None);
+/// // The version (the top of byte 6) is 0.
+/// assert_eq!(uuid_to_string_checked(&[0xb3, 0x9b, 0x70, 0xb0, 0x1c, 0xc8, 0x0b, 0x00, 0xb9, 0x32, 0x18, 0x31, 0x03, 0x76, 0x03, 0x15]), None);
+/// ```
+pub fn uuid_to_string_checked(bytes: &[u8]) -> Option<String> {
+    if bytes.len() != 16 {
+        return None;
+    }
+    let version = bytes[6] >> 4;
+    let variant = bytes[8] >> 6;
+    if (1..=5).contains(&version) && variant == 0b10 {
+        Some(uuid_to_string(bytes))
+    } else {
+        None
+    }
+}
+
 /// A specially configured tokio::Runtime for processing sequential tasks
 /// in the context of a Call or Connection.
 /// Pre-configured with the right parameters for single-threaded operation,
 /// and can be dropped safely on a different runtime thread.
 #[derive(Debug)]
 pub struct TaskQueueRuntime {
-    rt: Option<runtime::Runtime>,
+    rt:         Option<runtime::Runtime>,
+    /// Every thread the runtime has started, so waiting on it from one of
+    /// them can be refused instead of deadlocking.
+    thread_ids: Arc<Mutex<Vec<thread::ThreadId>>>,
 }
 
 impl Drop for TaskQueueRuntime {
@@ -361,16 +539,34 @@ impl Drop for TaskQueueRuntime {
 }
 
 impl TaskQueueRuntime {
+    /// Creates a runtime with a single worker thread, so tasks run one at
+    /// a time in the order they were spawned.
     pub fn new(name: &str) -> Result<Self> {
+        Self::with_threads(name, 1)
+    }
+
+    /// Creates a runtime with a pool of worker_threads threads, for work
+    /// that doesn't need to run in order, such as HTTP requests.
+    pub fn with_threads(name: &str, worker_threads: usize) -> Result<Self> {
+        if worker_threads == 0 {
+            return Err(RingRtcError::NoWorkerThreads.into());
+        }
+        let thread_ids = Arc::new(Mutex::new(Vec::new()));
+        let started_thread_ids = thread_ids.clone();
         let rt = Some(
             runtime::Builder::new_multi_thread()
-                .worker_threads(1)
-                .max_threads(1)
+                .worker_threads(worker_threads)
+                .max_threads(worker_threads)
                 .enable_all()
                 .thread_name(name)
+                .on_thread_start(move || {
+                    if let Ok(mut thread_ids) = started_thread_ids.lock() {
+                        thread_ids.push(thread::current().id());
+                    }
+                })
                 .build()?,
         );
-        Ok(TaskQueueRuntime { rt })
+        Ok(TaskQueueRuntime { rt, thread_ids })
     }
 
     pub fn spawn<F>(&self, future: F)
@@ -380,12 +576,93 @@ impl TaskQueueRuntime {
     {
         self.rt.as_ref().unwrap().spawn(future);
     }
+
+    /// Spawns the future and blocks until it resolves, returning its
+    /// output.  Fails rather than deadlocking if called from one of the
+    /// runtime's own threads.
+    pub fn spawn_blocking_result<F, T>(&self, future: F) -> Result<T>
+    where
+        F: Future<Output = T> + Send + 'static,
+        T: Send + 'static,
+    {
+        self.spawn_and_wait(future, None)
+    }
+
+    /// Like spawn_blocking_result(), but gives up after timeout.  The
+    /// future keeps running after a timeout; only its output is dropped.
+    pub fn spawn_with_timeout<F, T>(&self, future: F, timeout: Duration) -> Result<T>
+    where
+        F: Future<Output = T> + Send + 'static,
+        T: Send + 'static,
+    {
+        self.spawn_and_wait(future, Some(timeout))
+    }
+
+    fn spawn_and_wait<F, T>(&self, future: F, timeout: Option<Duration>) -> Result<T>
+    where
+        F: Future<Output = T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let poisoned = || RingRtcError::MutexPoisoned("TaskQueueRuntime mutex".to_string());
+
+        let current_thread_id = thread::current().id();
+        let thread_ids = self.thread_ids.lock().map_err(|_| poisoned())?;
+        if thread_ids.contains(&current_thread_id) {
+            return Err(RingRtcError::WaitOnOwnTaskQueue.into());
+        }
+        drop(thread_ids);
+
+        let condition: FutureResult<Option<T>> =
+            Arc::new((Mutex::new((false, None)), Condvar::new()));
+        let completion = condition.clone();
+        self.spawn(async move {
+            let output = future.await;
+            let &(ref mtx, ref cvar) = &*completion;
+            if let Ok(mut guard) = mtx.lock() {
+                guard.1 = Some(output);
+                guard.0 = true;
+                // We notify the condvar that the value has changed.
+                cvar.notify_one();
+            }
+        });
+
+        let deadline = timeout.map(|timeout| Instant::now() + timeout);
+        let &(ref mtx, ref cvar) = &*condition;
+        let mut guard = mtx.lock().map_err(|_| poisoned())?;
+        while !guard.0 {
+            guard = match deadline {
+                None => cvar.wait(guard).map_err(|_| poisoned())?,
+                Some(deadline) => {
+                    let now = Instant::now();
+                    if now >= deadline {
+                        return Err(RingRtcError::TaskQueueTimeout.into());
+                    }
+                    cvar.wait_timeout(guard, deadline - now)
+                        .map_err(|_| poisoned())?
+                        .0
+                }
+            };
+        }
+        guard.1.take().ok_or_else(|| {
+            RingRtcError::OptionValueNotSet("spawn_and_wait()".to_string(), "output".to_string())
+                .into()
+        })
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn panic_becomes_error() {
+        let result: Result<()> = catch_panic(|| panic!("boom"), || format_err!("panicked"));
+        assert_eq!(result.unwrap_err().to_string(), "panicked");
+
+        let result = catch_panic(|| Ok(7), || format_err!("panicked"));
+        assert_eq!(result.unwrap(), 7);
+    }
+
     #[test]
     fn check_ipv6() {
         let addrs = [
@@ -498,4 +775,141 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn bytes_to_hexstring_large_buffer() {
+        let bytes: Vec<u8> = (0..64 * 1024).map(|i| (i * 7 + i / 256) as u8).collect();
+
+        let start = std::time::Instant::now();
+        let hex = bytes_to_hexstring(&bytes);
+        debug!("bytes_to_hexstring(64 KiB) took {:?}", start.elapsed());
+
+        assert_eq!(hex.len(), bytes.len() * 2);
+        let expected: String = bytes.iter().map(|byte| format!("{:02x}", byte)).collect();
+        assert_eq!(hex, expected);
+        assert_eq!(hexstring_to_bytes(&hex).unwrap(), bytes);
+    }
+
+    #[test]
+    fn task_queue_runtime_with_threads() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::mpsc;
+
+        let completed = Arc::new(AtomicUsize::new(0));
+        let (done_sender, done_receiver) = mpsc::channel();
+        let runtime = TaskQueueRuntime::with_threads("test-pool", 2).unwrap();
+        for _ in 0..10 {
+            let completed = completed.clone();
+            let done_sender = done_sender.clone();
+            runtime.spawn(async move {
+                completed.fetch_add(1, Ordering::SeqCst);
+                let _ = done_sender.send(());
+            });
+        }
+        for _ in 0..10 {
+            done_receiver
+                .recv_timeout(Duration::from_secs(5))
+                .expect("every future completes");
+        }
+        assert_eq!(completed.load(Ordering::SeqCst), 10);
+
+        assert!(TaskQueueRuntime::with_threads("test-pool", 0).is_err());
+    }
+
+    #[test]
+    fn task_queue_runtime_spawn_blocking_result() {
+        let runtime = Arc::new(TaskQueueRuntime::new("test-worker").unwrap());
+        assert_eq!(runtime.spawn_blocking_result(async { 6 * 7 }).unwrap(), 42);
+        assert_eq!(
+            runtime
+                .spawn_with_timeout(async { "done" }, Duration::from_secs(5))
+                .unwrap(),
+            "done"
+        );
+
+        // Waiting from the runtime's own thread would deadlock.
+        let (result_sender, result_receiver) = std::sync::mpsc::channel();
+        let inner_runtime = runtime.clone();
+        runtime.spawn(async move {
+            let result = inner_runtime.spawn_blocking_result(async {});
+            // Don't let the runtime be dropped from its own thread.
+            drop(inner_runtime);
+            let _ = result_sender.send(result.is_err());
+        });
+        assert!(result_receiver
+            .recv_timeout(Duration::from_secs(5))
+            .unwrap());
+    }
+
+    #[test]
+    fn task_queue_runtime_spawn_with_timeout() {
+        let runtime = TaskQueueRuntime::new("test-worker").unwrap();
+        let result = runtime.spawn_with_timeout(
+            futures::future::pending::<()>(),
+            Duration::from_millis(50),
+        );
+        assert!(matches!(
+            result.err().and_then(|err| err.downcast::<RingRtcError>().ok()),
+            Some(RingRtcError::TaskQueueTimeout)
+        ));
+    }
+
+    #[test]
+    fn check_phone() {
+        let numbers = [
+            "+1234567",
+            "+14155550123",
+            "+442071838750",
+            "+4930123456789",
+            "+123456789012345",
+        ];
+
+        let prefix = ["", "text", "text ", "<", "@", "tel:"];
+
+        let suffix = ["", " text", ">", "@", ";"];
+
+        for n in numbers.iter() {
+            for p in prefix.iter() {
+                for s in suffix.iter() {
+                    let number = format!("{}{}{}", p, n, s);
+                    let scrubbed = redact_phone(&number);
+                    assert_eq!((&number, scrubbed), (&number, format!("{}[REDACTED]{}", p, s)));
+                }
+            }
+        }
+
+        // Too short, too long, or not prefixed with +.
+        for not_a_number in ["+123456", "+1234567890123456", "14155550123"].iter() {
+            assert_eq!(redact_phone(not_a_number), *not_a_number);
+        }
+    }
+
+    #[test]
+    fn check_uuid() {
+        let uuids = [
+            "00000000-0000-0000-0000-000000000000",
+            "1b4e28ba-2fa1-11d2-883f-0016d3cca427",
+            "1B4E28BA-2FA1-11D2-883F-0016D3CCA427",
+            "f81d4fae-7dec-11d0-a765-00a0c91e6bf6",
+        ];
+
+        let prefix = ["", "text", "text ", "<", "@", "/"];
+
+        let suffix = ["", " text", ">", "@", "/"];
+
+        for u in uuids.iter() {
+            for p in prefix.iter() {
+                for s in suffix.iter() {
+                    let uuid = format!("{}{}{}", p, u, s);
+                    let scrubbed = redact_uuid(&uuid);
+                    assert_eq!((&uuid, scrubbed), (&uuid, format!("{}[REDACTED]{}", p, s)));
+                }
+            }
+        }
+
+        // Not in the canonical form.
+        for not_a_uuid in ["1b4e28ba2fa111d2883f0016d3cca427", "1b4e28ba-2fa1-11d2-883f"].iter() {
+            assert_eq!(redact_uuid(not_a_uuid), *not_a_uuid);
+        }
+    }
 }
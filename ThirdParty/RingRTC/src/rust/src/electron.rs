@@ -125,7 +125,7 @@ impl SignalingSender for Sender<Event> {
         msg: signaling::Message,
     ) -> Result<()> {
         self.send(Event::SendSignaling(
-            recipient_id.to_string(),
+            PeerId::parse(recipient_id)?,
             receiver_device_id,
             call_id,
             msg,
@@ -141,12 +141,12 @@ impl SignalingSender for Sender<Event> {
 
 impl CallStateHandler for Sender<Event> {
     fn handle_call_state(&self, remote_peer_id: &str, call_state: CallState) -> Result<()> {
-        self.send(Event::CallState(remote_peer_id.to_string(), call_state))?;
+        self.send(Event::CallState(PeerId::parse(remote_peer_id)?, call_state))?;
         Ok(())
     }
 
     fn handle_remote_video_state(&self, remote_peer_id: &str, enabled: bool) -> Result<()> {
-        self.send(Event::RemoteVideoState(remote_peer_id.to_string(), enabled))?;
+        self.send(Event::RemoteVideoState(PeerId::parse(remote_peer_id)?, enabled))?;
         Ok(())
     }
 }
@@ -372,7 +372,8 @@ declare_types! {
         }
 
         method createOutgoingCall(mut cx) {
-            let peer_id = cx.argument::<JsString>(0)?.value() as PeerId;
+            let peer_id = PeerId::parse(&cx.argument::<JsString>(0)?.value())
+                .or_else(|err: failure::Error| cx.throw_error(format!("{}", err)))?;
             let video_enabled = cx.argument::<JsBoolean>(1)?.value();
             let local_device_id = cx.argument::<JsNumber>(2)?.value() as DeviceId;
 
@@ -501,7 +502,8 @@ declare_types! {
         }
 
         method receivedOffer(mut cx) {
-            let peer_id = cx.argument::<JsString>(0)?.value() as PeerId;
+            let peer_id = PeerId::parse(&cx.argument::<JsString>(0)?.value())
+                .or_else(|err: failure::Error| cx.throw_error(format!("{}", err)))?;
             let sender_device_id = cx.argument::<JsNumber>(1)?.value() as DeviceId;
             let receiver_device_id = cx.argument::<JsNumber>(2)?.value() as DeviceId;
             let age_sec = cx.argument::<JsNumber>(3)?.value() as u64;
@@ -547,7 +549,7 @@ declare_types! {
         }
 
         method receivedAnswer(mut cx) {
-            let _peer_id = cx.argument::<JsString>(0)?.value() as PeerId;
+            let _peer_id = cx.argument::<JsString>(0)?.value();
             let sender_device_id = cx.argument::<JsNumber>(1)?.value() as DeviceId;
             let call_id = CallId::new(get_id_arg(&mut cx, 2));
             let sender_supports_multi_ring = cx.argument::<JsBoolean>(3)?.value();
@@ -581,7 +583,8 @@ declare_types! {
         }
 
         method receivedIceCandidates(mut cx) {
-            let peer_id = cx.argument::<JsString>(0)?.value() as PeerId;
+            let peer_id = PeerId::parse(&cx.argument::<JsString>(0)?.value())
+                .or_else(|err: failure::Error| cx.throw_error(format!("{}", err)))?;
             let sender_device_id = cx.argument::<JsNumber>(1)?.value() as DeviceId;
             let call_id = CallId::new(get_id_arg(&mut cx, 2));
             let js_candidates = *cx.argument::<JsArray>(3)?;
@@ -608,7 +611,8 @@ declare_types! {
         }
 
         method receivedHangup(mut cx) {
-            let peer_id = cx.argument::<JsString>(0)?.value() as PeerId;
+            let peer_id = PeerId::parse(&cx.argument::<JsString>(0)?.value())
+                .or_else(|err: failure::Error| cx.throw_error(format!("{}", err)))?;
             let sender_device_id = cx.argument::<JsNumber>(1)?.value() as DeviceId;
             let call_id = CallId::new(get_id_arg(&mut cx, 2));
             let hangup_type = cx.argument::<JsNumber>(3)?.value() as i32;
@@ -640,7 +644,8 @@ declare_types! {
         }
 
         method receivedBusy(mut cx) {
-            let peer_id = cx.argument::<JsString>(0)?.value() as PeerId;
+            let peer_id = PeerId::parse(&cx.argument::<JsString>(0)?.value())
+                .or_else(|err: failure::Error| cx.throw_error(format!("{}", err)))?;
             let sender_device_id = cx.argument::<JsNumber>(1)?.value() as DeviceId;
             let call_id = CallId::new(get_id_arg(&mut cx, 2));
             debug!("JsCallManager.receivedBusy({}, {}, {})", peer_id, sender_device_id, call_id);
@@ -823,7 +828,7 @@ declare_types! {
                 let peer_connection_factory = cm.peer_connection_factory.clone();
                 let outgoing_audio_track = cm.outgoing_audio_track.clone();
                 let outgoing_video_track = cm.outgoing_video_track.clone();
-                let result = cm.call_manager.create_group_call_client(group_id, sfu_url, Some(peer_connection_factory), outgoing_audio_track, outgoing_video_track);
+                let result = cm.call_manager.create_group_call_client(group_id, sfu_url, Some(peer_connection_factory), outgoing_audio_track, outgoing_video_track, None);
                 if let Ok(v) = result {
                     client_id = v;
                 }
@@ -969,7 +974,7 @@ declare_types! {
                 };
 
                 if demux_id.is_some() && width.is_some() && height.is_some() {
-                    resolutions.push(group_call::VideoRequest { demux_id: demux_id.unwrap(), width: width.unwrap(), height: height.unwrap(), framerate });
+                    resolutions.push(group_call::VideoRequest { demux_id: demux_id.unwrap(), width: width.unwrap(), height: height.unwrap(), framerate, pinned: false });
                 } else {
                     warn!("Skipping resolution due to invalid field");
                 }
@@ -1039,7 +1044,7 @@ declare_types! {
         method peekGroupCall(mut cx) {
             let request_id = cx.argument::<JsNumber>(0)?.value() as u32;
 
-            let sfu_url = cx.argument::<JsString>(1)?.value() as PeerId;
+            let sfu_url = cx.argument::<JsString>(1)?.value();
 
             let membership_proof = cx.argument::<JsArrayBuffer>(2)?;
             let membership_proof = cx.borrow(&membership_proof, |handle| { handle.as_slice().to_vec() });
@@ -1224,6 +1229,11 @@ declare_types! {
                             signaling::Message::Busy => {
                                 ("onSendBusy", cx.undefined().upcast(), cx.undefined().upcast(), cx.undefined().upcast())
                             }
+                            signaling::Message::TransferRequest(_) | signaling::Message::TransferAccepted => {
+                                // Desktop doesn't support call transfer, so these are never sent.
+                                warn!("Dropping call transfer message for call_id: {}", call_id);
+                                continue;
+                            }
                         };
                         let error_message = format!("{} is a function", method_name);
                         let method = *observer.get(&mut cx, method_name)?.downcast::<JsFunction>().expect(&error_message);
@@ -1287,6 +1297,10 @@ declare_types! {
                             EndReason::DeclinedOnAnotherDevice => "DeclinedOnAnotherDevice",
                             EndReason::BusyOnAnotherDevice => "BusyOnAnotherDevice",
                             EndReason::CallerIsNotMultiring => "CallerIsNotMultiring",
+                            EndReason::UnsupportedProtocolVersion => "UnsupportedProtocolVersion",
+                            EndReason::Transferred => "Transferred",
+                            EndReason::RemoteAway => "RemoteAway",
+                            EndReason::NetworkChanged => "NetworkChanged",
                         };
                         let args = vec![
                             cx.string(peer_id),
@@ -1302,6 +1316,7 @@ declare_types! {
                             CallState::Ringing => "ringing",
                             CallState::Connected => "connected",
                             CallState::Connecting => "connecting",
+                            CallState::OnHold => "onHold",
                             // Ignoring Concluded state since application should not treat
                             // it as an 'ending' state transition.
                             CallState::Concluded => return Ok(cx.undefined().upcast()),
@@ -1408,7 +1423,8 @@ declare_types! {
                         method.call(&mut cx, observer, args)?;
                     }
 
-                    Event::GroupUpdate(GroupUpdate::JoinStateChanged(client_id, join_state)) => {
+                    // Desktop doesn't join with start_muted, so the mute state is left out.
+                    Event::GroupUpdate(GroupUpdate::JoinStateChanged(client_id, join_state, _)) => {
                         let method_name = "handleJoinStateChanged";
 
                         let args: Vec<Handle<JsValue>> = vec![
@@ -1478,7 +1494,16 @@ declare_types! {
                         }).or_else(|err: failure::Error| cx.throw_error(format!("{}", err)))?;
                     }
 
-                    Event::GroupUpdate(GroupUpdate::PeekChanged(client_id, members, creator, era_id, max_devices, device_count)) => {
+                    Event::GroupUpdate(GroupUpdate::IncomingInterpreterAudioTrack(_client_id, _remote_demux_id, _incoming_interpreter_audio_track)) => {
+                        // Not offered on desktop yet.  Dropping the track leaves it disabled.
+                    }
+
+                    Event::GroupUpdate(GroupUpdate::ResolutionChanged(_client_id, _remote_demux_id, _width, _height)) => {
+                        // Nothing to do: receiveGroupCallVideoFrame already returns
+                        // the dimensions of each frame it pulls.
+                    }
+
+                    Event::GroupUpdate(GroupUpdate::PeekChanged(client_id, members, _joined_devices, creator, era_id, max_devices, device_count)) => {
                         let method_name = "handlePeekChanged";
 
                         let js_members = JsArray::new(&mut cx, members.len() as u32);
@@ -1523,7 +1548,7 @@ declare_types! {
                         method.call(&mut cx, observer, args)?;
                     }
 
-                    Event::GroupUpdate(GroupUpdate::PeekResponse(request_id, members, creator, era_id, max_devices, device_count)) => {
+                    Event::GroupUpdate(GroupUpdate::PeekResponse(request_id, members, _joined_devices, creator, era_id, max_devices, device_count)) => {
                         let method_name = "handlePeekResponse";
                         let js_info = cx.empty_object();
                         let js_members = JsArray::new(&mut cx, members.len() as u32);
@@ -1563,6 +1588,51 @@ declare_types! {
                         method.call(&mut cx, observer, args)?;
                     }
 
+                    Event::GroupUpdate(GroupUpdate::EraChanged(_client_id, _old_era_id, _new_era_id)) => {
+                        // Nothing to do: handlePeekChanged already passes the eraId,
+                        // which desktop compares itself.
+                    }
+
+                    Event::GroupUpdate(GroupUpdate::OutgoingKeyframe(_client_id, _local_demux_id)) => {
+                        // Nothing to do: desktop doesn't use key frame timing.
+                    }
+
+                    Event::GroupUpdate(GroupUpdate::ParticipantQuality(_client_id, _remote_demux_id, _quality)) => {
+                        // Nothing to do: desktop doesn't show participant quality yet.
+                    }
+
+                    Event::GroupUpdate(GroupUpdate::AudioLevels(_client_id, _audio_levels)) => {
+                        // Nothing to do: desktop doesn't show audio levels yet.
+                    }
+
+                    Event::GroupUpdate(GroupUpdate::MediaKeysReceived(_client_id, _remote_demux_id)) => {
+                        // Nothing to do: desktop uses media_keys_received from the remote device states.
+                    }
+
+                    Event::GroupUpdate(GroupUpdate::PeekTimedOut(_request_id)) => {
+                        // Nothing to do: desktop doesn't peek with a timeout.
+                    }
+
+                    Event::GroupUpdate(GroupUpdate::CandidatePairChanged(_client_id, _local_demux_id, _candidate_pair_type)) => {
+                        // Nothing to do: desktop doesn't show whether media is relayed.
+                    }
+
+                    Event::GroupUpdate(GroupUpdate::JoinRejected(client_id, http_status, message)) => {
+                        let method_name = "handleJoinRejected";
+                        let message: neon::handle::Handle<JsValue> = match message {
+                            None => cx.null().upcast(),
+                            Some(message) => cx.string(message).upcast(),
+                        };
+                        let args: Vec<Handle<JsValue>> = vec![
+                            cx.number(client_id).upcast(),
+                            cx.number(http_status).upcast(),
+                            message,
+                        ];
+                        let error_message = format!("{} is a function", method_name);
+                        let method = *observer.get(&mut cx, method_name)?.downcast::<JsFunction>().expect(&error_message);
+                        method.call(&mut cx, observer, args)?;
+                    }
+
                     Event::GroupUpdate(GroupUpdate::Ended(client_id, reason)) => {
                         let method_name = "handleEnded";
                         let args : Vec<Handle<JsValue>> = vec![
@@ -15,6 +15,8 @@ pub enum RingRtcError {
     MutexPoisoned(String),
     #[fail(display = "Null pointer in: {}, var: {}", _0, _1)]
     NullPointer(String, String),
+    #[fail(display = "Invalid handle in: {}", _0)]
+    InvalidHandle(String),
     #[fail(display = "Expecting non-none option value in: {}, var: {}", _0, _1)]
     OptionValueNotSet(String, String),
     #[fail(display = "Couldn't register an actor")]
@@ -27,6 +29,13 @@ pub enum RingRtcError {
     CallManagerIsBusy,
     #[fail(display = "No active call found")]
     NoActiveCall,
+    #[fail(display = "No held call found")]
+    NoHeldCall,
+    #[fail(
+        display = "Suspended signaling queue is full, can't hold: {}, call_id: {}",
+        _0, _1
+    )]
+    SuspendedSignalingQueueFull(String, CallId),
     #[fail(display = "CallID not found in call_map: {}", _0)]
     CallIdNotFound(CallId),
     #[fail(display = "Connection not found in connection_map: {}", _0)]
@@ -47,12 +56,34 @@ pub enum RingRtcError {
     AppConnectionAlreadySet(DeviceId),
     #[fail(display = "Application Call Context is already set, call_id: {}", _0)]
     AppCallContextAlreadySet(CallId),
+    #[fail(display = "Group call client not found, client_id: {}", _0)]
+    GroupCallClientNotFound(u32),
+    #[fail(display = "Expecting either a call ID or a group call client ID, but not both")]
+    CallOrGroupCallClientRequired,
+    #[fail(display = "Call transfer is not supported on this platform")]
+    CallTransferNotSupported,
+    #[fail(display = "Invalid offer age: {} seconds", _0)]
+    InvalidOfferAge(u64),
+    #[fail(display = "Signaling message too large: {} bytes", _0)]
+    SignalingMessageTooLarge(usize),
+    #[fail(
+        display = "Invalid identity key length in: {}, var: {}, {} bytes",
+        _0, _1, _2
+    )]
+    InvalidIdentityKeyLength(String, String, usize),
+    #[fail(
+        display = "Invalid identity key type in: {}, var: {}, type: {:#04x}",
+        _0, _1, _2
+    )]
+    InvalidIdentityKeyType(String, String, u8),
 
     // WebRTC / C++ error codes
     #[fail(display = "Unable to create C++ PeerConnectionObserver")]
     CreatePeerConnectionObserver,
     #[fail(display = "Unable to create C++ signaling DataChannel")]
     CreateSignalingDataChannel,
+    #[fail(display = "Unable to add interpreter AudioTrack to C++ PeerConnection")]
+    AddInterpreterAudioTrack,
     #[fail(display = "Unable to create C++ PeerConnectionFactory")]
     CreatePeerConnectionFactory,
     #[fail(display = "Unable to create C++ PeerConnection")]
@@ -73,6 +104,10 @@ pub enum RingRtcError {
     #[allow(dead_code)]
     #[fail(display = "Unable to set Audio Device")]
     SetAudioDevice,
+    #[fail(display = "Unable to start C++ AudioLoopback")]
+    StartAudioLoopback,
+    #[fail(display = "No SRTP cipher suite negotiated yet")]
+    SrtpCipherSuiteNotNegotiated,
 
     // WebRTC / C++ session description error codes
     #[fail(
@@ -97,6 +132,12 @@ pub enum RingRtcError {
     SetSessionDescriptionObserverResult(String),
     #[fail(display = "AddIceCandidate failure")]
     AddIceCandidate,
+    #[fail(display = "Unable to insert DTMF tones")]
+    InsertDtmf,
+    #[fail(display = "Invalid DTMF tones: {}", _0)]
+    InvalidDtmfTones(String),
+    #[fail(display = "Invalid peer ID: {}", _0)]
+    InvalidPeerId(String),
 
     // WebRTC / C++ offer / answer error codes
     #[fail(display = "Unable to convert offer or answer to SDP")]
@@ -109,10 +150,14 @@ pub enum RingRtcError {
     MungeSdp,
     #[fail(display = "Unknown signaled protocol version")]
     UnknownSignaledProtocolVersion,
+    #[fail(display = "Invalid serialized signaling message")]
+    InvalidSerializedMessage,
 
     // DataChannel error codes
     #[fail(display = "Unable to send data channel message")]
     DataChannelSend,
+    #[fail(display = "Data message too large: {} bytes, max: {}", _0, _1)]
+    DataMessageTooLarge(usize, usize),
     #[fail(display = "Data channel protocol error: {}", _0)]
     DataChannelProtocol(String),
     #[fail(display = "Unable to send RTP data")]
@@ -131,10 +176,14 @@ pub enum RingRtcError {
     SfuClientReceivedUnexpectedResponseStatusCode(u16),
     #[fail(display = "SfuClient request failed")]
     SfuClientRequestFailed,
+    #[fail(display = "SfuClient join rejected with status code {}", _0)]
+    SfuClientJoinRejected(u16, Option<String>),
     #[fail(display = "SfuClient asked to make a request with no auth token")]
     SfuClientHasNotAuthToken,
     #[fail(display = "The maximum number of participants has been reached")]
     MaxParticipantsReached,
+    #[fail(display = "Client identifier can't be used as an HTTP header value")]
+    InvalidClientIdentifier,
 
     // Frame encryption error codes
     #[fail(display = "Frame Counter too big")]
@@ -149,4 +198,14 @@ pub enum RingRtcError {
     SrtpKeyNegotiationFailure,
     #[fail(display = "Buffer too small")]
     BufferTooSmall,
+    #[fail(display = "Hex string has an odd length: {}", _0)]
+    OddLengthHexString(usize),
+    #[fail(display = "Hex string has invalid character {:?} at index {}", _0, _1)]
+    InvalidHexCharacter(char, usize),
+    #[fail(display = "Task queue runtime needs at least one worker thread")]
+    NoWorkerThreads,
+    #[fail(display = "Can't wait on a task queue runtime from one of its own threads")]
+    WaitOnOwnTaskQueue,
+    #[fail(display = "Timed out waiting for a task queue runtime task")]
+    TaskQueueTimeout,
 }
@@ -11,13 +11,19 @@ use std::{fmt, ptr, slice, str};
 use libc::size_t;
 
 use crate::ios::call_manager;
-use crate::ios::call_manager::IOSCallManager;
+use crate::ios::call_manager::IOSCallManagerHandle;
 use crate::ios::logging::IOSLogger;
 
 use crate::common::{CallMediaType, DeviceId, FeatureLevel, HttpResponse};
-use crate::core::bandwidth_mode::BandwidthMode;
+use crate::core::bandwidth_mode::{BandwidthMode, EffectiveBandwidthMode};
 use crate::core::group_call;
 use crate::core::signaling;
+use crate::webrtc::media::LayerConfig;
+use crate::webrtc::stats_observer::{
+    AudioPlayoutStatistics,
+    CallStatistics,
+    MediaQualityStatistics,
+};
 
 ///
 #[repr(C)]
@@ -96,6 +102,15 @@ pub struct AppOptionalUInt32 {
     pub valid: bool,
 }
 
+/// Structure for passing optional u64 values to/from Swift.
+#[repr(C)]
+#[derive(Debug)]
+#[allow(non_snake_case)]
+pub struct AppOptionalUInt64 {
+    pub value: u64,
+    pub valid: bool,
+}
+
 /// Structure for passing optional bool values to/from Swift.
 #[repr(C)]
 #[derive(Debug)]
@@ -168,9 +183,11 @@ impl Drop for AppConnectionInterface {
 #[derive(Clone, Debug)]
 #[allow(non_snake_case)]
 pub struct AppCallContext {
-    pub object:  *mut c_void,
+    pub object:               *mut c_void,
     /// Swift object clean up method.
-    pub destroy: extern "C" fn(object: *mut c_void),
+    pub destroy:              extern "C" fn(object: *mut c_void),
+    /// Whether connections for the call get the RTP data channel.
+    pub enableRtpDataChannel: bool,
 }
 
 // Add an empty Send trait to allow transfer of ownership between threads.
@@ -271,6 +288,38 @@ pub struct AppUuidArray {
     pub count: size_t,
 }
 
+#[repr(C)]
+#[derive(Debug)]
+#[allow(non_snake_case)]
+pub struct AppJoinedDevice {
+    pub userId:  AppByteSlice,
+    pub demuxId: group_call::DemuxId,
+}
+
+#[repr(C)]
+#[derive(Debug)]
+#[allow(non_snake_case)]
+pub struct AppJoinedDeviceArray {
+    pub devices: *const AppJoinedDevice,
+    pub count:   size_t,
+}
+
+#[repr(C)]
+#[derive(Debug)]
+#[allow(non_snake_case)]
+pub struct AppAudioLevel {
+    pub demuxId: group_call::DemuxId,
+    pub level:   u8, // 0 to 100
+}
+
+#[repr(C)]
+#[derive(Debug)]
+#[allow(non_snake_case)]
+pub struct AppAudioLevelArray {
+    pub levels: *const AppAudioLevel,
+    pub count:  size_t,
+}
+
 #[repr(C)]
 #[derive(Debug)]
 #[allow(non_snake_case)]
@@ -279,6 +328,7 @@ pub struct AppVideoRequest {
     pub width:     u16,
     pub height:    u16,
     pub framerate: AppOptionalUInt16,
+    pub pinned:    bool,
 }
 
 #[repr(C)]
@@ -289,6 +339,70 @@ pub struct AppVideoRequestArray {
     pub count:       size_t,
 }
 
+/// Quality of one kind of outgoing media, as reported back by the
+/// remote side, and the NACKs, retransmissions, FIRs and PLIs so far.
+/// Not valid if there is no such media.
+#[repr(C)]
+#[derive(Debug, Default, PartialEq)]
+#[allow(non_snake_case)]
+pub struct AppMediaQualityStatistics {
+    pub roundTripTimeMs:      f64,
+    pub jitterMs:             f64,
+    pub packetsLost:          i32,
+    pub nackCount:            u32,
+    pub retransmittedPackets: u64,
+    pub firCount:             u32,
+    pub pliCount:             u32,
+    pub valid:                bool,
+}
+
+impl From<Option<MediaQualityStatistics>> for AppMediaQualityStatistics {
+    fn from(item: Option<MediaQualityStatistics>) -> Self {
+        match item {
+            Some(media) => Self {
+                roundTripTimeMs:      media.round_trip_time_ms,
+                jitterMs:             media.jitter_ms,
+                packetsLost:          media.packets_lost,
+                nackCount:            media.nack_count,
+                retransmittedPackets: media.retransmitted_packets,
+                firCount:             media.fir_count,
+                pliCount:             media.pli_count,
+                valid:                true,
+            },
+            None => Self::default(),
+        }
+    }
+}
+
+/// Snapshot of the quality of a 1:1 call, for passing to Swift.
+#[repr(C)]
+#[derive(Debug)]
+#[allow(non_snake_case)]
+pub struct AppCallStatistics {
+    pub audio:                       AppMediaQualityStatistics,
+    pub video:                       AppMediaQualityStatistics,
+    pub selectedPairRoundTripTimeMs: AppOptionalUInt32,
+    pub sendBitrateBps:              AppOptionalUInt64,
+}
+
+impl From<&CallStatistics> for AppCallStatistics {
+    fn from(item: &CallStatistics) -> Self {
+        let rtt_ms = item.selected_pair_rtt_ms();
+        Self {
+            audio:                       item.audio.into(),
+            video:                       item.video.into(),
+            selectedPairRoundTripTimeMs: AppOptionalUInt32 {
+                value: rtt_ms.unwrap_or_default(),
+                valid: rtt_ms.is_some(),
+            },
+            sendBitrateBps:              AppOptionalUInt64 {
+                value: item.send_bitrate_bps.unwrap_or_default(),
+                valid: item.send_bitrate_bps.is_some(),
+            },
+        }
+    }
+}
+
 #[repr(C)]
 #[derive(Debug)]
 #[allow(non_snake_case)]
@@ -356,6 +470,26 @@ pub struct AppInterface {
         broadcast: bool,
     ),
     ///
+    pub onSendTransferRequest: extern "C" fn(
+        object: *mut c_void,
+        callId: u64,
+        remote: *const c_void,
+        destinationDeviceId: u32,
+        broadcast: bool,
+        target: *const c_void,
+    ),
+    ///
+    pub onSendTransferAccepted: extern "C" fn(
+        object: *mut c_void,
+        callId: u64,
+        remote: *const c_void,
+        destinationDeviceId: u32,
+        broadcast: bool,
+    ),
+    ///
+    pub handleTransferRequest:
+        extern "C" fn(object: *mut c_void, remote: *const c_void, target: *const c_void),
+    ///
     pub sendCallMessage:
         extern "C" fn(object: *mut c_void, recipientUuid: AppByteSlice, message: AppByteSlice),
     ///
@@ -392,6 +526,11 @@ pub struct AppInterface {
         extern "C" fn(object: *mut c_void, remote1: *const c_void, remote2: *const c_void) -> bool,
     ///
     pub onCallConcluded:              extern "C" fn(object: *mut c_void, remote: *const c_void),
+    /// Application-defined payload received from the remote peer.
+    pub onDataMessage:
+        extern "C" fn(object: *mut c_void, remote: *const c_void, payload: AppByteSlice),
+    ///
+    pub onAudioLoopbackLevel:         extern "C" fn(object: *mut c_void, level: u16),
 
     // Group Calls
     ///
@@ -399,12 +538,15 @@ pub struct AppInterface {
         object: *mut c_void,
         requestId: u32,
         joinedMembers: AppUuidArray,
+        joinedDevices: AppJoinedDeviceArray,
         creator: AppByteSlice,
         eraId: AppByteSlice,
         maxDevices: AppOptionalUInt32,
         deviceCount: u32,
     ),
     ///
+    pub handlePeekTimedOut: extern "C" fn(object: *mut c_void, requestId: u32),
+    ///
     pub requestMembershipProof: extern "C" fn(object: *mut c_void, clientId: group_call::ClientId),
     ///
     pub requestGroupMembers: extern "C" fn(object: *mut c_void, clientId: group_call::ClientId),
@@ -412,8 +554,12 @@ pub struct AppInterface {
     pub handleConnectionStateChanged:
         extern "C" fn(object: *mut c_void, clientId: group_call::ClientId, connectionState: i32),
     ///
-    pub handleJoinStateChanged:
-        extern "C" fn(object: *mut c_void, clientId: group_call::ClientId, joinState: i32),
+    pub handleJoinStateChanged: extern "C" fn(
+        object: *mut c_void,
+        clientId: group_call::ClientId,
+        joinState: i32,
+        outgoingAudioMuted: AppOptionalBool,
+    ),
     ///
     pub handleRemoteDevicesChanged: extern "C" fn(
         object: *mut c_void,
@@ -432,12 +578,60 @@ pub struct AppInterface {
         object: *mut c_void,
         clientId: group_call::ClientId,
         joinedMembers: AppUuidArray,
+        joinedDevices: AppJoinedDeviceArray,
         creator: AppByteSlice,
         eraId: AppByteSlice,
         maxDevices: AppOptionalUInt32,
         deviceCount: u32,
     ),
     ///
+    pub handleEraChanged: extern "C" fn(
+        object: *mut c_void,
+        clientId: group_call::ClientId,
+        oldEraId: AppByteSlice,
+        newEraId: AppByteSlice,
+    ),
+    ///
+    pub handleParticipantQualityChanged: extern "C" fn(
+        object: *mut c_void,
+        clientId: group_call::ClientId,
+        remoteDemuxId: group_call::DemuxId,
+        quality: u8,
+    ),
+    ///
+    pub handleAudioLevels: extern "C" fn(
+        object: *mut c_void,
+        clientId: group_call::ClientId,
+        audioLevels: AppAudioLevelArray,
+    ),
+    ///
+    pub handleMediaKeysReceived: extern "C" fn(
+        object: *mut c_void,
+        clientId: group_call::ClientId,
+        remoteDemuxId: group_call::DemuxId,
+    ),
+    /// The remote device started sending interpreter audio, which stays
+    /// silent until ringrtcSetRemoteInterpreterAudioEnabled is called.
+    pub handleIncomingInterpreterAudioTrack: extern "C" fn(
+        object: *mut c_void,
+        clientId: group_call::ClientId,
+        remoteDemuxId: group_call::DemuxId,
+    ),
+    /// The type is 0 for host, 1 for srflx, 2 for prflx and 3 for relay.
+    pub handleCandidatePairChanged: extern "C" fn(
+        object: *mut c_void,
+        clientId: group_call::ClientId,
+        localDemuxId: group_call::DemuxId,
+        candidatePairType: i32,
+    ),
+    ///
+    pub handleJoinRejected: extern "C" fn(
+        object: *mut c_void,
+        clientId: group_call::ClientId,
+        httpStatus: u16,
+        message: AppByteSlice,
+    ),
+    ///
     pub handleEnded:
         extern "C" fn(object: *mut c_void, clientId: group_call::ClientId, reason: i32),
 }
@@ -487,18 +681,84 @@ pub extern "C" fn ringrtcInitialize(logObject: IOSLogger) -> *mut c_void {
     }
 }
 
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcSetLogBufferLimits(maxLines: usize, maxBytes: usize) {
+    call_manager::set_log_buffer_limits(maxLines, maxBytes);
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcGetRecentLogs(
+    logs: *mut u8,
+    logsCapacity: usize,
+    logsLen: *mut usize,
+) -> bool {
+    if logs.is_null() || logsLen.is_null() {
+        error!("ringrtcGetRecentLogs(): null logs buffer");
+        return false;
+    }
+
+    // Keep the newest logs if they don't all fit.
+    let recent_logs = call_manager::recent_logs();
+    let mut start = recent_logs.len().saturating_sub(logsCapacity);
+    while !recent_logs.is_char_boundary(start) {
+        start += 1;
+    }
+    let recent_logs = &recent_logs.as_bytes()[start..];
+
+    let logs = unsafe { slice::from_raw_parts_mut(logs, logsCapacity) };
+    logs[..recent_logs.len()].copy_from_slice(recent_logs);
+    unsafe { *logsLen = recent_logs.len() };
+    true
+}
+
 #[no_mangle]
 #[allow(non_snake_case)]
 pub extern "C" fn ringrtcCreate(
     appCallManager: *mut c_void,
     appInterface: AppInterface,
+    maxPendingIceCandidates: u32,
 ) -> *mut c_void {
-    match call_manager::create(appCallManager, appInterface) {
+    match call_manager::create(
+        appCallManager,
+        appInterface,
+        maxPendingIceCandidates as usize,
+    ) {
         Ok(v) => v,
         Err(_e) => ptr::null_mut(),
     }
 }
 
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcSetClientIdentifier(
+    callManager: *mut c_void,
+    clientIdentifier: AppByteSlice,
+) -> *mut c_void {
+    let client_identifier = match string_from_app_slice(&clientIdentifier) {
+        Some(v) => v,
+        None => {
+            error!("Invalid clientIdentifier");
+            return ptr::null_mut();
+        }
+    };
+
+    match call_manager::set_client_identifier(
+        callManager as *mut IOSCallManagerHandle,
+        client_identifier,
+    ) {
+        Ok(_v) => {
+            // Return the object reference back as indication of success.
+            callManager
+        }
+        Err(e) => {
+            error!("{}", e);
+            ptr::null_mut()
+        }
+    }
+}
+
 #[no_mangle]
 #[allow(non_snake_case)]
 pub extern "C" fn ringrtcCall(
@@ -508,7 +768,7 @@ pub extern "C" fn ringrtcCall(
     appLocalDevice: u32,
 ) -> *mut c_void {
     match call_manager::call(
-        callManager as *mut IOSCallManager,
+        callManager as *mut IOSCallManagerHandle,
         appRemote,
         CallMediaType::from_i32(callMediaType),
         appLocalDevice as DeviceId,
@@ -530,7 +790,7 @@ pub extern "C" fn ringrtcProceed(
     bandwidthMode: i32,
 ) -> *mut c_void {
     match call_manager::proceed(
-        callManager as *mut IOSCallManager,
+        callManager as *mut IOSCallManagerHandle,
         callId,
         appCallContext,
         BandwidthMode::from_i32(bandwidthMode),
@@ -546,7 +806,7 @@ pub extern "C" fn ringrtcProceed(
 #[no_mangle]
 #[allow(non_snake_case)]
 pub extern "C" fn ringrtcMessageSent(callManager: *mut c_void, callId: u64) -> *mut c_void {
-    match call_manager::message_sent(callManager as *mut IOSCallManager, callId) {
+    match call_manager::message_sent(callManager as *mut IOSCallManagerHandle, callId) {
         Ok(_v) => {
             // Return the object reference back as indication of success.
             callManager
@@ -558,7 +818,7 @@ pub extern "C" fn ringrtcMessageSent(callManager: *mut c_void, callId: u64) -> *
 #[no_mangle]
 #[allow(non_snake_case)]
 pub extern "C" fn ringrtcMessageSendFailure(callManager: *mut c_void, callId: u64) -> *mut c_void {
-    match call_manager::message_send_failure(callManager as *mut IOSCallManager, callId) {
+    match call_manager::message_send_failure(callManager as *mut IOSCallManagerHandle, callId) {
         Ok(_v) => {
             // Return the object reference back as indication of success.
             callManager
@@ -570,7 +830,19 @@ pub extern "C" fn ringrtcMessageSendFailure(callManager: *mut c_void, callId: u6
 #[no_mangle]
 #[allow(non_snake_case)]
 pub extern "C" fn ringrtcHangup(callManager: *mut c_void) -> *mut c_void {
-    match call_manager::hangup(callManager as *mut IOSCallManager) {
+    match call_manager::hangup(callManager as *mut IOSCallManagerHandle) {
+        Ok(_v) => {
+            // Return the object reference back as indication of success.
+            callManager
+        }
+        Err(_e) => ptr::null_mut(),
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcHangupAsAway(callManager: *mut c_void) -> *mut c_void {
+    match call_manager::hangup_as_away(callManager as *mut IOSCallManagerHandle) {
         Ok(_v) => {
             // Return the object reference back as indication of success.
             callManager
@@ -597,7 +869,7 @@ pub extern "C" fn ringrtcReceivedAnswer(
     };
 
     match call_manager::received_answer(
-        callManager as *mut IOSCallManager,
+        callManager as *mut IOSCallManagerHandle,
         callId,
         senderDeviceId as DeviceId,
         byte_vec_from_app_slice(&opaque),
@@ -639,7 +911,7 @@ pub extern "C" fn ringrtcReceivedOffer(
     };
 
     match call_manager::received_offer(
-        callManager as *mut IOSCallManager,
+        callManager as *mut IOSCallManagerHandle,
         callId,
         remotePeer,
         senderDeviceId as DeviceId,
@@ -692,7 +964,7 @@ pub extern "C" fn ringrtcReceivedIceCandidates(
     }
 
     match call_manager::received_ice(
-        callManager as *mut IOSCallManager,
+        callManager as *mut IOSCallManagerHandle,
         callId,
         signaling::ReceivedIce {
             ice:              signaling::Ice {
@@ -719,7 +991,7 @@ pub extern "C" fn ringrtcReceivedHangup(
     deviceId: u32,
 ) -> *mut c_void {
     match call_manager::received_hangup(
-        callManager as *mut IOSCallManager,
+        callManager as *mut IOSCallManagerHandle,
         callId,
         remoteDevice as DeviceId,
         signaling::HangupType::from_i32(hangupType).unwrap_or(signaling::HangupType::Normal),
@@ -741,7 +1013,97 @@ pub extern "C" fn ringrtcReceivedBusy(
     remoteDevice: u32,
 ) -> *mut c_void {
     match call_manager::received_busy(
-        callManager as *mut IOSCallManager,
+        callManager as *mut IOSCallManagerHandle,
+        callId,
+        remoteDevice as DeviceId,
+    ) {
+        Ok(_v) => {
+            // Return the object reference back as indication of success.
+            callManager
+        }
+        Err(_e) => ptr::null_mut(),
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcSendBusy(
+    callManager: *mut c_void,
+    callId: u64,
+    appRemote: *const c_void,
+) -> *mut c_void {
+    match call_manager::send_busy(callManager as *mut IOSCallManagerHandle, callId, appRemote) {
+        Ok(_v) => {
+            // Return the object reference back as indication of success.
+            callManager
+        }
+        Err(_e) => ptr::null_mut(),
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcTransferCall(
+    callManager: *mut c_void,
+    callId: u64,
+    appTarget: *const c_void,
+) -> *mut c_void {
+    match call_manager::transfer_call(
+        callManager as *mut IOSCallManagerHandle,
+        callId,
+        appTarget,
+    ) {
+        Ok(_v) => {
+            // Return the object reference back as indication of success.
+            callManager
+        }
+        Err(_e) => ptr::null_mut(),
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcReceivedTransferRequest(
+    callManager: *mut c_void,
+    callId: u64,
+    remoteDevice: u32,
+    appTarget: *const c_void,
+) -> *mut c_void {
+    match call_manager::received_transfer_request(
+        callManager as *mut IOSCallManagerHandle,
+        callId,
+        remoteDevice as DeviceId,
+        appTarget,
+    ) {
+        Ok(_v) => {
+            // Return the object reference back as indication of success.
+            callManager
+        }
+        Err(_e) => ptr::null_mut(),
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcAcceptTransfer(callManager: *mut c_void, callId: u64) -> *mut c_void {
+    match call_manager::accept_transfer(callManager as *mut IOSCallManagerHandle, callId) {
+        Ok(_v) => {
+            // Return the object reference back as indication of success.
+            callManager
+        }
+        Err(_e) => ptr::null_mut(),
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcReceivedTransferAccepted(
+    callManager: *mut c_void,
+    callId: u64,
+    remoteDevice: u32,
+) -> *mut c_void {
+    match call_manager::received_transfer_accepted(
+        callManager as *mut IOSCallManagerHandle,
         callId,
         remoteDevice as DeviceId,
     ) {
@@ -778,7 +1140,7 @@ pub extern "C" fn ringrtcReceivedCallMessage(
     }
 
     match call_manager::received_call_message(
-        callManager as *mut IOSCallManager,
+        callManager as *mut IOSCallManagerHandle,
         sender_uuid.unwrap(),
         senderDeviceId as DeviceId,
         localDeviceId as DeviceId,
@@ -812,7 +1174,7 @@ pub extern "C" fn ringrtcReceivedHttpResponse(
     };
 
     let result = call_manager::received_http_response(
-        callManager as *mut IOSCallManager,
+        callManager as *mut IOSCallManagerHandle,
         requestId,
         Some(response),
     );
@@ -826,8 +1188,11 @@ pub extern "C" fn ringrtcReceivedHttpResponse(
 pub extern "C" fn ringrtcHttpRequestFailed(callManager: *mut c_void, requestId: u32) {
     info!("ringrtcHttpRequestFailed():");
 
-    let result =
-        call_manager::received_http_response(callManager as *mut IOSCallManager, requestId, None);
+    let result = call_manager::received_http_response(
+        callManager as *mut IOSCallManagerHandle,
+        requestId,
+        None,
+    );
     if result.is_err() {
         error!("{:?}", result.err());
     }
@@ -836,7 +1201,7 @@ pub extern "C" fn ringrtcHttpRequestFailed(callManager: *mut c_void, requestId:
 #[no_mangle]
 #[allow(non_snake_case)]
 pub extern "C" fn ringrtcAccept(callManager: *mut c_void, callId: u64) -> *mut c_void {
-    match call_manager::accept_call(callManager as *mut IOSCallManager, callId) {
+    match call_manager::accept_call(callManager as *mut IOSCallManagerHandle, callId) {
         Ok(_v) => {
             // Return the object reference back as indication of success.
             callManager
@@ -847,26 +1212,32 @@ pub extern "C" fn ringrtcAccept(callManager: *mut c_void, callId: u64) -> *mut c
 
 #[no_mangle]
 #[allow(non_snake_case)]
-pub extern "C" fn ringrtcGetActiveConnection(callManager: *mut c_void) -> *mut c_void {
-    match call_manager::get_active_connection(callManager as *mut IOSCallManager) {
-        Ok(v) => v,
+pub extern "C" fn ringrtcNotifyRinging(callManager: *mut c_void, callId: u64) -> *mut c_void {
+    match call_manager::notify_ringing(callManager as *mut IOSCallManagerHandle, callId) {
+        Ok(_v) => {
+            // Return the object reference back as indication of success.
+            callManager
+        }
         Err(_e) => ptr::null_mut(),
     }
 }
 
 #[no_mangle]
 #[allow(non_snake_case)]
-pub extern "C" fn ringrtcGetActiveCallContext(callManager: *mut c_void) -> *mut c_void {
-    match call_manager::get_active_call_context(callManager as *mut IOSCallManager) {
-        Ok(v) => v,
+pub extern "C" fn ringrtcHoldCall(callManager: *mut c_void, callId: u64) -> *mut c_void {
+    match call_manager::hold_call(callManager as *mut IOSCallManagerHandle, callId) {
+        Ok(_v) => {
+            // Return the object reference back as indication of success.
+            callManager
+        }
         Err(_e) => ptr::null_mut(),
     }
 }
 
 #[no_mangle]
 #[allow(non_snake_case)]
-pub extern "C" fn ringrtcSetVideoEnable(callManager: *mut c_void, enable: bool) -> *mut c_void {
-    match call_manager::set_video_enable(callManager as *mut IOSCallManager, enable) {
+pub extern "C" fn ringrtcResumeCall(callManager: *mut c_void, callId: u64) -> *mut c_void {
+    match call_manager::resume_call(callManager as *mut IOSCallManagerHandle, callId) {
         Ok(_v) => {
             // Return the object reference back as indication of success.
             callManager
@@ -877,32 +1248,26 @@ pub extern "C" fn ringrtcSetVideoEnable(callManager: *mut c_void, enable: bool)
 
 #[no_mangle]
 #[allow(non_snake_case)]
-pub extern "C" fn ringrtcUpdateBandwidthMode(callManager: *mut c_void, bandwidthMode: i32) {
-    let result = call_manager::update_bandwidth_mode(
-        callManager as *mut IOSCallManager,
-        BandwidthMode::from_i32(bandwidthMode),
-    );
-    if result.is_err() {
-        error!("ringrtcUpdateBandwidthMode(): {:?}", result.err());
+pub extern "C" fn ringrtcGetActiveConnection(callManager: *mut c_void) -> *mut c_void {
+    match call_manager::get_active_connection(callManager as *mut IOSCallManagerHandle) {
+        Ok(v) => v,
+        Err(_e) => ptr::null_mut(),
     }
 }
 
 #[no_mangle]
 #[allow(non_snake_case)]
-pub extern "C" fn ringrtcDrop(callManager: *mut c_void, callId: u64) -> *mut c_void {
-    match call_manager::drop_call(callManager as *mut IOSCallManager, callId) {
-        Ok(_v) => {
-            // Return the object reference back as indication of success.
-            callManager
-        }
+pub extern "C" fn ringrtcGetActiveCallContext(callManager: *mut c_void) -> *mut c_void {
+    match call_manager::get_active_call_context(callManager as *mut IOSCallManagerHandle) {
+        Ok(v) => v,
         Err(_e) => ptr::null_mut(),
     }
 }
 
 #[no_mangle]
 #[allow(non_snake_case)]
-pub extern "C" fn ringrtcReset(callManager: *mut c_void) -> *mut c_void {
-    match call_manager::reset(callManager as *mut IOSCallManager) {
+pub extern "C" fn ringrtcSetAudioEnable(callManager: *mut c_void, enable: bool) -> *mut c_void {
+    match call_manager::set_audio_enable(callManager as *mut IOSCallManagerHandle, enable) {
         Ok(_v) => {
             // Return the object reference back as indication of success.
             callManager
@@ -913,8 +1278,8 @@ pub extern "C" fn ringrtcReset(callManager: *mut c_void) -> *mut c_void {
 
 #[no_mangle]
 #[allow(non_snake_case)]
-pub extern "C" fn ringrtcClose(callManager: *mut c_void) -> *mut c_void {
-    match call_manager::close(callManager as *mut IOSCallManager) {
+pub extern "C" fn ringrtcSetVideoEnable(callManager: *mut c_void, enable: bool) -> *mut c_void {
+    match call_manager::set_video_enable(callManager as *mut IOSCallManagerHandle, enable) {
         Ok(_v) => {
             // Return the object reference back as indication of success.
             callManager
@@ -923,19 +1288,444 @@ pub extern "C" fn ringrtcClose(callManager: *mut c_void) -> *mut c_void {
     }
 }
 
-// Group Calls
-
 #[no_mangle]
 #[allow(non_snake_case)]
-pub extern "C" fn ringrtcPeekGroupCall(
+pub extern "C" fn ringrtcSendDataMessage(
     callManager: *mut c_void,
-    requestId: u32,
-    sfuUrl: AppByteSlice,
-    proof: AppByteSlice,
-    appGroupMemberInfoArray: *const AppGroupMemberInfoArray,
-) {
-    info!("ringrtcPeekGroupCall():");
-
+    callId: u64,
+    payload: AppByteSlice,
+) -> *mut c_void {
+    let payload = match byte_vec_from_app_slice(&payload) {
+        Some(v) => v,
+        None => {
+            error!("Invalid payload");
+            return ptr::null_mut();
+        }
+    };
+
+    match call_manager::send_data_message(callManager as *mut IOSCallManagerHandle, callId, payload)
+    {
+        Ok(_v) => {
+            // Return the object reference back as indication of success.
+            callManager
+        }
+        Err(e) => {
+            error!("{}", e);
+            ptr::null_mut()
+        }
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcSendDtmf(
+    callManager: *mut c_void,
+    callId: u64,
+    tones: AppByteSlice,
+    durationMs: u32,
+    gapMs: u32,
+) -> *mut c_void {
+    let tones = match string_from_app_slice(&tones) {
+        Some(v) => v,
+        None => {
+            error!("Invalid tones");
+            return ptr::null_mut();
+        }
+    };
+
+    match call_manager::send_dtmf(
+        callManager as *mut IOSCallManagerHandle,
+        callId,
+        tones,
+        durationMs,
+        gapMs,
+    ) {
+        Ok(_v) => {
+            // Return the object reference back as indication of success.
+            callManager
+        }
+        Err(e) => {
+            error!("{}", e);
+            ptr::null_mut()
+        }
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcSetAudioJitterBuffer(
+    callManager: *mut c_void,
+    callId: u64,
+    targetMs: u32,
+) -> *mut c_void {
+    match call_manager::set_audio_jitter_buffer(
+        callManager as *mut IOSCallManagerHandle,
+        callId,
+        targetMs,
+    ) {
+        Ok(_v) => {
+            // Return the object reference back as indication of success.
+            callManager
+        }
+        Err(e) => {
+            error!("{}", e);
+            ptr::null_mut()
+        }
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcInjectReceiveLatency(
+    callManager: *mut c_void,
+    callId: u64,
+    extraMs: u32,
+) -> *mut c_void {
+    match call_manager::inject_receive_latency(
+        callManager as *mut IOSCallManagerHandle,
+        callId,
+        extraMs,
+    ) {
+        Ok(_v) => {
+            // Return the object reference back as indication of success.
+            callManager
+        }
+        Err(e) => {
+            error!("{}", e);
+            ptr::null_mut()
+        }
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcSetAudioFecLevel(
+    callManager: *mut c_void,
+    callId: u64,
+    fecLevel: u8,
+) -> *mut c_void {
+    match call_manager::set_audio_fec_level(
+        callManager as *mut IOSCallManagerHandle,
+        callId,
+        fecLevel,
+    ) {
+        Ok(_v) => {
+            // Return the object reference back as indication of success.
+            callManager
+        }
+        Err(e) => {
+            error!("{}", e);
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Writes the round trip time of the call's selected ICE candidate pair
+/// to `rttMs`. Returns null if the call isn't connected yet.
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcGetSelectedPairRtt(
+    callManager: *mut c_void,
+    callId: u64,
+    rttMs: *mut u32,
+) -> *mut c_void {
+    if rttMs.is_null() {
+        error!("ringrtcGetSelectedPairRtt(): rttMs is null");
+        return ptr::null_mut();
+    }
+
+    match call_manager::selected_pair_rtt_ms(callManager as *mut IOSCallManagerHandle, callId) {
+        Ok(Some(v)) => {
+            unsafe { *rttMs = v };
+            // Return the object reference back as indication of success.
+            callManager
+        }
+        Ok(None) => ptr::null_mut(),
+        Err(e) => {
+            error!("{}", e);
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Writes the playout health of the audio received in the call to `stats`.
+/// Returns null if no audio is being received yet.
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcGetAudioPlayoutStatistics(
+    callManager: *mut c_void,
+    callId: u64,
+    stats: *mut AudioPlayoutStatistics,
+) -> *mut c_void {
+    if stats.is_null() {
+        error!("ringrtcGetAudioPlayoutStatistics(): stats is null");
+        return ptr::null_mut();
+    }
+
+    match call_manager::audio_playout_statistics(callManager as *mut IOSCallManagerHandle, callId) {
+        Ok(Some(v)) => {
+            unsafe { *stats = v };
+            // Return the object reference back as indication of success.
+            callManager
+        }
+        Ok(None) => ptr::null_mut(),
+        Err(e) => {
+            error!("{}", e);
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Copies a snapshot of the quality of the active call into `stats`.
+/// Returns null if no call is active.
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcGetCallStatistics(
+    callManager: *mut c_void,
+    stats: *mut AppCallStatistics,
+) -> *mut c_void {
+    if stats.is_null() {
+        error!("ringrtcGetCallStatistics(): stats is null");
+        return ptr::null_mut();
+    }
+
+    match call_manager::get_call_statistics(callManager as *mut IOSCallManagerHandle) {
+        Ok(v) => {
+            unsafe { *stats = v };
+            // Return the object reference back as indication of success.
+            callManager
+        }
+        Err(e) => {
+            error!("{}", e);
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Copies the security fingerprint of the call, as ASCII, into `fingerprint`,
+/// which must have room for at least SECURITY_FINGERPRINT_LEN (35) bytes.
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcGetSecurityFingerprint(
+    callManager: *mut c_void,
+    callId: u64,
+    fingerprint: *mut u8,
+    fingerprintLen: usize,
+) -> *mut c_void {
+    if fingerprint.is_null() {
+        error!("ringrtcGetSecurityFingerprint(): null fingerprint buffer");
+        return ptr::null_mut();
+    }
+
+    match call_manager::security_fingerprint(callManager as *mut IOSCallManagerHandle, callId) {
+        Ok(v) if v.len() > fingerprintLen => {
+            error!(
+                "ringrtcGetSecurityFingerprint(): fingerprint buffer too small: {} < {}",
+                fingerprintLen,
+                v.len()
+            );
+            ptr::null_mut()
+        }
+        Ok(v) => {
+            let fingerprint = unsafe { slice::from_raw_parts_mut(fingerprint, fingerprintLen) };
+            fingerprint[..v.len()].copy_from_slice(v.as_bytes());
+            // Return the object reference back as indication of success.
+            callManager
+        }
+        Err(e) => {
+            error!("{}", e);
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Copies the name of the SRTP cipher suite negotiated for the call, as
+/// ASCII, into `suite`, and its length into `suiteLen`.  `suiteCapacity`
+/// of 32 bytes is enough for any suite.
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcGetSrtpCipherSuite(
+    callManager: *mut c_void,
+    callId: u64,
+    suite: *mut u8,
+    suiteCapacity: usize,
+    suiteLen: *mut usize,
+) -> *mut c_void {
+    if suite.is_null() || suiteLen.is_null() {
+        error!("ringrtcGetSrtpCipherSuite(): null suite buffer");
+        return ptr::null_mut();
+    }
+
+    match call_manager::srtp_cipher_suite(callManager as *mut IOSCallManagerHandle, callId) {
+        Ok(v) if v.len() > suiteCapacity => {
+            error!(
+                "ringrtcGetSrtpCipherSuite(): suite buffer too small: {} < {}",
+                suiteCapacity,
+                v.len()
+            );
+            ptr::null_mut()
+        }
+        Ok(v) => {
+            let suite = unsafe { slice::from_raw_parts_mut(suite, suiteCapacity) };
+            suite[..v.len()].copy_from_slice(v.as_bytes());
+            unsafe { *suiteLen = v.len() };
+            // Return the object reference back as indication of success.
+            callManager
+        }
+        Err(e) => {
+            error!("{}", e);
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Sets `active` to whether the media of the call is frame encrypted.
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcIsFrameEncryptionActive(
+    callManager: *mut c_void,
+    callId: u64,
+    active: *mut bool,
+) -> *mut c_void {
+    if active.is_null() {
+        error!("ringrtcIsFrameEncryptionActive(): active is null");
+        return ptr::null_mut();
+    }
+
+    match call_manager::is_frame_encryption_active(callManager as *mut IOSCallManagerHandle, callId)
+    {
+        Ok(v) => {
+            unsafe { *active = v };
+            // Return the object reference back as indication of success.
+            callManager
+        }
+        Err(e) => {
+            error!("{}", e);
+            ptr::null_mut()
+        }
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcUpdateBandwidthMode(callManager: *mut c_void, bandwidthMode: i32) {
+    let result = call_manager::update_bandwidth_mode(
+        callManager as *mut IOSCallManagerHandle,
+        BandwidthMode::from_i32(bandwidthMode),
+    );
+    if result.is_err() {
+        error!("ringrtcUpdateBandwidthMode(): {:?}", result.err());
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcUpdateMaxBitrate(callManager: *mut c_void, maxBitrateBps: u32) {
+    let result =
+        call_manager::update_max_bitrate(callManager as *mut IOSCallManagerHandle, maxBitrateBps);
+    if result.is_err() {
+        error!("ringrtcUpdateMaxBitrate(): {:?}", result.err());
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcDrop(callManager: *mut c_void, callId: u64) -> *mut c_void {
+    match call_manager::drop_call(callManager as *mut IOSCallManagerHandle, callId) {
+        Ok(_v) => {
+            // Return the object reference back as indication of success.
+            callManager
+        }
+        Err(_e) => ptr::null_mut(),
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcReset(callManager: *mut c_void) -> *mut c_void {
+    match call_manager::reset(callManager as *mut IOSCallManagerHandle) {
+        Ok(_v) => {
+            // Return the object reference back as indication of success.
+            callManager
+        }
+        Err(_e) => ptr::null_mut(),
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcSuspendSignaling(callManager: *mut c_void) -> *mut c_void {
+    match call_manager::suspend_signaling(callManager as *mut IOSCallManagerHandle) {
+        Ok(_v) => {
+            // Return the object reference back as indication of success.
+            callManager
+        }
+        Err(_e) => ptr::null_mut(),
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcResumeSignaling(callManager: *mut c_void) -> *mut c_void {
+    match call_manager::resume_signaling(callManager as *mut IOSCallManagerHandle) {
+        Ok(_v) => {
+            // Return the object reference back as indication of success.
+            callManager
+        }
+        Err(_e) => ptr::null_mut(),
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcStartAudioLoopback(callManager: *mut c_void) -> *mut c_void {
+    match call_manager::start_audio_loopback(callManager as *mut IOSCallManagerHandle) {
+        Ok(_v) => {
+            // Return the object reference back as indication of success.
+            callManager
+        }
+        Err(_e) => ptr::null_mut(),
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcStopAudioLoopback(callManager: *mut c_void) -> *mut c_void {
+    match call_manager::stop_audio_loopback(callManager as *mut IOSCallManagerHandle) {
+        Ok(_v) => {
+            // Return the object reference back as indication of success.
+            callManager
+        }
+        Err(_e) => ptr::null_mut(),
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcClose(callManager: *mut c_void) -> *mut c_void {
+    match call_manager::close(callManager as *mut IOSCallManagerHandle) {
+        Ok(_v) => {
+            // Return the object reference back as indication of success.
+            callManager
+        }
+        Err(_e) => ptr::null_mut(),
+    }
+}
+
+// Group Calls
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcPeekGroupCall(
+    callManager: *mut c_void,
+    requestId: u32,
+    sfuUrl: AppByteSlice,
+    proof: AppByteSlice,
+    appGroupMemberInfoArray: *const AppGroupMemberInfoArray,
+    timeoutMillis: u32,
+) {
+    info!("ringrtcPeekGroupCall():");
+
     let sfu_url = string_from_app_slice(&sfuUrl);
     if sfu_url.is_none() {
         error!("Invalid sfuUrl");
@@ -974,11 +1764,12 @@ pub extern "C" fn ringrtcPeekGroupCall(
     }
 
     let result = call_manager::peek_group_call(
-        callManager as *mut IOSCallManager,
+        callManager as *mut IOSCallManagerHandle,
         requestId,
         sfu_url.unwrap(),
         proof.unwrap(),
         group_members,
+        timeoutMillis,
     );
     if result.is_err() {
         error!("{:?}", result.err());
@@ -1008,11 +1799,57 @@ pub extern "C" fn ringrtcCreateGroupCallClient(
     }
 
     match call_manager::create_group_call_client(
-        callManager as *mut IOSCallManager,
+        callManager as *mut IOSCallManagerHandle,
+        group_id.unwrap(),
+        sfu_url.unwrap(),
+        nativeAudioTrack,
+        nativeVideoTrack,
+        None,
+    ) {
+        Ok(client_id) => client_id,
+        Err(_e) => 0,
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcRejoinGroupCallClient(
+    callManager: *mut c_void,
+    groupId: AppByteSlice,
+    sfuUrl: AppByteSlice,
+    nativeAudioTrack: *const c_void,
+    nativeVideoTrack: *const c_void,
+    previousEraId: AppByteSlice,
+    previousDemuxId: group_call::DemuxId,
+) -> group_call::ClientId {
+    info!("ringrtcRejoinGroupCallClient():");
+
+    let group_id = byte_vec_from_app_slice(&groupId);
+    if group_id.is_none() {
+        error!("Invalid groupId");
+        return group_call::INVALID_CLIENT_ID;
+    }
+    let sfu_url = string_from_app_slice(&sfuUrl);
+    if sfu_url.is_none() {
+        error!("Invalid sfuUrl");
+        return group_call::INVALID_CLIENT_ID;
+    }
+    let previous_era_id = string_from_app_slice(&previousEraId);
+    if previous_era_id.is_none() {
+        error!("Invalid previousEraId");
+        return group_call::INVALID_CLIENT_ID;
+    }
+
+    match call_manager::create_group_call_client(
+        callManager as *mut IOSCallManagerHandle,
         group_id.unwrap(),
         sfu_url.unwrap(),
         nativeAudioTrack,
         nativeVideoTrack,
+        Some(group_call::PreviousSession {
+            era_id:   previous_era_id.unwrap(),
+            demux_id: previousDemuxId,
+        }),
     ) {
         Ok(client_id) => client_id,
         Err(_e) => 0,
@@ -1028,7 +1865,7 @@ pub extern "C" fn ringrtcDeleteGroupCallClient(
     info!("ringrtcDeleteGroupCallClient():");
 
     let result =
-        call_manager::delete_group_call_client(callManager as *mut IOSCallManager, clientId);
+        call_manager::delete_group_call_client(callManager as *mut IOSCallManagerHandle, clientId);
     if result.is_err() {
         error!("{:?}", result.err());
     }
@@ -1039,7 +1876,7 @@ pub extern "C" fn ringrtcDeleteGroupCallClient(
 pub extern "C" fn ringrtcConnect(callManager: *mut c_void, clientId: group_call::ClientId) {
     info!("ringrtcConnect():");
 
-    let result = call_manager::connect(callManager as *mut IOSCallManager, clientId);
+    let result = call_manager::connect(callManager as *mut IOSCallManagerHandle, clientId);
     if result.is_err() {
         error!("{:?}", result.err());
     }
@@ -1050,7 +1887,26 @@ pub extern "C" fn ringrtcConnect(callManager: *mut c_void, clientId: group_call:
 pub extern "C" fn ringrtcJoin(callManager: *mut c_void, clientId: group_call::ClientId) {
     info!("ringrtcJoin():");
 
-    let result = call_manager::join(callManager as *mut IOSCallManager, clientId);
+    let result = call_manager::join(callManager as *mut IOSCallManagerHandle, clientId);
+    if result.is_err() {
+        error!("{:?}", result.err());
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcJoinWithOptions(
+    callManager: *mut c_void,
+    clientId: group_call::ClientId,
+    startMuted: bool,
+) {
+    info!("ringrtcJoinWithOptions():");
+
+    let result = call_manager::join_with_options(
+        callManager as *mut IOSCallManagerHandle,
+        clientId,
+        startMuted,
+    );
     if result.is_err() {
         error!("{:?}", result.err());
     }
@@ -1061,7 +1917,7 @@ pub extern "C" fn ringrtcJoin(callManager: *mut c_void, clientId: group_call::Cl
 pub extern "C" fn ringrtcLeave(callManager: *mut c_void, clientId: group_call::ClientId) {
     info!("ringrtcLeave():");
 
-    let result = call_manager::leave(callManager as *mut IOSCallManager, clientId);
+    let result = call_manager::leave(callManager as *mut IOSCallManagerHandle, clientId);
     if result.is_err() {
         error!("{:?}", result.err());
     }
@@ -1072,7 +1928,7 @@ pub extern "C" fn ringrtcLeave(callManager: *mut c_void, clientId: group_call::C
 pub extern "C" fn ringrtcDisconnect(callManager: *mut c_void, clientId: group_call::ClientId) {
     info!("ringrtcDisconnect():");
 
-    let result = call_manager::disconnect(callManager as *mut IOSCallManager, clientId);
+    let result = call_manager::disconnect(callManager as *mut IOSCallManagerHandle, clientId);
     if result.is_err() {
         error!("{:?}", result.err());
     }
@@ -1087,8 +1943,11 @@ pub extern "C" fn ringrtcSetOutgoingAudioMuted(
 ) {
     info!("ringrtcSetOutgoingAudioMuted():");
 
-    let result =
-        call_manager::set_outgoing_audio_muted(callManager as *mut IOSCallManager, clientId, muted);
+    let result = call_manager::set_outgoing_audio_muted(
+        callManager as *mut IOSCallManagerHandle,
+        clientId,
+        muted,
+    );
     if result.is_err() {
         error!("{:?}", result.err());
     }
@@ -1103,8 +1962,22 @@ pub extern "C" fn ringrtcSetOutgoingVideoMuted(
 ) {
     info!("ringrtcSetOutgoingVideoMuted():");
 
-    let result =
-        call_manager::set_outgoing_video_muted(callManager as *mut IOSCallManager, clientId, muted);
+    let result = call_manager::set_outgoing_video_muted(
+        callManager as *mut IOSCallManagerHandle,
+        clientId,
+        muted,
+    );
+    if result.is_err() {
+        error!("{:?}", result.err());
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcRefreshPeek(callManager: *mut c_void, clientId: group_call::ClientId) {
+    info!("ringrtcRefreshPeek():");
+
+    let result = call_manager::refresh_peek(callManager as *mut IOSCallManagerHandle, clientId);
     if result.is_err() {
         error!("{:?}", result.err());
     }
@@ -1115,7 +1988,8 @@ pub extern "C" fn ringrtcSetOutgoingVideoMuted(
 pub extern "C" fn ringrtcResendMediaKeys(callManager: *mut c_void, clientId: group_call::ClientId) {
     info!("ringrtcResendMediaKeys():");
 
-    let result = call_manager::resend_media_keys(callManager as *mut IOSCallManager, clientId);
+    let result =
+        call_manager::resend_media_keys(callManager as *mut IOSCallManagerHandle, clientId);
     if result.is_err() {
         error!("{:?}", result.err());
     }
@@ -1131,7 +2005,7 @@ pub extern "C" fn ringrtcSetBandwidthMode(
     info!("ringrtcSetBandwidthMode():");
 
     let result = call_manager::set_bandwidth_mode(
-        callManager as *mut IOSCallManager,
+        callManager as *mut IOSCallManagerHandle,
         clientId,
         BandwidthMode::from_i32(bandwidthMode),
     );
@@ -1167,11 +2041,12 @@ pub extern "C" fn ringrtcRequestVideo(
             width:     resolution.width,
             height:    resolution.height,
             framerate: optional_framerate,
+            pinned:    resolution.pinned,
         });
     }
 
     let result = call_manager::request_video(
-        callManager as *mut IOSCallManager,
+        callManager as *mut IOSCallManagerHandle,
         clientId,
         rendered_resolutions,
     );
@@ -1180,6 +2055,313 @@ pub extern "C" fn ringrtcRequestVideo(
     }
 }
 
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcSetMaxIncomingVideos(
+    callManager: *mut c_void,
+    clientId: group_call::ClientId,
+    max: u32,
+) {
+    info!("ringrtcSetMaxIncomingVideos():");
+
+    let result = call_manager::set_max_incoming_videos(
+        callManager as *mut IOSCallManagerHandle,
+        clientId,
+        max,
+    );
+    if result.is_err() {
+        error!("{:?}", result.err());
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcSetRemoteVideoHidden(
+    callManager: *mut c_void,
+    clientId: group_call::ClientId,
+    demuxId: group_call::DemuxId,
+    hidden: bool,
+) {
+    info!("ringrtcSetRemoteVideoHidden():");
+
+    let result = call_manager::set_remote_video_hidden(
+        callManager as *mut IOSCallManagerHandle,
+        clientId,
+        demuxId,
+        hidden,
+    );
+    if result.is_err() {
+        error!("{:?}", result.err());
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcEnterLowResourceMode(
+    callManager: *mut c_void,
+    clientId: group_call::ClientId,
+) {
+    info!("ringrtcEnterLowResourceMode():");
+
+    let result =
+        call_manager::enter_low_resource_mode(callManager as *mut IOSCallManagerHandle, clientId);
+    if result.is_err() {
+        error!("{:?}", result.err());
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcExitLowResourceMode(
+    callManager: *mut c_void,
+    clientId: group_call::ClientId,
+) {
+    info!("ringrtcExitLowResourceMode():");
+
+    let result =
+        call_manager::exit_low_resource_mode(callManager as *mut IOSCallManagerHandle, clientId);
+    if result.is_err() {
+        error!("{:?}", result.err());
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcSetRosterUpdateInterval(
+    callManager: *mut c_void,
+    clientId: group_call::ClientId,
+    intervalMillis: u32,
+) {
+    info!("ringrtcSetRosterUpdateInterval():");
+
+    let result = call_manager::set_roster_update_interval(
+        callManager as *mut IOSCallManagerHandle,
+        clientId,
+        intervalMillis,
+    );
+    if result.is_err() {
+        error!("{:?}", result.err());
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcSetOutgoingVideoCaps(
+    callManager: *mut c_void,
+    callId: u64,
+    maxWidth: u32,
+    maxHeight: u32,
+    maxFps: u32,
+) -> *mut c_void {
+    info!("ringrtcSetOutgoingVideoCaps():");
+
+    match call_manager::set_outgoing_video_caps(
+        callManager as *mut IOSCallManagerHandle,
+        Some(callId),
+        None,
+        maxWidth,
+        maxHeight,
+        maxFps,
+    ) {
+        Ok(_v) => {
+            // Return the object reference back as indication of success.
+            callManager
+        }
+        Err(e) => {
+            error!("{}", e);
+            ptr::null_mut()
+        }
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcSetGroupOutgoingVideoCaps(
+    callManager: *mut c_void,
+    clientId: group_call::ClientId,
+    maxWidth: u32,
+    maxHeight: u32,
+    maxFps: u32,
+) {
+    info!("ringrtcSetGroupOutgoingVideoCaps():");
+
+    let result = call_manager::set_outgoing_video_caps(
+        callManager as *mut IOSCallManagerHandle,
+        None,
+        Some(clientId),
+        maxWidth,
+        maxHeight,
+        maxFps,
+    );
+    if result.is_err() {
+        error!("{:?}", result.err());
+    }
+}
+
+/// Copies up to `layersLen` of the group call's outgoing simulcast layers,
+/// lowest first, into `layers`.  Returns how many layers there are, which
+/// can be more than were copied, or 0 on failure.
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcGetOutgoingSimulcastConfig(
+    callManager: *mut c_void,
+    clientId: group_call::ClientId,
+    layers: *mut LayerConfig,
+    layersLen: usize,
+) -> usize {
+    if layers.is_null() && layersLen > 0 {
+        error!("ringrtcGetOutgoingSimulcastConfig(): null layers buffer");
+        return 0;
+    }
+
+    match call_manager::outgoing_simulcast_config(
+        callManager as *mut IOSCallManagerHandle,
+        clientId,
+    ) {
+        Ok(config) => {
+            for (index, layer) in config.iter().take(layersLen).enumerate() {
+                unsafe { *layers.add(index) = *layer };
+            }
+            config.len()
+        }
+        Err(e) => {
+            error!("{}", e);
+            0
+        }
+    }
+}
+
+/// Copies the bandwidth mode being enforced into `mode`.  Exactly one of
+/// `callId` (for the active call) or `clientId` (for a group call) must
+/// be valid.
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcGetEffectiveBandwidthMode(
+    callManager: *mut c_void,
+    callId: AppOptionalUInt64,
+    clientId: AppOptionalUInt32,
+    mode: *mut EffectiveBandwidthMode,
+) -> *mut c_void {
+    if mode.is_null() {
+        error!("ringrtcGetEffectiveBandwidthMode(): mode is null");
+        return ptr::null_mut();
+    }
+
+    let call_id = if callId.valid {
+        Some(callId.value)
+    } else {
+        None
+    };
+    let client_id = if clientId.valid {
+        Some(clientId.value)
+    } else {
+        None
+    };
+
+    match call_manager::effective_bandwidth_mode(
+        callManager as *mut IOSCallManagerHandle,
+        call_id,
+        client_id,
+    ) {
+        Ok(v) => {
+            unsafe { *mode = v };
+            // Return the object reference back as indication of success.
+            callManager
+        }
+        Err(e) => {
+            error!("{}", e);
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Copies the demux ID of the remote device talking in the group call
+/// right now into `demuxId`.  Returns null if everyone is silent.
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcGetCurrentSpeaker(
+    callManager: *mut c_void,
+    clientId: group_call::ClientId,
+    demuxId: *mut group_call::DemuxId,
+) -> *mut c_void {
+    if demuxId.is_null() {
+        error!("ringrtcGetCurrentSpeaker(): demuxId is null");
+        return ptr::null_mut();
+    }
+
+    match call_manager::current_speaker(callManager as *mut IOSCallManagerHandle, clientId) {
+        Ok(Some(v)) => {
+            unsafe { *demuxId = v };
+            // Return the object reference back as indication of success.
+            callManager
+        }
+        Ok(None) => ptr::null_mut(),
+        Err(e) => {
+            error!("{}", e);
+            ptr::null_mut()
+        }
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcSetInterpreterTrack(
+    callManager: *mut c_void,
+    clientId: group_call::ClientId,
+    nativeAudioTrack: *const c_void,
+) {
+    info!("ringrtcSetInterpreterTrack():");
+
+    let result = call_manager::set_interpreter_track(
+        callManager as *mut IOSCallManagerHandle,
+        clientId,
+        nativeAudioTrack,
+    );
+    if result.is_err() {
+        error!("{:?}", result.err());
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcSetInterpreterAudioEnabled(
+    callManager: *mut c_void,
+    clientId: group_call::ClientId,
+    enabled: bool,
+) {
+    info!("ringrtcSetInterpreterAudioEnabled():");
+
+    let result = call_manager::set_interpreter_audio_enabled(
+        callManager as *mut IOSCallManagerHandle,
+        clientId,
+        enabled,
+    );
+    if result.is_err() {
+        error!("{:?}", result.err());
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcSetRemoteInterpreterAudioEnabled(
+    callManager: *mut c_void,
+    clientId: group_call::ClientId,
+    remoteDemuxId: group_call::DemuxId,
+    enabled: bool,
+) {
+    info!("ringrtcSetRemoteInterpreterAudioEnabled():");
+
+    let result = call_manager::set_remote_interpreter_audio_enabled(
+        callManager as *mut IOSCallManagerHandle,
+        clientId,
+        remoteDemuxId,
+        enabled,
+    );
+    if result.is_err() {
+        error!("{:?}", result.err());
+    }
+}
+
 #[no_mangle]
 #[allow(non_snake_case)]
 pub extern "C" fn ringrtcSetGroupMembers(
@@ -1215,7 +2397,7 @@ pub extern "C" fn ringrtcSetGroupMembers(
     }
 
     let result = call_manager::set_group_members(
-        callManager as *mut IOSCallManager,
+        callManager as *mut IOSCallManagerHandle,
         clientId,
         group_members,
     );
@@ -1240,7 +2422,7 @@ pub extern "C" fn ringrtcSetMembershipProof(
     }
 
     let result = call_manager::set_membership_proof(
-        callManager as *mut IOSCallManager,
+        callManager as *mut IOSCallManagerHandle,
         clientId,
         proof.unwrap(),
     );
@@ -10,21 +10,39 @@ use std::panic;
 use std::sync::Arc;
 use std::time::Duration;
 
-use crate::ios::api::call_manager_interface::{AppCallContext, AppInterface, AppObject};
+use crate::ios::api::call_manager_interface::{
+    AppCallContext,
+    AppCallStatistics,
+    AppInterface,
+    AppObject,
+};
+use crate::ios::error::IOSError;
 use crate::ios::ios_platform::IOSPlatform;
 use crate::ios::logging::{init_logging, IOSLogger};
 
-use crate::common::{CallId, CallMediaType, DeviceId, FeatureLevel, HttpResponse, Result};
-use crate::core::bandwidth_mode::BandwidthMode;
-use crate::core::call_manager::CallManager;
-use crate::core::util::{ptr_as_box, ptr_as_mut, uuid_to_string};
+use crate::common::{
+    log_buffer,
+    CallId,
+    CallMediaType,
+    DeviceId,
+    FeatureLevel,
+    HttpResponse,
+    Result,
+};
+use crate::core::bandwidth_mode::{BandwidthMode, EffectiveBandwidthMode};
+use crate::core::call_manager::{CallManager, CallManagerConfig, CallManagerHandle};
+use crate::core::util::{self, uuid_to_string};
 use crate::core::{group_call, signaling};
 use crate::error::RingRtcError;
 use crate::webrtc::media;
+use crate::webrtc::stats_observer::AudioPlayoutStatistics;
 
 /// Public type for iOS CallManager
 pub type IOSCallManager = CallManager<IOSPlatform>;
 
+/// Public type for the iOS CallManager as handed out to the application
+pub type IOSCallManagerHandle = CallManagerHandle<IOSPlatform>;
+
 /// Library initialization routine.
 ///
 /// Sets up the logging infrastructure.
@@ -40,25 +58,79 @@ pub fn initialize(log_object: IOSLogger) -> Result<()> {
     Ok(())
 }
 
+/// Sets how many lines and bytes of recent logs are kept for bug reports.
+pub fn set_log_buffer_limits(max_lines: usize, max_bytes: usize) {
+    info!(
+        "set_log_buffer_limits(): max_lines: {}, max_bytes: {}",
+        max_lines, max_bytes
+    );
+
+    log_buffer::set_log_buffer_limits(max_lines, max_bytes);
+}
+
+/// Returns the recent logs kept for bug reports.
+pub fn recent_logs() -> String {
+    log_buffer::recent_logs()
+}
+
+/// Runs f, turning a panic into an IOSError::CallManagerPanic so that the
+/// application gets an error instead of a crash.
+fn catch_panic<T>(caller: &str, f: impl FnOnce() -> Result<T>) -> Result<T> {
+    util::catch_panic(f, || IOSError::CallManagerPanic(caller.to_string()).into())
+}
+
+/// Runs f with the Call Manager behind the handle, failing if the handle
+/// is null or invalid, or if f panics.
+fn with_call_manager<T>(
+    call_manager: *mut IOSCallManagerHandle,
+    caller: &str,
+    f: impl FnOnce(&mut IOSCallManager) -> Result<T>,
+) -> Result<T> {
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
+    catch_panic(caller, || f(call_manager))
+}
+
 /// Creates a new IOSCallManager object.
-pub fn create(app_call_manager: *mut c_void, app_interface: AppInterface) -> Result<*mut c_void> {
-    info!("create_call_manager():");
+pub fn create(
+    app_call_manager: *mut c_void,
+    app_interface: AppInterface,
+    max_pending_ice_candidates: usize,
+) -> Result<*mut c_void> {
+    info!(
+        "create_call_manager(): max_pending_ice_candidates: {}",
+        max_pending_ice_candidates
+    );
     let platform = IOSPlatform::new(app_call_manager, app_interface)?;
 
-    let call_manager = IOSCallManager::new(platform)?;
+    let config = CallManagerConfig {
+        max_pending_ice_candidates,
+    };
+    let call_manager = IOSCallManager::with_config(platform, config)?;
 
-    let call_manager_box = Box::new(call_manager);
-    Ok(Box::into_raw(call_manager_box) as *mut c_void)
+    Ok(IOSCallManagerHandle::into_raw(call_manager) as *mut c_void)
+}
+
+/// Application notification to identify the app build in the headers
+/// of HTTP requests made on its behalf.
+pub fn set_client_identifier(
+    call_manager: *mut IOSCallManagerHandle,
+    client_identifier: String,
+) -> Result<()> {
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
+
+    info!("set_client_identifier(): {}", client_identifier);
+
+    call_manager.set_client_identifier(client_identifier)
 }
 
 /// Application notification to start a new call.
 pub fn call(
-    call_manager: *mut IOSCallManager,
+    call_manager: *mut IOSCallManagerHandle,
     app_remote: *const c_void,
     call_media_type: CallMediaType,
     app_local_device: DeviceId,
 ) -> Result<()> {
-    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
 
     info!("call():");
 
@@ -71,22 +143,23 @@ pub fn call(
 
 /// Application notification to proceed with a new call
 pub fn proceed(
-    call_manager: *mut IOSCallManager,
+    call_manager: *mut IOSCallManagerHandle,
     call_id: u64,
     app_call_context: AppCallContext,
     bandwidth_mode: BandwidthMode,
 ) -> Result<()> {
-    let call_manager = unsafe { ptr_as_mut(call_manager)? };
     let call_id = CallId::from(call_id);
 
     info!("proceed(): {}", call_id);
 
-    call_manager.proceed(call_id, Arc::new(app_call_context), bandwidth_mode)
+    with_call_manager(call_manager, "proceed()", |call_manager| {
+        call_manager.proceed(call_id, Arc::new(app_call_context), bandwidth_mode)
+    })
 }
 
 /// Application notification that the sending of the previous message was a success.
-pub fn message_sent(call_manager: *mut IOSCallManager, call_id: u64) -> Result<()> {
-    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+pub fn message_sent(call_manager: *mut IOSCallManagerHandle, call_id: u64) -> Result<()> {
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
     let call_id = CallId::from(call_id);
 
     info!("message_sent(): call_id: {}", call_id);
@@ -94,8 +167,8 @@ pub fn message_sent(call_manager: *mut IOSCallManager, call_id: u64) -> Result<(
 }
 
 /// Application notification that the sending of the previous message was a failure.
-pub fn message_send_failure(call_manager: *mut IOSCallManager, call_id: u64) -> Result<()> {
-    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+pub fn message_send_failure(call_manager: *mut IOSCallManagerHandle, call_id: u64) -> Result<()> {
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
     let call_id = CallId::from(call_id);
 
     info!("message_send_failure(): call_id: {}", call_id);
@@ -103,17 +176,26 @@ pub fn message_send_failure(call_manager: *mut IOSCallManager, call_id: u64) ->
 }
 
 /// Application notification of local hangup.
-pub fn hangup(call_manager: *mut IOSCallManager) -> Result<()> {
-    let call_manager = unsafe { ptr_as_mut(call_manager)? };
-
+pub fn hangup(call_manager: *mut IOSCallManagerHandle) -> Result<()> {
     info!("hangup():");
-    call_manager.hangup()
+    with_call_manager(call_manager, "hangup()", |call_manager| {
+        call_manager.hangup()
+    })
+}
+
+/// Application notification of a local hangup because the user
+/// stepped away, such as by backgrounding the app.
+pub fn hangup_as_away(call_manager: *mut IOSCallManagerHandle) -> Result<()> {
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
+
+    info!("hangup_as_away():");
+    call_manager.hangup_as_away()
 }
 
 /// Application notification of received answer message
 #[allow(clippy::too_many_arguments)]
 pub fn received_answer(
-    call_manager: *mut IOSCallManager,
+    call_manager: *mut IOSCallManagerHandle,
     call_id: u64,
     sender_device_id: DeviceId,
     opaque: Option<Vec<u8>>,
@@ -121,7 +203,6 @@ pub fn received_answer(
     sender_identity_key: Option<Vec<u8>>,
     receiver_identity_key: Option<Vec<u8>>,
 ) -> Result<()> {
-    let call_manager = unsafe { ptr_as_mut(call_manager)? };
     let call_id = CallId::from(call_id);
 
     info!(
@@ -162,6 +243,19 @@ pub fn received_answer(
         }
     };
 
+    // Catch malformed keys here rather than deep in the key derivation.
+    signaling::check_identity_key(
+        "received_answer()",
+        "sender_identity_key",
+        &sender_identity_key,
+    )?;
+    signaling::check_identity_key(
+        "received_answer()",
+        "receiver_identity_key",
+        &receiver_identity_key,
+    )?;
+
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
     call_manager.received_answer(
         call_id,
         signaling::ReceivedAnswer {
@@ -177,7 +271,7 @@ pub fn received_answer(
 /// Application notification of received offer message
 #[allow(clippy::too_many_arguments)]
 pub fn received_offer(
-    call_manager: *mut IOSCallManager,
+    call_manager: *mut IOSCallManagerHandle,
     call_id: u64,
     remote_peer: *const c_void,
     sender_device_id: DeviceId,
@@ -190,7 +284,6 @@ pub fn received_offer(
     sender_identity_key: Option<Vec<u8>>,
     receiver_identity_key: Option<Vec<u8>>,
 ) -> Result<()> {
-    let call_manager = unsafe { ptr_as_mut(call_manager)? };
     let call_id = CallId::from(call_id);
     let remote_peer = AppObject::from(remote_peer);
 
@@ -232,6 +325,15 @@ pub fn received_offer(
         }
     };
 
+    // Catch malformed keys here rather than deep in the key derivation.
+    signaling::check_identity_key("received_offer()", "sender_identity_key", &sender_identity_key)?;
+    signaling::check_identity_key(
+        "received_offer()",
+        "receiver_identity_key",
+        &receiver_identity_key,
+    )?;
+
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
     call_manager.received_offer(
         remote_peer,
         call_id,
@@ -250,11 +352,11 @@ pub fn received_offer(
 
 /// Application notification to add ICE candidates to a Connection
 pub fn received_ice(
-    call_manager: *mut IOSCallManager,
+    call_manager: *mut IOSCallManagerHandle,
     call_id: u64,
     received: signaling::ReceivedIce,
 ) -> Result<()> {
-    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
     let call_id = CallId::from(call_id);
 
     info!(
@@ -269,13 +371,13 @@ pub fn received_ice(
 
 /// Application notification of received Hangup message
 pub fn received_hangup(
-    call_manager: *mut IOSCallManager,
+    call_manager: *mut IOSCallManagerHandle,
     call_id: u64,
     sender_device_id: DeviceId,
     hangup_type: signaling::HangupType,
     hangup_device_id: DeviceId,
 ) -> Result<()> {
-    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
     let call_id = CallId::from(call_id);
 
     info!(
@@ -294,11 +396,11 @@ pub fn received_hangup(
 
 /// Application notification of received Busy message
 pub fn received_busy(
-    call_manager: *mut IOSCallManager,
+    call_manager: *mut IOSCallManagerHandle,
     call_id: u64,
     sender_device_id: DeviceId,
 ) -> Result<()> {
-    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
     let call_id = CallId::from(call_id);
 
     info!(
@@ -309,8 +411,89 @@ pub fn received_busy(
     call_manager.received_busy(call_id, signaling::ReceivedBusy { sender_device_id })
 }
 
+/// Application notification to reply busy to a call, because the user
+/// is busy elsewhere.
+pub fn send_busy(
+    call_manager: *mut IOSCallManagerHandle,
+    call_id: u64,
+    remote_peer: *const c_void,
+) -> Result<()> {
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
+    let call_id = CallId::from(call_id);
+
+    info!("send_busy(): call_id: {}", call_id);
+
+    call_manager.send_busy(AppObject::from(remote_peer), call_id)
+}
+
+/// Application request to transfer a call to another peer
+pub fn transfer_call(
+    call_manager: *mut IOSCallManagerHandle,
+    call_id: u64,
+    target_peer: *const c_void,
+) -> Result<()> {
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
+    let call_id = CallId::from(call_id);
+
+    info!("transfer_call(): call_id: {}", call_id);
+
+    call_manager.transfer_call(call_id, AppObject::from(target_peer))
+}
+
+/// Application notification of received TransferRequest message
+pub fn received_transfer_request(
+    call_manager: *mut IOSCallManagerHandle,
+    call_id: u64,
+    sender_device_id: DeviceId,
+    target_peer: *const c_void,
+) -> Result<()> {
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
+    let call_id = CallId::from(call_id);
+
+    info!(
+        "received_transfer_request(): call_id: {} sender device_id: {}",
+        call_id, sender_device_id
+    );
+
+    call_manager.received_transfer_request(
+        call_id,
+        signaling::ReceivedTransferRequest { sender_device_id },
+        AppObject::from(target_peer),
+    )
+}
+
+/// Application request to accept a transfer requested by the remote peer
+pub fn accept_transfer(call_manager: *mut IOSCallManagerHandle, call_id: u64) -> Result<()> {
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
+    let call_id = CallId::from(call_id);
+
+    info!("accept_transfer(): call_id: {}", call_id);
+
+    call_manager.accept_transfer(call_id)
+}
+
+/// Application notification of received TransferAccepted message
+pub fn received_transfer_accepted(
+    call_manager: *mut IOSCallManagerHandle,
+    call_id: u64,
+    sender_device_id: DeviceId,
+) -> Result<()> {
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
+    let call_id = CallId::from(call_id);
+
+    info!(
+        "received_transfer_accepted(): call_id: {} sender device_id: {}",
+        call_id, sender_device_id
+    );
+
+    call_manager.received_transfer_accepted(
+        call_id,
+        signaling::ReceivedTransferAccepted { sender_device_id },
+    )
+}
+
 pub fn received_call_message(
-    call_manager: *mut IOSCallManager,
+    call_manager: *mut IOSCallManagerHandle,
     sender_uuid: Vec<u8>,
     sender_device_id: DeviceId,
     local_device_id: DeviceId,
@@ -323,7 +506,7 @@ pub fn received_call_message(
     );
     debug!("  sender_uuid: {}", uuid_to_string(&sender_uuid));
 
-    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
     call_manager.received_call_message(
         sender_uuid,
         sender_device_id,
@@ -334,31 +517,75 @@ pub fn received_call_message(
 }
 
 pub fn received_http_response(
-    call_manager: *mut IOSCallManager,
+    call_manager: *mut IOSCallManagerHandle,
     request_id: u32,
     response: Option<HttpResponse>,
 ) -> Result<()> {
     info!("received_http_response(): request_id: {}", request_id,);
 
-    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
     call_manager.received_http_response(request_id, response)
 }
 
 /// Application notification to accept the incoming call
-pub fn accept_call(call_manager: *mut IOSCallManager, call_id: u64) -> Result<()> {
+pub fn accept_call(call_manager: *mut IOSCallManagerHandle, call_id: u64) -> Result<()> {
     let call_id = CallId::from(call_id);
 
     info!("accept_call(): {}", call_id);
 
-    let call_manager = unsafe { ptr_as_mut(call_manager)? };
-    call_manager.accept_call(call_id)
+    with_call_manager(call_manager, "accept_call()", |call_manager| {
+        call_manager.accept_call(call_id)
+    })
+}
+
+/// Application notification that the ringtone for the incoming call has
+/// started playing.
+///
+/// If the app accepted the call before LocalRinging, the accept is held
+/// back until both the call is connected and this has been called.
+pub fn notify_ringing(call_manager: *mut IOSCallManagerHandle, call_id: u64) -> Result<()> {
+    let call_id = CallId::from(call_id);
+
+    info!("notify_ringing(): {}", call_id);
+
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
+    call_manager.notify_ringing(call_id)
+}
+
+/// Application notification to put the active call on hold.
+///
+/// Meant to be called when CallKit performs a `CXSetHeldCallAction`
+/// with `isOnHold` set. Outgoing and incoming media stop; the app
+/// remains responsible for deactivating its audio session. An offer
+/// for another call that arrives while this one is held starts ringing
+/// as usual instead of getting a busy.
+pub fn hold_call(call_manager: *mut IOSCallManagerHandle, call_id: u64) -> Result<()> {
+    let call_id = CallId::from(call_id);
+
+    info!("hold_call(): {}", call_id);
+
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
+    call_manager.hold_call(call_id)
+}
+
+/// Application notification to resume a call after `hold_call()`,
+/// e.g. when CallKit unholds it. The app should reactivate its audio
+/// session first. Resuming the held call while a second call is
+/// active swaps them, putting the second call on hold.
+pub fn resume_call(call_manager: *mut IOSCallManagerHandle, call_id: u64) -> Result<()> {
+    let call_id = CallId::from(call_id);
+
+    info!("resume_call(): {}", call_id);
+
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
+    call_manager.resume_call(call_id)
 }
 
 /// CMI request for the active Connection object
-pub fn get_active_connection(call_manager: *mut IOSCallManager) -> Result<*mut c_void> {
+pub fn get_active_connection(call_manager: *mut IOSCallManagerHandle) -> Result<*mut c_void> {
     info!("get_active_connection():");
 
-    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
     let connection = call_manager.active_connection()?;
     let app_connection = connection.app_connection()?;
 
@@ -366,10 +593,10 @@ pub fn get_active_connection(call_manager: *mut IOSCallManager) -> Result<*mut c
 }
 
 /// CMI request for the active CallContext object
-pub fn get_active_call_context(call_manager: *mut IOSCallManager) -> Result<*mut c_void> {
+pub fn get_active_call_context(call_manager: *mut IOSCallManagerHandle) -> Result<*mut c_void> {
     info!("get_active_call_context():");
 
-    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
     let call = call_manager.active_call()?;
     let app_call_context = call.call_context()?;
 
@@ -377,221 +604,750 @@ pub fn get_active_call_context(call_manager: *mut IOSCallManager) -> Result<*mut
 }
 
 /// CMI request to set the video status
-pub fn set_video_enable(call_manager: *mut IOSCallManager, enable: bool) -> Result<()> {
+pub fn set_video_enable(call_manager: *mut IOSCallManagerHandle, enable: bool) -> Result<()> {
     info!("set_video_enable():");
 
-    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
     let mut active_connection = call_manager.active_connection()?;
     active_connection.inject_send_sender_status_via_data_channel(enable)
 }
 
+/// CMI request to mute or unmute the outgoing audio track
+pub fn set_audio_enable(call_manager: *mut IOSCallManagerHandle, enable: bool) -> Result<()> {
+    info!("set_audio_enable(): {}", enable);
+
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
+    call_manager.set_outgoing_audio_enabled(enable)
+}
+
+/// Request to send an application-defined payload to the remote peer
+/// of the active call.
+///
+/// The payload is sent once over the RTP data channel, which is
+/// unreliable: it may be lost or delivered out of order.
+pub fn send_data_message(
+    call_manager: *mut IOSCallManagerHandle,
+    call_id: u64,
+    payload: Vec<u8>,
+) -> Result<()> {
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
+    call_manager.send_rtp_data(CallId::from(call_id), payload)
+}
+
+/// Request to play DTMF tones into the outgoing audio of the active
+/// call, as RFC 4733 telephone-events.
+///
+/// The tones must all be one of `0-9`, `*`, `#` or `A-D`.
+pub fn send_dtmf(
+    call_manager: *mut IOSCallManagerHandle,
+    call_id: u64,
+    tones: String,
+    duration_ms: u32,
+    gap_ms: u32,
+) -> Result<()> {
+    let call_id = CallId::from(call_id);
+
+    info!("send_dtmf(): {}, tones: {}", call_id, tones.chars().count());
+
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
+    let active_call = call_manager.active_call()?;
+    if active_call.call_id() != call_id {
+        return Err(RingRtcError::CallIdNotFound(call_id).into());
+    }
+    let mut active_connection = active_call.active_connection()?;
+    active_connection.inject_send_dtmf(tones, duration_ms, gap_ms)
+}
+
+/// Request to change the audio jitter buffer target of the active call.
+///
+/// The target is clamped to a sane range. Small targets favor latency
+/// over smoothness and make audio glitches more likely.
+pub fn set_audio_jitter_buffer(
+    call_manager: *mut IOSCallManagerHandle,
+    call_id: u64,
+    target_ms: u32,
+) -> Result<()> {
+    let call_id = CallId::from(call_id);
+
+    info!("set_audio_jitter_buffer(): {}, {}ms", call_id, target_ms);
+
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
+    let active_call = call_manager.active_call()?;
+    if active_call.call_id() != call_id {
+        return Err(RingRtcError::CallIdNotFound(call_id).into());
+    }
+    active_call
+        .active_connection()?
+        .set_audio_jitter_buffer_target(target_ms)?;
+    Ok(())
+}
+
+/// Test only: delays received audio of the active call by another
+/// `extra_ms` before playout.  Does nothing unless built with the
+/// `testing` feature, so it is a no-op in release builds.
+pub fn inject_receive_latency(
+    call_manager: *mut IOSCallManagerHandle,
+    call_id: u64,
+    extra_ms: u32,
+) -> Result<()> {
+    let call_id = CallId::from(call_id);
+
+    info!("inject_receive_latency(): {}, {}ms", call_id, extra_ms);
+
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
+    let active_call = call_manager.active_call()?;
+    if active_call.call_id() != call_id {
+        return Err(RingRtcError::CallIdNotFound(call_id).into());
+    }
+    active_call
+        .active_connection()?
+        .inject_receive_latency(extra_ms)
+}
+
+/// Request to change the Opus in-band FEC level of the active call.
+///
+/// 0 turns FEC off; higher levels are clamped to the highest supported
+/// one. Redundancy comes out of the existing audio bitrate, so it trades
+/// quality on a clean link for resilience on a lossy one.
+pub fn set_audio_fec_level(
+    call_manager: *mut IOSCallManagerHandle,
+    call_id: u64,
+    level: u8,
+) -> Result<()> {
+    let call_id = CallId::from(call_id);
+
+    info!("set_audio_fec_level(): {}, {}", call_id, level);
+
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
+    let active_call = call_manager.active_call()?;
+    if active_call.call_id() != call_id {
+        return Err(RingRtcError::CallIdNotFound(call_id).into());
+    }
+    active_call
+        .active_connection()?
+        .set_audio_fec_level(level)?;
+    Ok(())
+}
+
+/// Returns the security fingerprint of the active call, for the users to
+/// compare with each other.  Available once the offer and answer have
+/// been exchanged.
+pub fn security_fingerprint(
+    call_manager: *mut IOSCallManagerHandle,
+    call_id: u64,
+) -> Result<String> {
+    let call_id = CallId::from(call_id);
+
+    info!("security_fingerprint(): {}", call_id);
+
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
+    let active_call = call_manager.active_call()?;
+    if active_call.call_id() != call_id {
+        return Err(RingRtcError::CallIdNotFound(call_id).into());
+    }
+    active_call.active_connection()?.security_fingerprint()
+}
+
+/// Returns the name of the SRTP cipher suite negotiated for the active
+/// call, for crypto-inventory logging.
+pub fn srtp_cipher_suite(
+    call_manager: *mut IOSCallManagerHandle,
+    call_id: u64,
+) -> Result<String> {
+    let call_id = CallId::from(call_id);
+
+    info!("srtp_cipher_suite(): {}", call_id);
+
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
+    call_manager.srtp_cipher_suite(call_id)
+}
+
+/// Returns whether the media of the active call is frame encrypted, for
+/// the application's encryption indicator.
+pub fn is_frame_encryption_active(
+    call_manager: *mut IOSCallManagerHandle,
+    call_id: u64,
+) -> Result<bool> {
+    let call_id = CallId::from(call_id);
+
+    info!("is_frame_encryption_active(): {}", call_id);
+
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
+    call_manager.is_frame_encryption_active(call_id)
+}
+
+/// Returns the round trip time of the ICE candidate pair in use by the
+/// active call, or None if it isn't connected yet.
+pub fn selected_pair_rtt_ms(
+    call_manager: *mut IOSCallManagerHandle,
+    call_id: u64,
+) -> Result<Option<u32>> {
+    let call_id = CallId::from(call_id);
+
+    debug!("selected_pair_rtt_ms(): {}", call_id);
+
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
+    let active_call = call_manager.active_call()?;
+    if active_call.call_id() != call_id {
+        return Err(RingRtcError::CallIdNotFound(call_id).into());
+    }
+    active_call.active_connection()?.selected_pair_rtt_ms()
+}
+
+/// Returns a snapshot of the quality of the active call.  Until its
+/// first stats report arrives, none of the values are valid.
+pub fn get_call_statistics(call_manager: *mut IOSCallManagerHandle) -> Result<AppCallStatistics> {
+    debug!("get_call_statistics():");
+
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
+    let call_statistics = call_manager
+        .active_connection()?
+        .call_statistics()?
+        .unwrap_or_default();
+    Ok(AppCallStatistics::from(&call_statistics))
+}
+
+/// Returns the playout health of audio received in the active call, or
+/// None if no audio is being received yet.
+pub fn audio_playout_statistics(
+    call_manager: *mut IOSCallManagerHandle,
+    call_id: u64,
+) -> Result<Option<AudioPlayoutStatistics>> {
+    let call_id = CallId::from(call_id);
+
+    debug!("audio_playout_statistics(): {}", call_id);
+
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
+    let active_call = call_manager.active_call()?;
+    if active_call.call_id() != call_id {
+        return Err(RingRtcError::CallIdNotFound(call_id).into());
+    }
+    active_call.active_connection()?.audio_playout_statistics()
+}
+
 /// Request to update the bandwidth mode on the direct connection
 pub fn update_bandwidth_mode(
-    call_manager: *mut IOSCallManager,
+    call_manager: *mut IOSCallManagerHandle,
     bandwidth_mode: BandwidthMode,
 ) -> Result<()> {
     info!("update_bandwidth_mode():");
 
-    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
     let mut active_connection = call_manager.active_connection()?;
     active_connection.inject_update_bandwidth_mode(bandwidth_mode)
 }
 
+/// Request to cap the send bitrate on the direct connection, whatever the
+/// bandwidth mode.  A max_bitrate_bps of 0 removes the cap.
+pub fn update_max_bitrate(
+    call_manager: *mut IOSCallManagerHandle,
+    max_bitrate_bps: u32,
+) -> Result<()> {
+    info!("update_max_bitrate(): {}", max_bitrate_bps);
+
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
+    let mut active_connection = call_manager.active_connection()?;
+    active_connection.inject_set_max_bitrate(max_bitrate_bps)
+}
+
 /// CMI request to drop the active call
-pub fn drop_call(call_manager: *mut IOSCallManager, call_id: u64) -> Result<()> {
+pub fn drop_call(call_manager: *mut IOSCallManagerHandle, call_id: u64) -> Result<()> {
     let call_id = CallId::from(call_id);
 
     info!("drop_call(): {}", call_id);
 
-    let call_manager = unsafe { ptr_as_mut(call_manager)? };
-    call_manager.drop_call(call_id)
+    with_call_manager(call_manager, "drop_call()", |call_manager| {
+        call_manager.drop_call(call_id)
+    })
 }
 
 /// CMI request to reset the Call Manager
-pub fn reset(call_manager: *mut IOSCallManager) -> Result<()> {
+pub fn reset(call_manager: *mut IOSCallManagerHandle) -> Result<()> {
     info!("reset():");
 
-    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
     call_manager.reset()
 }
 
+/// Application notification to hold outgoing signaling messages, such
+/// as when the app is being suspended.
+pub fn suspend_signaling(call_manager: *mut IOSCallManagerHandle) -> Result<()> {
+    info!("suspend_signaling():");
+
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
+    call_manager.suspend_signaling()
+}
+
+/// Application notification to send any signaling messages held since
+/// suspend_signaling().
+pub fn resume_signaling(call_manager: *mut IOSCallManagerHandle) -> Result<()> {
+    info!("resume_signaling():");
+
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
+    call_manager.resume_signaling()
+}
+
+/// Application request to play captured audio back out, to check the
+/// audio devices before a call.
+pub fn start_audio_loopback(call_manager: *mut IOSCallManagerHandle) -> Result<()> {
+    info!("start_audio_loopback():");
+
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
+    call_manager.start_audio_loopback()
+}
+
+/// Application request to stop the audio loopback.
+pub fn stop_audio_loopback(call_manager: *mut IOSCallManagerHandle) -> Result<()> {
+    info!("stop_audio_loopback():");
+
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
+    call_manager.stop_audio_loopback()
+}
+
 /// CMI request to close down the Call Manager.
 ///
 /// This is a blocking call.
-pub fn close(call_manager: *mut IOSCallManager) -> Result<()> {
+pub fn close(call_manager: *mut IOSCallManagerHandle) -> Result<()> {
     info!("close():");
 
-    // Convert the raw pointer back into a Box and let it go out of
+    // Take back ownership of the CallManager and let it go out of
     // scope when this function exits.
-    let mut call_manager = unsafe { ptr_as_box(call_manager)? };
+    let mut call_manager = unsafe { IOSCallManagerHandle::into_call_manager(call_manager)? };
     call_manager.close()
 }
 
 // Group Calls
 
 pub fn peek_group_call(
-    call_manager: *mut IOSCallManager,
+    call_manager: *mut IOSCallManagerHandle,
     request_id: u32,
     sfu_url: String,
     membership_proof: Vec<u8>,
     group_members: Vec<group_call::GroupMemberInfo>,
+    timeout_ms: u32,
 ) -> Result<()> {
-    info!("peek_group_call(): id: {}", request_id);
+    info!("peek_group_call(): id: {}, timeout: {}ms", request_id, timeout_ms);
 
-    let call_manager = unsafe { ptr_as_mut(call_manager)? };
-    call_manager.peek_group_call(request_id, sfu_url, membership_proof, group_members);
+    // A timeout of zero means no timeout.
+    let timeout = if timeout_ms == 0 {
+        None
+    } else {
+        Some(Duration::from_millis(timeout_ms.into()))
+    };
+
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
+    call_manager.peek_group_call_with_timeout(
+        request_id,
+        sfu_url,
+        membership_proof,
+        group_members,
+        timeout,
+    );
     Ok(())
 }
 
 pub fn create_group_call_client(
-    call_manager: *mut IOSCallManager,
+    call_manager: *mut IOSCallManagerHandle,
     group_id: group_call::GroupId,
     sfu_url: String,
     native_audio_track: *const c_void,
     native_video_track: *const c_void,
+    previous_session: Option<group_call::PreviousSession>,
 ) -> Result<group_call::ClientId> {
     info!("create_group_call_client():");
 
+    // Check before taking ownership; WebRTC doesn't tolerate null tracks.
+    if native_audio_track.is_null() {
+        return Err(RingRtcError::NullPointer(
+            "create_group_call_client".to_string(),
+            "native_audio_track".to_string(),
+        )
+        .into());
+    }
+    if native_video_track.is_null() {
+        return Err(RingRtcError::NullPointer(
+            "create_group_call_client".to_string(),
+            "native_video_track".to_string(),
+        )
+        .into());
+    }
+
     let outgoing_audio_track =
         media::AudioTrack::owned(native_audio_track as *const media::RffiAudioTrack);
     let outgoing_video_track =
         media::VideoTrack::owned(native_video_track as *const media::RffiVideoTrack);
 
-    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
     call_manager.create_group_call_client(
         group_id,
         sfu_url,
         None,
         outgoing_audio_track,
         outgoing_video_track,
+        previous_session,
     )
 }
 
 pub fn delete_group_call_client(
-    call_manager: *mut IOSCallManager,
+    call_manager: *mut IOSCallManagerHandle,
     client_id: group_call::ClientId,
 ) -> Result<()> {
     info!("delete_group_call_client(): id: {}", client_id);
 
-    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
     call_manager.delete_group_call_client(client_id);
     Ok(())
 }
 
-pub fn connect(call_manager: *mut IOSCallManager, client_id: group_call::ClientId) -> Result<()> {
+pub fn connect(
+    call_manager: *mut IOSCallManagerHandle,
+    client_id: group_call::ClientId,
+) -> Result<()> {
     info!("connect(): id: {}", client_id);
 
-    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
     call_manager.connect(client_id);
     Ok(())
 }
 
-pub fn join(call_manager: *mut IOSCallManager, client_id: group_call::ClientId) -> Result<()> {
+pub fn join(
+    call_manager: *mut IOSCallManagerHandle,
+    client_id: group_call::ClientId,
+) -> Result<()> {
     info!("join(): id: {}", client_id);
 
-    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
     call_manager.join(client_id);
     Ok(())
 }
 
-pub fn leave(call_manager: *mut IOSCallManager, client_id: group_call::ClientId) -> Result<()> {
+/// Joins the group call, optionally with outgoing audio muted from the
+/// start (for large calls where joiners shouldn't be heard right away).
+pub fn join_with_options(
+    call_manager: *mut IOSCallManagerHandle,
+    client_id: group_call::ClientId,
+    start_muted: bool,
+) -> Result<()> {
+    info!(
+        "join_with_options(): id: {} start_muted: {}",
+        client_id, start_muted
+    );
+
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
+    call_manager.join_with_options(client_id, group_call::JoinOptions { start_muted });
+    Ok(())
+}
+
+pub fn leave(
+    call_manager: *mut IOSCallManagerHandle,
+    client_id: group_call::ClientId,
+) -> Result<()> {
     info!("leave(): id: {}", client_id);
 
-    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
     call_manager.leave(client_id);
     Ok(())
 }
 
 pub fn disconnect(
-    call_manager: *mut IOSCallManager,
+    call_manager: *mut IOSCallManagerHandle,
     client_id: group_call::ClientId,
 ) -> Result<()> {
     info!("disconnect(): id: {}", client_id);
 
-    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
     call_manager.disconnect(client_id);
     Ok(())
 }
 
 pub fn set_outgoing_audio_muted(
-    call_manager: *mut IOSCallManager,
+    call_manager: *mut IOSCallManagerHandle,
     client_id: group_call::ClientId,
     muted: bool,
 ) -> Result<()> {
     info!("set_outgoing_audio_muted(): id: {}", client_id);
 
-    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
     call_manager.set_outgoing_audio_muted(client_id, muted);
     Ok(())
 }
 
 pub fn set_outgoing_video_muted(
-    call_manager: *mut IOSCallManager,
+    call_manager: *mut IOSCallManagerHandle,
     client_id: group_call::ClientId,
     muted: bool,
 ) -> Result<()> {
     info!("set_outgoing_video_muted(): id: {}", client_id);
 
-    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
     call_manager.set_outgoing_video_muted(client_id, muted);
     Ok(())
 }
 
+/// Request a fresh peek for a connected group call client. The result is
+/// delivered through handlePeekChanged even if the roster is unchanged.
+pub fn refresh_peek(
+    call_manager: *mut IOSCallManagerHandle,
+    client_id: group_call::ClientId,
+) -> Result<()> {
+    info!("refresh_peek(): id: {}", client_id);
+
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
+    call_manager.refresh_peek(client_id);
+    Ok(())
+}
+
 pub fn resend_media_keys(
-    call_manager: *mut IOSCallManager,
+    call_manager: *mut IOSCallManagerHandle,
     client_id: group_call::ClientId,
 ) -> Result<()> {
     info!("resend_media_keys(): id: {}", client_id);
 
-    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
     call_manager.resend_media_keys(client_id);
     Ok(())
 }
 
 pub fn set_bandwidth_mode(
-    call_manager: *mut IOSCallManager,
+    call_manager: *mut IOSCallManagerHandle,
     client_id: group_call::ClientId,
     bandwidth_mode: BandwidthMode,
 ) -> Result<()> {
     info!("set_bandwidth_mode(): id: {}", client_id);
 
-    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
     call_manager.set_bandwidth_mode(client_id, bandwidth_mode);
     Ok(())
 }
 
 pub fn request_video(
-    call_manager: *mut IOSCallManager,
+    call_manager: *mut IOSCallManagerHandle,
     client_id: group_call::ClientId,
     rendered_resolutions: Vec<group_call::VideoRequest>,
 ) -> Result<()> {
     info!("request_video(): id: {}", client_id);
 
-    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
     call_manager.request_video(client_id, rendered_resolutions);
     Ok(())
 }
 
+/// Limits how many remote videos are received at once, active speakers first.
+/// The rest are released until they rank within the limit again.
+pub fn set_max_incoming_videos(
+    call_manager: *mut IOSCallManagerHandle,
+    client_id: group_call::ClientId,
+    max: u32,
+) -> Result<()> {
+    info!("set_max_incoming_videos(): id: {}", client_id);
+
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
+    call_manager.set_max_incoming_videos(client_id, max);
+    Ok(())
+}
+
+/// Stops (or resumes) receiving a remote device's video while keeping its audio.
+/// This is local only: the remote device isn't told.
+pub fn set_remote_video_hidden(
+    call_manager: *mut IOSCallManagerHandle,
+    client_id: group_call::ClientId,
+    demux_id: group_call::DemuxId,
+    hidden: bool,
+) -> Result<()> {
+    info!("set_remote_video_hidden(): id: {}", client_id);
+
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
+    call_manager.set_remote_video_hidden(client_id, demux_id, hidden);
+    Ok(())
+}
+
+/// Drops all incoming and outgoing video, keeping audio, to free memory.
+/// Video requests are suspended until exit_low_resource_mode is called.
+pub fn enter_low_resource_mode(
+    call_manager: *mut IOSCallManagerHandle,
+    client_id: group_call::ClientId,
+) -> Result<()> {
+    info!("enter_low_resource_mode(): id: {}", client_id);
+
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
+    call_manager.set_low_resource_mode(client_id, true);
+    Ok(())
+}
+
+/// Restores the video that enter_low_resource_mode dropped, using the
+/// latest video requests.
+pub fn exit_low_resource_mode(
+    call_manager: *mut IOSCallManagerHandle,
+    client_id: group_call::ClientId,
+) -> Result<()> {
+    info!("exit_low_resource_mode(): id: {}", client_id);
+
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
+    call_manager.set_low_resource_mode(client_id, false);
+    Ok(())
+}
+
+/// Delivers remote device changes at most once per interval, except for
+/// devices leaving, which are reported right away.  Zero disables coalescing.
+pub fn set_roster_update_interval(
+    call_manager: *mut IOSCallManagerHandle,
+    client_id: group_call::ClientId,
+    interval_millis: u32,
+) -> Result<()> {
+    info!("set_roster_update_interval(): id: {}", client_id);
+
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
+    call_manager
+        .set_roster_update_interval(client_id, Duration::from_millis(interval_millis as u64));
+    Ok(())
+}
+
+/// Caps the resolution and frame rate of the outgoing video of the active
+/// 1:1 call and/or a group call, such as for a battery saver mode.  The
+/// caps are an upper bound in addition to the bandwidth mode, which can
+/// lower the video further but never lifts the caps.  0 leaves a
+/// dimension uncapped.
+pub fn set_outgoing_video_caps(
+    call_manager: *mut IOSCallManagerHandle,
+    call_id: Option<u64>,
+    client_id: Option<group_call::ClientId>,
+    max_width: u32,
+    max_height: u32,
+    max_fps: u32,
+) -> Result<()> {
+    info!(
+        "set_outgoing_video_caps(): call_id: {:?} client_id: {:?}",
+        call_id, client_id
+    );
+
+    let caps = media::VideoEncoderCaps {
+        max_width,
+        max_height,
+        max_fps,
+    };
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
+    if let Some(call_id) = call_id {
+        let call_id = CallId::from(call_id);
+        let active_call = call_manager.active_call()?;
+        if active_call.call_id() != call_id {
+            return Err(RingRtcError::CallIdNotFound(call_id).into());
+        }
+        active_call
+            .active_connection()?
+            .set_outgoing_video_caps(caps)?;
+    }
+    if let Some(client_id) = client_id {
+        call_manager.set_outgoing_video_caps(client_id, caps);
+    }
+    Ok(())
+}
+
+/// Returns the simulcast layers the group call's outgoing video encoder is
+/// configured to produce, lowest first, for debugging what the SFU gets.
+pub fn outgoing_simulcast_config(
+    call_manager: *mut IOSCallManagerHandle,
+    client_id: group_call::ClientId,
+) -> Result<Vec<media::LayerConfig>> {
+    info!("outgoing_simulcast_config(): client_id: {}", client_id);
+
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
+    call_manager.outgoing_simulcast_config(client_id)
+}
+
+/// Returns the bandwidth mode being enforced on either the active call
+/// or a group call, and whether RingRTC adapted it.
+pub fn effective_bandwidth_mode(
+    call_manager: *mut IOSCallManagerHandle,
+    call_id: Option<u64>,
+    client_id: Option<group_call::ClientId>,
+) -> Result<EffectiveBandwidthMode> {
+    let call_id = call_id.map(CallId::from);
+
+    info!(
+        "effective_bandwidth_mode(): call_id: {:?}, client_id: {:?}",
+        call_id, client_id
+    );
+
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
+    call_manager.effective_bandwidth_mode(call_id, client_id)
+}
+
+/// Returns the remote device talking in the group call right now, or None
+/// if everyone is silent.
+pub fn current_speaker(
+    call_manager: *mut IOSCallManagerHandle,
+    client_id: group_call::ClientId,
+) -> Result<Option<group_call::DemuxId>> {
+    debug!("current_speaker(): client_id: {}", client_id);
+
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
+    call_manager.current_speaker(client_id)
+}
+
+/// Sends a second audio track, such as an interpreter, in the group call.
+/// The track must have the ID "interpreter1".
+pub fn set_interpreter_track(
+    call_manager: *mut IOSCallManagerHandle,
+    client_id: group_call::ClientId,
+    native_audio_track: *const c_void,
+) -> Result<()> {
+    info!("set_interpreter_track(): id: {}", client_id);
+
+    let interpreter_audio_track =
+        media::AudioTrack::owned(native_audio_track as *const media::RffiAudioTrack);
+
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
+    call_manager.set_interpreter_track(client_id, interpreter_audio_track);
+    Ok(())
+}
+
+/// Turns on receiving (and allows sending) interpreter audio in the group call.
+pub fn set_interpreter_audio_enabled(
+    call_manager: *mut IOSCallManagerHandle,
+    client_id: group_call::ClientId,
+    enabled: bool,
+) -> Result<()> {
+    info!("set_interpreter_audio_enabled(): id: {}", client_id);
+
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
+    call_manager.set_interpreter_audio_enabled(client_id, enabled);
+    Ok(())
+}
+
+/// Plays or silences the interpreter audio of a remote device.
+pub fn set_remote_interpreter_audio_enabled(
+    call_manager: *mut IOSCallManagerHandle,
+    client_id: group_call::ClientId,
+    demux_id: group_call::DemuxId,
+    enabled: bool,
+) -> Result<()> {
+    info!("set_remote_interpreter_audio_enabled(): id: {}", client_id);
+
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
+    call_manager.set_remote_interpreter_audio_enabled(client_id, demux_id, enabled);
+    Ok(())
+}
+
 pub fn set_group_members(
-    call_manager: *mut IOSCallManager,
+    call_manager: *mut IOSCallManagerHandle,
     client_id: group_call::ClientId,
     members: Vec<group_call::GroupMemberInfo>,
 ) -> Result<()> {
     info!("set_group_members(): id: {}", client_id);
 
-    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
     call_manager.set_group_members(client_id, members);
     Ok(())
 }
 
 pub fn set_membership_proof(
-    call_manager: *mut IOSCallManager,
+    call_manager: *mut IOSCallManagerHandle,
     client_id: group_call::ClientId,
     proof: Vec<u8>,
 ) -> Result<()> {
     info!("set_group_membership_proof(): id: {}", client_id);
 
-    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    let call_manager = unsafe { IOSCallManagerHandle::as_call_manager(call_manager)? };
     call_manager.set_membership_proof(client_id, proof);
     Ok(())
 }
@@ -18,6 +18,8 @@ pub enum IOSError {
     CreateAppMediaStream,
     #[fail(display = "Creating IOSMediaStream failed")]
     CreateIOSMediaStream,
+    #[fail(display = "Call Manager panicked in: {}", _0)]
+    CallManagerPanic(String),
 
     // iOS Misc error codes
     #[fail(display = "Extracting native PeerConnection failed")]
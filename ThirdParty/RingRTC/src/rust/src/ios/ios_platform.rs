@@ -25,6 +25,8 @@ use crate::core::connection::{Connection, ConnectionType};
 use crate::core::platform::{Platform, PlatformItem};
 use crate::core::{group_call, signaling};
 use crate::ios::api::call_manager_interface::{
+    AppAudioLevel,
+    AppAudioLevelArray,
     AppByteSlice,
     AppCallContext,
     AppConnectionInterface,
@@ -32,6 +34,8 @@ use crate::ios::api::call_manager_interface::{
     AppHeaderArray,
     AppIceCandidateArray,
     AppInterface,
+    AppJoinedDevice,
+    AppJoinedDeviceArray,
     AppObject,
     AppOptionalBool,
     AppOptionalUInt32,
@@ -41,9 +45,9 @@ use crate::ios::api::call_manager_interface::{
 };
 use crate::ios::error::IOSError;
 use crate::ios::ios_media_stream::IOSMediaStream;
-use crate::webrtc::media::{MediaStream, VideoTrack};
+use crate::webrtc::media::{AudioTrack, MediaStream, VideoTrack};
 use crate::webrtc::peer_connection::{PeerConnection, RffiPeerConnection};
-use crate::webrtc::peer_connection_observer::PeerConnectionObserver;
+use crate::webrtc::peer_connection_observer::{CandidatePairType, PeerConnectionObserver};
 
 /// Concrete type for iOS AppIncomingMedia objects.
 impl PlatformItem for IOSMediaStream {}
@@ -129,14 +133,19 @@ impl Platform for IOSPlatform {
         // PeerConnection in Swift.
         let pc_observer =
             PeerConnectionObserver::new(connection_ptr, false /* enable_frame_encryption */)?;
+        connection.set_frame_encryption_enabled(pc_observer.frame_encryption_enabled())?;
 
+        // Only V2 uses DTLS; later versions derive the SRTP keys themselves.
+        let enable_dtls = !signaling_version.at_least(signaling::Version::V3);
+
+        let call_context = call.call_context()?;
         let app_connection_interface = (self.app_interface.onCreateConnectionInterface)(
             self.app_interface.object,
             pc_observer.rffi() as *mut c_void,
             remote_device_id,
-            call.call_context()?.object,
-            signaling_version.enable_dtls(),
-            true, /* always enable the RTP data channel */
+            call_context.object,
+            enable_dtls,
+            call_context.enableRtpDataChannel,
         );
 
         if app_connection_interface.object.is_null() || app_connection_interface.pc.is_null() {
@@ -268,27 +277,26 @@ impl Platform for IOSPlatform {
             return Ok(());
         }
 
-        let mut app_ice_candidates: Vec<AppByteSlice> = Vec::new();
-
-        for candidate in &send.ice.candidates_added {
-            let app_ice_candidate = app_slice_from_bytes(Some(&candidate.opaque));
-            app_ice_candidates.push(app_ice_candidate);
-        }
-
-        let app_ice_candidates_array = AppIceCandidateArray {
-            candidates: app_ice_candidates.as_ptr(),
-            count:      app_ice_candidates.len(),
-        };
-
         // The app_ice_candidates_array is passed up by reference and must
         // be consumed by the integration layer before returning.
-        (self.app_interface.onSendIceCandidates)(
-            self.app_interface.object,
-            u64::from(call_id) as u64,
-            remote_peer.ptr,
-            receiver_device_id,
-            broadcast,
-            &app_ice_candidates_array,
+        signaling::IceCandidate::for_each_batch(
+            &send.ice.candidates_added,
+            MAX_ICE_CANDIDATES_PER_BATCH,
+            |candidate| app_slice_from_bytes(Some(&candidate.opaque)),
+            |app_ice_candidates| {
+                let app_ice_candidates_array = AppIceCandidateArray {
+                    candidates: app_ice_candidates.as_ptr(),
+                    count:      app_ice_candidates.len(),
+                };
+                (self.app_interface.onSendIceCandidates)(
+                    self.app_interface.object,
+                    u64::from(call_id) as u64,
+                    remote_peer.ptr,
+                    receiver_device_id,
+                    broadcast,
+                    &app_ice_candidates_array,
+                )
+            },
         );
 
         Ok(())
@@ -343,6 +351,66 @@ impl Platform for IOSPlatform {
         Ok(())
     }
 
+    fn on_send_transfer_request(
+        &self,
+        remote_peer: &Self::AppRemotePeer,
+        call_id: CallId,
+        target_peer: &Self::AppRemotePeer,
+    ) -> Result<()> {
+        // Transfer requests are always broadcast
+        let broadcast = true;
+        let receiver_device_id = 0;
+
+        info!("on_send_transfer_request(): call_id: {}", call_id);
+
+        (self.app_interface.onSendTransferRequest)(
+            self.app_interface.object,
+            u64::from(call_id) as u64,
+            remote_peer.ptr,
+            receiver_device_id,
+            broadcast,
+            target_peer.ptr,
+        );
+
+        Ok(())
+    }
+
+    fn on_send_transfer_accepted(
+        &self,
+        remote_peer: &Self::AppRemotePeer,
+        call_id: CallId,
+    ) -> Result<()> {
+        // Transfer acceptances are always broadcast
+        let broadcast = true;
+        let receiver_device_id = 0;
+
+        info!("on_send_transfer_accepted(): call_id: {}", call_id);
+
+        (self.app_interface.onSendTransferAccepted)(
+            self.app_interface.object,
+            u64::from(call_id) as u64,
+            remote_peer.ptr,
+            receiver_device_id,
+            broadcast,
+        );
+
+        Ok(())
+    }
+
+    fn handle_transfer_request(
+        &self,
+        from_peer: &Self::AppRemotePeer,
+        to_peer: &Self::AppRemotePeer,
+    ) {
+        info!("handle_transfer_request():");
+
+        (self.app_interface.handleTransferRequest)(
+            self.app_interface.object,
+            from_peer.ptr,
+            to_peer.ptr,
+        );
+    }
+
     fn send_call_message(&self, recipient_uuid: Vec<u8>, message: Vec<u8>) -> Result<()> {
         info!("send_call_message():");
 
@@ -462,12 +530,33 @@ impl Platform for IOSPlatform {
         Ok(())
     }
 
+    fn acknowledges_ringing(&self) -> bool {
+        true
+    }
+
+    fn on_data_message(&self, remote_peer: &Self::AppRemotePeer, payload: Vec<u8>) -> Result<()> {
+        info!("on_data_message(): len: {}", payload.len());
+
+        (self.app_interface.onDataMessage)(
+            self.app_interface.object,
+            remote_peer.ptr,
+            app_slice_from_bytes(Some(&payload)),
+        );
+
+        Ok(())
+    }
+
+    fn on_audio_loopback_level(&self, level: u16) {
+        (self.app_interface.onAudioLoopbackLevel)(self.app_interface.object, level);
+    }
+
     // Group Calls
 
     fn handle_peek_response(
         &self,
         request_id: u32,
         joined_members: &[group_call::UserId],
+        joined_devices: &[(group_call::UserId, group_call::DemuxId)],
         creator: Option<group_call::UserId>,
         era_id: Option<&str>,
         max_devices: Option<u32>,
@@ -485,6 +574,19 @@ impl Platform for IOSPlatform {
             count: app_joined_members.len(),
         };
 
+        let app_joined_devices: Vec<AppJoinedDevice> = joined_devices
+            .iter()
+            .map(|(user_id, demux_id)| AppJoinedDevice {
+                userId:  app_slice_from_bytes(Some(user_id)),
+                demuxId: *demux_id,
+            })
+            .collect();
+
+        let app_joined_devices_array = AppJoinedDeviceArray {
+            devices: app_joined_devices.as_ptr(),
+            count:   app_joined_devices.len(),
+        };
+
         let app_creator = app_slice_from_bytes(creator.as_ref());
         let era_id = era_id.map(String::from);
         let app_era_id = app_slice_from_str(era_id.as_ref());
@@ -495,6 +597,7 @@ impl Platform for IOSPlatform {
             self.app_interface.object,
             request_id,
             app_joined_members_array,
+            app_joined_devices_array,
             app_creator,
             app_era_id,
             app_max_devices,
@@ -502,6 +605,10 @@ impl Platform for IOSPlatform {
         );
     }
 
+    fn handle_peek_timed_out(&self, request_id: u32) {
+        (self.app_interface.handlePeekTimedOut)(self.app_interface.object, request_id);
+    }
+
     fn request_membership_proof(&self, client_id: group_call::ClientId) {
         (self.app_interface.requestMembershipProof)(self.app_interface.object, client_id);
     }
@@ -526,6 +633,7 @@ impl Platform for IOSPlatform {
         &self,
         client_id: group_call::ClientId,
         join_state: group_call::JoinState,
+        outgoing_audio_muted: Option<bool>,
     ) {
         (self.app_interface.handleJoinStateChanged)(
             self.app_interface.object,
@@ -535,6 +643,7 @@ impl Platform for IOSPlatform {
                 group_call::JoinState::Joining => 1,
                 group_call::JoinState::Joined(_, _) => 2,
             },
+            app_option_from_bool(outgoing_audio_muted),
         );
     }
 
@@ -585,10 +694,26 @@ impl Platform for IOSPlatform {
         );
     }
 
+    fn handle_incoming_interpreter_audio_track(
+        &self,
+        client_id: group_call::ClientId,
+        remote_demux_id: group_call::DemuxId,
+        _incoming_interpreter_audio_track: AudioTrack,
+    ) {
+        // The group call keeps the track; the app turns it on and off with
+        // ringrtcSetRemoteInterpreterAudioEnabled.
+        (self.app_interface.handleIncomingInterpreterAudioTrack)(
+            self.app_interface.object,
+            client_id,
+            remote_demux_id,
+        );
+    }
+
     fn handle_peek_changed(
         &self,
         client_id: group_call::ClientId,
         joined_members: &[group_call::UserId],
+        joined_devices: &[(group_call::UserId, group_call::DemuxId)],
         creator: Option<group_call::UserId>,
         era_id: Option<&str>,
         max_devices: Option<u32>,
@@ -606,6 +731,19 @@ impl Platform for IOSPlatform {
             count: app_joined_members.len(),
         };
 
+        let app_joined_devices: Vec<AppJoinedDevice> = joined_devices
+            .iter()
+            .map(|(user_id, demux_id)| AppJoinedDevice {
+                userId:  app_slice_from_bytes(Some(user_id)),
+                demuxId: *demux_id,
+            })
+            .collect();
+
+        let app_joined_devices_array = AppJoinedDeviceArray {
+            devices: app_joined_devices.as_ptr(),
+            count:   app_joined_devices.len(),
+        };
+
         let app_creator = app_slice_from_bytes(creator.as_ref());
         let era_id = era_id.map(String::from);
         let app_era_id = app_slice_from_str(era_id.as_ref());
@@ -616,6 +754,7 @@ impl Platform for IOSPlatform {
             self.app_interface.object,
             client_id,
             app_joined_members_array,
+            app_joined_devices_array,
             app_creator,
             app_era_id,
             app_max_devices,
@@ -623,8 +762,106 @@ impl Platform for IOSPlatform {
         );
     }
 
+    fn handle_era_changed(
+        &self,
+        client_id: group_call::ClientId,
+        old_era_id: Option<&str>,
+        new_era_id: Option<&str>,
+    ) {
+        let old_era_id = old_era_id.map(String::from);
+        let new_era_id = new_era_id.map(String::from);
+
+        (self.app_interface.handleEraChanged)(
+            self.app_interface.object,
+            client_id,
+            app_slice_from_str(old_era_id.as_ref()),
+            app_slice_from_str(new_era_id.as_ref()),
+        );
+    }
+
+    fn handle_participant_quality_changed(
+        &self,
+        client_id: group_call::ClientId,
+        remote_demux_id: group_call::DemuxId,
+        quality: u8,
+    ) {
+        (self.app_interface.handleParticipantQualityChanged)(
+            self.app_interface.object,
+            client_id,
+            remote_demux_id,
+            quality,
+        );
+    }
+
+    fn handle_audio_levels(
+        &self,
+        client_id: group_call::ClientId,
+        audio_levels: Vec<(group_call::DemuxId, u8)>,
+    ) {
+        let app_audio_levels: Vec<AppAudioLevel> = audio_levels
+            .iter()
+            .map(|(demux_id, level)| AppAudioLevel {
+                demuxId: *demux_id,
+                level:   *level,
+            })
+            .collect();
+
+        let app_audio_levels_array = AppAudioLevelArray {
+            levels: app_audio_levels.as_ptr(),
+            count:  app_audio_levels.len(),
+        };
+
+        (self.app_interface.handleAudioLevels)(
+            self.app_interface.object,
+            client_id,
+            app_audio_levels_array,
+        );
+    }
+
+    fn handle_media_keys_received(
+        &self,
+        client_id: group_call::ClientId,
+        remote_demux_id: group_call::DemuxId,
+    ) {
+        (self.app_interface.handleMediaKeysReceived)(
+            self.app_interface.object,
+            client_id,
+            remote_demux_id,
+        );
+    }
+
+    fn handle_candidate_pair_changed(
+        &self,
+        client_id: group_call::ClientId,
+        local_demux_id: group_call::DemuxId,
+        candidate_pair_type: CandidatePairType,
+    ) {
+        (self.app_interface.handleCandidatePairChanged)(
+            self.app_interface.object,
+            client_id,
+            local_demux_id,
+            candidate_pair_type as i32,
+        );
+    }
+
+    fn handle_join_rejected(
+        &self,
+        client_id: group_call::ClientId,
+        http_status: u16,
+        message: Option<&str>,
+    ) {
+        let message = message.map(String::from);
+
+        (self.app_interface.handleJoinRejected)(
+            self.app_interface.object,
+            client_id,
+            http_status,
+            app_slice_from_str(message.as_ref()),
+        );
+    }
+
     fn handle_ended(&self, client_id: group_call::ClientId, reason: group_call::EndReason) {
-        (self.app_interface.handleEnded)(self.app_interface.object, client_id, reason as i32);
+        (self.app_interface.handleEnded)(self.app_interface.object, client_id, reason.as_i32());
     }
 }
 
@@ -643,6 +880,9 @@ impl IOSPlatform {
     }
 }
 
+/// The most ICE candidates passed to the app in one onSendIceCandidates.
+const MAX_ICE_CANDIDATES_PER_BATCH: usize = 20;
+
 fn app_slice_from_bytes(bytes: Option<&Vec<u8>>) -> AppByteSlice {
     match bytes {
         None => AppByteSlice {
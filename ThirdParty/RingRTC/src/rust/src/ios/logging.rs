@@ -13,7 +13,7 @@ use libc::size_t;
 
 use log::{LevelFilter, Log, Metadata, Record};
 
-use crate::common::Result;
+use crate::common::{log_buffer, Result};
 use crate::ios::api::call_manager_interface::AppByteSlice;
 use crate::ios::error::IOSError;
 
@@ -62,6 +62,7 @@ impl Log for IOSLogger {
             }
 
             let message_string = format!("{}", record.args());
+            log_buffer::record(format!("{} {}", record.level(), message_string));
             let message_byte_slice = AppByteSlice {
                 bytes: message_string.as_ptr(),
                 len:   message_string.len() as size_t,
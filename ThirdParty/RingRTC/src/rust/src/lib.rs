@@ -43,6 +43,7 @@ pub mod core {
     pub mod crypto;
     pub mod group_call;
     pub mod http_client;
+    pub mod ice_server_resolver;
     pub mod platform;
     pub mod sfu_client;
     pub mod signaling;
@@ -94,6 +95,7 @@ pub mod native;
 
 /// Foreign Function Interface (FFI) to WebRTC C++ library.
 pub mod webrtc {
+    pub mod audio_loopback;
     pub mod data_channel;
     pub mod ice_gatherer;
     #[cfg(feature = "simnet")]
@@ -110,6 +112,7 @@ pub mod webrtc {
     pub mod stats_observer;
     #[cfg(not(feature = "sim"))]
     mod ffi {
+        pub mod audio_loopback;
         pub mod data_channel;
         pub mod ice_gatherer;
         pub mod logging;
@@ -123,6 +126,7 @@ pub mod webrtc {
     }
     #[cfg(feature = "sim")]
     pub mod sim {
+        pub mod audio_loopback;
         pub mod data_channel;
         pub mod ice_gatherer;
         pub mod media;
@@ -21,6 +21,7 @@ pub mod hangup {
         HangupDeclined = 2,
         HangupBusy = 3,
         HangupNeedPermission = 4,
+        HangupAway = 5,
     }
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -54,4 +55,8 @@ pub struct Data {
     pub sequence_number: ::std::option::Option<u64>,
     #[prost(message, optional, tag="5")]
     pub receiver_status: ::std::option::Option<ReceiverStatus>,
+    /// Opaque payload from the application. It is not part of the
+    /// accumulated state, so it is only ever sent once.
+    #[prost(bytes, optional, tag="6")]
+    pub application_data: ::std::option::Option<std::vec::Vec<u8>>,
 }
@@ -104,6 +104,9 @@ pub struct SfuToDevice {
     /// Called forwardedEndpoints in the SFU's RtpDataChannelMessages.proto
     #[prost(message, optional, tag="7")]
     pub received_from_device: ::std::option::Option<sfu_to_device::ReceivedFromDevice>,
+    /// Sent periodically, for every device in the call.
+    #[prost(message, repeated, tag="8")]
+    pub connection_qualities: ::std::vec::Vec<sfu_to_device::ConnectionQuality>,
 }
 pub mod sfu_to_device {
     /// Called EndpointChangedMessage in the SFU's RtpDataChannelMessages.proto
@@ -172,4 +175,14 @@ pub mod sfu_to_device {
         #[prost(uint64, repeated, packed="false", tag="3")]
         pub all_devices_short_device_ids: ::std::vec::Vec<u64>,
     }
+    /// The quality of a remote device's uplink to the SFU, as the SFU sees it.
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct ConnectionQuality {
+        /// Functionally the same as a DemuxId, but oddly different.
+        #[prost(uint64, optional, tag="1")]
+        pub short_device_id: ::std::option::Option<u64>,
+        /// From 0 (unusable) to 100 (perfect).
+        #[prost(uint32, optional, tag="2")]
+        pub quality: ::std::option::Option<u32>,
+    }
 }
@@ -6,6 +6,8 @@ pub struct Offer {
     pub v3_or_v2: ::std::option::Option<ConnectionParametersV3OrV2>,
     #[prost(message, optional, tag="4")]
     pub v4: ::std::option::Option<ConnectionParametersV4>,
+    #[prost(message, optional, tag="5")]
+    pub v5: ::std::option::Option<ConnectionParametersV5>,
 }
 /// A serialized one these goes in the "opaque" field of the CallingMessage::Offer in SignalService.proto
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -14,6 +16,8 @@ pub struct Answer {
     pub v3_or_v2: ::std::option::Option<ConnectionParametersV3OrV2>,
     #[prost(message, optional, tag="4")]
     pub v4: ::std::option::Option<ConnectionParametersV4>,
+    #[prost(message, optional, tag="5")]
+    pub v5: ::std::option::Option<ConnectionParametersV5>,
 }
 /// A serialized one these goes in the "opaque" field of the CallingMessage::Ice in SignalService.proto
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -60,6 +64,24 @@ pub struct ConnectionParametersV4 {
     #[prost(uint64, optional, tag="5")]
     pub max_bitrate_bps: ::std::option::Option<u64>,
 }
+/// The V5 protocol is for the new key exchange, which will add its fields
+/// here. Until then it is the same as V4.  Senders should keep including
+/// a v4 (and/or V3OrV2) so that clients that don't know V5 can fall back.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ConnectionParametersV5 {
+    #[prost(bytes, optional, tag="1")]
+    pub public_key: ::std::option::Option<std::vec::Vec<u8>>,
+    #[prost(string, optional, tag="2")]
+    pub ice_ufrag: ::std::option::Option<std::string::String>,
+    #[prost(string, optional, tag="3")]
+    pub ice_pwd: ::std::option::Option<std::string::String>,
+    /// In other words, the video codecs the sender can receive.
+    #[prost(message, repeated, tag="4")]
+    pub receive_video_codecs: ::std::vec::Vec<VideoCodec>,
+    /// Used at call establishment to convey the bitrate that should be used for sending.
+    #[prost(uint64, optional, tag="5")]
+    pub max_bitrate_bps: ::std::option::Option<u64>,
+}
 /// A generic calling message that is opaque to the application but interpreted by RingRTC.
 /// A serialized one of these goes into the "Opaque" field in the CallingMessage variant
 /// in Signal protocol messages.
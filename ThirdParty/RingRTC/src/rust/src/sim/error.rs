@@ -20,6 +20,10 @@ pub enum SimError {
     SendHangupError,
     #[fail(display = "Simulation: Intentional: Send busy failed")]
     SendBusyError,
+    #[fail(display = "Simulation: Intentional: Send transfer request failed")]
+    SendTransferRequestError,
+    #[fail(display = "Simulation: Intentional: Send transfer accepted failed")]
+    SendTransferAcceptedError,
     #[fail(display = "Simulation: Intentional: Send accepted failed")]
     SendAcceptedError,
     #[fail(display = "Simulation: Intentional: Add Media Stream failed")]
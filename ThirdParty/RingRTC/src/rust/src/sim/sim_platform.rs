@@ -28,11 +28,14 @@ use crate::core::{group_call, signaling};
 use crate::sim::error::SimError;
 use crate::webrtc::media::{MediaStream, VideoTrack};
 use crate::webrtc::peer_connection::PeerConnection;
+use crate::webrtc::peer_connection_observer::NetworkRoute;
 use crate::webrtc::sim::peer_connection::RffiPeerConnection;
 
 /// Simulation implementation for platform::Platform::{AppIncomingMedia,
 /// AppRemotePeer, AppCallContext}
 type SimPlatformItem = String;
+
+type GroupConnectionStateUpdate = (group_call::ClientId, group_call::ConnectionState);
 impl PlatformItem for SimPlatformItem {}
 
 #[derive(Default)]
@@ -53,8 +56,14 @@ struct SimStats {
     busy_hangups_sent:            AtomicUsize,
     /// Number of need permission hangups sent
     need_permission_hangups_sent: AtomicUsize,
+    /// Number of away hangups sent
+    away_hangups_sent:            AtomicUsize,
     /// Number of busy messages sent
     busys_sent:                   AtomicUsize,
+    /// Number of transfer request messages sent
+    transfer_requests_sent:       AtomicUsize,
+    /// Number of transfer accepted messages sent
+    transfer_accepteds_sent:      AtomicUsize,
     /// Number of start outgoing call events
     start_outgoing:               AtomicUsize,
     /// Number of start incoming call events
@@ -63,6 +72,14 @@ struct SimStats {
     call_concluded:               AtomicUsize,
     /// Track stream counts
     stream_count:                 AtomicUsize,
+    /// Number of data messages received
+    data_messages_received:       AtomicUsize,
+    /// Number of outgoing key frames reported
+    outgoing_keyframes:           AtomicUsize,
+    /// Number of audio loopback levels reported
+    audio_loopback_levels:        AtomicUsize,
+    /// Number of signaling messages assumed sent
+    signaling_sent_assumed:       AtomicUsize,
 }
 
 /// Simulation implementation of platform::Platform.
@@ -79,10 +96,39 @@ pub struct SimPlatform {
     event_map:                    Arc<Mutex<HashMap<ApplicationEvent, usize>>>,
     /// Track whether disconnecting of incoming media happened
     incoming_media_disconnected:  Arc<AtomicBool>,
+    /// Track ICE candidate errors: (url, error_code, error_text)
+    ice_candidate_errors:         Arc<Mutex<Vec<(String, u16, String)>>>,
+    /// Track the IDs of HTTP requests sent
+    http_request_ids:             Arc<Mutex<Vec<u32>>>,
+    /// Track the headers of HTTP requests sent
+    http_request_headers:         Arc<Mutex<Vec<HashMap<String, String>>>>,
+    /// Track group call connection state updates
+    group_connection_states:      Arc<Mutex<Vec<GroupConnectionStateUpdate>>>,
+    /// Track the request IDs of peek responses
+    peek_responses:               Arc<Mutex<Vec<u32>>>,
+    /// Track the request IDs of peeks that timed out
+    peek_timeouts:                Arc<Mutex<Vec<u32>>>,
+    /// Track group call end notifications
+    group_call_ends:              Arc<Mutex<Vec<(group_call::ClientId, group_call::EndReason)>>>,
+    /// Track received transfer requests: (from_peer, to_peer)
+    transfer_requests:            Arc<Mutex<Vec<(String, String)>>>,
+    /// Track received DTMF tones, in order
+    dtmf_received:                Arc<Mutex<Vec<char>>>,
+    /// Track network route changes, in order
+    network_routes:               Arc<Mutex<Vec<NetworkRoute>>>,
     /// Call Manager
     call_manager:                 Arc<Mutex<Option<CallManager<Self>>>>,
     /// True to manually require message_sent() to be invoked for Ice messages.
     no_auto_message_sent_for_ice: Arc<AtomicBool>,
+    /// True to have the Call Manager assume every signaling message is sent.
+    assume_messages_sent:         Arc<AtomicBool>,
+    /// True to have early accepts wait for notify_ringing().
+    acknowledges_ringing:         Arc<AtomicBool>,
+    /// True to create connections that embed ICE candidates in the offer
+    /// or answer instead of trickling them.
+    disable_trickle_ice:          Arc<AtomicBool>,
+    /// True to create connections with frame encryption enabled.
+    enable_frame_encryption:      Arc<AtomicBool>,
 }
 
 impl fmt::Display for SimPlatform {
@@ -134,6 +180,12 @@ impl Platform for SimPlatform {
         )
         .unwrap();
         connection.set_app_connection(fake_pc).unwrap();
+        connection
+            .set_trickle_ice(!self.disable_trickle_ice.load(Ordering::Acquire))
+            .unwrap();
+        connection
+            .set_frame_encryption_enabled(self.enable_frame_encryption.load(Ordering::Acquire))
+            .unwrap();
 
         let peer_connection =
             PeerConnection::unowned(connection.app_connection_ptr_for_tests(), std::ptr::null());
@@ -175,6 +227,87 @@ impl Platform for SimPlatform {
         Ok(())
     }
 
+    fn on_ice_candidate_error(
+        &self,
+        remote_peer: &Self::AppRemotePeer,
+        url: String,
+        error_code: u16,
+        error_text: String,
+    ) -> Result<()> {
+        info!(
+            "on_ice_candidate_error(): remote_peer: {}, url: {}, error_code: {}",
+            remote_peer, url, error_code
+        );
+
+        let mut errors = self.ice_candidate_errors.lock().unwrap();
+        errors.push((url, error_code, error_text));
+        Ok(())
+    }
+
+    fn on_data_message(&self, remote_peer: &Self::AppRemotePeer, payload: Vec<u8>) -> Result<()> {
+        info!(
+            "on_data_message(): remote_peer: {}, len: {}",
+            remote_peer,
+            payload.len()
+        );
+
+        let _ = self
+            .stats
+            .data_messages_received
+            .fetch_add(1, Ordering::AcqRel);
+        Ok(())
+    }
+
+    fn on_dtmf(&self, remote_peer: &Self::AppRemotePeer, tone: char) -> Result<()> {
+        info!("on_dtmf(): remote_peer: {}", remote_peer);
+
+        let mut dtmf_received = self.dtmf_received.lock().unwrap();
+        dtmf_received.push(tone);
+        Ok(())
+    }
+
+    fn on_outgoing_keyframe(&self, remote_peer: &Self::AppRemotePeer) -> Result<()> {
+        info!("on_outgoing_keyframe(): remote_peer: {}", remote_peer);
+
+        let _ = self
+            .stats
+            .outgoing_keyframes
+            .fetch_add(1, Ordering::AcqRel);
+        Ok(())
+    }
+
+    fn on_network_route_changed(
+        &self,
+        remote_peer: &Self::AppRemotePeer,
+        network_route: NetworkRoute,
+    ) -> Result<()> {
+        info!(
+            "on_network_route_changed(): remote_peer: {}, network_route: {:?}",
+            remote_peer, network_route
+        );
+
+        let mut network_routes = self.network_routes.lock().unwrap();
+        network_routes.push(network_route);
+        Ok(())
+    }
+
+    fn assume_messages_sent(&self) -> bool {
+        self.assume_messages_sent.load(Ordering::Acquire)
+    }
+
+    fn acknowledges_ringing(&self) -> bool {
+        self.acknowledges_ringing.load(Ordering::Acquire)
+    }
+
+    fn on_signaling_sent_assumed(&self, call_id: CallId) {
+        info!("on_signaling_sent_assumed(): call_id: {}", call_id);
+
+        let _ = self
+            .stats
+            .signaling_sent_assumed
+            .fetch_add(1, Ordering::AcqRel);
+    }
+
     fn on_send_offer(
         &self,
         remote_peer: &Self::AppRemotePeer,
@@ -305,6 +438,9 @@ impl Platform for SimPlatform {
                         .need_permission_hangups_sent
                         .fetch_add(1, Ordering::AcqRel);
                 }
+                signaling::Hangup::Away => {
+                    let _ = self.stats.away_hangups_sent.fetch_add(1, Ordering::AcqRel);
+                }
             }
             if self.force_internal_fault.load(Ordering::Acquire) {
                 self.message_send_failure(call_id).unwrap();
@@ -334,19 +470,88 @@ impl Platform for SimPlatform {
         }
     }
 
+    fn on_send_transfer_request(
+        &self,
+        remote_peer: &Self::AppRemotePeer,
+        call_id: CallId,
+        target_peer: &Self::AppRemotePeer,
+    ) -> Result<()> {
+        info!(
+            "on_send_transfer_request(): remote_peer: {}, call_id: {}, target_peer: {}",
+            remote_peer, call_id, target_peer
+        );
+
+        if self.force_internal_fault.load(Ordering::Acquire) {
+            Err(SimError::SendTransferRequestError.into())
+        } else {
+            let _ = self
+                .stats
+                .transfer_requests_sent
+                .fetch_add(1, Ordering::AcqRel);
+            self.message_sent(call_id).unwrap();
+            Ok(())
+        }
+    }
+
+    fn on_send_transfer_accepted(
+        &self,
+        remote_peer: &Self::AppRemotePeer,
+        call_id: CallId,
+    ) -> Result<()> {
+        info!(
+            "on_send_transfer_accepted(): remote_peer: {}, call_id: {}",
+            remote_peer, call_id
+        );
+
+        if self.force_internal_fault.load(Ordering::Acquire) {
+            Err(SimError::SendTransferAcceptedError.into())
+        } else {
+            let _ = self
+                .stats
+                .transfer_accepteds_sent
+                .fetch_add(1, Ordering::AcqRel);
+            self.message_sent(call_id).unwrap();
+            Ok(())
+        }
+    }
+
+    fn handle_transfer_request(
+        &self,
+        from_peer: &Self::AppRemotePeer,
+        to_peer: &Self::AppRemotePeer,
+    ) {
+        info!(
+            "handle_transfer_request(): from_peer: {}, to_peer: {}",
+            from_peer, to_peer
+        );
+
+        self.transfer_requests
+            .lock()
+            .unwrap()
+            .push((from_peer.clone(), to_peer.clone()));
+    }
+
     fn send_call_message(&self, _recipient_uuid: Vec<u8>, _message: Vec<u8>) -> Result<()> {
         unimplemented!()
     }
 
     fn send_http_request(
         &self,
-        _request_id: u32,
-        _url: String,
+        request_id: u32,
+        url: String,
         _method: HttpMethod,
-        _headers: HashMap<String, String>,
+        headers: HashMap<String, String>,
         _body: Option<Vec<u8>>,
     ) -> Result<()> {
-        unimplemented!()
+        info!(
+            "send_http_request(): request_id: {}, url: {}",
+            request_id, url
+        );
+
+        let mut request_ids = self.http_request_ids.lock().unwrap();
+        request_ids.push(request_id);
+        self.http_request_headers.lock().unwrap().push(headers);
+        Ok(())
     }
 
     fn create_incoming_media(
@@ -414,28 +619,46 @@ impl Platform for SimPlatform {
         }
     }
 
-    fn request_membership_proof(&self, _client_id: group_call::ClientId) {
-        unimplemented!()
+    fn on_audio_loopback_level(&self, level: u16) {
+        info!("on_audio_loopback_level(): {}", level);
+        let _ = self
+            .stats
+            .audio_loopback_levels
+            .fetch_add(1, Ordering::AcqRel);
     }
 
-    fn request_group_members(&self, _client_id: group_call::ClientId) {
-        unimplemented!()
+    fn request_membership_proof(&self, client_id: group_call::ClientId) {
+        info!("request_membership_proof(): client_id: {}", client_id);
+    }
+
+    fn request_group_members(&self, client_id: group_call::ClientId) {
+        info!("request_group_members(): client_id: {}", client_id);
     }
 
     fn handle_connection_state_changed(
         &self,
-        _client_id: group_call::ClientId,
-        _connection_state: group_call::ConnectionState,
+        client_id: group_call::ClientId,
+        connection_state: group_call::ConnectionState,
     ) {
-        unimplemented!()
+        info!(
+            "handle_connection_state_changed(): client_id: {}, state: {:?}",
+            client_id, connection_state
+        );
+
+        let mut states = self.group_connection_states.lock().unwrap();
+        states.push((client_id, connection_state));
     }
 
     fn handle_join_state_changed(
         &self,
-        _client_id: group_call::ClientId,
-        _join_state: group_call::JoinState,
+        client_id: group_call::ClientId,
+        join_state: group_call::JoinState,
+        outgoing_audio_muted: Option<bool>,
     ) {
-        unimplemented!()
+        info!(
+            "handle_join_state_changed(): client_id: {}, state: {:?}, outgoing_audio_muted: {:?}",
+            client_id, join_state, outgoing_audio_muted
+        );
     }
 
     fn handle_remote_devices_changed(
@@ -459,6 +682,7 @@ impl Platform for SimPlatform {
         &self,
         _client_id: group_call::ClientId,
         _joined_members: &[group_call::UserId],
+        _joined_devices: &[(group_call::UserId, group_call::DemuxId)],
         _creator: Option<group_call::UserId>,
         _era_id: Option<&str>,
         _max_devices: Option<u32>,
@@ -469,18 +693,33 @@ impl Platform for SimPlatform {
 
     fn handle_peek_response(
         &self,
-        _request_id: u32,
+        request_id: u32,
         _joined_members: &[group_call::UserId],
+        _joined_devices: &[(group_call::UserId, group_call::DemuxId)],
         _creator: Option<group_call::UserId>,
         _era_id: Option<&str>,
         _max_devices: Option<u32>,
         _device_count: u32,
     ) {
-        unimplemented!()
+        info!("handle_peek_response(): request_id: {}", request_id);
+
+        self.peek_responses.lock().unwrap().push(request_id);
     }
 
-    fn handle_ended(&self, _client_id: group_call::ClientId, _reason: group_call::EndReason) {
-        unimplemented!()
+    fn handle_peek_timed_out(&self, request_id: u32) {
+        info!("handle_peek_timed_out(): request_id: {}", request_id);
+
+        self.peek_timeouts.lock().unwrap().push(request_id);
+    }
+
+    fn handle_ended(&self, client_id: group_call::ClientId, reason: group_call::EndReason) {
+        info!(
+            "handle_ended(): client_id: {}, reason: {:?}",
+            client_id, reason
+        );
+
+        let mut ends = self.group_call_ends.lock().unwrap();
+        ends.push((client_id, reason));
     }
 }
 
@@ -526,6 +765,23 @@ impl SimPlatform {
             .store(enable, Ordering::Release);
     }
 
+    pub fn assume_all_messages_sent(&mut self, enable: bool) {
+        self.assume_messages_sent.store(enable, Ordering::Release);
+    }
+
+    pub fn acknowledge_ringing(&mut self, enable: bool) {
+        self.acknowledges_ringing.store(enable, Ordering::Release);
+    }
+
+    pub fn disable_trickle_ice(&mut self, disable: bool) {
+        self.disable_trickle_ice.store(disable, Ordering::Release);
+    }
+
+    pub fn enable_frame_encryption(&mut self, enable: bool) {
+        self.enable_frame_encryption
+            .store(enable, Ordering::Release);
+    }
+
     pub fn event_count(&self, event: ApplicationEvent) -> usize {
         let mut errors = 0;
         let map = self.event_map.lock().unwrap();
@@ -557,6 +813,9 @@ impl SimPlatform {
             ApplicationEvent::EndedInternalFailure,
             ApplicationEvent::EndedConnectionFailure,
             ApplicationEvent::EndedAppDroppedCall,
+            ApplicationEvent::EndedTransferred,
+            ApplicationEvent::EndedRemoteAway,
+            ApplicationEvent::EndedNetworkChanged,
         ];
         for event in ended_events {
             ends += self.event_count(event);
@@ -565,6 +824,10 @@ impl SimPlatform {
         ends
     }
 
+    pub fn signaling_sent_assumed(&self) -> usize {
+        self.stats.signaling_sent_assumed.load(Ordering::Acquire)
+    }
+
     pub fn offers_sent(&self) -> usize {
         self.stats.offers_sent.load(Ordering::Acquire)
     }
@@ -599,10 +862,26 @@ impl SimPlatform {
             .load(Ordering::Acquire)
     }
 
+    pub fn away_hangups_sent(&self) -> usize {
+        self.stats.away_hangups_sent.load(Ordering::Acquire)
+    }
+
     pub fn busys_sent(&self) -> usize {
         self.stats.busys_sent.load(Ordering::Acquire)
     }
 
+    pub fn transfer_requests_sent(&self) -> usize {
+        self.stats.transfer_requests_sent.load(Ordering::Acquire)
+    }
+
+    pub fn transfer_accepteds_sent(&self) -> usize {
+        self.stats.transfer_accepteds_sent.load(Ordering::Acquire)
+    }
+
+    pub fn transfer_requests(&self) -> Vec<(String, String)> {
+        self.transfer_requests.lock().unwrap().clone()
+    }
+
     pub fn stream_count(&self) -> usize {
         self.stats.stream_count.load(Ordering::Acquire)
     }
@@ -622,4 +901,54 @@ impl SimPlatform {
     pub fn call_concluded_count(&self) -> usize {
         self.stats.call_concluded.load(Ordering::Acquire)
     }
+
+    pub fn ice_candidate_errors(&self) -> Vec<(String, u16, String)> {
+        self.ice_candidate_errors.lock().unwrap().clone()
+    }
+
+    pub fn data_messages_received(&self) -> usize {
+        self.stats.data_messages_received.load(Ordering::Acquire)
+    }
+
+    pub fn outgoing_keyframes(&self) -> usize {
+        self.stats.outgoing_keyframes.load(Ordering::Acquire)
+    }
+
+    pub fn dtmf_received(&self) -> Vec<char> {
+        self.dtmf_received.lock().unwrap().clone()
+    }
+
+    pub fn network_routes(&self) -> Vec<NetworkRoute> {
+        self.network_routes.lock().unwrap().clone()
+    }
+
+    pub fn audio_loopback_level_count(&self) -> usize {
+        self.stats.audio_loopback_levels.load(Ordering::Acquire)
+    }
+
+    pub fn http_request_ids(&self) -> Vec<u32> {
+        self.http_request_ids.lock().unwrap().clone()
+    }
+
+    pub fn http_request_headers(&self) -> Vec<HashMap<String, String>> {
+        self.http_request_headers.lock().unwrap().clone()
+    }
+
+    pub fn peek_responses(&self) -> Vec<u32> {
+        self.peek_responses.lock().unwrap().clone()
+    }
+
+    pub fn peek_timeouts(&self) -> Vec<u32> {
+        self.peek_timeouts.lock().unwrap().clone()
+    }
+
+    pub fn group_connection_states(
+        &self,
+    ) -> Vec<(group_call::ClientId, group_call::ConnectionState)> {
+        self.group_connection_states.lock().unwrap().clone()
+    }
+
+    pub fn group_call_ends(&self) -> Vec<(group_call::ClientId, group_call::EndReason)> {
+        self.group_call_ends.lock().unwrap().clone()
+    }
 }
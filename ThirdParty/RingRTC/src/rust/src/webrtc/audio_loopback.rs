@@ -0,0 +1,91 @@
+//
+// Copyright 2021 Signal Messenger, LLC
+// SPDX-License-Identifier: AGPL-3.0-only
+//
+
+//! WebRTC Audio Loopback
+//!
+//! Plays captured audio straight back out, without any network, so
+//! users can check their microphone and speaker before a call.
+
+use crate::common::Result;
+use crate::core::util::RustObject;
+use crate::error::RingRtcError;
+
+#[cfg(not(feature = "sim"))]
+use crate::webrtc::ffi::audio_loopback as loopback;
+#[cfg(not(feature = "sim"))]
+pub use crate::webrtc::ffi::audio_loopback::RffiAudioLoopback;
+
+#[cfg(feature = "sim")]
+use crate::webrtc::sim::audio_loopback as loopback;
+#[cfg(feature = "sim")]
+pub use crate::webrtc::sim::audio_loopback::RffiAudioLoopback;
+
+/// Called with the peak level of the captured audio, from 0 to 32767,
+/// roughly every 100ms.
+pub type AudioLevelHandler = Box<dyn Fn(u16) + Send + Sync>;
+
+/// A running audio loopback.  Dropping it stops the loopback.
+pub struct AudioLoopback {
+    /// Pointer to C++ webrtc::rffi::AudioLoopback object.
+    rffi:          *mut RffiAudioLoopback,
+    /// Boxed again so the C++ side gets a thin, stable pointer.
+    level_handler: *mut AudioLevelHandler,
+}
+
+// The C++ object does its own synchronization.
+unsafe impl Send for AudioLoopback {}
+
+impl AudioLoopback {
+    /// Starts routing captured audio to playout, reporting the level of
+    /// the captured audio to `level_handler`.
+    pub fn start(level_handler: AudioLevelHandler) -> Result<Self> {
+        let level_handler = Box::into_raw(Box::new(level_handler));
+        let rffi = unsafe {
+            loopback::Rust_startAudioLoopback(level_handler as RustObject, AUDIO_LOOPBACK_CBS_PTR)
+        };
+        if rffi.is_null() {
+            // Nothing refers to the handler if starting failed.
+            drop(unsafe { Box::from_raw(level_handler) });
+            return Err(RingRtcError::StartAudioLoopback.into());
+        }
+        Ok(Self {
+            rffi,
+            level_handler,
+        })
+    }
+}
+
+impl Drop for AudioLoopback {
+    fn drop(&mut self) {
+        info!("AudioLoopback::drop()");
+        unsafe {
+            // No more callbacks are made once this returns.
+            loopback::Rust_stopAudioLoopback(self.rffi);
+            drop(Box::from_raw(self.level_handler));
+        }
+    }
+}
+
+/// AudioLoopback OnAudioLevel() callback.
+#[allow(non_snake_case)]
+extern "C" fn audio_loopback_OnAudioLevel(level_handler: RustObject, level: u16) {
+    let level_handler = level_handler as *const AudioLevelHandler;
+    match unsafe { level_handler.as_ref() } {
+        Some(level_handler) => level_handler(level),
+        None => error!("audio_loopback_OnAudioLevel(): null level handler"),
+    }
+}
+
+/// AudioLoopback callback function pointers.
+#[repr(C)]
+#[allow(non_snake_case)]
+pub struct AudioLoopbackCallbacks {
+    pub onAudioLevel: extern "C" fn(level_handler: RustObject, level: u16),
+}
+
+const AUDIO_LOOPBACK_CBS: AudioLoopbackCallbacks = AudioLoopbackCallbacks {
+    onAudioLevel: audio_loopback_OnAudioLevel,
+};
+const AUDIO_LOOPBACK_CBS_PTR: *const AudioLoopbackCallbacks = &AUDIO_LOOPBACK_CBS;
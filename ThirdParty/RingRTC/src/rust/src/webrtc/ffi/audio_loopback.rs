@@ -0,0 +1,24 @@
+//
+// Copyright 2021 Signal Messenger, LLC
+// SPDX-License-Identifier: AGPL-3.0-only
+//
+
+//! WebRTC FFI Audio Loopback
+
+use crate::core::util::RustObject;
+use crate::webrtc::audio_loopback::AudioLoopbackCallbacks;
+
+/// Incomplete type for C++ webrtc::rffi::AudioLoopback
+#[repr(C)]
+pub struct RffiAudioLoopback {
+    _private: [u8; 0],
+}
+
+extern "C" {
+    pub fn Rust_startAudioLoopback(
+        observer: RustObject,
+        callbacks: *const AudioLoopbackCallbacks,
+    ) -> *mut RffiAudioLoopback;
+
+    pub fn Rust_stopAudioLoopback(loopback: *mut RffiAudioLoopback);
+}
@@ -8,8 +8,9 @@
 use std::os::raw::c_char;
 
 use crate::webrtc::ffi::ice_gatherer::RffiIceGatherer;
+use crate::webrtc::ffi::media::RffiAudioTrack;
 use crate::webrtc::ffi::peer_connection_observer::RffiPeerConnectionObserver;
-use crate::webrtc::media::RffiAudioEncoderConfig;
+use crate::webrtc::media::{LayerConfig, RffiAudioEncoderConfig, VideoEncoderCaps};
 use crate::webrtc::network::RffiIp;
 use crate::webrtc::rtp;
 use crate::webrtc::sdp_observer::{
@@ -56,11 +57,24 @@ extern "C" {
 
     pub fn Rust_setOutgoingMediaEnabled(peer_connection: *const RffiPeerConnection, enabled: bool);
 
+    pub fn Rust_setOutgoingAudioEnabled(peer_connection: *const RffiPeerConnection, enabled: bool);
+
     pub fn Rust_setIncomingMediaEnabled(
         peer_connection: *const RffiPeerConnection,
         enabled: bool,
     ) -> bool;
 
+    pub fn Rust_setAudioJitterBufferMinDelay(
+        peer_connection: *const RffiPeerConnection,
+        min_delay_ms: u32,
+    );
+
+    pub fn Rust_addInterpreterAudioTrack(
+        peer_connection: *const RffiPeerConnection,
+        pc_observer: *const RffiPeerConnectionObserver,
+        track: *const RffiAudioTrack,
+    ) -> bool;
+
     pub fn Rust_createSignalingDataChannel(
         peer_connection: *const RffiPeerConnection,
         pc_observer: *const RffiPeerConnectionObserver,
@@ -114,5 +128,25 @@ extern "C" {
         config: *const RffiAudioEncoderConfig,
     );
 
+    pub fn Rust_setOutgoingVideoCaps(
+        peer_connection: *const RffiPeerConnection,
+        caps: *const VideoEncoderCaps,
+    );
+
+    pub fn Rust_getOutgoingVideoLayers(
+        peer_connection: *const RffiPeerConnection,
+        layers_out: *mut LayerConfig,
+        layers_capacity: usize,
+    ) -> usize;
+
+    pub fn Rust_getSrtpCipherSuite(peer_connection: *const RffiPeerConnection) -> i32;
+
+    pub fn Rust_insertDtmf(
+        peer_connection: *const RffiPeerConnection,
+        tones: *const c_char,
+        duration_ms: i32,
+        gap_ms: i32,
+    ) -> bool;
+
     pub fn Rust_closePeerConnection(peer_connection: *const RffiPeerConnection);
 }
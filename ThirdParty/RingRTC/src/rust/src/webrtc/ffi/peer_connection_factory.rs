@@ -4,6 +4,7 @@
 //
 
 use crate::webrtc::ffi::media::{RffiAudioTrack, RffiVideoSource, RffiVideoTrack};
+use crate::webrtc::media::AudioProcessingConfig;
 use crate::webrtc::ffi::peer_connection::RffiPeerConnection;
 use crate::webrtc::ffi::peer_connection_observer::RffiPeerConnectionObserver;
 #[cfg(feature = "simnet")]
@@ -37,7 +38,8 @@ extern "C" {
         observer: *const RffiPeerConnectionObserver,
         certificate: *const RffiCertificate,
         hide_ip: bool,
-        ice_server: RffiIceServer,
+        ice_servers: *const RffiIceServer,
+        ice_servers_size: usize,
         outgoing_audio_track: *const RffiAudioTrack,
         outgoing_video_track: *const RffiVideoTrack,
         enable_dtls: bool,
@@ -46,6 +48,10 @@ extern "C" {
     pub fn Rust_createAudioTrack(
         factory: *const RffiPeerConnectionFactory,
     ) -> *const RffiAudioTrack;
+    pub fn Rust_configureAudioProcessing(
+        factory: *const RffiPeerConnectionFactory,
+        config: *const AudioProcessingConfig,
+    );
     pub fn Rust_createVideoSource(
         factory: *const RffiPeerConnectionFactory,
     ) -> *const RffiVideoSource;
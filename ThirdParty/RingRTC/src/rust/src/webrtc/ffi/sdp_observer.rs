@@ -73,6 +73,7 @@ extern "C" {
         ice_pwd: *const c_char,
         _dtls_fingerprint_sha256: *const [u8; 32],
         demux_id: u32,
+        interpreter_audio: bool,
     ) -> *mut RffiSessionDescription;
 
     pub fn Rust_remoteDescriptionForGroupCall(
@@ -81,6 +82,7 @@ extern "C" {
         _dtls_fingerprint_sha256: *const [u8; 32],
         demux_ids_data: *const u32,
         demux_ids_len: size_t,
+        interpreter_audio: bool,
     ) -> *mut RffiSessionDescription;
 
     pub fn Rust_releaseSessionDescription(sdi: *mut RffiSessionDescription);
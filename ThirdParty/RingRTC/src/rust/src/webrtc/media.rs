@@ -5,6 +5,7 @@
 
 use std::fmt;
 use std::marker::Send;
+use std::sync::Mutex;
 
 use crate::core::util::CppObject;
 
@@ -113,6 +114,17 @@ impl AudioTrack {
     pub fn set_enabled(&self, enabled: bool) {
         unsafe { media::Rust_setAudioTrackEnabled(self.rffi, enabled) }
     }
+
+    pub fn id(&self) -> Option<u32> {
+        // C++ takes any MediaStreamTrackInterface, which AudioTrackInterface is.
+        let id =
+            unsafe { media::Rust_getTrackIdAsUint32(self.rffi as *const media::RffiVideoTrack) };
+        if id == 0 {
+            None
+        } else {
+            Some(id)
+        }
+    }
 }
 
 impl fmt::Display for AudioTrack {
@@ -375,6 +387,66 @@ pub trait VideoSink {
     fn on_video_frame(&self, frame: VideoFrame);
 }
 
+/// A VideoSink that passes frames on to another (optional) VideoSink and
+/// calls `on_resolution_changed` whenever the resolution of the incoming
+/// frames changes, rather than for every frame.
+///
+/// The reported resolution has the frame's rotation applied, so a remote
+/// device being rotated is reported as a change.
+pub struct ResolutionChangeVideoSink {
+    sink:                  Option<Box<dyn VideoSink + Send>>,
+    last_resolution:       Mutex<Option<(u32, u32)>>,
+    on_resolution_changed: Box<dyn Fn(u32, u32) + Send + Sync>,
+}
+
+impl ResolutionChangeVideoSink {
+    pub fn new(
+        sink: Option<Box<dyn VideoSink + Send>>,
+        on_resolution_changed: Box<dyn Fn(u32, u32) + Send + Sync>,
+    ) -> Self {
+        Self {
+            sink,
+            last_resolution: Mutex::new(None),
+            on_resolution_changed,
+        }
+    }
+}
+
+impl VideoSink for ResolutionChangeVideoSink {
+    fn set_enabled(&self, enabled: bool) {
+        // Forget the last resolution so that the first frame after
+        // (re)enabling is always reported.
+        if let Ok(mut last_resolution) = self.last_resolution.lock() {
+            *last_resolution = None;
+        }
+        if let Some(sink) = &self.sink {
+            sink.set_enabled(enabled);
+        }
+    }
+
+    fn on_video_frame(&self, frame: VideoFrame) {
+        let rotated = frame.metadata.apply_rotation();
+        let resolution = (rotated.width, rotated.height);
+        let changed = match self.last_resolution.lock() {
+            Ok(mut last_resolution) => {
+                if *last_resolution == Some(resolution) {
+                    false
+                } else {
+                    *last_resolution = Some(resolution);
+                    true
+                }
+            }
+            Err(_) => false,
+        };
+        if changed {
+            (self.on_resolution_changed)(resolution.0, resolution.1);
+        }
+        if let Some(sink) = &self.sink {
+            sink.on_video_frame(frame);
+        }
+    }
+}
+
 // Since dyn pointers aren't safe to send over FFI (they are double-sized fat pointers),
 // we have to wrap them in something that can have a normal pointer.
 #[cfg(feature = "native")]
@@ -439,16 +511,16 @@ unsafe impl Sync for VideoTrack {}
 #[repr(C)]
 #[derive(Clone, Debug)]
 pub struct RffiAudioEncoderConfig {
-    packet_size_ms: u32,
+    pub packet_size_ms: u32,
 
-    bandwidth:         i32,
-    start_bitrate_bps: i32,
-    min_bitrate_bps:   i32,
-    max_bitrate_bps:   i32,
-    complexity:        i32,
-    enable_vbr:        i32,
-    enable_dtx:        i32,
-    enable_fec:        i32,
+    pub bandwidth:         i32,
+    pub start_bitrate_bps: i32,
+    pub min_bitrate_bps:   i32,
+    pub max_bitrate_bps:   i32,
+    pub complexity:        i32,
+    pub enable_vbr:        i32,
+    pub enable_dtx:        i32,
+    pub enable_fec:        i32,
 }
 
 // A nice form of RffiAudioEncoderConfig
@@ -513,6 +585,59 @@ impl From<&AudioEncoderConfig> for RffiAudioEncoderConfig {
     }
 }
 
+/// Upper bounds on the outgoing video, applied on top of whatever the
+/// bandwidth allows.  0 leaves a dimension unbounded.  The larger of
+/// max_width and max_height caps the long side of the video, so the
+/// caps hold in either orientation.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct VideoEncoderCaps {
+    pub max_width:  u32,
+    pub max_height: u32,
+    pub max_fps:    u32,
+}
+
+/// Which processing is applied to the outgoing audio.  Turning it off
+/// suits music, or accessories that do their own processing.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AudioProcessingConfig {
+    /// Acoustic echo cancellation.
+    pub aec: bool,
+    /// Noise suppression.
+    pub ns:  bool,
+    /// Automatic gain control.
+    pub agc: bool,
+}
+
+impl Default for AudioProcessingConfig {
+    fn default() -> Self {
+        Self {
+            aec: true,
+            ns:  true,
+            agc: true,
+        }
+    }
+}
+
+/// One simulcast layer the outgoing video encoder is configured to
+/// produce, lowest first.  width and height are those of the frames
+/// currently being captured, divided by scale_resolution_down_by.
+/// max_framerate, max_bitrate_bps and num_temporal_layers are 0 when
+/// WebRTC picks them itself.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct LayerConfig {
+    pub ssrc:                     u32,
+    pub active:                   bool,
+    pub width:                    u32,
+    pub height:                   u32,
+    pub scale_resolution_down_by: f64,
+    pub max_framerate:            u32,
+    pub max_bitrate_bps:          u32,
+    pub num_temporal_layers:      u32,
+}
+
 #[derive(Clone, Copy, Debug)]
 #[repr(i32)]
 pub enum AudioBandwidth {
@@ -524,3 +649,68 @@ pub enum AudioBandwidth {
     Medium    = 1102,
     Narrow    = 1101,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    fn frame(width: u32, height: u32, rotation: VideoRotation) -> VideoFrame {
+        VideoFrame::from_owned_buffer(
+            VideoFrameMetadata {
+                width,
+                height,
+                rotation,
+            },
+            std::ptr::null_mut(),
+        )
+    }
+
+    fn resolution_change_sink() -> (ResolutionChangeVideoSink, Arc<Mutex<Vec<(u32, u32)>>>) {
+        let changes = Arc::new(Mutex::new(Vec::new()));
+        let changes_to_record = changes.clone();
+        let sink = ResolutionChangeVideoSink::new(
+            None,
+            Box::new(move |width, height| {
+                changes_to_record.lock().unwrap().push((width, height));
+            }),
+        );
+        (sink, changes)
+    }
+
+    #[test]
+    fn resolution_change_reported_once_per_change() {
+        let (sink, changes) = resolution_change_sink();
+
+        sink.on_video_frame(frame(640, 480, VideoRotation::None));
+        sink.on_video_frame(frame(640, 480, VideoRotation::None));
+        sink.on_video_frame(frame(640, 480, VideoRotation::None));
+        assert_eq!(vec![(640, 480)], *changes.lock().unwrap());
+
+        sink.on_video_frame(frame(1280, 720, VideoRotation::None));
+        sink.on_video_frame(frame(1280, 720, VideoRotation::None));
+        assert_eq!(vec![(640, 480), (1280, 720)], *changes.lock().unwrap());
+    }
+
+    #[test]
+    fn resolution_change_reported_on_rotation() {
+        let (sink, changes) = resolution_change_sink();
+
+        sink.on_video_frame(frame(640, 480, VideoRotation::None));
+        sink.on_video_frame(frame(640, 480, VideoRotation::Clockwise180));
+        sink.on_video_frame(frame(640, 480, VideoRotation::Clockwise90));
+        sink.on_video_frame(frame(640, 480, VideoRotation::Clockwise270));
+        assert_eq!(vec![(640, 480), (480, 640)], *changes.lock().unwrap());
+    }
+
+    #[test]
+    fn resolution_change_reported_again_after_reenabling() {
+        let (sink, changes) = resolution_change_sink();
+
+        sink.on_video_frame(frame(640, 480, VideoRotation::None));
+        sink.set_enabled(false);
+        sink.set_enabled(true);
+        sink.on_video_frame(frame(640, 480, VideoRotation::None));
+        assert_eq!(vec![(640, 480), (640, 480)], *changes.lock().unwrap());
+    }
+}
@@ -13,7 +13,13 @@ use crate::core::util::redact_string;
 use crate::error::RingRtcError;
 use crate::webrtc::data_channel::DataChannel;
 use crate::webrtc::ice_gatherer::IceGatherer;
-use crate::webrtc::media::{AudioEncoderConfig, RffiAudioEncoderConfig};
+use crate::webrtc::media::{
+    AudioEncoderConfig,
+    AudioTrack,
+    LayerConfig,
+    RffiAudioEncoderConfig,
+    VideoEncoderCaps,
+};
 use crate::webrtc::peer_connection_observer::RffiPeerConnectionObserver;
 use crate::webrtc::rtp;
 use crate::webrtc::sdp_observer::{
@@ -39,7 +45,11 @@ pub use crate::webrtc::sim::peer_connection::{
     RffiPeerConnection,
 };
 #[cfg(feature = "sim")]
+use crate::webrtc::sdp_observer::RffiSessionDescription;
+#[cfg(feature = "sim")]
 use crate::webrtc::sim::ref_count;
+#[cfg(feature = "sim")]
+use crate::webrtc::stats_observer::AudioReceiverStatistics;
 
 /// Rust wrapper around WebRTC C++ PeerConnection object.
 pub struct PeerConnection {
@@ -102,11 +112,44 @@ impl PeerConnection {
         }
     }
 
+    #[cfg(feature = "sim")]
+    pub fn local_description(&self) -> Option<RffiSessionDescription> {
+        unsafe { (*self.rffi).local_description() }
+    }
+
+    #[cfg(feature = "sim")]
+    pub fn remote_description(&self) -> Option<RffiSessionDescription> {
+        unsafe { (*self.rffi).remote_description() }
+    }
+
+    #[cfg(feature = "sim")]
+    pub fn rtp_data_channel_enabled(&self) -> bool {
+        unsafe { (*self.rffi).rtp_data_channel_enabled() }
+    }
+
+    #[cfg(feature = "sim")]
+    pub fn ice_server_urls(&self) -> Vec<Vec<String>> {
+        unsafe { (*self.rffi).ice_server_urls() }
+    }
+
     #[cfg(feature = "sim")]
     pub fn set_rtp_packet_sink(&self, rtp_packet_sink: BoxedRtpPacketSink) {
         unsafe { (*self.rffi).set_rtp_packet_sink(rtp_packet_sink) }
     }
 
+    #[cfg(feature = "sim")]
+    pub fn set_audio_receivers_statistics(
+        &self,
+        audio_receiver_statistics: Vec<AudioReceiverStatistics>,
+    ) {
+        unsafe { (*self.rffi).set_audio_receivers_statistics(audio_receiver_statistics) }
+    }
+
+    #[cfg(feature = "testing")]
+    pub fn set_receive_latency(&self, receive_latency_ms: u32) {
+        unsafe { (*self.rffi).set_receive_latency(receive_latency_ms) }
+    }
+
     /// Rust wrapper around C++ PeerConnection::CreateDataChannel().
     /// Assumes the label "signaling" and unordered/unreliable for RTP.
     pub fn create_signaling_data_channel(&self) -> Result<DataChannel> {
@@ -179,12 +222,40 @@ impl PeerConnection {
         }
     }
 
+    /// Which disables/enables only the outgoing audio track, leaving
+    /// any video senders alone.
+    pub fn set_outgoing_audio_enabled(&self, enabled: bool) {
+        unsafe {
+            pc::Rust_setOutgoingAudioEnabled(self.rffi, enabled);
+        }
+    }
+
     pub fn set_incoming_media_enabled(&self, enabled: bool) {
         unsafe {
             pc::Rust_setIncomingMediaEnabled(self.rffi, enabled);
         }
     }
 
+    /// Rust wrapper around C++ RtpReceiverInterface::SetJitterBufferMinimumDelay()
+    /// for every audio receiver.
+    pub fn set_audio_jitter_buffer_min_delay(&self, min_delay_ms: u32) {
+        unsafe {
+            pc::Rust_setAudioJitterBufferMinDelay(self.rffi, min_delay_ms);
+        }
+    }
+
+    /// Adds a second outgoing audio track carrying an interpreter channel.
+    /// Only used for group calls.
+    pub fn add_interpreter_audio_track(&self, track: &AudioTrack) -> Result<()> {
+        let added = unsafe {
+            pc::Rust_addInterpreterAudioTrack(self.rffi, self.rffi_pc_observer, track.rffi())
+        };
+        if !added {
+            return Err(RingRtcError::AddInterpreterAudioTrack.into());
+        }
+        Ok(())
+    }
+
     /// Rust wrapper around C++ PeerConnection::AddIceCandidate().
     pub fn add_ice_candidate(&self, candidate: &signaling::IceCandidate) -> Result<()> {
         let sdp = candidate.to_v3_and_v2_sdp()?;
@@ -296,6 +367,64 @@ impl PeerConnection {
         unsafe { pc::Rust_configureAudioEncoders(self.rffi, &config) };
     }
 
+    pub fn set_outgoing_video_caps(&self, caps: &VideoEncoderCaps) {
+        info!("PeerConnection.set_outgoing_video_caps({:?})", caps);
+        unsafe { pc::Rust_setOutgoingVideoCaps(self.rffi, caps) };
+    }
+
+    /// The simulcast layers the outgoing video encoder is configured to
+    /// produce, lowest first.
+    pub fn outgoing_video_layers(&self) -> Vec<LayerConfig> {
+        // WebRTC never configures more simulcast layers than this.
+        const MAX_LAYERS: usize = 4;
+        let mut layers = vec![LayerConfig::default(); MAX_LAYERS];
+        let count = unsafe {
+            pc::Rust_getOutgoingVideoLayers(self.rffi, layers.as_mut_ptr(), layers.len())
+        };
+        if count > layers.len() {
+            warn!(
+                "PeerConnection.outgoing_video_layers(): dropping {} layers",
+                count - layers.len()
+            );
+        }
+        layers.truncate(count);
+        layers
+    }
+
+    /// The name of the SRTP crypto suite negotiated by DTLS, such as
+    /// "AEAD_AES_256_GCM", or None until DTLS completes (or without DTLS).
+    pub fn srtp_cipher_suite(&self) -> Option<String> {
+        let suite = unsafe { pc::Rust_getSrtpCipherSuite(self.rffi) };
+        // The names and values are those of rtc::SrtpCryptoSuiteToName().
+        match suite {
+            0 => None,
+            1 => Some("AES_CM_128_HMAC_SHA1_80".to_string()),
+            2 => Some("AES_CM_128_HMAC_SHA1_32".to_string()),
+            7 => Some("AEAD_AES_128_GCM".to_string()),
+            8 => Some("AEAD_AES_256_GCM".to_string()),
+            _ => Some(format!("0x{:04x}", suite)),
+        }
+    }
+
+    /// Rust wrapper around C++ DtmfSenderInterface::InsertDtmf(), which
+    /// sends the tones as RFC 4733 telephone-events on the audio sender.
+    pub fn insert_dtmf(&self, tones: &str, duration_ms: u32, gap_ms: u32) -> Result<()> {
+        let tones_c = CString::new(tones)?;
+        let insert_ok = unsafe {
+            pc::Rust_insertDtmf(
+                self.rffi,
+                tones_c.as_ptr(),
+                duration_ms as i32,
+                gap_ms as i32,
+            )
+        };
+        if insert_ok {
+            Ok(())
+        } else {
+            Err(RingRtcError::InsertDtmf.into())
+        }
+    }
+
     pub fn close(&self) {
         unsafe { pc::Rust_closePeerConnection(self.rffi) };
     }
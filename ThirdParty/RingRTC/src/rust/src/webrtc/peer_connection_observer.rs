@@ -45,7 +45,90 @@ pub enum IceConnectionState {
 #[repr(C)]
 #[derive(Debug)]
 pub struct CppIceCandidate {
-    sdp: *const c_char,
+    sdp:          *const c_char,
+    network_name: *const c_char,
+    is_loopback:  bool,
+}
+
+/// The local network interface an ICE candidate was gathered on.
+#[derive(Clone, Debug, Default)]
+pub struct IceCandidateNetwork {
+    pub name:        String,
+    pub is_loopback: bool,
+}
+
+/// Which local network interfaces may contribute ICE candidates.
+///
+/// Candidates gathered on an excluded interface are never sent to
+/// the remote peer.
+#[derive(Clone, Debug, Default)]
+pub struct IceInterfacePolicy {
+    pub exclude_loopback:    bool,
+    /// Interface names to exclude, such as "utun0".  A trailing '*'
+    /// matches any suffix, so "utun*" excludes every utun interface.
+    pub excluded_interfaces: Vec<String>,
+}
+
+impl IceInterfacePolicy {
+    pub fn allows(&self, network: &IceCandidateNetwork) -> bool {
+        if self.exclude_loopback && network.is_loopback {
+            return false;
+        }
+        !self
+            .excluded_interfaces
+            .iter()
+            .any(|pattern| match pattern.strip_suffix('*') {
+                Some(prefix) => network.name.starts_with(prefix),
+                None => network.name == *pattern,
+            })
+    }
+}
+
+/// The kind of local network a connection's media goes over.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NetworkRoute {
+    Unknown,
+    Wifi,
+    Cellular,
+    Ethernet,
+    Vpn,
+}
+
+impl NetworkRoute {
+    // From the value of a WebRTC rtc::AdapterType (see NetworkInterfaceType).
+    fn from_adapter_type(adapter_type: i32) -> Self {
+        match adapter_type {
+            1 => Self::Ethernet,
+            2 => Self::Wifi,
+            4 => Self::Cellular,
+            8 => Self::Vpn,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+/// The kind of path the selected ICE candidate pair takes, which tells
+/// whether media goes through a TURN server.  The values are the ones
+/// passed across the RFFI and to the iOS app.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CandidatePairType {
+    Host  = 0,
+    Srflx = 1,
+    Prflx = 2,
+    Relay = 3,
+}
+
+impl CandidatePairType {
+    // From the value given by the RFFI (see CandidatePairType() in C++).
+    fn from_i32(candidate_pair_type: i32) -> Option<Self> {
+        match candidate_pair_type {
+            0 => Some(Self::Host),
+            1 => Some(Self::Srflx),
+            2 => Some(Self::Prflx),
+            3 => Some(Self::Relay),
+            _ => None,
+        }
+    }
 }
 
 /// The callbacks from C++ will ultimately go to an impl of this.
@@ -58,8 +141,34 @@ pub trait PeerConnectionObserverTrait {
         &mut self,
         ice_candidate: signaling::IceCandidate,
         sdp_for_logging: &str,
+        network: &IceCandidateNetwork,
     ) -> Result<()>;
     fn handle_ice_connection_state_changed(&mut self, new_state: IceConnectionState) -> Result<()>;
+    // A STUN or TURN server returned an error, such as a TURN allocation
+    // failing because of bad credentials.
+    fn handle_ice_candidate_error(
+        &mut self,
+        _url: String,
+        _error_code: u16,
+        _error_text: String,
+    ) -> Result<()> {
+        Ok(())
+    }
+    // Every local candidate has been passed to handle_ice_candidate_gathered.
+    fn handle_ice_gathering_complete(&mut self) -> Result<()> {
+        Ok(())
+    }
+    // ICE selected a different candidate pair, which may be on a
+    // different kind of network, such as cellular instead of Wi-Fi.
+    fn handle_ice_network_route_changed(&mut self, _network_route: NetworkRoute) -> Result<()> {
+        Ok(())
+    }
+    fn handle_ice_candidate_pair_changed(
+        &mut self,
+        _candidate_pair_type: CandidatePairType,
+    ) -> Result<()> {
+        Ok(())
+    }
 
     // Media Events
     // Defaults allow an impl to choose between handling streams or tracks.
@@ -73,6 +182,17 @@ pub trait PeerConnectionObserverTrait {
     fn handle_incoming_video_added(&mut self, _incoming_track: VideoTrack) -> Result<()> {
         Ok(())
     }
+    fn handle_incoming_interpreter_audio_added(
+        &mut self,
+        _incoming_track: AudioTrack,
+    ) -> Result<()> {
+        Ok(())
+    }
+    // Only called when frame encryption is disabled; with it enabled, the
+    // observer sees the key frames itself in encrypt_media.
+    fn handle_outgoing_key_frame_encoded(&mut self) -> Result<()> {
+        Ok(())
+    }
 
     // Data channel events
     fn handle_signaling_data_channel_connected(&mut self, data_channel: DataChannel) -> Result<()>;
@@ -131,11 +251,23 @@ extern "C" fn pc_observer_OnIceCandidate<T>(
                 .to_string_lossy()
                 .into_owned()
         };
+        let network = unsafe {
+            IceCandidateNetwork {
+                name:        if (*cpp_candidate).network_name.is_null() {
+                    String::new()
+                } else {
+                    CStr::from_ptr((*cpp_candidate).network_name)
+                        .to_string_lossy()
+                        .into_owned()
+                },
+                is_loopback: (*cpp_candidate).is_loopback,
+            }
+        };
         // ICE candidates are the same for V2 and V3 and V4.
         let ice_candidate = signaling::IceCandidate::from_v3_and_v2_sdp(sdp.clone());
         if let Ok(ice_candidate) = ice_candidate {
             observer
-                .handle_ice_candidate_gathered(ice_candidate, sdp.as_str())
+                .handle_ice_candidate_gathered(ice_candidate, sdp.as_str(), &network)
                 .unwrap_or_else(|e| error!("Problems handling ice candidate: {}", e));
         } else {
             warn!("Failed to handle local ICE candidate SDP");
@@ -169,6 +301,97 @@ extern "C" fn pc_observer_OnIceConnectionChange<T>(
         .unwrap_or_else(|e| error!("Problems handling ICE connection state change: {}", e));
 }
 
+/// PeerConnectionObserver OnIceGatheringChange() callback, for the
+/// complete state only.
+#[allow(non_snake_case)]
+extern "C" fn pc_observer_OnIceGatheringComplete<T>(observer_ptr: *mut T)
+where
+    T: PeerConnectionObserverTrait,
+{
+    let observer = unsafe { &mut *observer_ptr };
+    info!(
+        "pc_observer_OnIceGatheringComplete(): {}",
+        observer.log_id()
+    );
+    observer
+        .handle_ice_gathering_complete()
+        .unwrap_or_else(|e| error!("Problems handling ICE gathering complete: {}", e));
+}
+
+/// PeerConnectionObserver OnIceCandidateError() callback.
+#[allow(non_snake_case)]
+extern "C" fn pc_observer_OnIceCandidateError<T>(
+    observer_ptr: *mut T,
+    url: *const c_char,
+    error_code: u16,
+    error_text: *const c_char,
+) where
+    T: PeerConnectionObserverTrait,
+{
+    let observer = unsafe { &mut *observer_ptr };
+    if url.is_null() || error_text.is_null() {
+        warn!("Ignoring ICE candidate error with null strings");
+        return;
+    }
+    let url = unsafe { CStr::from_ptr(url).to_string_lossy().into_owned() };
+    let error_text = unsafe { CStr::from_ptr(error_text).to_string_lossy().into_owned() };
+    info!(
+        "pc_observer_OnIceCandidateError(): {}, error_code: {}",
+        observer.log_id(),
+        error_code
+    );
+    observer
+        .handle_ice_candidate_error(url, error_code, error_text)
+        .unwrap_or_else(|e| error!("Problems handling ICE candidate error: {}", e));
+}
+
+/// PeerConnectionObserver OnIceSelectedCandidatePairChanged() callback.
+#[allow(non_snake_case)]
+extern "C" fn pc_observer_OnIceNetworkRouteChanged<T>(observer_ptr: *mut T, local_adapter_type: i32)
+where
+    T: PeerConnectionObserverTrait,
+{
+    let observer = unsafe { &mut *observer_ptr };
+    let network_route = NetworkRoute::from_adapter_type(local_adapter_type);
+    info!(
+        "pc_observer_OnIceNetworkRouteChanged(): {}, network_route: {:?}",
+        observer.log_id(),
+        network_route
+    );
+    observer
+        .handle_ice_network_route_changed(network_route)
+        .unwrap_or_else(|e| error!("Problems handling ICE network route change: {}", e));
+}
+
+/// PeerConnectionObserver OnIceCandidatePairChanged() callback.
+#[allow(non_snake_case)]
+extern "C" fn pc_observer_OnIceCandidatePairChanged<T>(
+    observer_ptr: *mut T,
+    candidate_pair_type: i32,
+) where
+    T: PeerConnectionObserverTrait,
+{
+    let observer = unsafe { &mut *observer_ptr };
+    let candidate_pair_type = match CandidatePairType::from_i32(candidate_pair_type) {
+        Some(candidate_pair_type) => candidate_pair_type,
+        None => {
+            warn!(
+                "pc_observer_OnIceCandidatePairChanged(): unknown type: {}",
+                candidate_pair_type
+            );
+            return;
+        }
+    };
+    info!(
+        "pc_observer_OnIceCandidatePairChanged(): {}, candidate_pair_type: {:?}",
+        observer.log_id(),
+        candidate_pair_type
+    );
+    observer
+        .handle_ice_candidate_pair_changed(candidate_pair_type)
+        .unwrap_or_else(|e| error!("Problems handling ICE candidate pair change: {}", e));
+}
+
 /// PeerConnectionObserver OnAddStream() callback.
 #[allow(non_snake_case)]
 extern "C" fn pc_observer_OnAddStream<T>(observer_ptr: *mut T, rffi_stream: *const RffiMediaStream)
@@ -227,6 +450,43 @@ extern "C" fn pc_observer_OnAddVideoRtpReceiver<T>(
         .unwrap_or_else(|e| error!("Problems handling incoming audio: {}", e));
 }
 
+/// PeerConnectionObserver OnAddTrack() callback for interpreter audio tracks.
+#[allow(non_snake_case)]
+extern "C" fn pc_observer_OnAddInterpreterAudioRtpReceiver<T>(
+    observer_ptr: *mut T,
+    rffi_track: *const RffiAudioTrack,
+) where
+    T: PeerConnectionObserverTrait,
+{
+    let observer = unsafe { &mut *observer_ptr };
+    info!(
+        "pc_observer_OnAddInterpreterAudioRtpReceiver(): {}, rffi_track: {:p}",
+        observer.log_id(),
+        rffi_track
+    );
+    let track = AudioTrack::owned(rffi_track);
+    observer
+        .handle_incoming_interpreter_audio_added(track)
+        .unwrap_or_else(|e| error!("Problems handling incoming interpreter audio: {}", e));
+}
+
+/// PeerConnectionObserver callback for each key frame encoded for the
+/// outgoing video.
+#[allow(non_snake_case)]
+extern "C" fn pc_observer_OnOutgoingKeyFrameEncoded<T>(observer_ptr: *mut T)
+where
+    T: PeerConnectionObserverTrait,
+{
+    let observer = unsafe { &mut *observer_ptr };
+    debug!(
+        "pc_observer_OnOutgoingKeyFrameEncoded(): {}",
+        observer.log_id()
+    );
+    observer
+        .handle_outgoing_key_frame_encoded()
+        .unwrap_or_else(|e| error!("Problems handling outgoing key frame: {}", e));
+}
+
 /// PeerConnectionObserver OnSignalingDataChannel() callback.
 #[allow(non_snake_case)]
 extern "C" fn pc_observer_OnSignalingDataChannel<T>(
@@ -422,13 +682,19 @@ where
     T: PeerConnectionObserverTrait,
 {
     // ICE events
-    onIceCandidate:        extern "C" fn(*mut T, *const CppIceCandidate),
-    onIceConnectionChange: extern "C" fn(*mut T, IceConnectionState),
+    onIceCandidate:            extern "C" fn(*mut T, *const CppIceCandidate),
+    onIceConnectionChange:     extern "C" fn(*mut T, IceConnectionState),
+    onIceCandidateError:       extern "C" fn(*mut T, *const c_char, u16, *const c_char),
+    onIceGatheringComplete:    extern "C" fn(*mut T),
+    onIceNetworkRouteChanged:  extern "C" fn(*mut T, i32),
+    onIceCandidatePairChanged: extern "C" fn(*mut T, i32),
 
     // Media events
-    onAddStream:           extern "C" fn(*mut T, *const RffiMediaStream),
-    onAddAudioRtpReceiver: extern "C" fn(*mut T, *const RffiAudioTrack),
-    onAddVideoRtpReceiver: extern "C" fn(*mut T, *const RffiVideoTrack),
+    onAddStream:                      extern "C" fn(*mut T, *const RffiMediaStream),
+    onAddAudioRtpReceiver:            extern "C" fn(*mut T, *const RffiAudioTrack),
+    onAddVideoRtpReceiver:            extern "C" fn(*mut T, *const RffiVideoTrack),
+    onAddInterpreterAudioRtpReceiver: extern "C" fn(*mut T, *const RffiAudioTrack),
+    onOutgoingKeyFrameEncoded:        extern "C" fn(*mut T),
 
     // Data channel events
     onSignalingDataChannel:        extern "C" fn(*mut T, *const RffiDataChannel),
@@ -460,8 +726,10 @@ where
     T: PeerConnectionObserverTrait,
 {
     /// Pointer to C++ webrtc::rffi::RffiPeerConnectionObserver.
-    rffi:          *const RffiPeerConnectionObserver,
-    observer_type: PhantomData<T>,
+    rffi:                    *const RffiPeerConnectionObserver,
+    /// Whether media frames are encrypted with the observer's callbacks.
+    enable_frame_encryption: bool,
+    observer_type:           PhantomData<T>,
 }
 
 impl<T> fmt::Display for PeerConnectionObserver<T>
@@ -497,13 +765,19 @@ where
 
         let pc_observer_callbacks = PeerConnectionObserverCallbacks::<T> {
             // ICE events
-            onIceCandidate:        pc_observer_OnIceCandidate::<T>,
-            onIceConnectionChange: pc_observer_OnIceConnectionChange::<T>,
+            onIceCandidate:            pc_observer_OnIceCandidate::<T>,
+            onIceConnectionChange:     pc_observer_OnIceConnectionChange::<T>,
+            onIceCandidateError:       pc_observer_OnIceCandidateError::<T>,
+            onIceGatheringComplete:    pc_observer_OnIceGatheringComplete::<T>,
+            onIceNetworkRouteChanged:  pc_observer_OnIceNetworkRouteChanged::<T>,
+            onIceCandidatePairChanged: pc_observer_OnIceCandidatePairChanged::<T>,
 
             // Media events
-            onAddStream:           pc_observer_OnAddStream::<T>,
-            onAddAudioRtpReceiver: pc_observer_OnAddAudioRtpReceiver::<T>,
-            onAddVideoRtpReceiver: pc_observer_OnAddVideoRtpReceiver::<T>,
+            onAddStream:                      pc_observer_OnAddStream::<T>,
+            onAddAudioRtpReceiver:            pc_observer_OnAddAudioRtpReceiver::<T>,
+            onAddVideoRtpReceiver:            pc_observer_OnAddVideoRtpReceiver::<T>,
+            onAddInterpreterAudioRtpReceiver: pc_observer_OnAddInterpreterAudioRtpReceiver::<T>,
+            onOutgoingKeyFrameEncoded:        pc_observer_OnOutgoingKeyFrameEncoded::<T>,
 
             // Data channel events
             onSignalingDataChannel:        pc_observer_OnSignalingDataChannel::<T>,
@@ -531,6 +805,7 @@ where
         } else {
             Ok(Self {
                 rffi,
+                enable_frame_encryption,
                 observer_type: PhantomData,
             })
         }
@@ -540,4 +815,10 @@ where
     pub fn rffi(&self) -> *const RffiPeerConnectionObserver {
         self.rffi
     }
+
+    /// Return whether the observer was created with frame encryption
+    /// enabled.
+    pub fn frame_encryption_enabled(&self) -> bool {
+        self.enable_frame_encryption
+    }
 }
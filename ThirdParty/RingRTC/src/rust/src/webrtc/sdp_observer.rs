@@ -262,6 +262,7 @@ impl SessionDescription {
         ice_pwd: &str,
         dtls_fingerprint_sha256: &[u8; 32],
         rtp_demux_id: Option<u32>,
+        interpreter_audio: bool,
     ) -> Result<Self> {
         let rffi_ice_ufrag = CString::new(ice_ufrag.as_bytes())?;
         let rffi_ice_pwd = CString::new(ice_pwd.as_bytes())?;
@@ -272,6 +273,7 @@ impl SessionDescription {
                 rffi_ice_pwd.as_ptr(),
                 dtls_fingerprint_sha256,
                 rtp_demux_id.unwrap_or(0),
+                interpreter_audio,
             )
         };
         if sdi.is_null() {
@@ -285,6 +287,7 @@ impl SessionDescription {
         ice_pwd: &str,
         dtls_fingerprint_sha256: &[u8; 32],
         rtp_demux_ids: &[u32],
+        interpreter_audio: bool,
     ) -> Result<Self> {
         let rffi_ice_ufrag = CString::new(ice_ufrag.as_bytes())?;
         let rffi_ice_pwd = CString::new(ice_pwd.as_bytes())?;
@@ -296,6 +299,7 @@ impl SessionDescription {
                 dtls_fingerprint_sha256,
                 rtp_demux_ids.as_ptr(),
                 rtp_demux_ids.len(),
+                interpreter_audio,
             )
         };
         if sdi.is_null() {
@@ -0,0 +1,36 @@
+//
+// Copyright 2021 Signal Messenger, LLC
+// SPDX-License-Identifier: AGPL-3.0-only
+//
+
+//! WebRTC Simulation Audio Loopback
+
+use crate::core::util::RustObject;
+use crate::webrtc::audio_loopback::AudioLoopbackCallbacks;
+
+/// Simulation type for webrtc::rffi::AudioLoopback
+pub struct RffiAudioLoopback {
+    _observer: RustObject,
+}
+
+#[allow(non_snake_case, clippy::missing_safety_doc)]
+pub unsafe fn Rust_startAudioLoopback(
+    observer: RustObject,
+    callbacks: *const AudioLoopbackCallbacks,
+) -> *mut RffiAudioLoopback {
+    info!("Rust_startAudioLoopback():");
+
+    // Report a single level, as if one batch of audio had been captured.
+    ((*callbacks).onAudioLevel)(observer, 1000);
+
+    Box::into_raw(Box::new(RffiAudioLoopback {
+        _observer: observer,
+    }))
+}
+
+#[allow(non_snake_case, clippy::missing_safety_doc)]
+pub unsafe fn Rust_stopAudioLoopback(loopback: *mut RffiAudioLoopback) {
+    info!("Rust_stopAudioLoopback():");
+
+    drop(Box::from_raw(loopback));
+}
@@ -3,6 +3,11 @@
 // SPDX-License-Identifier: AGPL-3.0-only
 //
 
+use std::collections::HashSet;
+use std::sync::Mutex;
+
+use lazy_static::lazy_static;
+
 pub use crate::webrtc::media::VideoRotation;
 
 pub type RffiMediaStream = u32;
@@ -34,9 +39,30 @@ pub unsafe fn Rust_setAudioTrackEnabled(_track: *const RffiAudioTrack, _enabled:
     info!("Rust_setAudioTrackEnabled()");
 }
 
+lazy_static! {
+    // The addresses of the video tracks that are disabled.  Tests that check
+    // this give each of their tracks an address of its own.
+    static ref DISABLED_VIDEO_TRACKS: Mutex<HashSet<usize>> = Mutex::new(HashSet::new());
+}
+
 #[allow(non_snake_case, clippy::missing_safety_doc)]
-pub unsafe fn Rust_setVideoTrackEnabled(_track: *const RffiVideoTrack, _enabled: bool) {
+pub unsafe fn Rust_setVideoTrackEnabled(track: *const RffiVideoTrack, enabled: bool) {
     info!("Rust_setVideoTrackEnabled()");
+    let mut disabled_video_tracks = DISABLED_VIDEO_TRACKS.lock().unwrap();
+    if enabled {
+        disabled_video_tracks.remove(&(track as usize));
+    } else {
+        disabled_video_tracks.insert(track as usize);
+    }
+}
+
+/// Whether the video track was last enabled (as tracks start out) or
+/// disabled.
+pub fn video_track_enabled(track: *const RffiVideoTrack) -> bool {
+    !DISABLED_VIDEO_TRACKS
+        .lock()
+        .unwrap()
+        .contains(&(track as usize))
 }
 
 #[allow(non_snake_case, clippy::missing_safety_doc)]
@@ -5,11 +5,14 @@
 
 //! WebRTC Simulation Peer Connection Interface
 
+use std::collections::HashMap;
+use std::ffi::CStr;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 use std::os::raw::c_char;
 use std::sync::{Arc, Mutex};
 
 use crate::core::platform::PlatformItem;
-use crate::webrtc::media::RffiAudioEncoderConfig;
+use crate::webrtc::media::{LayerConfig, RffiAudioEncoderConfig, VideoEncoderCaps};
 use crate::webrtc::rtp;
 use crate::webrtc::sdp_observer::{
     RffiCreateSessionDescriptionObserver,
@@ -17,8 +20,15 @@ use crate::webrtc::sdp_observer::{
     RffiSetSessionDescriptionObserver,
 };
 use crate::webrtc::sim::ice_gatherer::{RffiIceGatherer, FAKE_ICE_GATHERER};
+use crate::webrtc::sim::media::RffiAudioTrack;
 use crate::webrtc::sim::peer_connection_observer::RffiPeerConnectionObserver;
-use crate::webrtc::stats_observer::RffiStatsObserver;
+use crate::webrtc::stats_observer::{
+    AudioReceiverStatistics,
+    AudioSenderStatistics,
+    ConnectionStatistics,
+    RffiStatsObserver,
+    VideoSenderStatistics,
+};
 
 /// Simulation type for PeerConnection.
 #[derive(Clone)]
@@ -46,28 +56,58 @@ impl RffiPeerConnection {
     pub fn new() -> Self {
         Self {
             state: Arc::new(Mutex::new(RffiPeerConnectionState {
-                local_description_set:  false,
-                remote_description_set: false,
-                outgoing_audio_enabled: true,
-                incoming_rtp_enabled:   true,
-                rtp_packet_sink:        None,
+                local_description_set:            false,
+                remote_description_set:           false,
+                local_description:                None,
+                remote_description:               None,
+                outgoing_audio_enabled:           true,
+                outgoing_audio_track_enabled:     true,
+                incoming_rtp_enabled:             true,
+                rtp_packet_sink:                  None,
+                audio_jitter_buffer_min_delay_ms: None,
+                audio_encoder_config:             None,
+                outgoing_video_caps:              None,
+                selected_pair_round_trip_time:    None,
+                audio_sender_statistics:          Vec::new(),
+                video_sender_statistics:          Vec::new(),
+                audio_receiver_statistics:        Vec::new(),
+                receive_latency_ms:               0,
+                injected_jitter_buffer_delay:     HashMap::new(),
+                inserted_dtmf:                    String::new(),
+                max_send_bitrate_bps:             None,
+                rtp_data_channel_enabled:         true,
+                ice_server_urls:                  Vec::new(),
             })),
         }
     }
 
-    fn set_local_description(&self) {
+    fn set_local_description(&self, description: Option<RffiSessionDescription>) {
         let mut state = self.state.lock().unwrap();
         state.local_description_set = true;
+        state.local_description = description;
+    }
+
+    pub fn local_description(&self) -> Option<RffiSessionDescription> {
+        let state = self.state.lock().unwrap();
+        state.local_description
     }
 
-    fn set_remote_description(&self) {
+    fn set_remote_description(&self, description: Option<RffiSessionDescription>) {
         let mut state = self.state.lock().unwrap();
         state.remote_description_set = true;
+        state.remote_description = description;
+    }
+
+    pub fn remote_description(&self) -> Option<RffiSessionDescription> {
+        let state = self.state.lock().unwrap();
+        state.remote_description
     }
 
     fn set_outgoing_media_enabled(&self, enabled: bool) {
         let mut state = self.state.lock().unwrap();
-        if !(state.local_description_set && state.remote_description_set) {
+        // Disabling is fine at any point, such as when a join fails before
+        // the answer arrives.
+        if enabled && !(state.local_description_set && state.remote_description_set) {
             panic!("Can't Rust_setOutgoingMediaEnabled if you haven't received an answer yet.");
         }
         state.outgoing_audio_enabled = enabled;
@@ -78,25 +118,225 @@ impl RffiPeerConnection {
         state.outgoing_audio_enabled
     }
 
+    fn set_outgoing_audio_track_enabled(&self, enabled: bool) {
+        let mut state = self.state.lock().unwrap();
+        state.outgoing_audio_track_enabled = enabled;
+    }
+
+    pub fn outgoing_audio_track_enabled(&self) -> bool {
+        let state = self.state.lock().unwrap();
+        state.outgoing_audio_track_enabled
+    }
+
     fn set_incoming_media_enabled(&self, enabled: bool) {
         let mut state = self.state.lock().unwrap();
         state.incoming_rtp_enabled = enabled;
     }
 
+    pub fn incoming_rtp_enabled(&self) -> bool {
+        let state = self.state.lock().unwrap();
+        state.incoming_rtp_enabled
+    }
+
+    fn set_audio_jitter_buffer_min_delay(&self, min_delay_ms: u32) {
+        let mut state = self.state.lock().unwrap();
+        state.audio_jitter_buffer_min_delay_ms = Some(min_delay_ms);
+    }
+
+    pub fn audio_jitter_buffer_min_delay_ms(&self) -> Option<u32> {
+        let state = self.state.lock().unwrap();
+        state.audio_jitter_buffer_min_delay_ms
+    }
+
+    fn configure_audio_encoders(&self, config: &RffiAudioEncoderConfig) {
+        let mut state = self.state.lock().unwrap();
+        state.audio_encoder_config = Some(config.clone());
+    }
+
+    pub fn audio_encoder_config(&self) -> Option<RffiAudioEncoderConfig> {
+        let state = self.state.lock().unwrap();
+        state.audio_encoder_config.clone()
+    }
+
+    fn set_outgoing_video_caps(&self, caps: &VideoEncoderCaps) {
+        let mut state = self.state.lock().unwrap();
+        state.outgoing_video_caps = Some(*caps);
+    }
+
+    pub fn outgoing_video_caps(&self) -> Option<VideoEncoderCaps> {
+        let state = self.state.lock().unwrap();
+        state.outgoing_video_caps
+    }
+
+    /// Sets the round trip time (in seconds) of the selected candidate pair
+    /// reported by Rust_getStats(), or None to report no selected pair.
+    pub fn set_selected_pair_round_trip_time(&self, round_trip_time: Option<f64>) {
+        let mut state = self.state.lock().unwrap();
+        state.selected_pair_round_trip_time = round_trip_time;
+    }
+
+    fn selected_pair_round_trip_time(&self) -> Option<f64> {
+        let state = self.state.lock().unwrap();
+        state.selected_pair_round_trip_time
+    }
+
+    /// Sets the stats of the one audio sender reported by Rust_getStats(),
+    /// or None to report no audio being sent, such as to inject the NACKs
+    /// and retransmissions of a lossy link.
+    pub fn set_audio_sender_statistics(
+        &self,
+        audio_sender_statistics: Option<AudioSenderStatistics>,
+    ) {
+        let mut state = self.state.lock().unwrap();
+        state.audio_sender_statistics = audio_sender_statistics.into_iter().collect();
+    }
+
+    /// Sets the stats of every video sender reported by Rust_getStats(),
+    /// one per simulcast layer.
+    pub fn set_video_senders_statistics(
+        &self,
+        video_sender_statistics: Vec<VideoSenderStatistics>,
+    ) {
+        let mut state = self.state.lock().unwrap();
+        state.video_sender_statistics = video_sender_statistics;
+    }
+
+    fn sender_statistics(&self) -> (Vec<AudioSenderStatistics>, Vec<VideoSenderStatistics>) {
+        let state = self.state.lock().unwrap();
+        (
+            state.audio_sender_statistics.clone(),
+            state.video_sender_statistics.clone(),
+        )
+    }
+
+    /// Sets the stats of the one audio receiver reported by Rust_getStats(),
+    /// or None to report no audio being received.
+    pub fn set_audio_receiver_statistics(
+        &self,
+        audio_receiver_statistics: Option<AudioReceiverStatistics>,
+    ) {
+        self.set_audio_receivers_statistics(audio_receiver_statistics.into_iter().collect());
+    }
+
+    /// Sets the stats of every audio receiver reported by Rust_getStats(),
+    /// such as one per remote device of a group call.
+    pub fn set_audio_receivers_statistics(
+        &self,
+        audio_receiver_statistics: Vec<AudioReceiverStatistics>,
+    ) {
+        let mut state = self.state.lock().unwrap();
+        state.audio_receiver_statistics = audio_receiver_statistics;
+    }
+
+    /// Holds every received audio sample in the jitter buffer for another
+    /// `receive_latency_ms`, as reported by Rust_getStats().
+    pub fn set_receive_latency(&self, receive_latency_ms: u32) {
+        let mut state = self.state.lock().unwrap();
+        state.receive_latency_ms = receive_latency_ms;
+    }
+
+    fn audio_receiver_statistics(&self) -> Vec<AudioReceiverStatistics> {
+        let mut state = self.state.lock().unwrap();
+        let state = &mut *state;
+        let extra_delay_secs = state.receive_latency_ms as f64 / 1000.0;
+        let injected_jitter_buffer_delay = &mut state.injected_jitter_buffer_delay;
+        state
+            .audio_receiver_statistics
+            .iter()
+            .map(|audio_receiver| {
+                // Only samples emitted since the last report get the current latency.
+                let (emitted_count, injected_delay) = injected_jitter_buffer_delay
+                    .entry(audio_receiver.ssrc)
+                    .or_insert((0, 0.0));
+                *injected_delay += extra_delay_secs
+                    * audio_receiver
+                        .jitter_buffer_emitted_count
+                        .saturating_sub(*emitted_count) as f64;
+                *emitted_count = audio_receiver.jitter_buffer_emitted_count;
+                AudioReceiverStatistics {
+                    jitter_buffer_delay: audio_receiver.jitter_buffer_delay + *injected_delay,
+                    ..audio_receiver.clone()
+                }
+            })
+            .collect()
+    }
+
     pub fn set_rtp_packet_sink(&self, rtp_packet_sink: BoxedRtpPacketSink) {
         let mut state = self.state.lock().unwrap();
         state.rtp_packet_sink = Some(rtp_packet_sink);
     }
+
+    fn insert_dtmf(&self, tones: &str) {
+        let mut state = self.state.lock().unwrap();
+        state.inserted_dtmf.push_str(tones);
+    }
+
+    /// Every tone passed to Rust_insertDtmf(), in order.
+    pub fn inserted_dtmf(&self) -> String {
+        let state = self.state.lock().unwrap();
+        state.inserted_dtmf.clone()
+    }
+
+    fn set_max_send_bitrate(&self, max_bitrate_bps: i32) {
+        let mut state = self.state.lock().unwrap();
+        state.max_send_bitrate_bps = Some(max_bitrate_bps);
+    }
+
+    /// The latest value passed to Rust_setMaxSendBitrate(), if any.
+    pub fn max_send_bitrate_bps(&self) -> Option<i32> {
+        let state = self.state.lock().unwrap();
+        state.max_send_bitrate_bps
+    }
+
+    pub fn set_rtp_data_channel_enabled(&self, enabled: bool) {
+        let mut state = self.state.lock().unwrap();
+        state.rtp_data_channel_enabled = enabled;
+    }
+
+    /// The enable_rtp_data_channel given to Rust_createPeerConnection().
+    pub fn rtp_data_channel_enabled(&self) -> bool {
+        let state = self.state.lock().unwrap();
+        state.rtp_data_channel_enabled
+    }
+
+    pub fn set_ice_server_urls(&self, ice_server_urls: Vec<Vec<String>>) {
+        let mut state = self.state.lock().unwrap();
+        state.ice_server_urls = ice_server_urls;
+    }
+
+    /// The URLs of each ICE server given to Rust_createPeerConnection().
+    pub fn ice_server_urls(&self) -> Vec<Vec<String>> {
+        let state = self.state.lock().unwrap();
+        state.ice_server_urls.clone()
+    }
 }
 
 pub type BoxedRtpPacketSink = Box<dyn Fn(rtp::Header, &[u8]) + Send + 'static>;
 
 struct RffiPeerConnectionState {
-    local_description_set:  bool,
-    remote_description_set: bool,
-    outgoing_audio_enabled: bool,
-    incoming_rtp_enabled:   bool,
-    rtp_packet_sink:        Option<BoxedRtpPacketSink>,
+    local_description_set:            bool,
+    remote_description_set:           bool,
+    local_description:                Option<RffiSessionDescription>,
+    remote_description:               Option<RffiSessionDescription>,
+    outgoing_audio_enabled:           bool,
+    outgoing_audio_track_enabled:     bool,
+    incoming_rtp_enabled:             bool,
+    rtp_packet_sink:                  Option<BoxedRtpPacketSink>,
+    audio_jitter_buffer_min_delay_ms: Option<u32>,
+    audio_encoder_config:             Option<RffiAudioEncoderConfig>,
+    outgoing_video_caps:              Option<VideoEncoderCaps>,
+    selected_pair_round_trip_time:    Option<f64>,
+    audio_sender_statistics:          Vec<AudioSenderStatistics>,
+    video_sender_statistics:          Vec<VideoSenderStatistics>,
+    audio_receiver_statistics:        Vec<AudioReceiverStatistics>,
+    receive_latency_ms:               u32,
+    // Per SSRC, the jitter_buffer_emitted_count last reported and the delay
+    // injected into the samples emitted up to then.
+    injected_jitter_buffer_delay:     HashMap<u32, (u64, f64)>,
+    inserted_dtmf:                    String,
+    max_send_bitrate_bps:             Option<i32>,
+    rtp_data_channel_enabled:         bool,
+    ice_server_urls:                  Vec<Vec<String>>,
 }
 
 /// Simulation type for DataChannelInterface.
@@ -116,10 +356,10 @@ pub unsafe fn Rust_createOffer(
 pub unsafe fn Rust_setLocalDescription(
     peer_connection: *const RffiPeerConnection,
     _ssd_observer: *const RffiSetSessionDescriptionObserver,
-    _local_desc: *const RffiSessionDescription,
+    local_desc: *const RffiSessionDescription,
 ) {
     info!("Rust_setLocalDescription():");
-    (*peer_connection).set_local_description();
+    (*peer_connection).set_local_description(local_desc.as_ref().copied());
 }
 
 #[allow(non_snake_case, clippy::missing_safety_doc)]
@@ -134,10 +374,10 @@ pub unsafe fn Rust_createAnswer(
 pub unsafe fn Rust_setRemoteDescription(
     peer_connection: *const RffiPeerConnection,
     _ssd_observer: *const RffiSetSessionDescriptionObserver,
-    _remote_desc: *const RffiSessionDescription,
+    remote_desc: *const RffiSessionDescription,
 ) {
     info!("Rust_setRemoteDescription():");
-    (*peer_connection).set_remote_description();
+    (*peer_connection).set_remote_description(remote_desc.as_ref().copied());
 }
 
 #[allow(non_snake_case, clippy::missing_safety_doc)]
@@ -149,6 +389,15 @@ pub unsafe fn Rust_setOutgoingMediaEnabled(
     (*peer_connection).set_outgoing_media_enabled(enabled);
 }
 
+#[allow(non_snake_case, clippy::missing_safety_doc)]
+pub unsafe fn Rust_setOutgoingAudioEnabled(
+    peer_connection: *const RffiPeerConnection,
+    enabled: bool,
+) {
+    info!("Rust_setOutgoingAudioEnabled({})", enabled);
+    (*peer_connection).set_outgoing_audio_track_enabled(enabled);
+}
+
 #[allow(non_snake_case, clippy::missing_safety_doc)]
 pub unsafe fn Rust_setIncomingMediaEnabled(
     peer_connection: *const RffiPeerConnection,
@@ -159,6 +408,25 @@ pub unsafe fn Rust_setIncomingMediaEnabled(
     true
 }
 
+#[allow(non_snake_case, clippy::missing_safety_doc)]
+pub unsafe fn Rust_setAudioJitterBufferMinDelay(
+    peer_connection: *const RffiPeerConnection,
+    min_delay_ms: u32,
+) {
+    info!("Rust_setAudioJitterBufferMinDelay({})", min_delay_ms);
+    (*peer_connection).set_audio_jitter_buffer_min_delay(min_delay_ms);
+}
+
+#[allow(non_snake_case, clippy::missing_safety_doc)]
+pub unsafe fn Rust_addInterpreterAudioTrack(
+    _peer_connection: *const RffiPeerConnection,
+    _pc_observer: *const RffiPeerConnectionObserver,
+    _track: *const RffiAudioTrack,
+) -> bool {
+    info!("Rust_addInterpreterAudioTrack():");
+    true
+}
+
 #[allow(non_snake_case, clippy::missing_safety_doc)]
 pub unsafe fn Rust_createSignalingDataChannel(
     _peer_connection: *const RffiPeerConnection,
@@ -207,18 +475,38 @@ pub unsafe fn Rust_useSharedIceGatherer(
 
 #[allow(non_snake_case, clippy::missing_safety_doc)]
 pub unsafe fn Rust_getStats(
-    _peer_connection: *const RffiPeerConnection,
-    _stats_observer: *const RffiStatsObserver,
+    peer_connection: *const RffiPeerConnection,
+    stats_observer: *const RffiStatsObserver,
 ) {
     info!("Rust_getStats:");
+    let connection_statistics: Vec<ConnectionStatistics> = (*peer_connection)
+        .selected_pair_round_trip_time()
+        .map(|current_round_trip_time| ConnectionStatistics {
+            current_round_trip_time,
+            local_candidate_type: 0,
+            remote_candidate_type: 0,
+            remote_address: SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), 0).into(),
+        })
+        .into_iter()
+        .collect();
+    let (audio_sender_statistics, video_sender_statistics) =
+        (*peer_connection).sender_statistics();
+    let audio_receiver_statistics = (*peer_connection).audio_receiver_statistics();
+    (*stats_observer).deliver_stats(
+        &audio_sender_statistics,
+        &video_sender_statistics,
+        &audio_receiver_statistics,
+        &connection_statistics,
+    );
 }
 
 #[allow(non_snake_case, clippy::missing_safety_doc)]
 pub unsafe fn Rust_setMaxSendBitrate(
-    _peer_connection: *const RffiPeerConnection,
-    _max_bitrate_bps: i32,
+    peer_connection: *const RffiPeerConnection,
+    max_bitrate_bps: i32,
 ) {
-    info!("Rust_setMaxSendBitrate:");
+    info!("Rust_setMaxSendBitrate({})", max_bitrate_bps);
+    (*peer_connection).set_max_send_bitrate(max_bitrate_bps);
 }
 
 #[allow(non_snake_case, clippy::missing_safety_doc)]
@@ -257,10 +545,61 @@ pub unsafe fn Rust_receiveRtp(
 
 #[allow(non_snake_case, clippy::missing_safety_doc)]
 pub unsafe fn Rust_configureAudioEncoders(
-    _peer_connection: *const RffiPeerConnection,
-    _config: *const RffiAudioEncoderConfig,
+    peer_connection: *const RffiPeerConnection,
+    config: *const RffiAudioEncoderConfig,
 ) {
     info!("Rust_configureAudioEncoders:");
+    (*peer_connection).configure_audio_encoders(&*config);
+}
+
+#[allow(non_snake_case, clippy::missing_safety_doc)]
+pub unsafe fn Rust_setOutgoingVideoCaps(
+    peer_connection: *const RffiPeerConnection,
+    caps: *const VideoEncoderCaps,
+) {
+    info!("Rust_setOutgoingVideoCaps:");
+    (*peer_connection).set_outgoing_video_caps(&*caps);
+}
+
+/// Reports WebRTC's default three simulcast layers for 640x480 capture.
+#[allow(non_snake_case, clippy::missing_safety_doc)]
+pub unsafe fn Rust_getOutgoingVideoLayers(
+    _peer_connection: *const RffiPeerConnection,
+    layers_out: *mut LayerConfig,
+    layers_capacity: usize,
+) -> usize {
+    info!("Rust_getOutgoingVideoLayers:");
+    let scales = [4.0, 2.0, 1.0];
+    for (i, scale) in scales.iter().enumerate().take(layers_capacity) {
+        *layers_out.add(i) = LayerConfig {
+            active: true,
+            width: (640.0 / scale) as u32,
+            height: (480.0 / scale) as u32,
+            scale_resolution_down_by: *scale,
+            ..Default::default()
+        };
+    }
+    scales.len()
+}
+
+/// Reports SRTP_AEAD_AES_256_GCM, which WebRTC prefers when GCM is enabled.
+#[allow(non_snake_case, clippy::missing_safety_doc)]
+pub unsafe fn Rust_getSrtpCipherSuite(_peer_connection: *const RffiPeerConnection) -> i32 {
+    info!("Rust_getSrtpCipherSuite:");
+    8
+}
+
+#[allow(non_snake_case, clippy::missing_safety_doc)]
+pub unsafe fn Rust_insertDtmf(
+    peer_connection: *const RffiPeerConnection,
+    tones: *const c_char,
+    _duration_ms: i32,
+    _gap_ms: i32,
+) -> bool {
+    info!("Rust_insertDtmf:");
+    let tones = CStr::from_ptr(tones).to_string_lossy();
+    (*peer_connection).insert_dtmf(&tones);
+    true
 }
 
 #[allow(non_snake_case, clippy::missing_safety_doc)]
@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: AGPL-3.0-only
 //
 
+use crate::webrtc::media::AudioProcessingConfig;
 use crate::webrtc::peer_connection_factory::RffiIceServer;
 use crate::webrtc::sim::media::{
     RffiAudioTrack,
@@ -14,13 +15,21 @@ use crate::webrtc::sim::media::{
 };
 use crate::webrtc::sim::peer_connection::RffiPeerConnection;
 use crate::webrtc::sim::peer_connection_observer::RffiPeerConnectionObserver;
-use std::ffi::CString;
+use sha2::{Digest, Sha256};
+use std::ffi::{CStr, CString};
 use std::os::raw::c_char;
 use std::ptr::copy_nonoverlapping;
+use std::sync::Mutex;
 
-pub type RffiPeerConnectionFactory = u32;
+pub struct RffiPeerConnectionFactory {
+    audio_processing_config: Mutex<Option<AudioProcessingConfig>>,
+}
 
-pub static FAKE_PEER_CONNECTION_FACTORY: RffiPeerConnectionFactory = 10;
+impl RffiPeerConnectionFactory {
+    pub fn audio_processing_config(&self) -> Option<AudioProcessingConfig> {
+        *self.audio_processing_config.lock().unwrap()
+    }
+}
 
 pub type RffiCertificate = u32;
 
@@ -31,7 +40,9 @@ pub unsafe fn Rust_createPeerConnectionFactory(
     _use_injectable_network: bool,
 ) -> *const RffiPeerConnectionFactory {
     info!("Rust_createPeerConnectionFactory()");
-    &FAKE_PEER_CONNECTION_FACTORY
+    Box::leak(Box::new(RffiPeerConnectionFactory {
+        audio_processing_config: Mutex::new(None),
+    }))
 }
 
 #[allow(non_snake_case, clippy::missing_safety_doc, clippy::too_many_arguments)]
@@ -40,14 +51,30 @@ pub unsafe fn Rust_createPeerConnection(
     _observer: *const RffiPeerConnectionObserver,
     _certificate: *const RffiCertificate,
     _hide_ip: bool,
-    _ice_server: RffiIceServer,
+    ice_servers: *const RffiIceServer,
+    ice_servers_size: usize,
     _outgoing_audio_track: *const RffiAudioTrack,
     _outgoing_video_track: *const RffiVideoTrack,
     _enable_dtls: bool,
-    _enable_rtp_data_channel: bool,
+    enable_rtp_data_channel: bool,
 ) -> *const RffiPeerConnection {
     info!("Rust_createPeerConnection()");
-    Box::leak(Box::new(RffiPeerConnection::new()))
+    let peer_connection = RffiPeerConnection::new();
+    peer_connection.set_rtp_data_channel_enabled(enable_rtp_data_channel);
+    let ice_server_urls = (0..ice_servers_size)
+        .map(|i| {
+            let ice_server = &*ice_servers.add(i);
+            (0..ice_server.urls_size)
+                .map(|j| {
+                    CStr::from_ptr(*ice_server.urls.add(j))
+                        .to_string_lossy()
+                        .into_owned()
+                })
+                .collect()
+        })
+        .collect();
+    peer_connection.set_ice_server_urls(ice_server_urls);
+    Box::leak(Box::new(peer_connection))
 }
 
 #[allow(non_snake_case, clippy::missing_safety_doc)]
@@ -58,6 +85,15 @@ pub unsafe fn Rust_createAudioTrack(
     &FAKE_AUDIO_TRACK
 }
 
+#[allow(non_snake_case, clippy::missing_safety_doc)]
+pub unsafe fn Rust_configureAudioProcessing(
+    factory: *const RffiPeerConnectionFactory,
+    config: *const AudioProcessingConfig,
+) {
+    info!("Rust_configureAudioProcessing()");
+    *(*factory).audio_processing_config.lock().unwrap() = Some(*config);
+}
+
 #[allow(non_snake_case, clippy::missing_safety_doc)]
 pub unsafe fn Rust_createVideoSource(
     _factory: *const RffiPeerConnectionFactory,
@@ -83,10 +119,14 @@ pub unsafe fn Rust_generateCertificate() -> *const RffiCertificate {
 
 #[allow(non_snake_case, clippy::missing_safety_doc)]
 pub unsafe fn Rust_computeCertificateFingerprintSha256(
-    _cert: *const RffiCertificate,
-    _fingerprint: *mut [u8; 32],
+    cert: *const RffiCertificate,
+    fingerprint: *mut [u8; 32],
 ) -> bool {
     info!("Rust_computeCertificateFingerprintSha256()");
+    // Hash the fake certificate in place of its DER encoding so that it
+    // has a stable fingerprint.
+    let hash = Sha256::digest(&(*cert).to_be_bytes());
+    (*fingerprint).copy_from_slice(&hash);
     true
 }
 
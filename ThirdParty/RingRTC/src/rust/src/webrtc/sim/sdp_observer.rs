@@ -27,6 +27,8 @@ pub type RffiSessionDescription = &'static str;
 static mut FAKE_SDP: &str = "FAKE SDP";
 static mut FAKE_SDP_OFFER: &str = "FAKE SDP OFFER";
 static mut FAKE_SDP_ANSWER: &str = "FAKE SDP ANSWER";
+static mut FAKE_SDP_OFFER_WITH_INTERPRETER: &str = "FAKE SDP OFFER WITH INTERPRETER";
+static mut FAKE_SDP_ANSWER_WITH_INTERPRETER: &str = "FAKE SDP ANSWER WITH INTERPRETER";
 
 /// Simulation type for webrtc::rffi::CreateSessionDescriptionObserverRffi
 pub type RffiCreateSessionDescriptionObserver = u32;
@@ -140,9 +142,14 @@ pub unsafe fn Rust_localDescriptionForGroupCall(
     _ice_pwd: *const c_char,
     _dtls_fingerprint_sha256: *const [u8; 32],
     _demux_id: u32,
+    interpreter_audio: bool,
 ) -> *mut RffiSessionDescription {
     info!("Rust_localDescriptionForGroupCall(): ");
-    &mut FAKE_SDP_OFFER
+    if interpreter_audio {
+        &mut FAKE_SDP_OFFER_WITH_INTERPRETER
+    } else {
+        &mut FAKE_SDP_OFFER
+    }
 }
 
 #[allow(non_snake_case, clippy::missing_safety_doc)]
@@ -152,9 +159,14 @@ pub unsafe fn Rust_remoteDescriptionForGroupCall(
     _dtls_fingerprint_sha256: *const [u8; 32],
     _demux_ids_data: *const u32,
     _demux_ids_len: size_t,
+    interpreter_audio: bool,
 ) -> *mut RffiSessionDescription {
     info!("Rust_remoteDescriptionForGroupCall(): ");
-    &mut FAKE_SDP_ANSWER
+    if interpreter_audio {
+        &mut FAKE_SDP_ANSWER_WITH_INTERPRETER
+    } else {
+        &mut FAKE_SDP_ANSWER
+    }
 }
 
 #[allow(non_snake_case, clippy::missing_safety_doc)]
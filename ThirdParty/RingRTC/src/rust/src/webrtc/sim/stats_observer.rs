@@ -9,12 +9,57 @@ use std::ffi::c_void;
 use std::ptr;
 
 use crate::core::util::RustObject;
-use crate::webrtc::stats_observer::{MediaStatistics, StatsObserver, StatsObserverCallbacks};
+use crate::webrtc::stats_observer::{
+    AudioReceiverStatistics,
+    AudioSenderStatistics,
+    ConnectionStatistics,
+    MediaStatistics,
+    StatsObserver,
+    StatsObserverCallbacks,
+    VideoSenderStatistics,
+};
 
 /// Simulation type for webrtc::rffi::StatsObserverRffi
-pub type RffiStatsObserver = u32;
+pub struct RffiStatsObserver {
+    stats_observer:     RustObject,
+    stats_observer_cbs: *const c_void,
+}
 
-static FAKE_STATS_OBSERVER: u32 = 21;
+impl RffiStatsObserver {
+    /// Deliver a stats report to the Rust observer, like
+    /// OnStatsDelivered() does.
+    ///
+    /// # Safety
+    ///
+    /// The Rust StatsObserver this was created for must still be alive.
+    pub unsafe fn deliver_stats(
+        &self,
+        audio_sender_statistics: &[AudioSenderStatistics],
+        video_sender_statistics: &[VideoSenderStatistics],
+        audio_receiver_statistics: &[AudioReceiverStatistics],
+        connection_statistics: &[ConnectionStatistics],
+    ) {
+        let media_statistics = MediaStatistics {
+            timestamp_us:                   0,
+            audio_sender_statistics_size:   audio_sender_statistics.len() as u32,
+            audio_sender_statistics:        audio_sender_statistics.as_ptr(),
+            video_sender_statistics_size:   video_sender_statistics.len() as u32,
+            video_sender_statistics:        video_sender_statistics.as_ptr(),
+            audio_receiver_statistics_size: audio_receiver_statistics.len() as u32,
+            audio_receiver_statistics:      audio_receiver_statistics.as_ptr(),
+            video_receiver_statistics_size: 0,
+            video_receiver_statistics:      ptr::null(),
+            connection_statistics_size:     connection_statistics.len() as u32,
+            connection_statistics:          connection_statistics.as_ptr(),
+        };
+
+        let callbacks = self.stats_observer_cbs as *const StatsObserverCallbacks;
+        ((*callbacks).onStatsComplete)(
+            self.stats_observer as *mut StatsObserver,
+            &media_statistics,
+        );
+    }
+}
 
 #[allow(non_snake_case, clippy::missing_safety_doc)]
 pub unsafe fn Rust_createStatsObserver(
@@ -23,21 +68,14 @@ pub unsafe fn Rust_createStatsObserver(
 ) -> *const RffiStatsObserver {
     info!("Rust_createStatsObserver():");
 
-    let dummy = MediaStatistics {
-        timestamp_us:                   0,
-        audio_sender_statistics_size:   0,
-        audio_sender_statistics:        ptr::null(),
-        video_sender_statistics_size:   0,
-        video_sender_statistics:        ptr::null(),
-        audio_receiver_statistics_size: 0,
-        audio_receiver_statistics:      ptr::null(),
-        video_receiver_statistics_size: 0,
-        video_receiver_statistics:      ptr::null(),
+    let rffi_stats_observer = RffiStatsObserver {
+        stats_observer,
+        stats_observer_cbs,
     };
 
     // Hit on the onComplete() callback
-    let callbacks = stats_observer_cbs as *const StatsObserverCallbacks;
-    ((*callbacks).onStatsComplete)(stats_observer as *mut StatsObserver, &dummy);
+    rffi_stats_observer.deliver_stats(&[], &[], &[], &[]);
 
-    &FAKE_STATS_OBSERVER
+    // Never freed; the simulated release_ref() doesn't do anything.
+    Box::into_raw(Box::new(rffi_stats_observer))
 }
@@ -5,10 +5,14 @@
 
 //! WebRTC Create Session Description
 
+use std::collections::HashMap;
 use std::ffi::c_void;
+use std::net::SocketAddr;
+use std::sync::Mutex;
 use std::{ptr, slice};
 
-use crate::core::util::{ptr_as_mut, RustObject};
+use crate::core::util::{ptr_as_mut, redact_string, RustObject};
+use crate::webrtc::network::RffiIpPort;
 
 #[cfg(not(feature = "sim"))]
 use crate::webrtc::ffi::ref_count::release_ref;
@@ -28,7 +32,28 @@ pub use crate::webrtc::sim::stats_observer::RffiStatsObserver;
 #[derive(Debug)]
 pub struct StatsObserver {
     /// Pointer to C++ webrtc::rffi::StatsObserverRffi object.
-    rffi_stats_observer: *const RffiStatsObserver,
+    rffi_stats_observer:   *const RffiStatsObserver,
+    /// Round trip time of the selected ICE candidate pair, as of the
+    /// latest stats report.
+    selected_pair_rtt_ms:  Mutex<Option<u32>>,
+    /// Snapshot of the call quality as of the latest stats report.
+    call_statistics:       Mutex<CallStatistics>,
+    /// Timestamp of the latest stats report and the total bytes sent by
+    /// all senders as of it, for working out the send bitrate.
+    bytes_sent:            Mutex<Option<(i64, u64)>>,
+    /// Playout counters of received audio as of the latest stats report,
+    /// and the playout statistics for the period that ended with it.
+    audio_playout:         Mutex<(AudioPlayoutCounters, Option<AudioPlayoutStatistics>)>,
+    /// Total energy of each received audio stream, by SSRC, as of the
+    /// latest stats report, and the energy of each during the period that
+    /// ended with it, until taken.
+    received_audio_energy: Mutex<(HashMap<u32, f64>, Option<Vec<(u32, f64)>>)>,
+    /// Level (0 to 1) of the sent audio, if any, and of each received
+    /// audio stream, by SSRC, as of the latest report, until taken.  Only
+    /// kept when reports aren't logged, since those are polled often.
+    audio_levels:          Mutex<Option<(Option<f64>, Vec<(u32, f64)>)>>,
+    /// Whether each stats report is logged.
+    log_stats:             bool,
 }
 
 unsafe impl Send for StatsObserver {}
@@ -42,86 +67,139 @@ impl Drop for StatsObserver {
 }
 
 impl StatsObserver {
-    /// Create a new StatsObserver.
-    fn new() -> Self {
-        info!(
-            "ringrtc_stats!,\
-                timestamp_us,\
-                audio,\
-                send,\
-                ssrc,\
-                packets_sent,\
-                bytes_sent,\
-                remote_packets_lost,\
-                remote_jitter,\
-                remote_round_trip_time,\
-                audio_level,\
-                total_audio_energy"
-        );
-        info!(
-            "ringrtc_stats!,\
-                timestamp_us,\
-                video,\
-                send,\
-                ssrc,\
-                packets_sent,\
-                bytes_sent,\
-                frames_encoded,\
-                key_frames_encoded,\
-                total_encode_time,\
-                frame_width,\
-                frame_height,\
-                retransmitted_packets_sent,\
-                retransmitted_bytes_sent,\
-                total_packet_send_delay,\
-                nack_count,\
-                fir_count,\
-                pli_count,\
-                quality_limitation_reason,\
-                quality_limitation_resolution_changes,\
-                remote_packets_lost,\
-                remote_jitter,\
-                remote_round_trip_time"
-        );
-        info!(
-            "ringrtc_stats!,\
-                timestamp_us,\
-                audio,\
-                recv,\
-                ssrc,\
-                packets_received,\
-                packets_lost,\
-                bytes_received,\
-                jitter,\
-                frames_decoded,\
-                total_decode_time,\
-                audio_level,\
-                total_audio_energy"
-        );
-        info!(
-            "ringrtc_stats!,\
-                timestamp_us,\
-                video,\
-                recv,\
-                ssrc,\
-                packets_received,\
-                packets_lost,\
-                packets_repaired,\
-                bytes_received,\
-                frames_decoded,\
-                key_frames_decoded,\
-                total_decode_time,\
-                frame_width,\
-                frame_height"
-        );
+    /// Create a new StatsObserver, which logs every report if `log_stats`.
+    fn new(log_stats: bool) -> Self {
+        if log_stats {
+            info!(
+                "ringrtc_stats!,\
+                    timestamp_us,\
+                    audio,\
+                    send,\
+                    ssrc,\
+                    packets_sent,\
+                    bytes_sent,\
+                    remote_packets_lost,\
+                    remote_jitter,\
+                    remote_round_trip_time,\
+                    audio_level,\
+                    total_audio_energy,\
+                    retransmitted_packets_sent,\
+                    nack_count"
+            );
+            info!(
+                "ringrtc_stats!,\
+                    timestamp_us,\
+                    video,\
+                    send,\
+                    ssrc,\
+                    packets_sent,\
+                    bytes_sent,\
+                    frames_encoded,\
+                    key_frames_encoded,\
+                    total_encode_time,\
+                    frame_width,\
+                    frame_height,\
+                    retransmitted_packets_sent,\
+                    retransmitted_bytes_sent,\
+                    total_packet_send_delay,\
+                    nack_count,\
+                    fir_count,\
+                    pli_count,\
+                    quality_limitation_reason,\
+                    quality_limitation_resolution_changes,\
+                    remote_packets_lost,\
+                    remote_jitter,\
+                    remote_round_trip_time"
+            );
+            info!(
+                "ringrtc_stats!,\
+                    timestamp_us,\
+                    audio,\
+                    recv,\
+                    ssrc,\
+                    packets_received,\
+                    packets_lost,\
+                    bytes_received,\
+                    jitter,\
+                    frames_decoded,\
+                    total_decode_time,\
+                    audio_level,\
+                    total_audio_energy,\
+                    nack_count,\
+                    jitter_buffer_delay,\
+                    jitter_buffer_emitted_count,\
+                    total_samples_received,\
+                    concealed_samples,\
+                    concealment_events,\
+                    inserted_samples_for_deceleration,\
+                    removed_samples_for_acceleration"
+            );
+            info!(
+                "ringrtc_stats!,\
+                    timestamp_us,\
+                    video,\
+                    recv,\
+                    ssrc,\
+                    packets_received,\
+                    packets_lost,\
+                    packets_repaired,\
+                    bytes_received,\
+                    frames_decoded,\
+                    key_frames_decoded,\
+                    total_decode_time,\
+                    frame_width,\
+                    frame_height,\
+                    nack_count,\
+                    fir_count,\
+                    pli_count"
+            );
+        }
 
         Self {
             rffi_stats_observer: ptr::null(),
+            selected_pair_rtt_ms: Mutex::new(None),
+            call_statistics: Mutex::new(CallStatistics::default()),
+            bytes_sent: Mutex::new(None),
+            audio_playout: Mutex::new((AudioPlayoutCounters::default(), None)),
+            received_audio_energy: Mutex::new((HashMap::new(), None)),
+            audio_levels: Mutex::new(None),
+            log_stats,
         }
     }
 
     /// Invoked when statistics are received via the stats observer callback.
     fn on_stats_complete(&mut self, media_statistics: &MediaStatistics) {
+        if !self.log_stats {
+            // Only the received audio energy and the audio levels are wanted
+            // from these reports.
+            let audio_senders = unsafe {
+                if media_statistics.audio_sender_statistics.is_null() {
+                    &[]
+                } else {
+                    slice::from_raw_parts(
+                        media_statistics.audio_sender_statistics,
+                        media_statistics.audio_sender_statistics_size as usize,
+                    )
+                }
+            };
+            let audio_receivers = unsafe {
+                if media_statistics.audio_receiver_statistics.is_null() {
+                    &[]
+                } else {
+                    slice::from_raw_parts(
+                        media_statistics.audio_receiver_statistics,
+                        media_statistics.audio_receiver_statistics_size as usize,
+                    )
+                }
+            };
+            self.update_received_audio_energy(audio_receivers);
+            self.update_audio_levels(audio_senders, audio_receivers);
+            return;
+        }
+
+        let mut call_statistics = CallStatistics::default();
+        let mut bytes_sent = 0;
+
         if media_statistics.audio_sender_statistics_size > 0 {
             let audio_senders = unsafe {
                 if media_statistics.audio_sender_statistics.is_null() {
@@ -133,9 +211,24 @@ impl StatsObserver {
                     )
                 }
             };
+            call_statistics.audio = audio_senders.first().map(|audio_sender| {
+                MediaQualityStatistics {
+                    nack_count: audio_senders.iter().map(|sender| sender.nack_count).sum(),
+                    retransmitted_packets: audio_senders
+                        .iter()
+                        .map(|sender| sender.retransmitted_packets_sent)
+                        .sum(),
+                    ..MediaQualityStatistics::from_remote(
+                        audio_sender.remote_round_trip_time,
+                        audio_sender.remote_jitter,
+                        audio_sender.remote_packets_lost,
+                    )
+                }
+            });
             for audio_sender in audio_senders.iter() {
+                bytes_sent += audio_sender.bytes_sent;
                 info!(
-                    "ringrtc_stats!,{},audio,send,{},{},{},{},{:.5},{:.3},{:.5},{:.3}",
+                    "ringrtc_stats!,{},audio,send,{},{},{},{},{:.5},{:.3},{:.5},{:.3},{},{}",
                     media_statistics.timestamp_us,
                     audio_sender.ssrc,
                     audio_sender.packets_sent,
@@ -145,6 +238,8 @@ impl StatsObserver {
                     audio_sender.remote_round_trip_time,
                     audio_sender.audio_level,
                     audio_sender.total_audio_energy,
+                    audio_sender.retransmitted_packets_sent,
+                    audio_sender.nack_count,
                 );
             }
         }
@@ -160,7 +255,24 @@ impl StatsObserver {
                     )
                 }
             };
+            call_statistics.video = video_senders.first().map(|video_sender| {
+                MediaQualityStatistics {
+                    nack_count: video_senders.iter().map(|sender| sender.nack_count).sum(),
+                    retransmitted_packets: video_senders
+                        .iter()
+                        .map(|sender| sender.retransmitted_packets_sent)
+                        .sum(),
+                    fir_count: video_senders.iter().map(|sender| sender.fir_count).sum(),
+                    pli_count: video_senders.iter().map(|sender| sender.pli_count).sum(),
+                    ..MediaQualityStatistics::from_remote(
+                        video_sender.remote_round_trip_time,
+                        video_sender.remote_jitter,
+                        video_sender.remote_packets_lost,
+                    )
+                }
+            });
             for video_sender in video_senders.iter() {
+                bytes_sent += video_sender.bytes_sent;
                 info!("ringrtc_stats!,{},video,send,{},{},{},{},{},{:.3},{},{},{},{},{:.3},{},{},{},{},{},{},{:.5},{:.3}",
                       media_statistics.timestamp_us,
                       video_sender.ssrc,
@@ -197,9 +309,11 @@ impl StatsObserver {
                     )
                 }
             };
+            self.update_audio_playout(audio_receivers);
+            self.update_received_audio_energy(audio_receivers);
             for audio_receiver in audio_receivers.iter() {
                 info!(
-                    "ringrtc_stats!,{},audio,recv,{},{},{},{},{:.5},{},{:.3},{:.5},{:.3}",
+                    "ringrtc_stats!,{},audio,recv,{},{},{},{},{:.5},{},{:.3},{:.5},{:.3},{},{:.3},{},{},{},{},{},{}",
                     media_statistics.timestamp_us,
                     audio_receiver.ssrc,
                     audio_receiver.packets_received,
@@ -210,8 +324,19 @@ impl StatsObserver {
                     audio_receiver.total_decode_time,
                     audio_receiver.audio_level,
                     audio_receiver.total_audio_energy,
+                    audio_receiver.nack_count,
+                    audio_receiver.jitter_buffer_delay,
+                    audio_receiver.jitter_buffer_emitted_count,
+                    audio_receiver.total_samples_received,
+                    audio_receiver.concealed_samples,
+                    audio_receiver.concealment_events,
+                    audio_receiver.inserted_samples_for_deceleration,
+                    audio_receiver.removed_samples_for_acceleration,
                 );
             }
+        } else {
+            self.update_audio_playout(&[]);
+            self.update_received_audio_energy(&[]);
         }
 
         if media_statistics.video_receiver_statistics_size > 0 {
@@ -227,7 +352,7 @@ impl StatsObserver {
             };
             for video_receive in video_receivers.iter() {
                 info!(
-                    "ringrtc_stats!,{},video,recv,{},{},{},{},{},{},{},{:.3},{},{}",
+                    "ringrtc_stats!,{},video,recv,{},{},{},{},{},{},{},{:.3},{},{},{},{},{}",
                     media_statistics.timestamp_us,
                     video_receive.ssrc,
                     video_receive.packets_received,
@@ -239,9 +364,165 @@ impl StatsObserver {
                     video_receive.total_decode_time,
                     video_receive.frame_width,
                     video_receive.frame_height,
+                    video_receive.nack_count,
+                    video_receive.fir_count,
+                    video_receive.pli_count,
                 );
             }
         }
+
+        let connections = unsafe {
+            if media_statistics.connection_statistics.is_null() {
+                &[]
+            } else {
+                slice::from_raw_parts(
+                    media_statistics.connection_statistics,
+                    media_statistics.connection_statistics_size as usize,
+                )
+            }
+        };
+        // A report without a selected pair means we aren't connected (anymore).
+        let selected_pair_rtt_ms = connections
+            .first()
+            .map(|connection| (connection.current_round_trip_time * 1000.0).round() as u32);
+        if let Ok(mut rtt_ms) = self.selected_pair_rtt_ms.lock() {
+            *rtt_ms = selected_pair_rtt_ms;
+        }
+
+        call_statistics.selected_pair = connections.first().map(SelectedCandidatePair::from);
+        call_statistics.send_bitrate_bps =
+            self.update_send_bitrate(media_statistics.timestamp_us, bytes_sent);
+        if let Ok(mut snapshot) = self.call_statistics.lock() {
+            *snapshot = call_statistics;
+        }
+    }
+
+    /// Remember the bytes sent as of this report and return the bitrate
+    /// since the previous one, if there was one.
+    fn update_send_bitrate(&self, timestamp_us: i64, bytes_sent: u64) -> Option<u64> {
+        let mut previous = self.bytes_sent.lock().ok()?;
+        let send_bitrate_bps = match *previous {
+            Some((previous_timestamp_us, previous_bytes_sent))
+                if timestamp_us > previous_timestamp_us && bytes_sent >= previous_bytes_sent =>
+            {
+                let elapsed_us = (timestamp_us - previous_timestamp_us) as u64;
+                Some((bytes_sent - previous_bytes_sent) * 8 * 1_000_000 / elapsed_us)
+            }
+            _ => None,
+        };
+        *previous = Some((timestamp_us, bytes_sent));
+        send_bitrate_bps
+    }
+
+    /// Return a snapshot of the call quality as of the latest stats
+    /// report. Everything is None until the first report arrives.
+    pub fn call_statistics(&self) -> CallStatistics {
+        self.call_statistics
+            .lock()
+            .map(|call_statistics| call_statistics.clone())
+            .unwrap_or_default()
+    }
+
+    /// Return the round trip time of the selected ICE candidate pair
+    /// from the latest stats report, or None if there is no selected
+    /// pair yet.
+    pub fn selected_pair_rtt_ms(&self) -> Option<u32> {
+        self.selected_pair_rtt_ms
+            .lock()
+            .ok()
+            .and_then(|rtt_ms| *rtt_ms)
+    }
+
+    /// Compute the playout statistics for the period since the previous
+    /// report. Counters of all audio receivers are added together.
+    fn update_audio_playout(&self, audio_receivers: &[AudioReceiverStatistics]) {
+        let mut audio_playout = match self.audio_playout.lock() {
+            Ok(audio_playout) => audio_playout,
+            Err(_) => return,
+        };
+        if audio_receivers.is_empty() {
+            *audio_playout = (AudioPlayoutCounters::default(), None);
+            return;
+        }
+
+        let counters = audio_receivers
+            .iter()
+            .fold(AudioPlayoutCounters::default(), |sum, audio_receiver| {
+                sum.add(audio_receiver)
+            });
+        let statistics = counters.since(&audio_playout.0);
+        *audio_playout = (counters, Some(statistics));
+    }
+
+    /// Return the playout statistics of received audio for the latest
+    /// stats period, or None if no audio is being received.
+    pub fn audio_playout_statistics(&self) -> Option<AudioPlayoutStatistics> {
+        self.audio_playout
+            .lock()
+            .ok()
+            .and_then(|audio_playout| audio_playout.1)
+    }
+
+    /// Compute the energy of each received audio stream for the period
+    /// since the previous report.  A stream new in this report gets its
+    /// total energy, which is small if it just started.
+    fn update_received_audio_energy(&self, audio_receivers: &[AudioReceiverStatistics]) {
+        let mut received_audio_energy = match self.received_audio_energy.lock() {
+            Ok(received_audio_energy) => received_audio_energy,
+            Err(_) => return,
+        };
+        let (totals, recent) = &mut *received_audio_energy;
+
+        let energies = audio_receivers
+            .iter()
+            .map(|audio_receiver| {
+                let previous = totals.get(&audio_receiver.ssrc).copied().unwrap_or(0.0);
+                let energy = (audio_receiver.total_audio_energy - previous).max(0.0);
+                (audio_receiver.ssrc, energy)
+            })
+            .collect();
+        *totals = audio_receivers
+            .iter()
+            .map(|audio_receiver| (audio_receiver.ssrc, audio_receiver.total_audio_energy))
+            .collect();
+        *recent = Some(energies);
+    }
+
+    /// Return the energy of each received audio stream, by SSRC, for the
+    /// period that ended with the latest stats report, or None if there
+    /// hasn't been a report since the last call.
+    pub fn take_received_audio_energy(&self) -> Option<Vec<(u32, f64)>> {
+        self.received_audio_energy
+            .lock()
+            .ok()
+            .and_then(|mut received_audio_energy| received_audio_energy.1.take())
+    }
+
+    fn update_audio_levels(
+        &self,
+        audio_senders: &[AudioSenderStatistics],
+        audio_receivers: &[AudioReceiverStatistics],
+    ) {
+        let sent = audio_senders
+            .first()
+            .map(|audio_sender| audio_sender.audio_level);
+        let received = audio_receivers
+            .iter()
+            .map(|audio_receiver| (audio_receiver.ssrc, audio_receiver.audio_level))
+            .collect();
+        if let Ok(mut audio_levels) = self.audio_levels.lock() {
+            *audio_levels = Some((sent, received));
+        }
+    }
+
+    /// Return the level (0 to 1) of the sent audio, if any, and of each
+    /// received audio stream, by SSRC, as of the latest stats report, or
+    /// None if there hasn't been a report since the last call.
+    pub fn take_audio_levels(&self) -> Option<(Option<f64>, Vec<(u32, f64)>)> {
+        self.audio_levels
+            .lock()
+            .ok()
+            .and_then(|mut audio_levels| audio_levels.take())
     }
 
     /// Set the RFFI observer object.
@@ -256,20 +537,22 @@ impl StatsObserver {
 }
 
 #[repr(C)]
-#[derive(Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct AudioSenderStatistics {
-    pub ssrc:                   u32,
-    pub packets_sent:           u32,
-    pub bytes_sent:             u64,
-    pub remote_packets_lost:    i32,
-    pub remote_jitter:          f64,
-    pub remote_round_trip_time: f64,
-    pub audio_level:            f64,
-    pub total_audio_energy:     f64,
+    pub ssrc:                       u32,
+    pub packets_sent:               u32,
+    pub bytes_sent:                 u64,
+    pub remote_packets_lost:        i32,
+    pub remote_jitter:              f64,
+    pub remote_round_trip_time:     f64,
+    pub audio_level:                f64,
+    pub total_audio_energy:         f64,
+    pub retransmitted_packets_sent: u64,
+    pub nack_count:                 u32,
 }
 
 #[repr(C)]
-#[derive(Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct VideoSenderStatistics {
     pub ssrc: u32,
     pub packets_sent: u32,
@@ -293,17 +576,116 @@ pub struct VideoSenderStatistics {
 }
 
 #[repr(C)]
-#[derive(Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct AudioReceiverStatistics {
-    pub ssrc:               u32,
-    pub packets_received:   u32,
-    pub packets_lost:       i32,
-    pub bytes_received:     u64,
-    pub jitter:             f64,
-    pub frames_decoded:     u32,
-    pub total_decode_time:  f64,
-    pub audio_level:        f64,
-    pub total_audio_energy: f64,
+    pub ssrc:                              u32,
+    pub packets_received:                  u32,
+    pub packets_lost:                      i32,
+    pub bytes_received:                    u64,
+    pub jitter:                            f64,
+    pub frames_decoded:                    u32,
+    pub total_decode_time:                 f64,
+    pub audio_level:                       f64,
+    pub total_audio_energy:                f64,
+    pub nack_count:                        u32,
+    pub jitter_buffer_delay:               f64,
+    pub jitter_buffer_emitted_count:       u64,
+    pub total_samples_received:            u64,
+    pub concealed_samples:                 u64,
+    pub concealment_events:                u64,
+    pub inserted_samples_for_deceleration: u64,
+    pub removed_samples_for_acceleration:  u64,
+}
+
+/// Health of the playout of received audio over one stats period, to
+/// tell glitches caused by playout apart from audio that arrived badly.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct AudioPlayoutStatistics {
+    /// Average time played samples spent in the jitter buffer.
+    pub jitter_buffer_delay_ms: f64,
+    /// Fraction of samples played that were concealed (expanded)
+    /// because no audio was available in time.
+    pub expand_rate:            f64,
+    /// Fraction of samples removed to shrink the jitter buffer.
+    pub accelerate_rate:        f64,
+    /// Fraction of samples inserted to grow the jitter buffer.
+    pub preemptive_expand_rate: f64,
+    /// Number of times concealment started.
+    pub concealment_events:     u64,
+}
+
+/// Cumulative playout counters, from which AudioPlayoutStatistics are
+/// computed per period.
+#[derive(Clone, Copy, Debug, Default)]
+struct AudioPlayoutCounters {
+    jitter_buffer_delay:               f64,
+    jitter_buffer_emitted_count:       u64,
+    total_samples_received:            u64,
+    concealed_samples:                 u64,
+    concealment_events:                u64,
+    inserted_samples_for_deceleration: u64,
+    removed_samples_for_acceleration:  u64,
+}
+
+impl AudioPlayoutCounters {
+    fn add(self, audio_receiver: &AudioReceiverStatistics) -> Self {
+        Self {
+            jitter_buffer_delay:               self.jitter_buffer_delay
+                + audio_receiver.jitter_buffer_delay,
+            jitter_buffer_emitted_count:       self.jitter_buffer_emitted_count
+                + audio_receiver.jitter_buffer_emitted_count,
+            total_samples_received:            self.total_samples_received
+                + audio_receiver.total_samples_received,
+            concealed_samples:                 self.concealed_samples
+                + audio_receiver.concealed_samples,
+            concealment_events:                self.concealment_events
+                + audio_receiver.concealment_events,
+            inserted_samples_for_deceleration: self.inserted_samples_for_deceleration
+                + audio_receiver.inserted_samples_for_deceleration,
+            removed_samples_for_acceleration:  self.removed_samples_for_acceleration
+                + audio_receiver.removed_samples_for_acceleration,
+        }
+    }
+
+    // The counters can go backwards when a receiver goes away, so deltas saturate.
+    fn since(&self, previous: &Self) -> AudioPlayoutStatistics {
+        let samples = self
+            .total_samples_received
+            .saturating_sub(previous.total_samples_received);
+        let rate = |current: u64, previous: u64| {
+            if samples == 0 {
+                0.0
+            } else {
+                current.saturating_sub(previous) as f64 / samples as f64
+            }
+        };
+        let emitted = self
+            .jitter_buffer_emitted_count
+            .saturating_sub(previous.jitter_buffer_emitted_count);
+        let jitter_buffer_delay_ms = if emitted == 0 {
+            0.0
+        } else {
+            (self.jitter_buffer_delay - previous.jitter_buffer_delay).max(0.0) * 1000.0
+                / emitted as f64
+        };
+
+        AudioPlayoutStatistics {
+            jitter_buffer_delay_ms,
+            expand_rate: rate(self.concealed_samples, previous.concealed_samples),
+            accelerate_rate: rate(
+                self.removed_samples_for_acceleration,
+                previous.removed_samples_for_acceleration,
+            ),
+            preemptive_expand_rate: rate(
+                self.inserted_samples_for_deceleration,
+                previous.inserted_samples_for_deceleration,
+            ),
+            concealment_events: self
+                .concealment_events
+                .saturating_sub(previous.concealment_events),
+        }
+    }
 }
 
 #[repr(C)]
@@ -319,6 +701,147 @@ pub struct VideoReceiverStatistics {
     pub total_decode_time:  f64,
     pub frame_width:        u32,
     pub frame_height:       u32,
+    pub nack_count:         u32,
+    pub fir_count:          u32,
+    pub pli_count:          u32,
+}
+
+#[repr(C)]
+#[derive(Debug)]
+pub struct ConnectionStatistics {
+    pub current_round_trip_time: f64,
+    pub local_candidate_type:    i32,
+    pub remote_candidate_type:   i32,
+    pub remote_address:          RffiIpPort,
+}
+
+/// Type of an ICE candidate, as in the candidate-attribute of RFC 5245.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CandidateType {
+    Unknown,
+    Host,
+    ServerReflexive,
+    PeerReflexive,
+    Relay,
+}
+
+impl CandidateType {
+    /// Maps the value reported in ConnectionStatistics.
+    fn from_i32(candidate_type: i32) -> Self {
+        match candidate_type {
+            1 => CandidateType::Host,
+            2 => CandidateType::ServerReflexive,
+            3 => CandidateType::PeerReflexive,
+            4 => CandidateType::Relay,
+            _ => CandidateType::Unknown,
+        }
+    }
+}
+
+/// Quality of one kind of outgoing media, as reported back by the
+/// remote side, and how much recovery the remote side asked for: NACKs
+/// and the packets retransmitted for them, and FIRs and PLIs (key frame
+/// requests, so always 0 for audio).  The counts are totals for the
+/// call so far, summed over all senders of the media.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct MediaQualityStatistics {
+    pub round_trip_time_ms:    f64,
+    pub jitter_ms:             f64,
+    pub packets_lost:          i32,
+    pub nack_count:            u32,
+    pub retransmitted_packets: u64,
+    pub fir_count:             u32,
+    pub pli_count:             u32,
+}
+
+impl MediaQualityStatistics {
+    fn from_remote(round_trip_time: f64, jitter: f64, packets_lost: i32) -> Self {
+        Self {
+            round_trip_time_ms: round_trip_time * 1000.0,
+            jitter_ms: jitter * 1000.0,
+            packets_lost,
+            ..Default::default()
+        }
+    }
+}
+
+/// The ICE candidate pair the call is using.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SelectedCandidatePair {
+    pub round_trip_time_ms:    f64,
+    pub local_candidate_type:  CandidateType,
+    pub remote_candidate_type: CandidateType,
+    pub remote_address:        SocketAddr,
+}
+
+impl From<&ConnectionStatistics> for SelectedCandidatePair {
+    fn from(connection: &ConnectionStatistics) -> Self {
+        Self {
+            round_trip_time_ms:    connection.current_round_trip_time * 1000.0,
+            local_candidate_type:  CandidateType::from_i32(connection.local_candidate_type),
+            remote_candidate_type: CandidateType::from_i32(connection.remote_candidate_type),
+            remote_address:        connection.remote_address.socket_addr(),
+        }
+    }
+}
+
+/// Snapshot of the quality of a call, taken from the latest stats
+/// report. Each part is None if the report didn't include it, and the
+/// send bitrate is None until there have been two reports.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CallStatistics {
+    pub audio:            Option<MediaQualityStatistics>,
+    pub video:            Option<MediaQualityStatistics>,
+    pub selected_pair:    Option<SelectedCandidatePair>,
+    pub send_bitrate_bps: Option<u64>,
+}
+
+impl CallStatistics {
+    /// Returns the round trip time of the selected candidate pair,
+    /// rounded to the nearest millisecond.
+    pub fn selected_pair_rtt_ms(&self) -> Option<u32> {
+        self.selected_pair
+            .map(|pair| pair.round_trip_time_ms.round() as u32)
+    }
+
+    /// Returns a one-line description suitable for logs, with IP
+    /// addresses redacted.
+    pub fn to_info_string(&self) -> String {
+        let media = |media: &Option<MediaQualityStatistics>| match media {
+            Some(media) => format!(
+                "rtt: {:.1}ms, jitter: {:.1}ms, lost: {}, nacks: {}, retransmitted: {}, firs: {}, plis: {}",
+                media.round_trip_time_ms,
+                media.jitter_ms,
+                media.packets_lost,
+                media.nack_count,
+                media.retransmitted_packets,
+                media.fir_count,
+                media.pli_count
+            ),
+            None => "none".to_owned(),
+        };
+        let selected_pair = match &self.selected_pair {
+            Some(pair) => format!(
+                "rtt: {:.1}ms, local: {:?}, remote: {:?} {}",
+                pair.round_trip_time_ms,
+                pair.local_candidate_type,
+                pair.remote_candidate_type,
+                pair.remote_address
+            ),
+            None => "none".to_owned(),
+        };
+        let send_bitrate = match self.send_bitrate_bps {
+            Some(send_bitrate_bps) => format!("{}bps", send_bitrate_bps),
+            None => "none".to_owned(),
+        };
+        redact_string(&format!(
+            "audio: [{}], video: [{}], selected pair: [{}], send bitrate: {}",
+            media(&self.audio),
+            media(&self.video),
+            selected_pair,
+            send_bitrate
+        ))
+    }
 }
 
 /// MediaStatistics struct that holds all the statistics.
@@ -334,6 +857,8 @@ pub struct MediaStatistics {
     pub audio_receiver_statistics:      *const AudioReceiverStatistics,
     pub video_receiver_statistics_size: u32,
     pub video_receiver_statistics:      *const VideoReceiverStatistics,
+    pub connection_statistics_size:     u32,
+    pub connection_statistics:          *const ConnectionStatistics,
 }
 
 /// StatsObserver OnStatsComplete() callback.
@@ -368,7 +893,18 @@ const STATS_OBSERVER_CBS_PTR: *const StatsObserverCallbacks = &STATS_OBSERVER_CB
 /// registering the collector callbacks to this module, and wraps the
 /// result in a Rust StatsObserver object.
 pub fn create_stats_observer() -> Box<StatsObserver> {
-    let stats_observer = Box::new(StatsObserver::new());
+    create(true)
+}
+
+/// Create a new Rust StatsObserver object that doesn't log its reports,
+/// for polling the received audio energy more often than stats are
+/// logged.
+pub fn create_audio_energy_observer() -> Box<StatsObserver> {
+    create(false)
+}
+
+fn create(log_stats: bool) -> Box<StatsObserver> {
+    let stats_observer = Box::new(StatsObserver::new(log_stats));
     let stats_observer_ptr = Box::into_raw(stats_observer);
     let rffi_stats_observer = unsafe {
         stats::Rust_createStatsObserver(
@@ -381,3 +917,58 @@ pub fn create_stats_observer() -> Box<StatsObserver> {
     stats_observer.set_rffi_stats_observer(rffi_stats_observer);
     stats_observer
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn call_statistics_info_string_redacts_address() {
+        let call_statistics = CallStatistics {
+            audio:            Some(MediaQualityStatistics::from_remote(0.05, 0.0125, 3)),
+            video:            None,
+            selected_pair:    Some(SelectedCandidatePair {
+                round_trip_time_ms:    48.0,
+                local_candidate_type:  CandidateType::Host,
+                remote_candidate_type: CandidateType::Relay,
+                remote_address:        "203.0.113.7:3478".parse().unwrap(),
+            }),
+            send_bitrate_bps: None,
+        };
+
+        let info = call_statistics.to_info_string();
+        assert!(!info.contains("203.0.113.7"), "{}", info);
+        assert!(info.contains("[REDACTED]:3478"), "{}", info);
+        assert!(info.contains("rtt: 50.0ms, jitter: 12.5ms, lost: 3"), "{}", info);
+        assert!(info.contains("video: [none]"), "{}", info);
+        assert!(info.contains("local: Host, remote: Relay"), "{}", info);
+        assert!(info.contains("send bitrate: none"), "{}", info);
+    }
+
+    #[test]
+    fn selected_pair_rtt_rounded() {
+        let mut call_statistics = CallStatistics::default();
+        assert_eq!(call_statistics.selected_pair_rtt_ms(), None);
+
+        call_statistics.selected_pair = Some(SelectedCandidatePair {
+            round_trip_time_ms:    47.6,
+            local_candidate_type:  CandidateType::Host,
+            remote_candidate_type: CandidateType::Relay,
+            remote_address:        "203.0.113.7:3478".parse().unwrap(),
+        });
+        assert_eq!(call_statistics.selected_pair_rtt_ms(), Some(48));
+    }
+
+    #[test]
+    fn send_bitrate_from_bytes_sent() {
+        let stats_observer = StatsObserver::new(true);
+        assert_eq!(stats_observer.update_send_bitrate(1_000_000, 5_000), None);
+        // 25,000 bytes in half a second.
+        assert_eq!(
+            stats_observer.update_send_bitrate(1_500_000, 30_000),
+            Some(400_000)
+        );
+        // Counters that went backwards (a new sender) don't give a bitrate.
+        assert_eq!(stats_observer.update_send_bitrate(2_000_000, 1_000), None);
+    }
+}
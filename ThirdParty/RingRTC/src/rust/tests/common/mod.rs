@@ -7,6 +7,7 @@
 
 // Requires the 'sim' feature
 
+use std::collections::HashMap;
 use std::env;
 use std::sync::Mutex;
 use std::time::{Duration, SystemTime};
@@ -21,10 +22,11 @@ use simplelog::{Config, ConfigBuilder, SimpleLogger};
 
 use ringrtc::common::{ApplicationEvent, CallMediaType, DeviceId, FeatureLevel};
 use ringrtc::core::call::Call;
-use ringrtc::core::call_manager::CallManager;
+use ringrtc::core::call_manager::{CallManager, CallManagerConfig};
 use ringrtc::core::connection::Connection;
-use ringrtc::core::signaling;
+use ringrtc::core::{group_call, signaling};
 use ringrtc::sim::sim_platform::SimPlatform;
+use ringrtc::webrtc::peer_connection_observer::NetworkRoute;
 
 /*
 use ringrtc::common::{CallDirection, CallId};
@@ -124,10 +126,14 @@ impl Drop for TestContext {
 #[allow(dead_code)]
 impl TestContext {
     pub fn new() -> Self {
-        info!("TestContext::new()");
+        Self::with_config(CallManagerConfig::default())
+    }
+
+    pub fn with_config(config: CallManagerConfig) -> Self {
+        info!("TestContext::with_config(): {:?}", config);
 
         let mut platform = SimPlatform::new();
-        let call_manager = CallManager::new(platform.clone()).unwrap();
+        let call_manager = CallManager::with_config(platform.clone(), config).unwrap();
 
         platform.set_call_manager(call_manager.clone());
 
@@ -168,6 +174,31 @@ impl TestContext {
         platform.no_auto_message_sent_for_ice(enable);
     }
 
+    pub fn assume_all_messages_sent(&self, enable: bool) {
+        let mut platform = self.call_manager.platform().unwrap();
+        platform.assume_all_messages_sent(enable);
+    }
+
+    pub fn acknowledge_ringing(&self, enable: bool) {
+        let mut platform = self.call_manager.platform().unwrap();
+        platform.acknowledge_ringing(enable);
+    }
+
+    pub fn disable_trickle_ice(&self, disable: bool) {
+        let mut platform = self.call_manager.platform().unwrap();
+        platform.disable_trickle_ice(disable);
+    }
+
+    pub fn enable_frame_encryption(&self, enable: bool) {
+        let mut platform = self.call_manager.platform().unwrap();
+        platform.enable_frame_encryption(enable);
+    }
+
+    pub fn signaling_sent_assumed(&self) -> usize {
+        let platform = self.call_manager.platform().unwrap();
+        platform.signaling_sent_assumed()
+    }
+
     pub fn offers_sent(&self) -> usize {
         let platform = self.call_manager.platform().unwrap();
         platform.offers_sent()
@@ -193,6 +224,11 @@ impl TestContext {
         platform.need_permission_hangups_sent()
     }
 
+    pub fn away_hangups_sent(&self) -> usize {
+        let platform = self.call_manager.platform().unwrap();
+        platform.away_hangups_sent()
+    }
+
     pub fn accepted_hangups_sent(&self) -> usize {
         let platform = self.call_manager.platform().unwrap();
         platform.accepted_hangups_sent()
@@ -233,6 +269,21 @@ impl TestContext {
         platform.busys_sent()
     }
 
+    pub fn transfer_requests_sent(&self) -> usize {
+        let platform = self.call_manager.platform().unwrap();
+        platform.transfer_requests_sent()
+    }
+
+    pub fn transfer_accepteds_sent(&self) -> usize {
+        let platform = self.call_manager.platform().unwrap();
+        platform.transfer_accepteds_sent()
+    }
+
+    pub fn transfer_requests(&self) -> Vec<(String, String)> {
+        let platform = self.call_manager.platform().unwrap();
+        platform.transfer_requests()
+    }
+
     pub fn stream_count(&self) -> usize {
         let platform = self.call_manager.platform().unwrap();
         platform.stream_count()
@@ -252,6 +303,68 @@ impl TestContext {
         let platform = self.call_manager.platform().unwrap();
         platform.call_concluded_count()
     }
+
+    pub fn audio_loopback_level_count(&self) -> usize {
+        let platform = self.call_manager.platform().unwrap();
+        platform.audio_loopback_level_count()
+    }
+
+    pub fn ice_candidate_errors(&self) -> Vec<(String, u16, String)> {
+        let platform = self.call_manager.platform().unwrap();
+        platform.ice_candidate_errors()
+    }
+
+    pub fn data_messages_received(&self) -> usize {
+        let platform = self.call_manager.platform().unwrap();
+        platform.data_messages_received()
+    }
+
+    pub fn outgoing_keyframes(&self) -> usize {
+        let platform = self.call_manager.platform().unwrap();
+        platform.outgoing_keyframes()
+    }
+
+    pub fn dtmf_received(&self) -> Vec<char> {
+        let platform = self.call_manager.platform().unwrap();
+        platform.dtmf_received()
+    }
+
+    pub fn network_routes(&self) -> Vec<NetworkRoute> {
+        let platform = self.call_manager.platform().unwrap();
+        platform.network_routes()
+    }
+
+    pub fn http_request_ids(&self) -> Vec<u32> {
+        let platform = self.call_manager.platform().unwrap();
+        platform.http_request_ids()
+    }
+
+    pub fn http_request_headers(&self) -> Vec<HashMap<String, String>> {
+        let platform = self.call_manager.platform().unwrap();
+        platform.http_request_headers()
+    }
+
+    pub fn peek_responses(&self) -> Vec<u32> {
+        let platform = self.call_manager.platform().unwrap();
+        platform.peek_responses()
+    }
+
+    pub fn peek_timeouts(&self) -> Vec<u32> {
+        let platform = self.call_manager.platform().unwrap();
+        platform.peek_timeouts()
+    }
+
+    pub fn group_connection_states(
+        &self,
+    ) -> Vec<(group_call::ClientId, group_call::ConnectionState)> {
+        let platform = self.call_manager.platform().unwrap();
+        platform.group_connection_states()
+    }
+
+    pub fn group_call_ends(&self) -> Vec<(group_call::ClientId, group_call::EndReason)> {
+        let platform = self.call_manager.platform().unwrap();
+        platform.group_call_ends()
+    }
 }
 
 pub fn random_received_offer(age: Duration) -> signaling::ReceivedOffer {
@@ -13,16 +13,42 @@ extern crate log;
 use std::ptr;
 use std::time::Duration;
 
-use ringrtc::common::{ApplicationEvent, CallId, CallState, ConnectionState, DeviceId};
+use ringrtc::common::{
+    ApplicationEvent,
+    CallId,
+    CallMediaType,
+    CallState,
+    ConnectionState,
+    DeviceId,
+};
 use ringrtc::core::bandwidth_mode::BandwidthMode;
-use ringrtc::core::call_manager::MAX_MESSAGE_AGE_SEC;
+use ringrtc::core::call::Call;
+use ringrtc::core::call_manager::{
+    CallManagerConfig,
+    MAX_MESSAGE_AGE_SEC,
+    MAX_VALID_MESSAGE_AGE_SEC,
+};
+use ringrtc::core::connection::ReconnectPolicy;
 use ringrtc::core::signaling;
+use ringrtc::sim::sim_platform::SimPlatform;
 use ringrtc::webrtc::data_channel::DataChannel;
 use ringrtc::webrtc::media::MediaStream;
+use ringrtc::webrtc::peer_connection_observer::{
+    IceCandidateNetwork,
+    IceInterfacePolicy,
+    PeerConnectionObserverTrait,
+};
 
 #[macro_use]
 mod common;
-use common::{random_received_ice_candidate, random_received_offer, test_init, TestContext, PRNG};
+use common::{
+    random_ice_candidate,
+    random_received_ice_candidate,
+    random_received_offer,
+    test_init,
+    TestContext,
+    PRNG,
+};
 
 // Create an inbound call session up to the ConnectingBeforeAccepted state.
 //
@@ -37,7 +63,10 @@ use common::{random_received_ice_candidate, random_received_offer, test_init, Te
 // - check answer sent
 // Now in the Connecting state.
 fn start_inbound_call() -> TestContext {
-    let context = TestContext::new();
+    start_inbound_call_with_context(TestContext::new())
+}
+
+fn start_inbound_call_with_context(context: TestContext) -> TestContext {
     let mut cm = context.cm();
 
     let remote_peer = format!("REMOTE_PEER-{}", PRNG.gen::<u16>()).to_owned();
@@ -111,7 +140,11 @@ fn inbound_ice_connecting() {
 // Now in the ConnectedAndAccepted state.
 
 fn connect_inbound_call() -> TestContext {
-    let context = start_inbound_call();
+    connect_inbound_call_with_context(TestContext::new())
+}
+
+fn connect_inbound_call_with_context(context: TestContext) -> TestContext {
+    let context = start_inbound_call_with_context(context);
     let mut cm = context.cm();
     let active_call = context.active_call();
     let mut active_connection = context.active_connection();
@@ -195,6 +228,308 @@ fn inbound_call_connected() {
     let _ = connect_inbound_call();
 }
 
+#[test]
+fn reconnect_policy_delays() {
+    let policy = ReconnectPolicy {
+        initial_delay: Duration::from_millis(100),
+        multiplier:    2.0,
+        max_delay:     Duration::from_millis(500),
+        max_attempts:  5,
+    };
+    assert_eq!(
+        vec![100, 200, 400, 500, 500],
+        policy
+            .delays()
+            .map(|delay| delay.as_millis())
+            .collect::<Vec<_>>()
+    );
+
+    // A multiplier below 1 doesn't shrink the delays.
+    let policy = ReconnectPolicy {
+        multiplier:   0.5,
+        max_attempts: 3,
+        ..policy
+    };
+    assert_eq!(
+        vec![100, 100, 100],
+        policy
+            .delays()
+            .map(|delay| delay.as_millis())
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(Duration::from_millis(500), policy.delay(u32::MAX));
+}
+
+// Connect an inbound call that gives ICE 10ms, 20ms and then 40ms to
+// reconnect, and drop its connection.
+fn disconnect_inbound_call_with_reconnect_policy() -> TestContext {
+    let context = TestContext::new();
+    context
+        .cm()
+        .set_reconnect_policy(Some(ReconnectPolicy {
+            initial_delay: Duration::from_millis(10),
+            multiplier:    2.0,
+            max_delay:     Duration::from_millis(40),
+            max_attempts:  3,
+        }))
+        .expect(error_line!());
+    let context = connect_inbound_call_with_context(context);
+    let mut cm = context.cm();
+    let mut active_connection = context.active_connection();
+
+    info!("test: injecting ice disconnected");
+    active_connection
+        .inject_ice_disconnected()
+        .expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(
+        context.active_call().state().expect(error_line!()),
+        CallState::ReconnectingAfterAccepted
+    );
+    assert_eq!(context.event_count(ApplicationEvent::Reconnecting), 1);
+
+    context
+}
+
+#[test]
+fn inbound_call_reconnect_attempts_exhausted() {
+    test_init();
+
+    let context = disconnect_inbound_call_with_reconnect_policy();
+    let mut cm = context.cm();
+
+    std::thread::sleep(Duration::from_millis(300));
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(context.error_count(), 0);
+    assert_eq!(context.ended_count(), 1);
+    assert_eq!(
+        context.event_count(ApplicationEvent::EndedConnectionFailure),
+        1
+    );
+    assert_eq!(context.event_count(ApplicationEvent::EndedNetworkChanged), 0);
+    assert_eq!(cm.active_call().is_ok(), false);
+}
+
+#[test]
+fn inbound_call_reconnected_within_reconnect_policy() {
+    test_init();
+
+    let context = disconnect_inbound_call_with_reconnect_policy();
+    let mut cm = context.cm();
+    let active_call = context.active_call();
+
+    info!("test: injecting ice connected");
+    context
+        .active_connection()
+        .inject_ice_connected()
+        .expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    std::thread::sleep(Duration::from_millis(300));
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(
+        active_call.state().expect(error_line!()),
+        CallState::ConnectedAndAccepted
+    );
+    assert_eq!(context.event_count(ApplicationEvent::Reconnected), 1);
+    assert_eq!(context.error_count(), 0);
+    assert_eq!(context.ended_count(), 0);
+}
+
+// Accept the call and then connect the data channel, returning once the
+// call is ringing.
+fn accept_inbound_call_before_ringing(context: &TestContext) {
+    let mut cm = context.cm();
+    let active_call = context.active_call();
+    let mut active_connection = context.active_connection();
+
+    info!("test: accepting call before ringing");
+    cm.accept_call(active_call.call_id()).expect(error_line!());
+
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(
+        active_call.state().expect(error_line!()),
+        CallState::ConnectingBeforeAccepted
+    );
+    assert_eq!(context.event_count(ApplicationEvent::LocalAccepted), 0);
+
+    active_connection
+        .inject_ice_connected()
+        .expect(error_line!());
+    let data_channel = unsafe { DataChannel::new(ptr::null()) };
+    active_connection
+        .inject_received_signaling_data_channel(data_channel)
+        .expect(error_line!());
+    active_connection
+        .handle_received_incoming_media(MediaStream::new(ptr::null()))
+        .expect(error_line!());
+
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(context.event_count(ApplicationEvent::LocalRinging), 1);
+}
+
+// Start an inbound call for an app that calls notify_ringing().
+fn start_inbound_call_acknowledging_ringing() -> TestContext {
+    let context = TestContext::new();
+    context.acknowledge_ringing(true);
+    start_inbound_call_with_context(context)
+}
+
+#[test]
+fn inbound_call_accepted_before_ringing() {
+    test_init();
+
+    let context = start_inbound_call_acknowledging_ringing();
+    let mut cm = context.cm();
+    let active_call = context.active_call();
+
+    accept_inbound_call_before_ringing(&context);
+
+    // The queued accept waits for the app to acknowledge ringing.
+    assert_eq!(
+        active_call.state().expect(error_line!()),
+        CallState::ConnectedWithDataChannelBeforeAccepted
+    );
+    assert_eq!(context.event_count(ApplicationEvent::LocalAccepted), 0);
+
+    cm.notify_ringing(active_call.call_id())
+        .expect(error_line!());
+
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(
+        active_call.state().expect(error_line!()),
+        CallState::ConnectedAndAccepted
+    );
+    assert_eq!(context.event_count(ApplicationEvent::LocalAccepted), 1);
+    assert_eq!(context.error_count(), 0);
+    assert_eq!(context.ended_count(), 0);
+}
+
+#[test]
+fn inbound_call_accepted_before_ringing_already_acknowledged() {
+    test_init();
+
+    let context = start_inbound_call_acknowledging_ringing();
+    let mut cm = context.cm();
+    let active_call = context.active_call();
+
+    // The app can start its ringtone before the call is connected.
+    cm.notify_ringing(active_call.call_id())
+        .expect(error_line!());
+
+    accept_inbound_call_before_ringing(&context);
+
+    assert_eq!(
+        active_call.state().expect(error_line!()),
+        CallState::ConnectedAndAccepted
+    );
+    assert_eq!(context.event_count(ApplicationEvent::LocalAccepted), 1);
+    assert_eq!(
+        true,
+        context
+            .active_connection()
+            .app_connection()
+            .unwrap()
+            .outgoing_audio_enabled(),
+    );
+    assert_eq!(context.error_count(), 0);
+    assert_eq!(context.ended_count(), 0);
+}
+
+// Platforms that never call notify_ringing() get an early accept
+// applied as soon as the call is connected.
+#[test]
+fn inbound_call_accepted_before_ringing_without_acknowledgement() {
+    test_init();
+
+    let context = start_inbound_call();
+    let active_call = context.active_call();
+
+    accept_inbound_call_before_ringing(&context);
+
+    assert_eq!(
+        active_call.state().expect(error_line!()),
+        CallState::ConnectedAndAccepted
+    );
+    assert_eq!(context.event_count(ApplicationEvent::LocalAccepted), 1);
+    assert_eq!(context.error_count(), 0);
+    assert_eq!(context.ended_count(), 0);
+}
+
+// Bring an inbound call up to ringing with a short ring timeout.
+fn ring_inbound_call_with_timeout(ring_timeout: Duration) -> TestContext {
+    let context = TestContext::new();
+    context
+        .cm()
+        .set_ring_timeout(ring_timeout)
+        .expect(error_line!());
+    let context = start_inbound_call_with_context(context);
+    let mut cm = context.cm();
+    let mut active_connection = context.active_connection();
+
+    active_connection
+        .inject_ice_connected()
+        .expect(error_line!());
+    let data_channel = unsafe { DataChannel::new(ptr::null()) };
+    active_connection
+        .inject_received_signaling_data_channel(data_channel)
+        .expect(error_line!());
+
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(
+        context.active_call().state().expect(error_line!()),
+        CallState::ConnectedWithDataChannelBeforeAccepted
+    );
+    assert_eq!(context.event_count(ApplicationEvent::LocalRinging), 1);
+
+    context
+}
+
+#[test]
+fn inbound_call_ring_timeout() {
+    test_init();
+
+    let context = ring_inbound_call_with_timeout(Duration::from_millis(50));
+    let mut cm = context.cm();
+
+    std::thread::sleep(Duration::from_millis(200));
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(context.error_count(), 0);
+    assert_eq!(context.event_count(ApplicationEvent::EndedTimeout), 1);
+    assert_eq!(context.normal_hangups_sent(), 1);
+    assert_eq!(cm.active_call().is_ok(), false);
+}
+
+#[test]
+fn inbound_call_accepted_before_ring_timeout() {
+    test_init();
+
+    let context = ring_inbound_call_with_timeout(Duration::from_millis(50));
+    let mut cm = context.cm();
+    let active_call = context.active_call();
+
+    cm.accept_call(active_call.call_id()).expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    std::thread::sleep(Duration::from_millis(200));
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(context.error_count(), 0);
+    assert_eq!(context.event_count(ApplicationEvent::EndedTimeout), 0);
+    assert_eq!(
+        active_call.state().expect(error_line!()),
+        CallState::ConnectedAndAccepted
+    );
+}
+
 #[test]
 fn inbound_call_hangup_accepted() {
     test_init();
@@ -221,6 +556,58 @@ fn inbound_call_hangup_accepted() {
     );
 }
 
+#[test]
+fn inbound_call_hangup_away() {
+    test_init();
+
+    let context = connect_inbound_call();
+    let mut cm = context.cm();
+    let active_call = context.active_call();
+
+    cm.received_hangup(
+        active_call.call_id(),
+        signaling::ReceivedHangup {
+            sender_device_id: 1 as DeviceId,
+            hangup:           signaling::Hangup::Away,
+        },
+    )
+    .expect(error_line!());
+
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(context.error_count(), 0);
+    assert_eq!(context.event_count(ApplicationEvent::EndedRemoteAway), 1);
+    assert_eq!(context.event_count(ApplicationEvent::EndedRemoteHangup), 0);
+}
+
+#[test]
+fn inbound_call_hangup_from_unknown_type_is_normal() {
+    test_init();
+
+    let context = connect_inbound_call();
+    let mut cm = context.cm();
+    let active_call = context.active_call();
+
+    // What a peer from before Away existed would make of it, or what we
+    // make of a type from a newer peer.
+    let hangup_type =
+        signaling::HangupType::from_i32(99).unwrap_or(signaling::HangupType::Normal);
+    cm.received_hangup(
+        active_call.call_id(),
+        signaling::ReceivedHangup {
+            sender_device_id: 1 as DeviceId,
+            hangup:           signaling::Hangup::from_type_and_device_id(hangup_type, 0),
+        },
+    )
+    .expect(error_line!());
+
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(context.error_count(), 0);
+    assert_eq!(context.event_count(ApplicationEvent::EndedRemoteHangup), 1);
+    assert_eq!(context.event_count(ApplicationEvent::EndedRemoteAway), 0);
+}
+
 #[test]
 fn inbound_call_hangup_declined() {
     test_init();
@@ -362,8 +749,453 @@ fn receive_offer_while_active() {
     assert_eq!(context.call_concluded_count(), 1);
 }
 
-#[test]
-fn receive_expired_offer() {
+// Hold the connected inbound call and take a second inbound call up to
+// the ConnectedAndAccepted state while the first one stays on hold.
+//
+// Returns the held call and the second call, which is now active.
+fn accept_second_call_while_on_hold(
+    context: &TestContext,
+) -> (Call<SimPlatform>, Call<SimPlatform>) {
+    let mut cm = context.cm();
+    let held_call = context.active_call();
+
+    cm.hold_call(held_call.call_id()).expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(held_call.state().expect(error_line!()), CallState::OnHold);
+
+    let remote_peer = format!("REMOTE_PEER-{}", PRNG.gen::<u16>()).to_owned();
+    let call_id = CallId::new(PRNG.gen::<u64>());
+    cm.received_offer(
+        remote_peer,
+        call_id,
+        random_received_offer(Duration::from_secs(0)),
+    )
+    .expect(error_line!());
+
+    cm.synchronize().expect(error_line!());
+
+    // The held call is set aside instead of turning the caller away.
+    assert_eq!(context.busys_sent(), 0);
+    assert_eq!(context.start_incoming_count(), 2);
+    let second_call = context.active_call();
+    assert_eq!(second_call.call_id(), call_id);
+    assert_eq!(
+        cm.held_call().expect(error_line!()).call_id(),
+        held_call.call_id()
+    );
+
+    cm.proceed(
+        call_id,
+        format!("CONTEXT-{}", PRNG.gen::<u16>()).to_owned(),
+        BandwidthMode::Normal,
+    )
+    .expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    cm.received_ice(call_id, random_received_ice_candidate())
+        .expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    let mut connection = second_call
+        .get_connection(1 as DeviceId)
+        .expect(error_line!());
+    connection.inject_ice_connected().expect(error_line!());
+    let data_channel = unsafe { DataChannel::new(ptr::null()) };
+    connection
+        .inject_received_signaling_data_channel(data_channel)
+        .expect(error_line!());
+    connection
+        .handle_received_incoming_media(MediaStream::new(ptr::null()))
+        .expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    cm.accept_call(call_id).expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(
+        second_call.state().expect(error_line!()),
+        CallState::ConnectedAndAccepted
+    );
+    assert_eq!(held_call.state().expect(error_line!()), CallState::OnHold);
+    assert_eq!(context.error_count(), 0);
+    assert_eq!(context.ended_count(), 0);
+
+    (held_call, second_call)
+}
+
+fn media_enabled(call: &Call<SimPlatform>) -> (bool, bool) {
+    let connection = call.active_connection().expect(error_line!());
+    let pc = connection.app_connection().expect(error_line!());
+    (pc.outgoing_audio_enabled(), pc.incoming_rtp_enabled())
+}
+
+#[test]
+fn receive_offer_while_on_hold_and_swap() {
+    test_init();
+
+    let context = connect_inbound_call();
+    let mut cm = context.cm();
+    let (first_call, second_call) = accept_second_call_while_on_hold(&context);
+
+    // The held call neither sends nor receives media.
+    assert_eq!(media_enabled(&first_call), (false, false));
+    assert_eq!(media_enabled(&second_call), (true, true));
+
+    info!("test: swapping calls");
+    cm.resume_call(first_call.call_id()).expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(
+        first_call.state().expect(error_line!()),
+        CallState::ConnectedAndAccepted
+    );
+    assert_eq!(second_call.state().expect(error_line!()), CallState::OnHold);
+    assert_eq!(
+        cm.active_call().expect(error_line!()).call_id(),
+        first_call.call_id()
+    );
+    assert_eq!(
+        cm.held_call().expect(error_line!()).call_id(),
+        second_call.call_id()
+    );
+    assert_eq!(media_enabled(&first_call), (true, true));
+    assert_eq!(media_enabled(&second_call), (false, false));
+    assert_eq!(context.event_count(ApplicationEvent::LocalHold), 2);
+    assert_eq!(context.event_count(ApplicationEvent::LocalResume), 1);
+
+    // With one call active and another held, a third caller gets a busy.
+    let remote_peer = format!("REMOTE_PEER-{}", PRNG.gen::<u16>()).to_owned();
+    cm.received_offer(
+        remote_peer,
+        CallId::new(PRNG.gen::<u64>()),
+        random_received_offer(Duration::from_secs(0)),
+    )
+    .expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(context.busys_sent(), 1);
+
+    // A hangup for the held call ends it and leaves the active call alone.
+    cm.received_hangup(
+        second_call.call_id(),
+        signaling::ReceivedHangup {
+            sender_device_id: 1 as DeviceId,
+            hangup:           signaling::Hangup::Normal,
+        },
+    )
+    .expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(
+        second_call.state().expect(error_line!()),
+        CallState::Terminated
+    );
+    assert!(cm.held_call().is_err());
+    assert_eq!(
+        first_call.state().expect(error_line!()),
+        CallState::ConnectedAndAccepted
+    );
+    assert_eq!(context.event_count(ApplicationEvent::EndedRemoteHangup), 1);
+    assert_eq!(context.ended_count(), 1);
+    assert_eq!(context.error_count(), 0);
+}
+
+#[test]
+fn resume_held_call_after_second_call_ends() {
+    test_init();
+
+    let context = connect_inbound_call();
+    let mut cm = context.cm();
+    let (first_call, second_call) = accept_second_call_while_on_hold(&context);
+
+    cm.hangup().expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(
+        second_call.state().expect(error_line!()),
+        CallState::Terminated
+    );
+    assert_eq!(first_call.state().expect(error_line!()), CallState::OnHold);
+    assert!(cm.active_call().is_err());
+
+    cm.resume_call(first_call.call_id()).expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(
+        first_call.state().expect(error_line!()),
+        CallState::ConnectedAndAccepted
+    );
+    assert_eq!(
+        cm.active_call().expect(error_line!()).call_id(),
+        first_call.call_id()
+    );
+    assert!(cm.held_call().is_err());
+    assert_eq!(media_enabled(&first_call), (true, true));
+    assert_eq!(context.event_count(ApplicationEvent::EndedLocalHangup), 1);
+    assert_eq!(context.ended_count(), 1);
+    assert_eq!(context.error_count(), 0);
+}
+
+#[test]
+fn resume_while_second_call_rings_ignored() {
+    test_init();
+
+    let context = connect_inbound_call();
+    let mut cm = context.cm();
+    let first_call = context.active_call();
+
+    cm.hold_call(first_call.call_id()).expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    let remote_peer = format!("REMOTE_PEER-{}", PRNG.gen::<u16>()).to_owned();
+    let call_id = CallId::new(PRNG.gen::<u64>());
+    cm.received_offer(
+        remote_peer,
+        call_id,
+        random_received_offer(Duration::from_secs(0)),
+    )
+    .expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    // The second call hasn't been accepted, so it can't be held and the
+    // first call stays on hold.
+    cm.resume_call(first_call.call_id()).expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(first_call.state().expect(error_line!()), CallState::OnHold);
+    assert_eq!(cm.active_call().expect(error_line!()).call_id(), call_id);
+    assert_eq!(
+        cm.held_call().expect(error_line!()).call_id(),
+        first_call.call_id()
+    );
+    assert_eq!(context.ended_count(), 0);
+}
+
+#[test]
+fn excluded_interface_candidates_not_sent() {
+    test_init();
+
+    let context = start_inbound_call();
+    let mut cm = context.cm();
+    let mut active_connection = context.active_connection();
+
+    active_connection
+        .set_ice_interface_policy(IceInterfacePolicy {
+            exclude_loopback:    true,
+            excluded_interfaces: vec!["utun*".to_string(), "tun0".to_string()],
+        })
+        .expect(error_line!());
+
+    let ice_candidates_sent = context.ice_candidates_sent();
+    for (name, is_loopback) in &[
+        ("utun2", false),
+        ("tun0", false),
+        ("lo0", true),
+        ("tun1", false),
+        ("en0", false),
+    ] {
+        let network = IceCandidateNetwork {
+            name:        name.to_string(),
+            is_loopback: *is_loopback,
+        };
+        active_connection
+            .handle_ice_candidate_gathered(random_ice_candidate(), "", &network)
+            .expect(error_line!());
+    }
+
+    cm.synchronize().expect(error_line!());
+
+    // Only tun1 and en0 make it out.
+    assert_eq!(context.ice_candidates_sent(), ice_candidates_sent + 2);
+    assert_eq!(context.error_count(), 0);
+}
+
+#[test]
+fn answer_without_trickle_ice() {
+    test_init();
+
+    let context = TestContext::new();
+    context.disable_trickle_ice(true);
+    let mut cm = context.cm();
+
+    let remote_peer = format!("REMOTE_PEER-{}", PRNG.gen::<u16>()).to_owned();
+    let call_id = CallId::new(PRNG.gen::<u64>());
+    cm.received_offer(
+        remote_peer,
+        call_id,
+        random_received_offer(Duration::from_secs(0)),
+    )
+    .expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    let active_call = context.active_call();
+    cm.proceed(
+        active_call.call_id(),
+        format!("CONTEXT-{}", PRNG.gen::<u16>()).to_owned(),
+        BandwidthMode::Normal,
+    )
+    .expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    // The answer waits for ICE gathering.
+    assert_eq!(context.answers_sent(), 0);
+
+    let mut active_connection = active_call
+        .get_connection(1 as DeviceId)
+        .expect(error_line!());
+    let network = IceCandidateNetwork {
+        name:        "en0".to_string(),
+        is_loopback: false,
+    };
+    for _ in 0..3 {
+        active_connection
+            .handle_ice_candidate_gathered(random_ice_candidate(), "", &network)
+            .expect(error_line!());
+    }
+    cm.synchronize().expect(error_line!());
+    assert_eq!(context.answers_sent(), 0);
+
+    active_connection
+        .inject_ice_gathering_complete()
+        .expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    // The candidates went in the answer, not in separate Ice messages.
+    assert_eq!(context.answers_sent(), 1);
+    assert_eq!(context.ice_candidates_sent(), 0);
+    assert_eq!(context.error_count(), 0);
+}
+
+#[test]
+fn frame_encryption_reported_per_call() {
+    test_init();
+
+    for enabled in &[false, true] {
+        let context = TestContext::new();
+        context.enable_frame_encryption(*enabled);
+        let mut cm = context.cm();
+
+        let remote_peer = format!("REMOTE_PEER-{}", PRNG.gen::<u16>()).to_owned();
+        let call_id = CallId::new(PRNG.gen::<u64>());
+        cm.received_offer(
+            remote_peer,
+            call_id,
+            random_received_offer(Duration::from_secs(0)),
+        )
+        .expect(error_line!());
+        cm.synchronize().expect(error_line!());
+
+        // There's no connection to ask until the call proceeds.
+        assert!(cm.is_frame_encryption_active(call_id).is_err());
+
+        cm.proceed(
+            call_id,
+            format!("CONTEXT-{}", PRNG.gen::<u16>()).to_owned(),
+            BandwidthMode::Normal,
+        )
+        .expect(error_line!());
+        cm.synchronize().expect(error_line!());
+
+        assert_eq!(
+            cm.is_frame_encryption_active(call_id).expect(error_line!()),
+            *enabled
+        );
+        assert!(cm
+            .is_frame_encryption_active(CallId::new(call_id.as_u64().wrapping_add(1)))
+            .is_err());
+        assert_eq!(context.error_count(), 0);
+    }
+}
+
+#[test]
+fn send_busy_for_ringing_call() {
+    test_init();
+
+    let context = TestContext::new();
+    let mut cm = context.cm();
+
+    let remote_peer = format!("REMOTE_PEER-{}", PRNG.gen::<u16>()).to_owned();
+    let call_id = CallId::new(PRNG.gen::<u64>());
+    cm.received_offer(
+        remote_peer.clone(),
+        call_id,
+        random_received_offer(Duration::from_secs(0)),
+    )
+    .expect(error_line!());
+
+    cm.synchronize().expect(error_line!());
+    assert_eq!(cm.active_call().is_ok(), true);
+
+    // The user turns out to be in a call on another device.
+    cm.send_busy(remote_peer, call_id).expect(error_line!());
+
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(context.error_count(), 0);
+    assert_eq!(context.busys_sent(), 1);
+    assert_eq!(cm.active_call().is_ok(), false);
+    assert_eq!(context.event_count(ApplicationEvent::EndedLocalHangup), 1);
+}
+
+#[test]
+fn send_busy_for_unknown_call() {
+    test_init();
+
+    let context = TestContext::new();
+    let mut cm = context.cm();
+
+    let remote_peer = format!("REMOTE_PEER-{}", PRNG.gen::<u16>()).to_owned();
+    let call_id = CallId::new(PRNG.gen::<u64>());
+    cm.send_busy(remote_peer, call_id).expect(error_line!());
+
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(context.error_count(), 0);
+    assert_eq!(context.busys_sent(), 1);
+    assert_eq!(context.call_concluded_count(), 1);
+}
+
+#[test]
+fn receive_transfer_request_and_accept() {
+    test_init();
+
+    let context = connect_inbound_call();
+    let mut cm = context.cm();
+    let active_call = context.active_call();
+    let call_id = active_call.call_id();
+    let remote_peer = {
+        let remote_peer = active_call.remote_peer().expect(error_line!());
+        remote_peer.to_owned()
+    };
+
+    let target_peer = format!("TARGET_PEER-{}", PRNG.gen::<u16>()).to_owned();
+    cm.received_transfer_request(
+        call_id,
+        signaling::ReceivedTransferRequest {
+            sender_device_id: 1 as DeviceId,
+        },
+        target_peer.clone(),
+    )
+    .expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    // Nothing ends until the application accepts.
+    assert_eq!(context.transfer_requests(), vec![(remote_peer, target_peer)]);
+    assert_eq!(cm.active_call().is_ok(), true);
+    assert_eq!(context.ended_count(), 0);
+
+    cm.accept_transfer(call_id).expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(context.error_count(), 0);
+    assert_eq!(context.transfer_accepteds_sent(), 1);
+    assert_eq!(context.event_count(ApplicationEvent::EndedTransferred), 1);
+    assert_eq!(cm.active_call().is_ok(), false);
+}
+
+#[test]
+fn receive_expired_offer() {
     test_init();
 
     let context = TestContext::new();
@@ -387,6 +1219,41 @@ fn receive_expired_offer() {
     );
 }
 
+#[test]
+fn receive_offer_with_unsupported_version() {
+    test_init();
+
+    let context = TestContext::new();
+    let mut cm = context.cm();
+
+    // Only a field (15) from a version this build doesn't know about.
+    let offer = signaling::Offer::new(CallMediaType::Audio, vec![0x7a, 0x02, 0x08, 0x01])
+        .expect(error_line!());
+    assert!(offer.supported_versions().is_empty());
+
+    let remote_peer = format!("REMOTE_PEER-{}", PRNG.gen::<u16>()).to_owned();
+    let call_id = CallId::new(PRNG.gen::<u64>());
+    cm.received_offer(
+        remote_peer,
+        call_id,
+        signaling::ReceivedOffer {
+            offer,
+            ..random_received_offer(Duration::from_secs(0))
+        },
+    )
+    .expect(error_line!());
+
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(context.error_count(), 0);
+    assert_eq!(
+        context.event_count(ApplicationEvent::ReceivedOfferWithUnsupportedVersion),
+        1
+    );
+    assert_eq!(context.call_concluded_count(), 1);
+    assert!(cm.active_call().is_err());
+}
+
 #[test]
 fn receive_offer_before_age_limit() {
     test_init();
@@ -464,3 +1331,113 @@ fn receive_expired_offer_after_age_limit() {
         1
     );
 }
+
+#[test]
+fn receive_offer_with_bogus_age() {
+    test_init();
+
+    let context = TestContext::new();
+    let mut cm = context.cm();
+
+    // Such as -1 from the app reinterpreted as unsigned.
+    let remote_peer = format!("REMOTE_PEER-{}", PRNG.gen::<u16>()).to_owned();
+    let call_id = CallId::new(PRNG.gen::<u64>());
+    assert!(cm
+        .received_offer(
+            remote_peer,
+            call_id,
+            random_received_offer(Duration::from_secs(u64::MAX)),
+        )
+        .is_err());
+
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(context.error_count(), 0);
+    assert_eq!(
+        context.event_count(ApplicationEvent::ReceivedOfferExpired),
+        0
+    );
+    assert!(cm.active_call().is_err());
+
+    // Just past the limit is still bogus, just under it merely expired.
+    let remote_peer = format!("REMOTE_PEER-{}", PRNG.gen::<u16>()).to_owned();
+    assert!(cm
+        .received_offer(
+            remote_peer.clone(),
+            call_id,
+            random_received_offer(Duration::from_secs(MAX_VALID_MESSAGE_AGE_SEC + 1)),
+        )
+        .is_err());
+    cm.received_offer(
+        remote_peer,
+        call_id,
+        random_received_offer(Duration::from_secs(MAX_VALID_MESSAGE_AGE_SEC)),
+    )
+    .expect(error_line!());
+
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(context.error_count(), 0);
+    assert_eq!(
+        context.event_count(ApplicationEvent::ReceivedOfferExpired),
+        1
+    );
+}
+
+#[test]
+fn pending_ice_candidates_capped_per_device() {
+    test_init();
+
+    let context = TestContext::with_config(CallManagerConfig {
+        max_pending_ice_candidates: 5,
+    });
+    let mut cm = context.cm();
+
+    let remote_peer = format!("REMOTE_PEER-{}", PRNG.gen::<u16>()).to_owned();
+    let call_id = CallId::new(PRNG.gen::<u64>());
+    cm.received_offer(
+        remote_peer,
+        call_id,
+        random_received_offer(Duration::from_secs(0)),
+    )
+    .expect(error_line!());
+
+    cm.synchronize().expect(error_line!());
+
+    let active_call = context.active_call();
+    assert_eq!(
+        active_call.state().expect(error_line!()),
+        CallState::WaitingToProceed
+    );
+
+    // Flood from the offering device, one candidate at a time.
+    for _ in 0..8 {
+        cm.received_ice(call_id, random_received_ice_candidate())
+            .expect(error_line!());
+    }
+    cm.synchronize().expect(error_line!());
+    assert_eq!(cm.dropped_pending_ice_candidates().expect(error_line!()), 3);
+
+    // Another device has its own allowance, even when candidates arrive in bulk.
+    let received = signaling::ReceivedIce {
+        ice:              signaling::Ice {
+            candidates_added: (0..7).map(|_| random_ice_candidate()).collect(),
+        },
+        sender_device_id: 2 as DeviceId,
+    };
+    cm.received_ice(call_id, received).expect(error_line!());
+    cm.synchronize().expect(error_line!());
+    assert_eq!(cm.dropped_pending_ice_candidates().expect(error_line!()), 5);
+
+    // The call can still proceed with the trimmed buffer.
+    cm.proceed(
+        call_id,
+        format!("CONTEXT-{}", PRNG.gen::<u16>()).to_owned(),
+        BandwidthMode::Normal,
+    )
+    .expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(cm.dropped_pending_ice_candidates().expect(error_line!()), 5);
+    assert_eq!(context.error_count(), 0);
+}
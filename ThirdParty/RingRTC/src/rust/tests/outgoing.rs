@@ -10,10 +10,14 @@ extern crate ringrtc;
 #[macro_use]
 extern crate log;
 
+use std::collections::HashMap;
 use std::ptr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 use std::thread;
 use std::time::Duration;
 
+use ringrtc::common::units::DataRate;
 use ringrtc::common::{
     ApplicationEvent,
     CallId,
@@ -21,11 +25,38 @@ use ringrtc::common::{
     CallState,
     ConnectionState,
     DeviceId,
+    HttpMethod,
+    HttpResponse,
 };
-use ringrtc::core::bandwidth_mode::BandwidthMode;
+use ringrtc::core::bandwidth_mode::{AudioEncoderPreset, BandwidthMode, EffectiveBandwidthMode};
+use ringrtc::core::call_manager::{CallManagerHandle, MAX_SUSPENDED_SIGNALING_MESSAGES};
+use ringrtc::core::connection::{
+    security_fingerprint,
+    MAX_AUDIO_FEC_LEVEL,
+    MAX_AUDIO_JITTER_BUFFER_TARGET_MS,
+    MAX_DATA_MESSAGE_SIZE,
+    MIN_AUDIO_JITTER_BUFFER_TARGET_MS,
+    SECURITY_FINGERPRINT_LEN,
+    STATS_PERIOD_SEC,
+    TELEPHONE_EVENT_PAYLOAD_TYPE,
+};
+use ringrtc::core::group_call;
+use ringrtc::core::http_client::HttpClient;
 use ringrtc::core::signaling;
+use ringrtc::core::util::redact_string;
 use ringrtc::sim::error::SimError;
-use ringrtc::webrtc::media::MediaStream;
+use ringrtc::sim::sim_platform::SimPlatform;
+use ringrtc::webrtc::media::{MediaStream, VideoEncoderCaps, VideoTrack};
+use ringrtc::webrtc::peer_connection_factory::PeerConnectionFactory;
+use ringrtc::webrtc::peer_connection_observer::{NetworkRoute, PeerConnectionObserverTrait};
+use ringrtc::webrtc::rtp;
+use ringrtc::webrtc::stats_observer::{
+    AudioPlayoutStatistics,
+    AudioReceiverStatistics,
+    AudioSenderStatistics,
+    CandidateType,
+    VideoSenderStatistics,
+};
 
 #[macro_use]
 mod common;
@@ -49,6 +80,374 @@ fn create_cm() {
     let _ = TestContext::new();
 }
 
+// Check that closing the call manager ends live group calls first:
+// -- creates a call manager and two connected group call clients
+// -- closes the call manager
+// -- checks that each client reported NotConnected and ended
+#[test]
+fn close_with_group_call_clients() {
+    test_init();
+
+    let context = TestContext::new();
+    let mut cm = context.cm();
+
+    let peer_connection_factory = PeerConnectionFactory::new(false).expect(error_line!());
+    let mut client_ids = Vec::new();
+    for group_id in &[b"group 1", b"group 2"] {
+        let outgoing_audio_track = peer_connection_factory
+            .create_outgoing_audio_track()
+            .expect(error_line!());
+        let outgoing_video_source = peer_connection_factory
+            .create_outgoing_video_source()
+            .expect(error_line!());
+        let outgoing_video_track = peer_connection_factory
+            .create_outgoing_video_track(&outgoing_video_source)
+            .expect(error_line!());
+        let client_id = cm
+            .create_group_call_client(
+                group_id.to_vec(),
+                "https://sfu.example".to_string(),
+                Some(peer_connection_factory.clone()),
+                outgoing_audio_track,
+                outgoing_video_track,
+                None,
+            )
+            .expect(error_line!());
+        cm.connect(client_id);
+        client_ids.push(client_id);
+    }
+
+    cm.close().expect(error_line!());
+
+    let states = context.group_connection_states();
+    let ends = context.group_call_ends();
+    for client_id in client_ids {
+        assert_eq!(
+            states.iter().filter(|(id, _)| *id == client_id).last(),
+            Some(&(client_id, group_call::ConnectionState::NotConnected))
+        );
+        let client_ends: Vec<_> = ends.iter().filter(|(id, _)| *id == client_id).collect();
+        assert_eq!(
+            client_ends,
+            vec![&(
+                client_id,
+                group_call::EndReason::DeviceExplicitlyDisconnected
+            )]
+        );
+    }
+}
+
+// Check that the outgoing simulcast layers of a group call are reported:
+// -- creates a call manager
+// -- creates and connects a group call client
+// -- checks the default three layers are reported for the client
+// -- checks an unknown client is an error
+#[test]
+fn group_call_outgoing_simulcast_config() {
+    test_init();
+
+    let context = TestContext::new();
+    let mut cm = context.cm();
+
+    let peer_connection_factory = PeerConnectionFactory::new(false).expect(error_line!());
+    let outgoing_audio_track = peer_connection_factory
+        .create_outgoing_audio_track()
+        .expect(error_line!());
+    let outgoing_video_source = peer_connection_factory
+        .create_outgoing_video_source()
+        .expect(error_line!());
+    let outgoing_video_track = peer_connection_factory
+        .create_outgoing_video_track(&outgoing_video_source)
+        .expect(error_line!());
+    let client_id = cm
+        .create_group_call_client(
+            b"group".to_vec(),
+            "https://sfu.example".to_string(),
+            Some(peer_connection_factory),
+            outgoing_audio_track,
+            outgoing_video_track,
+            None,
+        )
+        .expect(error_line!());
+    cm.connect(client_id);
+
+    let layers = cm.outgoing_simulcast_config(client_id).expect(error_line!());
+    assert_eq!(
+        vec![(160, 120, 4.0), (320, 240, 2.0), (640, 480, 1.0)],
+        layers
+            .iter()
+            .map(|layer| (layer.width, layer.height, layer.scale_resolution_down_by))
+            .collect::<Vec<_>>()
+    );
+    assert!(layers.iter().all(|layer| layer.active));
+
+    let err = cm
+        .outgoing_simulcast_config(client_id + 1)
+        .expect_err(error_line!());
+    assert_eq!(
+        format!("{}", err),
+        format!("Group call client not found, client_id: {}", client_id + 1)
+    );
+
+    cm.close().expect(error_line!());
+}
+
+// Check that a group call client isn't created without a video track:
+// -- creates a call manager
+// -- creates a group call client with a null video track
+// -- checks the error names the track and no client was started
+#[test]
+fn create_group_call_client_with_null_video_track() {
+    test_init();
+
+    let context = TestContext::new();
+    let mut cm = context.cm();
+
+    let peer_connection_factory = PeerConnectionFactory::new(false).expect(error_line!());
+    let outgoing_audio_track = peer_connection_factory
+        .create_outgoing_audio_track()
+        .expect(error_line!());
+    let outgoing_video_track = VideoTrack::owned(ptr::null());
+
+    let err = cm
+        .create_group_call_client(
+            b"group".to_vec(),
+            "https://sfu.example".to_string(),
+            Some(peer_connection_factory),
+            outgoing_audio_track,
+            outgoing_video_track,
+            None,
+        )
+        .expect_err(error_line!());
+    assert_eq!(
+        format!("{}", err),
+        "Null pointer in: create_group_call_client, var: outgoing_video_track"
+    );
+
+    cm.close().expect(error_line!());
+    assert!(context.group_connection_states().is_empty());
+}
+
+// Check that a call manager handle is rejected when it has been
+// tampered with:
+// -- creates a call manager handle
+// -- corrupts the handle's tag and checks it is rejected
+// -- restores the tag and releases the call manager
+#[test]
+fn call_manager_handle_tampered() {
+    test_init();
+
+    let context = TestContext::new();
+    let handle = CallManagerHandle::into_raw(context.cm());
+
+    unsafe {
+        assert!(CallManagerHandle::as_call_manager(handle).is_ok());
+
+        // The tag is the first field of the handle.
+        *(handle as *mut u64) ^= 1;
+        assert!(CallManagerHandle::as_call_manager(handle).is_err());
+        assert!(CallManagerHandle::into_call_manager(handle).is_err());
+
+        *(handle as *mut u64) ^= 1;
+        assert!(CallManagerHandle::into_call_manager(handle).is_ok());
+    }
+
+    let null_handle: *mut CallManagerHandle<SimPlatform> = ptr::null_mut();
+    assert!(unsafe { CallManagerHandle::as_call_manager(null_handle) }.is_err());
+}
+
+// Check that HTTP responses are only applied once:
+// -- makes an HTTP request through the call manager
+// -- delivers its response, which calls the callback
+// -- delivers the same response again, which is dropped
+// -- delivers a response for a request never made, which is dropped
+#[test]
+fn stale_http_response_dropped() {
+    test_init();
+
+    let context = TestContext::new();
+    let mut cm = context.cm();
+
+    let responses = Arc::new(AtomicUsize::new(0));
+    let responses_clone = responses.clone();
+    cm.make_request(
+        "https://sfu.example.com".to_owned(),
+        HttpMethod::Get,
+        HashMap::new(),
+        None,
+        Box::new(move |_response| {
+            responses_clone.fetch_add(1, Ordering::AcqRel);
+        }),
+    );
+
+    let request_ids = context.http_request_ids();
+    assert_eq!(request_ids.len(), 1);
+    let request_id = request_ids[0];
+
+    let response = || HttpResponse {
+        status_code: 200,
+        body:        vec![],
+    };
+
+    cm.received_http_response(request_id, Some(response()))
+        .expect(error_line!());
+    cm.synchronize().expect(error_line!());
+    assert_eq!(responses.load(Ordering::Acquire), 1);
+
+    cm.received_http_response(request_id, Some(response()))
+        .expect(error_line!());
+    cm.synchronize().expect(error_line!());
+    assert_eq!(responses.load(Ordering::Acquire), 1);
+
+    cm.received_http_response(request_id + 1, None)
+        .expect(error_line!());
+    cm.synchronize().expect(error_line!());
+    assert_eq!(responses.load(Ordering::Acquire), 1);
+    assert_eq!(context.error_count(), 0);
+}
+
+// Check that the client identifier is sent with HTTP requests:
+// -- rejects identifiers that aren't safe as header values
+// -- peeks a group call, which includes the headers
+// -- makes a request without any headers of its own, which includes them too
+#[test]
+fn client_identifier_sent_with_http_requests() {
+    test_init();
+
+    let context = TestContext::new();
+    let mut cm = context.cm();
+
+    for invalid in &["", " padded", "line\r\nbreak", "caf\u{e9}"] {
+        assert!(cm.set_client_identifier(invalid.to_string()).is_err());
+    }
+    assert!(cm.set_client_identifier("x".repeat(257)).is_err());
+
+    cm.set_client_identifier("Acria-iOS/5.12.0 (iOS 14.4)".to_owned())
+        .expect(error_line!());
+
+    cm.peek_group_call(
+        1,
+        "https://sfu.example.com".to_owned(),
+        b"00000000-0000-0000-0000-000000000000:proof".to_vec(),
+        vec![],
+    );
+    cm.make_request(
+        "https://sfu.example.com".to_owned(),
+        HttpMethod::Get,
+        HashMap::new(),
+        None,
+        Box::new(|_response| {}),
+    );
+
+    let headers = context.http_request_headers();
+    assert_eq!(headers.len(), 2);
+    for headers in headers {
+        assert_eq!(
+            headers.get("User-Agent").map(String::as_str),
+            Some("Acria-iOS/5.12.0 (iOS 14.4)")
+        );
+        assert_eq!(
+            headers.get("X-Client").map(String::as_str),
+            Some("Acria-iOS/5.12.0 (iOS 14.4)")
+        );
+    }
+    assert_eq!(context.error_count(), 0);
+}
+
+// Check that a peek with a timeout reports it when the SFU never responds:
+// -- peeks a group call with a short timeout and gives no response
+// -- checks that the timeout is delivered for that request, and only once
+// -- checks that a response arriving afterwards is dropped
+#[test]
+fn peek_group_call_timed_out() {
+    test_init();
+
+    let context = TestContext::new();
+    let mut cm = context.cm();
+
+    cm.peek_group_call_with_timeout(
+        7,
+        "https://sfu.example.com".to_owned(),
+        b"00000000-0000-0000-0000-000000000000:proof".to_vec(),
+        vec![],
+        Some(Duration::from_millis(100)),
+    );
+    let request_ids = context.http_request_ids();
+    assert_eq!(request_ids.len(), 1);
+
+    thread::sleep(Duration::from_millis(300));
+    cm.synchronize().expect(error_line!());
+    assert_eq!(context.peek_timeouts(), vec![7]);
+
+    cm.received_http_response(request_ids[0], None)
+        .expect(error_line!());
+    cm.synchronize().expect(error_line!());
+    assert_eq!(context.peek_timeouts(), vec![7]);
+    assert!(context.peek_responses().is_empty());
+    assert_eq!(context.error_count(), 0);
+}
+
+// Check that a peek response cancels the peek's timeout:
+// -- peeks a group call with a timeout and responds before it passes
+// -- checks that the response is delivered and the timeout never is
+#[test]
+fn peek_group_call_responded_before_timeout() {
+    test_init();
+
+    let context = TestContext::new();
+    let mut cm = context.cm();
+
+    cm.peek_group_call_with_timeout(
+        7,
+        "https://sfu.example.com".to_owned(),
+        b"00000000-0000-0000-0000-000000000000:proof".to_vec(),
+        vec![],
+        Some(Duration::from_millis(200)),
+    );
+    let request_ids = context.http_request_ids();
+    assert_eq!(request_ids.len(), 1);
+
+    cm.received_http_response(request_ids[0], None)
+        .expect(error_line!());
+    cm.synchronize().expect(error_line!());
+    assert_eq!(context.peek_responses(), vec![7]);
+
+    thread::sleep(Duration::from_millis(400));
+    cm.synchronize().expect(error_line!());
+    assert!(context.peek_timeouts().is_empty());
+    assert_eq!(context.error_count(), 0);
+}
+
+// Check the audio loopback self-check:
+// -- starts and stops the loopback without any call
+// -- checks that the captured audio level was reported
+// -- checks that the loopback can't start once a call is in progress
+#[test]
+fn audio_loopback_without_call() {
+    test_init();
+
+    let context = TestContext::new();
+    let mut cm = context.cm();
+
+    cm.start_audio_loopback().expect(error_line!());
+    assert_eq!(context.audio_loopback_level_count(), 1);
+    cm.stop_audio_loopback().expect(error_line!());
+
+    // Stopping again does nothing.
+    cm.stop_audio_loopback().expect(error_line!());
+
+    cm.call(
+        "REMOTE_PEER".to_owned(),
+        CallMediaType::Audio,
+        1 as DeviceId,
+    )
+    .expect(error_line!());
+    cm.synchronize().expect(error_line!());
+    assert!(cm.start_audio_loopback().is_err());
+    assert_eq!(context.audio_loopback_level_count(), 1);
+    assert_eq!(context.error_count(), 0);
+}
+
 // Create an outbound call, sending offer to an unknown number of remotes.
 //
 // - create call manager
@@ -328,6 +727,82 @@ fn outbound_ice_failed() {
     );
 }
 
+#[test]
+fn outbound_ice_candidate_error() {
+    test_init();
+
+    let context = start_outbound_call();
+    let mut cm = context.cm();
+    let mut active_connection = context.active_connection();
+
+    info!("test: injecting TURN auth failure");
+    let url = "turn:192.0.2.1:3478?transport=udp".to_string();
+    active_connection
+        .handle_ice_candidate_error(url.clone(), 401, "Unauthorized".to_string())
+        .expect(error_line!());
+
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(
+        context.ice_candidate_errors(),
+        vec![(redact_string(&url), 401, "Unauthorized".to_string())]
+    );
+
+    // The error is only reported; it doesn't end the call.
+    assert_eq!(context.error_count(), 0);
+    assert_eq!(context.ended_count(), 0);
+}
+
+#[test]
+fn outbound_ice_network_route_changed() {
+    test_init();
+
+    let context = connect_outbound_call();
+    let mut cm = context.cm();
+    let mut active_connection = context.active_connection();
+
+    info!("test: moving from Wi-Fi to cellular");
+    active_connection
+        .handle_ice_network_route_changed(NetworkRoute::Wifi)
+        .expect(error_line!());
+    active_connection
+        .handle_ice_network_route_changed(NetworkRoute::Cellular)
+        .expect(error_line!());
+
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(
+        context.network_routes(),
+        vec![NetworkRoute::Wifi, NetworkRoute::Cellular]
+    );
+    assert_eq!(context.error_count(), 0);
+    assert_eq!(context.ended_count(), 0);
+}
+
+#[test]
+fn outbound_outgoing_keyframe() {
+    test_init();
+
+    let context = connect_outbound_call();
+    let mut cm = context.cm();
+    let mut active_connection = context.active_connection();
+
+    info!("test: forcing two key frames");
+    active_connection
+        .handle_outgoing_key_frame_encoded()
+        .expect(error_line!());
+    active_connection
+        .handle_outgoing_key_frame_encoded()
+        .expect(error_line!());
+
+    cm.synchronize().expect(error_line!());
+
+    // Each one is reported as it is encoded, without waiting for stats.
+    assert_eq!(context.outgoing_keyframes(), 2);
+    assert_eq!(context.error_count(), 0);
+    assert_eq!(context.ended_count(), 0);
+}
+
 #[test]
 fn outbound_ice_disconnected_before_call_accepted() {
     test_init();
@@ -452,6 +927,45 @@ fn outbound_call_connected_ice_failed() {
     );
 }
 
+#[test]
+fn outbound_call_reconnecting_ice_failed() {
+    test_init();
+
+    let context = connect_outbound_call();
+    let mut cm = context.cm();
+    let active_call = context.active_call();
+    let mut active_connection = context.active_connection();
+
+    info!("test: injecting ice disconnected");
+    active_connection
+        .inject_ice_disconnected()
+        .expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(
+        active_connection.state().expect(error_line!()),
+        ConnectionState::ReconnectingAfterAccepted
+    );
+
+    // Failing to reconnect means the network the call was on went away,
+    // which is told apart from failing to connect in the first place.
+    info!("test: injecting ice connection failed");
+    active_connection.inject_ice_failed().expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(
+        active_call.state().expect(error_line!()),
+        CallState::Terminated
+    );
+    assert_eq!(context.error_count(), 0);
+    assert_eq!(context.ended_count(), 1);
+    assert_eq!(context.event_count(ApplicationEvent::EndedNetworkChanged), 1);
+    assert_eq!(
+        context.event_count(ApplicationEvent::EndedConnectionFailure),
+        0
+    );
+}
+
 #[test]
 fn outbound_call_connected_local_hangup() {
     test_init();
@@ -479,27 +993,50 @@ fn outbound_call_connected_local_hangup() {
 }
 
 #[test]
-fn outbound_ice_disconnected_after_call_connected_and_reconnect() {
+fn outbound_call_connected_local_hangup_as_away() {
     test_init();
 
     let context = connect_outbound_call();
     let mut cm = context.cm();
     let active_call = context.active_call();
-    let mut active_connection = context.active_connection();
 
-    info!("test: injecting ice disconnected");
-    active_connection
-        .inject_ice_disconnected()
-        .expect(error_line!());
+    cm.hangup_as_away().expect(error_line!());
 
     cm.synchronize().expect(error_line!());
 
-    // When ICE disconnects after the call is connected, the system
-    // should move to the ReconnectingAfterAccepted state.
     assert_eq!(
-        active_connection.state().expect(error_line!()),
-        ConnectionState::ReconnectingAfterAccepted
-    );
+        active_call.state().expect(error_line!()),
+        CallState::Terminated
+    );
+    assert_eq!(context.error_count(), 0);
+    assert_eq!(context.ended_count(), 1);
+    assert_eq!(context.event_count(ApplicationEvent::EndedLocalHangup), 1);
+    assert_eq!(context.away_hangups_sent(), 1);
+    assert_eq!(context.normal_hangups_sent(), 0);
+}
+
+#[test]
+fn outbound_ice_disconnected_after_call_connected_and_reconnect() {
+    test_init();
+
+    let context = connect_outbound_call();
+    let mut cm = context.cm();
+    let active_call = context.active_call();
+    let mut active_connection = context.active_connection();
+
+    info!("test: injecting ice disconnected");
+    active_connection
+        .inject_ice_disconnected()
+        .expect(error_line!());
+
+    cm.synchronize().expect(error_line!());
+
+    // When ICE disconnects after the call is connected, the system
+    // should move to the ReconnectingAfterAccepted state.
+    assert_eq!(
+        active_connection.state().expect(error_line!()),
+        ConnectionState::ReconnectingAfterAccepted
+    );
     assert_eq!(
         active_call.state().expect(error_line!()),
         CallState::ReconnectingAfterAccepted
@@ -624,158 +1161,1303 @@ fn inject_send_sender_status_via_data_channel() {
 }
 
 #[test]
-fn update_bandwidth_mode_default() {
+fn inject_send_data_message_via_data_channel() {
     test_init();
 
     let context = connect_outbound_call();
     let mut cm = context.cm();
-    let active_connection = context.active_connection();
+    let mut active_connection = context.active_connection();
 
     active_connection
-        .update_bandwidth_mode(BandwidthMode::Normal)
+        .inject_send_data_message_via_data_channel(vec![1, 2, 3])
         .expect(error_line!());
 
+    // Payloads over the limit are rejected up front.
+    assert!(active_connection
+        .inject_send_data_message_via_data_channel(vec![0; MAX_DATA_MESSAGE_SIZE + 1])
+        .is_err());
+
     cm.synchronize().expect(error_line!());
     assert_eq!(context.error_count(), 0);
+}
 
-    // TODO -- verify that the bitrate was changed
-    // TODO -- verify that the data channel object sent a message
+#[test]
+fn send_rtp_data_for_active_call() {
+    test_init();
+
+    let context = connect_outbound_call();
+    let mut cm = context.cm();
+    let active_call = context.active_call();
+
+    cm.send_rtp_data(active_call.call_id(), vec![1, 2, 3]).expect(error_line!());
+
+    // Payloads over the limit are rejected up front, as are other calls.
+    assert!(cm
+        .send_rtp_data(active_call.call_id(), vec![0; MAX_DATA_MESSAGE_SIZE + 1])
+        .is_err());
+    let other_call_id = CallId::new(active_call.call_id().as_u64().wrapping_add(1));
+    assert!(cm.send_rtp_data(other_call_id, vec![1]).is_err());
+
+    cm.synchronize().expect(error_line!());
+    assert_eq!(context.error_count(), 0);
 }
 
 #[test]
-fn update_bandwidth_mode_low() {
+fn received_data_message_via_data_channel() {
     test_init();
 
     let context = connect_outbound_call();
     let mut cm = context.cm();
-    let active_connection = context.active_connection();
+    let mut active_connection = context.active_connection();
+
+    for i in 0..5 {
+        active_connection
+            .inject_received_data_message_via_data_channel(vec![i; 16])
+            .expect(error_line!());
+    }
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(context.error_count(), 0);
+    assert_eq!(context.data_messages_received(), 5);
+}
+
+#[test]
+fn inject_send_dtmf() {
+    test_init();
+
+    let context = connect_outbound_call();
+    let mut cm = context.cm();
+    let mut active_connection = context.active_connection();
 
     active_connection
-        .update_bandwidth_mode(BandwidthMode::Low)
+        .inject_send_dtmf("12#A".to_string(), 100, 70)
+        .expect(error_line!());
+    active_connection
+        .inject_send_dtmf("*0D".to_string(), 100, 70)
         .expect(error_line!());
 
+    // Anything outside [0-9*#A-D] is rejected up front, as is nothing.
+    for tones in &["12x", "a", "1 2", ""] {
+        assert!(active_connection
+            .inject_send_dtmf(tones.to_string(), 100, 70)
+            .is_err());
+    }
+
     cm.synchronize().expect(error_line!());
+    assert_eq!(
+        active_connection.app_connection().unwrap().inserted_dtmf(),
+        "12#A*0D"
+    );
     assert_eq!(context.error_count(), 0);
-
-    // TODO -- verify that the bitrate was changed
-    // TODO -- verify that the data channel object sent a message
 }
 
 #[test]
-fn inject_local_ice_candidate() {
+fn inject_set_max_bitrate() {
     test_init();
 
     let context = connect_outbound_call();
     let mut cm = context.cm();
     let mut active_connection = context.active_connection();
+    let app_connection = active_connection.app_connection().unwrap();
+    let max_send_bitrate_bps = || app_connection.max_send_bitrate_bps();
+    let normal_bps = BandwidthMode::Normal.max_bitrate().as_bps() as i32;
 
-    let ice_candidate = random_ice_candidate();
-    let force_send = true;
     active_connection
-        .inject_local_ice_candidate(ice_candidate, force_send, "")
+        .inject_set_max_bitrate(256_000)
+        .expect(error_line!());
+    cm.synchronize().expect(error_line!());
+    assert_eq!(max_send_bitrate_bps(), Some(256_000));
+
+    // The cap holds when the mode changes, but a cap above the mode's
+    // bitrate doesn't raise it.
+    active_connection
+        .inject_update_bandwidth_mode(BandwidthMode::VeryLow)
+        .expect(error_line!());
+    cm.synchronize().expect(error_line!());
+    let very_low_bps = BandwidthMode::VeryLow.max_bitrate().as_bps() as i32;
+    assert_eq!(max_send_bitrate_bps(), Some(very_low_bps));
+
+    active_connection
+        .inject_update_bandwidth_mode(BandwidthMode::Normal)
         .expect(error_line!());
+    cm.synchronize().expect(error_line!());
+    assert_eq!(max_send_bitrate_bps(), Some(256_000));
 
+    // 0 removes the cap.
+    active_connection
+        .inject_set_max_bitrate(0)
+        .expect(error_line!());
     cm.synchronize().expect(error_line!());
+    assert_eq!(max_send_bitrate_bps(), Some(normal_bps));
     assert_eq!(context.error_count(), 0);
-    assert_eq!(context.ice_candidates_sent(), 1);
 }
 
 #[test]
-fn receive_remote_ice_candidate() {
+fn received_dtmf_via_telephone_events() {
     test_init();
 
     let context = connect_outbound_call();
     let mut cm = context.cm();
-    let active_call = context.active_call();
+    let mut active_connection = context.active_connection();
 
-    // add a received ICE candidate
-    let call_id = active_call.call_id();
-    cm.received_ice(call_id, random_received_ice_candidate())
-        .expect("receive_ice");
+    let header = |pt, seqnum, timestamp| rtp::Header {
+        pt,
+        seqnum,
+        timestamp,
+        ssrc: 2002,
+    };
+    // Each event is sent in several packets with the same timestamp.
+    // Event 9 is '9' and event 11 is '#'.
+    active_connection.handle_rtp_received(
+        header(TELEPHONE_EVENT_PAYLOAD_TYPE, 1, 960),
+        &[9, 10, 0, 160],
+    );
+    active_connection.handle_rtp_received(
+        header(TELEPHONE_EVENT_PAYLOAD_TYPE, 2, 960),
+        &[9, 10, 1, 64],
+    );
+    active_connection.handle_rtp_received(
+        header(TELEPHONE_EVENT_PAYLOAD_TYPE, 3, 960),
+        &[9, 138, 1, 224],
+    );
+    // Audio and events that aren't DTMF tones are ignored.
+    active_connection.handle_rtp_received(header(102, 4, 1920), &[9, 10, 0, 160]);
+    active_connection.handle_rtp_received(
+        header(TELEPHONE_EVENT_PAYLOAD_TYPE, 5, 2880),
+        &[16, 10, 0, 160],
+    );
+    active_connection.handle_rtp_received(
+        header(TELEPHONE_EVENT_PAYLOAD_TYPE, 6, 3840),
+        &[11, 10, 0, 160],
+    );
     cm.synchronize().expect(error_line!());
 
     assert_eq!(context.error_count(), 0);
-
-    // TODO -- verify the ice candidate was buffered
-    // TODO -- verify the ice candidate was applied to the peer_connection
+    assert_eq!(context.dtmf_received(), vec!['9', '#']);
 }
 
 #[test]
-fn received_remote_hangup_before_connection() {
+fn outbound_call_hold_and_resume() {
     test_init();
 
-    let context = start_outbound_and_proceed();
+    let context = connect_outbound_call();
     let mut cm = context.cm();
     let active_call = context.active_call();
+    let active_connection = context.active_connection();
 
-    // Receiving a Hangup/Normal before connection implies the callee is declining.
-    cm.received_hangup(
-        active_call.call_id(),
-        signaling::ReceivedHangup {
-            sender_device_id: 1 as DeviceId,
-            hangup:           signaling::Hangup::Normal,
-        },
-    )
-    .expect(error_line!());
+    assert!(active_connection
+        .app_connection()
+        .unwrap()
+        .outgoing_audio_enabled());
+
+    info!("test: holding call");
+    cm.hold_call(active_call.call_id()).expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(active_call.state().expect(error_line!()), CallState::OnHold);
+    assert!(!active_connection
+        .app_connection()
+        .unwrap()
+        .outgoing_audio_enabled());
+    assert!(!active_connection
+        .app_connection()
+        .unwrap()
+        .incoming_rtp_enabled());
+    assert_eq!(context.event_count(ApplicationEvent::LocalHold), 1);
 
+    info!("test: resuming call");
+    cm.resume_call(active_call.call_id()).expect(error_line!());
     cm.synchronize().expect(error_line!());
 
+    assert_eq!(
+        active_call.state().expect(error_line!()),
+        CallState::ConnectedAndAccepted
+    );
+    assert!(active_connection
+        .app_connection()
+        .unwrap()
+        .outgoing_audio_enabled());
+    assert!(active_connection
+        .app_connection()
+        .unwrap()
+        .incoming_rtp_enabled());
+    assert_eq!(context.event_count(ApplicationEvent::LocalResume), 1);
     assert_eq!(context.error_count(), 0);
-    assert_eq!(context.event_count(ApplicationEvent::EndedRemoteHangup), 1);
-    assert_eq!(context.normal_hangups_sent(), 0);
-    // Other callees should get Hangup/Declined.
-    assert_eq!(context.declined_hangups_sent(), 1);
+    assert_eq!(context.ended_count(), 0);
 }
 
 #[test]
-fn received_remote_hangup_before_connection_with_message_in_flight() {
+fn outbound_call_mute_outgoing_audio() {
     test_init();
 
-    let context = start_outbound_and_proceed();
+    let context = connect_outbound_call();
     let mut cm = context.cm();
-    let active_call = context.active_call();
-    let parent_connection = active_call.get_parent_connection();
+    let active_connection = context.active_connection();
 
-    // Simulate sending of an ICE candidate message, and leaving it 'in-flight' so
-    // the subsequent Hangup message is queued until message_sent() is called.
-    context.no_auto_message_sent_for_ice(true);
+    assert!(active_connection
+        .app_connection()
+        .unwrap()
+        .outgoing_audio_track_enabled());
 
-    let ice_candidate = random_ice_candidate();
-    let force_send = true;
-    parent_connection
+    // This is all the iOS set_audio_enable() does.
+    info!("test: muting outgoing audio");
+    cm.set_outgoing_audio_enabled(false).expect(error_line!());
+    assert!(!active_connection
+        .app_connection()
         .unwrap()
-        .inject_local_ice_candidate(ice_candidate, force_send, "")
-        .expect(error_line!());
+        .outgoing_audio_track_enabled());
+    // Muting doesn't stop the senders the way holding does.
+    assert!(active_connection
+        .app_connection()
+        .unwrap()
+        .outgoing_audio_enabled());
 
+    info!("test: unmuting outgoing audio");
+    cm.set_outgoing_audio_enabled(true).expect(error_line!());
+    assert!(active_connection
+        .app_connection()
+        .unwrap()
+        .outgoing_audio_track_enabled());
+
+    cm.hangup().expect(error_line!());
     cm.synchronize().expect(error_line!());
+
+    // With no call left there's nothing to mute.
+    assert!(cm.set_outgoing_audio_enabled(false).is_err());
     assert_eq!(context.error_count(), 0);
-    assert_eq!(context.ice_candidates_sent(), 1);
+}
 
-    // Receiving a Normal hangup before connection implies the callee is declining.
-    cm.received_hangup(
-        active_call.call_id(),
-        signaling::ReceivedHangup {
-            sender_device_id: 1 as DeviceId,
-            hangup:           signaling::Hangup::Normal,
-        },
-    )
-    .expect(error_line!());
+#[test]
+fn outbound_call_hold_before_accepted_ignored() {
+    test_init();
+
+    let context = start_outbound_call();
+    let mut cm = context.cm();
+    let active_call = context.active_call();
+
+    cm.hold_call(active_call.call_id()).expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(
+        active_call.state().expect(error_line!()),
+        CallState::ConnectingBeforeAccepted
+    );
+    assert_eq!(context.event_count(ApplicationEvent::LocalHold), 0);
 
+    // Resuming a call that isn't on hold does nothing either.
+    cm.resume_call(active_call.call_id()).expect(error_line!());
     cm.synchronize().expect(error_line!());
 
+    assert_eq!(
+        active_call.state().expect(error_line!()),
+        CallState::ConnectingBeforeAccepted
+    );
+    assert_eq!(context.event_count(ApplicationEvent::LocalResume), 0);
     assert_eq!(context.error_count(), 0);
-    assert_eq!(context.event_count(ApplicationEvent::EndedRemoteHangup), 1);
+    assert_eq!(context.ended_count(), 0);
+}
 
-    // Now free the message queue so that the next message can fly.
-    cm.message_sent(active_call.call_id()).expect(error_line!());
+#[test]
+fn outbound_call_on_hold_local_hangup() {
+    test_init();
+
+    let context = connect_outbound_call();
+    let mut cm = context.cm();
+    let active_call = context.active_call();
+
+    cm.hold_call(active_call.call_id()).expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(active_call.state().expect(error_line!()), CallState::OnHold);
 
+    cm.hangup().expect(error_line!());
     cm.synchronize().expect(error_line!());
 
+    assert_eq!(
+        active_call.state().expect(error_line!()),
+        CallState::Terminated
+    );
     assert_eq!(context.error_count(), 0);
-    assert_eq!(context.normal_hangups_sent(), 0);
+    assert_eq!(context.ended_count(), 1);
+    assert_eq!(context.event_count(ApplicationEvent::EndedLocalHangup), 1);
+    assert_eq!(context.normal_hangups_sent(), 1);
+}
 
-    // We expect that a Hangup/Declined still goes out via Signal messaging.
-    assert_eq!(context.declined_hangups_sent(), 1);
+#[test]
+fn audio_call_uses_audio_call_encoder_preset() {
+    test_init();
+
+    let context = connect_outbound_call();
+    let active_connection = context.active_connection();
+
+    let audio_call_preset = AudioEncoderPreset::for_call_media_type(CallMediaType::Audio);
+    let video_call_preset = AudioEncoderPreset::for_call_media_type(CallMediaType::Video);
+    assert!(audio_call_preset.max_bitrate_bps > video_call_preset.max_bitrate_bps);
+
+    let config = active_connection
+        .app_connection()
+        .unwrap()
+        .audio_encoder_config()
+        .expect(error_line!());
+    assert_eq!(
+        config.max_bitrate_bps,
+        audio_call_preset.max_bitrate_bps as i32
+    );
+    assert_eq!(config.complexity, audio_call_preset.complexity as i32);
+    // The higher cap doesn't change where the bitrate starts.
+    assert_eq!(config.start_bitrate_bps, 40_000);
+
+    assert_eq!(context.error_count(), 0);
+}
+
+#[test]
+fn set_audio_fec_level() {
+    test_init();
+
+    let context = connect_outbound_call();
+    let active_connection = context.active_connection();
+    let fec_enabled = || {
+        active_connection
+            .app_connection()
+            .unwrap()
+            .audio_encoder_config()
+            .expect(error_line!())
+            .enable_fec
+    };
+
+    // FEC is on by default.
+    assert_eq!(fec_enabled(), 1);
+
+    assert_eq!(
+        active_connection
+            .set_audio_fec_level(0)
+            .expect(error_line!()),
+        0
+    );
+    assert_eq!(fec_enabled(), 0);
+
+    // Out of range levels are clamped.
+    assert_eq!(
+        active_connection
+            .set_audio_fec_level(u8::MAX)
+            .expect(error_line!()),
+        MAX_AUDIO_FEC_LEVEL
+    );
+    assert_eq!(fec_enabled(), 1);
+
+    // The level sticks across bandwidth mode changes.
+    active_connection
+        .set_audio_fec_level(0)
+        .expect(error_line!());
+    active_connection
+        .update_bandwidth_mode(BandwidthMode::Low)
+        .expect(error_line!());
+    assert_eq!(fec_enabled(), 0);
+
+    assert_eq!(context.error_count(), 0);
+}
+
+#[test]
+fn effective_bandwidth_mode_of_call() {
+    test_init();
+
+    let context = connect_outbound_call();
+    let cm = context.cm();
+    let call_id = context.active_call().call_id();
+    let active_connection = context.active_connection();
+    let effective_mode = || {
+        cm.effective_bandwidth_mode(Some(call_id), None)
+            .expect(error_line!())
+    };
+
+    assert_eq!(
+        effective_mode(),
+        EffectiveBandwidthMode {
+            mode:     BandwidthMode::Normal,
+            adaptive: false,
+        }
+    );
+
+    active_connection
+        .update_bandwidth_mode(BandwidthMode::Low)
+        .expect(error_line!());
+    assert_eq!(
+        effective_mode(),
+        EffectiveBandwidthMode {
+            mode:     BandwidthMode::Low,
+            adaptive: false,
+        }
+    );
+
+    // The remote asking for less lowers the mode below what was set.
+    active_connection
+        .set_remote_max_bitrate(DataRate::from_kbps(100))
+        .expect(error_line!());
+    assert_eq!(
+        effective_mode(),
+        EffectiveBandwidthMode {
+            mode:     BandwidthMode::VeryLow,
+            adaptive: true,
+        }
+    );
+
+    // Exactly one of a current call ID or a group call client is needed.
+    assert!(cm.effective_bandwidth_mode(None, None).is_err());
+    assert!(cm.effective_bandwidth_mode(Some(call_id), Some(1)).is_err());
+    assert!(cm
+        .effective_bandwidth_mode(Some(CallId::new(call_id.as_u64() + 1)), None)
+        .is_err());
+    assert!(cm.effective_bandwidth_mode(None, Some(1)).is_err());
+
+    assert_eq!(context.error_count(), 0);
+}
+
+#[test]
+fn outgoing_video_caps_reach_encoder() {
+    test_init();
+
+    let context = connect_outbound_call();
+    let active_connection = context.active_connection();
+    let encoder_caps = || {
+        active_connection
+            .app_connection()
+            .unwrap()
+            .outgoing_video_caps()
+    };
+
+    // Nothing is capped by default.
+    assert_eq!(encoder_caps(), None);
+
+    let battery_saver = VideoEncoderCaps {
+        max_width:  640,
+        max_height: 360,
+        max_fps:    15,
+    };
+    active_connection
+        .set_outgoing_video_caps(battery_saver)
+        .expect(error_line!());
+    assert_eq!(encoder_caps(), Some(battery_saver));
+
+    // The caps stay in place when the bandwidth mode changes.
+    active_connection
+        .update_bandwidth_mode(BandwidthMode::Low)
+        .expect(error_line!());
+    assert_eq!(encoder_caps(), Some(battery_saver));
+
+    active_connection
+        .set_outgoing_video_caps(VideoEncoderCaps::default())
+        .expect(error_line!());
+    assert_eq!(encoder_caps(), Some(VideoEncoderCaps::default()));
+
+    assert_eq!(context.error_count(), 0);
+}
+
+#[test]
+fn selected_pair_rtt_from_stats() {
+    test_init();
+
+    let context = connect_outbound_call();
+    let mut active_connection = context.active_connection();
+
+    // Nothing until stats have reported a selected candidate pair.
+    assert_eq!(
+        active_connection
+            .selected_pair_rtt_ms()
+            .expect(error_line!()),
+        None
+    );
+
+    active_connection
+        .app_connection()
+        .unwrap()
+        .set_selected_pair_round_trip_time(Some(0.085));
+    active_connection
+        .tick(STATS_PERIOD_SEC)
+        .expect(error_line!());
+    assert_eq!(
+        active_connection
+            .selected_pair_rtt_ms()
+            .expect(error_line!()),
+        Some(85)
+    );
+
+    // Losing the selected pair clears it again.
+    active_connection
+        .app_connection()
+        .unwrap()
+        .set_selected_pair_round_trip_time(None);
+    active_connection
+        .tick(2 * STATS_PERIOD_SEC)
+        .expect(error_line!());
+    assert_eq!(
+        active_connection
+            .selected_pair_rtt_ms()
+            .expect(error_line!()),
+        None
+    );
+
+    assert_eq!(context.error_count(), 0);
+}
+
+#[test]
+fn call_statistics_from_stats() {
+    test_init();
+
+    let context = connect_outbound_call();
+    let cm = context.cm();
+    let call_id = context.active_call().call_id();
+    let mut active_connection = context.active_connection();
+
+    active_connection
+        .app_connection()
+        .unwrap()
+        .set_selected_pair_round_trip_time(Some(0.085));
+    active_connection
+        .tick(STATS_PERIOD_SEC)
+        .expect(error_line!());
+
+    let call_statistics = cm
+        .get_call_statistics(call_id)
+        .expect(error_line!())
+        .expect(error_line!());
+    assert_eq!(call_statistics.audio, None);
+    assert_eq!(call_statistics.video, None);
+    let selected_pair = call_statistics.selected_pair.expect(error_line!());
+    assert_eq!(selected_pair.round_trip_time_ms, 85.0);
+    assert_eq!(call_statistics.selected_pair_rtt_ms(), Some(85));
+    assert_eq!(selected_pair.local_candidate_type, CandidateType::Unknown);
+    // One report isn't enough to work out a bitrate.
+    assert_eq!(call_statistics.send_bitrate_bps, None);
+
+    assert!(cm
+        .get_call_statistics(CallId::new(call_id.as_u64() + 1))
+        .is_err());
+
+    assert_eq!(context.error_count(), 0);
+
+    // Without an active call there are no statistics to get.
+    assert!(TestContext::new().cm().active_connection().is_err());
+}
+
+#[test]
+fn retransmission_statistics_from_stats() {
+    test_init();
+
+    let context = connect_outbound_call();
+    let cm = context.cm();
+    let call_id = context.active_call().call_id();
+    let mut active_connection = context.active_connection();
+
+    // A lossy link: the remote side NACKs what it missed and asks for key
+    // frames on both simulcast layers.
+    let app_connection = active_connection.app_connection().unwrap();
+    app_connection.set_audio_sender_statistics(Some(AudioSenderStatistics {
+        ssrc: 1,
+        retransmitted_packets_sent: 12,
+        nack_count: 15,
+        ..Default::default()
+    }));
+    app_connection.set_video_senders_statistics(vec![
+        VideoSenderStatistics {
+            ssrc: 2,
+            retransmitted_packets_sent: 40,
+            nack_count: 30,
+            fir_count: 1,
+            pli_count: 3,
+            ..Default::default()
+        },
+        VideoSenderStatistics {
+            ssrc: 3,
+            retransmitted_packets_sent: 20,
+            nack_count: 10,
+            fir_count: 0,
+            pli_count: 2,
+            ..Default::default()
+        },
+    ]);
+    active_connection
+        .tick(STATS_PERIOD_SEC)
+        .expect(error_line!());
+
+    let call_statistics = cm
+        .get_call_statistics(call_id)
+        .expect(error_line!())
+        .expect(error_line!());
+    let audio = call_statistics.audio.expect(error_line!());
+    assert_eq!(audio.nack_count, 15);
+    assert_eq!(audio.retransmitted_packets, 12);
+    assert_eq!(audio.fir_count, 0);
+    assert_eq!(audio.pli_count, 0);
+    let video = call_statistics.video.expect(error_line!());
+    assert_eq!(video.nack_count, 40);
+    assert_eq!(video.retransmitted_packets, 60);
+    assert_eq!(video.fir_count, 1);
+    assert_eq!(video.pli_count, 5);
+
+    assert_eq!(context.error_count(), 0);
+}
+
+#[test]
+fn audio_playout_statistics_from_stats() {
+    test_init();
+
+    let context = connect_outbound_call();
+    let mut active_connection = context.active_connection();
+
+    // Nothing until stats have reported received audio.
+    active_connection
+        .tick(STATS_PERIOD_SEC)
+        .expect(error_line!());
+    assert_eq!(
+        active_connection
+            .audio_playout_statistics()
+            .expect(error_line!()),
+        None
+    );
+
+    // Healthy playout: 10 seconds at 48 kHz with a steady 60 ms jitter buffer.
+    active_connection
+        .app_connection()
+        .unwrap()
+        .set_audio_receiver_statistics(Some(AudioReceiverStatistics {
+            jitter_buffer_delay: 28_800.0,
+            jitter_buffer_emitted_count: 480_000,
+            total_samples_received: 480_000,
+            ..Default::default()
+        }));
+    active_connection
+        .tick(2 * STATS_PERIOD_SEC)
+        .expect(error_line!());
+    let playout = active_connection
+        .audio_playout_statistics()
+        .expect(error_line!())
+        .expect(error_line!());
+    assert_eq!(playout.jitter_buffer_delay_ms.round(), 60.0);
+    assert_eq!(playout.expand_rate, 0.0);
+    assert_eq!(playout.concealment_events, 0);
+
+    // Underruns in the next period show up as concealment even though
+    // nothing about the packets changed.
+    active_connection
+        .app_connection()
+        .unwrap()
+        .set_audio_receiver_statistics(Some(AudioReceiverStatistics {
+            jitter_buffer_delay: 28_800.0 + 9_600.0,
+            jitter_buffer_emitted_count: 480_000 + 96_000,
+            total_samples_received: 480_000 + 480_000,
+            concealed_samples: 48_000,
+            concealment_events: 12,
+            inserted_samples_for_deceleration: 9_600,
+            removed_samples_for_acceleration: 4_800,
+            ..Default::default()
+        }));
+    active_connection
+        .tick(3 * STATS_PERIOD_SEC)
+        .expect(error_line!());
+    let playout = active_connection
+        .audio_playout_statistics()
+        .expect(error_line!())
+        .expect(error_line!());
+    assert_eq!(playout.jitter_buffer_delay_ms.round(), 100.0);
+    assert_eq!(
+        playout,
+        AudioPlayoutStatistics {
+            jitter_buffer_delay_ms: playout.jitter_buffer_delay_ms,
+            expand_rate:            0.1,
+            accelerate_rate:        0.01,
+            preemptive_expand_rate: 0.02,
+            concealment_events:     12,
+        }
+    );
+
+    // No more audio being received clears it.
+    active_connection
+        .app_connection()
+        .unwrap()
+        .set_audio_receiver_statistics(None);
+    active_connection
+        .tick(4 * STATS_PERIOD_SEC)
+        .expect(error_line!());
+    assert_eq!(
+        active_connection
+            .audio_playout_statistics()
+            .expect(error_line!()),
+        None
+    );
+
+    assert_eq!(context.error_count(), 0);
+}
+
+#[cfg(feature = "testing")]
+#[test]
+fn injected_receive_latency_in_playout_statistics() {
+    test_init();
+
+    let context = connect_outbound_call();
+    let mut active_connection = context.active_connection();
+
+    // A steady 60 ms jitter buffer, 10 seconds at 48 kHz per period.
+    let receive_seconds = |seconds: u64| AudioReceiverStatistics {
+        jitter_buffer_delay: 0.06 * (seconds * 48_000) as f64,
+        jitter_buffer_emitted_count: seconds * 48_000,
+        total_samples_received: seconds * 48_000,
+        ..Default::default()
+    };
+
+    active_connection
+        .app_connection()
+        .unwrap()
+        .set_audio_receiver_statistics(Some(receive_seconds(10)));
+    active_connection
+        .tick(STATS_PERIOD_SEC)
+        .expect(error_line!());
+    active_connection
+        .app_connection()
+        .unwrap()
+        .set_audio_receiver_statistics(Some(receive_seconds(20)));
+    active_connection
+        .tick(2 * STATS_PERIOD_SEC)
+        .expect(error_line!());
+    let playout = active_connection
+        .audio_playout_statistics()
+        .expect(error_line!())
+        .expect(error_line!());
+    assert_eq!(playout.jitter_buffer_delay_ms.round(), 60.0);
+
+    // The injected latency adds to what the jitter buffer holds.
+    active_connection
+        .inject_receive_latency(150)
+        .expect(error_line!());
+    active_connection
+        .app_connection()
+        .unwrap()
+        .set_audio_receiver_statistics(Some(receive_seconds(30)));
+    active_connection
+        .tick(3 * STATS_PERIOD_SEC)
+        .expect(error_line!());
+    let playout = active_connection
+        .audio_playout_statistics()
+        .expect(error_line!())
+        .expect(error_line!());
+    assert_eq!(playout.jitter_buffer_delay_ms.round(), 210.0);
+    assert_eq!(playout.expand_rate, 0.0);
+
+    assert_eq!(context.error_count(), 0);
+}
+
+#[test]
+fn set_audio_jitter_buffer_target() {
+    test_init();
+
+    let context = connect_outbound_call();
+    let active_connection = context.active_connection();
+
+    assert_eq!(
+        active_connection
+            .app_connection()
+            .unwrap()
+            .audio_jitter_buffer_min_delay_ms(),
+        None
+    );
+
+    assert_eq!(
+        active_connection
+            .set_audio_jitter_buffer_target(150)
+            .expect(error_line!()),
+        150
+    );
+    assert_eq!(
+        active_connection
+            .app_connection()
+            .unwrap()
+            .audio_jitter_buffer_min_delay_ms(),
+        Some(150)
+    );
+
+    // Out of range targets are clamped.
+    active_connection
+        .set_audio_jitter_buffer_target(0)
+        .expect(error_line!());
+    assert_eq!(
+        active_connection
+            .app_connection()
+            .unwrap()
+            .audio_jitter_buffer_min_delay_ms(),
+        Some(MIN_AUDIO_JITTER_BUFFER_TARGET_MS)
+    );
+    active_connection
+        .set_audio_jitter_buffer_target(u32::MAX)
+        .expect(error_line!());
+    assert_eq!(
+        active_connection
+            .app_connection()
+            .unwrap()
+            .audio_jitter_buffer_min_delay_ms(),
+        Some(MAX_AUDIO_JITTER_BUFFER_TARGET_MS)
+    );
+
+    assert_eq!(context.error_count(), 0);
+}
+
+#[test]
+fn security_fingerprint_is_stable() {
+    test_init();
+
+    let caller_identity_key = [1u8; 33];
+    let callee_identity_key = [2u8; 33];
+    let fingerprint = security_fingerprint(&caller_identity_key, &callee_identity_key);
+    assert_eq!(fingerprint, "15938 90021 85709 68875 32335 74788");
+    assert_eq!(fingerprint.len(), SECURITY_FINGERPRINT_LEN);
+    // The order of the keys matters.
+    assert_ne!(
+        security_fingerprint(&callee_identity_key, &caller_identity_key),
+        fingerprint
+    );
+
+    // Keys as they come from the app: a type byte and 32 bytes of key.
+    let mut caller_identity_key = [0x05u8; 33];
+    let mut callee_identity_key = [0x05u8; 33];
+    for i in 0..32 {
+        caller_identity_key[i + 1] = i as u8;
+        callee_identity_key[i + 1] = (i + 32) as u8;
+    }
+    assert_eq!(
+        security_fingerprint(&caller_identity_key, &callee_identity_key),
+        "05998 06461 03682 73922 87712 16342"
+    );
+}
+
+#[test]
+fn outbound_security_fingerprint() {
+    test_init();
+
+    let context = connect_outbound_call();
+    let active_connection = context.active_connection();
+
+    // The simulated signaling carries empty identity keys.
+    assert_eq!(
+        active_connection
+            .security_fingerprint()
+            .expect(error_line!()),
+        security_fingerprint(&[], &[])
+    );
+    assert_eq!(context.error_count(), 0);
+}
+
+#[test]
+fn update_bandwidth_mode_default() {
+    test_init();
+
+    let context = connect_outbound_call();
+    let mut cm = context.cm();
+    let active_connection = context.active_connection();
+
+    active_connection
+        .update_bandwidth_mode(BandwidthMode::Normal)
+        .expect(error_line!());
+
+    cm.synchronize().expect(error_line!());
+    assert_eq!(context.error_count(), 0);
+
+    // TODO -- verify that the bitrate was changed
+    // TODO -- verify that the data channel object sent a message
+}
+
+#[test]
+fn update_bandwidth_mode_low() {
+    test_init();
+
+    let context = connect_outbound_call();
+    let mut cm = context.cm();
+    let active_connection = context.active_connection();
+
+    active_connection
+        .update_bandwidth_mode(BandwidthMode::Low)
+        .expect(error_line!());
+
+    cm.synchronize().expect(error_line!());
+    assert_eq!(context.error_count(), 0);
+
+    // TODO -- verify that the bitrate was changed
+    // TODO -- verify that the data channel object sent a message
+}
+
+#[test]
+fn inject_local_ice_candidate() {
+    test_init();
+
+    let context = connect_outbound_call();
+    let mut cm = context.cm();
+    let mut active_connection = context.active_connection();
+
+    let ice_candidate = random_ice_candidate();
+    let force_send = true;
+    active_connection
+        .inject_local_ice_candidate(ice_candidate, force_send, "")
+        .expect(error_line!());
+
+    cm.synchronize().expect(error_line!());
+    assert_eq!(context.error_count(), 0);
+    assert_eq!(context.ice_candidates_sent(), 1);
+}
+
+#[test]
+fn local_ice_candidates_held_while_signaling_suspended() {
+    test_init();
+
+    let context = connect_outbound_call();
+    let mut cm = context.cm();
+    let mut active_connection = context.active_connection();
+
+    cm.suspend_signaling().expect(error_line!());
+
+    let force_send = true;
+    for _ in 0..3 {
+        active_connection
+            .inject_local_ice_candidate(random_ice_candidate(), force_send, "")
+            .expect(error_line!());
+    }
+
+    cm.synchronize().expect(error_line!());
+    assert_eq!(context.error_count(), 0);
+    assert_eq!(context.ice_candidates_sent(), 0);
+
+    cm.resume_signaling().expect(error_line!());
+
+    cm.synchronize().expect(error_line!());
+    assert_eq!(context.error_count(), 0);
+    assert_eq!(context.ice_candidates_sent(), 3);
+}
+
+// Check that a full suspended queue never drops the messages it holds:
+// -- suspends signaling and fills the queue with busy messages
+// -- generates an ICE candidate, which is dropped
+// -- resumes signaling, which sends every busy message
+// -- generates another ICE candidate, which is sent
+#[test]
+fn local_ice_candidates_dropped_while_suspended_queue_full() {
+    test_init();
+
+    let context = connect_outbound_call();
+    let mut cm = context.cm();
+    let mut active_connection = context.active_connection();
+    let ice_candidates_sent = context.ice_candidates_sent();
+
+    cm.suspend_signaling().expect(error_line!());
+
+    for _ in 0..MAX_SUSPENDED_SIGNALING_MESSAGES {
+        let remote_peer = format!("REMOTE_PEER-{}", PRNG.gen::<u16>()).to_owned();
+        cm.send_busy(remote_peer, CallId::new(PRNG.gen::<u64>()))
+            .expect(error_line!());
+    }
+
+    let force_send = true;
+    active_connection
+        .inject_local_ice_candidate(random_ice_candidate(), force_send, "")
+        .expect(error_line!());
+
+    cm.synchronize().expect(error_line!());
+    assert_eq!(context.busys_sent(), 0);
+
+    cm.resume_signaling().expect(error_line!());
+
+    cm.synchronize().expect(error_line!());
+    assert_eq!(context.busys_sent(), MAX_SUSPENDED_SIGNALING_MESSAGES);
+    assert_eq!(context.ice_candidates_sent(), ice_candidates_sent);
+
+    active_connection
+        .inject_local_ice_candidate(random_ice_candidate(), force_send, "")
+        .expect(error_line!());
+
+    cm.synchronize().expect(error_line!());
+    assert_eq!(context.ice_candidates_sent(), ice_candidates_sent + 1);
+    assert_eq!(context.error_count(), 0);
+}
+
+#[test]
+fn receive_remote_ice_candidate() {
+    test_init();
+
+    let context = connect_outbound_call();
+    let mut cm = context.cm();
+    let active_call = context.active_call();
+
+    // add a received ICE candidate
+    let call_id = active_call.call_id();
+    cm.received_ice(call_id, random_received_ice_candidate())
+        .expect("receive_ice");
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(context.error_count(), 0);
+
+    // TODO -- verify the ice candidate was buffered
+    // TODO -- verify the ice candidate was applied to the peer_connection
+}
+
+#[test]
+fn receive_remote_ice_candidate_after_hangup() {
+    test_init();
+
+    let context = connect_outbound_call();
+    let mut cm = context.cm();
+    let active_call = context.active_call();
+    let call_id = active_call.call_id();
+
+    cm.hangup().expect(error_line!());
+    cm.synchronize().expect(error_line!());
+    assert_eq!(
+        active_call.state().expect(error_line!()),
+        CallState::Terminated
+    );
+
+    cm.received_ice(call_id, random_received_ice_candidate())
+        .expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(context.error_count(), 0);
+    assert_eq!(context.ended_count(), 1);
+}
+
+#[test]
+fn connected_call_reports_srtp_cipher_suite() {
+    test_init();
+
+    let context = connect_outbound_call();
+    let cm = context.cm();
+    let active_call = context.active_call();
+
+    let suite = cm
+        .srtp_cipher_suite(active_call.call_id())
+        .expect(error_line!());
+    assert!(!suite.is_empty());
+    assert_eq!(suite, "AEAD_AES_256_GCM");
+
+    let call_id = active_call.call_id();
+    assert!(cm
+        .srtp_cipher_suite(CallId::new(call_id.as_u64() + 1))
+        .is_err());
+    assert_eq!(context.error_count(), 0);
+}
+
+#[test]
+fn transfer_call_ends_when_accepted() {
+    test_init();
+
+    let context = connect_outbound_call();
+    let mut cm = context.cm();
+    let active_call = context.active_call();
+    let call_id = active_call.call_id();
+
+    let target_peer = format!("TARGET_PEER-{}", PRNG.gen::<u16>()).to_owned();
+    cm.transfer_call(call_id, target_peer).expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    // The call carries on until the remote side accepts.
+    assert_eq!(context.transfer_requests_sent(), 1);
+    assert_eq!(
+        active_call.state().expect(error_line!()),
+        CallState::ConnectedAndAccepted
+    );
+    assert_eq!(context.ended_count(), 0);
+
+    cm.received_transfer_accepted(
+        call_id,
+        signaling::ReceivedTransferAccepted {
+            sender_device_id: 1 as DeviceId,
+        },
+    )
+    .expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(context.error_count(), 0);
+    assert_eq!(context.event_count(ApplicationEvent::EndedTransferred), 1);
+    assert_eq!(context.normal_hangups_sent(), 0);
+    assert_eq!(cm.active_call().is_ok(), false);
+}
+
+#[test]
+fn transfer_accepted_ignored_unless_requested_by_active_device() {
+    test_init();
+
+    let context = connect_outbound_call();
+    let mut cm = context.cm();
+    let active_call = context.active_call();
+    let call_id = active_call.call_id();
+
+    // Nothing was requested yet, so an accept is ignored.
+    cm.received_transfer_accepted(
+        call_id,
+        signaling::ReceivedTransferAccepted {
+            sender_device_id: 1 as DeviceId,
+        },
+    )
+    .expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(context.event_count(ApplicationEvent::EndedTransferred), 0);
+    assert_eq!(
+        active_call.state().expect(error_line!()),
+        CallState::ConnectedAndAccepted
+    );
+
+    let target_peer = format!("TARGET_PEER-{}", PRNG.gen::<u16>()).to_owned();
+    cm.transfer_call(call_id, target_peer).expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    // An accept from a device we're not connected to is ignored.
+    cm.received_transfer_accepted(
+        call_id,
+        signaling::ReceivedTransferAccepted {
+            sender_device_id: 2 as DeviceId,
+        },
+    )
+    .expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(context.error_count(), 0);
+    assert_eq!(context.event_count(ApplicationEvent::EndedTransferred), 0);
+    assert_eq!(
+        active_call.state().expect(error_line!()),
+        CallState::ConnectedAndAccepted
+    );
+    assert_eq!(cm.active_call().is_ok(), true);
+}
+
+#[test]
+fn received_remote_hangup_before_connection() {
+    test_init();
+
+    let context = start_outbound_and_proceed();
+    let mut cm = context.cm();
+    let active_call = context.active_call();
+
+    // Receiving a Hangup/Normal before connection implies the callee is declining.
+    cm.received_hangup(
+        active_call.call_id(),
+        signaling::ReceivedHangup {
+            sender_device_id: 1 as DeviceId,
+            hangup:           signaling::Hangup::Normal,
+        },
+    )
+    .expect(error_line!());
+
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(context.error_count(), 0);
+    assert_eq!(context.event_count(ApplicationEvent::EndedRemoteHangup), 1);
+    assert_eq!(context.normal_hangups_sent(), 0);
+    // Other callees should get Hangup/Declined.
+    assert_eq!(context.declined_hangups_sent(), 1);
+}
+
+#[test]
+fn received_remote_away_before_connection() {
+    test_init();
+
+    let context = start_outbound_and_proceed();
+    let mut cm = context.cm();
+    let active_call = context.active_call();
+
+    cm.received_hangup(
+        active_call.call_id(),
+        signaling::ReceivedHangup {
+            sender_device_id: 1 as DeviceId,
+            hangup:           signaling::Hangup::Away,
+        },
+    )
+    .expect(error_line!());
+
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(context.error_count(), 0);
+    assert_eq!(context.event_count(ApplicationEvent::EndedRemoteAway), 1);
+    assert_eq!(context.event_count(ApplicationEvent::EndedRemoteHangup), 0);
+    // Like a Hangup/Normal, other callees should get Hangup/Declined.
+    assert_eq!(context.declined_hangups_sent(), 1);
+}
+
+#[test]
+fn received_remote_hangup_before_connection_with_message_in_flight() {
+    test_init();
+
+    let context = start_outbound_and_proceed();
+    let mut cm = context.cm();
+    let active_call = context.active_call();
+    let parent_connection = active_call.get_parent_connection();
+
+    // Simulate sending of an ICE candidate message, and leaving it 'in-flight' so
+    // the subsequent Hangup message is queued until message_sent() is called.
+    context.no_auto_message_sent_for_ice(true);
+
+    let ice_candidate = random_ice_candidate();
+    let force_send = true;
+    parent_connection
+        .unwrap()
+        .inject_local_ice_candidate(ice_candidate, force_send, "")
+        .expect(error_line!());
+
+    cm.synchronize().expect(error_line!());
+    assert_eq!(context.error_count(), 0);
+    assert_eq!(context.ice_candidates_sent(), 1);
+
+    // Receiving a Normal hangup before connection implies the callee is declining.
+    cm.received_hangup(
+        active_call.call_id(),
+        signaling::ReceivedHangup {
+            sender_device_id: 1 as DeviceId,
+            hangup:           signaling::Hangup::Normal,
+        },
+    )
+    .expect(error_line!());
+
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(context.error_count(), 0);
+    assert_eq!(context.event_count(ApplicationEvent::EndedRemoteHangup), 1);
+
+    // Now free the message queue so that the next message can fly.
+    cm.message_sent(active_call.call_id()).expect(error_line!());
+
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(context.error_count(), 0);
+    assert_eq!(context.normal_hangups_sent(), 0);
+
+    // We expect that a Hangup/Declined still goes out via Signal messaging.
+    assert_eq!(context.declined_hangups_sent(), 1);
+}
+
+#[test]
+fn signaling_sent_assumed_only_in_assume_mode() {
+    test_init();
+
+    let context = start_outbound_and_proceed();
+    let mut cm = context.cm();
+    let active_call = context.active_call();
+    let parent_connection = active_call.get_parent_connection().unwrap();
+
+    // The offer waited for message_sent() as usual.
+    assert_eq!(context.signaling_sent_assumed(), 0);
+
+    // Leave the ICE message unconfirmed; in assume mode the Hangup
+    // still goes out right after it.
+    context.assume_all_messages_sent(true);
+    context.no_auto_message_sent_for_ice(true);
+
+    parent_connection
+        .inject_local_ice_candidate(random_ice_candidate(), true, "")
+        .expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(context.ice_candidates_sent(), 1);
+    assert_eq!(context.signaling_sent_assumed(), 1);
+
+    cm.hangup().expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(context.normal_hangups_sent(), 1);
+    assert_eq!(context.signaling_sent_assumed(), 2);
+    assert_eq!(context.error_count(), 0);
 }
 
 #[test]
@@ -0,0 +1,19 @@
+//
+// Copyright 2019-2021 Signal Messenger, LLC
+// SPDX-License-Identifier: AGPL-3.0-only
+//
+
+//! Test redaction with the force-redaction feature, which applies it
+//! even in debug builds.
+
+extern crate ringrtc;
+
+use ringrtc::core::util::redact_string;
+
+#[test]
+fn ipv4_redacted_in_debug_build() {
+    assert_eq!(
+        redact_string("candidate:1 1 udp 2122260223 192.168.1.7 50000 typ host"),
+        "candidate:1 1 udp 2122260223 [REDACTED] 50000 typ host"
+    );
+}